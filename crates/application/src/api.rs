@@ -20,6 +20,7 @@ use common::{
         ConvexOrigin,
         FunctionCaller,
         RepeatableTimestamp,
+        UdfType,
     },
     RequestId,
 };
@@ -64,6 +65,7 @@ use value::{
 };
 
 use crate::{
+    rate_limiter::RateLimitCaller,
     Application,
     FunctionError,
     FunctionReturn,
@@ -98,6 +100,22 @@ pub trait ApplicationApi: Send + Sync {
         auth_token: AuthenticationToken,
     ) -> anyhow::Result<Identity>;
 
+    /// Fail with a rate limited error if calling `path` would exceed one of
+    /// the deployment's rate limits. Called before executing public functions.
+    /// `udf_type` is the type the caller expects `path` to have, if known.
+    ///
+    /// Implementations that forward calls to another backend only count the
+    /// calls they execute themselves, and pass `caller` along with the rest.
+    async fn check_rate_limits(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: &Identity,
+        path: &ExportPath,
+        udf_type: Option<UdfType>,
+        caller: &RateLimitCaller,
+    ) -> anyhow::Result<()>;
+
     /// Execute a public query on the root app. This method is used by the sync
     /// worker and HTTP API for the majority of traffic as the main entry point
     /// for queries.
@@ -137,6 +155,7 @@ pub trait ApplicationApi: Send + Sync {
         path: ExportPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
         // Identifier used to make this mutation idempotent.
         mutation_identifier: Option<SessionRequestIdentifier>,
         // The length of the mutation queue at the time the mutation was executed.
@@ -152,6 +171,7 @@ pub trait ApplicationApi: Send + Sync {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
         mutation_identifier: Option<SessionRequestIdentifier>,
         // The length of the mutation queue at the time the mutation was executed.
         mutation_queue_length: Option<usize>,
@@ -166,6 +186,7 @@ pub trait ApplicationApi: Send + Sync {
         path: ExportPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
     ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>>;
 
     /// Execute an admin action for a particular component for the dashboard.
@@ -177,6 +198,7 @@ pub trait ApplicationApi: Send + Sync {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
     ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>>;
 
     /// Execute an HTTP action on the root app.
@@ -201,6 +223,9 @@ pub trait ApplicationApi: Send + Sync {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+        // Identifier used to make this call idempotent if it's a mutation.
+        mutation_identifier: Option<SessionRequestIdentifier>,
     ) -> anyhow::Result<Result<FunctionReturn, FunctionError>>;
 
     async fn latest_timestamp(
@@ -274,6 +299,19 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         self.authenticate(auth_token, validate_time).await
     }
 
+    async fn check_rate_limits(
+        &self,
+        _host: &ResolvedHostname,
+        _request_id: RequestId,
+        identity: &Identity,
+        path: &ExportPath,
+        _udf_type: Option<UdfType>,
+        caller: &RateLimitCaller,
+    ) -> anyhow::Result<()> {
+        self.check_function_rate_limits(identity, path.udf_path(), caller)
+            .await
+    }
+
     async fn execute_public_query(
         &self,
         _host: &ResolvedHostname,
@@ -344,6 +382,7 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         path: ExportPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        _rate_limit_caller: &RateLimitCaller,
         // Identifier used to make this mutation idempotent.
         mutation_identifier: Option<SessionRequestIdentifier>,
         mutation_queue_length: Option<usize>,
@@ -372,6 +411,7 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        _rate_limit_caller: &RateLimitCaller,
         mutation_identifier: Option<SessionRequestIdentifier>,
         mutation_queue_length: Option<usize>,
     ) -> anyhow::Result<Result<RedactedMutationReturn, RedactedMutationError>> {
@@ -399,6 +439,7 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         path: ExportPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        _rate_limit_caller: &RateLimitCaller,
     ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
         anyhow::ensure!(
             caller.allowed_visibility() == AllowedVisibility::PublicOnly,
@@ -422,6 +463,7 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        _rate_limit_caller: &RateLimitCaller,
     ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
         anyhow::ensure!(
             path.component.is_root() || identity.is_admin() || identity.is_system(),
@@ -445,12 +487,22 @@ impl<RT: Runtime> ApplicationApi for Application<RT> {
         path: CanonicalizedComponentFunctionPath,
         args: Vec<JsonValue>,
         caller: FunctionCaller,
+        _rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
     ) -> anyhow::Result<Result<FunctionReturn, FunctionError>> {
         anyhow::ensure!(
             path.component.is_root() || identity.is_admin() || identity.is_system(),
             "Only admin or system users can call functions on non-root components directly"
         );
-        self.any_udf(request_id, path, args, identity, caller).await
+        self.any_udf(
+            request_id,
+            path,
+            args,
+            identity,
+            caller,
+            mutation_identifier,
+        )
+        .await
     }
 
     async fn latest_timestamp(
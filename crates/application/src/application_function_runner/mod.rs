@@ -35,6 +35,7 @@ use common::{
     errors::JsError,
     execution_context::ExecutionContext,
     fastrace_helpers::EncodedSpan,
+    identity::InertIdentity,
     knobs::{
         APPLICATION_FUNCTION_RUNNER_SEMAPHORE_TIMEOUT,
         APPLICATION_MAX_CONCURRENT_HTTP_ACTIONS,
@@ -75,6 +76,12 @@ use common::{
     RequestId,
 };
 use database::{
+    query::{
+        HybridSearch,
+        HybridSearchResult,
+        TextSearch,
+        TextSearchResult,
+    },
     unauthorized_error,
     Database,
     Token,
@@ -214,6 +221,7 @@ use crate::{
         ActionCompletion,
         FunctionExecutionLog,
     },
+    usage_accounting::UsageAccounting,
     ActionError,
     ActionReturn,
     MutationError,
@@ -572,7 +580,7 @@ pub struct ApplicationFunctionRunner<RT: Runtime> {
     runtime: RT,
     pub(crate) database: Database<RT>,
 
-    key_broker: KeyBroker,
+    pub(crate) key_broker: KeyBroker,
 
     isolate_functions: FunctionRouter<RT>,
     // Used for analyze, schema, etc.
@@ -587,6 +595,7 @@ pub struct ApplicationFunctionRunner<RT: Runtime> {
     cache_manager: CacheManager<RT>,
     default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
     node_action_limiter: Limiter,
+    usage_accounting: Option<UsageAccounting<RT>>,
 }
 
 impl<RT: Runtime> ApplicationFunctionRunner<RT> {
@@ -602,6 +611,7 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         function_log: FunctionExecutionLog<RT>,
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         cache: QueryCache,
+        usage_accounting: Option<UsageAccounting<RT>>,
     ) -> Self {
         let isolate_functions = FunctionRouter::new(
             function_runner,
@@ -634,7 +644,46 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
                 UdfType::Action,
                 *APPLICATION_MAX_CONCURRENT_NODE_ACTIONS,
             ),
+            usage_accounting,
+        }
+    }
+
+    /// Fail with a rate limiting error if the component `path` belongs to has
+    /// exceeded one of its daily usage quotas.
+    ///
+    /// This is checked for calls from clients, for `ctx.runQuery`,
+    /// `ctx.runMutation` and `ctx.runAction` from actions, and for scheduled
+    /// functions and crons. Component functions called from queries and
+    /// mutations run within the caller's transaction, so they're only limited
+    /// by the check on the caller's component.
+    pub(crate) fn check_usage_quota(
+        &self,
+        identity: &Identity,
+        path: &PublicFunctionPath,
+    ) -> anyhow::Result<()> {
+        if path.is_system() {
+            return Ok(());
+        }
+        self.check_component_usage_quota(
+            identity,
+            &path.clone().debug_into_component_path().component,
+        )
+    }
+
+    pub(crate) fn check_component_usage_quota(
+        &self,
+        identity: &Identity,
+        component: &ComponentPath,
+    ) -> anyhow::Result<()> {
+        let Some(usage_accounting) = &self.usage_accounting else {
+            return Ok(());
+        };
+        // Quotas only apply to calls that count towards usage, so calls from
+        // admins (e.g. the dashboard or CLI) are always allowed.
+        if identity.is_system() || identity.is_admin() {
+            return Ok(());
         }
+        usage_accounting.check_quota(component)
     }
 
     pub(crate) async fn shutdown(&self) -> anyhow::Result<()> {
@@ -769,6 +818,10 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
             },
         };
         let udf_path_string = (!path.is_system()).then_some(path.udf_path().to_string());
+        let function_path = {
+            let path = path.clone().debug_into_component_path();
+            format!("{}:{}", path.component, path.udf_path)
+        };
 
         let mut backoff = Backoff::new(
             *UDF_EXECUTOR_OCC_INITIAL_BACKOFF,
@@ -794,7 +847,7 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
 
             // Return the previous execution's result if the mutation was committed already.
             if let Some(result) = self
-                .check_mutation_status(&mut tx, &mutation_identifier)
+                .check_mutation_status(&mut tx, &mutation_identifier, &identity, &function_path)
                 .await?
             {
                 return Ok(result);
@@ -832,7 +885,7 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
 
             // Save a CommittedMutation object so we won't rerun this mutation if
             // successful.
-            self.write_mutation_status(&mut tx, &mutation_identifier, &outcome)
+            self.write_mutation_status(&mut tx, &mutation_identifier, &function_path, &outcome)
                 .await?;
 
             let stats = tx.take_stats();
@@ -1344,7 +1397,9 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
                 let mut environment_variables =
                     system_env_vars(&mut tx, self.default_system_env_vars.clone()).await?;
                 let user_environment_variables =
-                    EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+                    EnvironmentVariablesModel::new(&mut tx, &self.key_broker)
+                        .get_all()
+                        .await?;
                 environment_variables.extend(user_environment_variables);
 
                 // Fetch source and external_deps presigned URI first
@@ -1827,11 +1882,17 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         Ok(result)
     }
 
+    /// Returns the stored result if the mutation with this identifier already
+    /// committed. The identifier comes from the client (or a read replica
+    /// forwarding it), so the stored result is only handed back to the same
+    /// identity calling the same function.
     #[fastrace::trace]
     async fn check_mutation_status(
         &self,
         tx: &mut Transaction<RT>,
         mutation_identifier: &Option<SessionRequestIdentifier>,
+        identity: &InertIdentity,
+        function_path: &str,
     ) -> anyhow::Result<Option<Result<MutationReturn, MutationError>>> {
         let Some(ref identifier) = mutation_identifier else {
             return Ok(None);
@@ -1839,8 +1900,22 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         let mutation_status = SessionRequestModel::new(tx)
             .get_session_request_record(identifier, Identity::system())
             .await?;
-        let result = match mutation_status {
-            Some((ts, SessionRequestOutcome::Mutation { result, log_lines })) => {
+        let Some((ts, record)) = mutation_status else {
+            return Ok(None);
+        };
+        if record.identity != *identity
+            || record
+                .function_path
+                .as_ref()
+                .is_some_and(|path| path != function_path)
+        {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "MutationIdentifierMismatch",
+                "This mutation identifier was already used by a different caller or function",
+            ));
+        }
+        let result = match record.outcome {
+            SessionRequestOutcome::Mutation { result, log_lines } => {
                 let age = tx.begin_timestamp().secs_since_f64(ts);
                 tracing::info!(
                     "Mutation already executed {age:.3}s ago so skipping {:?}",
@@ -1853,7 +1928,6 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
                     ts,
                 })
             },
-            None => return Ok(None),
         };
         Ok(Some(result))
     }
@@ -1863,6 +1937,7 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         &self,
         tx: &mut Transaction<RT>,
         mutation_identifier: &Option<SessionRequestIdentifier>,
+        function_path: &str,
         outcome: &ValidatedUdfOutcome,
     ) -> anyhow::Result<()> {
         let Some(ref identifier) = mutation_identifier else {
@@ -1877,6 +1952,7 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
                     log_lines: outcome.log_lines.clone(),
                 },
                 identity: outcome.identity.clone(),
+                function_path: Some(function_path.to_string()),
             };
             SessionRequestModel::new(tx)
                 .record_session_request(record, Identity::system())
@@ -1907,11 +1983,13 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         args: Vec<JsonValue>,
         context: ExecutionContext,
     ) -> anyhow::Result<FunctionResult> {
+        let path = PublicFunctionPath::Component(path);
+        self.check_usage_quota(&identity, &path)?;
         let ts = self.database.now_ts_for_reads();
         let result = self
             .run_query_at_ts(
                 context.request_id,
-                PublicFunctionPath::Component(path),
+                path,
                 args,
                 identity,
                 *ts,
@@ -1933,10 +2011,12 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         args: Vec<JsonValue>,
         context: ExecutionContext,
     ) -> anyhow::Result<FunctionResult> {
+        let path = PublicFunctionPath::Component(path);
+        self.check_usage_quota(&identity, &path)?;
         let result = self
             .retry_mutation(
                 context.request_id,
-                PublicFunctionPath::Component(path),
+                path,
                 args,
                 identity,
                 None,
@@ -1961,11 +2041,13 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         args: Vec<JsonValue>,
         context: ExecutionContext,
     ) -> anyhow::Result<FunctionResult> {
+        let path = PublicFunctionPath::Component(path);
+        self.check_usage_quota(&identity, &path)?;
         let _tx = self.database.begin(identity.clone()).await?;
         let result = self
             .run_action(
                 context.request_id,
-                PublicFunctionPath::Component(path),
+                path,
                 args,
                 identity,
                 FunctionCaller::Action {
@@ -2149,6 +2231,30 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         self.database.vector_search(identity, query).await
     }
 
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let query = HybridSearch::try_from(query).map_err(|e| {
+            let message = e.to_string();
+            e.context(ErrorMetadata::bad_request("InvalidHybridSearch", message))
+        })?;
+        self.database.hybrid_search(identity, query).await
+    }
+
+    async fn text_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<TextSearchResult>, FunctionUsageStats)> {
+        let query = TextSearch::try_from(query).map_err(|e| {
+            let message = e.to_string();
+            e.context(ErrorMetadata::bad_request("InvalidTextSearch", message))
+        })?;
+        self.database.text_search(identity, query).await
+    }
+
     async fn lookup_function_handle(
         &self,
         identity: Identity,
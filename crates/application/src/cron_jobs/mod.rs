@@ -329,6 +329,11 @@ impl<RT: Runtime> CronJobContext<RT> {
             })?
             .udf_type;
 
+        // Crons count towards their component's usage, so retry them later
+        // while the component is over its quota.
+        self.runner
+            .check_component_usage_quota(tx.identity(), &path.component)?;
+
         let job_id = job.id;
         match udf_type {
             UdfType::Mutation => {
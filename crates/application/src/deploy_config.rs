@@ -32,7 +32,10 @@ use common::{
         Runtime,
         UnixTimestamp,
     },
-    schemas::DatabaseSchema,
+    schemas::{
+        DatabaseSchema,
+        REFERENCE_INDEX_PREFIX,
+    },
     types::{
         EnvVarName,
         EnvVarValue,
@@ -99,6 +102,13 @@ use model::{
         ModuleSource,
         SourceMap,
     },
+    rate_limits::{
+        types::{
+            RateLimitPolicy,
+            SerializedRateLimitPolicy,
+        },
+        RateLimitModel,
+    },
     source_packages::{
         types::SourcePackage,
         upload_download::download_package,
@@ -169,7 +179,9 @@ impl<RT: Runtime> Application<RT> {
 
         let (user_environment_variables, system_env_var_overrides) = {
             let mut tx = self.begin(Identity::system()).await?;
-            let vars = EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+            let vars = EnvironmentVariablesModel::new(&mut tx, &self.key_broker)
+                .get_all()
+                .await?;
             let system_env_var_overrides = system_env_var_overrides(&mut tx).await?;
             tx.into_token()?;
             (vars, system_env_var_overrides)
@@ -203,6 +215,7 @@ impl<RT: Runtime> Application<RT> {
                         .collect::<Result<Vec<_>, _>>()?;
                     Some(auth_info)
                 },
+                rate_limits: None,
             },
         )
         .await?;
@@ -256,6 +269,7 @@ impl<RT: Runtime> Application<RT> {
             external_deps_id,
             component_definition_packages,
             app_auth: auth_info,
+            rate_limits: config.config.rate_limits.clone(),
             analysis: evaluated_components,
             app,
             schema_change,
@@ -539,10 +553,22 @@ impl<RT: Runtime> Application<RT> {
             let namespace = TableNamespace::from(component_id);
             let mut indexes_complete = 0;
             let mut indexes_total = 0;
-            for index in IndexModel::new(&mut tx)
+            let application_indexes = IndexModel::new(&mut tx)
                 .get_application_indexes(namespace)
+                .await?;
+            // Deletes need the indexes backing references, so wait for those too.
+            let reference_indexes = IndexModel::new(&mut tx)
+                .get_system_indexes(namespace)
                 .await?
-            {
+                .into_iter()
+                .filter(|index| {
+                    index
+                        .name
+                        .descriptor()
+                        .as_str()
+                        .starts_with(REFERENCE_INDEX_PREFIX)
+                });
+            for index in application_indexes.into_iter().chain(reference_indexes) {
                 if !index.config.is_backfilling() {
                     indexes_complete += 1;
                 }
@@ -601,7 +627,9 @@ impl<RT: Runtime> Application<RT> {
                 async move {
                     // Validate that environment variables haven't changed since `start_push`.
                     let environment_variables =
-                        EnvironmentVariablesModel::new(tx).get_all().await?;
+                        EnvironmentVariablesModel::new(tx, &self.key_broker)
+                            .get_all()
+                            .await?;
                     if environment_variables != start_push.environment_variables {
                         anyhow::bail!(ErrorMetadata::bad_request(
                             "RaceDetected",
@@ -613,6 +641,9 @@ impl<RT: Runtime> Application<RT> {
                     let auth_diff = AuthInfoModel::new(tx)
                         .put(start_push.app_auth.clone())
                         .await?;
+                    RateLimitModel::new(tx)
+                        .put(start_push.rate_limits.clone())
+                        .await?;
 
                     // Diff the component definitions.
                     let (definition_diffs, modules_by_definition, udf_config_by_definition) =
@@ -699,7 +730,9 @@ impl<RT: Runtime> Application<RT> {
         // Note: This is not transactional with the rest of the deploy to avoid keeping
         // a transaction open for a long time.
         let mut tx = self.begin(Identity::system()).await?;
-        let user_environment_variables = EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+        let user_environment_variables = EnvironmentVariablesModel::new(&mut tx, &self.key_broker)
+            .get_all()
+            .await?;
         let system_env_var_overrides = system_env_var_overrides(&mut tx).await?;
         drop(tx);
         // Run analyze to make sure the new modules are valid.
@@ -819,6 +852,9 @@ pub struct StartPushRequest {
     pub component_definitions: Vec<ComponentDefinitionConfigJson>,
 
     pub node_dependencies: Vec<NodeDependencyJson>,
+
+    #[serde(default)]
+    pub rate_limits: Vec<SerializedRateLimitPolicy>,
 }
 
 impl StartPushRequest {
@@ -827,6 +863,11 @@ impl StartPushRequest {
             config: ConfigMetadata {
                 functions: self.functions,
                 auth_info: vec![],
+                rate_limits: self
+                    .rate_limits
+                    .into_iter()
+                    .map(RateLimitPolicy::try_from)
+                    .collect::<anyhow::Result<_>>()?,
             },
             app_definition: self.app_definition.try_into()?,
             component_definitions: self
@@ -853,6 +894,7 @@ pub struct StartPushResponse {
     pub component_definition_packages: BTreeMap<ComponentDefinitionPath, SourcePackage>,
 
     pub app_auth: Vec<AuthInfo>,
+    pub rate_limits: Vec<RateLimitPolicy>,
     pub analysis: BTreeMap<ComponentDefinitionPath, EvaluatedComponentDefinition>,
 
     pub app: CheckedComponent,
@@ -102,6 +102,14 @@ use value::{
     ConvexArray,
 };
 
+use crate::{
+    function_log_store::{
+        FunctionLogStore,
+        StoredFunctionExecution,
+    },
+    udf_prometheus::UdfPrometheusMetrics,
+};
+
 /// A function's execution is summarized by this structure and stored in the
 /// UdfExecutionLog
 #[derive(Debug, Clone)]
@@ -518,10 +526,17 @@ pub struct FunctionExecutionLog<RT: Runtime> {
     inner: Arc<Mutex<Inner<RT>>>,
     usage_tracking: UsageCounter,
     rt: RT,
+    /// If set, completed executions are also written to durable storage.
+    store: Option<FunctionLogStore>,
 }
 
 impl<RT: Runtime> FunctionExecutionLog<RT> {
-    pub fn new(rt: RT, usage_tracking: UsageCounter, log_manager: Arc<dyn LogSender>) -> Self {
+    pub fn new(
+        rt: RT,
+        usage_tracking: UsageCounter,
+        log_manager: Arc<dyn LogSender>,
+        store: Option<FunctionLogStore>,
+    ) -> Self {
         let base_ts = rt.system_time();
         let inner = Inner {
             rt: rt.clone(),
@@ -539,14 +554,21 @@ impl<RT: Runtime> FunctionExecutionLog<RT> {
                     histogram_significant_figures: *knobs::UDF_METRICS_SIGNIFICANT_FIGURES,
                 },
             ),
+            prometheus_metrics: UdfPrometheusMetrics::new()
+                .expect("Failed to initialize per-function Prometheus metrics"),
         };
         Self {
             inner: Arc::new(Mutex::new(inner)),
             rt,
             usage_tracking,
+            store,
         }
     }
 
+    pub fn store(&self) -> Option<&FunctionLogStore> {
+        self.store.as_ref()
+    }
+
     pub async fn log_query(
         &self,
         outcome: &UdfOutcome,
@@ -1085,6 +1107,12 @@ impl<RT: Runtime> FunctionExecutionLog<RT> {
     }
 
     fn log_execution(&self, execution: FunctionExecution, send_console_events: bool) {
+        if let Some(store) = &self.store {
+            match StoredFunctionExecution::from_execution(&execution) {
+                Ok(stored) => store.append(stored),
+                Err(mut e) => report_error_sync(&mut e),
+            }
+        }
         if let Err(mut e) = self
             .inner
             .lock()
@@ -1310,6 +1338,12 @@ impl<RT: Runtime> FunctionExecutionLog<RT> {
         window.resample_histograms(&metrics, buckets, &percentiles)
     }
 
+    /// Per-function call counts, error counts, cache hits and latency
+    /// histograms in the Prometheus text exposition format.
+    pub fn prometheus_metrics(&self) -> anyhow::Result<String> {
+        self.inner.lock().prometheus_metrics.encode()
+    }
+
     pub fn table_rate(
         &self,
         table_name: TableName,
@@ -1536,6 +1570,7 @@ struct Inner<RT: Runtime> {
     log_waiters: WithHeapSize<Vec<oneshot::Sender<()>>>,
     log_manager: Arc<dyn LogSender>,
     metrics: MetricStore,
+    prometheus_metrics: UdfPrometheusMetrics,
 }
 
 impl<RT: Runtime> Inner<RT> {
@@ -1611,13 +1646,23 @@ impl<RT: Runtime> Inner<RT> {
 
         let identifier = execution.identifier();
 
-        let name = udf_invocations_metric(&identifier);
-        self.metrics.add_counter(&name, ts, 1.0)?;
-
         let is_err = match &execution.params {
             UdfParams::Function { error, .. } => error.is_some(),
             UdfParams::Http { result, .. } => result.is_err(),
         };
+        let (component, function) = identifier.clone().into_component_and_udf_path();
+        self.prometheus_metrics.record(
+            component,
+            function,
+            execution.udf_type,
+            is_err,
+            (execution.udf_type == UdfType::Query).then_some(execution.cached_result),
+            Duration::from_secs_f64(execution.execution_time),
+        );
+
+        let name = udf_invocations_metric(&identifier);
+        self.metrics.add_counter(&name, ts, 1.0)?;
+
         if is_err {
             let name = udf_errors_metric(&identifier);
             self.metrics.add_counter(&name, ts, 1.0)?;
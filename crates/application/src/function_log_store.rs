@@ -0,0 +1,416 @@
+//! Durable storage for function executions.
+//!
+//! [`FunctionExecutionLog`](crate::function_log::FunctionExecutionLog) only
+//! keeps the most recent executions in memory. When a [`FunctionLogStore`] is
+//! configured, completed executions (including their log lines) are also
+//! appended as JSON lines to files in a local directory, so they survive
+//! restarts and can be searched with [`FunctionLogStore::query`].
+//!
+//! Files are named `functions-<start>.jsonl` where `<start>` is the timestamp
+//! of the file's first execution in milliseconds since the Unix epoch. The
+//! writer rotates to a new file once the current one exceeds
+//! [`FUNCTION_LOG_STORE_MAX_FILE_BYTES`] and deletes files whose entries are
+//! all older than [`FUNCTION_LOG_STORE_RETENTION`].
+
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+};
+
+use anyhow::Context;
+use common::{
+    knobs::{
+        FUNCTION_LOG_STORE_BUFFER_SIZE,
+        FUNCTION_LOG_STORE_MAX_FILE_BYTES,
+        FUNCTION_LOG_STORE_RETENTION,
+    },
+    runtime::{
+        Runtime,
+        SpawnHandle,
+    },
+};
+use parking_lot::Mutex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value as JsonValue;
+use tokio::{
+    fs,
+    io::{
+        AsyncBufReadExt,
+        AsyncWriteExt,
+        BufReader,
+    },
+    sync::mpsc,
+};
+
+use crate::{
+    function_log::{
+        FunctionExecution,
+        UdfParams,
+    },
+    metrics::log_function_log_store_dropped,
+};
+
+/// The maximum number of executions returned by a single query.
+pub const MAX_FUNCTION_LOG_QUERY_LIMIT: usize = 1000;
+
+const FILE_PREFIX: &str = "functions-";
+const FILE_SUFFIX: &str = ".jsonl";
+
+/// A completed function execution as stored in the durable function log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredFunctionExecution {
+    /// Seconds since the Unix epoch when the execution completed.
+    pub timestamp: f64,
+    pub udf_type: String,
+    /// `None` for the root component.
+    pub component_path: Option<String>,
+    /// The function path, or the route for HTTP actions.
+    pub identifier: String,
+    pub request_id: String,
+    pub execution_id: String,
+    pub cached_result: bool,
+    /// Execution time in seconds.
+    pub execution_time: f64,
+    pub error: Option<String>,
+    /// The response status for HTTP actions that didn't throw.
+    pub http_status: Option<u16>,
+    pub log_lines: Vec<JsonValue>,
+}
+
+impl StoredFunctionExecution {
+    pub fn from_execution(execution: &FunctionExecution) -> anyhow::Result<Self> {
+        let (component_path, identifier, error, http_status) = match &execution.params {
+            UdfParams::Function { error, identifier } => (
+                identifier.component.clone().serialize(),
+                String::from(identifier.udf_path.clone().strip()),
+                error.as_ref().map(|e| e.to_string()),
+                None,
+            ),
+            UdfParams::Http { result, identifier } => {
+                let (error, http_status) = match result {
+                    Ok(status) => (None, Some(status.0.as_u16())),
+                    Err(e) => (Some(e.to_string()), None),
+                };
+                (None, identifier.to_string(), error, http_status)
+            },
+        };
+        Ok(Self {
+            timestamp: execution.unix_timestamp.as_secs_f64(),
+            udf_type: execution.udf_type.to_string(),
+            component_path,
+            identifier,
+            request_id: execution.context.request_id.to_string(),
+            execution_id: execution.context.execution_id.to_string(),
+            cached_result: execution.cached_result,
+            execution_time: execution.execution_time,
+            error,
+            http_status,
+            log_lines: execution.log_lines.clone().to_jsons(true, false)?,
+        })
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    fn matches(&self, filter: &FunctionLogFilter) -> bool {
+        if let Some(start) = filter.start
+            && self.timestamp < start
+        {
+            return false;
+        }
+        if let Some(end) = filter.end
+            && self.timestamp >= end
+        {
+            return false;
+        }
+        if let Some(component_path) = &filter.component_path
+            && self.component_path.as_ref() != Some(component_path)
+        {
+            return false;
+        }
+        if let Some(identifier) = &filter.identifier
+            && &self.identifier != identifier
+        {
+            return false;
+        }
+        if let Some(request_id) = &filter.request_id
+            && &self.request_id != request_id
+        {
+            return false;
+        }
+        match filter.status {
+            Some(ExecutionStatus::Success) if !self.is_success() => return false,
+            Some(ExecutionStatus::Failure) if self.is_success() => return false,
+            _ => (),
+        }
+        if let Some(text) = &filter.text {
+            let text = text.to_lowercase();
+            let contains = |s: &str| s.to_lowercase().contains(&text);
+            let in_log_lines = self.log_lines.iter().any(|line| match line {
+                JsonValue::String(s) => contains(s),
+                JsonValue::Object(obj) => match obj.get("messages") {
+                    Some(JsonValue::Array(messages)) => {
+                        messages.iter().any(|m| m.as_str().is_some_and(contains))
+                    },
+                    _ => false,
+                },
+                _ => false,
+            });
+            if !(in_log_lines
+                || contains(&self.identifier)
+                || self.error.as_deref().is_some_and(contains))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutionStatus {
+    Success,
+    Failure,
+}
+
+/// Filters for [`FunctionLogStore::query`]. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionLogFilter {
+    /// Seconds since the Unix epoch, inclusive.
+    pub start: Option<f64>,
+    /// Seconds since the Unix epoch, exclusive.
+    pub end: Option<f64>,
+    /// Only match functions in this component. The root component can't be
+    /// selected on its own.
+    pub component_path: Option<String>,
+    pub identifier: Option<String>,
+    pub request_id: Option<String>,
+    pub status: Option<ExecutionStatus>,
+    /// Case-insensitive substring match against the function identifier,
+    /// error message and log lines.
+    pub text: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct FunctionLogStore {
+    dir: PathBuf,
+    sender: mpsc::Sender<StoredFunctionExecution>,
+    writer: Arc<Mutex<Box<dyn SpawnHandle>>>,
+}
+
+impl FunctionLogStore {
+    pub async fn new<RT: Runtime>(rt: RT, dir: PathBuf) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create function log directory {dir:?}"))?;
+        let (sender, receiver) = mpsc::channel(*FUNCTION_LOG_STORE_BUFFER_SIZE);
+        let writer = rt.spawn(
+            "function_log_store_writer",
+            Self::write_loop(rt.clone(), dir.clone(), receiver),
+        );
+        Ok(Self {
+            dir,
+            sender,
+            writer: Arc::new(Mutex::new(writer)),
+        })
+    }
+
+    /// Queue an execution to be written. This never blocks: if the writer is
+    /// falling behind, the execution is dropped from the durable log.
+    pub fn append(&self, execution: StoredFunctionExecution) {
+        if self.sender.try_send(execution).is_err() {
+            log_function_log_store_dropped();
+        }
+    }
+
+    /// Executions matching `filter`, newest first, returning at most `limit`
+    /// entries. Executions that are still buffered waiting to be written
+    /// aren't included.
+    pub async fn query(
+        &self,
+        filter: &FunctionLogFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<StoredFunctionExecution>> {
+        let limit = limit.min(MAX_FUNCTION_LOG_QUERY_LIMIT);
+        let files = list_files(&self.dir).await?;
+        let mut results = vec![];
+        // Each file covers the range from its own start to the start of the
+        // next file, so walk files newest first and stop once the files are
+        // entirely before `filter.start`.
+        let mut next_file_start_ms = None;
+        for (start_ms, path) in files.into_iter().rev() {
+            let file_end_ms = next_file_start_ms.replace(start_ms);
+            if let Some(end) = filter.end
+                && (start_ms as f64) / 1000. >= end
+            {
+                continue;
+            }
+            if let (Some(start), Some(file_end_ms)) = (filter.start, file_end_ms)
+                && (file_end_ms as f64) / 1000. < start
+            {
+                break;
+            }
+            let mut matches = read_file(&path)
+                .await?
+                .into_iter()
+                .filter(|execution| execution.matches(filter))
+                .collect::<Vec<_>>();
+            matches.reverse();
+            results.extend(matches);
+            if results.len() >= limit {
+                results.truncate(limit);
+                break;
+            }
+        }
+        Ok(results)
+    }
+
+    pub fn shutdown(&self) {
+        self.writer.lock().shutdown();
+    }
+
+    async fn write_loop<RT: Runtime>(
+        rt: RT,
+        dir: PathBuf,
+        mut receiver: mpsc::Receiver<StoredFunctionExecution>,
+    ) {
+        let mut writer = FileWriter {
+            rt,
+            dir,
+            current: None,
+        };
+        while let Some(execution) = receiver.recv().await {
+            let mut batch = vec![execution];
+            while let Ok(execution) = receiver.try_recv() {
+                batch.push(execution);
+            }
+            if let Err(e) = writer.write(batch).await {
+                // Drop the batch rather than retrying, so a persistent failure
+                // (e.g. a full disk) doesn't grow memory without bound.
+                tracing::error!("Failed to write to durable function log: {e:#}");
+                writer.current = None;
+            }
+        }
+    }
+}
+
+struct FileWriter<RT: Runtime> {
+    rt: RT,
+    dir: PathBuf,
+    current: Option<(fs::File, u64)>,
+}
+
+impl<RT: Runtime> FileWriter<RT> {
+    async fn write(&mut self, batch: Vec<StoredFunctionExecution>) -> anyhow::Result<()> {
+        let first_timestamp = batch
+            .iter()
+            .map(|execution| execution.timestamp)
+            .fold(f64::INFINITY, f64::min);
+        let mut buf = vec![];
+        for execution in batch {
+            serde_json::to_writer(&mut buf, &execution)?;
+            buf.push(b'\n');
+        }
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|(_, size)| *size >= *FUNCTION_LOG_STORE_MAX_FILE_BYTES)
+        {
+            self.rotate(first_timestamp).await?;
+        }
+        let (file, size) = self.current.as_mut().context("No current log file")?;
+        file.write_all(&buf).await?;
+        file.flush().await?;
+        *size += buf.len() as u64;
+        Ok(())
+    }
+
+    /// Start a new file whose first entry was logged at `first_timestamp`.
+    async fn rotate(&mut self, first_timestamp: f64) -> anyhow::Result<()> {
+        let start_ms = (first_timestamp * 1000.) as u128;
+        let path = self
+            .dir
+            .join(format!("{FILE_PREFIX}{start_ms}{FILE_SUFFIX}"));
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .with_context(|| format!("Failed to open function log file {path:?}"))?;
+        let size = file.metadata().await?.len();
+        self.current = Some((file, size));
+        self.delete_expired_files().await
+    }
+
+    async fn delete_expired_files(&self) -> anyhow::Result<()> {
+        let cutoff_ms = self
+            .rt
+            .unix_timestamp()
+            .as_nanos()
+            .saturating_sub(FUNCTION_LOG_STORE_RETENTION.as_nanos())
+            / 1_000_000;
+        let files = list_files(&self.dir).await?;
+        // A file's entries are all older than the cutoff if the next file
+        // started before the cutoff.
+        for window in files.windows(2) {
+            let [(_, path), (next_start_ms, _)] = window else {
+                continue;
+            };
+            if *next_start_ms >= cutoff_ms {
+                break;
+            }
+            tracing::info!("Deleting expired function log file {path:?}");
+            fs::remove_file(path).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Log files in `dir` with their start times, oldest first.
+async fn list_files(dir: &Path) -> anyhow::Result<Vec<(u128, PathBuf)>> {
+    let mut files = vec![];
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name();
+        let Some(start_ms) = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix(FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(FILE_SUFFIX))
+            .and_then(|start| start.parse::<u128>().ok())
+        else {
+            continue;
+        };
+        files.push((start_ms, entry.path()));
+    }
+    files.sort();
+    Ok(files)
+}
+
+async fn read_file(path: &Path) -> anyhow::Result<Vec<StoredFunctionExecution>> {
+    let file = match fs::File::open(path).await {
+        Ok(file) => file,
+        // The file may have been deleted by retention since it was listed.
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let mut lines = BufReader::new(file).lines();
+    let mut executions = vec![];
+    while let Some(line) = lines.next_line().await? {
+        // Skip lines that fail to parse, e.g. a partially written line after
+        // a crash.
+        match serde_json::from_str(&line) {
+            Ok(execution) => executions.push(execution),
+            Err(e) => tracing::warn!("Skipping invalid function log line in {path:?}: {e}"),
+        }
+    }
+    Ok(executions)
+}
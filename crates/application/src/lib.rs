@@ -67,7 +67,9 @@ use common::{
         Resource,
     },
     document::{
+        CreationTime,
         DocumentUpdate,
+        ParsedDocument,
         CREATION_TIME_FIELD_PATH,
     },
     errors::{
@@ -79,6 +81,7 @@ use common::{
         APPLICATION_MAX_CONCURRENT_UPLOADS,
         MAX_JOBS_CANCEL_BATCH,
         MAX_USER_MODULES,
+        SCHEMA_MIGRATION_DEFAULT_BATCH_SIZE,
         SNAPSHOT_LIST_LIMIT,
     },
     log_lines::LogLines,
@@ -206,6 +209,13 @@ use model::{
         types::CanonicalUrl,
         CanonicalUrlsModel,
     },
+    code_versions::{
+        types::{
+            CodeRollbackDiff,
+            CodeVersion,
+        },
+        CodeVersionModel,
+    },
     components::{
         config::ComponentConfigModel,
         handles::FunctionHandlesModel,
@@ -227,7 +237,11 @@ use model::{
         DatabaseGlobalsModel,
     },
     deployment_audit_log::{
-        types::DeploymentAuditLogEvent,
+        types::{
+            DeploymentAuditLogEntry,
+            DeploymentAuditLogEvent,
+            DeploymentAuditLogFilter,
+        },
         DeploymentAuditLogModel,
     },
     environment_variables::{
@@ -262,7 +276,18 @@ use model::{
         },
         ModuleModel,
     },
+    rate_limits::{
+        types::{
+            RateLimitKey,
+            RateLimitPolicy,
+        },
+        RateLimitModel,
+    },
     scheduled_jobs::SchedulerModel,
+    schema_migrations::{
+        types::SchemaMigration,
+        SchemaMigrationModel,
+    },
     session_requests::types::SessionRequestIdentifier,
     snapshot_imports::types::{
         ImportFormat,
@@ -273,6 +298,7 @@ use model::{
         types::{
             PackageSize,
             SourcePackage,
+            SourcePackageId,
         },
         upload_download::upload_package,
         SourcePackageModel,
@@ -281,6 +307,13 @@ use model::{
         types::UdfConfig,
         UdfConfigModel,
     },
+    usage::{
+        types::{
+            UsageQuota,
+            UsageRollupKey,
+        },
+        UsageModel,
+    },
 };
 use node_executor::Actions;
 use parking_lot::Mutex;
@@ -372,13 +405,30 @@ use crate::{
         UdfMetricSummary,
         UdfRate,
     },
+    function_log_store::{
+        FunctionLogFilter,
+        FunctionLogStore,
+        StoredFunctionExecution,
+    },
     log_visibility::LogVisibility,
     module_cache::ModuleCache,
+    rate_limiter::{
+        FunctionRateLimiter,
+        RateLimitCaller,
+    },
     redaction::{
         RedactedJsError,
         RedactedLogLines,
     },
+    schema_migration_worker::SchemaMigrationWorker,
     snapshot_import::SnapshotImportWorker,
+    usage_accounting::{
+        summarize_usage,
+        UsageAccounting,
+        UsageAccountingWorker,
+        UsageGroupBy,
+        UsageSummaryRow,
+    },
 };
 
 pub mod airbyte_import;
@@ -389,15 +439,20 @@ pub mod cron_jobs;
 pub mod deploy_config;
 mod exports;
 pub mod function_log;
+pub mod function_log_store;
 pub mod log_visibility;
 mod metrics;
 mod module_cache;
+pub mod rate_limiter;
 pub mod redaction;
 pub mod scheduled_jobs;
+mod schema_migration_worker;
 mod schema_worker;
 pub mod snapshot_import;
 mod system_table_cleanup;
 mod table_summary_worker;
+pub mod udf_prometheus;
+pub mod usage_accounting;
 pub mod valid_identifier;
 
 #[cfg(any(test, feature = "testing"))]
@@ -515,6 +570,8 @@ pub struct FunctionError {
 pub enum EnvVarChange {
     Unset(EnvVarName),
     Set(EnvironmentVariable),
+    /// Set a variable whose value can't be read back through admin APIs.
+    SetSecret(EnvironmentVariable),
 }
 
 #[derive(Clone)]
@@ -544,15 +601,19 @@ pub struct Application<RT: Runtime> {
     search_and_vector_bootstrap_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
     table_summary_worker: TableSummaryClient,
     schema_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
+    schema_migration_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
     snapshot_import_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
     export_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
     system_table_cleanup_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
+    usage_accounting: Option<UsageAccounting<RT>>,
+    usage_accounting_worker: Arc<Mutex<Box<dyn SpawnHandle>>>,
     migration_worker: Arc<Mutex<Option<Box<dyn SpawnHandle>>>>,
     log_sender: Arc<dyn LogSender>,
     log_visibility: Arc<dyn LogVisibility<RT>>,
     module_cache: ModuleCache<RT>,
     system_env_var_names: HashSet<EnvVarName>,
     app_auth: Arc<ApplicationAuth>,
+    function_rate_limiter: Arc<FunctionRateLimiter<RT>>,
 }
 
 impl<RT: Runtime> Clone for Application<RT> {
@@ -575,15 +636,19 @@ impl<RT: Runtime> Clone for Application<RT> {
             search_and_vector_bootstrap_worker: self.search_and_vector_bootstrap_worker.clone(),
             table_summary_worker: self.table_summary_worker.clone(),
             schema_worker: self.schema_worker.clone(),
+            schema_migration_worker: self.schema_migration_worker.clone(),
             snapshot_import_worker: self.snapshot_import_worker.clone(),
             export_worker: self.export_worker.clone(),
             system_table_cleanup_worker: self.system_table_cleanup_worker.clone(),
+            usage_accounting: self.usage_accounting.clone(),
+            usage_accounting_worker: self.usage_accounting_worker.clone(),
             migration_worker: self.migration_worker.clone(),
             log_sender: self.log_sender.clone(),
             log_visibility: self.log_visibility.clone(),
             module_cache: self.module_cache.clone(),
             system_env_var_names: self.system_env_var_names.clone(),
             app_auth: self.app_auth.clone(),
+            function_rate_limiter: self.function_rate_limiter.clone(),
         }
     }
 }
@@ -656,6 +721,28 @@ impl<RT: Runtime> Application<RT> {
         })
     }
 
+    /// Rewrite environment variables stored in plaintext or encrypted with a
+    /// previous instance secret so they're encrypted with the current one.
+    ///
+    /// The function runner only decrypts with the current secret, so call
+    /// this before `Application::new` starts any workers that run functions.
+    pub async fn reencrypt_environment_variables(
+        database: &Database<RT>,
+        key_broker: &KeyBroker,
+    ) -> anyhow::Result<()> {
+        let mut tx = database.begin(Identity::system()).await?;
+        let reencrypted = EnvironmentVariablesModel::new(&mut tx, key_broker)
+            .reencrypt_all()
+            .await?;
+        if reencrypted > 0 {
+            database
+                .commit_with_write_source(tx, "reencrypt_env_vars")
+                .await?;
+            tracing::info!("Re-encrypted {reencrypted} environment variables");
+        }
+        Ok(())
+    }
+
     pub async fn new(
         runtime: RT,
         database: Database<RT>,
@@ -675,6 +762,8 @@ impl<RT: Runtime> Application<RT> {
         log_visibility: Arc<dyn LogVisibility<RT>>,
         app_auth: Arc<ApplicationAuth>,
         cache: QueryCache,
+        usage_accounting: Option<UsageAccounting<RT>>,
+        function_log_store: Option<FunctionLogStore>,
     ) -> anyhow::Result<Self> {
         let module_cache =
             ModuleCache::new(runtime.clone(), application_storage.modules_storage.clone()).await;
@@ -724,10 +813,24 @@ impl<RT: Runtime> Application<RT> {
             runtime.spawn("system_table_cleanup_worker", system_table_cleanup_worker),
         ));
 
+        let usage_accounting_worker = match usage_accounting {
+            Some(ref usage_accounting) => runtime.spawn(
+                "usage_accounting_worker",
+                UsageAccountingWorker::new(
+                    runtime.clone(),
+                    database.clone(),
+                    usage_accounting.clone(),
+                ),
+            ),
+            None => runtime.spawn("usage_accounting_worker", async {}),
+        };
+        let usage_accounting_worker = Arc::new(Mutex::new(usage_accounting_worker));
+
         let function_log = FunctionExecutionLog::new(
             runtime.clone(),
             database.usage_counter(),
             log_sender.clone(),
+            function_log_store,
         );
         let runner = Arc::new(ApplicationFunctionRunner::new(
             runtime.clone(),
@@ -741,6 +844,7 @@ impl<RT: Runtime> Application<RT> {
             function_log.clone(),
             default_system_env_vars.clone(),
             cache,
+            usage_accounting.clone(),
         ));
         function_runner.set_action_callbacks(runner.clone());
 
@@ -763,6 +867,11 @@ impl<RT: Runtime> Application<RT> {
             runtime.spawn("cron_job_executor", cron_job_executor_fut),
         ));
 
+        let schema_migration_worker = Arc::new(Mutex::new(runtime.spawn(
+            "schema_migration_worker",
+            SchemaMigrationWorker::new(runtime.clone(), database.clone(), runner.clone()),
+        )));
+
         let export_worker = ExportWorker::new(
             runtime.clone(),
             database.clone(),
@@ -795,6 +904,7 @@ impl<RT: Runtime> Application<RT> {
         )));
 
         Ok(Self {
+            function_rate_limiter: Arc::new(FunctionRateLimiter::new(runtime.clone())),
             runtime,
             database,
             runner,
@@ -812,9 +922,12 @@ impl<RT: Runtime> Application<RT> {
             search_and_vector_bootstrap_worker,
             table_summary_worker,
             schema_worker,
+            schema_migration_worker,
             export_worker,
             snapshot_import_worker,
             system_table_cleanup_worker,
+            usage_accounting,
+            usage_accounting_worker,
             migration_worker,
             log_sender,
             log_visibility,
@@ -824,6 +937,106 @@ impl<RT: Runtime> Application<RT> {
         })
     }
 
+    /// Create an application on top of a read replica [`Database`] (see
+    /// `Database::load_follower`). Read replicas serve queries and
+    /// subscriptions but don't run any background workers that write, such as
+    /// index backfills, scheduled jobs, crons, imports or exports. Those run on
+    /// the leader, and their effects reach the replica through the document
+    /// log.
+    pub async fn new_read_replica(
+        runtime: RT,
+        database: Database<RT>,
+        file_storage: FileStorage<RT>,
+        application_storage: ApplicationStorage,
+        usage_tracking: UsageCounter,
+        key_broker: KeyBroker,
+        instance_name: String,
+        function_runner: Arc<dyn FunctionRunner<RT>>,
+        convex_origin: ConvexOrigin,
+        convex_site: ConvexSite,
+        node_actions: Actions<RT>,
+        log_sender: Arc<dyn LogSender>,
+        log_visibility: Arc<dyn LogVisibility<RT>>,
+        app_auth: Arc<ApplicationAuth>,
+        cache: QueryCache,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            database.is_read_replica(),
+            "Application::new_read_replica requires a read replica database"
+        );
+        let module_cache =
+            ModuleCache::new(runtime.clone(), application_storage.modules_storage.clone()).await;
+        let module_loader = Arc::new(module_cache.clone());
+
+        let default_system_env_vars = btreemap! {
+            CONVEX_ORIGIN.clone() => convex_origin.parse()?,
+            CONVEX_SITE.clone() => convex_site.parse()?
+        };
+        let disabled_worker = |name: &'static str| -> Arc<Mutex<Box<dyn SpawnHandle>>> {
+            Arc::new(Mutex::new(runtime.spawn(name, async {})))
+        };
+
+        let search_and_vector_bootstrap_worker =
+            Arc::new(Mutex::new(database.start_search_and_vector_bootstrap()));
+        let table_summary_worker =
+            TableSummaryWorker::start_follower(runtime.clone(), database.clone());
+
+        let function_log = FunctionExecutionLog::new(
+            runtime.clone(),
+            database.usage_counter(),
+            log_sender.clone(),
+            None,
+        );
+        let runner = Arc::new(ApplicationFunctionRunner::new(
+            runtime.clone(),
+            database.clone(),
+            key_broker.clone(),
+            function_runner.clone(),
+            node_actions,
+            file_storage.transactional_file_storage.clone(),
+            application_storage.modules_storage.clone(),
+            module_loader,
+            function_log.clone(),
+            default_system_env_vars.clone(),
+            cache,
+            None,
+        ));
+        function_runner.set_action_callbacks(runner.clone());
+
+        Ok(Self {
+            scheduled_job_runner: ScheduledJobRunner::disabled(runtime.clone()),
+            cron_job_executor: disabled_worker("cron_job_executor"),
+            index_worker: disabled_worker("index_worker"),
+            fast_forward_worker: disabled_worker("fast_forward_worker"),
+            search_worker: Arc::new(Mutex::new(SearchIndexWorkers::disabled())),
+            schema_worker: disabled_worker("schema_worker"),
+            schema_migration_worker: disabled_worker("schema_migration_worker"),
+            export_worker: disabled_worker("export_worker"),
+            snapshot_import_worker: disabled_worker("snapshot_import_worker"),
+            system_table_cleanup_worker: disabled_worker("system_table_cleanup_worker"),
+            usage_accounting: None,
+            usage_accounting_worker: disabled_worker("usage_accounting_worker"),
+            migration_worker: Arc::new(Mutex::new(None)),
+            function_rate_limiter: Arc::new(FunctionRateLimiter::new(runtime.clone())),
+            runtime,
+            database,
+            runner,
+            function_log,
+            file_storage,
+            application_storage,
+            usage_tracking,
+            key_broker,
+            instance_name,
+            search_and_vector_bootstrap_worker,
+            table_summary_worker,
+            log_sender,
+            log_visibility,
+            module_cache,
+            system_env_var_names: default_system_env_vars.into_keys().collect(),
+            app_auth,
+        })
+    }
+
     pub fn runtime(&self) -> RT {
         self.runtime.clone()
     }
@@ -886,6 +1099,158 @@ impl<RT: Runtime> Application<RT> {
         self.database.usage_counter().clone()
     }
 
+    /// Usage recorded in `_usage_rollups` for hours starting in
+    /// `[start_secs, end_secs)`, summed per group. Usage is flushed to
+    /// `_usage_rollups` periodically, so the most recent usage may be missing.
+    pub async fn usage_summary(
+        &self,
+        identity: Identity,
+        start_secs: u64,
+        end_secs: u64,
+        group_by: UsageGroupBy,
+    ) -> anyhow::Result<Vec<UsageSummaryRow>> {
+        let mut tx = self.begin(identity).await?;
+        let rollups = UsageModel::new(&mut tx)
+            .rollups_in_range(
+                UsageRollupKey::hour_containing(start_secs),
+                end_secs.try_into()?,
+            )
+            .await?;
+        Ok(summarize_usage(
+            rollups.into_iter().map(|rollup| rollup.into_value()),
+            group_by,
+        ))
+    }
+
+    pub async fn list_usage_quotas(&self, identity: Identity) -> anyhow::Result<Vec<UsageQuota>> {
+        let mut tx = self.begin(identity).await?;
+        UsageModel::new(&mut tx).list_quotas().await
+    }
+
+    /// Set the daily quotas for a component, replacing any existing quotas.
+    /// Setting no limits removes the component's quotas.
+    pub async fn set_usage_quota(
+        &self,
+        identity: Identity,
+        quota: UsageQuota,
+    ) -> anyhow::Result<()> {
+        let Some(usage_accounting) = &self.usage_accounting else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "UsageAccountingDisabled",
+                "Usage quotas can't be set because usage accounting is disabled on this backend.",
+            ));
+        };
+        let mut tx = self.begin(identity).await?;
+        UsageModel::new(&mut tx).set_quota(quota.clone()).await?;
+        self.commit(tx, "set_usage_quota").await?;
+        usage_accounting.update_quota(quota);
+        Ok(())
+    }
+
+    /// Start running the mutation at `udf_path` over every document in
+    /// `table_name`. Pending schemas that cover the table aren't enforced
+    /// until the migration finishes.
+    pub async fn start_schema_migration(
+        &self,
+        identity: Identity,
+        table_name: TableName,
+        udf_path: CanonicalizedUdfPath,
+        batch_size: Option<u64>,
+        dry_run: bool,
+    ) -> anyhow::Result<DeveloperDocumentId> {
+        let mut tx = self.begin(identity).await?;
+        if !TableModel::new(&mut tx).table_exists(TableNamespace::root_component(), &table_name) {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "TableNotFound",
+                format!("Table {table_name} not found"),
+            ));
+        }
+        let id = SchemaMigrationModel::new(&mut tx)
+            .start(
+                table_name,
+                udf_path,
+                batch_size.unwrap_or(*SCHEMA_MIGRATION_DEFAULT_BATCH_SIZE),
+                dry_run,
+            )
+            .await?;
+        self.commit(tx, "start_schema_migration").await?;
+        Ok(id.into())
+    }
+
+    pub async fn list_schema_migrations(
+        &self,
+        identity: Identity,
+    ) -> anyhow::Result<Vec<ParsedDocument<SchemaMigration>>> {
+        let mut tx = self.begin(identity).await?;
+        SchemaMigrationModel::new(&mut tx).list().await
+    }
+
+    pub async fn pause_schema_migration(
+        &self,
+        identity: Identity,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.begin(identity).await?;
+        SchemaMigrationModel::new(&mut tx).pause(id).await?;
+        self.commit(tx, "pause_schema_migration").await?;
+        Ok(())
+    }
+
+    pub async fn resume_schema_migration(
+        &self,
+        identity: Identity,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.begin(identity).await?;
+        SchemaMigrationModel::new(&mut tx).resume(id).await?;
+        self.commit(tx, "resume_schema_migration").await?;
+        Ok(())
+    }
+
+    /// Stop a running or paused migration. Batches that already committed are
+    /// not rolled back.
+    pub async fn cancel_schema_migration(
+        &self,
+        identity: Identity,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.begin(identity).await?;
+        SchemaMigrationModel::new(&mut tx).cancel(id).await?;
+        self.commit(tx, "cancel_schema_migration").await?;
+        Ok(())
+    }
+
+    /// Retained code versions of the root component, newest first, along with
+    /// the source package it's currently running.
+    pub async fn list_code_versions(
+        &self,
+        identity: Identity,
+    ) -> anyhow::Result<(Vec<ParsedDocument<CodeVersion>>, Option<SourcePackageId>)> {
+        let mut tx = self.begin(identity).await?;
+        let mut model = CodeVersionModel::new(&mut tx);
+        let versions = model.list().await?;
+        let active_source_package = model.active_source_package().await?;
+        Ok((versions, active_source_package))
+    }
+
+    /// Atomically re-activate a previously pushed code version, leaving the
+    /// schema and indexes untouched.
+    pub async fn rollback_code_version(
+        &self,
+        identity: Identity,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<CodeRollbackDiff> {
+        let mut tx = self.begin(identity).await?;
+        let diff = CodeVersionModel::new(&mut tx).rollback(id).await?;
+        self.commit_with_audit_log_events(
+            tx,
+            vec![DeploymentAuditLogEvent::RollbackCode { diff: diff.clone() }],
+            "rollback_code_version",
+        )
+        .await?;
+        Ok(diff)
+    }
+
     #[fastrace::trace]
     pub async fn document_deltas(
         &self,
@@ -1052,6 +1417,7 @@ impl<RT: Runtime> Application<RT> {
         journal: Option<Option<String>>,
         caller: FunctionCaller,
     ) -> anyhow::Result<RedactedQueryReturn> {
+        self.runner.check_usage_quota(&identity, &path)?;
         let persistence_version = self.database.persistence_version();
         let block_logging = self
             .log_visibility
@@ -1126,6 +1492,7 @@ impl<RT: Runtime> Application<RT> {
         mutation_queue_length: Option<usize>,
     ) -> anyhow::Result<Result<RedactedMutationReturn, RedactedMutationError>> {
         identity.ensure_can_run_function(UdfType::Mutation)?;
+        self.runner.check_usage_quota(&identity, &path)?;
         let block_logging = self
             .log_visibility
             .should_redact_logs_and_error(
@@ -1189,6 +1556,7 @@ impl<RT: Runtime> Application<RT> {
         caller: FunctionCaller,
     ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
         identity.ensure_can_run_function(UdfType::Action)?;
+        self.runner.check_usage_quota(&identity, &name)?;
 
         let block_logging = self
             .log_visibility
@@ -1256,6 +1624,9 @@ impl<RT: Runtime> Application<RT> {
         mut response_streamer: HttpActionResponseStreamer,
     ) -> anyhow::Result<()> {
         identity.ensure_can_run_function(UdfType::HttpAction)?;
+        // HTTP actions are always routed by the root component.
+        self.runner
+            .check_component_usage_quota(&identity, &ComponentPath::root())?;
         let block_logging = self
             .log_visibility
             .should_redact_logs_and_error(
@@ -1312,7 +1683,8 @@ impl<RT: Runtime> Application<RT> {
         Ok(())
     }
 
-    /// Run a function of an arbitrary type from its name
+    /// Run a function of an arbitrary type from its name. Mutations with a
+    /// `mutation_identifier` only run once per identifier.
     pub async fn any_udf(
         &self,
         request_id: RequestId,
@@ -1320,6 +1692,7 @@ impl<RT: Runtime> Application<RT> {
         args: Vec<JsonValue>,
         identity: Identity,
         caller: FunctionCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
     ) -> anyhow::Result<Result<FunctionReturn, FunctionError>> {
         let block_logging = self
             .log_visibility
@@ -1391,7 +1764,7 @@ impl<RT: Runtime> Application<RT> {
                     PublicFunctionPath::Component(path),
                     args,
                     identity,
-                    None,
+                    mutation_identifier,
                     caller,
                     None,
                 )
@@ -1565,10 +1938,11 @@ impl<RT: Runtime> Application<RT> {
     ) -> anyhow::Result<Vec<DeploymentAuditLogEvent>> {
         let mut audit_events = vec![];
 
-        let mut model = EnvironmentVariablesModel::new(tx);
+        let mut model = EnvironmentVariablesModel::new(tx, &self.key_broker);
         for change in changes {
+            let secret = matches!(change, EnvVarChange::SetSecret(_));
             match change {
-                EnvVarChange::Set(env_var) => {
+                EnvVarChange::Set(env_var) | EnvVarChange::SetSecret(env_var) => {
                     let name = env_var.name();
                     if let Some(_existing) = model.delete(name).await? {
                         audit_events.push(DeploymentAuditLogEvent::UpdateEnvironmentVariable {
@@ -1579,7 +1953,13 @@ impl<RT: Runtime> Application<RT> {
                             name: name.clone(),
                         });
                     }
-                    model.create(env_var, &self.system_env_var_names).await?;
+                    if secret {
+                        model
+                            .create_secret(env_var, &self.system_env_var_names)
+                            .await?;
+                    } else {
+                        model.create(env_var, &self.system_env_var_names).await?;
+                    }
                 },
                 EnvVarChange::Unset(name) => {
                     if let Some(_existing) = model.delete(&name).await? {
@@ -1607,7 +1987,9 @@ impl<RT: Runtime> Application<RT> {
         tx: &mut Transaction<RT>,
         environment_variables: Vec<EnvironmentVariable>,
     ) -> anyhow::Result<Vec<DeploymentAuditLogEvent>> {
-        let all_env_vars = EnvironmentVariablesModel::new(tx).get_all().await?;
+        let all_env_vars = EnvironmentVariablesModel::new(tx, &self.key_broker)
+            .get_all()
+            .await?;
         anyhow::ensure!(
             environment_variables.len() as u64 + all_env_vars.len() as u64
                 <= (ENV_VAR_LIMIT as u64),
@@ -1633,7 +2015,7 @@ impl<RT: Runtime> Application<RT> {
         tx: &mut Transaction<RT>,
         environment_variable: EnvironmentVariable,
     ) -> anyhow::Result<()> {
-        let mut env_var_model = EnvironmentVariablesModel::new(tx);
+        let mut env_var_model = EnvironmentVariablesModel::new(tx, &self.key_broker);
         if env_var_model
             .get(environment_variable.name())
             .await?
@@ -1654,7 +2036,7 @@ impl<RT: Runtime> Application<RT> {
     ) -> anyhow::Result<()> {
         let mut tx = self.begin(identity).await?;
 
-        if !EnvironmentVariablesModel::new(&mut tx)
+        if !EnvironmentVariablesModel::new(&mut tx, &self.key_broker)
             .get_all()
             .await?
             .is_empty()
@@ -1694,7 +2076,7 @@ impl<RT: Runtime> Application<RT> {
         tx: &mut Transaction<RT>,
         id: ResolvedDocumentId,
     ) -> anyhow::Result<DeploymentAuditLogEvent> {
-        let mut model = EnvironmentVariablesModel::new(tx);
+        let mut model = EnvironmentVariablesModel::new(tx, &self.key_broker);
         let Some(env_var) = model.get_by_id_legacy(id).await? else {
             anyhow::bail!(ErrorMetadata::bad_request(
                 "EnvironmentVariableNotFound",
@@ -1866,7 +2248,9 @@ impl<RT: Runtime> Application<RT> {
                 source_map: auth_config_source.source_map.clone(),
                 environment,
             };
-            let user_environment_variables = EnvironmentVariablesModel::new(tx).get_all().await?;
+            let user_environment_variables = EnvironmentVariablesModel::new(tx, &runner.key_broker)
+                .get_all()
+                .await?;
             let auth_config = Self::evaluate_auth_config(
                 runner,
                 user_environment_variables,
@@ -1938,7 +2322,9 @@ impl<RT: Runtime> Application<RT> {
             })
             .transpose()?;
 
-        let user_environment_variables = EnvironmentVariablesModel::new(tx).get_all().await?;
+        let user_environment_variables = EnvironmentVariablesModel::new(tx, &runner.key_broker)
+            .get_all()
+            .await?;
         let system_env_var_overrides = system_env_var_overrides(tx).await?;
         let auth_providers = Self::get_evaluated_auth_config(
             runner,
@@ -1949,7 +2335,7 @@ impl<RT: Runtime> Application<RT> {
         )
         .await?;
 
-        let config_metadata = ConfigMetadata::from_file(config_file, auth_providers);
+        let config_metadata = ConfigMetadata::from_file(config_file, auth_providers)?;
 
         let (config_diff, schema) = ConfigModel::new(tx, ComponentId::Root)
             .apply(
@@ -2331,7 +2717,9 @@ impl<RT: Runtime> Application<RT> {
         let mut tx = self.begin(identity.clone()).await?;
         let (user_environment_variables, system_env_var_overrides) = if component.is_root() {
             let user_environment_variables =
-                EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+                EnvironmentVariablesModel::new(&mut tx, &self.key_broker)
+                    .get_all()
+                    .await?;
             (
                 user_environment_variables,
                 system_env_var_overrides(&mut tx).await?,
@@ -2853,6 +3241,13 @@ impl<RT: Runtime> Application<RT> {
         self.function_log.cache_hit_percentage(identifier, window)
     }
 
+    pub async fn prometheus_function_metrics(&self, identity: Identity) -> anyhow::Result<String> {
+        if !(identity.is_admin() || identity.is_system()) {
+            anyhow::bail!(unauthorized_error("prometheus_function_metrics"));
+        }
+        self.function_log.prometheus_metrics()
+    }
+
     pub async fn latency_percentiles(
         &self,
         identity: Identity,
@@ -2902,6 +3297,26 @@ impl<RT: Runtime> Application<RT> {
         Ok(self.function_log.stream(cursor).await)
     }
 
+    /// Search the durable function log. Fails if this backend wasn't
+    /// configured with a [`FunctionLogStore`].
+    pub async fn query_function_log(
+        &self,
+        identity: Identity,
+        filter: FunctionLogFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<StoredFunctionExecution>> {
+        if !(identity.is_admin() || identity.is_system()) {
+            anyhow::bail!(unauthorized_error("query_function_log"));
+        }
+        let Some(store) = self.function_log.store() else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "FunctionLogStoreDisabled",
+                "The durable function log isn't enabled on this backend.",
+            ));
+        };
+        store.query(&filter, limit).await
+    }
+
     pub async fn stream_function_logs(
         &self,
         identity: Identity,
@@ -2971,6 +3386,68 @@ impl<RT: Runtime> Application<RT> {
         Ok((count, vec![]))
     }
 
+    /// A page of deployment audit log events matching `filter`, newest first,
+    /// and a cursor for the next page.
+    pub async fn list_deployment_audit_log(
+        &self,
+        identity: Identity,
+        filter: DeploymentAuditLogFilter,
+        cursor: Option<CreationTime>,
+        page_size: usize,
+    ) -> anyhow::Result<(Vec<DeploymentAuditLogEntry>, Option<CreationTime>)> {
+        let mut tx = self.begin(identity).await?;
+        DeploymentAuditLogModel::new(&mut tx)
+            .list_filtered(&filter, cursor, page_size)
+            .await
+    }
+
+    /// Fail with a rate limited error if calling `udf_path` would exceed one
+    /// of the deployment's rate limits.
+    pub async fn check_function_rate_limits(
+        &self,
+        identity: &Identity,
+        udf_path: &CanonicalizedUdfPath,
+        caller: &RateLimitCaller,
+    ) -> anyhow::Result<()> {
+        if FunctionRateLimiter::<RT>::is_exempt(identity) {
+            return Ok(());
+        }
+        let policies = self.rate_limit_policies().await?;
+        self.function_rate_limiter
+            .check(&policies, udf_path, identity, caller)
+    }
+
+    /// The request headers that the deployment's rate limits count calls by.
+    pub async fn rate_limit_header_names(&self) -> anyhow::Result<BTreeSet<String>> {
+        let policies = self.rate_limit_policies().await?;
+        Ok(policies
+            .iter()
+            .filter_map(|policy| match &policy.key {
+                RateLimitKey::Header(name) => Some(name.clone()),
+                RateLimitKey::Identity | RateLimitKey::Ip => None,
+            })
+            .collect())
+    }
+
+    async fn rate_limit_policies(&self) -> anyhow::Result<Arc<Vec<RateLimitPolicy>>> {
+        if let Some(policies) = self
+            .function_rate_limiter
+            .cached_policies(self.database.log())?
+        {
+            return Ok(policies);
+        }
+        let mut tx = self.begin(Identity::system()).await?;
+        let policies = RateLimitModel::new(&mut tx)
+            .get_all()
+            .await?
+            .into_iter()
+            .map(|policy| policy.into_value())
+            .collect();
+        Ok(self
+            .function_rate_limiter
+            .cache_policies(tx.into_token()?, policies))
+    }
+
     /// Commit a transaction and send audit log events to the log manager if the
     /// transaction commits successfully.
     pub async fn commit_with_audit_log_events(
@@ -3440,6 +3917,9 @@ impl<RT: Runtime> Application<RT> {
 
     pub async fn shutdown(&self) -> anyhow::Result<()> {
         self.log_sender.shutdown()?;
+        if let Some(function_log_store) = self.function_log.store() {
+            function_log_store.shutdown();
+        }
         self.table_summary_worker.shutdown().await?;
         self.system_table_cleanup_worker.lock().shutdown();
         self.schema_worker.lock().shutdown();
@@ -3452,6 +3932,13 @@ impl<RT: Runtime> Application<RT> {
         self.runner.shutdown().await?;
         self.scheduled_job_runner.shutdown();
         self.cron_job_executor.lock().shutdown();
+        self.schema_migration_worker.lock().shutdown();
+        self.usage_accounting_worker.lock().shutdown();
+        if let Some(usage_accounting) = &self.usage_accounting
+            && let Err(mut e) = usage_accounting.flush(&self.database).await
+        {
+            report_error(&mut e.context("Failed to flush usage rollups")).await;
+        }
         self.database.shutdown().await?;
         let migration_worker = self.migration_worker.lock().take();
         if let Some(migration_worker) = migration_worker {
@@ -1,4 +1,5 @@
 use metrics::{
+    log_counter,
     log_counter_with_labels,
     log_distribution_with_labels,
     register_convex_counter,
@@ -84,3 +85,11 @@ register_convex_histogram!(
 pub fn table_summary_bootstrap_timer() -> StatusTimer {
     StatusTimer::new(&TABLE_SUMMARY_BOOTSTRAP_SECONDS)
 }
+
+register_convex_counter!(
+    FUNCTION_LOG_STORE_DROPPED_TOTAL,
+    "Number of function executions dropped from the durable function log"
+);
+pub fn log_function_log_store_dropped() {
+    log_counter(&FUNCTION_LOG_STORE_DROPPED_TOTAL, 1);
+}
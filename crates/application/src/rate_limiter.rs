@@ -0,0 +1,206 @@
+//! Enforces the rate limit policies in `_rate_limits` on public function calls
+//! before they execute.
+//!
+//! Query subscriptions over the sync protocol aren't rate limited: the server
+//! reruns them when their reads change rather than when the client asks, and
+//! each WebSocket's query set is already bounded.
+
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    net::IpAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use common::{
+    knobs::RATE_LIMIT_MAX_TRACKED_KEYS,
+    runtime::Runtime,
+};
+use database::{
+    LogReader,
+    Token,
+};
+use errors::ErrorMetadata;
+use http::HeaderMap;
+use keybroker::Identity;
+use model::rate_limits::types::{
+    RateLimitKey,
+    RateLimitPolicy,
+};
+use parking_lot::Mutex;
+use sync_types::CanonicalizedUdfPath;
+
+/// What rate limit policies can count a call by, besides its identity.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitCaller {
+    pub ip: Option<IpAddr>,
+    pub headers: HeaderMap,
+}
+
+/// Token buckets for each rate limit policy, keyed by caller. Buckets for
+/// policies that are no longer deployed are dropped on the next check.
+pub struct FunctionRateLimiter<RT: Runtime> {
+    runtime: RT,
+    /// The deployed policies along with the token for the transaction that
+    /// read them, so later checks can skip the read while it's still fresh.
+    policies: Mutex<Option<(Token, Arc<Vec<RateLimitPolicy>>)>>,
+    buckets: Mutex<BTreeMap<RateLimitPolicy, BTreeMap<String, TokenBucket>>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: tokio::time::Instant,
+}
+
+impl<RT: Runtime> FunctionRateLimiter<RT> {
+    pub fn new(runtime: RT) -> Self {
+        Self {
+            runtime,
+            policies: Mutex::new(None),
+            buckets: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The cached policies, unless a write since they were read could have
+    /// changed them.
+    pub fn cached_policies(
+        &self,
+        log: &LogReader,
+    ) -> anyhow::Result<Option<Arc<Vec<RateLimitPolicy>>>> {
+        let mut cached = self.policies.lock();
+        let Some((token, policies)) = cached.take() else {
+            return Ok(None);
+        };
+        let Some(token) = log.refresh_reads_until_max_ts(token)? else {
+            return Ok(None);
+        };
+        *cached = Some((token, policies.clone()));
+        Ok(Some(policies))
+    }
+
+    /// Cache `policies`, which were read by the transaction for `token`.
+    pub fn cache_policies(
+        &self,
+        token: Token,
+        policies: Vec<RateLimitPolicy>,
+    ) -> Arc<Vec<RateLimitPolicy>> {
+        let policies = Arc::new(policies);
+        *self.policies.lock() = Some((token, policies.clone()));
+        policies
+    }
+
+    /// Count a call to `udf_path` against every policy that targets it,
+    /// failing with a rate limited error if any of them is exhausted. A
+    /// rejected call doesn't consume tokens from any policy. Admins and the
+    /// system aren't rate limited.
+    pub fn check(
+        &self,
+        policies: &[RateLimitPolicy],
+        udf_path: &CanonicalizedUdfPath,
+        identity: &Identity,
+        caller: &RateLimitCaller,
+    ) -> anyhow::Result<()> {
+        if Self::is_exempt(identity) {
+            return Ok(());
+        }
+        let now = self.runtime.monotonic_now();
+        let mut buckets = self.buckets.lock();
+        let deployed: BTreeSet<_> = policies.iter().collect();
+        buckets.retain(|policy, _| deployed.contains(policy));
+
+        let mut matching = vec![];
+        let mut exceeded: Option<(&RateLimitPolicy, Duration)> = None;
+        for policy in policies.iter().filter(|p| p.target.matches(udf_path)) {
+            let interval = policy.replenish_interval();
+            anyhow::ensure!(!interval.is_zero(), "Rate limit period must be nonzero");
+            let burst = f64::from(policy.burst());
+            let key = caller_key(&policy.key, identity, caller);
+            let bucket = buckets
+                .entry(policy.clone())
+                .or_default()
+                .entry(key.clone())
+                .or_insert(TokenBucket {
+                    tokens: burst,
+                    updated: now,
+                });
+            bucket.refill(now, interval, burst);
+            if bucket.tokens < 1.0 {
+                let retry_after = interval.mul_f64(1.0 - bucket.tokens);
+                if exceeded.is_none_or(|(_, longest)| retry_after > longest) {
+                    exceeded = Some((policy, retry_after));
+                }
+            }
+            matching.push((policy, key));
+        }
+
+        if let Some((policy, retry_after)) = exceeded {
+            let retry_after = retry_after.max(Duration::from_millis(1));
+            anyhow::bail!(ErrorMetadata::rate_limited_with_retry_after(
+                "RateLimited",
+                format!(
+                    "Rate limit exceeded for {}: at most {} calls per {}ms. Retry after {}ms.",
+                    policy.target,
+                    policy.requests,
+                    policy.period.as_millis(),
+                    retry_after.as_millis(),
+                ),
+                retry_after,
+            ));
+        }
+        for (policy, key) in matching {
+            let Some(policy_buckets) = buckets.get_mut(policy) else {
+                continue;
+            };
+            if let Some(bucket) = policy_buckets.get_mut(&key) {
+                bucket.tokens -= 1.0;
+            }
+            if policy_buckets.len() > *RATE_LIMIT_MAX_TRACKED_KEYS {
+                // A full bucket behaves the same as a new one, so forget it.
+                let interval = policy.replenish_interval();
+                let burst = f64::from(policy.burst());
+                policy_buckets.retain(|_, bucket| {
+                    bucket.refill(now, interval, burst);
+                    bucket.tokens < burst
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_exempt(identity: &Identity) -> bool {
+        identity.is_admin() || identity.is_system() || matches!(identity, Identity::ActingUser(..))
+    }
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: tokio::time::Instant, interval: Duration, burst: f64) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() / interval.as_secs_f64()).min(burst);
+        self.updated = now;
+    }
+}
+
+fn caller_key(key: &RateLimitKey, identity: &Identity, caller: &RateLimitCaller) -> String {
+    let ip_key = || match caller.ip {
+        Some(ip) => format!("ip:{ip}"),
+        None => "ip:unknown".to_string(),
+    };
+    match key {
+        RateLimitKey::Identity => match identity {
+            Identity::User(user) => format!("user:{}|{}", user.issuer, user.subject),
+            _ => ip_key(),
+        },
+        RateLimitKey::Ip => ip_key(),
+        RateLimitKey::Header(name) => {
+            let value = caller
+                .headers
+                .get(name.as_str())
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+                .unwrap_or_default();
+            format!("header:{value}")
+        },
+    }
+}
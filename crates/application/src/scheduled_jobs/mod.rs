@@ -143,6 +143,17 @@ impl ScheduledJobRunner {
         }
     }
 
+    /// Scheduled jobs are only executed and garbage collected by the leader,
+    /// so read replicas use a runner that does nothing.
+    pub fn disabled<RT: Runtime>(rt: RT) -> Self {
+        Self {
+            executor: Arc::new(Mutex::new(rt.spawn("scheduled_job_executor", async {}))),
+            garbage_collector: Arc::new(Mutex::new(
+                rt.spawn("scheduled_job_garbage_collector", async {}),
+            )),
+        }
+    }
+
     pub fn shutdown(&self) {
         self.executor.lock().shutdown();
         self.garbage_collector.lock().shutdown();
@@ -548,6 +559,11 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
             },
         };
 
+        // Scheduled functions count towards their component's usage, so retry
+        // them later while the component is over its quota.
+        self.runner
+            .check_component_usage_quota(tx.identity(), &path.component)?;
+
         // Note that we do validate that the scheduled function execute during
         // scheduling, but the modules can have been modified since scheduling.
         match udf_type {
@@ -0,0 +1,281 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use common::{
+    backoff::Backoff,
+    components::{
+        CanonicalizedComponentFunctionPath,
+        ComponentPath,
+        PublicFunctionPath,
+    },
+    document::ParsedDocument,
+    errors::report_error,
+    execution_context::{
+        ExecutionContext,
+        ExecutionId,
+    },
+    persistence::LatestDocument,
+    runtime::Runtime,
+    types::AllowedVisibility,
+    RequestId,
+};
+use database::{
+    Database,
+    IndexModel,
+    Transaction,
+};
+use errors::ErrorMetadataAnyhowExt;
+use futures::{
+    pin_mut,
+    Future,
+    TryStreamExt,
+};
+use keybroker::Identity;
+use model::schema_migrations::{
+    types::{
+        SchemaMigration,
+        SchemaMigrationState,
+    },
+    SchemaMigrationModel,
+};
+use usage_tracking::FunctionUsageTracker;
+use value::{
+    ConvexArray,
+    ConvexValue,
+    ResolvedDocumentId,
+    TableNamespace,
+};
+
+use crate::{
+    application_function_runner::ApplicationFunctionRunner,
+    metrics::log_worker_starting,
+};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Drives running schema migrations forward one batch at a time. Each batch
+/// runs the migration mutation on up to `batch_size` documents following the
+/// migration's cursor and advances the cursor in the same transaction, so a
+/// batch is either applied and checkpointed together or not at all.
+///
+/// Documents are visited in `_id` order from a fresh snapshot for every batch,
+/// so documents inserted behind the cursor while the migration is running are
+/// not revisited. Code writing to the table should already produce documents
+/// in the new shape before the migration starts.
+pub struct SchemaMigrationWorker<RT: Runtime> {
+    runtime: RT,
+    database: Database<RT>,
+    runner: Arc<ApplicationFunctionRunner<RT>>,
+}
+
+impl<RT: Runtime> SchemaMigrationWorker<RT> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        runtime: RT,
+        database: Database<RT>,
+        runner: Arc<ApplicationFunctionRunner<RT>>,
+    ) -> impl Future<Output = ()> + Send {
+        let worker = Self {
+            runtime,
+            database,
+            runner,
+        };
+        async move {
+            tracing::info!("Starting SchemaMigrationWorker");
+            let mut backoff = Backoff::new(INITIAL_BACKOFF, MAX_BACKOFF);
+            loop {
+                if let Err(e) = worker.run().await {
+                    let delay = backoff.fail(&mut worker.runtime.rng());
+                    report_error(&mut e.context("SchemaMigrationWorker died")).await;
+                    tracing::error!("Schema migration worker failed, sleeping {delay:?}");
+                    worker.runtime.wait(delay).await;
+                } else {
+                    backoff.reset();
+                }
+            }
+        }
+    }
+
+    /// Runs a single batch of the oldest running migration, or waits until a
+    /// migration starts running if there are none.
+    pub async fn run(&self) -> anyhow::Result<()> {
+        let status = log_worker_starting("SchemaMigrationWorker");
+        let mut tx = self
+            .database
+            .begin_with_usage(Identity::Unknown(None), FunctionUsageTracker::new())
+            .await?;
+        let Some(migration) = SchemaMigrationModel::new(&mut tx).next_running().await? else {
+            drop(status);
+            tracing::debug!("SchemaMigrationWorker waiting...");
+            let token = tx.into_token()?;
+            let subscription = self.database.subscribe(token).await?;
+            subscription.wait_for_invalidation().await;
+            return Ok(());
+        };
+        self.run_batch(tx, migration).await
+    }
+
+    async fn run_batch(
+        &self,
+        mut tx: Transaction<RT>,
+        migration: ParsedDocument<SchemaMigration>,
+    ) -> anyhow::Result<()> {
+        let (id, mut migration) = migration.into_id_and_value();
+        let table_mapping = tx
+            .table_mapping()
+            .namespace(TableNamespace::root_component());
+        let Ok(tablet_id) = table_mapping.name_to_tablet()(migration.table_name.clone()) else {
+            // The table was deleted, so there is nothing left to migrate.
+            migration.state = SchemaMigrationState::Completed;
+            SchemaMigrationModel::new(&mut tx)
+                .replace(id, migration)
+                .await?;
+            self.database
+                .commit_with_write_source(tx, "schema_migration_completed")
+                .await?;
+            return Ok(());
+        };
+        let by_id = IndexModel::new(&mut tx)
+            .by_id_index_metadata(tablet_id)
+            .await?
+            .id()
+            .internal_id();
+
+        let cursor = migration
+            .cursor
+            .map(|developer_id| ResolvedDocumentId::new(tablet_id, developer_id));
+        let stream = self
+            .database
+            .table_iterator(tx.begin_timestamp(), migration.batch_size as usize)
+            .stream_documents_in_table(tablet_id, by_id, cursor);
+        pin_mut!(stream);
+        let mut batch = Vec::new();
+        while let Some(LatestDocument { value: doc, .. }) = stream.try_next().await? {
+            batch.push(doc.id());
+            if batch.len() >= migration.batch_size as usize {
+                break;
+            }
+        }
+
+        let Some(last_id) = batch.last().copied() else {
+            tracing::info!("Schema migration of {} completed", migration.table_name);
+            migration.state = SchemaMigrationState::Completed;
+            SchemaMigrationModel::new(&mut tx)
+                .replace(id, migration)
+                .await?;
+            self.database
+                .commit_with_write_source(tx, "schema_migration_completed")
+                .await?;
+            return Ok(());
+        };
+
+        let (mut tx, result) = if migration.dry_run {
+            // Run the batch in a throwaway transaction and only commit progress.
+            let dry_run_tx = self
+                .database
+                .begin_with_usage(Identity::Unknown(None), FunctionUsageTracker::new())
+                .await?;
+            let (_, result) = self
+                .migrate_documents(dry_run_tx, &migration, &batch)
+                .await?;
+            (tx, result)
+        } else {
+            self.migrate_documents(tx, &migration, &batch).await?
+        };
+        let changed = match result {
+            Ok(changed) => changed,
+            Err(error) => return self.mark_failed(id, error).await,
+        };
+        migration.cursor = Some(last_id.developer_id);
+        migration.documents_processed += batch.len() as u64;
+        migration.documents_changed += changed;
+        SchemaMigrationModel::new(&mut tx)
+            .replace(id, migration)
+            .await?;
+        if let Err(e) = self
+            .database
+            .commit_with_write_source(tx, "schema_migration_batch")
+            .await
+        {
+            if e.is_deterministic_user_error() {
+                return self.mark_failed(id, e.to_string()).await;
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Runs the migration mutation on each document in `batch` that still
+    /// exists, returning how many of them it modified, or the error message if
+    /// the mutation failed on any of them.
+    async fn migrate_documents(
+        &self,
+        mut tx: Transaction<RT>,
+        migration: &SchemaMigration,
+        batch: &[ResolvedDocumentId],
+    ) -> anyhow::Result<(Transaction<RT>, Result<u64, String>)> {
+        let path = CanonicalizedComponentFunctionPath {
+            component: ComponentPath::root(),
+            udf_path: migration.udf_path.clone(),
+        };
+        let mut changed = 0;
+        for id in batch {
+            // The document may have been deleted since the batch was read.
+            let Some(before) = tx.get(*id).await? else {
+                continue;
+            };
+            let arguments =
+                ConvexArray::try_from(vec![ConvexValue::Object(before.clone().into_value().0)])?;
+            let context =
+                ExecutionContext::new_from_parts(RequestId::new(), ExecutionId::new(), None, true);
+            let (new_tx, outcome) = self
+                .runner
+                .run_mutation_no_udf_log(
+                    tx,
+                    PublicFunctionPath::Component(path.clone()),
+                    arguments,
+                    AllowedVisibility::All,
+                    context,
+                    None,
+                )
+                .await?;
+            tx = new_tx;
+            if let Err(e) = outcome.result {
+                return Ok((
+                    tx,
+                    Err(format!(
+                        "Failed to migrate document {}: {e}",
+                        id.developer_id
+                    )),
+                ));
+            }
+            if tx.get(*id).await?.as_ref() != Some(&before) {
+                changed += 1;
+            }
+        }
+        Ok((tx, Ok(changed)))
+    }
+
+    async fn mark_failed(&self, id: ResolvedDocumentId, error: String) -> anyhow::Result<()> {
+        tracing::info!("Schema migration failed: {error}");
+        let mut tx = self.database.begin(Identity::system()).await?;
+        let mut model = SchemaMigrationModel::new(&mut tx);
+        // An admin may have paused or canceled the migration in the meantime.
+        let Some(migration) = model.get(id.developer_id).await? else {
+            return Ok(());
+        };
+        let (id, mut migration) = migration.into_id_and_value();
+        if migration.state != SchemaMigrationState::Running {
+            return Ok(());
+        }
+        migration.state = SchemaMigrationState::Failed { error };
+        model.replace(id, migration).await?;
+        self.database
+            .commit_with_write_source(tx, "schema_migration_failed")
+            .await?;
+        Ok(())
+    }
+}
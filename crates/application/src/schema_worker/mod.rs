@@ -1,5 +1,9 @@
 use std::{
-    collections::BTreeMap,
+    collections::{
+        BTreeMap,
+        HashMap,
+        HashSet,
+    },
     sync::Arc,
     time::Duration,
 };
@@ -11,7 +15,10 @@ use common::{
     errors::report_error,
     persistence::LatestDocument,
     runtime::Runtime,
-    schemas::DatabaseSchema,
+    schemas::{
+        DatabaseSchema,
+        SchemaValidationError,
+    },
     types::{
         IndexId,
         RepeatableTimestamp,
@@ -37,7 +44,12 @@ use metrics::{
     log_document_validated,
     schema_validation_timer,
 };
+use model::schema_migrations::SchemaMigrationModel;
 use value::{
+    id_v6::DeveloperDocumentId,
+    values_to_bytes,
+    ConvexValue,
+    FieldName,
     NamespacedTableMapping,
     ResolvedDocumentId,
     TableNamespace,
@@ -97,12 +109,26 @@ impl<RT: Runtime> SchemaWorker<RT> {
         tx: &mut Transaction<RT>,
     ) -> anyhow::Result<Vec<PendingSchemaWork>> {
         let mut pending_schema_work = Vec::new();
+        // Reading the migrations here means the worker's subscription is
+        // invalidated when a migration finishes.
+        let migrating_tables = SchemaMigrationModel::new(tx).active_tables().await?;
         let namespaces: Vec<_> = tx.table_mapping().namespaces_for_name(&SCHEMAS_TABLE);
         for namespace in namespaces {
             if let Some((id, db_schema)) = SchemaModel::new(tx, namespace)
                 .get_by_state(SchemaState::Pending)
                 .await?
             {
+                if namespace == TableNamespace::root_component()
+                    && db_schema
+                        .tables
+                        .keys()
+                        .any(|table_name| migrating_tables.contains(table_name))
+                {
+                    tracing::debug!(
+                        "SchemaWorker found a pending schema waiting on a schema migration"
+                    );
+                    continue;
+                }
                 tracing::debug!("SchemaWorker found a pending schema and is validating it...");
                 let timer = schema_validation_timer();
                 let table_mapping = tx.table_mapping().namespace(namespace);
@@ -183,38 +209,113 @@ impl<RT: Runtime> SchemaWorker<RT> {
                         &table_mapping,
                         &virtual_system_mapping,
                     ) {
-                        let mut backoff = Backoff::new(INITIAL_COMMIT_BACKOFF, MAX_COMMIT_BACKOFF);
-                        while backoff.failures() < MAX_COMMIT_FAILURES {
-                            let mut tx = self.database.begin(Identity::system()).await?;
-                            SchemaModel::new(&mut tx, namespace)
-                                .mark_failed(id, schema_error.clone())
-                                .await?;
-                            if let Err(e) = self
-                                .database
-                                .commit_with_write_source(tx, "schema_worker_mark_failed")
-                                .await
-                            {
-                                if e.is_occ() {
-                                    let delay = backoff.fail(&mut self.runtime.rng());
-                                    tracing::error!(
-                                        "Schema worker failed to commit ({e}), retrying after \
-                                         {delay:?}"
-                                    );
-                                    self.runtime.wait(delay).await;
-                                } else {
-                                    return Err(e);
-                                }
-                            } else {
-                                break;
-                            }
-                        }
+                        self.mark_failed(namespace, id, schema_error).await?;
+                        tracing::info!("Schema is invalid");
+                        timer.finish_developer_error();
+                        return Ok(());
+                    }
+                }
+            }
 
+            // Existing documents must not violate any newly added unique constraints.
+            let persistence_version = self.database.persistence_version();
+            for (table_name, index) in
+                DatabaseSchema::unique_indexes_to_validate(&db_schema, active_schema.as_deref())
+            {
+                let Ok(tablet_id) = table_mapping.name_to_tablet()(table_name.clone()) else {
+                    continue;
+                };
+                let table_iterator = self.database.table_iterator(ts, 1000);
+                let stream = table_iterator.stream_documents_in_table(
+                    tablet_id,
+                    *by_id_indexes.get(&tablet_id).ok_or_else(|| {
+                        anyhow::anyhow!("Failed to find id index for table id {tablet_id}")
+                    })?,
+                    None,
+                );
+                pin_mut!(stream);
+                let mut seen = HashMap::new();
+                while let Some(LatestDocument { value: doc, .. }) = stream.try_next().await? {
+                    let index_key = doc.index_key(&index.fields[..], persistence_version);
+                    if index_key.indexed_values().iter().any(|v| v.is_none()) {
+                        continue;
+                    }
+                    let key = values_to_bytes(index_key.indexed_values());
+                    if let Some(first_id) = seen.insert(key, doc.developer_id()) {
+                        let schema_error = SchemaValidationError::DuplicateUniqueIndexKey {
+                            table_name: table_name.clone(),
+                            index_descriptor: index.index_descriptor.clone(),
+                            first_id,
+                            second_id: doc.developer_id(),
+                        };
+                        self.mark_failed(namespace, id, schema_error).await?;
                         tracing::info!("Schema is invalid");
                         timer.finish_developer_error();
                         return Ok(());
                     }
                 }
             }
+
+            // Existing documents must not reference missing documents through
+            // newly declared references.
+            for (table_name, reference) in
+                DatabaseSchema::references_to_validate(&db_schema, active_schema.as_deref())
+            {
+                let Ok(tablet_id) = table_mapping.name_to_tablet()(table_name.clone()) else {
+                    continue;
+                };
+                let mut referenced_ids = HashSet::new();
+                if let Ok(referenced_tablet_id) =
+                    table_mapping.name_to_tablet()(reference.referenced_table.clone())
+                {
+                    let table_iterator = self.database.table_iterator(ts, 1000);
+                    let stream = table_iterator.stream_documents_in_table(
+                        referenced_tablet_id,
+                        *by_id_indexes.get(&referenced_tablet_id).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Failed to find id index for table id {referenced_tablet_id}"
+                            )
+                        })?,
+                        None,
+                    );
+                    pin_mut!(stream);
+                    while let Some(LatestDocument { value: doc, .. }) = stream.try_next().await? {
+                        referenced_ids.insert(doc.developer_id());
+                    }
+                }
+                let table_iterator = self.database.table_iterator(ts, 1000);
+                let stream = table_iterator.stream_documents_in_table(
+                    tablet_id,
+                    *by_id_indexes.get(&tablet_id).ok_or_else(|| {
+                        anyhow::anyhow!("Failed to find id index for table id {tablet_id}")
+                    })?,
+                    None,
+                );
+                pin_mut!(stream);
+                while let Some(LatestDocument { value: doc, .. }) = stream.try_next().await? {
+                    let Some(ConvexValue::String(referenced_id)) =
+                        doc.value().get(&FieldName::from(reference.field.clone()))
+                    else {
+                        continue;
+                    };
+                    let exists = DeveloperDocumentId::decode(referenced_id)
+                        .is_ok_and(|referenced_id| referenced_ids.contains(&referenced_id));
+                    if !exists {
+                        let schema_error = SchemaValidationError::DanglingReference {
+                            table_name: table_name.clone(),
+                            id: doc.developer_id(),
+                            field: reference.field.clone(),
+                            referenced_table: reference.referenced_table.clone(),
+                            referenced_id: referenced_id.to_string(),
+                        };
+                        self.mark_failed(namespace, id, schema_error).await?;
+                        tracing::info!("Schema is invalid");
+                        timer.finish_developer_error();
+                        return Ok(());
+                    }
+                }
+            }
+
             let mut tx = self.database.begin(Identity::system()).await?;
             if let Err(error) = SchemaModel::new(&mut tx, namespace)
                 .mark_validated(id)
@@ -239,6 +340,39 @@ impl<RT: Runtime> SchemaWorker<RT> {
         subscription.wait_for_invalidation().await;
         Ok(())
     }
+
+    async fn mark_failed(
+        &self,
+        namespace: TableNamespace,
+        id: ResolvedDocumentId,
+        schema_error: SchemaValidationError,
+    ) -> anyhow::Result<()> {
+        let mut backoff = Backoff::new(INITIAL_COMMIT_BACKOFF, MAX_COMMIT_BACKOFF);
+        while backoff.failures() < MAX_COMMIT_FAILURES {
+            let mut tx = self.database.begin(Identity::system()).await?;
+            SchemaModel::new(&mut tx, namespace)
+                .mark_failed(id, schema_error.clone())
+                .await?;
+            if let Err(e) = self
+                .database
+                .commit_with_write_source(tx, "schema_worker_mark_failed")
+                .await
+            {
+                if e.is_occ() {
+                    let delay = backoff.fail(&mut self.runtime.rng());
+                    tracing::error!(
+                        "Schema worker failed to commit ({e}), retrying after {delay:?}"
+                    );
+                    self.runtime.wait(delay).await;
+                } else {
+                    return Err(e);
+                }
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -259,8 +393,12 @@ mod tests {
             },
             DatabaseSchema,
             DocumentSchema,
+            IndexSchema,
+            OnDeleteAction,
+            ReferenceSchema,
             TableDefinition,
         },
+        types::IndexDescriptor,
     };
     use database::{
         test_helpers::new_test_database,
@@ -270,7 +408,10 @@ mod tests {
     use keybroker::Identity;
     use maplit::btreemap;
     use runtime::testing::TestRuntime;
-    use value::TableName;
+    use value::{
+        IdentifierFieldName,
+        TableName,
+    };
 
     use super::SchemaWorker;
 
@@ -288,6 +429,7 @@ mod tests {
             indexes: btreemap! {},
             search_indexes: btreemap! {},
             vector_indexes: btreemap! {},
+            references: Default::default(),
             document_type: Some(DocumentSchema::Any),
         };
         let db_schema = DatabaseSchema {
@@ -331,4 +473,103 @@ mod tests {
         assert!(matches!(schema.state, SchemaState::Failed { .. }));
         Ok(())
     }
+
+    #[convex_macro::test_runtime]
+    async fn test_unique_index_validation(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = new_test_database(rt.clone()).await;
+        let schema_worker = SchemaWorker {
+            runtime: rt.clone(),
+            database: db.clone(),
+        };
+        let mut tx = db.begin(Identity::system()).await?;
+        let table_name = "users".parse::<TableName>()?;
+        for email in ["ada@example.com", "grace@example.com", "ada@example.com"] {
+            UserFacingModel::new_root_for_test(&mut tx)
+                .insert(table_name.clone(), assert_obj!("email" => email))
+                .await?;
+        }
+        let index_descriptor = IndexDescriptor::new("by_email")?;
+        let db_schema = DatabaseSchema {
+            tables: btreemap! {
+                table_name.clone() => TableDefinition {
+                    table_name: table_name.clone(),
+                    indexes: btreemap! {
+                        index_descriptor.clone() => IndexSchema {
+                            index_descriptor,
+                            fields: vec!["email".parse()?].try_into()?,
+                            unique: true,
+                        },
+                    },
+                    search_indexes: btreemap! {},
+                    vector_indexes: btreemap! {},
+                    references: Default::default(),
+                    document_type: Some(DocumentSchema::Any),
+                },
+            },
+            schema_validation: true,
+        };
+        let (id, _) = SchemaModel::new_root_for_test(&mut tx)
+            .submit_pending(db_schema)
+            .await?;
+        db.commit(tx).await?;
+
+        schema_worker.run().await?;
+        let mut tx = db.begin(Identity::system()).await?;
+        let doc = tx.get(id).await?.unwrap();
+        let schema: SchemaMetadata = doc.into_value().into_value().try_into()?;
+        let SchemaState::Failed { error, table_name } = &schema.state else {
+            panic!("Expected failed schema, got {:?}", schema.state);
+        };
+        assert!(error.contains("unique index \"by_email\""), "{error}");
+        assert_eq!(table_name.as_deref(), Some("users"));
+        Ok(())
+    }
+
+    #[convex_macro::test_runtime]
+    async fn test_dangling_reference_validation(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = new_test_database(rt.clone()).await;
+        let schema_worker = SchemaWorker {
+            runtime: rt.clone(),
+            database: db.clone(),
+        };
+        let mut tx = db.begin(Identity::system()).await?;
+        let users = "users".parse::<TableName>()?;
+        let posts = "posts".parse::<TableName>()?;
+        let mut model = UserFacingModel::new_root_for_test(&mut tx);
+        let ada = model.insert(users.clone(), assert_obj!()).await?;
+        let grace = model.insert(users.clone(), assert_obj!()).await?;
+        for author in [ada, grace] {
+            model
+                .insert(posts.clone(), assert_obj!("author" => author.encode()))
+                .await?;
+        }
+        model.delete(grace).await?;
+        let author: IdentifierFieldName = "author".parse()?;
+        let mut db_schema = db_schema!(
+            users.clone() => DocumentSchema::Any,
+            posts.clone() => DocumentSchema::Any,
+        );
+        db_schema.tables.get_mut(&posts).unwrap().references = btreemap! {
+            author.clone() => ReferenceSchema {
+                field: author,
+                referenced_table: users,
+                on_delete: OnDeleteAction::Restrict,
+            },
+        };
+        let (id, _) = SchemaModel::new_root_for_test(&mut tx)
+            .submit_pending(db_schema)
+            .await?;
+        db.commit(tx).await?;
+
+        schema_worker.run().await?;
+        let mut tx = db.begin(Identity::system()).await?;
+        let doc = tx.get(id).await?.unwrap();
+        let schema: SchemaMetadata = doc.into_value().into_value().try_into()?;
+        let SchemaState::Failed { error, table_name } = &schema.state else {
+            panic!("Expected failed schema, got {:?}", schema.state);
+        };
+        assert!(error.contains(&grace.encode()), "{error}");
+        assert_eq!(table_name.as_deref(), Some("posts"));
+        Ok(())
+    }
 }
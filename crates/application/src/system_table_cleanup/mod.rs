@@ -495,6 +495,7 @@ mod tests {
                             log_lines: vec![].into(),
                         },
                         identity: InertIdentity::System,
+                        function_path: None,
                     },
                     Identity::system(),
                 )
@@ -180,6 +180,44 @@ impl<RT: Runtime> TableSummaryWorker<RT> {
     }
 }
 
+impl<RT: Runtime> TableSummaryWorker<RT> {
+    /// Read replicas can't write table summary checkpoints. Instead they
+    /// bootstrap table summaries from the latest checkpoint written by the
+    /// leader and then keep them up to date by following the leader's
+    /// commits.
+    pub(crate) fn start_follower(runtime: RT, database: Database<RT>) -> TableSummaryClient {
+        let (cancel_sender, cancel_receiver) = oneshot::channel();
+        let runtime_ = runtime.clone();
+        let bootstrap = async move {
+            tracing::info!("Starting table summary bootstrap from the leader's checkpoint");
+            let cancel_fut = cancel_receiver.fuse();
+            pin_mut!(cancel_fut);
+            loop {
+                match database.finish_table_summary_bootstrap().await {
+                    Ok(()) => {
+                        tracing::info!("Table summary bootstrap finished");
+                        break;
+                    },
+                    Err(mut err) => report_error(&mut err).await,
+                }
+                let wait_fut = runtime_.wait(Duration::from_secs(10)).fuse();
+                pin_mut!(wait_fut);
+                select_biased! {
+                    _ = cancel_fut => break,
+                    _ = wait_fut => {},
+                }
+            }
+        };
+        let handle = runtime.spawn("table_summary_follower", bootstrap);
+        TableSummaryClient {
+            inner: Arc::new(Mutex::new(Some(Inner {
+                handle,
+                cancel_sender,
+            }))),
+        }
+    }
+}
+
 impl TableSummaryClient {
     pub async fn shutdown(&self) -> anyhow::Result<()> {
         let inner = { self.inner.lock().take() };
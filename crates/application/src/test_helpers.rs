@@ -112,6 +112,7 @@ use crate::{
     },
     log_visibility::RedactLogsToClient,
     scheduled_jobs::ScheduledJobContext,
+    usage_accounting::UsageAccounting,
     Application,
 };
 
@@ -123,6 +124,8 @@ pub struct ApplicationFixtureArgs {
     pub tp: Option<TestPersistence>,
     pub event_logger: Option<Arc<dyn UsageEventLogger>>,
     pub node_executor: Option<Arc<dyn NodeExecutor>>,
+    /// Track usage in memory so usage quotas can be set.
+    pub usage_accounting: bool,
 }
 
 impl ApplicationFixtureArgs {
@@ -273,6 +276,9 @@ impl<RT: Runtime> ApplicationTestExt<RT> for Application<RT> {
                 Arc::new(NullAccessTokenAuth),
             )),
             QueryCache::new(*UDF_CACHE_MAX_SIZE),
+            args.usage_accounting
+                .then(|| UsageAccounting::new(rt.clone())),
+            None,
         )
         .await?;
 
@@ -35,7 +35,10 @@ export default {
 "#;
     let application = Application::new_for_tests(&rt).await?;
     let mut tx = application.begin(Identity::system()).await?;
-    let user_environment_variables = EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+    let user_environment_variables =
+        EnvironmentVariablesModel::new(&mut tx, application.key_broker())
+            .get_all()
+            .await?;
     let system_env_var_overrides = system_env_var_overrides(&mut tx).await?;
     let config = Application::get_evaluated_auth_config(
         application.runner(),
@@ -50,6 +53,7 @@ export default {
         &ConfigFile {
             functions: "convex".to_owned(),
             auth_info: None,
+            rate_limits: None,
         },
     )
     .await
@@ -86,7 +90,10 @@ export default {
             "https://xkcd.example.com".to_string(),
         )
         .await?;
-    let user_environment_variables = EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+    let user_environment_variables =
+        EnvironmentVariablesModel::new(&mut tx, application.key_broker())
+            .get_all()
+            .await?;
     let system_env_var_overrides = system_env_var_overrides(&mut tx).await?;
     let config = Application::get_evaluated_auth_config(
         application.runner(),
@@ -101,6 +108,7 @@ export default {
         &ConfigFile {
             functions: "convex".to_owned(),
             auth_info: None,
+            rate_limits: None,
         },
     )
     .await
@@ -0,0 +1,80 @@
+use common::components::ComponentId;
+use keybroker::Identity;
+use model::{
+    config::types::{
+        ConfigFile,
+        ModuleConfig,
+    },
+    modules::ModuleModel,
+};
+use runtime::testing::TestRuntime;
+
+use crate::{
+    deploy_config::ModuleJson,
+    test_helpers::ApplicationTestExt as _,
+    Application,
+};
+
+fn module(path: &str) -> ModuleConfig {
+    ModuleJson {
+        environment: None,
+        source_map: None,
+        path: path.to_string(),
+        source: format!("// {path}"),
+    }
+    .try_into()
+    .unwrap()
+}
+
+async fn push(application: &Application<TestRuntime>, path: &str) -> anyhow::Result<()> {
+    application
+        .push_config_no_components(
+            Identity::system(),
+            ConfigFile {
+                auth_info: None,
+                rate_limits: None,
+                functions: "convex/".into(),
+            },
+            vec![module(path)],
+            "1.3939.3939".parse()?,
+            None,
+            None,
+        )
+        .await?;
+    Ok(())
+}
+
+async fn module_paths(application: &Application<TestRuntime>) -> anyhow::Result<Vec<String>> {
+    let mut tx = application.begin(Identity::system()).await?;
+    Ok(ModuleModel::new(&mut tx)
+        .get_application_metadata(ComponentId::Root)
+        .await?
+        .into_iter()
+        .map(|module| module.path.as_str().to_string())
+        .collect())
+}
+
+#[convex_macro::test_runtime]
+async fn test_rollback_code_version(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    push(&application, "first.js").await?;
+    push(&application, "second.js").await?;
+    assert_eq!(module_paths(&application).await?, vec!["second.js"]);
+
+    let (versions, active) = application.list_code_versions(Identity::system()).await?;
+    assert_eq!(versions.len(), 2);
+    assert_eq!(Some(versions[0].source_package_id), active);
+
+    let diff = application
+        .rollback_code_version(Identity::system(), versions[1].developer_id())
+        .await?;
+    assert_eq!(diff.module_diff.added, vec!["first.js"]);
+    assert_eq!(diff.module_diff.removed, vec!["second.js"]);
+    assert_eq!(module_paths(&application).await?, vec!["first.js"]);
+
+    // Rolling back doesn't record a new version, it re-activates the old one.
+    let (versions_after, active) = application.list_code_versions(Identity::system()).await?;
+    assert_eq!(versions_after.len(), 2);
+    assert_eq!(Some(versions[1].source_package_id), active);
+    Ok(())
+}
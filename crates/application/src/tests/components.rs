@@ -73,6 +73,7 @@ async fn run_component_function<RT: Runtime>(
             args,
             Identity::system(),
             FunctionCaller::Test,
+            None,
         )
         .boxed()
         .await
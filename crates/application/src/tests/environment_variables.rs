@@ -74,7 +74,7 @@ async fn test_env_variable_delete_and_create(rt: TestRuntime) -> anyhow::Result<
         )
         .await?;
 
-    EnvironmentVariablesModel::new(&mut tx)
+    EnvironmentVariablesModel::new(&mut tx, application.key_broker())
         .get(&name)
         .await?
         .unwrap()
@@ -90,7 +90,7 @@ async fn test_env_variable_delete_and_create(rt: TestRuntime) -> anyhow::Result<
         )
         .await?;
 
-    let after = EnvironmentVariablesModel::new(&mut tx)
+    let after = EnvironmentVariablesModel::new(&mut tx, application.key_broker())
         .get(&name)
         .await?
         .unwrap()
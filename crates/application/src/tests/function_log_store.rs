@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use common::runtime::Runtime;
+use runtime::testing::TestRuntime;
+use serde_json::json;
+
+use crate::function_log_store::{
+    ExecutionStatus,
+    FunctionLogFilter,
+    FunctionLogStore,
+    StoredFunctionExecution,
+};
+
+fn execution(
+    timestamp: f64,
+    identifier: &str,
+    request_id: &str,
+    error: Option<&str>,
+    log_line: &str,
+) -> StoredFunctionExecution {
+    StoredFunctionExecution {
+        timestamp,
+        udf_type: "Mutation".to_string(),
+        component_path: None,
+        identifier: identifier.to_string(),
+        request_id: request_id.to_string(),
+        execution_id: format!("{request_id}-execution"),
+        cached_result: false,
+        execution_time: 0.01,
+        error: error.map(|e| e.to_string()),
+        http_status: None,
+        log_lines: vec![json!({
+            "messages": [log_line],
+            "level": "LOG",
+            "timestamp": timestamp,
+            "isTruncated": false,
+        })],
+    }
+}
+
+async fn wait_for_entries(
+    rt: &TestRuntime,
+    store: &FunctionLogStore,
+    count: usize,
+) -> anyhow::Result<()> {
+    for _ in 0..100 {
+        if store.query(&FunctionLogFilter::default(), 100).await?.len() >= count {
+            return Ok(());
+        }
+        rt.wait(Duration::from_millis(50)).await;
+    }
+    anyhow::bail!("durable function log never reached {count} entries");
+}
+
+#[convex_macro::test_runtime]
+async fn test_query_function_log_store(rt: TestRuntime) -> anyhow::Result<()> {
+    let dir = tempfile::TempDir::new()?;
+    let store = FunctionLogStore::new(rt.clone(), dir.path().to_path_buf()).await?;
+    let now = rt.unix_timestamp().as_secs_f64();
+    store.append(execution(
+        now + 1.,
+        "messages.js:send",
+        "r1",
+        None,
+        "sent hello",
+    ));
+    store.append(execution(
+        now + 2.,
+        "messages.js:send",
+        "r2",
+        Some("Uncaught Error: too long"),
+        "sending",
+    ));
+    store.append(execution(
+        now + 3.,
+        "users.js:create",
+        "r3",
+        None,
+        "created Alice",
+    ));
+    wait_for_entries(&rt, &store, 3).await?;
+
+    // Newest first.
+    let all = store.query(&FunctionLogFilter::default(), 100).await?;
+    let request_ids: Vec<_> = all.iter().map(|e| e.request_id.as_str()).collect();
+    assert_eq!(request_ids, vec!["r3", "r2", "r1"]);
+
+    let limited = store.query(&FunctionLogFilter::default(), 1).await?;
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].request_id, "r3");
+
+    let by_function = store
+        .query(
+            &FunctionLogFilter {
+                identifier: Some("messages.js:send".to_string()),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(by_function.len(), 2);
+
+    let failures = store
+        .query(
+            &FunctionLogFilter {
+                status: Some(ExecutionStatus::Failure),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].request_id, "r2");
+
+    let in_range = store
+        .query(
+            &FunctionLogFilter {
+                start: Some(now + 1.5),
+                end: Some(now + 3.),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(in_range.len(), 1);
+    assert_eq!(in_range[0].request_id, "r2");
+
+    // Text search matches log lines and errors, case-insensitively.
+    let by_text = store
+        .query(
+            &FunctionLogFilter {
+                text: Some("alice".to_string()),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(by_text.len(), 1);
+    assert_eq!(by_text[0].request_id, "r3");
+    let by_error_text = store
+        .query(
+            &FunctionLogFilter {
+                text: Some("TOO LONG".to_string()),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(by_error_text.len(), 1);
+
+    // Executions survive reopening the store.
+    store.shutdown();
+    let reopened = FunctionLogStore::new(rt.clone(), dir.path().to_path_buf()).await?;
+    let by_request = reopened
+        .query(
+            &FunctionLogFilter {
+                request_id: Some("r1".to_string()),
+                ..Default::default()
+            },
+            100,
+        )
+        .await?;
+    assert_eq!(by_request.len(), 1);
+    assert_eq!(by_request[0].identifier, "messages.js:send");
+    Ok(())
+}
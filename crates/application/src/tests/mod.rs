@@ -2,22 +2,28 @@ mod airbyte_import;
 mod analyze;
 mod auth;
 mod auth_config;
+mod code_versions;
 pub mod components;
 mod cron_jobs;
 mod environment_variables;
 mod fivetran_import;
+mod function_log_store;
 mod http_action;
 mod indexes;
 mod mutation;
 mod occ_retries;
 mod push;
 mod query_cache;
+mod rate_limits;
 mod returns_validation;
 mod scheduled_jobs;
 mod schema;
+mod schema_migrations;
 mod source_package;
 mod storage;
 mod streaming_export;
+mod udf_prometheus;
+mod usage_accounting;
 
 const NODE_SOURCE: &str = r#"
 var nodeFunction = () => {};
@@ -49,6 +49,7 @@ async fn test_max_size_push(rt: TestRuntime) -> anyhow::Result<()> {
                 },
                 component_definitions: vec![],
                 node_dependencies: vec![],
+                rate_limits: vec![],
             })
             .await?;
     }
@@ -64,6 +65,7 @@ async fn test_max_size_push_no_components(rt: TestRuntime) -> anyhow::Result<()>
                 Identity::system(),
                 ConfigFile {
                     auth_info: None,
+                    rate_limits: None,
                     functions: "convex/".into(),
                 },
                 make_modules()
@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use errors::ErrorMetadataAnyhowExt;
+use http::{
+    HeaderMap,
+    HeaderValue,
+};
+use keybroker::{
+    testing::TestUserIdentity,
+    Identity,
+    UserIdentity,
+};
+use model::{
+    config::types::ConfigFile,
+    rate_limits::{
+        types::{
+            SerializedRateLimitKey,
+            SerializedRateLimitPolicy,
+        },
+        RateLimitModel,
+    },
+};
+use runtime::testing::TestRuntime;
+
+use crate::{
+    deploy_config::ModuleJson,
+    rate_limiter::RateLimitCaller,
+    test_helpers::ApplicationTestExt as _,
+    Application,
+};
+
+async fn push_rate_limits(
+    application: &Application<TestRuntime>,
+    rate_limits: Vec<SerializedRateLimitPolicy>,
+) -> anyhow::Result<()> {
+    let module = ModuleJson {
+        environment: None,
+        source_map: None,
+        path: "messages.js".to_string(),
+        source: "// messages.js".to_string(),
+    }
+    .try_into()?;
+    application
+        .push_config_no_components(
+            Identity::system(),
+            ConfigFile {
+                functions: "convex/".into(),
+                auth_info: None,
+                rate_limits: Some(rate_limits),
+            },
+            vec![module],
+            "1.3939.3939".parse()?,
+            None,
+            None,
+        )
+        .await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_rate_limits(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    push_rate_limits(
+        &application,
+        vec![
+            SerializedRateLimitPolicy {
+                function: Some("messages:send".to_string()),
+                module: None,
+                key: SerializedRateLimitKey::Identity,
+                requests: 2,
+                period_ms: 60_000,
+                burst: None,
+            },
+            SerializedRateLimitPolicy {
+                function: None,
+                module: Some("messages".to_string()),
+                key: SerializedRateLimitKey::Header {
+                    name: "X-Api-Key".to_string(),
+                },
+                requests: 1,
+                period_ms: 60_000,
+                burst: Some(3),
+            },
+        ],
+    )
+    .await?;
+    let mut tx = application.begin(Identity::system()).await?;
+    assert_eq!(RateLimitModel::new(&mut tx).get_all().await?.len(), 2);
+
+    let user = Identity::user(UserIdentity::test());
+    let caller_with_key = |key: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static(key));
+        RateLimitCaller { ip: None, headers }
+    };
+    let send = "messages:send".parse()?;
+    let list = "messages:list".parse()?;
+
+    let caller = caller_with_key("send");
+    application
+        .check_function_rate_limits(&user, &send, &caller)
+        .await?;
+    application
+        .check_function_rate_limits(&user, &send, &caller)
+        .await?;
+    let err = application
+        .check_function_rate_limits(&user, &send, &caller)
+        .await
+        .unwrap_err();
+    assert!(err.is_rate_limited(), "{err:?}");
+    assert!(err.msg().contains("Retry after"), "{err:?}");
+    let retry_after = err.retry_after().context("Missing retry after")?;
+    assert!(retry_after > Duration::ZERO && retry_after <= Duration::from_secs(30));
+
+    // Admins aren't rate limited.
+    application
+        .check_function_rate_limits(&Identity::system(), &send, &caller)
+        .await?;
+
+    // Each header value gets its own bucket for the module's policy, and
+    // callers without the header share one.
+    for caller in [RateLimitCaller::default(), caller_with_key("list")] {
+        for _ in 0..3 {
+            application
+                .check_function_rate_limits(&user, &list, &caller)
+                .await?;
+        }
+        assert!(application
+            .check_function_rate_limits(&user, &list, &caller)
+            .await
+            .unwrap_err()
+            .is_rate_limited());
+    }
+
+    // Removing the policies lifts the limits.
+    push_rate_limits(&application, vec![]).await?;
+    application
+        .check_function_rate_limits(&user, &send, &caller_with_key("send"))
+        .await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_rejected_call_consumes_no_tokens(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    push_rate_limits(
+        &application,
+        vec![
+            SerializedRateLimitPolicy {
+                function: None,
+                module: Some("messages".to_string()),
+                key: SerializedRateLimitKey::Identity,
+                requests: 3,
+                period_ms: 60_000,
+                burst: None,
+            },
+            SerializedRateLimitPolicy {
+                function: Some("messages:send".to_string()),
+                module: None,
+                key: SerializedRateLimitKey::Header {
+                    name: "X-Api-Key".to_string(),
+                },
+                requests: 1,
+                period_ms: 60_000,
+                burst: None,
+            },
+        ],
+    )
+    .await?;
+
+    let user = Identity::user(UserIdentity::test());
+    let caller_with_key = |key: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static(key));
+        RateLimitCaller { ip: None, headers }
+    };
+    let send = "messages:send".parse()?;
+    let list = "messages:list".parse()?;
+
+    application
+        .check_function_rate_limits(&user, &send, &caller_with_key("a"))
+        .await?;
+    // The per-key policy rejects this call, so it shouldn't count against the
+    // module's policy either.
+    for _ in 0..3 {
+        assert!(application
+            .check_function_rate_limits(&user, &send, &caller_with_key("a"))
+            .await
+            .unwrap_err()
+            .is_rate_limited());
+    }
+    application
+        .check_function_rate_limits(&user, &send, &caller_with_key("b"))
+        .await?;
+    application
+        .check_function_rate_limits(&user, &list, &RateLimitCaller::default())
+        .await?;
+    assert!(application
+        .check_function_rate_limits(&user, &list, &RateLimitCaller::default())
+        .await
+        .unwrap_err()
+        .is_rate_limited());
+    Ok(())
+}
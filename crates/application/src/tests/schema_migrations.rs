@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use common::{
+    assert_obj,
+    query::{
+        Order,
+        Query,
+    },
+};
+use database::{
+    ResolvedQuery,
+    UserFacingModel,
+};
+use keybroker::Identity;
+use model::schema_migrations::types::{
+    SchemaMigration,
+    SchemaMigrationState,
+};
+use runtime::testing::TestRuntime;
+use value::{
+    id_v6::DeveloperDocumentId,
+    ConvexObject,
+    ConvexValue,
+    TableNamespace,
+};
+
+use crate::{
+    test_helpers::{
+        ApplicationTestExt,
+        OBJECTS_TABLE,
+    },
+    Application,
+};
+
+async fn insert_objects(application: &Application<TestRuntime>, n: usize) -> anyhow::Result<()> {
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = UserFacingModel::new(&mut tx, TableNamespace::test_user());
+    for i in 0..n {
+        model
+            .insert(OBJECTS_TABLE.clone(), assert_obj!("index" => i as f64))
+            .await?;
+    }
+    application.commit_test(tx).await?;
+    Ok(())
+}
+
+async fn objects(application: &Application<TestRuntime>) -> anyhow::Result<Vec<ConvexObject>> {
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut query_stream = ResolvedQuery::new(
+        &mut tx,
+        TableNamespace::test_user(),
+        Query::full_table_scan(OBJECTS_TABLE.clone(), Order::Asc),
+    )?;
+    let mut objects = vec![];
+    while let Some(doc) = query_stream.next(&mut tx, None).await? {
+        objects.push(doc.into_value().0);
+    }
+    Ok(objects)
+}
+
+/// Let the schema migration worker run until the migration stops running.
+async fn wait_for_migration(
+    rt: &TestRuntime,
+    application: &Application<TestRuntime>,
+    id: DeveloperDocumentId,
+) -> anyhow::Result<SchemaMigration> {
+    for _ in 0..100 {
+        let migration = application
+            .list_schema_migrations(Identity::system())
+            .await?
+            .into_iter()
+            .find(|migration| migration.developer_id() == id)
+            .unwrap()
+            .into_value();
+        if migration.state != SchemaMigrationState::Running {
+            return Ok(migration);
+        }
+        rt.wait(Duration::from_secs(1)).await;
+    }
+    anyhow::bail!("Schema migration did not finish");
+}
+
+#[convex_macro::test_runtime]
+async fn test_schema_migration(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+    insert_objects(&application, 5).await?;
+
+    let id = application
+        .start_schema_migration(
+            Identity::system(),
+            OBJECTS_TABLE.clone(),
+            "migrations:addCounter".parse()?,
+            Some(2),
+            false,
+        )
+        .await?;
+    let migration = wait_for_migration(&rt, &application, id).await?;
+    assert_eq!(migration.state, SchemaMigrationState::Completed);
+    assert_eq!(migration.documents_processed, 5);
+    assert_eq!(migration.documents_changed, 5);
+    for object in objects(&application).await? {
+        assert_eq!(object.get("counter"), Some(&ConvexValue::from(0.)));
+    }
+
+    // Running it again doesn't change anything.
+    let id = application
+        .start_schema_migration(
+            Identity::system(),
+            OBJECTS_TABLE.clone(),
+            "migrations:addCounter".parse()?,
+            None,
+            false,
+        )
+        .await?;
+    let migration = wait_for_migration(&rt, &application, id).await?;
+    assert_eq!(migration.state, SchemaMigrationState::Completed);
+    assert_eq!(migration.documents_processed, 5);
+    assert_eq!(migration.documents_changed, 0);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_schema_migration_dry_run(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+    insert_objects(&application, 5).await?;
+
+    let id = application
+        .start_schema_migration(
+            Identity::system(),
+            OBJECTS_TABLE.clone(),
+            "migrations:addCounter".parse()?,
+            Some(2),
+            true,
+        )
+        .await?;
+    let migration = wait_for_migration(&rt, &application, id).await?;
+    assert_eq!(migration.state, SchemaMigrationState::Completed);
+    assert_eq!(migration.documents_changed, 5);
+    for object in objects(&application).await? {
+        assert_eq!(object.get("counter"), None);
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_schema_migration_failure(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+    insert_objects(&application, 5).await?;
+
+    let id = application
+        .start_schema_migration(
+            Identity::system(),
+            OBJECTS_TABLE.clone(),
+            "migrations:failOnOdd".parse()?,
+            Some(5),
+            false,
+        )
+        .await?;
+    let migration = wait_for_migration(&rt, &application, id).await?;
+    let SchemaMigrationState::Failed { error } = &migration.state else {
+        panic!("Expected failed migration, got {:?}", migration.state);
+    };
+    assert!(error.contains("Can't migrate document"), "{error}");
+    // The failing batch is not committed.
+    assert_eq!(migration.documents_processed, 0);
+    for object in objects(&application).await? {
+        assert_eq!(object.get("counter"), None);
+    }
+    Ok(())
+}
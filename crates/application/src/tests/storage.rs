@@ -31,6 +31,7 @@ use crate::{
         ApplicationApi,
         ExecuteQueryTimestamp,
     },
+    rate_limiter::RateLimitCaller,
     test_helpers::ApplicationTestExt,
     Application,
 };
@@ -124,6 +125,7 @@ async fn test_storage_get_url(rt: TestRuntime) -> anyhow::Result<()> {
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
         )
         .await??;
     must_let!(let ConvexValue::String(url) = action_result.value.unpack());
@@ -170,6 +172,7 @@ async fn test_storage_get_url(rt: TestRuntime) -> anyhow::Result<()> {
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
         )
         .await??;
     must_let!(let ConvexValue::String(url) = action_result.value.unpack());
@@ -205,6 +208,7 @@ async fn test_storage_generate_upload_url(rt: TestRuntime) -> anyhow::Result<()>
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
             None,
             None,
         )
@@ -224,6 +228,7 @@ async fn test_storage_generate_upload_url(rt: TestRuntime) -> anyhow::Result<()>
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
         )
         .await??;
     must_let!(let ConvexValue::String(url) = action_result.value.unpack());
@@ -252,6 +257,7 @@ async fn test_storage_generate_upload_url(rt: TestRuntime) -> anyhow::Result<()>
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
             None,
             None,
         )
@@ -271,6 +277,7 @@ async fn test_storage_generate_upload_url(rt: TestRuntime) -> anyhow::Result<()>
             },
             args.clone(),
             caller.clone(),
+            &RateLimitCaller::default(),
         )
         .await??;
     must_let!(let ConvexValue::String(url) = action_result.value.unpack());
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use common::types::UdfType;
+
+use crate::udf_prometheus::UdfPrometheusMetrics;
+
+#[test]
+fn test_prometheus_function_metrics() -> anyhow::Result<()> {
+    let mut metrics = UdfPrometheusMetrics::new_with_max_functions(2)?;
+    metrics.record(
+        None,
+        "messages.js:list".to_string(),
+        UdfType::Query,
+        false,
+        Some(true),
+        Duration::from_millis(3),
+    );
+    metrics.record(
+        None,
+        "messages.js:list".to_string(),
+        UdfType::Query,
+        false,
+        Some(false),
+        Duration::from_millis(20),
+    );
+    metrics.record(
+        Some("chat".to_string()),
+        "messages.js:send".to_string(),
+        UdfType::Mutation,
+        true,
+        None,
+        Duration::from_millis(40),
+    );
+    // Beyond the cardinality limit.
+    metrics.record(
+        None,
+        "users.js:create".to_string(),
+        UdfType::Mutation,
+        false,
+        None,
+        Duration::from_millis(40),
+    );
+
+    let output = metrics.encode()?;
+    assert!(output.contains(
+        r#"convex_function_invocations_total{component="",function="messages.js:list",udf_type="query"} 2"#
+    ));
+    assert!(output.contains(
+        r#"convex_function_cache_hits_total{component="",function="messages.js:list",udf_type="query"} 1"#
+    ));
+    assert!(output.contains(
+        r#"convex_function_cache_misses_total{component="",function="messages.js:list",udf_type="query"} 1"#
+    ));
+    assert!(output.contains(
+        r#"convex_function_errors_total{component="chat",function="messages.js:send",udf_type="mutation"} 1"#
+    ));
+    assert!(output.contains(
+        r#"convex_function_execution_seconds_bucket{component="",function="messages.js:list",udf_type="query",le="0.005"} 1"#
+    ));
+    assert!(!output.contains("users.js:create"));
+    assert!(output.contains(
+        r#"convex_function_invocations_total{component="_other",function="_other",udf_type="mutation"} 1"#
+    ));
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use common::{
+    components::{
+        CanonicalizedComponentFunctionPath,
+        ComponentPath,
+    },
+    execution_context::ExecutionContext,
+    pause::PauseController,
+    runtime::Runtime,
+};
+use database::{
+    test_helpers::DbFixtures,
+    TableModel,
+};
+use errors::ErrorMetadataAnyhowExt;
+use events::usage::{
+    FunctionCallUsageFields,
+    UsageEvent,
+    UsageEventLogger,
+};
+use keybroker::Identity;
+use model::{
+    scheduled_jobs::{
+        types::ScheduledJobState,
+        SchedulerModel,
+    },
+    test_helpers::DbFixturesWithModel,
+    usage::{
+        types::UsageQuota,
+        UsageModel,
+    },
+};
+use runtime::testing::TestRuntime;
+use serde_json::json;
+use udf::helpers::parse_udf_args;
+use value::TableNamespace;
+
+use crate::{
+    scheduled_jobs::SCHEDULED_JOB_EXECUTED,
+    test_helpers::{
+        ApplicationFixtureArgs,
+        ApplicationTestExt,
+        OBJECTS_TABLE,
+        OBJECTS_TABLE_COMPONENT,
+    },
+    usage_accounting::{
+        summarize_usage,
+        UsageAccounting,
+        UsageGroupBy,
+    },
+    Application,
+};
+
+fn function_call(udf_id: &str, status: &str) -> UsageEvent {
+    UsageEvent::FunctionCall {
+        fields: FunctionCallUsageFields {
+            id: "id".to_string(),
+            request_id: "request_id".to_string(),
+            status: status.to_string(),
+            component_path: None,
+            udf_id: udf_id.to_string(),
+            udf_id_type: "function".to_string(),
+            tag: "mutation".to_string(),
+            memory_megabytes: 0,
+            duration_millis: 10,
+            environment: "isolate".to_string(),
+            is_tracked: true,
+            response_sha256: None,
+            is_occ: false,
+            occ_table_name: None,
+            occ_document_id: None,
+            occ_write_source: None,
+            occ_retry_count: None,
+        },
+    }
+}
+
+fn database_bandwidth(udf_id: &str, table_name: &str, egress: u64) -> UsageEvent {
+    UsageEvent::DatabaseBandwidth {
+        id: "id".to_string(),
+        request_id: "request_id".to_string(),
+        component_path: None,
+        udf_id: udf_id.to_string(),
+        table_name: table_name.to_string(),
+        ingress: 0,
+        egress,
+        egress_rows: 1,
+    }
+}
+
+#[convex_macro::test_runtime]
+async fn test_flush_and_summarize(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, .. } = DbFixtures::new_with_model(&rt).await?;
+    let accounting = UsageAccounting::new(rt.clone());
+    accounting
+        .record_async(vec![
+            function_call("a.js:f", "success"),
+            function_call("a.js:f", "failure"),
+            function_call("b.js:g", "success"),
+            database_bandwidth("a.js:f", "messages", 100),
+            database_bandwidth("b.js:g", "messages", 50),
+        ])
+        .await;
+    accounting.flush(&db).await?;
+    // Flushing again is a no-op since all usage has been written.
+    accounting.flush(&db).await?;
+
+    let mut tx = db.begin(Identity::system()).await?;
+    let rollups: Vec<_> = UsageModel::new(&mut tx)
+        .rollups_in_range(0, i64::MAX)
+        .await?
+        .into_iter()
+        .map(|rollup| rollup.into_value())
+        .collect();
+
+    let by_function = summarize_usage(rollups.clone().into_iter(), UsageGroupBy::Function);
+    assert_eq!(by_function.len(), 2);
+    assert_eq!(by_function[0].udf_id.as_deref(), Some("a.js:f"));
+    assert_eq!(by_function[0].metrics.function_calls, 2);
+    assert_eq!(by_function[0].metrics.function_failures, 1);
+    assert_eq!(by_function[0].metrics.database_egress_bytes, 100);
+
+    let by_table = summarize_usage(rollups.clone().into_iter(), UsageGroupBy::Table);
+    assert_eq!(by_table.len(), 1);
+    assert_eq!(by_table[0].table_name.as_deref(), Some("messages"));
+    assert_eq!(by_table[0].metrics.database_egress_bytes, 150);
+
+    let by_component = summarize_usage(rollups.into_iter(), UsageGroupBy::Component);
+    assert_eq!(by_component.len(), 1);
+    assert_eq!(by_component[0].component_path, None);
+    assert_eq!(by_component[0].metrics.function_calls, 3);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_quota_exceeded(rt: TestRuntime) -> anyhow::Result<()> {
+    let accounting = UsageAccounting::new(rt.clone());
+    accounting.update_quota(UsageQuota {
+        component_path: None,
+        max_function_calls_per_day: Some(2),
+        max_database_bandwidth_bytes_per_day: None,
+    });
+    let root = ComponentPath::root();
+
+    accounting
+        .record_async(vec![function_call("a.js:f", "success")])
+        .await;
+    accounting.check_quota(&root)?;
+
+    accounting
+        .record_async(vec![function_call("a.js:f", "success")])
+        .await;
+    let err = accounting.check_quota(&root).unwrap_err();
+    assert_eq!(err.short_msg(), "UsageQuotaExceeded");
+
+    // Removing the quota allows calls again.
+    accounting.update_quota(UsageQuota {
+        component_path: None,
+        max_function_calls_per_day: None,
+        max_database_bandwidth_bytes_per_day: None,
+    });
+    accounting.check_quota(&root)?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_scheduled_job_deferred_over_quota(
+    rt: TestRuntime,
+    pause_controller: PauseController,
+) -> anyhow::Result<()> {
+    let application = Application::new_for_tests_with_args(
+        &rt,
+        ApplicationFixtureArgs {
+            usage_accounting: true,
+            ..Default::default()
+        },
+    )
+    .await?;
+    application.load_udf_tests_modules().await?;
+    application
+        .set_usage_quota(
+            Identity::system(),
+            UsageQuota {
+                component_path: None,
+                max_function_calls_per_day: Some(0),
+                max_database_bandwidth_bytes_per_day: None,
+            },
+        )
+        .await?;
+
+    let hold_guard = pause_controller.hold(SCHEDULED_JOB_EXECUTED);
+    let mut tx = application.begin(Identity::system()).await?;
+    let path = CanonicalizedComponentFunctionPath {
+        component: ComponentPath::test_user(),
+        udf_path: "basic:insertObject".parse()?,
+    };
+    let job_id = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .schedule(
+            path.clone(),
+            parse_udf_args(&path.udf_path, vec![json!({})])?,
+            rt.unix_timestamp(),
+            ExecutionContext::new_for_test(),
+        )
+        .await?;
+    application.commit_test(tx).await?;
+
+    // The job isn't run while its component is over quota, and is retried
+    // later instead of failing.
+    let pause_guard = hold_guard.wait_for_blocked().await.unwrap();
+    let mut tx = application.begin(Identity::system()).await?;
+    let job = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .list()
+        .await?
+        .into_iter()
+        .find(|job| job.id() == job_id)
+        .unwrap()
+        .into_value();
+    assert_eq!(job.state, ScheduledJobState::Pending);
+    assert_eq!(job.attempts.system_errors, 1);
+    assert!(
+        TableModel::new(&mut tx)
+            .table_is_empty(OBJECTS_TABLE_COMPONENT.into(), &OBJECTS_TABLE)
+            .await?
+    );
+    pause_guard.unpause();
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Per-function Prometheus metrics, exported alongside the in-memory
+//! [`udf_metrics::MetricStore`] that backs the dashboard.
+//!
+//! The metric store only keeps a sliding window of buckets, so it can't serve
+//! Prometheus counters, which must be monotonic for the lifetime of the
+//! process. Instead, each execution recorded into the metric store is also
+//! recorded here into cumulative counters and latency histograms labeled by
+//! component, function and UDF type.
+use std::{
+    collections::HashSet,
+    time::Duration,
+};
+
+use common::{
+    knobs::UDF_PROMETHEUS_MAX_FUNCTIONS,
+    types::UdfType,
+};
+use metrics::prometheus::{
+    HistogramOpts,
+    HistogramVec,
+    IntCounterVec,
+    Opts,
+    Registry,
+    TextEncoder,
+};
+
+/// Series for functions beyond the cardinality limit are folded into this
+/// label value.
+pub const OTHER_FUNCTIONS_LABEL: &str = "_other";
+
+const LABELS: &[&str] = &["component", "function", "udf_type"];
+
+const EXECUTION_TIME_BUCKETS_SECONDS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0,
+];
+
+pub struct UdfPrometheusMetrics {
+    registry: Registry,
+    invocations: IntCounterVec,
+    errors: IntCounterVec,
+    cache_hits: IntCounterVec,
+    cache_misses: IntCounterVec,
+    execution_time: HistogramVec,
+
+    /// `(component, function)` pairs that have their own series.
+    functions: HashSet<(String, String)>,
+    max_functions: usize,
+}
+
+impl UdfPrometheusMetrics {
+    pub fn new() -> anyhow::Result<Self> {
+        Self::new_with_max_functions(*UDF_PROMETHEUS_MAX_FUNCTIONS)
+    }
+
+    pub fn new_with_max_functions(max_functions: usize) -> anyhow::Result<Self> {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str| -> anyhow::Result<IntCounterVec> {
+            let counter = IntCounterVec::new(Opts::new(name, help), LABELS)?;
+            registry.register(Box::new(counter.clone()))?;
+            Ok(counter)
+        };
+        let invocations = counter(
+            "convex_function_invocations_total",
+            "Number of function executions",
+        )?;
+        let errors = counter(
+            "convex_function_errors_total",
+            "Number of function executions that failed",
+        )?;
+        let cache_hits = counter(
+            "convex_function_cache_hits_total",
+            "Number of query executions served from the query cache",
+        )?;
+        let cache_misses = counter(
+            "convex_function_cache_misses_total",
+            "Number of query executions that missed the query cache",
+        )?;
+        let execution_time = HistogramVec::new(
+            HistogramOpts::new(
+                "convex_function_execution_seconds",
+                "Function execution time in seconds",
+            )
+            .buckets(EXECUTION_TIME_BUCKETS_SECONDS.to_vec()),
+            LABELS,
+        )?;
+        registry.register(Box::new(execution_time.clone()))?;
+        Ok(Self {
+            registry,
+            invocations,
+            errors,
+            cache_hits,
+            cache_misses,
+            execution_time,
+            functions: HashSet::new(),
+            max_functions,
+        })
+    }
+
+    /// Record a single execution. `cached_result` is only set for queries.
+    pub fn record(
+        &mut self,
+        component: Option<String>,
+        function: String,
+        udf_type: UdfType,
+        is_error: bool,
+        cached_result: Option<bool>,
+        execution_time: Duration,
+    ) {
+        let component = component.unwrap_or_default();
+        let key = (component, function);
+        let (component, function) = if self.functions.contains(&key) {
+            (key.0.as_str(), key.1.as_str())
+        } else if self.functions.len() < self.max_functions {
+            self.functions.insert(key.clone());
+            (key.0.as_str(), key.1.as_str())
+        } else {
+            (OTHER_FUNCTIONS_LABEL, OTHER_FUNCTIONS_LABEL)
+        };
+        let labels = [component, function, udf_type.to_lowercase_string()];
+
+        self.invocations.with_label_values(&labels).inc();
+        if is_error {
+            self.errors.with_label_values(&labels).inc();
+        }
+        match cached_result {
+            Some(true) => self.cache_hits.with_label_values(&labels).inc(),
+            Some(false) => self.cache_misses.with_label_values(&labels).inc(),
+            None => {},
+        }
+        self.execution_time
+            .with_label_values(&labels)
+            .observe(execution_time.as_secs_f64());
+    }
+
+    /// Render all series in the Prometheus text exposition format.
+    pub fn encode(&self) -> anyhow::Result<String> {
+        let encoder = TextEncoder::new();
+        Ok(encoder.encode_to_string(&self.registry.gather())?)
+    }
+}
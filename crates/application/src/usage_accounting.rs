@@ -0,0 +1,540 @@
+//! Built-in usage accounting for self-hosted deployments.
+//!
+//! [`UsageAccounting`] is a [`UsageEventLogger`] that aggregates usage events
+//! in memory into hourly buckets, which [`UsageAccountingWorker`] periodically
+//! writes into the `_usage_rollups` system table. It also keeps a running
+//! total of each component's usage for the current UTC day so that calls can
+//! be rejected once a component exceeds its quota in `_usage_quotas`.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use common::{
+    components::ComponentPath,
+    errors::report_error,
+    knobs::{
+        USAGE_ROLLUP_FLUSH_INTERVAL,
+        USAGE_ROLLUP_RETENTION,
+    },
+    runtime::Runtime,
+};
+use database::Database;
+use errors::ErrorMetadata;
+use events::usage::{
+    UsageEvent,
+    UsageEventLogger,
+};
+use futures::Future;
+use keybroker::Identity;
+use model::usage::{
+    types::{
+        UsageMetrics,
+        UsageQuota,
+        UsageRollup,
+        UsageRollupKey,
+        SECONDS_PER_DAY,
+    },
+    UsageModel,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+/// Maximum number of rollups written or deleted in a single transaction.
+const ROLLUP_CHUNK_SIZE: usize = 256;
+
+#[derive(Default)]
+struct UsageAccountingInner {
+    /// Usage recorded since the last flush to `_usage_rollups`.
+    pending: BTreeMap<UsageRollupKey, UsageMetrics>,
+    /// Start of the UTC day that `daily` covers.
+    day_start: i64,
+    /// Usage per component for the current UTC day, including usage that has
+    /// already been flushed.
+    daily: BTreeMap<Option<String>, UsageMetrics>,
+    quotas: BTreeMap<Option<String>, UsageQuota>,
+}
+
+impl UsageAccountingInner {
+    fn roll_day(&mut self, day_start: i64) {
+        if self.day_start != day_start {
+            self.day_start = day_start;
+            self.daily.clear();
+        }
+    }
+
+    fn add(&mut self, key: UsageRollupKey, metrics: UsageMetrics) {
+        self.pending.entry(key).or_default().merge(&metrics);
+    }
+
+    fn add_daily(&mut self, component_path: &Option<String>, metrics: &UsageMetrics) {
+        self.daily
+            .entry(component_path.clone())
+            .or_default()
+            .merge(metrics);
+    }
+}
+
+#[derive(Clone)]
+pub struct UsageAccounting<RT: Runtime> {
+    runtime: RT,
+    inner: Arc<Mutex<UsageAccountingInner>>,
+}
+
+impl<RT: Runtime> fmt::Debug for UsageAccounting<RT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("UsageAccounting")
+            .field("pending", &inner.pending.len())
+            .field("quotas", &inner.quotas.len())
+            .finish()
+    }
+}
+
+fn day_containing(unix_secs: u64) -> i64 {
+    (unix_secs - unix_secs % SECONDS_PER_DAY) as i64
+}
+
+impl<RT: Runtime> UsageAccounting<RT> {
+    pub fn new(runtime: RT) -> Self {
+        Self {
+            runtime,
+            inner: Arc::new(Mutex::new(UsageAccountingInner::default())),
+        }
+    }
+
+    fn now_secs(&self) -> u64 {
+        self.runtime.unix_timestamp().as_secs()
+    }
+
+    fn record(&self, events: Vec<UsageEvent>) {
+        let now = self.now_secs();
+        let hour_start = UsageRollupKey::hour_containing(now);
+        let key = |component_path: Option<String>,
+                   udf_id: Option<String>,
+                   table_name: Option<String>| UsageRollupKey {
+            hour_start,
+            component_path,
+            udf_id,
+            table_name,
+        };
+
+        let mut inner = self.inner.lock();
+        inner.roll_day(day_containing(now));
+        for event in events {
+            match event {
+                UsageEvent::FunctionCall { fields } => {
+                    // Only count calls that would count towards usage, which
+                    // excludes system functions and CLI-driven calls.
+                    if !fields.is_tracked {
+                        continue;
+                    }
+                    let metrics = UsageMetrics {
+                        function_calls: 1,
+                        function_failures: (fields.status != "success") as u64,
+                        function_duration_millis: fields.duration_millis,
+                        action_compute_mb_millis: fields.memory_megabytes * fields.duration_millis,
+                        ..Default::default()
+                    };
+                    inner.add_daily(&fields.component_path, &metrics);
+                    inner.add(
+                        key(fields.component_path, Some(fields.udf_id), None),
+                        metrics,
+                    );
+                },
+                UsageEvent::FunctionStorageCalls {
+                    component_path,
+                    udf_id,
+                    count,
+                    ..
+                } => {
+                    let metrics = UsageMetrics {
+                        storage_calls: count,
+                        ..Default::default()
+                    };
+                    inner.add(key(component_path, Some(udf_id), None), metrics);
+                },
+                UsageEvent::FunctionStorageBandwidth {
+                    component_path,
+                    udf_id,
+                    ingress,
+                    egress,
+                    ..
+                } => {
+                    let metrics = UsageMetrics {
+                        storage_ingress_bytes: ingress,
+                        storage_egress_bytes: egress,
+                        ..Default::default()
+                    };
+                    inner.add(key(component_path, Some(udf_id), None), metrics);
+                },
+                UsageEvent::StorageCall { component_path, .. } => {
+                    let metrics = UsageMetrics {
+                        storage_calls: 1,
+                        ..Default::default()
+                    };
+                    inner.add(key(component_path, None, None), metrics);
+                },
+                UsageEvent::StorageBandwidth {
+                    component_path,
+                    ingress,
+                    egress,
+                    ..
+                } => {
+                    let metrics = UsageMetrics {
+                        storage_ingress_bytes: ingress,
+                        storage_egress_bytes: egress,
+                        ..Default::default()
+                    };
+                    inner.add(key(component_path, None, None), metrics);
+                },
+                UsageEvent::DatabaseBandwidth {
+                    component_path,
+                    udf_id,
+                    table_name,
+                    ingress,
+                    egress,
+                    egress_rows,
+                    ..
+                } => {
+                    let metrics = UsageMetrics {
+                        database_ingress_bytes: ingress,
+                        database_egress_bytes: egress,
+                        database_egress_rows: egress_rows,
+                        ..Default::default()
+                    };
+                    inner.add_daily(&component_path, &metrics);
+                    inner.add(key(component_path, Some(udf_id), Some(table_name)), metrics);
+                },
+                UsageEvent::VectorBandwidth {
+                    component_path,
+                    udf_id,
+                    table_name,
+                    ingress,
+                    egress,
+                    ..
+                } => {
+                    let metrics = UsageMetrics {
+                        vector_ingress_bytes: ingress,
+                        vector_egress_bytes: egress,
+                        ..Default::default()
+                    };
+                    inner.add(key(component_path, Some(udf_id), Some(table_name)), metrics);
+                },
+                UsageEvent::CurrentVectorStorage { tables } => {
+                    for table in tables {
+                        let metrics = UsageMetrics {
+                            vector_storage_bytes: Some(table.size),
+                            ..Default::default()
+                        };
+                        inner.add(
+                            key(table.component_path, None, Some(table.table_name)),
+                            metrics,
+                        );
+                    }
+                },
+                UsageEvent::CurrentDatabaseStorage { tables, .. } => {
+                    for table in tables {
+                        let metrics = UsageMetrics {
+                            database_storage_bytes: Some(
+                                table.total_document_size + table.total_index_size,
+                            ),
+                            ..Default::default()
+                        };
+                        inner.add(
+                            key(table.component_path, None, Some(table.table_name)),
+                            metrics,
+                        );
+                    }
+                },
+                UsageEvent::CurrentDocumentCounts { tables, .. } => {
+                    for table in tables {
+                        let metrics = UsageMetrics {
+                            document_count: Some(table.num_documents),
+                            ..Default::default()
+                        };
+                        inner.add(
+                            key(table.component_path, None, Some(table.table_name)),
+                            metrics,
+                        );
+                    }
+                },
+                // File storage isn't broken down by component, and read limit
+                // insights aren't usage.
+                UsageEvent::CurrentFileStorage { .. } | UsageEvent::InsightReadLimit { .. } => {},
+            }
+        }
+    }
+
+    /// Fail with a rate limiting error if `component_path` has exceeded one of
+    /// its daily quotas.
+    pub fn check_quota(&self, component_path: &ComponentPath) -> anyhow::Result<()> {
+        let component = component_path.clone().serialize();
+        let now = self.now_secs();
+        let mut inner = self.inner.lock();
+        inner.roll_day(day_containing(now));
+        let Some(quota) = inner.quotas.get(&component) else {
+            return Ok(());
+        };
+        let usage = inner.daily.get(&component).cloned().unwrap_or_default();
+        let name = match &component {
+            Some(path) => format!("Component '{path}'"),
+            None => "This deployment".to_string(),
+        };
+        if let Some(max) = quota.max_function_calls_per_day
+            && usage.function_calls >= max
+        {
+            anyhow::bail!(ErrorMetadata::rate_limited(
+                "UsageQuotaExceeded",
+                format!(
+                    "{name} has exceeded its quota of {max} function calls per day. The quota \
+                     resets at midnight UTC."
+                ),
+            ));
+        }
+        if let Some(max) = quota.max_database_bandwidth_bytes_per_day
+            && usage.database_bandwidth_bytes() >= max
+        {
+            anyhow::bail!(ErrorMetadata::rate_limited(
+                "UsageQuotaExceeded",
+                format!(
+                    "{name} has exceeded its quota of {max} bytes of database bandwidth per day. \
+                     The quota resets at midnight UTC."
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Update the cached quota for a component after it's been written to
+    /// `_usage_quotas`.
+    pub fn update_quota(&self, quota: UsageQuota) {
+        let mut inner = self.inner.lock();
+        if quota.max_function_calls_per_day.is_none()
+            && quota.max_database_bandwidth_bytes_per_day.is_none()
+        {
+            inner.quotas.remove(&quota.component_path);
+        } else {
+            inner.quotas.insert(quota.component_path.clone(), quota);
+        }
+    }
+
+    fn set_quotas(&self, quotas: Vec<UsageQuota>) {
+        self.inner.lock().quotas = quotas
+            .into_iter()
+            .map(|quota| (quota.component_path.clone(), quota))
+            .collect();
+    }
+
+    /// Write all pending usage to `_usage_rollups`. Usage that fails to be
+    /// written is kept in memory to be retried on the next flush.
+    pub async fn flush(&self, database: &Database<RT>) -> anyhow::Result<()> {
+        let pending = std::mem::take(&mut self.inner.lock().pending);
+        let mut remaining = pending.into_iter().peekable();
+        while remaining.peek().is_some() {
+            let chunk: Vec<_> = remaining.by_ref().take(ROLLUP_CHUNK_SIZE).collect();
+            let result: anyhow::Result<()> = try {
+                let mut tx = database.begin(Identity::system()).await?;
+                let mut model = UsageModel::new(&mut tx);
+                for (key, metrics) in &chunk {
+                    model.add_to_rollup(key.clone(), metrics).await?;
+                }
+                database
+                    .commit_with_write_source(tx, "usage_rollups")
+                    .await?;
+            };
+            if let Err(e) = result {
+                let mut inner = self.inner.lock();
+                for (key, metrics) in chunk.into_iter().chain(remaining) {
+                    inner.add(key, metrics);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<RT: Runtime> UsageEventLogger for UsageAccounting<RT> {
+    async fn record_async(&self, events: Vec<UsageEvent>) {
+        self.record(events);
+    }
+
+    async fn shutdown(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageGroupBy {
+    Component,
+    Function,
+    Table,
+}
+
+/// Usage summed over a time range for one group. Fields that aren't part of
+/// the grouping are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageSummaryRow {
+    pub component_path: Option<String>,
+    pub udf_id: Option<String>,
+    pub table_name: Option<String>,
+    pub metrics: UsageMetrics,
+}
+
+/// Sum `rollups`, which must be ordered by hour, per `group_by`. Counters are
+/// summed across hours, while storage gauges take the latest value for each
+/// table and are then summed across tables.
+pub fn summarize_usage(
+    rollups: impl Iterator<Item = UsageRollup>,
+    group_by: UsageGroupBy,
+) -> Vec<UsageSummaryRow> {
+    let mut latest: BTreeMap<(Option<String>, Option<String>, Option<String>), UsageMetrics> =
+        BTreeMap::new();
+    for UsageRollup { key, metrics } in rollups {
+        latest
+            .entry((key.component_path, key.udf_id, key.table_name))
+            .or_default()
+            .merge(&metrics);
+    }
+    let mut groups: BTreeMap<(Option<String>, Option<String>, Option<String>), UsageMetrics> =
+        BTreeMap::new();
+    for ((component_path, udf_id, table_name), metrics) in latest {
+        let group = match group_by {
+            UsageGroupBy::Component => (component_path, None, None),
+            UsageGroupBy::Function => match udf_id {
+                Some(udf_id) => (component_path, Some(udf_id), None),
+                None => continue,
+            },
+            UsageGroupBy::Table => match table_name {
+                Some(table_name) => (component_path, None, Some(table_name)),
+                None => continue,
+            },
+        };
+        let total = groups.entry(group).or_default();
+        let sum_gauge = |total: Option<u64>, value: Option<u64>| match (total, value) {
+            (Some(total), Some(value)) => Some(total + value),
+            (total, value) => total.or(value),
+        };
+        let document_count = sum_gauge(total.document_count, metrics.document_count);
+        let database_storage_bytes =
+            sum_gauge(total.database_storage_bytes, metrics.database_storage_bytes);
+        let vector_storage_bytes =
+            sum_gauge(total.vector_storage_bytes, metrics.vector_storage_bytes);
+        total.merge(&metrics);
+        total.document_count = document_count;
+        total.database_storage_bytes = database_storage_bytes;
+        total.vector_storage_bytes = vector_storage_bytes;
+    }
+    groups
+        .into_iter()
+        .map(
+            |((component_path, udf_id, table_name), metrics)| UsageSummaryRow {
+                component_path,
+                udf_id,
+                table_name,
+                metrics,
+            },
+        )
+        .collect()
+}
+
+pub struct UsageAccountingWorker<RT: Runtime> {
+    runtime: RT,
+    database: Database<RT>,
+    accounting: UsageAccounting<RT>,
+}
+
+impl<RT: Runtime> UsageAccountingWorker<RT> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        runtime: RT,
+        database: Database<RT>,
+        accounting: UsageAccounting<RT>,
+    ) -> impl Future<Output = ()> + Send {
+        let worker = UsageAccountingWorker {
+            runtime,
+            database,
+            accounting,
+        };
+        async move {
+            let mut loaded_daily_usage = false;
+            loop {
+                if let Err(e) = worker.run(&mut loaded_daily_usage).await {
+                    report_error(&mut e.context("UsageAccountingWorker died")).await;
+                    worker.runtime.wait(*USAGE_ROLLUP_FLUSH_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    async fn run(&self, loaded_daily_usage: &mut bool) -> anyhow::Result<()> {
+        tracing::info!("Starting UsageAccountingWorker");
+        if !*loaded_daily_usage {
+            self.load_daily_usage().await?;
+            *loaded_daily_usage = true;
+        }
+        loop {
+            self.load_quotas().await?;
+            self.runtime.wait(*USAGE_ROLLUP_FLUSH_INTERVAL).await;
+            self.accounting.flush(&self.database).await?;
+            self.delete_expired_rollups().await?;
+        }
+    }
+
+    /// Seed today's per-component totals from rollups flushed before this
+    /// process started, so restarting the backend doesn't reset quotas.
+    async fn load_daily_usage(&self) -> anyhow::Result<()> {
+        let day_start = day_containing(self.accounting.now_secs());
+        let mut tx = self.database.begin(Identity::system()).await?;
+        let rollups = UsageModel::new(&mut tx)
+            .rollups_in_range(day_start, day_start + SECONDS_PER_DAY as i64)
+            .await?;
+        let mut inner = self.accounting.inner.lock();
+        inner.roll_day(day_start);
+        for rollup in rollups {
+            let rollup = rollup.into_value();
+            let metrics = UsageMetrics {
+                function_calls: rollup.metrics.function_calls,
+                database_ingress_bytes: rollup.metrics.database_ingress_bytes,
+                database_egress_bytes: rollup.metrics.database_egress_bytes,
+                ..Default::default()
+            };
+            inner.add_daily(&rollup.key.component_path, &metrics);
+        }
+        Ok(())
+    }
+
+    async fn load_quotas(&self) -> anyhow::Result<()> {
+        let mut tx = self.database.begin(Identity::system()).await?;
+        let quotas = UsageModel::new(&mut tx).list_quotas().await?;
+        self.accounting.set_quotas(quotas);
+        Ok(())
+    }
+
+    async fn delete_expired_rollups(&self) -> anyhow::Result<()> {
+        let cutoff = UsageRollupKey::hour_containing(
+            self.accounting
+                .now_secs()
+                .saturating_sub(USAGE_ROLLUP_RETENTION.as_secs()),
+        );
+        loop {
+            let mut tx = self.database.begin(Identity::system()).await?;
+            let deleted = UsageModel::new(&mut tx)
+                .delete_rollups_before(cutoff, ROLLUP_CHUNK_SIZE)
+                .await?;
+            if deleted == 0 {
+                return Ok(());
+            }
+            self.database
+                .commit_with_write_source(tx, "usage_rollups_cleanup")
+                .await?;
+            tracing::info!("Deleted {deleted} expired usage rollups");
+        }
+    }
+}
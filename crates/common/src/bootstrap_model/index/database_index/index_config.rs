@@ -12,6 +12,9 @@ pub struct DeveloperDatabaseIndexConfig {
     /// Ordered field(s) to index. The "unindexed" primary key ordering of
     /// documents by [`DocumentId`] is represented by an empty vector.
     pub fields: IndexedFields,
+    /// If set, no two documents may have the same values for `fields`.
+    /// Documents missing any of the indexed fields are exempt.
+    pub unique: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -19,6 +22,8 @@ pub struct DeveloperDatabaseIndexConfig {
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct SerializedDeveloperDatabaseIndexConfig {
     fields: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
 }
 
 impl TryFrom<DeveloperDatabaseIndexConfig> for SerializedDeveloperDatabaseIndexConfig {
@@ -30,6 +35,7 @@ impl TryFrom<DeveloperDatabaseIndexConfig> for SerializedDeveloperDatabaseIndexC
                 .into_iter()
                 .map(String::from)
                 .collect(),
+            unique: config.unique.then_some(true),
         })
     }
 }
@@ -45,6 +51,7 @@ impl TryFrom<SerializedDeveloperDatabaseIndexConfig> for DeveloperDatabaseIndexC
                 .map(|p| p.parse())
                 .collect::<anyhow::Result<Vec<FieldPath>>>()?
                 .try_into()?,
+            unique: config.unique.unwrap_or(false),
         })
     }
 }
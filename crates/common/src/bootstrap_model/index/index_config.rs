@@ -250,6 +250,8 @@ mod tests {
         vector_index::{
             DeveloperVectorIndexConfig,
             FragmentedVectorSegment,
+            VectorDistanceMetric,
+            VectorHnswConfig,
             VectorIndexBackfillState,
             VectorIndexState,
         },
@@ -287,6 +289,9 @@ mod tests {
                     dimensions: 1536.try_into()?,
                     vector_field: "embedding.field".parse()?,
                     filter_fields: btreeset! { "filter1".parse()?, "filter2".parse()? },
+                    metric: VectorDistanceMetric::Cosine,
+                    quantization: None,
+                    hnsw: VectorHnswConfig::default(),
                 },
                 on_disk_state: VectorIndexState::Backfilling(VectorIndexBackfillState {
                     cursor: None,
@@ -1,4 +1,7 @@
-use std::collections::BTreeSet;
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
 
 use serde::{
     Deserialize,
@@ -24,14 +27,19 @@ use super::{
     vector_index::{
         DeveloperVectorIndexConfig,
         VectorDimensions,
+        VectorDistanceMetric,
+        VectorHnswConfig,
         VectorIndexBackfillState,
         VectorIndexState,
+        VectorQuantization,
     },
     IndexConfig,
 };
 use crate::{
     bootstrap_model::index::text_index::{
         DeveloperTextIndexConfig,
+        SearchFieldBoost,
+        TextIndexAnalyzer,
         TextIndexBackfillState,
         TextIndexState,
     },
@@ -66,11 +74,26 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         index_created_lower_bound: Timestamp,
         name: GenericIndexName<T>,
         fields: IndexedFields,
+    ) -> Self {
+        Self::new_backfilling_database_index(
+            index_created_lower_bound,
+            name,
+            DeveloperDatabaseIndexConfig {
+                fields,
+                unique: false,
+            },
+        )
+    }
+
+    pub fn new_backfilling_database_index(
+        index_created_lower_bound: Timestamp,
+        name: GenericIndexName<T>,
+        developer_config: DeveloperDatabaseIndexConfig,
     ) -> Self {
         Self {
             name,
             config: IndexConfig::Database {
-                developer_config: DeveloperDatabaseIndexConfig { fields },
+                developer_config,
                 on_disk_state: DatabaseIndexState::Backfilling(DatabaseIndexBackfillState {
                     index_created_lower_bound,
                     retention_started: false,
@@ -82,13 +105,17 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
     pub fn new_backfilling_text_index(
         name: GenericIndexName<T>,
         search_field: FieldPath,
+        extra_search_fields: BTreeMap<FieldPath, SearchFieldBoost>,
         filter_fields: BTreeSet<FieldPath>,
+        analyzer: TextIndexAnalyzer,
     ) -> Self {
         Self::new_text_index(
             name,
             DeveloperTextIndexConfig {
                 search_field,
+                extra_search_fields,
                 filter_fields,
+                analyzer,
             },
             TextIndexState::Backfilling(TextIndexBackfillState::new()),
         )
@@ -99,6 +126,9 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         vector_field: FieldPath,
         dimensions: VectorDimensions,
         filter_fields: BTreeSet<FieldPath>,
+        metric: VectorDistanceMetric,
+        quantization: Option<VectorQuantization>,
+        hnsw: VectorHnswConfig,
     ) -> Self {
         Self {
             name,
@@ -107,6 +137,9 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
                     dimensions,
                     vector_field,
                     filter_fields,
+                    metric,
+                    quantization,
+                    hnsw,
                 },
                 on_disk_state: VectorIndexState::Backfilling(VectorIndexBackfillState {
                     segments: vec![],
@@ -135,7 +168,10 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         Self {
             name,
             config: IndexConfig::Database {
-                developer_config: DeveloperDatabaseIndexConfig { fields },
+                developer_config: DeveloperDatabaseIndexConfig {
+                    fields,
+                    unique: false,
+                },
                 on_disk_state: DatabaseIndexState::Enabled,
             },
         }
@@ -145,6 +181,16 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         matches!(self.config, IndexConfig::Database { .. })
     }
 
+    pub fn is_unique_database_index(&self) -> bool {
+        matches!(
+            self.config,
+            IndexConfig::Database {
+                developer_config: DeveloperDatabaseIndexConfig { unique: true, .. },
+                ..
+            }
+        )
+    }
+
     pub fn is_text_index(&self) -> bool {
         matches!(self.config, IndexConfig::Text { .. })
     }
@@ -154,6 +154,24 @@ pub fn too_many_filter_fields(num_fields: usize) -> ErrorMetadata {
         format!("Search indexes may have up to {num_fields} filter fields."),
     )
 }
+pub fn too_many_extra_search_fields(num_fields: usize) -> ErrorMetadata {
+    ErrorMetadata::bad_request(
+        "IndexTooManyExtraSearchFields",
+        format!("Search indexes may have up to {num_fields} extra search fields."),
+    )
+}
+pub fn extra_search_field_is_search_field(
+    index: &IndexDescriptor,
+    field: &FieldPath,
+) -> ErrorMetadata {
+    ErrorMetadata::bad_request(
+        "IndexExtraSearchFieldIsSearchField",
+        format!(
+            "In index \"{index}\": The field \"{field}\" is the `searchField` and can't also be \
+             in `extraSearchFields`."
+        ),
+    )
+}
 pub fn too_many_indexes(table_name: &TableName, num_indexes: usize) -> ErrorMetadata {
     ErrorMetadata::bad_request(
         "TooManyIndexes",
@@ -40,4 +40,5 @@ pub static TABLE_ID_FIELD_PATH: LazyLock<FieldPath> =
 
 pub const MAX_INDEX_FIELDS_SIZE: usize = 16;
 pub const MAX_TEXT_INDEX_FILTER_FIELDS_SIZE: usize = 16;
+pub const MAX_TEXT_INDEX_EXTRA_SEARCH_FIELDS_SIZE: usize = 8;
 pub const MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE: usize = 16;
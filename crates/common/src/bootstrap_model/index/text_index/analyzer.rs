@@ -0,0 +1,111 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use errors::ErrorMetadata;
+use pb::searchlight::TextIndexAnalyzer as TextIndexAnalyzerProto;
+
+/// How a text index splits and normalizes its search field into terms. Query
+/// text is analyzed the same way, so changing an index's analyzer rebuilds it.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum TextIndexAnalyzer {
+    /// Split on non-alphanumeric characters and lowercase.
+    #[default]
+    Standard,
+    /// `Standard` with English stop words removed and words stemmed.
+    English,
+    /// `Standard` with German stop words removed, words stemmed, and accents
+    /// folded.
+    German,
+    /// `Standard` with French stop words removed, words stemmed, and accents
+    /// folded.
+    French,
+    /// `Standard` with Spanish stop words removed, words stemmed, and accents
+    /// folded.
+    Spanish,
+    /// `Standard` with accented characters folded to their ASCII equivalents.
+    AsciiFolding,
+    /// Character trigrams of each word, for matching within words.
+    Ngram,
+    /// Overlapping pairs of Chinese, Japanese, and Korean characters, which
+    /// aren't separated by spaces, and `Standard` words for everything else.
+    CjkBigram,
+}
+
+impl TextIndexAnalyzer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextIndexAnalyzer::Standard => "standard",
+            TextIndexAnalyzer::English => "english",
+            TextIndexAnalyzer::German => "german",
+            TextIndexAnalyzer::French => "french",
+            TextIndexAnalyzer::Spanish => "spanish",
+            TextIndexAnalyzer::AsciiFolding => "asciiFolding",
+            TextIndexAnalyzer::Ngram => "ngram",
+            TextIndexAnalyzer::CjkBigram => "cjkBigram",
+        }
+    }
+}
+
+impl fmt::Display for TextIndexAnalyzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TextIndexAnalyzer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(TextIndexAnalyzer::Standard),
+            "english" => Ok(TextIndexAnalyzer::English),
+            "german" => Ok(TextIndexAnalyzer::German),
+            "french" => Ok(TextIndexAnalyzer::French),
+            "spanish" => Ok(TextIndexAnalyzer::Spanish),
+            "asciiFolding" => Ok(TextIndexAnalyzer::AsciiFolding),
+            "ngram" => Ok(TextIndexAnalyzer::Ngram),
+            "cjkBigram" => Ok(TextIndexAnalyzer::CjkBigram),
+            _ => Err(anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidTextIndexAnalyzerError",
+                format!(
+                    "Unknown text index analyzer \"{s}\". Expected \"standard\", \"english\", \
+                     \"german\", \"french\", \"spanish\", \"asciiFolding\", \"ngram\", or \
+                     \"cjkBigram\"."
+                )
+            ))),
+        }
+    }
+}
+
+impl From<TextIndexAnalyzer> for TextIndexAnalyzerProto {
+    fn from(value: TextIndexAnalyzer) -> Self {
+        match value {
+            TextIndexAnalyzer::Standard => TextIndexAnalyzerProto::Standard,
+            TextIndexAnalyzer::English => TextIndexAnalyzerProto::English,
+            TextIndexAnalyzer::German => TextIndexAnalyzerProto::German,
+            TextIndexAnalyzer::French => TextIndexAnalyzerProto::French,
+            TextIndexAnalyzer::Spanish => TextIndexAnalyzerProto::Spanish,
+            TextIndexAnalyzer::AsciiFolding => TextIndexAnalyzerProto::AsciiFolding,
+            TextIndexAnalyzer::Ngram => TextIndexAnalyzerProto::Ngram,
+            TextIndexAnalyzer::CjkBigram => TextIndexAnalyzerProto::CjkBigram,
+        }
+    }
+}
+
+impl From<TextIndexAnalyzerProto> for TextIndexAnalyzer {
+    fn from(value: TextIndexAnalyzerProto) -> Self {
+        match value {
+            TextIndexAnalyzerProto::Standard => TextIndexAnalyzer::Standard,
+            TextIndexAnalyzerProto::English => TextIndexAnalyzer::English,
+            TextIndexAnalyzerProto::German => TextIndexAnalyzer::German,
+            TextIndexAnalyzerProto::French => TextIndexAnalyzer::French,
+            TextIndexAnalyzerProto::Spanish => TextIndexAnalyzer::Spanish,
+            TextIndexAnalyzerProto::AsciiFolding => TextIndexAnalyzer::AsciiFolding,
+            TextIndexAnalyzerProto::Ngram => TextIndexAnalyzer::Ngram,
+            TextIndexAnalyzerProto::CjkBigram => TextIndexAnalyzer::CjkBigram,
+        }
+    }
+}
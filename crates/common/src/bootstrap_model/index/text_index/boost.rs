@@ -0,0 +1,63 @@
+use std::fmt;
+
+use errors::ErrorMetadata;
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+
+/// The largest boost a text index's search field can have.
+pub const MAX_SEARCH_FIELD_BOOST: f64 = 100.;
+
+/// How much a match in one of a text index's extra search fields counts
+/// towards a document's score, relative to a match in its `searchField`. Each
+/// matching term's BM25 score is multiplied by its field's boost.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SearchFieldBoost(
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "(1u32..=400).prop_map(|b| f64::from(b) / 4.)")
+    )]
+    f64,
+);
+
+impl SearchFieldBoost {
+    pub fn new(boost: f64) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            boost.is_finite() && boost > 0. && boost <= MAX_SEARCH_FIELD_BOOST,
+            ErrorMetadata::bad_request(
+                "InvalidSearchFieldBoost",
+                format!(
+                    "Search field boosts must be greater than 0 and at most \
+                     {MAX_SEARCH_FIELD_BOOST}, got {boost}."
+                )
+            )
+        );
+        Ok(Self(boost))
+    }
+
+    pub fn get(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Default for SearchFieldBoost {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+// Boosts are always finite and positive, so comparing their bits is the same
+// as comparing their values.
+impl PartialEq for SearchFieldBoost {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for SearchFieldBoost {}
+
+impl fmt::Display for SearchFieldBoost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
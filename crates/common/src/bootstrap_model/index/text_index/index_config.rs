@@ -1,11 +1,20 @@
-use std::collections::BTreeSet;
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
 
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
 use serde::{
     Deserialize,
     Serialize,
 };
 use value::codegen_convex_serialization;
 
+use super::{
+    SearchFieldBoost,
+    TextIndexAnalyzer,
+};
 use crate::paths::FieldPath;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,8 +23,21 @@ pub struct DeveloperTextIndexConfig {
     /// The field to index for full text search.
     pub search_field: FieldPath,
 
+    /// More fields to index for full text search, each stored as its own
+    /// tantivy field and scored with its own BM25 statistics. A match's score
+    /// is multiplied by its field's boost, and `search_field` has a boost of 1.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "prop::collection::btree_map(any::<FieldPath>(), \
+                             any::<SearchFieldBoost>(), 0..4)")
+    )]
+    pub extra_search_fields: BTreeMap<FieldPath, SearchFieldBoost>,
+
     /// Other fields to index for equality filtering.
     pub filter_fields: BTreeSet<FieldPath>,
+
+    /// How the search field and queries are split into terms.
+    pub analyzer: TextIndexAnalyzer,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -23,7 +45,23 @@ pub struct DeveloperTextIndexConfig {
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct SerializedDeveloperTextIndexConfig {
     search_field: String,
+    // Omitted for single field indexes, which were the only kind before extra
+    // search fields were added.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extra_search_fields: Vec<SerializedSearchFieldBoost>,
     filter_fields: Vec<String>,
+    // Omitted for the standard analyzer, which indexes created before
+    // analyzers were selectable use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analyzer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+struct SerializedSearchFieldBoost {
+    field_path: String,
+    boost: f64,
 }
 
 impl TryFrom<DeveloperTextIndexConfig> for SerializedDeveloperTextIndexConfig {
@@ -32,7 +70,17 @@ impl TryFrom<DeveloperTextIndexConfig> for SerializedDeveloperTextIndexConfig {
     fn try_from(config: DeveloperTextIndexConfig) -> anyhow::Result<Self> {
         Ok(Self {
             search_field: config.search_field.into(),
+            extra_search_fields: config
+                .extra_search_fields
+                .into_iter()
+                .map(|(field_path, boost)| SerializedSearchFieldBoost {
+                    field_path: field_path.into(),
+                    boost: boost.get(),
+                })
+                .collect(),
             filter_fields: config.filter_fields.into_iter().map(String::from).collect(),
+            analyzer: (config.analyzer != TextIndexAnalyzer::Standard)
+                .then(|| config.analyzer.to_string()),
         })
     }
 }
@@ -43,11 +91,26 @@ impl TryFrom<SerializedDeveloperTextIndexConfig> for DeveloperTextIndexConfig {
     fn try_from(config: SerializedDeveloperTextIndexConfig) -> anyhow::Result<Self> {
         Ok(Self {
             search_field: config.search_field.parse()?,
+            extra_search_fields: config
+                .extra_search_fields
+                .into_iter()
+                .map(|field| {
+                    Ok((
+                        field.field_path.parse()?,
+                        SearchFieldBoost::new(field.boost)?,
+                    ))
+                })
+                .collect::<anyhow::Result<_>>()?,
             filter_fields: config
                 .filter_fields
                 .into_iter()
                 .map(|p| p.parse())
                 .collect::<anyhow::Result<BTreeSet<FieldPath>>>()?,
+            analyzer: config
+                .analyzer
+                .map(|a| a.parse())
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -58,11 +121,23 @@ impl TryFrom<pb::searchlight::SearchIndexConfig> for DeveloperTextIndexConfig {
     type Error = anyhow::Error;
 
     fn try_from(proto: pb::searchlight::SearchIndexConfig) -> anyhow::Result<Self> {
+        let analyzer = proto.analyzer().into();
         Ok(DeveloperTextIndexConfig {
             search_field: proto
                 .search_field_path
                 .ok_or_else(|| anyhow::format_err!("Missing search_field_path"))?
                 .try_into()?,
+            extra_search_fields: proto
+                .extra_search_fields
+                .into_iter()
+                .map(|field| {
+                    let field_path = field
+                        .field_path
+                        .ok_or_else(|| anyhow::format_err!("Missing field_path"))?
+                        .try_into()?;
+                    Ok((field_path, SearchFieldBoost::new(field.boost)?))
+                })
+                .collect::<anyhow::Result<_>>()?,
             filter_fields: proto
                 .filter_fields
                 .into_iter()
@@ -70,6 +145,7 @@ impl TryFrom<pb::searchlight::SearchIndexConfig> for DeveloperTextIndexConfig {
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .collect(),
+            analyzer,
         })
     }
 }
@@ -78,11 +154,20 @@ impl From<DeveloperTextIndexConfig> for pb::searchlight::SearchIndexConfig {
     fn from(config: DeveloperTextIndexConfig) -> Self {
         pb::searchlight::SearchIndexConfig {
             search_field_path: Some(config.search_field.into()),
+            extra_search_fields: config
+                .extra_search_fields
+                .into_iter()
+                .map(|(field_path, boost)| pb::searchlight::SearchFieldBoost {
+                    field_path: Some(field_path.into()),
+                    boost: boost.get(),
+                })
+                .collect(),
             filter_fields: config
                 .filter_fields
                 .into_iter()
                 .map(|f| f.into())
                 .collect::<Vec<_>>(),
+            analyzer: pb::searchlight::TextIndexAnalyzer::from(config.analyzer) as i32,
         }
     }
 }
@@ -275,6 +275,9 @@ pub enum TextSnapshotVersion {
     V1MissingAsUndefined,
     /// V2 uses string IDs
     V2UseStringIds,
+    /// V3 stores filter field values in fast fields for range filters and
+    /// sorting.
+    V3FilterValues,
 }
 
 impl TextSnapshotVersion {
@@ -282,7 +285,7 @@ impl TextSnapshotVersion {
         // Add a new TextSnapshotVersion if the index key format changes between
         // different persistence versions.
         match persistence_version {
-            PersistenceVersion::V5 => Self::V2UseStringIds,
+            PersistenceVersion::V5 => Self::V3FilterValues,
         }
     }
 
@@ -291,6 +294,7 @@ impl TextSnapshotVersion {
             Self::V0 => 0,
             Self::V1MissingAsUndefined => 1,
             Self::V2UseStringIds => 2,
+            Self::V3FilterValues => 3,
         }
     }
 
@@ -299,9 +303,22 @@ impl TextSnapshotVersion {
             0 => Ok(Self::V0),
             1 => Ok(Self::V1MissingAsUndefined),
             2 => Ok(Self::V2UseStringIds),
+            3 => Ok(Self::V3FilterValues),
             _ => anyhow::bail!("unrecognized search snapshot version {code:?}"),
         }
     }
+
+    /// Whether a backend writing `current` snapshots can query this one.
+    /// Snapshots from before filter field values were stored only lack range
+    /// filters and sorting, so they stay queryable while they're rebuilt.
+    pub fn is_queryable_as(&self, current: Self) -> bool {
+        *self == current || (*self == Self::V2UseStringIds && current == Self::V3FilterValues)
+    }
+
+    /// Whether the snapshot's segments store the values of its filter fields.
+    pub fn has_filter_values(&self) -> bool {
+        matches!(self, Self::V3FilterValues)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -1,13 +1,20 @@
+mod analyzer;
 mod backfill_state;
+mod boost;
 mod index_config;
 mod index_snapshot;
 mod index_state;
 
 pub use self::{
+    analyzer::TextIndexAnalyzer,
     backfill_state::{
         TextBackfillCursor,
         TextIndexBackfillState,
     },
+    boost::{
+        SearchFieldBoost,
+        MAX_SEARCH_FIELD_BOOST,
+    },
     index_config::{
         DeveloperTextIndexConfig,
         SerializedDeveloperTextIndexConfig,
@@ -0,0 +1,147 @@
+use errors::ErrorMetadata;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+pub const DEFAULT_HNSW_M: u32 = 16;
+pub const DEFAULT_HNSW_EF_CONSTRUCT: u32 = 100;
+pub const MIN_HNSW_M: u32 = 4;
+pub const MAX_HNSW_M: u32 = 64;
+pub const MIN_HNSW_EF: u32 = 4;
+pub const MAX_HNSW_EF: u32 = 1024;
+
+/// Parameters for the HNSW graphs built for large vector segments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct VectorHnswConfig {
+    /// Edges per node in the graph. More edges are more accurate but use more
+    /// memory and disk.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "MIN_HNSW_M..=MAX_HNSW_M")
+    )]
+    m: u32,
+    /// Neighbours considered while building the graph. Larger values are
+    /// more accurate but slower to build.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "MIN_HNSW_EF..=MAX_HNSW_EF")
+    )]
+    ef_construct: u32,
+    /// Neighbours considered while searching the graph, defaulting to
+    /// `ef_construct`. Larger values are more accurate but slower to search.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "proptest::option::of(MIN_HNSW_EF..=MAX_HNSW_EF)")
+    )]
+    ef: Option<u32>,
+}
+
+impl Default for VectorHnswConfig {
+    fn default() -> Self {
+        Self {
+            m: DEFAULT_HNSW_M,
+            ef_construct: DEFAULT_HNSW_EF_CONSTRUCT,
+            ef: None,
+        }
+    }
+}
+
+impl VectorHnswConfig {
+    pub fn new(m: Option<u32>, ef_construct: Option<u32>, ef: Option<u32>) -> anyhow::Result<Self> {
+        let m = m.unwrap_or(DEFAULT_HNSW_M);
+        let ef_construct = ef_construct.unwrap_or(DEFAULT_HNSW_EF_CONSTRUCT);
+        anyhow::ensure!(
+            (MIN_HNSW_M..=MAX_HNSW_M).contains(&m),
+            invalid_hnsw_config_error(format!(
+                "HNSW m {m} must be between {MIN_HNSW_M} and {MAX_HNSW_M}."
+            ))
+        );
+        for (name, value) in [("efConstruct", Some(ef_construct)), ("ef", ef)] {
+            if let Some(value) = value {
+                anyhow::ensure!(
+                    (MIN_HNSW_EF..=MAX_HNSW_EF).contains(&value),
+                    invalid_hnsw_config_error(format!(
+                        "HNSW {name} {value} must be between {MIN_HNSW_EF} and {MAX_HNSW_EF}."
+                    ))
+                );
+            }
+        }
+        Ok(Self {
+            m,
+            ef_construct,
+            ef,
+        })
+    }
+
+    pub fn m(&self) -> u32 {
+        self.m
+    }
+
+    pub fn ef_construct(&self) -> u32 {
+        self.ef_construct
+    }
+
+    pub fn ef(&self) -> Option<u32> {
+        self.ef
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn invalid_hnsw_config_error(msg: String) -> ErrorMetadata {
+    ErrorMetadata::bad_request("InvalidVectorHnswConfigError", msg)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SerializedVectorHnswConfig {
+    m: i64,
+    ef_construct: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ef: Option<i64>,
+}
+
+impl From<VectorHnswConfig> for SerializedVectorHnswConfig {
+    fn from(config: VectorHnswConfig) -> Self {
+        Self {
+            m: config.m as i64,
+            ef_construct: config.ef_construct as i64,
+            ef: config.ef.map(|ef| ef as i64),
+        }
+    }
+}
+
+impl TryFrom<SerializedVectorHnswConfig> for VectorHnswConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(config: SerializedVectorHnswConfig) -> anyhow::Result<Self> {
+        Self::new(
+            Some(config.m.try_into()?),
+            Some(config.ef_construct.try_into()?),
+            config.ef.map(u32::try_from).transpose()?,
+        )
+    }
+}
+
+impl From<VectorHnswConfig> for pb::searchlight::VectorHnswConfig {
+    fn from(config: VectorHnswConfig) -> Self {
+        Self {
+            m: config.m,
+            ef_construct: config.ef_construct,
+            ef: config.ef,
+        }
+    }
+}
+
+impl TryFrom<pb::searchlight::VectorHnswConfig> for VectorHnswConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(proto: pb::searchlight::VectorHnswConfig) -> anyhow::Result<Self> {
+        Self::new(Some(proto.m), Some(proto.ef_construct), proto.ef)
+    }
+}
@@ -9,7 +9,13 @@ use value::{
     FieldPath,
 };
 
-use super::VectorDimensions;
+use super::{
+    SerializedVectorHnswConfig,
+    VectorDimensions,
+    VectorDistanceMetric,
+    VectorHnswConfig,
+    VectorQuantization,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
@@ -22,6 +28,28 @@ pub struct DeveloperVectorIndexConfig {
 
     /// Other fields to index for equality filtering.
     pub filter_fields: BTreeSet<FieldPath>,
+
+    /// How vectors are compared when searching.
+    pub metric: VectorDistanceMetric,
+
+    /// Compressed vectors to search HNSW segments with, if any.
+    pub quantization: Option<VectorQuantization>,
+
+    /// Parameters for building and searching HNSW segments.
+    pub hnsw: VectorHnswConfig,
+}
+
+impl DeveloperVectorIndexConfig {
+    /// The estimated bytes stored for each vector, including its quantized
+    /// copy.
+    pub fn bytes_per_vector(&self) -> u64 {
+        let full = u32::from(self.dimensions) as u64 * 4;
+        let quantized = self
+            .quantization
+            .map(|q| q.bytes_per_vector(self.dimensions))
+            .unwrap_or(0);
+        full + quantized
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -33,6 +61,15 @@ pub struct SerializedDeveloperVectorIndexConfig {
     dimensions: i64,
     vector_field: String,
     filter_fields: Vec<String>,
+    // Omitted for cosine, which indexes created before metrics were
+    // selectable use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metric: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quantization: Option<String>,
+    // Omitted for the default parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hnsw: Option<SerializedVectorHnswConfig>,
 }
 
 impl TryFrom<DeveloperVectorIndexConfig> for SerializedDeveloperVectorIndexConfig {
@@ -43,6 +80,10 @@ impl TryFrom<DeveloperVectorIndexConfig> for SerializedDeveloperVectorIndexConfi
             dimensions: u32::from(config.dimensions) as i64,
             vector_field: config.vector_field.into(),
             filter_fields: config.filter_fields.into_iter().map(String::from).collect(),
+            metric: (config.metric != VectorDistanceMetric::Cosine)
+                .then(|| config.metric.to_string()),
+            quantization: config.quantization.map(|q| q.to_string()),
+            hnsw: (!config.hnsw.is_default()).then(|| config.hnsw.into()),
         })
     }
 }
@@ -59,6 +100,17 @@ impl TryFrom<SerializedDeveloperVectorIndexConfig> for DeveloperVectorIndexConfi
                 .into_iter()
                 .map(|p| p.parse())
                 .collect::<anyhow::Result<BTreeSet<FieldPath>>>()?,
+            metric: config
+                .metric
+                .map(|m| m.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            quantization: config.quantization.map(|q| q.parse()).transpose()?,
+            hnsw: config
+                .hnsw
+                .map(VectorHnswConfig::try_from)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -72,6 +124,12 @@ impl TryFrom<pb::searchlight::VectorIndexConfig> for DeveloperVectorIndexConfig
     type Error = anyhow::Error;
 
     fn try_from(proto: pb::searchlight::VectorIndexConfig) -> anyhow::Result<Self> {
+        let metric = proto.metric().into();
+        let quantization = proto
+            .quantization
+            .map(pb::searchlight::VectorQuantization::try_from)
+            .transpose()?
+            .map(VectorQuantization::from);
         Ok(DeveloperVectorIndexConfig {
             dimensions: VectorDimensions::try_from(proto.dimension)?,
             vector_field: proto
@@ -85,6 +143,13 @@ impl TryFrom<pb::searchlight::VectorIndexConfig> for DeveloperVectorIndexConfig
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .collect(),
+            metric,
+            quantization,
+            hnsw: proto
+                .hnsw
+                .map(VectorHnswConfig::try_from)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -99,6 +164,11 @@ impl From<DeveloperVectorIndexConfig> for pb::searchlight::VectorIndexConfig {
                 .into_iter()
                 .map(|f| f.into())
                 .collect::<Vec<_>>(),
+            metric: pb::searchlight::VectorDistanceMetric::from(config.metric) as i32,
+            quantization: config
+                .quantization
+                .map(|q| pb::searchlight::VectorQuantization::from(q) as i32),
+            hnsw: Some(config.hnsw.into()),
         }
     }
 }
@@ -18,12 +18,47 @@ use super::segment::{
 pub struct VectorIndexSnapshot {
     pub data: VectorIndexSnapshotData,
     pub ts: Timestamp,
+    pub version: VectorSnapshotVersion,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum VectorSnapshotVersion {
+    /// V0 is the original version for vector snapshots.
+    V0,
+    /// V1 also stores numeric filter field values for range filters.
+    V1NumericFilterValues,
+}
+
+impl VectorSnapshotVersion {
+    pub fn to_code(&self) -> i64 {
+        match self {
+            Self::V0 => 0,
+            Self::V1NumericFilterValues => 1,
+        }
+    }
+
+    pub fn from_code(code: i64) -> anyhow::Result<Self> {
+        match code {
+            0 => Ok(Self::V0),
+            1 => Ok(Self::V1NumericFilterValues),
+            _ => anyhow::bail!("unrecognized vector snapshot version {code:?}"),
+        }
+    }
+
+    /// Whether the snapshot's segments store numeric filter field values.
+    pub fn has_numeric_filter_values(&self) -> bool {
+        matches!(self, Self::V1NumericFilterValues)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct SerializedVectorIndexSnapshot {
     data: WithUnknown<SerializedVectorIndexSnapshotData>,
     ts: i64,
+    // Snapshots written before versions existed don't have this field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<i64>,
 }
 
 impl TryFrom<VectorIndexSnapshot> for SerializedVectorIndexSnapshot {
@@ -33,6 +68,7 @@ impl TryFrom<VectorIndexSnapshot> for SerializedVectorIndexSnapshot {
         Ok(SerializedVectorIndexSnapshot {
             ts: value.ts.into(),
             data: WithUnknown::<SerializedVectorIndexSnapshotData>::try_from(value.data)?,
+            version: Some(value.version.to_code()),
         })
     }
 }
@@ -44,6 +80,11 @@ impl TryFrom<SerializedVectorIndexSnapshot> for VectorIndexSnapshot {
         Ok(VectorIndexSnapshot {
             ts: value.ts.try_into()?,
             data: value.data.try_into()?,
+            version: value
+                .version
+                .map(VectorSnapshotVersion::from_code)
+                .transpose()?
+                .unwrap_or(VectorSnapshotVersion::V0),
         })
     }
 }
@@ -0,0 +1,74 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use errors::ErrorMetadata;
+use pb::searchlight::VectorDistanceMetric as VectorDistanceMetricProto;
+
+/// How a vector index compares vectors. Scores are always oriented so that
+/// higher is closer: cosine similarity, dot product, or negated Euclidean
+/// distance.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum VectorDistanceMetric {
+    #[default]
+    Cosine,
+    Dot,
+    Euclidean,
+}
+
+impl VectorDistanceMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VectorDistanceMetric::Cosine => "cosine",
+            VectorDistanceMetric::Dot => "dot",
+            VectorDistanceMetric::Euclidean => "euclidean",
+        }
+    }
+}
+
+impl fmt::Display for VectorDistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VectorDistanceMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cosine" => Ok(VectorDistanceMetric::Cosine),
+            "dot" => Ok(VectorDistanceMetric::Dot),
+            "euclidean" => Ok(VectorDistanceMetric::Euclidean),
+            _ => Err(anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidVectorDistanceMetricError",
+                format!(
+                    "Unknown vector distance metric \"{s}\". Expected \"cosine\", \"dot\", or \
+                     \"euclidean\"."
+                )
+            ))),
+        }
+    }
+}
+
+impl From<VectorDistanceMetric> for VectorDistanceMetricProto {
+    fn from(value: VectorDistanceMetric) -> Self {
+        match value {
+            VectorDistanceMetric::Cosine => VectorDistanceMetricProto::Cosine,
+            VectorDistanceMetric::Dot => VectorDistanceMetricProto::Dot,
+            VectorDistanceMetric::Euclidean => VectorDistanceMetricProto::Euclidean,
+        }
+    }
+}
+
+impl From<VectorDistanceMetricProto> for VectorDistanceMetric {
+    fn from(value: VectorDistanceMetricProto) -> Self {
+        match value {
+            VectorDistanceMetricProto::Cosine => VectorDistanceMetric::Cosine,
+            VectorDistanceMetricProto::Dot => VectorDistanceMetric::Dot,
+            VectorDistanceMetricProto::Euclidean => VectorDistanceMetric::Euclidean,
+        }
+    }
+}
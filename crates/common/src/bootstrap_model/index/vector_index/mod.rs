@@ -1,8 +1,11 @@
 mod backfill_state;
 mod dimensions;
+mod hnsw;
 mod index_config;
 mod index_snapshot;
 mod index_state;
+mod metric;
+mod quantization;
 mod segment;
 
 pub use self::{
@@ -12,6 +15,16 @@ pub use self::{
         MAX_VECTOR_DIMENSIONS,
         MIN_VECTOR_DIMENSIONS,
     },
+    hnsw::{
+        SerializedVectorHnswConfig,
+        VectorHnswConfig,
+        DEFAULT_HNSW_EF_CONSTRUCT,
+        DEFAULT_HNSW_M,
+        MAX_HNSW_EF,
+        MAX_HNSW_M,
+        MIN_HNSW_EF,
+        MIN_HNSW_M,
+    },
     index_config::{
         DeveloperVectorIndexConfig,
         SerializedDeveloperVectorIndexConfig,
@@ -19,11 +32,14 @@ pub use self::{
     index_snapshot::{
         VectorIndexSnapshot,
         VectorIndexSnapshotData,
+        VectorSnapshotVersion,
     },
     index_state::{
         SerializedVectorIndexState,
         VectorIndexState,
     },
+    metric::VectorDistanceMetric,
+    quantization::VectorQuantization,
     segment::FragmentedVectorSegment,
 };
 
@@ -107,6 +123,22 @@ mod tests {
 
             must_let!(let VectorIndexSnapshotData::Unknown(_) = snapshot.data);
         }
+
+        #[test]
+        fn missing_version_defaults_to_v0(
+            ts in any::<Timestamp>(),
+            serialized_index_state_name in serialized_index_state_name_having_data(),
+        ) {
+            let legacy_object = assert_obj!(
+                "state" => serialized_index_state_name.as_str(),
+                "data" => {"something" => "invalid"},
+                "ts" => ConvexValue::Int64(ts.into()),
+            );
+            let state: VectorIndexState = legacy_object.try_into().unwrap();
+            let snapshot = extract_snapshot(serialized_index_state_name, state);
+
+            assert_eq!(snapshot.version, VectorSnapshotVersion::V0);
+        }
     }
 
     fn extract_snapshot(
@@ -0,0 +1,78 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use errors::ErrorMetadata;
+use pb::searchlight::VectorQuantization as VectorQuantizationProto;
+
+use super::VectorDimensions;
+
+/// Compressed copies of an index's vectors that HNSW segments search first,
+/// rescoring the best candidates against the original vectors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum VectorQuantization {
+    /// One byte per dimension.
+    Scalar,
+    /// One bit per dimension.
+    Binary,
+}
+
+impl VectorQuantization {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VectorQuantization::Scalar => "scalar",
+            VectorQuantization::Binary => "binary",
+        }
+    }
+
+    /// Bytes stored per vector for the quantized copy, on top of the original
+    /// vector.
+    pub fn bytes_per_vector(&self, dimensions: VectorDimensions) -> u64 {
+        let dimensions = u32::from(dimensions) as u64;
+        match self {
+            VectorQuantization::Scalar => dimensions,
+            VectorQuantization::Binary => dimensions.div_ceil(8),
+        }
+    }
+}
+
+impl fmt::Display for VectorQuantization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VectorQuantization {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "scalar" => Ok(VectorQuantization::Scalar),
+            "binary" => Ok(VectorQuantization::Binary),
+            _ => Err(anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidVectorQuantizationError",
+                format!("Unknown vector quantization \"{s}\". Expected \"scalar\" or \"binary\".")
+            ))),
+        }
+    }
+}
+
+impl From<VectorQuantization> for VectorQuantizationProto {
+    fn from(value: VectorQuantization) -> Self {
+        match value {
+            VectorQuantization::Scalar => VectorQuantizationProto::Scalar,
+            VectorQuantization::Binary => VectorQuantizationProto::Binary,
+        }
+    }
+}
+
+impl From<VectorQuantizationProto> for VectorQuantization {
+    fn from(value: VectorQuantizationProto) -> Self {
+        match value {
+            VectorQuantizationProto::Scalar => VectorQuantization::Scalar,
+            VectorQuantizationProto::Binary => VectorQuantization::Binary,
+        }
+    }
+}
@@ -10,7 +10,10 @@ use value::{
     FieldName,
 };
 
-use super::VectorDimensions;
+use super::{
+    DeveloperVectorIndexConfig,
+    VectorDimensions,
+};
 use crate::types::ObjectKey;
 
 /// A qdrant Segment that's split into three separate parts, the qdrant Segment
@@ -76,7 +79,10 @@ impl FragmentedVectorSegment {
     /// HNSW index (if present). Index overhead is larger as a percentage for
     /// small dimensional vectors than large dimensional vectors.
     pub fn non_deleted_size_bytes(&self, dimensions: VectorDimensions) -> anyhow::Result<u64> {
-        Self::size_bytes(self.non_deleted_vectors()?, dimensions)
+        Self::size_bytes(
+            self.non_deleted_vectors()?,
+            u32::from(dimensions) as u64 * 4,
+        )
     }
 
     /// The estimated size bytes based on both deleted and non-deleted vectors
@@ -85,17 +91,16 @@ impl FragmentedVectorSegment {
     /// The actual size of the segment in s3 will be bigger due to the overhead
     /// from the HNSW index (if present). Index overhead is larger as a
     /// percentage for small dimensional vectors than large dimensional
-    /// vectors.
-    pub fn total_size_bytes(&self, dimensions: VectorDimensions) -> anyhow::Result<u64> {
-        Self::size_bytes(self.num_vectors as u64, dimensions)
+    /// vectors. Quantized copies of the vectors are included.
+    pub fn total_size_bytes(&self, config: &DeveloperVectorIndexConfig) -> anyhow::Result<u64> {
+        Self::size_bytes(self.num_vectors as u64, config.bytes_per_vector())
     }
 
-    fn size_bytes(estimated_vectors: u64, dimensions: VectorDimensions) -> anyhow::Result<u64> {
+    fn size_bytes(estimated_vectors: u64, bytes_per_vector: u64) -> anyhow::Result<u64> {
         // A little extra paranoia since all of these numbers are not originally u64 and
         // can overflow u32.
         (estimated_vectors)
-            .checked_mul(u32::from(dimensions) as u64)
-            .and_then(|value| value.checked_mul(4_u64))
+            .checked_mul(bytes_per_vector)
             .context("Overflowed size calculation!")
     }
 
@@ -15,6 +15,11 @@ use std::{
 use async_trait::async_trait;
 use bytes::Bytes;
 use errors::ErrorMetadata;
+use fastrace::{
+    collector::SpanContext,
+    future::FutureExt as _,
+    Span,
+};
 use futures::{
     future::BoxFuture,
     StreamExt,
@@ -32,6 +37,7 @@ use tokio::select;
 use crate::http::{
     HttpRequestStream,
     HttpResponseStream,
+    TRACEPARENT_HEADER,
 };
 
 /// Http client used for fetch syscall.
@@ -86,9 +92,39 @@ impl ProxiedFetchClient {
     }
 }
 
+/// Starts a client span for an outgoing fetch under the current local parent
+/// and propagates it to the destination in a `traceparent` header. If the
+/// request already carries its own trace context it is left untouched and
+/// linked from the span instead.
+fn client_span(request: &mut HttpRequestStream) -> anyhow::Result<Span> {
+    // Only record the origin because query params might contain some PII.
+    let origin = request.url.origin().unicode_serialization();
+    let span = Span::enter_with_local_parent("fetch")
+        .with_property(|| ("span.kind", "client"))
+        .with_property(|| ("http.request.method", request.method.to_string()))
+        .with_property(|| ("server.address", origin));
+    let existing_ctx = request
+        .headers
+        .get(TRACEPARENT_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .and_then(SpanContext::decode_w3c_traceparent);
+    match existing_ctx {
+        Some(ctx) => span.add_link(ctx),
+        None => {
+            if let Some(ctx) = SpanContext::from_span(&span) {
+                request
+                    .headers
+                    .insert(TRACEPARENT_HEADER, ctx.encode_w3c_traceparent().parse()?);
+            }
+        },
+    }
+    Ok(span)
+}
+
 #[async_trait]
 impl FetchClient for ProxiedFetchClient {
     async fn fetch(&self, mut request: HttpRequestStream) -> anyhow::Result<HttpResponseStream> {
+        let span = client_span(&mut request)?;
         let mut request_builder = self
             .http_client
             .request(request.method, request.url.as_str());
@@ -99,7 +135,7 @@ impl FetchClient for ProxiedFetchClient {
         }
         let raw_request = request_builder.build()?;
         let raw_response = select! {
-            response = self.http_client.execute(raw_request) => {
+            response = self.http_client.execute(raw_request).in_span(span) => {
                 response?
             },
             _ = &mut request.signal => {
@@ -266,6 +302,10 @@ pub enum InternalFetchPurpose {
 #[cfg(test)]
 mod tests {
     use errors::ErrorMetadataAnyhowExt;
+    use fastrace::{
+        collector::SpanContext,
+        Span,
+    };
     use futures::FutureExt;
     use http::{
         HeaderMap,
@@ -273,7 +313,10 @@ mod tests {
         StatusCode,
     };
 
-    use super::ProxiedFetchClient;
+    use super::{
+        client_span,
+        ProxiedFetchClient,
+    };
     use crate::http::{
         categorize_http_response_stream,
         fetch::{
@@ -286,6 +329,7 @@ mod tests {
         HttpResponseStream,
         CONVEX_CLIENT_HEADER,
         CONVEX_CLIENT_HEADER_VALUE,
+        TRACEPARENT_HEADER,
     };
 
     #[tokio::test]
@@ -380,4 +424,42 @@ mod tests {
             "success"
         );
     }
+
+    #[test]
+    fn test_fetch_propagates_traceparent() -> anyhow::Result<()> {
+        let parent_ctx = SpanContext::random();
+        let root = Span::root("action", parent_ctx);
+        let _guard = root.set_local_parent();
+
+        let mut request: HttpRequestStream = HttpRequest {
+            headers: HeaderMap::new(),
+            url: "https://example.com/path?secret=1".parse()?,
+            method: Method::GET,
+            body: None,
+        }
+        .into();
+        let _span = client_span(&mut request)?;
+        let traceparent = request
+            .headers
+            .get(TRACEPARENT_HEADER)
+            .expect("traceparent should be injected")
+            .to_str()?;
+        let ctx = SpanContext::decode_w3c_traceparent(traceparent).unwrap();
+        // The fetch span continues the action's trace as a new child span.
+        assert_eq!(ctx.trace_id, parent_ctx.trace_id);
+        assert_ne!(ctx.span_id, parent_ctx.span_id);
+
+        // A traceparent set by the action itself is preserved.
+        let existing = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let mut request: HttpRequestStream = HttpRequest {
+            headers: HeaderMap::from_iter([(TRACEPARENT_HEADER, existing.parse()?)]),
+            url: "https://example.com".parse()?,
+            method: Method::GET,
+            body: None,
+        }
+        .into();
+        let _span = client_span(&mut request)?;
+        assert_eq!(request.headers.get(TRACEPARENT_HEADER).unwrap(), existing);
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     convert::Infallible,
     fmt,
     future::Future,
@@ -103,6 +104,7 @@ use utoipa::ToSchema;
 use self::metrics::log_http_request;
 use crate::{
     errors::report_error_sync,
+    fastrace_helpers::get_sampled_span,
     knobs::{
         HTTP_SERVER_TCP_BACKLOG,
         PROPAGATE_UPSTREAM_TRACES,
@@ -521,6 +523,14 @@ impl HttpError {
     }
 }
 
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.status_code, self.error_code, self.msg)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
 /// `HttpResponseError` is used to convert `anyhow::Error` (and
 /// `HttpError` inside it if present) into `http::Response` that is returned
 /// from the HTTP middleware. All HTTP handlers should return
@@ -549,16 +559,30 @@ impl IntoResponse for HttpResponseError {
         // This is the only place we capture errors to sentry because it is the exit
         // point of the HTTP layer
         report_error_sync(&mut self.trace);
-        self.http_error.into_response()
+        let retry_after = self.trace.retry_after();
+        let mut response = self.http_error.into_response();
+        if let Some(retry_after) = retry_after {
+            // `Retry-After` is in whole seconds, so round up to avoid clients retrying
+            // before the limit has replenished.
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(http::header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
 
 impl From<anyhow::Error> for HttpResponseError {
     fn from(err: anyhow::Error) -> HttpResponseError {
-        let http_error = HttpError {
-            status_code: err.http_status(),
-            error_code: err.short_msg().to_string().into(),
-            msg: err.msg().to_string().into(),
+        let http_error = match err.downcast_ref::<HttpError>() {
+            // Responses relayed from another backend are passed through as is.
+            Some(http_error) => http_error.clone(),
+            None => HttpError {
+                status_code: err.http_status(),
+                error_code: err.short_msg().to_string().into(),
+                msg: err.msg().to_string().into(),
+            },
         };
         Self {
             trace: err,
@@ -831,16 +855,33 @@ pub async fn stats_middleware<RM: RouteMapper>(
     // Capture URI before req is moved
     let uri = req.uri().to_string();
 
-    // Sampling isn't done here, and should be done upstream
+    let upstream_trace_id = traceparent.map(|span_ctx| format!("{:032x}", span_ctx.trace_id.0));
+
+    // Requests with an upstream trace context inherit its sampling decision.
+    // Otherwise, sample according to `REQUEST_TRACE_SAMPLE_CONFIG`.
     let root = match traceparent {
-        Some(span_ctx) if *PROPAGATE_UPSTREAM_TRACES => {
-            Span::root(route.to_owned(), span_ctx).with_property(|| ("span.kind", "server"))
-        },
-        _ => Span::noop(),
+        Some(span_ctx) if *PROPAGATE_UPSTREAM_TRACES => Span::root(route.to_owned(), span_ctx),
+        _ => get_sampled_span(
+            &resolved_host.instance_name,
+            &route,
+            &mut rand::rng(),
+            BTreeMap::new(),
+        ),
+    }
+    .with_property(|| ("span.kind", "server"))
+    .with_property(|| ("dev.convex.request_id", request_id.to_string()));
+    let root = match &upstream_trace_id {
+        Some(trace_id) => root.with_property(|| ("dev.convex.upstream_trace_id", trace_id.clone())),
+        None => root,
     };
 
-    // Add the request_id to sentry
-    sentry::configure_scope(|scope| scope.set_tag("request_id", &request_id));
+    // Add the request_id (and upstream trace id, if any) to sentry
+    sentry::configure_scope(|scope| {
+        scope.set_tag("request_id", &request_id);
+        if let Some(trace_id) = &upstream_trace_id {
+            scope.set_tag("upstream_trace_id", trace_id);
+        }
+    });
 
     let resp = next.run(req).in_span(root).await;
 
@@ -1073,7 +1114,8 @@ async fn request_id_from_req_parts(
     {
         request_id_header.parse::<RequestId>()
     } else {
-        // Generate a new request_id
+        // Generate a new request_id. An upstream trace id is recorded on the
+        // root span instead, since many requests can share one trace.
         let request_id = RequestId::new();
         parts
             .headers
@@ -1281,11 +1323,56 @@ mod tests {
         INTERNAL_SERVER_ERROR,
         INTERNAL_SERVER_ERROR_MSG,
     };
-    use http::StatusCode;
+    use http::{
+        Request,
+        StatusCode,
+    };
 
-    use super::HttpResponseError;
+    use super::{
+        request_id_from_req_parts,
+        HttpResponseError,
+        CONVEX_REQUEST_ID_HEADER,
+        TRACEPARENT_HEADER,
+    };
     use crate::http::HttpError;
 
+    #[tokio::test]
+    async fn test_request_id_ignores_traceparent() -> anyhow::Result<()> {
+        // Requests sharing an upstream trace still get distinct request ids.
+        let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+        let request = || {
+            Request::builder()
+                .header(
+                    TRACEPARENT_HEADER,
+                    format!("00-{trace_id}-00f067aa0ba902b7-01"),
+                )
+                .body(())
+        };
+        let (mut parts, _) = request()?.into_parts();
+        let first = request_id_from_req_parts(&mut parts).await?;
+        assert_ne!(first.as_str(), trace_id);
+        assert_eq!(
+            parts.headers.get(CONVEX_REQUEST_ID_HEADER).unwrap(),
+            first.as_str()
+        );
+        let (mut parts, _) = request()?.into_parts();
+        let second = request_id_from_req_parts(&mut parts).await?;
+        assert_ne!(first, second);
+
+        // An explicit request id is used as is.
+        let (mut parts, _) = Request::builder()
+            .header(
+                TRACEPARENT_HEADER,
+                format!("00-{trace_id}-00f067aa0ba902b7-01"),
+            )
+            .header(CONVEX_REQUEST_ID_HEADER, "abc123")
+            .body(())?
+            .into_parts();
+        let request_id = request_id_from_req_parts(&mut parts).await?;
+        assert_eq!(request_id.as_str(), "abc123");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_http_response_error_internal_server_error() -> anyhow::Result<()> {
         let err_text = "some random error";
@@ -1350,4 +1437,15 @@ mod tests {
         assert_eq!(error.message(), msg);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_http_error_passed_through() -> anyhow::Result<()> {
+        let relayed = HttpError::new(StatusCode::BAD_GATEWAY, "Upstream", "Upstream failed");
+        let err = anyhow::Error::new(relayed.clone())
+            .context(ErrorMetadata::overloaded("Upstream", "Upstream failed"));
+        let http_response_err: HttpResponseError = err.into();
+        let response = http_response_err.into_response();
+        assert_eq!(HttpError::from_response(response).await?, relayed);
+        Ok(())
+    }
 }
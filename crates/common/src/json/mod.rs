@@ -6,6 +6,10 @@ use errors::ErrorMetadata;
 mod expression;
 mod query;
 pub use expression::JsonExpression;
+pub use query::{
+    JsonSearchFilterExpression,
+    JsonSearchOrder,
+};
 use serde::{
     de::DeserializeOwned,
     Serialize,
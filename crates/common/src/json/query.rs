@@ -21,6 +21,7 @@ use crate::{
         QuerySource,
         Search,
         SearchFilterExpression,
+        SearchOrder,
         MAX_QUERY_OPERATORS,
     },
     types::{
@@ -74,7 +75,7 @@ enum JsonIndexRangeExpression {
 }
 #[derive(Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct JsonFieldPathAndValue {
+pub struct JsonFieldPathAndValue {
     field_path: String,
     value: JsonValue,
 }
@@ -157,17 +158,22 @@ struct JsonFullTableScan {
 struct JsonSearch {
     index_name: String,
     filters: Vec<JsonSearchFilterExpression>,
+    order: Option<JsonSearchOrder>,
 }
 
 #[derive(Deserialize, Serialize)]
 #[serde(tag = "type")]
-enum JsonSearchFilterExpression {
+pub enum JsonSearchFilterExpression {
     #[serde(rename_all = "camelCase")]
     Search {
         field_path: String,
         value: String,
     },
     Eq(JsonFieldPathAndValue),
+    Gt(JsonFieldPathAndValue),
+    Gte(JsonFieldPathAndValue),
+    Lt(JsonFieldPathAndValue),
+    Lte(JsonFieldPathAndValue),
 }
 
 impl TryFrom<JsonSearchFilterExpression> for SearchFilterExpression {
@@ -182,6 +188,22 @@ impl TryFrom<JsonSearchFilterExpression> for SearchFilterExpression {
                 FieldPath::from_str(&field_and_value.field_path)?,
                 MaybeValue::try_from(field_and_value.value)?.0,
             )),
+            JsonSearchFilterExpression::Gt(field_and_value) => Ok(SearchFilterExpression::Gt(
+                FieldPath::from_str(&field_and_value.field_path)?,
+                MaybeValue::try_from(field_and_value.value)?.0,
+            )),
+            JsonSearchFilterExpression::Gte(field_and_value) => Ok(SearchFilterExpression::Gte(
+                FieldPath::from_str(&field_and_value.field_path)?,
+                MaybeValue::try_from(field_and_value.value)?.0,
+            )),
+            JsonSearchFilterExpression::Lt(field_and_value) => Ok(SearchFilterExpression::Lt(
+                FieldPath::from_str(&field_and_value.field_path)?,
+                MaybeValue::try_from(field_and_value.value)?.0,
+            )),
+            JsonSearchFilterExpression::Lte(field_and_value) => Ok(SearchFilterExpression::Lte(
+                FieldPath::from_str(&field_and_value.field_path)?,
+                MaybeValue::try_from(field_and_value.value)?.0,
+            )),
         }
     }
 }
@@ -201,6 +223,57 @@ impl From<SearchFilterExpression> for JsonSearchFilterExpression {
                     value: MaybeValue(value).into(),
                 })
             },
+            SearchFilterExpression::Gt(field_path, value) => {
+                JsonSearchFilterExpression::Gt(JsonFieldPathAndValue {
+                    field_path: field_path.into(),
+                    value: MaybeValue(value).into(),
+                })
+            },
+            SearchFilterExpression::Gte(field_path, value) => {
+                JsonSearchFilterExpression::Gte(JsonFieldPathAndValue {
+                    field_path: field_path.into(),
+                    value: MaybeValue(value).into(),
+                })
+            },
+            SearchFilterExpression::Lt(field_path, value) => {
+                JsonSearchFilterExpression::Lt(JsonFieldPathAndValue {
+                    field_path: field_path.into(),
+                    value: MaybeValue(value).into(),
+                })
+            },
+            SearchFilterExpression::Lte(field_path, value) => {
+                JsonSearchFilterExpression::Lte(JsonFieldPathAndValue {
+                    field_path: field_path.into(),
+                    value: MaybeValue(value).into(),
+                })
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSearchOrder {
+    field_path: String,
+    order: String,
+}
+
+impl TryFrom<JsonSearchOrder> for SearchOrder {
+    type Error = anyhow::Error;
+
+    fn try_from(json_order: JsonSearchOrder) -> Result<Self> {
+        Ok(SearchOrder {
+            field_path: FieldPath::from_str(&json_order.field_path)?,
+            order: try_order_from_string(Some(json_order.order))?,
+        })
+    }
+}
+
+impl From<SearchOrder> for JsonSearchOrder {
+    fn from(order: SearchOrder) -> Self {
+        JsonSearchOrder {
+            field_path: order.field_path.into(),
+            order: order.order.into(),
         }
     }
 }
@@ -255,6 +328,7 @@ impl TryFrom<JsonQuerySource> for QuerySource {
                     table: index_name.table().clone(),
                     index_name,
                     filters: filter_expressions,
+                    order: json_search.order.map(SearchOrder::try_from).transpose()?,
                 })
             },
         })
@@ -285,10 +359,12 @@ impl From<QuerySource> for JsonQuerySource {
             QuerySource::Search(Search {
                 index_name,
                 filters,
+                order,
                 ..
             }) => JsonQuerySource::Search(JsonSearch {
                 index_name: index_name.to_string(),
                 filters: filters.into_iter().map(|filter| filter.into()).collect(),
+                order: order.map(JsonSearchOrder::from),
             }),
         }
     }
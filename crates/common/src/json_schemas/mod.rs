@@ -156,6 +156,17 @@ pub fn record(key_type: String, value_schema: JsonValue) -> JsonValue {
     })
 }
 
+/// Adds validation keywords such as `minLength` or `maximum` to `schema`.
+pub fn with_keywords(
+    mut schema: JsonValue,
+    keywords: serde_json::Map<String, JsonValue>,
+) -> JsonValue {
+    if let JsonValue::Object(ref mut object) = schema {
+        object.extend(keywords);
+    }
+    schema
+}
+
 pub fn union(variant_schemas: Vec<JsonValue>) -> JsonValue {
     json!({ "anyOf": variant_schemas })
 }
@@ -69,6 +69,23 @@ pub static SHARED_UDF_CACHE_MAX_SIZE: LazyLock<usize> =
 pub static MAX_UDF_EXECUTION: LazyLock<usize> =
     LazyLock::new(|| env_config("MAX_UDF_EXECUTION", 1000));
 
+/// Size at which the durable function log rotates to a new file.
+pub static FUNCTION_LOG_STORE_MAX_FILE_BYTES: LazyLock<u64> =
+    LazyLock::new(|| env_config("FUNCTION_LOG_STORE_MAX_FILE_BYTES", 64 * 1024 * 1024));
+
+/// Files in the durable function log are deleted once all of their entries are
+/// older than this number of days.
+pub static FUNCTION_LOG_STORE_RETENTION: LazyLock<Duration> = LazyLock::new(|| {
+    let days = env_config("FUNCTION_LOG_STORE_RETENTION_DAYS", 7);
+    Duration::from_days(days)
+});
+
+/// How many executions can be buffered waiting to be written to the durable
+/// function log. Executions are dropped from the durable log (but not from the
+/// in-memory log) if the buffer is full.
+pub static FUNCTION_LOG_STORE_BUFFER_SIZE: LazyLock<usize> =
+    LazyLock::new(|| env_config("FUNCTION_LOG_STORE_BUFFER_SIZE", 10000));
+
 /// What is the metrics aggregation window for UDF metrics?
 pub static UDF_METRICS_BUCKET_WIDTH: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("UDF_METRICS_BUCKET_WIDTH_SECS", 60)));
@@ -91,6 +108,12 @@ pub static UDF_METRICS_MAX_DURATION: LazyLock<Duration> = LazyLock::new(|| {
 pub static UDF_METRICS_SIGNIFICANT_FIGURES: LazyLock<u8> =
     LazyLock::new(|| env_config("UDF_METRICS_SIGNIFICANT_FIGURES", 2));
 
+/// Maximum number of distinct functions to export per-function Prometheus
+/// metrics for. Executions of functions beyond this limit are reported under
+/// a single `_other` series to bound label cardinality.
+pub static UDF_PROMETHEUS_MAX_FUNCTIONS: LazyLock<usize> =
+    LazyLock::new(|| env_config("UDF_PROMETHEUS_MAX_FUNCTIONS", 1000));
+
 /// How often to flush function activity reports to analytics (in seconds).
 pub static UDF_ANALYTICS_POLL_TIME: LazyLock<u64> =
     LazyLock::new(|| env_config("UDF_ANALYTICS_POLL_TIME", 60));
@@ -196,6 +219,11 @@ pub static HTTP_SERVER_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 pub static TRANSACTION_MAX_NUM_USER_WRITES: LazyLock<usize> =
     LazyLock::new(|| env_config("TRANSACTION_MAX_NUM_USER_WRITES", 16000));
 
+/// Max number of documents a single delete can update or delete through
+/// `onDelete` reference actions, including documents reached through cascades.
+pub static REFERENCE_ACTIONS_MAX_DOCUMENTS: LazyLock<usize> =
+    LazyLock::new(|| env_config("REFERENCE_ACTIONS_MAX_DOCUMENTS", 4096));
+
 /// Max size of user writes in a transaction, in bytes
 pub static TRANSACTION_MAX_USER_WRITE_SIZE_BYTES: LazyLock<usize> = LazyLock::new(|| {
     env_config("TRANSACTION_MAX_USER_WRITE_SIZE_BYTES", 1 << 24) // 16 MiB
@@ -356,6 +384,13 @@ pub static MAX_REPEATABLE_TIMESTAMP_IDLE_FREQUENCY: LazyLock<Duration> = LazyLoc
 pub static MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY", 5)));
 
+/// How often a read replica polls persistence for a new max_repeatable_ts
+/// and applies the leader's commits up to it. Commits only become visible on
+/// the replica once the leader has bumped max_repeatable_ts, see
+/// `MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY`.
+pub static FOLLOWER_LOG_TAIL_INTERVAL: LazyLock<Duration> =
+    LazyLock::new(|| Duration::from_millis(env_config("FOLLOWER_LOG_TAIL_INTERVAL_MS", 250)));
+
 /// The maximum delay between runs of retention, this is now only used for error
 /// backoff and the initial delay when backend is started.
 pub static MAX_RETENTION_DELAY_SECONDS: LazyLock<Duration> =
@@ -577,6 +612,48 @@ pub static MAX_EXPIRED_SNAPSHOT_AGE: LazyLock<Duration> = LazyLock::new(|| {
     Duration::from_days(days)
 });
 
+/// How often usage aggregated in memory is written to the `_usage_rollups`
+/// table, and how often usage quotas are reloaded.
+pub static USAGE_ROLLUP_FLUSH_INTERVAL: LazyLock<Duration> =
+    LazyLock::new(|| Duration::from_secs(env_config("USAGE_ROLLUP_FLUSH_INTERVAL_SECS", 60)));
+
+/// Hourly usage rollups older than this number of days are deleted.
+pub static USAGE_ROLLUP_RETENTION: LazyLock<Duration> = LazyLock::new(|| {
+    let days = env_config("USAGE_ROLLUP_RETENTION_DAYS", 90);
+    Duration::from_days(days)
+});
+
+/// Default number of documents a schema migration processes per transaction
+/// when the migration doesn't specify its own batch size.
+pub static SCHEMA_MIGRATION_DEFAULT_BATCH_SIZE: LazyLock<u64> =
+    LazyLock::new(|| env_config("SCHEMA_MIGRATION_DEFAULT_BATCH_SIZE", 100));
+
+/// Number of pushed code versions retained for instant rollback.
+pub static CODE_VERSION_RETENTION_COUNT: LazyLock<usize> =
+    LazyLock::new(|| env_config("CODE_VERSION_RETENTION_COUNT", 10));
+
+/// Maximum number of audit log events scanned for a single page of a filtered
+/// audit log listing. Pages that hit this limit may come back short, with a
+/// cursor to continue from.
+pub static AUDIT_LOG_QUERY_MAX_SCANNED: LazyLock<usize> =
+    LazyLock::new(|| env_config("AUDIT_LOG_QUERY_MAX_SCANNED", 4096));
+
+/// Maximum page size when listing audit log events.
+pub static AUDIT_LOG_QUERY_MAX_PAGE_SIZE: LazyLock<usize> =
+    LazyLock::new(|| env_config("AUDIT_LOG_QUERY_MAX_PAGE_SIZE", 1000));
+
+/// Whether public function rate limits keyed by IP use the client address from
+/// the `X-Forwarded-For` header instead of the connection's peer address. Only
+/// enable this behind a proxy that sets the header, or on a leader that read
+/// replicas forward calls to.
+pub static RATE_LIMIT_TRUST_FORWARDED_FOR: LazyLock<bool> =
+    LazyLock::new(|| env_config("RATE_LIMIT_TRUST_FORWARDED_FOR", false));
+
+/// Number of distinct keys a single rate limit policy tracks before it drops
+/// the keys whose buckets have fully refilled.
+pub static RATE_LIMIT_MAX_TRACKED_KEYS: LazyLock<usize> =
+    LazyLock::new(|| env_config("RATE_LIMIT_MAX_TRACKED_KEYS", 100_000));
+
 /// Number of chunks processed per second when calculating table summaries.
 pub static TABLE_SUMMARY_CHUNKS_PER_SECOND: LazyLock<NonZeroU32> = LazyLock::new(|| {
     env_config(
@@ -876,6 +953,12 @@ pub static AXIOM_MAX_ATTRIBUTES: LazyLock<usize> =
 pub static MULTI_SEGMENT_FULL_SCAN_THRESHOLD_KB: LazyLock<usize> =
     LazyLock::new(|| env_config("MULTI_SEGMENT_FULL_SCAN_THRESHOLD_KB", 50_000));
 
+/// When searching quantized vector segments, how many times the requested
+/// number of candidates to fetch using the quantized vectors before rescoring
+/// them against the original vectors.
+pub static VECTOR_QUANTIZATION_OVERSAMPLING: LazyLock<f64> =
+    LazyLock::new(|| env_config("VECTOR_QUANTIZATION_OVERSAMPLING", 2.0));
+
 /// The maximum size that we will compact any given set of segments to.
 /// Default to using 3 segments per 1.1 million vectors.
 pub static SEGMENT_MAX_SIZE_BYTES: LazyLock<u64> =
@@ -1017,6 +1100,54 @@ pub static AWS_LAMBDA_CLIENT_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 pub static NODE_ANALYZE_MAX_RETRIES: LazyLock<usize> =
     LazyLock::new(|| env_config("NODE_ANALYZE_MAX_RETRIES", 3));
 
+/// The maximum number of node worker processes the local node executor runs at
+/// once. Each worker runs one action at a time, so this also bounds the number
+/// of concurrent node actions on a self-hosted backend.
+pub static NODE_EXECUTOR_POOL_SIZE: LazyLock<usize> = LazyLock::new(|| {
+    env_config(
+        "NODE_EXECUTOR_POOL_SIZE",
+        *APPLICATION_MAX_CONCURRENT_NODE_ACTIONS,
+    )
+});
+
+/// The number of invocations a local node worker serves before it's replaced
+/// with a fresh process, bounding how long leaked state can accumulate.
+pub static NODE_EXECUTOR_MAX_INVOCATIONS_PER_WORKER: LazyLock<usize> =
+    LazyLock::new(|| env_config("NODE_EXECUTOR_MAX_INVOCATIONS_PER_WORKER", 100));
+
+/// The resident memory a local node worker may use while running a single
+/// invocation. Workers that exceed it are killed and replaced.
+pub static NODE_EXECUTOR_MEMORY_LIMIT_MB: LazyLock<u64> =
+    LazyLock::new(|| env_config("NODE_EXECUTOR_MEMORY_LIMIT_MB", 512));
+
+/// The CPU time a local node worker may use while running a single
+/// invocation. Workers that exceed it are killed and replaced.
+pub static NODE_EXECUTOR_CPU_TIME_LIMIT: LazyLock<Duration> = LazyLock::new(|| {
+    Duration::from_secs(env_config(
+        "NODE_EXECUTOR_CPU_TIME_LIMIT_SECS",
+        ACTION_USER_TIMEOUT.as_secs(),
+    ))
+});
+
+/// A cgroup v2 directory delegated to the backend, e.g.
+/// `/sys/fs/cgroup/convex-node`. When set, each local node worker runs in its
+/// own child cgroup with `memory.max` and `cpu.max` applied, so the kernel
+/// enforces the limits too. Requires the `memory` and `cpu` controllers to be
+/// enabled in the directory's `cgroup.subtree_control`.
+pub static NODE_EXECUTOR_CGROUP_PARENT: LazyLock<Option<String>> =
+    LazyLock::new(|| std::env::var("NODE_EXECUTOR_CGROUP_PARENT").ok());
+
+/// The share of a CPU core each local node worker's cgroup may use, in
+/// percent. Only applies when `NODE_EXECUTOR_CGROUP_PARENT` is set.
+pub static NODE_EXECUTOR_CGROUP_CPU_PERCENT: LazyLock<u64> =
+    LazyLock::new(|| env_config("NODE_EXECUTOR_CGROUP_CPU_PERCENT", 100));
+
+/// The maximum number of times to retry a request to a remote node executor.
+/// Action executions are only retried when the request can't have reached the
+/// executor, so they still run at most once.
+pub static REMOTE_NODE_EXECUTOR_MAX_RETRIES: LazyLock<u32> =
+    LazyLock::new(|| env_config("REMOTE_NODE_EXECUTOR_MAX_RETRIES", 3));
+
 /// The number of seconds backend should wait for requests to drain before
 /// shutting down after SIGINT.
 pub static BACKEND_REQUEST_DRAIN_TIMEOUT: LazyLock<Duration> =
@@ -501,7 +501,7 @@ pub enum SearchVersion {
 /// A query against a search index.
 ///
 /// Results are returned in relevancy order based on how well they match
-/// the search filter.
+/// the search filter, unless the query specifies an `order`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Search {
     /// The search index being queried.
@@ -511,9 +511,12 @@ pub struct Search {
     /// The filters to apply within the search index.
     ///
     /// This must include exactly one `Search` expression against the
-    /// index's `searchField` and any number of `Eq` expressions comparing
-    /// the index's `filterFields`.
+    /// index's `searchField` and any number of `Eq`, `Gt`, `Gte`, `Lt` and
+    /// `Lte` expressions comparing the index's `filterFields`.
     pub filters: Vec<SearchFilterExpression>,
+
+    /// Order the matching documents by a field instead of by relevance.
+    pub order: Option<SearchOrder>,
 }
 
 impl Search {
@@ -526,10 +529,21 @@ impl Search {
                 .into_iter()
                 .map(|f| f.to_internal())
                 .collect::<anyhow::Result<Vec<InternalSearchFilterExpression>>>()?,
+            order: self.order,
         })
     }
 }
 
+/// The order of a search query's results when they aren't in relevancy
+/// order. `field_path` must be one of the index's `filterFields` or
+/// `_creationTime`, and ties are broken by relevance.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SearchOrder {
+    pub field_path: FieldPath,
+    pub order: Order,
+}
+
 /// While `Search` is constructed and used at the query layer using TableNames,
 /// `InternalSearch` is used within transaction and searchlight and uses
 /// TableIds.
@@ -543,9 +557,12 @@ pub struct InternalSearch {
     /// The filters to apply within the search index.
     ///
     /// This must include exactly one `Search` expression against the
-    /// index's `searchField` and any number of `Eq` expressions comparing
-    /// the index's `filterFields`.
+    /// index's `searchField` and any number of `Eq`, `Gt`, `Gte`, `Lt` and
+    /// `Lte` expressions comparing the index's `filterFields`.
     pub filters: Vec<InternalSearchFilterExpression>,
+
+    /// Order the matching documents by a field instead of by relevance.
+    pub order: Option<SearchOrder>,
 }
 
 impl InternalSearch {
@@ -601,11 +618,45 @@ pub fn search_value_to_bytes(value: Option<&ConvexValue>) -> Vec<u8> {
     FilterValue::from_search_value(value).into()
 }
 
+/// The sort key of a value in a document, used for range filters and ordering
+/// on filter fields. Unlike `FilterValue`, this is never hashed, so comparing
+/// the bytes compares the values.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, From, Into)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct FilterSortKey(Vec<u8>);
+
+impl FilterSortKey {
+    pub fn from_search_value(value: Option<&ConvexValue>) -> Self {
+        match value {
+            Some(value) => Self(value.sort_key()),
+            None => Self(vec![UNDEFINED_TAG]),
+        }
+    }
+}
+
+impl Deref for FilterSortKey {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl HeapSize for FilterSortKey {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
 /// Filters to apply while querying a search index.
 #[derive(Clone, Debug, PartialEq)]
 pub enum SearchFilterExpression {
     Search(FieldPath, String),
     Eq(FieldPath, Option<ConvexValue>),
+    Gt(FieldPath, Option<ConvexValue>),
+    Gte(FieldPath, Option<ConvexValue>),
+    Lt(FieldPath, Option<ConvexValue>),
+    Lte(FieldPath, Option<ConvexValue>),
 }
 
 /// Filters to apply while querying a search index.
@@ -614,6 +665,10 @@ pub enum SearchFilterExpression {
 pub enum InternalSearchFilterExpression {
     Search(FieldPath, String),
     Eq(FieldPath, FilterValue),
+    Gt(FieldPath, FilterSortKey),
+    Gte(FieldPath, FilterSortKey),
+    Lt(FieldPath, FilterSortKey),
+    Lte(FieldPath, FilterSortKey),
 }
 
 impl SearchFilterExpression {
@@ -624,6 +679,22 @@ impl SearchFilterExpression {
                 field,
                 FilterValue::from_search_value(v.as_ref()),
             ),
+            Self::Gt(field, v) => InternalSearchFilterExpression::Gt(
+                field,
+                FilterSortKey::from_search_value(v.as_ref()),
+            ),
+            Self::Gte(field, v) => InternalSearchFilterExpression::Gte(
+                field,
+                FilterSortKey::from_search_value(v.as_ref()),
+            ),
+            Self::Lt(field, v) => InternalSearchFilterExpression::Lt(
+                field,
+                FilterSortKey::from_search_value(v.as_ref()),
+            ),
+            Self::Lte(field, v) => InternalSearchFilterExpression::Lte(
+                field,
+                FilterSortKey::from_search_value(v.as_ref()),
+            ),
         };
         Ok(expression)
     }
@@ -704,6 +775,7 @@ mod proptest {
             Order,
             QueryOperator,
             SearchFilterExpression,
+            SearchOrder,
         },
         types::IndexName,
     };
@@ -740,6 +812,14 @@ mod proptest {
                     .prop_map(|(field_path, s)| SearchFilterExpression::Search(field_path, s)),
                 any::<(FieldPath, Option<ConvexValue>)>()
                     .prop_map(|(field_path, v)| SearchFilterExpression::Eq(field_path, v)),
+                any::<(FieldPath, Option<ConvexValue>)>()
+                    .prop_map(|(field_path, v)| SearchFilterExpression::Gt(field_path, v)),
+                any::<(FieldPath, Option<ConvexValue>)>()
+                    .prop_map(|(field_path, v)| SearchFilterExpression::Gte(field_path, v)),
+                any::<(FieldPath, Option<ConvexValue>)>()
+                    .prop_map(|(field_path, v)| SearchFilterExpression::Lt(field_path, v)),
+                any::<(FieldPath, Option<ConvexValue>)>()
+                    .prop_map(|(field_path, v)| SearchFilterExpression::Lte(field_path, v)),
             ]
         }
     }
@@ -826,11 +906,13 @@ mod proptest {
             (
                 prop::collection::vec(any::<SearchFilterExpression>(), 0..4),
                 any::<IndexName>(),
+                any::<Option<SearchOrder>>(),
             )
-                .prop_map(|(search_filter_expressions, index_name)| Search {
+                .prop_map(|(search_filter_expressions, index_name, order)| Search {
                     table: index_name.table().clone(),
                     index_name,
                     filters: search_filter_expressions,
+                    order,
                 })
         }
     }
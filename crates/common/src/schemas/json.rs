@@ -29,10 +29,13 @@ use super::{
         LiteralValidator,
         ObjectValidator,
         Validator,
+        ValueConstraints,
     },
     DatabaseSchema,
     DocumentSchema,
     IndexSchema,
+    OnDeleteAction,
+    ReferenceSchema,
     VectorIndexSchema,
 };
 use crate::{
@@ -43,7 +46,16 @@ use crate::{
             search_field_not_unique,
             vector_field_not_unique,
         },
-        vector_index::VectorDimensions,
+        text_index::{
+            SearchFieldBoost,
+            TextIndexAnalyzer,
+        },
+        vector_index::{
+            VectorDimensions,
+            VectorDistanceMetric,
+            VectorHnswConfig,
+            VectorQuantization,
+        },
     },
     json::JsonSerializable,
     schemas::{
@@ -118,6 +130,8 @@ pub struct TableDefinitionJson {
     indexes: Vec<IndexSchemaJson>,
     search_indexes: Option<Vec<SearchIndexSchemaJson>>,
     vector_indexes: Option<Vec<VectorIndexSchemaJson>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    references: Option<Vec<ReferenceSchemaJson>>,
     document_type: Option<ValidatorJson>,
 }
 
@@ -245,16 +259,87 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
             }
         }
 
+        let mut references = BTreeMap::new();
+        for reference in j.references.unwrap_or_default() {
+            let reference = parse_reference(&table_name, document_type.as_ref(), reference)?;
+            if references.contains_key(&reference.field) {
+                anyhow::bail!(ErrorMetadata::bad_request(
+                    "InvalidReference",
+                    format!(
+                        "In table \"{table_name}\": field \"{}\" has more than one onDelete action",
+                        reference.field
+                    ),
+                ));
+            }
+            references.insert(reference.field.clone(), reference);
+        }
+
         Ok(Self {
             table_name,
             indexes,
             search_indexes,
             vector_indexes,
+            references,
             document_type,
         })
     }
 }
 
+fn parse_reference(
+    table_name: &TableName,
+    document_type: Option<&DocumentSchema>,
+    j: ReferenceSchemaJson,
+) -> anyhow::Result<ReferenceSchema> {
+    let invalid_reference = |msg: String| {
+        ErrorMetadata::bad_request(
+            "InvalidReference",
+            format!("In table \"{table_name}\": {msg}"),
+        )
+    };
+    let field: IdentifierFieldName = j.field.parse().map_err(|_| {
+        invalid_reference(format!(
+            "onDelete actions can only be declared on top-level fields, found \"{}\"",
+            j.field
+        ))
+    })?;
+    let on_delete: OnDeleteAction = j.on_delete.parse()?;
+    let Some((referenced_table, nullable)) =
+        document_type.and_then(|document_type| document_type.id_field_target(&field))
+    else {
+        anyhow::bail!(invalid_reference(format!(
+            "field \"{field}\" must be a `v.id()` field to declare an onDelete action"
+        )));
+    };
+    if on_delete == OnDeleteAction::SetNull && !nullable {
+        anyhow::bail!(invalid_reference(format!(
+            "field \"{field}\" must also accept `v.null()` to use onDelete \"setNull\""
+        )));
+    }
+    let reference = ReferenceSchema {
+        field,
+        referenced_table,
+        on_delete,
+    };
+    IndexName::new_reserved(table_name.clone(), reference.index_descriptor()?)?;
+    Ok(reference)
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceSchemaJson {
+    field: String,
+    on_delete: String,
+}
+
+impl From<ReferenceSchema> for ReferenceSchemaJson {
+    fn from(reference: ReferenceSchema) -> Self {
+        Self {
+            field: reference.field.to_string(),
+            on_delete: reference.on_delete.to_string(),
+        }
+    }
+}
+
 impl TryFrom<TableDefinition> for TableDefinitionJson {
     type Error = anyhow::Error;
 
@@ -264,6 +349,7 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
             indexes,
             search_indexes,
             vector_indexes,
+            references,
             document_type,
         }: TableDefinition,
     ) -> anyhow::Result<Self> {
@@ -285,11 +371,18 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
                 .map(VectorIndexSchemaJson::try_from)
                 .collect::<anyhow::Result<Vec<_>>>()?,
         );
+        let references = (!references.is_empty()).then(|| {
+            references
+                .into_values()
+                .map(ReferenceSchemaJson::from)
+                .collect()
+        });
         Ok(TableDefinitionJson {
             table_name,
             indexes,
             search_indexes,
             vector_indexes,
+            references,
             document_type,
         })
     }
@@ -300,6 +393,8 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
 pub struct IndexSchemaJson {
     index_descriptor: String,
     fields: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
 }
 
 impl JsonSerializable for IndexSchema {
@@ -327,6 +422,7 @@ impl TryFrom<IndexSchemaJson> for IndexSchema {
         Ok(Self {
             index_descriptor,
             fields,
+            unique: j.unique.unwrap_or(false),
         })
     }
 }
@@ -338,6 +434,7 @@ impl TryFrom<IndexSchema> for IndexSchemaJson {
         IndexSchema {
             index_descriptor,
             fields,
+            unique,
         }: IndexSchema,
     ) -> anyhow::Result<Self> {
         Ok(IndexSchemaJson {
@@ -346,6 +443,7 @@ impl TryFrom<IndexSchema> for IndexSchemaJson {
                 .into_iter()
                 .map(String::from)
                 .collect::<Vec<_>>(),
+            unique: unique.then_some(true),
         })
     }
 }
@@ -358,6 +456,23 @@ pub struct VectorIndexSchemaJson {
     dimensions: Option<u32>,
     dimension: Option<u32>,
     filter_fields: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metric: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quantization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hnsw: Option<VectorHnswSchemaJson>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorHnswSchemaJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    m: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ef_construct: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ef: Option<u32>,
 }
 
 impl JsonSerializable for VectorIndexSchema {
@@ -389,7 +504,36 @@ impl TryFrom<VectorIndexSchemaJson> for VectorIndexSchema {
                 None => anyhow::bail!("Missing dimensions field"),
             },
         };
-        Self::new(index_descriptor, vector_field, dimension, filter_fields)
+        let metric = j
+            .metric
+            .map(|m| m.parse::<VectorDistanceMetric>())
+            .transpose()
+            .map_err(|e| e.wrap_error_message(|s| format!("In index \"{index_descriptor}\": {s}")))?
+            .unwrap_or_default();
+        let quantization = j
+            .quantization
+            .map(|q| q.parse::<VectorQuantization>())
+            .transpose()
+            .map_err(|e| {
+                e.wrap_error_message(|s| format!("In index \"{index_descriptor}\": {s}"))
+            })?;
+        let hnsw = match j.hnsw {
+            Some(hnsw) => {
+                VectorHnswConfig::new(hnsw.m, hnsw.ef_construct, hnsw.ef).map_err(|e| {
+                    e.wrap_error_message(|s| format!("In index \"{index_descriptor}\": {s}"))
+                })?
+            },
+            None => VectorHnswConfig::default(),
+        };
+        Self::new(
+            index_descriptor,
+            vector_field,
+            dimension,
+            filter_fields,
+            metric,
+            quantization,
+            hnsw,
+        )
     }
 }
 
@@ -402,6 +546,9 @@ impl TryFrom<VectorIndexSchema> for VectorIndexSchemaJson {
             vector_field,
             dimension,
             filter_fields,
+            metric,
+            quantization,
+            hnsw,
             ..
         }: VectorIndexSchema,
     ) -> anyhow::Result<Self> {
@@ -414,6 +561,13 @@ impl TryFrom<VectorIndexSchema> for VectorIndexSchemaJson {
                 .into_iter()
                 .map(String::from)
                 .collect::<Vec<_>>(),
+            metric: (metric != VectorDistanceMetric::Cosine).then(|| metric.to_string()),
+            quantization: quantization.map(|q| q.to_string()),
+            hnsw: (!hnsw.is_default()).then(|| VectorHnswSchemaJson {
+                m: Some(hnsw.m()),
+                ef_construct: Some(hnsw.ef_construct()),
+                ef: hnsw.ef(),
+            }),
         })
     }
 }
@@ -423,7 +577,11 @@ impl TryFrom<VectorIndexSchema> for VectorIndexSchemaJson {
 pub struct SearchIndexSchemaJson {
     index_descriptor: String,
     search_field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extra_search_fields: Option<BTreeMap<String, f64>>,
     filter_fields: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analyzer: Option<String>,
 }
 
 impl JsonSerializable for SearchIndexSchema {
@@ -438,6 +596,20 @@ impl TryFrom<SearchIndexSchemaJson> for SearchIndexSchema {
         let search_field = j.search_field.parse().with_context(|| {
             index_validation_error::invalid_index_field(&index_descriptor, &j.search_field)
         })?;
+        let extra_search_fields = j
+            .extra_search_fields
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(f, boost)| {
+                let field_path = f.parse().with_context(|| {
+                    index_validation_error::invalid_index_field(&index_descriptor, &f)
+                })?;
+                let boost = SearchFieldBoost::new(boost).map_err(|e| {
+                    e.wrap_error_message(|s| format!("In index \"{index_descriptor}\": {s}"))
+                })?;
+                Ok((field_path, boost))
+            })
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
         let filter_fields = j
             .filter_fields
             .into_iter()
@@ -447,8 +619,20 @@ impl TryFrom<SearchIndexSchemaJson> for SearchIndexSchema {
                 })
             })
             .collect::<anyhow::Result<BTreeSet<_>>>()?;
-
-        Self::new(index_descriptor, search_field, filter_fields)
+        let analyzer = j
+            .analyzer
+            .map(|a| a.parse::<TextIndexAnalyzer>())
+            .transpose()
+            .map_err(|e| e.wrap_error_message(|s| format!("In index \"{index_descriptor}\": {s}")))?
+            .unwrap_or_default();
+
+        Self::new(
+            index_descriptor,
+            search_field,
+            extra_search_fields,
+            filter_fields,
+            analyzer,
+        )
     }
 }
 
@@ -459,17 +643,26 @@ impl TryFrom<SearchIndexSchema> for SearchIndexSchemaJson {
         SearchIndexSchema {
             index_descriptor,
             search_field,
+            extra_search_fields,
             filter_fields,
+            analyzer,
             ..
         }: SearchIndexSchema,
     ) -> anyhow::Result<Self> {
         Ok(SearchIndexSchemaJson {
             index_descriptor: index_descriptor.to_string(),
             search_field: String::from(search_field),
+            extra_search_fields: (!extra_search_fields.is_empty()).then(|| {
+                extra_search_fields
+                    .into_iter()
+                    .map(|(field_path, boost)| (String::from(field_path), boost.get()))
+                    .collect()
+            }),
             filter_fields: filter_fields
                 .into_iter()
                 .map(String::from)
                 .collect::<BTreeSet<_>>(),
+            analyzer: (analyzer != TextIndexAnalyzer::Standard).then(|| analyzer.to_string()),
         })
     }
 }
@@ -567,11 +760,23 @@ impl TryFrom<FieldValidator> for FieldTypeJson {
 #[serde(tag = "type")]
 pub enum ValidatorJson {
     Null,
-    Number,
-    Bigint,
+    Number {
+        #[serde(flatten)]
+        constraints: ValueConstraintsJson,
+    },
+    Bigint {
+        #[serde(flatten)]
+        constraints: ValueConstraintsJson,
+    },
     Boolean,
-    String,
-    Bytes,
+    String {
+        #[serde(flatten)]
+        constraints: ValueConstraintsJson,
+    },
+    Bytes {
+        #[serde(flatten)]
+        constraints: ValueConstraintsJson,
+    },
     Any,
     Literal {
         value: JsonValue,
@@ -582,6 +787,8 @@ pub enum ValidatorJson {
     },
     Array {
         value: Box<ValidatorJson>,
+        #[serde(flatten)]
+        constraints: ValueConstraintsJson,
     },
     Set {
         value: Box<ValidatorJson>,
@@ -606,21 +813,82 @@ impl JsonSerializable for Validator {
     type Json = ValidatorJson;
 }
 
+/// The optional refinements on `v.number()`, `v.int64()`, `v.string()`,
+/// `v.bytes()`, and `v.array()`.
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueConstraintsJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min: Option<JsonValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max: Option<JsonValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+}
+
+impl ValueConstraintsJson {
+    fn constrain(self, validator: Validator) -> anyhow::Result<Validator> {
+        let bound = |bound: Option<JsonValue>| bound.map(ConvexValue::try_from).transpose();
+        let constraints = ValueConstraints {
+            min: bound(self.min)?,
+            max: bound(self.max)?,
+            min_length: self.min_length,
+            max_length: self.max_length,
+            pattern: self.pattern.as_deref().map(str::parse).transpose()?,
+            format: self.format.as_deref().map(str::parse).transpose()?,
+        };
+        Validator::constrained(validator, constraints)
+    }
+}
+
+impl TryFrom<ValueConstraints> for ValueConstraintsJson {
+    type Error = anyhow::Error;
+
+    fn try_from(c: ValueConstraints) -> anyhow::Result<Self> {
+        let bound = |bound: Option<ConvexValue>| -> anyhow::Result<Option<JsonValue>> {
+            Ok(match bound {
+                Some(ConvexValue::Float64(f)) => Some(JsonValue::Number(
+                    serde_json::Number::from_f64(f)
+                        .with_context(|| format!("Validator bound {f} isn't a JSON number"))?,
+                )),
+                Some(bound) => Some(JsonValue::from(bound)),
+                None => None,
+            })
+        };
+        Ok(Self {
+            min: bound(c.min)?,
+            max: bound(c.max)?,
+            min_length: c.min_length,
+            max_length: c.max_length,
+            pattern: c.pattern.map(|p| p.as_str().to_string()),
+            format: c.format.map(|f| f.to_string()),
+        })
+    }
+}
+
 impl TryFrom<ValidatorJson> for Validator {
     type Error = anyhow::Error;
 
     fn try_from(s: ValidatorJson) -> anyhow::Result<Self> {
         match s {
             ValidatorJson::Null => Ok(Validator::Null),
-            ValidatorJson::Number => Ok(Validator::Float64),
-            ValidatorJson::Bigint => Ok(Validator::Int64),
+            ValidatorJson::Number { constraints } => constraints.constrain(Validator::Float64),
+            ValidatorJson::Bigint { constraints } => constraints.constrain(Validator::Int64),
             ValidatorJson::Boolean => Ok(Validator::Boolean),
-            ValidatorJson::String => Ok(Validator::String),
-            ValidatorJson::Bytes => Ok(Validator::Bytes),
+            ValidatorJson::String { constraints } => constraints.constrain(Validator::String),
+            ValidatorJson::Bytes { constraints } => constraints.constrain(Validator::Bytes),
             ValidatorJson::Any => Ok(Validator::Any),
             ValidatorJson::Literal { value } => Ok(Validator::Literal(value.try_into()?)),
             ValidatorJson::Id { table_name } => Ok(Validator::Id(table_name.parse()?)),
-            ValidatorJson::Array { value } => Ok(Validator::Array(Box::new((*value).try_into()?))),
+            ValidatorJson::Array { value, constraints } => {
+                constraints.constrain(Validator::Array(Box::new((*value).try_into()?)))
+            },
             ValidatorJson::Set { value } => Ok(Validator::Set(Box::new((*value).try_into()?))),
             ValidatorJson::Map { keys, values } => Ok(Validator::Map(
                 Box::new((*keys).try_into()?),
@@ -678,16 +946,25 @@ impl TryFrom<Validator> for ValidatorJson {
                 table_name: table_name.to_string(),
             },
             Validator::Null => ValidatorJson::Null,
-            Validator::Float64 => ValidatorJson::Number,
-            Validator::Int64 => ValidatorJson::Bigint,
+            Validator::Float64 => ValidatorJson::Number {
+                constraints: ValueConstraintsJson::default(),
+            },
+            Validator::Int64 => ValidatorJson::Bigint {
+                constraints: ValueConstraintsJson::default(),
+            },
             Validator::Boolean => ValidatorJson::Boolean,
-            Validator::String => ValidatorJson::String,
-            Validator::Bytes => ValidatorJson::Bytes,
+            Validator::String => ValidatorJson::String {
+                constraints: ValueConstraintsJson::default(),
+            },
+            Validator::Bytes => ValidatorJson::Bytes {
+                constraints: ValueConstraintsJson::default(),
+            },
             Validator::Literal(literal) => ValidatorJson::Literal {
                 value: literal.try_into()?,
             },
             Validator::Array(t) => ValidatorJson::Array {
                 value: Box::new(ValidatorJson::try_from(*t)?),
+                constraints: ValueConstraintsJson::default(),
             },
             Validator::Set(t) => ValidatorJson::Set {
                 value: Box::new(ValidatorJson::try_from(*t)?),
@@ -713,6 +990,20 @@ impl TryFrom<Validator> for ValidatorJson {
                     .collect::<anyhow::Result<Vec<_>>>()?,
             },
             Validator::Any => ValidatorJson::Any,
+            Validator::Constrained(validator, c) => {
+                let mut json = ValidatorJson::try_from(*validator)?;
+                match &mut json {
+                    ValidatorJson::Number { constraints }
+                    | ValidatorJson::Bigint { constraints }
+                    | ValidatorJson::String { constraints }
+                    | ValidatorJson::Bytes { constraints }
+                    | ValidatorJson::Array { constraints, .. } => {
+                        *constraints = c.try_into()?;
+                    },
+                    _ => anyhow::bail!("Constraints on unsupported validator {json:?}"),
+                }
+                json
+            },
         })
     }
 }
@@ -7,6 +7,7 @@ use std::{
     fmt::Display,
     iter,
     marker::PhantomData,
+    str::FromStr,
 };
 
 use errors::ErrorMetadata;
@@ -41,7 +42,17 @@ use crate::{
     bootstrap_model::index::{
         database_index::IndexedFields,
         index_validation_error,
-        vector_index::VectorDimensions,
+        text_index::{
+            SearchFieldBoost,
+            TextIndexAnalyzer,
+        },
+        vector_index::{
+            VectorDimensions,
+            VectorDistanceMetric,
+            VectorHnswConfig,
+            VectorQuantization,
+        },
+        MAX_TEXT_INDEX_EXTRA_SEARCH_FIELDS_SIZE,
         MAX_TEXT_INDEX_FILTER_FIELDS_SIZE,
         MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE,
     },
@@ -92,6 +103,29 @@ pub enum SchemaValidationError {
         table_in_schema: TableName,
         table_name: TableName,
     },
+
+    #[display(
+        "Documents with IDs \"{first_id}\" and \"{second_id}\" in table \"{table_name}\" have the \
+         same values for unique index \"{index_descriptor}\""
+    )]
+    DuplicateUniqueIndexKey {
+        table_name: TableName,
+        index_descriptor: IndexDescriptor,
+        first_id: DeveloperDocumentId,
+        second_id: DeveloperDocumentId,
+    },
+
+    #[display(
+        "Document with ID \"{id}\" in table \"{table_name}\" has field \"{field}\" set to \
+         \"{referenced_id}\", which doesn't exist in table \"{referenced_table}\""
+    )]
+    DanglingReference {
+        table_name: TableName,
+        id: DeveloperDocumentId,
+        field: IdentifierFieldName,
+        referenced_table: TableName,
+        referenced_id: String,
+    },
 }
 
 #[derive(derive_more::Display, Debug, Clone, PartialEq)]
@@ -171,6 +205,7 @@ macro_rules! db_schema {
                         indexes: Default::default(),
                         search_indexes: Default::default(),
                         vector_indexes: Default::default(),
+                        references: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -202,6 +237,7 @@ macro_rules! db_schema_not_validated {
                         indexes: Default::default(),
                         search_indexes: Default::default(),
                         vector_indexes: Default::default(),
+                        references: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -247,6 +283,9 @@ macro_rules! db_schema_with_vector_indexes {
                                 value::FieldPath::from_str($vector_field)?,
                                 1536u32.try_into()?,
                                 Default::default(),
+                                Default::default(),
+                                None,
+                                Default::default(),
                             )?,
                         );
                     )*
@@ -255,6 +294,7 @@ macro_rules! db_schema_with_vector_indexes {
                         indexes: Default::default(),
                         search_indexes: Default::default(),
                         vector_indexes,
+                        references: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -269,6 +309,73 @@ macro_rules! db_schema_with_vector_indexes {
 }
 
 impl DatabaseSchema {
+    /// References from any table in the schema to documents in `table_name`.
+    pub fn references_to<'a>(
+        &'a self,
+        table_name: &'a TableName,
+    ) -> impl Iterator<Item = (&'a TableName, &'a ReferenceSchema)> + 'a {
+        self.tables
+            .iter()
+            .flat_map(move |(referencing_table, table)| {
+                table
+                    .references
+                    .values()
+                    .filter(move |reference| reference.referenced_table == *table_name)
+                    .map(move |reference| (referencing_table, reference))
+            })
+    }
+
+    /// References in `new_schema` on fields that `active_schema` doesn't
+    /// already declare, so existing documents must be checked for dangling
+    /// IDs.
+    pub fn references_to_validate<'a>(
+        new_schema: &'a DatabaseSchema,
+        active_schema: Option<&DatabaseSchema>,
+    ) -> Vec<(&'a TableName, &'a ReferenceSchema)> {
+        new_schema
+            .tables
+            .iter()
+            .flat_map(|(table_name, table_definition)| {
+                table_definition
+                    .references
+                    .values()
+                    .filter(move |reference| {
+                        let active_reference = active_schema
+                            .and_then(|schema| schema.tables.get(table_name))
+                            .and_then(|table| table.references.get(&reference.field));
+                        active_reference.map(|r| &r.referenced_table)
+                            != Some(&reference.referenced_table)
+                    })
+                    .map(move |reference| (table_name, reference))
+            })
+            .collect()
+    }
+
+    /// Unique indexes in `new_schema` that aren't already enforced by
+    /// `active_schema`, so existing documents must be checked for duplicates.
+    pub fn unique_indexes_to_validate<'a>(
+        new_schema: &'a DatabaseSchema,
+        active_schema: Option<&DatabaseSchema>,
+    ) -> Vec<(&'a TableName, &'a IndexSchema)> {
+        new_schema
+            .tables
+            .iter()
+            .flat_map(|(table_name, table_definition)| {
+                table_definition
+                    .indexes
+                    .values()
+                    .filter(|index| index.unique)
+                    .filter(move |index| {
+                        let active_index = active_schema
+                            .and_then(|schema| schema.tables.get(table_name))
+                            .and_then(|table| table.indexes.get(&index.index_descriptor));
+                        active_index != Some(*index)
+                    })
+                    .map(move |index| (table_name, index))
+            })
+            .collect()
+    }
+
     pub fn tables_to_validate<'a, C: ShapeConfig, S: ShapeCounter, F>(
         new_schema: &'a DatabaseSchema,
         active_schema: Option<&DatabaseSchema>,
@@ -534,6 +641,7 @@ pub struct TableDefinition {
     pub indexes: BTreeMap<IndexDescriptor, IndexSchema>,
     pub search_indexes: BTreeMap<IndexDescriptor, SearchIndexSchema>,
     pub vector_indexes: BTreeMap<IndexDescriptor, VectorIndexSchema>,
+    pub references: BTreeMap<IdentifierFieldName, ReferenceSchema>,
     pub document_type: Option<DocumentSchema>, /* FIXME: `Option` could be removed here, since
                                                 * `None` is handled the same way as
                                                 * `Some(DocumentSchema::Any)`. */
@@ -560,6 +668,16 @@ impl TableDefinition {
                     (index_descriptor, (&search_index_schema.search_field))
                 });
 
+        let search_index_extra_search_fields =
+            self.search_indexes
+                .iter()
+                .flat_map(|(index_descriptor, search_index_schema)| {
+                    search_index_schema
+                        .extra_search_fields
+                        .keys()
+                        .map(move |field_path| (index_descriptor, field_path))
+                });
+
         let search_index_filter_fields =
             self.search_indexes
                 .iter()
@@ -574,6 +692,7 @@ impl TableDefinition {
 
         index_fields
             .chain(search_index_fields)
+            .chain(search_index_extra_search_fields)
             .chain(search_index_filter_fields)
             .chain(vector_index_fields)
     }
@@ -631,6 +750,7 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
                                 .into_iter()
                                 .map(|i| (i.index_descriptor.clone(), i))
                                 .collect(),
+                            references: BTreeMap::new(),
                             document_type,
                         })
                     } else {
@@ -641,11 +761,72 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
     }
 }
 
+/// Prefix of the reserved indexes maintained on referencing tables to look up
+/// the documents that point at a given ID.
+pub const REFERENCE_INDEX_PREFIX: &str = "_ref_";
+
+/// What happens to documents that reference a deleted document.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, derive_more::Display)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum OnDeleteAction {
+    /// Fail the delete while any referencing documents exist.
+    #[display("restrict")]
+    Restrict,
+    /// Delete the referencing documents in the same transaction.
+    #[display("cascade")]
+    Cascade,
+    /// Set the referencing field to `null`.
+    #[display("setNull")]
+    SetNull,
+}
+
+impl FromStr for OnDeleteAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "restrict" => Ok(Self::Restrict),
+            "cascade" => Ok(Self::Cascade),
+            "setNull" => Ok(Self::SetNull),
+            _ => anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidOnDeleteAction",
+                format!(
+                    "Invalid onDelete action \"{s}\". Expected \"restrict\", \"cascade\" or \
+                     \"setNull\"."
+                ),
+            )),
+        }
+    }
+}
+
+/// A top-level `v.id()` field whose referenced document's deletion is
+/// handled according to `on_delete`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferenceSchema {
+    pub field: IdentifierFieldName,
+    pub referenced_table: TableName,
+    pub on_delete: OnDeleteAction,
+}
+
+impl ReferenceSchema {
+    /// The reserved index on the referencing table over `field`.
+    pub fn index_descriptor(&self) -> anyhow::Result<IndexDescriptor> {
+        IndexDescriptor::new(format!("{REFERENCE_INDEX_PREFIX}{}", self.field))
+    }
+
+    pub fn field_path(&self) -> anyhow::Result<FieldPath> {
+        FieldPath::new(vec![self.field.clone()])
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct IndexSchema {
     pub index_descriptor: IndexDescriptor,
     pub fields: IndexedFields,
+    /// Whether the index enforces that no two documents share the same values
+    /// for `fields`.
+    pub unique: bool,
 }
 
 impl Display for IndexSchema {
@@ -659,11 +840,18 @@ impl Display for IndexSchema {
 pub struct SearchIndexSchema {
     pub index_descriptor: IndexDescriptor,
     pub search_field: FieldPath,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "prop::collection::btree_map(any::<FieldPath>(), \
+                             any::<SearchFieldBoost>(), 0..4)")
+    )]
+    pub extra_search_fields: BTreeMap<FieldPath, SearchFieldBoost>,
     #[cfg_attr(
         any(test, feature = "testing"),
         proptest(strategy = "prop::collection::btree_set(any::<FieldPath>(), 0..8)")
     )]
     pub filter_fields: BTreeSet<FieldPath>,
+    pub analyzer: TextIndexAnalyzer,
 
     // Private field to force all creations to go through the constructor.
     _pd: PhantomData<()>,
@@ -673,17 +861,32 @@ impl SearchIndexSchema {
     pub fn new(
         index_descriptor: IndexDescriptor,
         search_field: FieldPath,
+        extra_search_fields: BTreeMap<FieldPath, SearchFieldBoost>,
         filter_fields: BTreeSet<FieldPath>,
+        analyzer: TextIndexAnalyzer,
     ) -> anyhow::Result<Self> {
         if filter_fields.len() > MAX_TEXT_INDEX_FILTER_FIELDS_SIZE {
             anyhow::bail!(index_validation_error::too_many_filter_fields(
                 MAX_TEXT_INDEX_FILTER_FIELDS_SIZE
             ));
         }
+        if extra_search_fields.len() > MAX_TEXT_INDEX_EXTRA_SEARCH_FIELDS_SIZE {
+            anyhow::bail!(index_validation_error::too_many_extra_search_fields(
+                MAX_TEXT_INDEX_EXTRA_SEARCH_FIELDS_SIZE
+            ));
+        }
+        if extra_search_fields.contains_key(&search_field) {
+            anyhow::bail!(index_validation_error::extra_search_field_is_search_field(
+                &index_descriptor,
+                &search_field
+            ));
+        }
         Ok(Self {
             index_descriptor,
             search_field,
+            extra_search_fields,
             filter_fields,
+            analyzer,
             _pd: PhantomData,
         })
     }
@@ -700,6 +903,9 @@ pub struct VectorIndexSchema {
         proptest(strategy = "prop::collection::btree_set(any::<FieldPath>(), 0..8)")
     )]
     pub filter_fields: BTreeSet<FieldPath>,
+    pub metric: VectorDistanceMetric,
+    pub quantization: Option<VectorQuantization>,
+    pub hnsw: VectorHnswConfig,
 
     // Private field to force all creations to go through the constructor.
     _pd: PhantomData<()>,
@@ -711,6 +917,9 @@ impl VectorIndexSchema {
         vector_field: FieldPath,
         dimension: VectorDimensions,
         filter_fields: BTreeSet<FieldPath>,
+        metric: VectorDistanceMetric,
+        quantization: Option<VectorQuantization>,
+        hnsw: VectorHnswConfig,
     ) -> anyhow::Result<Self> {
         if filter_fields.len() > MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE {
             anyhow::bail!(index_validation_error::too_many_filter_fields(
@@ -722,6 +931,9 @@ impl VectorIndexSchema {
             vector_field,
             dimension,
             filter_fields,
+            metric,
+            quantization,
+            hnsw,
             _pd: PhantomData,
         })
     }
@@ -817,6 +1029,32 @@ impl DocumentSchema {
         }
     }
 
+    /// If `field` is a top-level `v.id()` field in every object that has it,
+    /// returns the referenced table and whether all of them also accept
+    /// `null`.
+    pub fn id_field_target(&self, field: &IdentifierFieldName) -> Option<(TableName, bool)> {
+        let DocumentSchema::Union(validators) = self else {
+            return None;
+        };
+        let mut target: Option<(TableName, bool)> = None;
+        for validator in validators {
+            let Some(field_validator) = validator.0.get(field) else {
+                continue;
+            };
+            let (table_name, nullable) = field_validator.validator.id_target()?;
+            match &mut target {
+                None => target = Some((table_name, nullable)),
+                Some((existing, existing_nullable)) => {
+                    if *existing != table_name {
+                        return None;
+                    }
+                    *existing_nullable &= nullable;
+                },
+            }
+        }
+        target
+    }
+
     /// Returns the field names from top level objects in the schema that are
     /// optional.
     pub fn optional_top_level_fields(&self) -> HashSet<IdentifierFieldName> {
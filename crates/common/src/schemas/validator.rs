@@ -8,6 +8,8 @@ use std::{
         Display,
     },
     iter,
+    str::FromStr,
+    sync::LazyLock,
 };
 
 use errors::ErrorMetadata;
@@ -75,6 +77,9 @@ pub enum Validator {
     Object(ObjectValidator),
     Union(Vec<Validator>),
     Any,
+    /// A `v.float64()`, `v.int64()`, `v.string()`, `v.bytes()` or `v.array()`
+    /// validator whose values are further restricted by [`ValueConstraints`].
+    Constrained(Box<Validator>, ValueConstraints),
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -148,6 +153,14 @@ impl Display for Validator {
                 display_sequence(f, ["v.union(", ")"], validators.iter())
             },
             Validator::Any => write!(f, "v.any()"),
+            Validator::Constrained(validator, constraints) => match &**validator {
+                Validator::Float64 => write!(f, "v.float64({constraints})"),
+                Validator::Int64 => write!(f, "v.int64({constraints})"),
+                Validator::String => write!(f, "v.string({constraints})"),
+                Validator::Bytes => write!(f, "v.bytes({constraints})"),
+                Validator::Array(element) => write!(f, "v.array({element}, {constraints})"),
+                validator => write!(f, "{validator}"),
+            },
         }
     }
 }
@@ -318,6 +331,21 @@ impl Validator {
                 });
             },
             (Validator::Any, _) => return Ok(()),
+            (Validator::Constrained(validator, constraints), value) => {
+                validator.check_value_internal(
+                    value,
+                    all_tables_number_to_name,
+                    context.clone(),
+                )?;
+                if let Some(constraint) = constraints.violated_by(value) {
+                    return Err(ValidationError::ConstraintViolation {
+                        value: value.clone(),
+                        constraint,
+                        validator: self.clone(),
+                        context,
+                    });
+                }
+            },
             (..) => {
                 return Err(ValidationError::NoMatch {
                     value: value.clone(),
@@ -464,6 +492,9 @@ impl Validator {
             // Identical types
             (v1, v2) if v1 == v2 => true,
 
+            // Constraints only ever narrow the set of accepted values
+            (Validator::Constrained(validator, _), _) => validator.is_subset(superset),
+
             // Types that are subsets of other ones
             (_, Validator::Any)
             | (Validator::Literal(LiteralValidator::String(_)), Validator::String)
@@ -519,6 +550,104 @@ impl Validator {
             Validator::Union(unions) => unions
                 .iter()
                 .any(|v| v.is_string_subtype_with_string_literal()),
+            Validator::Constrained(validator, _) => {
+                validator.is_string_subtype_with_string_literal()
+            },
+        }
+    }
+
+    /// Wraps `validator` in [`ValueConstraints`], checking that each
+    /// constraint applies to the validator's type.
+    pub fn constrained(
+        validator: Validator,
+        constraints: ValueConstraints,
+    ) -> anyhow::Result<Self> {
+        if constraints == ValueConstraints::default() {
+            return Ok(validator);
+        }
+        let invalid = |msg: String| {
+            anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidValidatorConstraint",
+                format!("Invalid constraints {constraints} on `{validator}`: {msg}"),
+            ))
+        };
+        let (numeric, lengthed, textual) = match &validator {
+            Validator::Float64 | Validator::Int64 => (true, false, false),
+            Validator::String => (false, true, true),
+            Validator::Bytes | Validator::Array(_) => (false, true, false),
+            _ => {
+                return Err(invalid(
+                    "constraints aren't supported on this type".to_string(),
+                ))
+            },
+        };
+        if !numeric && (constraints.min.is_some() || constraints.max.is_some()) {
+            return Err(invalid("`min` and `max` only apply to numbers".to_string()));
+        }
+        for bound in [&constraints.min, &constraints.max].into_iter().flatten() {
+            match (&validator, bound) {
+                (Validator::Float64, ConvexValue::Float64(f)) if !f.is_nan() => {},
+                (Validator::Int64, ConvexValue::Int64(_)) => {},
+                _ => return Err(invalid(format!("bound {bound} doesn't match the type"))),
+            }
+        }
+        if let (Some(min), Some(max)) = (&constraints.min, &constraints.max)
+            && min > max
+        {
+            return Err(invalid("`min` is greater than `max`".to_string()));
+        }
+        if !lengthed && (constraints.min_length.is_some() || constraints.max_length.is_some()) {
+            return Err(invalid(
+                "`minLength` and `maxLength` only apply to strings, bytes and arrays".to_string(),
+            ));
+        }
+        if let (Some(min_length), Some(max_length)) =
+            (constraints.min_length, constraints.max_length)
+            && min_length > max_length
+        {
+            return Err(invalid(
+                "`minLength` is greater than `maxLength`".to_string(),
+            ));
+        }
+        if !textual && (constraints.pattern.is_some() || constraints.format.is_some()) {
+            return Err(invalid(
+                "`pattern` and `format` only apply to strings".to_string(),
+            ));
+        }
+        Ok(Validator::Constrained(Box::new(validator), constraints))
+    }
+
+    /// Strips any [`ValueConstraints`] from this validator.
+    pub fn unconstrained(&self) -> &Validator {
+        match self {
+            Validator::Constrained(validator, _) => validator,
+            validator => validator,
+        }
+    }
+
+    /// Returns the referenced table if this is a `v.id()` validator, possibly
+    /// in a union with `v.null()`, along with whether `null` is allowed.
+    pub fn id_target(&self) -> Option<(TableName, bool)> {
+        match self {
+            Validator::Id(table_name) => Some((table_name.clone(), false)),
+            Validator::Union(options) => {
+                let nullable = options.iter().any(|o| matches!(o, Validator::Null));
+                let mut tables = options
+                    .iter()
+                    .filter(|o| !matches!(o, Validator::Null))
+                    .map(|o| match o {
+                        Validator::Id(table_name) => Some(table_name),
+                        _ => None,
+                    });
+                let first = tables.next()??;
+                for table_name in tables {
+                    if table_name? != first {
+                        return None;
+                    }
+                }
+                Some((first.clone(), nullable))
+            },
+            _ => None,
         }
     }
 
@@ -565,8 +694,12 @@ impl Validator {
     fn is_valid_vector_validator(validator: &Validator) -> bool {
         match validator {
             Validator::Array(validator) => {
-                matches!(**validator, Validator::Float64 | Validator::Any)
+                matches!(
+                    validator.unconstrained(),
+                    Validator::Float64 | Validator::Any
+                )
             },
+            Validator::Constrained(validator, _) => Self::is_valid_vector_validator(validator),
             Validator::Any => true,
             Validator::Union(validators) => validators.iter().any(Self::is_valid_vector_validator),
             _ => false,
@@ -609,7 +742,7 @@ impl Validator {
             // Values that map to `any`
             | Validator::Record(_, _)
             | Validator::Any => Ok(()),
-            Validator::Array(element_validator) => {
+            Validator::Array(element_validator) | Validator::Constrained(element_validator, _) => {
                 element_validator.ensure_supported_for_streaming_export()
             },
             Validator::Set(element_validator) => {
@@ -686,6 +819,10 @@ impl Validator {
                 json_schemas::union(options)
             },
             Validator::Any => json_schemas::any(),
+            Validator::Constrained(validator, constraints) => json_schemas::with_keywords(
+                validator.to_json_schema(value_format),
+                constraints.json_schema_keywords(validator),
+            ),
         };
         json_schema
     }
@@ -700,7 +837,7 @@ impl Validator {
                         yield table_name;
                     }
                 },
-                Self::Array(item) | Self::Set(item) => {
+                Self::Array(item) | Self::Set(item) | Self::Constrained(item, _) => {
                     for table_name in item.foreign_keys() {
                         yield table_name;
                     }
@@ -742,7 +879,7 @@ impl Validator {
             | Self::Literal(_)
             | Self::Any => false,
             Self::Set(_) | Self::Map(..) => true,
-            Self::Array(a) => a.has_map_or_set(),
+            Self::Array(a) | Self::Constrained(a, _) => a.has_map_or_set(),
             Self::Record(k, v) => k.has_map_or_set() || v.has_map_or_set(),
             Self::Object(o) => o.has_map_or_set(),
             Self::Union(u) => u.iter().any(|o| o.has_map_or_set()),
@@ -764,7 +901,8 @@ impl Validator {
             | Validator::Set(_)
             | Validator::Record(..)
             | Validator::Map(..)
-            | Validator::Any => self,
+            | Validator::Any
+            | Validator::Constrained(..) => self,
             Validator::Object(o) => Validator::Object(o.filter_system_fields()),
             Validator::Union(validators) => Validator::Union(
                 validators
@@ -878,6 +1016,249 @@ impl TryFrom<ConvexValue> for LiteralValidator {
     }
 }
 
+/// Refinements on the values a [`Validator`] accepts beyond their type, e.g.
+/// `v.string({ minLength: 1, format: "email" })`. Every bound is inclusive.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ValueConstraints {
+    /// Lower bound for `v.float64()` (as a `Float64`) or `v.int64()` (as an
+    /// `Int64`).
+    pub min: Option<ConvexValue>,
+    pub max: Option<ConvexValue>,
+    /// Bounds on the length of strings (in UTF-16 code units, matching
+    /// JavaScript's `length`), bytes, and arrays.
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    /// A regular expression that strings must contain a match for.
+    pub pattern: Option<StringPattern>,
+    pub format: Option<StringFormat>,
+}
+
+impl ValueConstraints {
+    /// Returns the first constraint that `value` doesn't satisfy. `value` must
+    /// already match the constrained validator's type.
+    pub fn violated_by(&self, value: &ConvexValue) -> Option<String> {
+        if let Some(min) = &self.min {
+            let satisfied = match (value, min) {
+                (ConvexValue::Float64(v), ConvexValue::Float64(min)) => v >= min,
+                (ConvexValue::Int64(v), ConvexValue::Int64(min)) => v >= min,
+                _ => false,
+            };
+            if !satisfied {
+                return Some(format!("min: {}", display_bound(min)));
+            }
+        }
+        if let Some(max) = &self.max {
+            let satisfied = match (value, max) {
+                (ConvexValue::Float64(v), ConvexValue::Float64(max)) => v <= max,
+                (ConvexValue::Int64(v), ConvexValue::Int64(max)) => v <= max,
+                _ => false,
+            };
+            if !satisfied {
+                return Some(format!("max: {}", display_bound(max)));
+            }
+        }
+        if self.min_length.is_some() || self.max_length.is_some() {
+            let length = match value {
+                ConvexValue::String(s) => s.encode_utf16().count() as u64,
+                ConvexValue::Bytes(b) => b.len() as u64,
+                ConvexValue::Array(a) => a.len() as u64,
+                _ => return Some("minLength/maxLength".to_string()),
+            };
+            if let Some(min_length) = self.min_length
+                && length < min_length
+            {
+                return Some(format!("minLength: {min_length}"));
+            }
+            if let Some(max_length) = self.max_length
+                && length > max_length
+            {
+                return Some(format!("maxLength: {max_length}"));
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            if !matches!(value, ConvexValue::String(s) if pattern.0.is_match(s)) {
+                return Some(format!("pattern: {pattern}"));
+            }
+        }
+        if let Some(format) = &self.format {
+            if !matches!(value, ConvexValue::String(s) if format.matches(s)) {
+                return Some(format!("format: \"{format}\""));
+            }
+        }
+        None
+    }
+
+    /// JSON Schema keywords for these constraints on `validator`. Constraints
+    /// on values that JSON Schema can't see (`v.int64()` and `v.bytes()`,
+    /// which are encoded as strings or objects) are omitted.
+    fn json_schema_keywords(&self, validator: &Validator) -> serde_json::Map<String, JsonValue> {
+        let mut keywords = serde_json::Map::new();
+        match validator {
+            Validator::Float64 => {
+                for (keyword, bound) in [("minimum", &self.min), ("maximum", &self.max)] {
+                    if let Some(ConvexValue::Float64(f)) = bound
+                        && let Some(n) = Number::from_f64(*f)
+                    {
+                        keywords.insert(keyword.to_string(), JsonValue::Number(n));
+                    }
+                }
+            },
+            Validator::String => {
+                if let Some(min_length) = self.min_length {
+                    keywords.insert("minLength".to_string(), min_length.into());
+                }
+                if let Some(max_length) = self.max_length {
+                    keywords.insert("maxLength".to_string(), max_length.into());
+                }
+                if let Some(pattern) = &self.pattern {
+                    keywords.insert("pattern".to_string(), pattern.to_string().into());
+                }
+                if let Some(format) = &self.format {
+                    keywords.insert("format".to_string(), format.json_schema_format().into());
+                }
+            },
+            Validator::Array(_) => {
+                if let Some(min_length) = self.min_length {
+                    keywords.insert("minItems".to_string(), min_length.into());
+                }
+                if let Some(max_length) = self.max_length {
+                    keywords.insert("maxItems".to_string(), max_length.into());
+                }
+            },
+            _ => {},
+        }
+        keywords
+    }
+}
+
+fn display_bound(bound: &ConvexValue) -> String {
+    match bound {
+        ConvexValue::Int64(i) => format!("{i}n"),
+        bound => bound.to_string(),
+    }
+}
+
+impl Display for ValueConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(min) = &self.min {
+            parts.push(format!("min: {}", display_bound(min)));
+        }
+        if let Some(max) = &self.max {
+            parts.push(format!("max: {}", display_bound(max)));
+        }
+        if let Some(min_length) = self.min_length {
+            parts.push(format!("minLength: {min_length}"));
+        }
+        if let Some(max_length) = self.max_length {
+            parts.push(format!("maxLength: {max_length}"));
+        }
+        if let Some(pattern) = &self.pattern {
+            parts.push(format!("pattern: {pattern}"));
+        }
+        if let Some(format) = &self.format {
+            parts.push(format!("format: \"{format}\""));
+        }
+        write!(f, "{{ {} }}", parts.join(", "))
+    }
+}
+
+/// A regular expression constraint on strings. Patterns use the syntax of the
+/// `regex` crate, which covers JavaScript's except for lookaround and
+/// backreferences.
+#[derive(Clone, Debug)]
+pub struct StringPattern(regex::Regex);
+
+impl StringPattern {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl FromStr for StringPattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let regex = regex::Regex::new(s).map_err(|e| {
+            anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidValidatorConstraint",
+                format!("Invalid pattern /{s}/: {e}"),
+            ))
+        })?;
+        Ok(Self(regex))
+    }
+}
+
+impl Display for StringPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/", self.as_str())
+    }
+}
+
+impl PartialEq for StringPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for StringPattern {}
+
+impl PartialOrd for StringPattern {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StringPattern {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+static EMAIL_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap());
+
+/// Well-known string formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, derive_more::Display)]
+pub enum StringFormat {
+    #[display("email")]
+    Email,
+    /// An absolute URL with a host, e.g. `https://example.com/path`.
+    #[display("url")]
+    Url,
+}
+
+impl StringFormat {
+    pub fn matches(&self, s: &str) -> bool {
+        match self {
+            StringFormat::Email => EMAIL_REGEX.is_match(s),
+            StringFormat::Url => url::Url::parse(s).is_ok_and(|url| url.has_host()),
+        }
+    }
+
+    fn json_schema_format(&self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Url => "uri",
+        }
+    }
+}
+
+impl FromStr for StringFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "email" => Ok(StringFormat::Email),
+            "url" => Ok(StringFormat::Url),
+            _ => anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidValidatorConstraint",
+                format!("Unknown string format \"{s}\". Expected \"email\" or \"url\"."),
+            )),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 #[cfg_attr(
@@ -1102,6 +1483,18 @@ Validator: {validator}"
         validator: Validator,
         context: ValidationContext,
     },
+    #[display(
+        "Value does not satisfy the constraint `{constraint}`.
+{context}
+Value: {value}
+Validator: {validator}"
+    )]
+    ConstraintViolation {
+        value: ConvexValue,
+        constraint: String,
+        validator: Validator,
+        context: ValidationContext,
+    },
 }
 
 #[cfg(test)]
@@ -1705,6 +2098,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_value_constraints() -> anyhow::Result<()> {
+        let table_mapping = empty_table_mapping();
+        let virtual_system_mapping = VirtualSystemMapping::default();
+        let check = |validator: &Validator, value: ConvexValue| {
+            validator.check_value(&value, &table_mapping, &virtual_system_mapping)
+        };
+
+        let number = Validator::json_deserialize(r#"{"type": "number", "min": 0, "max": 1}"#)?;
+        assert_eq!(number.to_string(), "v.float64({ min: 0.0, max: 1.0 })");
+        check(&number, assert_val!(0.5))?;
+        let err = check(&number, assert_val!(1.5)).unwrap_err();
+        must_let::must_let!(let ValidationError::ConstraintViolation { constraint, .. } = err);
+        assert_eq!(constraint, "max: 1.0");
+        assert!(check(&number, assert_val!(f64::NAN)).is_err());
+        assert!(check(&number, assert_val!(1)).is_err());
+
+        let string = Validator::json_deserialize(
+            r#"{"type": "string", "minLength": 3, "pattern": "^[a-z]+@", "format": "email"}"#,
+        )?;
+        check(&string, assert_val!("ada@example.com"))?;
+        assert!(check(&string, assert_val!("Ada@example.com")).is_err());
+        assert!(check(&string, assert_val!("ada@example")).is_err());
+
+        let url = Validator::json_deserialize(r#"{"type": "string", "format": "url"}"#)?;
+        check(&url, assert_val!("https://example.com/path"))?;
+        assert!(check(&url, assert_val!("example.com")).is_err());
+
+        let array = Validator::json_deserialize(
+            r#"{"type": "array", "value": {"type": "bigint", "min": {"$integer": "AQAAAAAAAAA="}}, "maxLength": 2}"#,
+        )?;
+        check(&array, assert_val!([1, 2]))?;
+        assert!(check(&array, assert_val!([0])).is_err());
+        assert!(check(&array, assert_val!([1, 1, 1])).is_err());
+
+        // Constraints round-trip through JSON.
+        for validator in [&number, &string, &url, &array] {
+            assert_eq!(
+                &Validator::json_deserialize(&validator.clone().json_serialize()?)?,
+                validator
+            );
+        }
+
+        // Constraints must apply to the validator's type.
+        for invalid in [
+            r#"{"type": "boolean", "min": 1}"#,
+            r#"{"type": "number", "minLength": 1}"#,
+            r#"{"type": "bytes", "pattern": "a"}"#,
+            r#"{"type": "bigint", "min": 1}"#,
+            r#"{"type": "string", "minLength": 2, "maxLength": 1}"#,
+            r#"{"type": "string", "pattern": "("}"#,
+            r#"{"type": "string", "format": "phone"}"#,
+        ] {
+            assert!(Validator::json_deserialize(invalid).is_err(), "{invalid}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_id_match() -> anyhow::Result<()> {
         let table1: TableName = "table1".parse()?;
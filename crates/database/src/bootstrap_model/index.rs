@@ -35,6 +35,7 @@ use common::{
         DatabaseSchema,
         TableDefinition,
         MAX_INDEXES_PER_TABLE,
+        REFERENCE_INDEX_PREFIX,
     },
     types::{
         IndexDescriptor,
@@ -350,6 +351,8 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
         };
         self.apply_index_diff(namespace, &only_dropped_tables)
             .await?;
+        self.drop_unused_reference_indexes(namespace, tables_in_schema)
+            .await?;
 
         // Added indexes should have backfilled via build_indexes
         // (for < 0.14.0 CLIs) or in apply_config (for >= 0.14.0 CLIs).
@@ -408,10 +411,13 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
             // Collect the database indexes.
             for (index_descriptor, index_schema) in &table_schema.indexes {
                 let index_name = IndexName::new(table_name.clone(), index_descriptor.clone())?;
-                indexes_in_schema.push(IndexMetadata::new_backfilling(
+                indexes_in_schema.push(IndexMetadata::new_backfilling_database_index(
                     *self.tx.begin_timestamp(),
                     index_name.clone(),
-                    index_schema.fields.clone(),
+                    DeveloperDatabaseIndexConfig {
+                        fields: index_schema.fields.clone(),
+                        unique: index_schema.unique,
+                    },
                 ))
             }
 
@@ -421,7 +427,9 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                 indexes_in_schema.push(IndexMetadata::new_backfilling_text_index(
                     index_name.clone(),
                     index_schema.search_field.clone(),
+                    index_schema.extra_search_fields.clone(),
                     index_schema.filter_fields.clone(),
+                    index_schema.analyzer,
                 ))
             }
             for (index_descriptor, index_schema) in &table_schema.vector_indexes {
@@ -431,6 +439,9 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                     index_schema.vector_field.clone(),
                     index_schema.dimension,
                     index_schema.filter_fields.clone(),
+                    index_schema.metric,
+                    index_schema.quantization,
+                    index_schema.hnsw,
                 ));
             }
         }
@@ -558,15 +569,83 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
             self.apply_index_diff(namespace, &only_new_and_mutated)
                 .await?;
         }
+        self.add_reference_indexes(namespace, schema).await?;
         Ok(diff)
     }
 
+    /// Adds the reserved indexes that back `schema`'s references and don't
+    /// exist yet. These are system indexes, so they aren't part of the index
+    /// diff and are enabled as soon as they're backfilled.
+    pub async fn add_reference_indexes(
+        &mut self,
+        namespace: TableNamespace,
+        schema: &DatabaseSchema,
+    ) -> anyhow::Result<()> {
+        let existing: BTreeSet<_> = self
+            .get_system_indexes(namespace)
+            .await?
+            .into_iter()
+            .map(|index| index.into_value().name)
+            .collect();
+        for (table_name, table_schema) in &schema.tables {
+            for reference in table_schema.references.values() {
+                let index_name =
+                    IndexName::new_reserved(table_name.clone(), reference.index_descriptor()?)?;
+                if existing.contains(&index_name) {
+                    continue;
+                }
+                tracing::info!("Adding reference index {index_name}");
+                let index = IndexMetadata::new_backfilling(
+                    *self.tx.begin_timestamp(),
+                    index_name,
+                    vec![reference.field_path()?].try_into()?,
+                );
+                self.add_system_index(namespace, index).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops reference indexes that no longer back a reference in
+    /// `tables_in_schema`.
+    async fn drop_unused_reference_indexes(
+        &mut self,
+        namespace: TableNamespace,
+        tables_in_schema: &BTreeMap<TableName, TableDefinition>,
+    ) -> anyhow::Result<()> {
+        for index in self.get_system_indexes(namespace).await? {
+            if !index
+                .name
+                .descriptor()
+                .as_str()
+                .starts_with(REFERENCE_INDEX_PREFIX)
+            {
+                continue;
+            }
+            let in_use = tables_in_schema
+                .get(index.name.table())
+                .is_some_and(|table_schema| {
+                    table_schema.references.values().any(|reference| {
+                        reference
+                            .index_descriptor()
+                            .is_ok_and(|descriptor| descriptor == *index.name.descriptor())
+                    })
+                });
+            if !in_use {
+                tracing::info!("Dropping reference index {}", index.name);
+                self.drop_index(index.id()).await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn build_indexes(
         &mut self,
         namespace: TableNamespace,
         schema: &DatabaseSchema,
     ) -> anyhow::Result<LegacyIndexDiff> {
         let diff: LegacyIndexDiff = self.get_index_diff(namespace, &schema.tables).await?.into();
+        self.add_reference_indexes(namespace, schema).await?;
         if diff.is_empty() {
             return Ok(diff);
         }
@@ -586,7 +665,7 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
             self.require_enabled_index_metadata(printable_index_name, resolved_index_name)?;
         match metadata.config.clone() {
             IndexConfig::Database {
-                developer_config: DeveloperDatabaseIndexConfig { fields },
+                developer_config: DeveloperDatabaseIndexConfig { fields, .. },
                 ..
             } => Ok(fields),
             _ => anyhow::bail!(index_not_a_database_index_error(printable_index_name)),
@@ -959,20 +1038,27 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
             };
             let metadata = match index.into_value().config {
                 IndexConfig::Database {
-                    developer_config: DeveloperDatabaseIndexConfig { fields },
-                    ..
-                } => IndexMetadata::new_backfilling(*self.tx.begin_timestamp(), index_name, fields),
+                    developer_config, ..
+                } => IndexMetadata::new_backfilling_database_index(
+                    *self.tx.begin_timestamp(),
+                    index_name,
+                    developer_config,
+                ),
                 IndexConfig::Text {
                     developer_config:
                         DeveloperTextIndexConfig {
                             search_field,
+                            extra_search_fields,
                             filter_fields,
+                            analyzer,
                         },
                     ..
                 } => IndexMetadata::new_backfilling_text_index(
                     index_name,
                     search_field,
+                    extra_search_fields,
                     filter_fields,
+                    analyzer,
                 ),
                 IndexConfig::Vector {
                     developer_config:
@@ -980,6 +1066,9 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                             dimensions,
                             vector_field,
                             filter_fields,
+                            metric,
+                            quantization,
+                            hnsw,
                         },
                     ..
                 } => IndexMetadata::new_backfilling_vector_index(
@@ -987,6 +1076,9 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                     vector_field,
                     dimensions,
                     filter_fields,
+                    metric,
+                    quantization,
+                    hnsw,
                 ),
             };
             SystemMetadataModel::new_global(self.tx)
@@ -22,19 +22,26 @@ use common::{
         ParsedDocument,
         ResolvedDocument,
     },
+    knobs::REFERENCE_ACTIONS_MAX_DOCUMENTS,
     query::{
+        IndexRange,
+        IndexRangeExpression,
         Order,
         Query,
     },
     runtime::Runtime,
     schemas::{
         DatabaseSchema,
+        OnDeleteAction,
         SchemaValidationError,
     },
+    types::IndexName,
 };
 use errors::ErrorMetadata;
 use value::{
+    ConvexValue,
     FieldPath,
+    IdentifierFieldName,
     NamespacedTableMapping,
     ResolvedDocumentId,
     TableName,
@@ -48,6 +55,7 @@ use crate::{
         SystemIndex,
         SystemTable,
     },
+    IndexModel,
     ResolvedQuery,
     SystemMetadataModel,
     TableModel,
@@ -117,6 +125,9 @@ impl<'a, RT: Runtime> SchemaModel<'a, RT> {
                 next_schema: next_schema.clone(),
             });
         if let Some(schema_id) = schema_id {
+            if let Some(next_schema) = &next_schema {
+                self.ensure_reference_indexes_enabled(next_schema)?;
+            }
             self.mark_active(schema_id).await?;
         } else {
             self.clear_active().await?;
@@ -125,6 +136,33 @@ impl<'a, RT: Runtime> SchemaModel<'a, RT> {
         Ok((schema_diff, next_schema))
     }
 
+    /// Deletes look up referencing documents through the reserved indexes
+    /// added when the schema was pushed, so a schema with references can't be
+    /// activated until they've finished backfilling.
+    fn ensure_reference_indexes_enabled(&mut self, schema: &DatabaseSchema) -> anyhow::Result<()> {
+        for (table_name, table_schema) in &schema.tables {
+            for reference in table_schema.references.values() {
+                let index_name =
+                    IndexName::new_reserved(table_name.clone(), reference.index_descriptor()?)?;
+                if IndexModel::new(self.tx)
+                    .enabled_index_metadata(self.namespace, &index_name)?
+                    .is_none()
+                {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "ReferenceIndexNotReady",
+                        format!(
+                            "Can't activate the schema until the index backing the reference \
+                             \"{}\" in table \"{table_name}\" has finished backfilling. Wait for \
+                             the push to complete and try again.",
+                            reference.field,
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[fastrace::trace]
     pub async fn enforce(&mut self, document: &ResolvedDocument) -> anyhow::Result<()> {
         let schema_table_mapping = self.tx.table_mapping().namespace(self.namespace);
@@ -209,6 +247,88 @@ impl<'a, RT: Runtime> SchemaModel<'a, RT> {
         Ok(())
     }
 
+    /// Returns the documents that reference `document` through a declared
+    /// `onDelete` reference in the active schema, failing if any of them
+    /// restrict the delete or if there are more than `max_actions` of them.
+    /// Callers apply the returned actions after the delete itself has been
+    /// written.
+    pub async fn reference_actions_for_deletion(
+        &mut self,
+        document: &ResolvedDocument,
+        max_actions: usize,
+    ) -> anyhow::Result<Vec<(ResolvedDocumentId, IdentifierFieldName, OnDeleteAction)>> {
+        let table_mapping = self.tx.table_mapping().namespace(self.namespace);
+        if table_mapping.is_system_tablet(document.id().tablet_id) {
+            return Ok(vec![]);
+        }
+        let Some((_id, active_schema)) = self.get_by_state(SchemaState::Active).await? else {
+            return Ok(vec![]);
+        };
+        let table_name = table_mapping.tablet_name(document.id().tablet_id)?;
+        let deleted_id = document.developer_id().encode();
+        let mut actions = vec![];
+        for (referencing_table, reference) in active_schema.references_to(&table_name) {
+            if !table_mapping.name_exists(referencing_table) {
+                continue;
+            }
+            let index_name =
+                IndexName::new_reserved(referencing_table.clone(), reference.index_descriptor()?)?;
+            // Schemas aren't activated through a push until their reference
+            // indexes are enabled, so this only skips references on schemas
+            // activated while the index was still backfilling.
+            if IndexModel::new(self.tx)
+                .enabled_index_metadata(self.namespace, &index_name)?
+                .is_none()
+            {
+                continue;
+            }
+            let query = Query::index_range(IndexRange {
+                index_name,
+                range: vec![IndexRangeExpression::Eq(
+                    reference.field_path()?,
+                    ConvexValue::String(deleted_id.clone().try_into()?).into(),
+                )],
+                order: Order::Asc,
+            });
+            let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+            while let Some(referencing) = query_stream.next(self.tx, None).await? {
+                if referencing.id() == document.id() {
+                    continue;
+                }
+                if reference.on_delete == OnDeleteAction::Restrict {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "ReferenceRestrictViolation",
+                        format!(
+                            "Cannot delete document {deleted_id} in table \"{table_name}\": \
+                             document {} in table \"{referencing_table}\" references it through \
+                             field \"{}\", which is declared with onDelete \"restrict\".",
+                            referencing.developer_id().encode(),
+                            reference.field,
+                        ),
+                    ));
+                }
+                if actions.len() >= max_actions {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "TooManyReferenceActions",
+                        format!(
+                            "Cannot delete document {deleted_id} in table \"{table_name}\": it \
+                             would update or delete more than {} referencing documents through \
+                             onDelete reference actions. Delete or update the referencing \
+                             documents in smaller batches first.",
+                            *REFERENCE_ACTIONS_MAX_DOCUMENTS,
+                        ),
+                    ));
+                }
+                actions.push((
+                    referencing.id(),
+                    reference.field.clone(),
+                    reference.on_delete,
+                ));
+            }
+        }
+        Ok(actions)
+    }
+
     pub async fn get_by_state(
         &mut self,
         state: SchemaState,
@@ -387,6 +507,8 @@ impl<'a, RT: Runtime> SchemaModel<'a, RT> {
                     SchemaValidationError::ReferencedTableCannotBeDeleted {
                         table_name, ..
                     } => table_name,
+                    SchemaValidationError::DuplicateUniqueIndexKey { table_name, .. } => table_name,
+                    SchemaValidationError::DanglingReference { table_name, .. } => table_name,
                 };
                 SystemMetadataModel::new(self.tx, self.namespace)
                     .patch(
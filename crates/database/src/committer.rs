@@ -39,11 +39,13 @@ use common::{
     knobs::{
         COMMITTER_QUEUE_SIZE,
         COMMIT_TRACE_THRESHOLD,
+        FOLLOWER_LOG_TAIL_INTERVAL,
         MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY,
         MAX_REPEATABLE_TIMESTAMP_IDLE_FREQUENCY,
         TRANSACTION_WARN_READ_SET_INTERVALS,
     },
     persistence::{
+        new_static_repeatable_recent,
         ConflictStrategy,
         DocumentLogEntry,
         Persistence,
@@ -53,6 +55,7 @@ use common::{
         RetentionValidator,
         TimestampRange,
     },
+    query::Order,
     runtime::{
         block_in_place,
         try_join,
@@ -120,6 +123,10 @@ use crate::{
         next_commit_ts_seconds,
         table_summary_finish_bootstrap_timer,
     },
+    persistence_helpers::{
+        stream_transactions,
+        TransactionRevisions,
+    },
     reads::ReadSet,
     search_index_bootstrap::{
         stream_revision_pairs_for_indexes,
@@ -158,6 +165,14 @@ enum PersistenceWrite {
         result: oneshot::Sender<Timestamp>,
         commit_id: usize,
     },
+    /// A follower read the leader's commits up to `new_max_repeatable` from
+    /// the document log.
+    FollowerTail {
+        transactions: Vec<TransactionRevisions>,
+        new_max_repeatable: Timestamp,
+        timer: Timer<VMHistogram>,
+        commit_id: usize,
+    },
 }
 
 impl PersistenceWrite {
@@ -165,6 +180,28 @@ impl PersistenceWrite {
         match self {
             Self::Commit { commit_id, .. } => *commit_id,
             Self::MaxRepeatableTimestamp { commit_id, .. } => *commit_id,
+            Self::FollowerTail { commit_id, .. } => *commit_id,
+        }
+    }
+}
+
+/// How the committer interacts with persistence.
+#[derive(Clone)]
+pub(crate) enum CommitterPersistence {
+    /// The committer holds the persistence lease, writes commits and
+    /// advances max_repeatable_ts.
+    Leader(Arc<dyn Persistence>),
+    /// The committer only has read access to persistence. It rejects all
+    /// writes and instead applies commits made by the leader by tailing the
+    /// document log up to the leader's max_repeatable_ts.
+    Follower(Arc<dyn PersistenceReader>),
+}
+
+impl CommitterPersistence {
+    pub(crate) fn reader(&self) -> Arc<dyn PersistenceReader> {
+        match self {
+            Self::Leader(persistence) => persistence.reader(),
+            Self::Follower(reader) => reader.clone(),
         }
     }
 }
@@ -178,7 +215,7 @@ pub struct Committer<RT: Runtime> {
     log: LogWriter,
 
     snapshot_manager: Writer<SnapshotManager>,
-    persistence: Arc<dyn Persistence>,
+    persistence: CommitterPersistence,
     runtime: RT,
 
     last_assigned_ts: Timestamp,
@@ -192,7 +229,7 @@ impl<RT: Runtime> Committer<RT> {
     pub(crate) fn start(
         log: LogWriter,
         snapshot_manager: Writer<SnapshotManager>,
-        persistence: Arc<dyn Persistence>,
+        persistence: CommitterPersistence,
         runtime: RT,
         retention_validator: Arc<dyn RetentionValidator>,
         shutdown: ShutdownSignal,
@@ -235,7 +272,11 @@ impl<RT: Runtime> Committer<RT> {
         // quick bump.
         // None means a bump is ongoing. Avoid parallel bumps in case they
         // commit out of order and regress the repeatable timestamp.
-        let mut next_bump_wait = Some(*MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY);
+        // Followers use the same timer to poll the leader's document log.
+        let mut next_bump_wait = Some(match self.persistence {
+            CommitterPersistence::Leader(_) => *MAX_REPEATABLE_TIMESTAMP_COMMIT_DELAY,
+            CommitterPersistence::Follower(_) => *FOLLOWER_LOG_TAIL_INTERVAL,
+        });
 
         // This span starts with receiving a commit message and ends with that same
         // commit getting published. It captures all of the committer activity
@@ -263,11 +304,20 @@ impl<RT: Runtime> Committer<RT> {
                         span_commit_id = Some(commit_id);
                         Span::root("bump_max_repeatable", SpanContext::random())
                     });
-                    // Advance the repeatable read timestamp so non-leaders can
-                    // establish a recent repeatable snapshot.
                     next_bump_wait = None;
-                    let (tx, _rx) = oneshot::channel();
-                    self.bump_max_repeatable_ts(tx, commit_id, committer_span);
+                    match &self.persistence {
+                        CommitterPersistence::Leader(_) => {
+                            // Advance the repeatable read timestamp so non-leaders can
+                            // establish a recent repeatable snapshot.
+                            let (tx, _rx) = oneshot::channel();
+                            self.bump_max_repeatable_ts(tx, commit_id, committer_span);
+                        },
+                        CommitterPersistence::Follower(reader) => {
+                            // Catch up with everything the leader has made repeatable.
+                            let reader = reader.clone();
+                            self.tail_document_log(reader, commit_id, committer_span);
+                        },
+                    }
                     commit_id += 1;
                     last_bumped_repeatable_ts = self.runtime.monotonic_now();
                 }
@@ -312,6 +362,22 @@ impl<RT: Runtime> Committer<RT> {
                             let _ = result.send(new_max_repeatable);
                             drop(timer);
                         },
+                        PersistenceWrite::FollowerTail {
+                            transactions,
+                            new_max_repeatable,
+                            timer,
+                            ..
+                        } => {
+                            let span = committer_span
+                                .as_ref()
+                                .map(|root| Span::enter_with_parent("publish_follower_tail", root))
+                                .unwrap_or_else(Span::noop);
+                            let _guard = span.set_local_parent();
+                            self.publish_follower_transactions(transactions)?;
+                            self.publish_max_repeatable_ts(new_max_repeatable)?;
+                            next_bump_wait = Some(*FOLLOWER_LOG_TAIL_INTERVAL);
+                            timer.finish();
+                        },
                     }
                     // Report the trace if it is longer than the threshold
                     if let Some(id) = span_commit_id && id == pending_commit_id {
@@ -589,13 +655,15 @@ impl<RT: Runtime> Committer<RT> {
         commit_id: usize,
         root_span: &Span,
     ) {
+        let CommitterPersistence::Leader(persistence) = self.persistence.clone() else {
+            panic!("Only the leader can bump max_repeatable_ts");
+        };
         let timer = metrics::bump_repeatable_ts_timer();
         // next_max_repeatable_ts bumps the last_assigned_ts, so all future commits on
         // this committer will be after new_max_repeatable.
         let new_max_repeatable = self
             .next_max_repeatable_ts()
             .expect("new_max_repeatable should exist");
-        let persistence = self.persistence.clone();
         let span = Span::enter_with_parent("bump_max_repeatable_ts", root_span);
         let runtime = self.runtime.clone();
         self.persistence_writes.push_back(
@@ -646,6 +714,127 @@ impl<RT: Runtime> Committer<RT> {
         );
     }
 
+    /// Read the transactions the leader committed after our latest snapshot
+    /// and up to its current max_repeatable_ts. Followers can't read past
+    /// max_repeatable_ts because commits after it may still be in flight.
+    fn tail_document_log(
+        &mut self,
+        reader: Arc<dyn PersistenceReader>,
+        commit_id: usize,
+        root_span: &Span,
+    ) {
+        let timer = metrics::follower_tail_timer();
+        let (latest_ts, bootstrap_tables) = {
+            let snapshot_manager = self.snapshot_manager.read();
+            let (latest_ts, snapshot) = snapshot_manager.latest();
+            (latest_ts, BootstrapTableIds::new(snapshot.table_mapping()))
+        };
+        let retention_validator = self.retention_validator.clone();
+        let span = Span::enter_with_parent("tail_document_log", root_span);
+        let runtime = self.runtime.clone();
+        self.persistence_writes.push_back(
+            async move {
+                let read_transactions = || {
+                    let reader = reader.clone();
+                    let retention_validator = retention_validator.clone();
+                    async move {
+                        let new_max_repeatable =
+                            new_static_repeatable_recent(reader.as_ref()).await?;
+                        if new_max_repeatable <= latest_ts {
+                            return anyhow::Ok((latest_ts, vec![]));
+                        }
+                        let repeatable_persistence = RepeatablePersistence::new(
+                            reader,
+                            new_max_repeatable,
+                            retention_validator,
+                        );
+                        let range = TimestampRange::new((
+                            Bound::Excluded(*latest_ts),
+                            Bound::Included(*new_max_repeatable),
+                        ))?;
+                        let transactions: Vec<_> = stream_transactions(
+                            bootstrap_tables,
+                            &repeatable_persistence,
+                            range,
+                            Order::Asc,
+                        )
+                        .try_collect()
+                        .await?;
+                        Ok((new_max_repeatable, transactions))
+                    }
+                };
+                // Like bumping max_repeatable_ts on the leader, retry transient
+                // read failures instead of restarting the whole backend.
+                let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+                let (new_max_repeatable, transactions) = loop {
+                    match read_transactions().await {
+                        Ok(result) => break result,
+                        Err(mut e) => {
+                            let delay = backoff.fail(&mut runtime.rng());
+                            report_error(&mut e).await;
+                            tracing::error!(
+                                "Failed to read the document log from persistence, retrying after \
+                                 {:.2}s",
+                                delay.as_secs_f32()
+                            );
+                            runtime.wait(delay).await;
+                        },
+                    }
+                };
+                Ok(PersistenceWrite::FollowerTail {
+                    transactions,
+                    new_max_repeatable: *new_max_repeatable,
+                    timer,
+                    commit_id,
+                })
+            }
+            .in_span(span)
+            .boxed(),
+        );
+    }
+
+    /// Apply the leader's transactions to the snapshot manager and write log,
+    /// in commit order, as if they had been committed here.
+    #[fastrace::trace]
+    fn publish_follower_transactions(
+        &mut self,
+        transactions: Vec<TransactionRevisions>,
+    ) -> anyhow::Result<()> {
+        let apply_timer = metrics::commit_apply_timer();
+        for TransactionRevisions { ts, revision_pairs } in transactions {
+            let mut snapshot = self.snapshot_manager.read().latest_snapshot();
+            let mut ordered_updates = Vec::with_capacity(revision_pairs.len());
+            for revision_pair in revision_pairs {
+                let Some(id) = revision_pair
+                    .document()
+                    .or(revision_pair.prev_document())
+                    .map(|document| document.id())
+                else {
+                    // Deleting a document that never existed doesn't change anything.
+                    continue;
+                };
+                let document_update = DocumentUpdateWithPrevTs {
+                    id,
+                    old_document: revision_pair
+                        .prev_rev
+                        .and_then(|rev| rev.document.map(|document| (document, rev.ts))),
+                    new_document: revision_pair.rev.document,
+                };
+                snapshot.update(&document_update, ts)?;
+                ordered_updates.push((id, PackedDocumentUpdate::pack(&document_update)));
+            }
+            metrics::log_follower_tail_revisions(ordered_updates.len());
+            self.log.append(
+                ts,
+                ordered_updates.into_iter().collect(),
+                WriteSource::new("follower"),
+            );
+            self.snapshot_manager.write().push(ts, snapshot);
+        }
+        apply_timer.finish();
+        Ok(())
+    }
+
     fn publish_max_repeatable_ts(&mut self, new_max_repeatable: Timestamp) -> anyhow::Result<()> {
         // Bump the latest snapshot in snapshot_manager so reads on this leader
         // can know this timestamp is repeatable.
@@ -864,6 +1053,10 @@ impl<RT: Runtime> Committer<RT> {
             let _ = result.send(Ok(*transaction.begin_timestamp));
             return None;
         }
+        let CommitterPersistence::Leader(persistence) = self.persistence.clone() else {
+            let _ = result.send(Err(metrics::read_replica_write_error().into()));
+            return None;
+        };
         let commit_timer = metrics::commit_timer();
         metrics::log_write_tx(&transaction);
 
@@ -893,7 +1086,6 @@ impl<RT: Runtime> Committer<RT> {
 
         // necessary because this value is moved
         let parent_trace_copy = parent_trace.clone();
-        let persistence = self.persistence.clone();
         let request_span = initialize_root_from_parent(
             "Committer::persistence_writes_future",
             parent_trace.clone(),
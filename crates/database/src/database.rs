@@ -61,6 +61,7 @@ use common::{
     },
     persistence::{
         new_idle_repeatable_ts,
+        new_static_repeatable_recent,
         ConflictStrategy,
         DocumentLogEntry,
         DocumentStream,
@@ -84,6 +85,7 @@ use common::{
     sync::split_rw_lock::{
         new_split_rw_lock,
         Reader,
+        Writer,
     },
     types::{
         GenericIndexName,
@@ -161,6 +163,7 @@ use crate::{
     committer::{
         Committer,
         CommitterClient,
+        CommitterPersistence,
     },
     defaults::{
         bootstrap_system_tables,
@@ -172,6 +175,14 @@ use crate::{
         vector::vector_search_with_retries_timer,
         verify_invariants_timer,
     },
+    query::{
+        self,
+        HybridSearch,
+        HybridSearchResult,
+        TableFilter,
+        TextSearch,
+        TextSearchResult,
+    },
     retention::LeaderRetentionManager,
     schema_registry::SchemaRegistry,
     search_index_bootstrap::SearchIndexBootstrapWorker,
@@ -198,6 +209,7 @@ use crate::{
     transaction_index::{
         TextIndexManagerSnapshot,
         TransactionIndex,
+        VectorIndexManagerSnapshot,
     },
     write_log::{
         new_write_log,
@@ -261,7 +273,10 @@ pub struct Database<RT: Runtime> {
     pub(crate) runtime: RT,
     reader: Arc<dyn PersistenceReader>,
     write_commits_since_load: Arc<AtomicUsize>,
-    retention_manager: LeaderRetentionManager<RT>,
+    /// `None` if this is a read replica, in which case the leader is
+    /// responsible for retention.
+    retention_manager: Option<LeaderRetentionManager<RT>>,
+    retention_validator: Arc<dyn RetentionValidator>,
     pub searcher: Arc<dyn Searcher>,
     pub search_storage: Arc<OnceLock<Arc<dyn Storage>>>,
     usage_counter: UsageCounter,
@@ -819,6 +834,96 @@ impl<RT: Runtime> Database<RT> {
         )
         .await?;
 
+        Ok(Self::start(
+            runtime,
+            CommitterPersistence::Leader(persistence),
+            snapshot_reader,
+            snapshot_writer,
+            ts,
+            Some(retention_manager.clone()),
+            Arc::new(retention_manager),
+            searcher,
+            shutdown,
+            virtual_system_mapping,
+            usage_events,
+            bootstrap_metadata,
+        ))
+    }
+
+    /// Load a read replica of the database that only has read access to
+    /// persistence and doesn't take the lease. Instead of committing, the
+    /// replica tails the document log written by the leader, so its in-memory
+    /// indexes, write log and subscriptions follow the leader's commits up to
+    /// the leader's max_repeatable_ts. All non-readonly commits fail with a
+    /// `ReadReplicaWriteError`.
+    #[fastrace::trace]
+    pub async fn load_follower(
+        reader: Arc<dyn PersistenceReader>,
+        runtime: RT,
+        searcher: Arc<dyn Searcher>,
+        shutdown: ShutdownSignal,
+        virtual_system_mapping: VirtualSystemMapping,
+        usage_events: Arc<dyn UsageEventLogger>,
+    ) -> anyhow::Result<Self> {
+        let _load_database_timer = metrics::load_database_timer();
+
+        let snapshot_ts = new_static_repeatable_recent(reader.as_ref()).await?;
+        anyhow::ensure!(
+            snapshot_ts > RepeatableTimestamp::MIN,
+            "Cannot follow an uninitialized database. Start the leader backend first."
+        );
+        let follower_retention_manager = FollowerRetentionManager::new_with_repeatable_ts(
+            runtime.clone(),
+            reader.clone(),
+            snapshot_ts,
+        )
+        .await?;
+        let DatabaseSnapshot {
+            bootstrap_metadata,
+            ts,
+            snapshot,
+            ..
+        } = DatabaseSnapshot::load(
+            runtime.clone(),
+            reader.clone(),
+            snapshot_ts,
+            Arc::new(follower_retention_manager.clone()),
+        )
+        .await?;
+
+        let snapshot_manager = SnapshotManager::new(*ts, snapshot);
+        let (snapshot_reader, snapshot_writer) = new_split_rw_lock(snapshot_manager);
+
+        Ok(Self::start(
+            runtime,
+            CommitterPersistence::Follower(reader),
+            snapshot_reader,
+            snapshot_writer,
+            ts,
+            None,
+            Arc::new(follower_retention_manager),
+            searcher,
+            shutdown,
+            virtual_system_mapping,
+            usage_events,
+            bootstrap_metadata,
+        ))
+    }
+
+    fn start(
+        runtime: RT,
+        persistence: CommitterPersistence,
+        snapshot_reader: Reader<SnapshotManager>,
+        snapshot_writer: Writer<SnapshotManager>,
+        ts: RepeatableTimestamp,
+        retention_manager: Option<LeaderRetentionManager<RT>>,
+        retention_validator: Arc<dyn RetentionValidator>,
+        searcher: Arc<dyn Searcher>,
+        shutdown: ShutdownSignal,
+        virtual_system_mapping: VirtualSystemMapping,
+        usage_events: Arc<dyn UsageEventLogger>,
+        bootstrap_metadata: BootstrapMetadata,
+    ) -> Self {
         let persistence_reader = persistence.reader();
         let (log_owner, log_reader, log_writer) = new_write_log(*ts, persistence_reader.version());
         let subscriptions =
@@ -829,7 +934,7 @@ impl<RT: Runtime> Database<RT> {
             snapshot_writer,
             persistence,
             runtime.clone(),
-            Arc::new(retention_manager.clone()),
+            retention_validator.clone(),
             shutdown,
         );
         let table_mapping_snapshot_cache =
@@ -839,7 +944,7 @@ impl<RT: Runtime> Database<RT> {
         let component_paths_snapshot_cache =
             AsyncLru::new(runtime.clone(), 10, 2, "component_paths_snapshot");
         let list_snapshot_table_iterator_cache = Arc::new(Mutex::new(None));
-        let database = Self {
+        Self {
             committer,
             subscriptions,
             runtime,
@@ -848,6 +953,7 @@ impl<RT: Runtime> Database<RT> {
             snapshot_manager: snapshot_reader,
             reader: persistence_reader.clone(),
             write_commits_since_load: Arc::new(AtomicUsize::new(0)),
+            retention_validator,
             searcher,
             search_storage: Arc::new(OnceLock::new()),
             usage_counter,
@@ -857,9 +963,13 @@ impl<RT: Runtime> Database<RT> {
             by_id_indexes_snapshot_cache,
             component_paths_snapshot_cache,
             list_snapshot_table_iterator_cache,
-        };
+        }
+    }
 
-        Ok(database)
+    /// Whether this database is a read replica loaded with
+    /// [`Database::load_follower`].
+    pub fn is_read_replica(&self) -> bool {
+        self.retention_manager.is_none()
     }
 
     pub fn set_search_storage(&self, search_storage: Arc<dyn Storage>) {
@@ -905,13 +1015,15 @@ impl<RT: Runtime> Database<RT> {
     pub async fn shutdown(&self) -> anyhow::Result<()> {
         self.committer.shutdown();
         self.subscriptions.shutdown();
-        self.retention_manager.shutdown().await?;
+        if let Some(retention_manager) = &self.retention_manager {
+            retention_manager.shutdown().await?;
+        }
         tracing::info!("Database shutdown");
         Ok(())
     }
 
     pub fn retention_validator(&self) -> Arc<dyn RetentionValidator> {
-        Arc::new(self.retention_manager.clone())
+        self.retention_validator.clone()
     }
 
     /// Load the set of documents and tombstones in the given table between
@@ -1478,16 +1590,22 @@ impl<RT: Runtime> Database<RT> {
                 RepeatablePersistence::new(
                     self.reader.clone(),
                     repeatable_ts,
-                    Arc::new(self.retention_manager.clone()),
+                    self.retention_validator(),
                 )
                 .read_snapshot(repeatable_ts)?,
             ),
             Arc::new(TextIndexManagerSnapshot::new(
-                snapshot.index_registry,
+                snapshot.index_registry.clone(),
                 snapshot.text_indexes,
                 self.searcher.clone(),
                 self.search_storage.clone(),
             )),
+            Arc::new(VectorIndexManagerSnapshot::new(
+                snapshot.index_registry,
+                snapshot.vector_indexes,
+                self.searcher.clone(),
+                self.search_storage.clone(),
+            )),
         );
         let count_snapshot = Arc::new(snapshot.table_summaries);
         let tx = Transaction::new(
@@ -1501,7 +1619,7 @@ impl<RT: Runtime> Database<RT> {
             count_snapshot,
             self.runtime.clone(),
             usage_tracker,
-            Arc::new(self.retention_manager.clone()),
+            self.retention_validator(),
             self.virtual_system_mapping.clone(),
         );
         Ok(tx)
@@ -2114,6 +2232,35 @@ impl<RT: Runtime> Database<RT> {
         Ok((results, usage.gather_user_stats()))
     }
 
+    /// Run a hybrid text and vector search at the latest timestamp, outside of
+    /// any user transaction. Used by actions, which can't read the database
+    /// directly.
+    pub async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: HybridSearch,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let usage = FunctionUsageTracker::new();
+        let mut tx = self.begin_with_usage(identity, usage.clone()).await?;
+        let results =
+            query::hybrid_search(&mut tx, query, TableFilter::ExcludePrivateSystemTables).await?;
+        Ok((results, usage.gather_user_stats()))
+    }
+
+    /// Run a text search at the latest timestamp, outside of any user
+    /// transaction. Used by actions, which can't read the database directly.
+    pub async fn text_search(
+        &self,
+        identity: Identity,
+        query: TextSearch,
+    ) -> anyhow::Result<(Vec<TextSearchResult>, FunctionUsageStats)> {
+        let usage = FunctionUsageTracker::new();
+        let mut tx = self.begin_with_usage(identity, usage.clone()).await?;
+        let results =
+            query::text_search(&mut tx, query, TableFilter::ExcludePrivateSystemTables).await?;
+        Ok((results, usage.gather_user_stats()))
+    }
+
     pub async fn search_with_compiled_query(
         &self,
         index_id: IndexId,
@@ -15,15 +15,18 @@ use std::{
 
 use common::{
     backoff::Backoff,
-    bootstrap_model::index::{
-        database_index::{
-            DatabaseIndexState,
-            IndexedFields,
+    bootstrap_model::{
+        index::{
+            database_index::{
+                DatabaseIndexState,
+                IndexedFields,
+            },
+            IndexConfig,
+            IndexMetadata,
+            TabletIndexMetadata,
+            INDEX_TABLE,
         },
-        IndexConfig,
-        IndexMetadata,
-        TabletIndexMetadata,
-        INDEX_TABLE,
+        schema::SchemaState,
     },
     document::{
         ParseDocument,
@@ -31,6 +34,7 @@ use common::{
         ResolvedDocument,
     },
     errors::report_error,
+    interval::Interval,
     knobs::{
         ENABLE_INDEX_BACKFILL,
         INDEX_BACKFILL_CHUNK_RATE,
@@ -60,6 +64,7 @@ use common::{
         RateLimiter,
         Runtime,
     },
+    schemas::SchemaValidationError,
     types::{
         DatabaseIndexUpdate,
         IndexId,
@@ -90,7 +95,9 @@ use maplit::btreeset;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use value::{
+    values_to_bytes,
     DeveloperDocumentId,
+    TableName,
     TableNamespace,
 };
 
@@ -102,6 +109,7 @@ use crate::{
     retention::LeaderRetentionManager,
     Database,
     ResolvedQuery,
+    SchemaModel,
     SystemMetadataModel,
     TableIterator,
 };
@@ -580,6 +588,30 @@ impl<RT: Runtime> IndexWorker<RT> {
 
         let name = index_metadata.name.clone();
 
+        // Writes skip pending unique indexes, so the index may already have
+        // duplicate keys. Fail the schema that added it rather than letting it
+        // be enabled.
+        if index_metadata.is_unique_database_index()
+            && !is_system_index_on_user_table
+            && !is_index_on_system_table
+        {
+            let ts = tx.begin_timestamp();
+            let table_name = tx.table_mapping().tablet_name(*name.table())?;
+            if let Some(error) = self
+                .find_duplicate_key(&index_metadata, table_name, ts)
+                .await?
+            {
+                tracing::info!("Unique index {name} has duplicate keys: {error}");
+                let namespace = tx.table_mapping().tablet_namespace(*name.table())?;
+                let mut schema_model = SchemaModel::new(&mut tx, namespace);
+                for state in [SchemaState::Pending, SchemaState::Validated] {
+                    if let Some((schema_id, _)) = schema_model.get_by_state(state).await? {
+                        schema_model.mark_failed(schema_id, error.clone()).await?;
+                    }
+                }
+            }
+        }
+
         SystemMetadataModel::new_global(&mut tx)
             .replace(full_index_id, index_metadata.into_value().try_into()?)
             .await?;
@@ -597,6 +629,58 @@ impl<RT: Runtime> IndexWorker<RT> {
         log_index_backfilled();
         Ok(())
     }
+
+    /// Scan a backfilled unique index at `ts` for two documents with the same
+    /// key. Entries are sorted by key, so duplicates are adjacent.
+    async fn find_duplicate_key(
+        &self,
+        index_metadata: &TabletIndexMetadata,
+        table_name: TableName,
+        ts: RepeatableTimestamp,
+    ) -> anyhow::Result<Option<SchemaValidationError>> {
+        let IndexConfig::Database {
+            ref developer_config,
+            ..
+        } = index_metadata.config
+        else {
+            return Ok(None);
+        };
+        let tablet_id = *index_metadata.name.table();
+        let snapshot = RepeatablePersistence::new(
+            self.index_writer.reader.clone(),
+            ts,
+            self.index_writer.retention_validator.clone(),
+        )
+        .read_snapshot(ts)?;
+        let stream = snapshot.index_scan(
+            index_metadata.id().internal_id(),
+            tablet_id,
+            &Interval::all(),
+            Order::Asc,
+            *INDEX_BACKFILL_CHUNK_SIZE,
+        );
+        pin_mut!(stream);
+        let mut previous: Option<(Vec<u8>, DeveloperDocumentId)> = None;
+        while let Some((_, LatestDocument { value: doc, .. })) = stream.try_next().await? {
+            let index_key = doc.index_key(&developer_config.fields[..], self.persistence_version);
+            if index_key.indexed_values().iter().any(|v| v.is_none()) {
+                continue;
+            }
+            let key = values_to_bytes(index_key.indexed_values());
+            if let Some((previous_key, first_id)) = &previous
+                && *previous_key == key
+            {
+                return Ok(Some(SchemaValidationError::DuplicateUniqueIndexKey {
+                    table_name,
+                    index_descriptor: index_metadata.name.descriptor().clone(),
+                    first_id: *first_id,
+                    second_id: doc.developer_id(),
+                }));
+            }
+            previous = Some((key, doc.developer_id()));
+        }
+        Ok(None)
+    }
 }
 
 impl<RT: Runtime> IndexWriter<RT> {
@@ -59,6 +59,13 @@ pub trait SearchIndex: Clone + Debug {
 
     type Schema: Send + Sync + 'static;
 
+    /// The format of the index's snapshots. Snapshots in an older format are
+    /// rebuilt.
+    type SnapshotVersion: Copy + Debug + PartialEq + Send + Sync + 'static;
+
+    /// The format of the snapshots this backend builds.
+    fn current_snapshot_version() -> Self::SnapshotVersion;
+
     /// Returns the generalized `SearchIndexConfig` if it matches the type of
     /// the parser (e.g. Text vs Vector) and `None` otherwise.
     fn get_config(_config: IndexConfig) -> Option<SearchIndexConfig<Self>>;
@@ -159,6 +166,7 @@ pub struct SearchIndexConfig<T: SearchIndex> {
 pub struct SearchSnapshot<T: SearchIndex> {
     pub ts: Timestamp,
     pub data: SnapshotData<T::Segment>,
+    pub version: T::SnapshotVersion,
 }
 
 pub struct BackfillState<T: SearchIndex> {
@@ -200,6 +208,7 @@ impl<T: SearchIndex> SearchOnDiskState<T> {
         let snapshot = SearchSnapshot {
             ts,
             data: SnapshotData::MultiSegment(segments),
+            version: T::current_snapshot_version(),
         };
         match self {
             Self::Backfilling(_) => anyhow::bail!("Can't update backfilling index!"),
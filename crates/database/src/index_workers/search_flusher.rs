@@ -364,6 +364,11 @@ impl<RT: Runtime, T: SearchIndex + 'static> SearchFlusher<RT, T> {
                     SnapshotData::Unknown(_) => {
                         anyhow::bail!("Unknown index format, not rebuilding")
                     },
+                    SnapshotData::MultiSegment(_)
+                        if matches!(job.build_reason, BuildReason::VersionMismatch) =>
+                    {
+                        return self.rebuild_multipart_segments(job, build_index_args).await;
+                    },
                     SnapshotData::MultiSegment(ref parts) => {
                         let ts = IndexWorkerMetadataModel::new(&mut tx)
                             .get_fast_forward_ts(snapshot.ts, job.index_id)
@@ -430,6 +435,71 @@ impl<RT: Runtime, T: SearchIndex + 'static> SearchFlusher<RT, T> {
         })
     }
 
+    /// Rebuilds every segment of an index whose snapshot was written in an
+    /// older format. Unlike a backfill, the whole table is read in one job so
+    /// the index keeps its state while it's rebuilt.
+    async fn rebuild_multipart_segments(
+        &self,
+        job: &IndexBuild<T>,
+        build_index_args: T::BuildIndexArgs,
+    ) -> anyhow::Result<IndexBuildResult<T>> {
+        let tx = self.database.begin(Identity::system()).await?;
+        let snapshot_ts = tx.begin_timestamp();
+        drop(tx);
+
+        let mut segments = vec![];
+        let mut cursor = None;
+        let mut new_segment_stats = None;
+        let mut new_segment_id = None;
+        let backfill_result = loop {
+            let index_path = TempDir::new()?;
+            let MultiSegmentBuildResult {
+                new_segment,
+                updated_previous_segments,
+                backfill_result,
+            } = self
+                .build_multipart_segment_in_dir(
+                    job,
+                    &index_path,
+                    snapshot_ts,
+                    MultipartBuildType::IncrementalComplete {
+                        cursor,
+                        backfill_snapshot_ts: snapshot_ts,
+                    },
+                    segments,
+                    build_index_args.clone(),
+                )
+                .await?;
+            segments = updated_previous_segments;
+            if let Some(new_segment) = new_segment {
+                let new_segment = self.upload_new_segment(new_segment).await?;
+                new_segment_stats = Some(new_segment.statistics()?);
+                new_segment_id = Some(new_segment.id().to_string());
+                segments.push(new_segment);
+            }
+            let backfill_result = backfill_result.context("Missing rebuild progress")?;
+            if backfill_result.is_backfill_complete {
+                break backfill_result;
+            }
+            cursor = backfill_result.new_cursor;
+        };
+
+        let total_stats = segments
+            .iter()
+            .map(|segment| segment.statistics())
+            .reduce(SegmentStatistics::add)
+            .transpose()?
+            .unwrap_or_default();
+        Ok(IndexBuildResult {
+            snapshot_ts,
+            data: SnapshotData::MultiSegment(segments),
+            total_stats,
+            new_segment_stats,
+            new_segment_id,
+            backfill_result: Some(backfill_result),
+        })
+    }
+
     async fn build_multipart_segment_in_dir(
         &self,
         job: &IndexBuild<T>,
@@ -189,6 +189,12 @@ impl SearchIndexWorkers {
         }
     }
 
+    /// Read replicas don't build or compact search indexes. The leader does
+    /// and replicas pick up the new segments from the index metadata.
+    pub fn disabled() -> Self {
+        Self { handles: vec![] }
+    }
+
     pub fn shutdown(&mut self) {
         self.handles.iter_mut().for_each(|handle| handle.shutdown())
     }
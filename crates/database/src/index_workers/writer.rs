@@ -422,6 +422,21 @@ impl<RT: Runtime, T: SearchIndex> Inner<RT, T> {
 
         let (developer_config, state) = T::extract_metadata(metadata)?;
 
+        // A rebuild replaces every segment, so none of the current ones are kept.
+        if matches!(job.build_reason, BuildReason::VersionMismatch) {
+            anyhow::ensure!(backfill_result.is_backfill_complete);
+            self.write_metadata(
+                tx,
+                job.metadata_id,
+                job.index_name.clone(),
+                developer_config,
+                state.with_updated_snapshot(*backfill_complete_ts, new_and_modified_segments)?,
+            )
+            .await?;
+            finish_search_index_merge_timer(timer, SearchIndexMergeType::NotRequired);
+            return Ok(());
+        }
+
         // Find new segment and add to current segments to avoid race with compactor
         let new_segment = new_segment_id
             .map(|new_segment_id| {
@@ -446,6 +461,7 @@ impl<RT: Runtime, T: SearchIndex> Inner<RT, T> {
                 SearchOnDiskState::Backfilled(SearchSnapshot {
                     ts: *backfill_complete_ts,
                     data: SnapshotData::MultiSegment(new_and_modified_segments),
+                    version: T::current_snapshot_version(),
                 })
             } else {
                 SearchOnDiskState::Backfilling(BackfillState {
@@ -81,6 +81,8 @@ pub use transaction::{
 pub use transaction_index::{
     TextIndexManagerSnapshot,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
+    VectorIndexManagerSnapshot,
 };
 pub use vector_index_worker::flusher::VectorIndexFlusher;
 pub use write_limits::BiggestDocumentWrites;
@@ -302,6 +302,17 @@ pub fn subscriptions_update_timer() -> Timer<VMHistogram> {
     Timer::new(&DATABASE_SUBSCRIPTIONS_UPDATE_SECONDS)
 }
 
+register_convex_histogram!(
+    DATABASE_SUBSCRIPTIONS_VECTOR_CHECKS_TOTAL,
+    "Number of vector subscriptions checked against a written document"
+);
+pub fn log_vector_subscription_checks(num_checks: usize) {
+    log_distribution(
+        &DATABASE_SUBSCRIPTIONS_VECTOR_CHECKS_TOTAL,
+        num_checks as f64,
+    );
+}
+
 register_convex_counter!(DATABASE_COMMITTER_FULL_TOTAL, "Committer queue full count");
 
 pub fn committer_full_error() -> ErrorMetadata {
@@ -340,6 +351,38 @@ pub fn bump_repeatable_ts_timer() -> Timer<VMHistogram> {
     Timer::new(&BUMP_REPEATABLE_TS_SECONDS)
 }
 
+register_convex_counter!(
+    DATABASE_READ_REPLICA_WRITE_TOTAL,
+    "Count of commits rejected because the database is a read replica"
+);
+pub fn read_replica_write_error() -> ErrorMetadata {
+    log_counter(&DATABASE_READ_REPLICA_WRITE_TOTAL, 1);
+    ErrorMetadata::bad_request(
+        "ReadReplicaWriteError",
+        "This backend is a read replica and cannot commit writes. Send mutations to the leader \
+         backend instead.",
+    )
+}
+
+register_convex_histogram!(
+    DATABASE_FOLLOWER_TAIL_SECONDS,
+    "Time to read new commits from the leader's document log"
+);
+pub fn follower_tail_timer() -> Timer<VMHistogram> {
+    Timer::new(&DATABASE_FOLLOWER_TAIL_SECONDS)
+}
+
+register_convex_counter!(
+    DATABASE_FOLLOWER_TAIL_REVISIONS_TOTAL,
+    "Number of document revisions applied by a read replica"
+);
+pub fn log_follower_tail_revisions(num_revisions: usize) {
+    log_counter(
+        &DATABASE_FOLLOWER_TAIL_REVISIONS_TOTAL,
+        num_revisions as u64,
+    );
+}
+
 register_convex_histogram!(NEXT_COMMIT_TS_SECONDS, "Time to bump max_repeatable_ts");
 pub fn next_commit_ts_seconds() -> Timer<VMHistogram> {
     Timer::new(&NEXT_COMMIT_TS_SECONDS)
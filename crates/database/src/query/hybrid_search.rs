@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+
+use common::{
+    components::ComponentId,
+    json::JsonExpression,
+    query::{
+        Expression,
+        Search,
+        SearchFilterExpression,
+        SearchVersion,
+    },
+    runtime::Runtime,
+    types::IndexName,
+};
+use errors::ErrorMetadata;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    json,
+    Value as JsonValue,
+};
+use value::{
+    ConvexValue,
+    DeveloperDocumentId,
+    FieldPath,
+    TableNamespace,
+};
+use vector::{
+    VectorSearch,
+    VectorSearchExpression,
+    DEFAULT_VECTOR_LIMIT,
+    MAX_VECTOR_RESULTS,
+};
+
+use super::{
+    vector_search,
+    TableFilter,
+};
+use crate::{
+    IndexModel,
+    Transaction,
+};
+
+/// The `k` in reciprocal rank fusion's `1 / (k + rank)`. Larger values shrink
+/// the gap between the top ranked results and the rest.
+const DEFAULT_RRF_K: f64 = 60.;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HybridSearchRequest {
+    pub query: JsonValue,
+}
+
+/// A text search and a vector search over the same table whose rankings are
+/// fused into a single list of results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridSearch {
+    pub component_id: ComponentId,
+    pub text_index_name: IndexName,
+    /// The `searchField` of the text index.
+    pub search_field: FieldPath,
+    pub text_query: String,
+    pub vector_index_name: IndexName,
+    pub vector: Vec<f32>,
+    pub limit: Option<u32>,
+    /// Equality filters that both searches apply. Documents must match all of
+    /// them, and the fields must be filter fields of both indexes.
+    pub filters: BTreeMap<FieldPath, Option<ConvexValue>>,
+    pub fusion: HybridFusion,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HybridFusion {
+    /// Score each document by summing `1 / (k + rank)` over the searches that
+    /// returned it.
+    ReciprocalRank { k: f64 },
+    /// Min-max normalize each search's scores to [0, 1] and sum them with these
+    /// weights.
+    Weighted {
+        text_weight: f64,
+        vector_weight: f64,
+    },
+}
+
+impl Default for HybridFusion {
+    fn default() -> Self {
+        HybridFusion::ReciprocalRank { k: DEFAULT_RRF_K }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HybridSearchResult {
+    pub id: DeveloperDocumentId,
+    pub score: f64,
+}
+
+impl From<HybridSearchResult> for JsonValue {
+    fn from(value: HybridSearchResult) -> Self {
+        json!({
+            "_id": String::from(value.id),
+            "_score": value.score,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HybridSearchJson {
+    component_id: Option<String>,
+    text_index_name: String,
+    search_field: String,
+    text_query: String,
+    vector_index_name: String,
+    vector: Vec<f32>,
+    limit: Option<u32>,
+    filter: Option<JsonExpression>,
+    fusion: Option<HybridFusionJson>,
+}
+
+impl HybridSearchJson {
+    /// Like `VectorSearchJson::insert_component_id`, the component id is only
+    /// known once we're executing inside v8.
+    pub fn insert_component_id(&mut self, component_id: ComponentId) {
+        self.component_id = component_id.serialize_to_string();
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type")]
+enum HybridFusionJson {
+    Rrf {
+        k: Option<f64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Weighted {
+        text_weight: f64,
+        vector_weight: f64,
+    },
+}
+
+impl TryFrom<JsonValue> for HybridSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        let search: HybridSearchJson = serde_json::from_value(value)?;
+        let component_id = ComponentId::deserialize_from_string(search.component_id.as_deref())?;
+        let text_index_name: IndexName = search.text_index_name.parse()?;
+        let vector_index_name: IndexName = search.vector_index_name.parse()?;
+        anyhow::ensure!(
+            text_index_name.table() == vector_index_name.table(),
+            ErrorMetadata::bad_request(
+                "InvalidHybridSearch",
+                format!(
+                    "Hybrid search indexes must be on the same table, but {text_index_name} and \
+                     {vector_index_name} aren't."
+                )
+            )
+        );
+        let filters = match search.filter {
+            Some(filter) => filters_from_expression(filter.try_into()?)?,
+            None => BTreeMap::new(),
+        };
+        let fusion = match search.fusion {
+            None => HybridFusion::default(),
+            Some(HybridFusionJson::Rrf { k }) => {
+                let k = k.unwrap_or(DEFAULT_RRF_K);
+                anyhow::ensure!(
+                    k.is_finite() && k >= 0.,
+                    ErrorMetadata::bad_request(
+                        "InvalidHybridSearch",
+                        format!("Reciprocal rank fusion's `k` must be non-negative, got {k}.")
+                    )
+                );
+                HybridFusion::ReciprocalRank { k }
+            },
+            Some(HybridFusionJson::Weighted {
+                text_weight,
+                vector_weight,
+            }) => {
+                anyhow::ensure!(
+                    [text_weight, vector_weight]
+                        .iter()
+                        .all(|w| w.is_finite() && *w >= 0.),
+                    ErrorMetadata::bad_request(
+                        "InvalidHybridSearch",
+                        format!(
+                            "Hybrid search weights must be non-negative, got {text_weight} and \
+                             {vector_weight}."
+                        )
+                    )
+                );
+                HybridFusion::Weighted {
+                    text_weight,
+                    vector_weight,
+                }
+            },
+        };
+        Ok(Self {
+            component_id,
+            text_index_name,
+            search_field: search.search_field.parse()?,
+            text_query: search.text_query,
+            vector_index_name,
+            vector: search.vector,
+            limit: search.limit,
+            filters,
+            fusion,
+        })
+    }
+}
+
+/// Shared filters are a `q.eq` or a `q.and` of `q.eq`s, matching text search
+/// filters.
+fn filters_from_expression(
+    expression: Expression,
+) -> anyhow::Result<BTreeMap<FieldPath, Option<ConvexValue>>> {
+    let clauses = match expression {
+        Expression::And(clauses) => clauses,
+        expression => vec![expression],
+    };
+    let mut filters = BTreeMap::new();
+    for clause in clauses {
+        let Expression::Eq(left, right) = clause else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchFilter",
+                "Hybrid search filters should be a combination of `q.eq` and `q.and`."
+            ));
+        };
+        let (Expression::Field(field_path), Expression::Literal(value)) = (*left, *right) else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchFilter",
+                "`q.eq` must take a field path as its first argument and a value as its second"
+            ));
+        };
+        if filters.contains_key(&field_path) {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchFilter",
+                format!("Hybrid search filters can only compare {field_path:?} once.")
+            ));
+        }
+        filters.insert(field_path, value.0);
+    }
+    Ok(filters)
+}
+
+/// Run a hybrid search within a transaction.
+///
+/// Each search fetches up to `limit` results before they're fused, and both
+/// are recorded in the transaction's read set like `vector_search`. With more
+/// than one filter, the vector search fetches up to `MAX_VECTOR_RESULTS`
+/// results instead and reads their documents until `limit` of them match
+/// every filter.
+#[fastrace::trace]
+pub async fn hybrid_search<RT: Runtime>(
+    tx: &mut Transaction<RT>,
+    query: HybridSearch,
+    table_filter: TableFilter,
+) -> anyhow::Result<Vec<HybridSearchResult>> {
+    let limit = query.limit.unwrap_or(DEFAULT_VECTOR_LIMIT);
+    anyhow::ensure!(
+        limit as usize <= MAX_VECTOR_RESULTS,
+        ErrorMetadata::bad_request(
+            "HybridLimitTooLargeError",
+            format!(
+                "Hybrid searches can fetch at most {MAX_VECTOR_RESULTS} results, requested \
+                 {limit}."
+            )
+        )
+    );
+    let namespace = TableNamespace::from(query.component_id);
+
+    let stable_index_name =
+        IndexModel::new(tx).stable_index_name(namespace, &query.text_index_name, table_filter)?;
+    let Some(tablet_id) = stable_index_name
+        .tablet_index_name()
+        .map(|index_name| *index_name.table())
+    else {
+        return Ok(vec![]);
+    };
+    let table_number = tx.table_mapping().tablet_number(tablet_id)?;
+    let mut search_filters = vec![SearchFilterExpression::Search(
+        query.search_field,
+        query.text_query,
+    )];
+    search_filters.extend(
+        query.filters.iter().map(|(field_path, value)| {
+            SearchFilterExpression::Eq(field_path.clone(), value.clone())
+        }),
+    );
+    let search = Search {
+        table: query.text_index_name.table().clone(),
+        index_name: query.text_index_name,
+        filters: search_filters,
+        order: None,
+    };
+    let text_ranking: Vec<_> = tx
+        .search(&stable_index_name, &search, SearchVersion::V2)
+        .await?
+        .into_iter()
+        .take(limit as usize)
+        .map(|(revision, _)| {
+            (
+                DeveloperDocumentId::new(table_number, revision.id),
+                revision.score as f64,
+            )
+        })
+        .collect();
+
+    // Vector filters on different fields match documents that match any of
+    // them, so fetch extra results to narrow down to the documents that match
+    // all of them.
+    let needs_post_filter = query.filters.len() > 1;
+    let vector_query = VectorSearch {
+        index_name: query.vector_index_name,
+        component_id: query.component_id,
+        limit: Some(if needs_post_filter {
+            MAX_VECTOR_RESULTS as u32
+        } else {
+            limit
+        }),
+        vector: query.vector,
+        expressions: query
+            .filters
+            .iter()
+            .map(|(field_path, value)| {
+                VectorSearchExpression::Eq(field_path.clone(), value.clone())
+            })
+            .collect(),
+    };
+    let mut vector_results = vector_search(tx, vector_query, table_filter).await?;
+    if needs_post_filter {
+        let mut filtered = Vec::with_capacity(limit as usize);
+        for result in vector_results {
+            if filtered.len() >= limit as usize {
+                break;
+            }
+            let id = tx.resolve_developer_id(&result.id, namespace)?;
+            let Some(document) = tx.get(id).await? else {
+                continue;
+            };
+            let value = document.value();
+            if query.filters.iter().all(|(field_path, filter_value)| {
+                value.get_path(field_path) == filter_value.as_ref()
+            }) {
+                filtered.push(result);
+            }
+        }
+        vector_results = filtered;
+    }
+    let vector_ranking: Vec<_> = vector_results
+        .into_iter()
+        .map(|result| (result.id, result.score as f64))
+        .collect();
+
+    Ok(query
+        .fusion
+        .fuse(&text_ranking, &vector_ranking, limit as usize))
+}
+
+impl HybridFusion {
+    /// Fuse rankings of `(id, score)` pairs, each ordered from best to worst.
+    fn fuse(
+        &self,
+        text_ranking: &[(DeveloperDocumentId, f64)],
+        vector_ranking: &[(DeveloperDocumentId, f64)],
+        limit: usize,
+    ) -> Vec<HybridSearchResult> {
+        let mut scores: BTreeMap<DeveloperDocumentId, f64> = BTreeMap::new();
+        match *self {
+            HybridFusion::ReciprocalRank { k } => {
+                for ranking in [text_ranking, vector_ranking] {
+                    for (rank, (id, _)) in ranking.iter().enumerate() {
+                        *scores.entry(*id).or_default() += 1. / (k + (rank + 1) as f64);
+                    }
+                }
+            },
+            HybridFusion::Weighted {
+                text_weight,
+                vector_weight,
+            } => {
+                for (ranking, weight) in
+                    [(text_ranking, text_weight), (vector_ranking, vector_weight)]
+                {
+                    let min = ranking
+                        .iter()
+                        .map(|(_, s)| *s)
+                        .fold(f64::INFINITY, f64::min);
+                    let max = ranking
+                        .iter()
+                        .map(|(_, s)| *s)
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    for (id, score) in ranking {
+                        // If every result has the same score, they're all the best match.
+                        let normalized = if max > min {
+                            (score - min) / (max - min)
+                        } else {
+                            1.
+                        };
+                        *scores.entry(*id).or_default() += weight * normalized;
+                    }
+                }
+            },
+        }
+        let mut results: Vec<_> = scores
+            .into_iter()
+            .map(|(id, score)| HybridSearchResult { id, score })
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.id.cmp(&b.id)));
+        results.truncate(limit);
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use value::{
+        DeveloperDocumentId,
+        InternalId,
+        TableNumber,
+    };
+
+    use super::HybridFusion;
+
+    fn id(n: u128) -> DeveloperDocumentId {
+        DeveloperDocumentId::new(
+            TableNumber::try_from(1).unwrap(),
+            InternalId(n.to_le_bytes()),
+        )
+    }
+
+    fn ids(fusion: HybridFusion, text: &[(u128, f64)], vector: &[(u128, f64)]) -> Vec<u128> {
+        let text: Vec<_> = text.iter().map(|(n, s)| (id(*n), *s)).collect();
+        let vector: Vec<_> = vector.iter().map(|(n, s)| (id(*n), *s)).collect();
+        fusion
+            .fuse(&text, &vector, 3)
+            .into_iter()
+            .map(|result| u128::from_le_bytes(result.id.internal_id().0))
+            .collect()
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let fusion = HybridFusion::ReciprocalRank { k: 60. };
+        // 2 is ranked well by both searches, so it beats the top result of each.
+        assert_eq!(
+            ids(
+                fusion,
+                &[(1, 9.), (2, 8.), (3, 1.)],
+                &[(4, 0.9), (2, 0.8), (5, 0.1)]
+            ),
+            vec![2, 1, 4],
+        );
+    }
+
+    #[test]
+    fn test_weighted_fusion() {
+        let fusion = HybridFusion::Weighted {
+            text_weight: 1.,
+            vector_weight: 3.,
+        };
+        // Normalized text scores: 1 => 1, 2 => 0.5, 3 => 0.
+        // Normalized vector scores: 3 => 1, 1 => 0.
+        assert_eq!(
+            ids(fusion, &[(1, 4.), (2, 3.), (3, 2.)], &[(3, 0.9), (1, 0.5)]),
+            vec![3, 1, 2],
+        );
+    }
+}
@@ -63,11 +63,30 @@ use crate::{
 };
 
 mod filter;
+mod hybrid_search;
 mod index_range;
 mod limit;
 mod search_query;
-
+mod text_search;
+mod vector_search;
+
+pub use hybrid_search::{
+    hybrid_search,
+    HybridFusion,
+    HybridSearch,
+    HybridSearchJson,
+    HybridSearchRequest,
+    HybridSearchResult,
+};
 pub use index_range::soft_data_limit;
+pub use text_search::{
+    text_search,
+    TextSearch,
+    TextSearchJson,
+    TextSearchRequest,
+    TextSearchResult,
+};
+pub use vector_search::vector_search;
 
 // Even in the presence of large prefetch hints, we should never fetch too much
 // data at once.
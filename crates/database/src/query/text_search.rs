@@ -0,0 +1,250 @@
+use common::{
+    bootstrap_model::index::IndexConfig,
+    components::ComponentId,
+    json::{
+        JsonSearchFilterExpression,
+        JsonSearchOrder,
+    },
+    query::{
+        Search,
+        SearchFilterExpression,
+        SearchOrder,
+        SearchVersion,
+    },
+    runtime::Runtime,
+    types::IndexName,
+};
+use errors::ErrorMetadata;
+use search::{
+    SearchHighlight,
+    TantivySearchIndexSchema,
+    DEFAULT_SNIPPET_TOKENS,
+    MAX_CANDIDATE_REVISIONS,
+    MAX_SNIPPET_TOKENS,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    json,
+    Value as JsonValue,
+};
+use value::{
+    DeveloperDocumentId,
+    TableNamespace,
+};
+
+use super::TableFilter;
+use crate::{
+    IndexModel,
+    Transaction,
+    UserFacingModel,
+};
+
+/// How many results a text search returns if it doesn't specify a limit.
+const DEFAULT_TEXT_SEARCH_LIMIT: u32 = 10;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextSearchRequest {
+    pub query: JsonValue,
+}
+
+/// A text search that returns scored ids rather than documents, optionally
+/// highlighting where each result matched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSearch {
+    pub component_id: ComponentId,
+    pub index_name: IndexName,
+    /// The same `q.search(...).eq(...)` filters as a search query.
+    pub filters: Vec<SearchFilterExpression>,
+    /// Order results by a filter field or `_creationTime` instead of by score.
+    pub order: Option<SearchOrder>,
+    pub limit: Option<u32>,
+    /// If set, highlight each result with a snippet spanning this many words.
+    pub snippet_tokens: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSearchResult {
+    pub id: DeveloperDocumentId,
+    pub score: f64,
+    pub highlight: Option<SearchHighlight>,
+}
+
+impl From<TextSearchResult> for JsonValue {
+    fn from(value: TextSearchResult) -> Self {
+        let mut result = json!({
+            "_id": String::from(value.id),
+            "_score": value.score,
+        });
+        if let Some(highlight) = value.highlight {
+            result["_highlight"] = json!({
+                "offsets": highlight.offsets,
+                "snippet": highlight.snippet,
+                "snippetOffset": highlight.snippet_offset,
+            });
+        }
+        result
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextSearchJson {
+    component_id: Option<String>,
+    index_name: String,
+    filters: Vec<JsonSearchFilterExpression>,
+    order: Option<JsonSearchOrder>,
+    limit: Option<u32>,
+    highlight: Option<TextSearchHighlightJson>,
+}
+
+impl TextSearchJson {
+    /// Like `VectorSearchJson::insert_component_id`, the component id is only
+    /// known once we're executing inside v8.
+    pub fn insert_component_id(&mut self, component_id: ComponentId) {
+        self.component_id = component_id.serialize_to_string();
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextSearchHighlightJson {
+    snippet_tokens: Option<u32>,
+}
+
+impl TryFrom<JsonValue> for TextSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
+        let search: TextSearchJson = serde_json::from_value(value)?;
+        let component_id = ComponentId::deserialize_from_string(search.component_id.as_deref())?;
+        let snippet_tokens = search
+            .highlight
+            .map(|highlight| {
+                let snippet_tokens = highlight
+                    .snippet_tokens
+                    .map_or(DEFAULT_SNIPPET_TOKENS, |n| n as usize);
+                anyhow::ensure!(
+                    (1..=MAX_SNIPPET_TOKENS).contains(&snippet_tokens),
+                    ErrorMetadata::bad_request(
+                        "InvalidSnippetTokens",
+                        format!(
+                            "Highlight snippets must span between 1 and {MAX_SNIPPET_TOKENS} \
+                             words, got {snippet_tokens}."
+                        )
+                    )
+                );
+                Ok(snippet_tokens)
+            })
+            .transpose()?;
+        Ok(Self {
+            component_id,
+            index_name: search.index_name.parse()?,
+            filters: search
+                .filters
+                .into_iter()
+                .map(SearchFilterExpression::try_from)
+                .collect::<anyhow::Result<_>>()?,
+            order: search.order.map(SearchOrder::try_from).transpose()?,
+            limit: search.limit,
+            snippet_tokens,
+        })
+    }
+}
+
+/// Run a text search within a transaction, recording it in the transaction's
+/// read set like a search query.
+///
+/// Highlights are computed by re-analyzing each result's search field with the
+/// index's analyzer, so they line up with the positions the search matched.
+#[fastrace::trace]
+pub async fn text_search<RT: Runtime>(
+    tx: &mut Transaction<RT>,
+    query: TextSearch,
+    table_filter: TableFilter,
+) -> anyhow::Result<Vec<TextSearchResult>> {
+    let limit = query.limit.unwrap_or(DEFAULT_TEXT_SEARCH_LIMIT);
+    anyhow::ensure!(
+        limit as usize <= MAX_CANDIDATE_REVISIONS,
+        ErrorMetadata::bad_request(
+            "TextSearchLimitTooLargeError",
+            format!(
+                "Text searches can fetch at most {MAX_CANDIDATE_REVISIONS} results, requested \
+                 {limit}."
+            )
+        )
+    );
+    let namespace = TableNamespace::from(query.component_id);
+
+    let stable_index_name =
+        IndexModel::new(tx).stable_index_name(namespace, &query.index_name, table_filter)?;
+    let Some(tablet_id) = stable_index_name
+        .tablet_index_name()
+        .map(|index_name| *index_name.table())
+    else {
+        return Ok(vec![]);
+    };
+    let table_number = tx.table_mapping().tablet_number(tablet_id)?;
+    let search_text = query.filters.iter().find_map(|filter| match filter {
+        SearchFilterExpression::Search(_, text) => Some(text.clone()),
+        _ => None,
+    });
+    let search = Search {
+        table: query.index_name.table().clone(),
+        index_name: query.index_name.clone(),
+        filters: query.filters,
+        order: query.order,
+    };
+    let candidates = tx
+        .search(&stable_index_name, &search, SearchVersion::V2)
+        .await?;
+
+    let schema = match (query.snippet_tokens, search_text) {
+        (Some(snippet_tokens), Some(search_text)) => {
+            let Some(metadata) =
+                IndexModel::new(tx).enabled_index_metadata(namespace, &query.index_name)?
+            else {
+                anyhow::bail!("Missing text index {}", query.index_name);
+            };
+            let IndexConfig::Text {
+                ref developer_config,
+                ..
+            } = metadata.config
+            else {
+                anyhow::bail!("{} is not a text index", query.index_name);
+            };
+            Some((
+                TantivySearchIndexSchema::new(developer_config),
+                snippet_tokens,
+                search_text,
+            ))
+        },
+        _ => None,
+    };
+
+    let mut results = Vec::with_capacity(limit as usize);
+    for (candidate, _) in candidates.into_iter().take(limit as usize) {
+        let id = DeveloperDocumentId::new(table_number, candidate.id);
+        let highlight = match schema {
+            Some((ref schema, snippet_tokens, ref search_text)) => {
+                let (document, _) = UserFacingModel::new(tx, namespace)
+                    .get_with_ts(id, None)
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Unable to load search result {id}@{:?}", candidate.ts)
+                    })?;
+                schema.highlight(search_text, document.value(), snippet_tokens)?
+            },
+            None => None,
+        };
+        results.push(TextSearchResult {
+            id,
+            score: candidate.score as f64,
+            highlight,
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,56 @@
+use common::runtime::Runtime;
+use value::{
+    Size,
+    TableNamespace,
+};
+use vector::{
+    PublicVectorSearchQueryResult,
+    VectorSearch,
+};
+
+use super::TableFilter;
+use crate::{
+    IndexModel,
+    Transaction,
+};
+
+/// Run a vector search within a transaction.
+///
+/// Unlike `Database::vector_search`, the search is recorded in the
+/// transaction's read set, so a query that runs it is invalidated when a
+/// write could change its results.
+#[fastrace::trace]
+pub async fn vector_search<RT: Runtime>(
+    tx: &mut Transaction<RT>,
+    query: VectorSearch,
+    table_filter: TableFilter,
+) -> anyhow::Result<Vec<PublicVectorSearchQueryResult>> {
+    let namespace = TableNamespace::from(query.component_id);
+    let stable_index_name =
+        IndexModel::new(tx).stable_index_name(namespace, &query.index_name, table_filter)?;
+    let Some(tablet_id) = stable_index_name
+        .tablet_index_name()
+        .map(|index_name| *index_name.table())
+    else {
+        return Ok(vec![]);
+    };
+    let component_id = query.component_id;
+    let results: Vec<_> = tx.vector_search(&stable_index_name, query).await?;
+
+    let table_number = tx.table_mapping().tablet_number(tablet_id)?;
+    let table_name = tx.table_mapping().tablet_name(tablet_id)?;
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|r| r.to_public(table_number))
+        .collect();
+    let size: u64 = results.iter().map(|row| row.size() as u64).sum();
+    let component_path = tx.must_component_path(component_id)?;
+    tx.usage_tracker.track_vector_egress_size(
+        component_path,
+        table_name.to_string(),
+        size,
+        // We don't have system owned vector indexes.
+        false,
+    );
+    Ok(results)
+}
@@ -40,6 +40,7 @@ use value::{
     TableName,
     TabletId,
 };
+use vector::VectorQueryReads;
 
 #[cfg(doc)]
 use crate::Transaction;
@@ -84,11 +85,12 @@ impl HeapSize for IndexReads {
 pub struct ReadSet {
     indexed: WithHeapSize<BTreeMap<TabletIndexName, IndexReads>>,
     search: WithHeapSize<BTreeMap<TabletIndexName, SearchQueryReads>>,
+    vector: WithHeapSize<BTreeMap<TabletIndexName, VectorQueryReads>>,
 }
 
 impl HeapSize for ReadSet {
     fn heap_size(&self) -> usize {
-        self.indexed.heap_size() + self.search.heap_size()
+        self.indexed.heap_size() + self.search.heap_size() + self.vector.heap_size()
     }
 }
 
@@ -97,16 +99,19 @@ impl ReadSet {
         Self {
             indexed: WithHeapSize::default(),
             search: WithHeapSize::default(),
+            vector: WithHeapSize::default(),
         }
     }
 
     pub fn new(
         indexed: BTreeMap<TabletIndexName, IndexReads>,
         search: BTreeMap<TabletIndexName, SearchQueryReads>,
+        vector: BTreeMap<TabletIndexName, VectorQueryReads>,
     ) -> Self {
         Self {
             indexed: indexed.into(),
             search: search.into(),
+            vector: vector.into(),
         }
     }
 
@@ -119,13 +124,22 @@ impl ReadSet {
         self.search.iter()
     }
 
+    pub fn iter_vector(&self) -> impl Iterator<Item = (&TabletIndexName, &VectorQueryReads)> {
+        self.vector.iter()
+    }
+
     pub fn consume(
         self,
     ) -> (
         impl Iterator<Item = (TabletIndexName, IndexReads)>,
         impl Iterator<Item = (TabletIndexName, SearchQueryReads)>,
+        impl Iterator<Item = (TabletIndexName, VectorQueryReads)>,
     ) {
-        (self.indexed.into_iter(), self.search.into_iter())
+        (
+            self.indexed.into_iter(),
+            self.search.into_iter(),
+            self.vector.into_iter(),
+        )
     }
 
     /// Determine whether a mutation to a document overlaps with the read set.
@@ -187,6 +201,16 @@ impl ReadSet {
                 });
             }
         }
+
+        for (index, vector_reads) in iter_indexes_for_table(&self.vector, document.id().tablet_id) {
+            if vector_reads.overlaps_document(document) {
+                return Some(ConflictingRead {
+                    index: index.clone(),
+                    id: document.id(),
+                    stack_traces: None,
+                });
+            }
+        }
         None
     }
 
@@ -362,13 +386,16 @@ impl TransactionReadSet {
         user_tx_size: TransactionReadSize,
         system_tx_size: TransactionReadSize,
     ) {
-        let (index_reads, search_reads) = reads.consume();
+        let (index_reads, search_reads, vector_reads) = reads.consume();
         for (index_name, index_reads) in index_reads {
             self._record_indexed(index_name, index_reads.fields, index_reads.intervals.iter());
         }
         for (index_name, search_reads) in search_reads {
             self.record_search(index_name, search_reads);
         }
+        for (index_name, vector_reads) in vector_reads {
+            self.record_vector(index_name, vector_reads);
+        }
         self.num_intervals += num_intervals;
         self.user_tx_size += user_tx_size;
         self.system_tx_size += system_tx_size;
@@ -491,6 +518,14 @@ impl TransactionReadSet {
         );
     }
 
+    pub fn record_vector(&mut self, index_name: TabletIndexName, vector_reads: VectorQueryReads) {
+        self.read_set.vector.mutate_entry_or_insert_with(
+            index_name,
+            VectorQueryReads::empty,
+            |existing_reads| existing_reads.merge(vector_reads),
+        );
+    }
+
     pub fn num_intervals(&self) -> usize {
         self.num_intervals
     }
@@ -523,6 +558,9 @@ impl proptest::arbitrary::Arbitrary for ReadSet {
             #[proptest(strategy = "prop::collection::vec(any::<(TabletIndexName, \
                                    SearchQueryReads)>(), 0..4)")]
             search: Vec<(TabletIndexName, SearchQueryReads)>,
+            #[proptest(strategy = "prop::collection::vec(any::<(TabletIndexName, \
+                                   VectorQueryReads)>(), 0..4)")]
+            vector: Vec<(TabletIndexName, VectorQueryReads)>,
         }
 
         any::<GeneratedReads>().prop_map(|generated_reads| {
@@ -544,9 +582,14 @@ impl proptest::arbitrary::Arbitrary for ReadSet {
                 .search
                 .into_iter()
                 .collect::<BTreeMap<_, _>>();
+            let vector = generated_reads
+                .vector
+                .into_iter()
+                .collect::<BTreeMap<_, _>>();
             Self {
                 indexed: indexed.into(),
                 search: search.into(),
+                vector: vector.into(),
             }
         })
     }
@@ -558,6 +601,7 @@ mod tests {
 
     use common::{
         assert_obj,
+        bootstrap_model::index::text_index::TextIndexAnalyzer,
         document::{
             CreationTime,
             PackedDocument,
@@ -632,6 +676,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::Zero,
                     token: "word".to_string(),
@@ -679,6 +724,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::One,
                     token: "wod".to_string(),
@@ -718,6 +764,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::Two,
                     token: "word".to_string(),
@@ -761,6 +808,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::Zero,
                     token: "word".to_string(),
@@ -808,6 +856,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::One,
                     token: "wrd".to_string(),
@@ -855,6 +904,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str(field_path)?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::Two,
                     token: "word".to_string(),
@@ -923,6 +973,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str("textField")?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Exact("word".to_string()),
             }]
             .into(),
@@ -1048,6 +1099,7 @@ mod tests {
         let search_reads = SearchQueryReads::new(
             vec![TextQueryTermRead {
                 field_path: FieldPath::from_str("extraField")?,
+                analyzer: TextIndexAnalyzer::Standard,
                 term: TextQueryTerm::Fuzzy {
                     max_distance: FuzzyDistance::Zero,
                     token: "word".to_string(),
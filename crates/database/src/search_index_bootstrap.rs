@@ -143,7 +143,10 @@ impl IndexesToBootstrap {
                     let vector_index_bootstrap_data = VectorIndexBootstrapData {
                         index_id: index_id.internal_id(),
                         on_disk_state,
-                        memory_index: MemoryVectorIndex::new(WriteTimestamp::Committed(ts.succ()?)),
+                        memory_index: MemoryVectorIndex::new(
+                            WriteTimestamp::Committed(ts.succ()?),
+                            developer_config.metric,
+                        ),
                         qdrant_schema,
                     };
                     if let Some(vector_indexes) =
@@ -365,11 +368,11 @@ impl TextIndexBootstrapData {
             WriteTimestamp::Committed(revision_pair.ts()),
             revision_pair
                 .prev_document()
-                .map(|d| anyhow::Ok((self.tantivy_schema.index_into_terms(d)?, d.creation_time())))
+                .map(|d| self.tantivy_schema.index_into_memory_document(d))
                 .transpose()?,
             revision_pair
                 .document()
-                .map(|d| anyhow::Ok((self.tantivy_schema.index_into_terms(d)?, d.creation_time())))
+                .map(|d| self.tantivy_schema.index_into_memory_document(d))
                 .transpose()?,
         )
     }
@@ -927,6 +930,9 @@ mod tests {
             vector_field,
             (2u32).try_into()?,
             btreeset![filter_field],
+            Default::default(),
+            None,
+            Default::default(),
         );
         Ok(metadata)
     }
@@ -1157,7 +1163,9 @@ mod tests {
         let index = IndexMetadata::new_backfilling_text_index(
             "test.by_text".parse()?,
             "searchField".parse()?,
+            Default::default(),
             btreeset! {"filterField".parse()?},
+            Default::default(),
         );
         IndexModel::new(&mut tx)
             .add_application_index(TableNamespace::test_user(), index)
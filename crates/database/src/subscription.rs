@@ -56,6 +56,7 @@ use tokio::sync::{
     },
     watch,
 };
+use vector::VectorQueryReads;
 
 use crate::{
     metrics,
@@ -319,6 +320,11 @@ impl SubscriptionManager {
                             &mut buffer,
                         );
                     }
+                    self.vector_overlapping(
+                        document_change.old_document.as_ref(),
+                        document_change.new_document.as_ref(),
+                        &mut to_notify,
+                    );
                 }
             })?;
 
@@ -362,6 +368,7 @@ impl SubscriptionManager {
             persistence_version,
             &mut IndexKeyBuffer::new(),
         );
+        self.vector_overlapping(Some(document), None, to_notify);
     }
 
     fn overlapping(
@@ -382,6 +389,54 @@ impl SubscriptionManager {
         self.subscriptions.search.add_matches(document, to_notify);
     }
 
+    /// Vector subscriptions can't be looked up by key like index ranges, so
+    /// each vector search on the written table is scored against the old and
+    /// new revisions of the document. That's O(subscribers * dimension) per
+    /// write, so skip indexes whose vector and filter values the write didn't
+    /// change, and subscribers that are already being notified.
+    fn vector_overlapping(
+        &self,
+        old_document: Option<&PackedDocument>,
+        new_document: Option<&PackedDocument>,
+        to_notify: &mut BTreeSet<SubscriberId>,
+    ) {
+        let Some(tablet_id) = old_document.or(new_document).map(|d| d.id().tablet_id) else {
+            return;
+        };
+        let mut num_checks = 0;
+        // `TabletIndexName` is ordered by tablet first, so this visits just
+        // the indexes on the written table.
+        for (_, subscribers) in self
+            .subscriptions
+            .vector
+            .range(TabletIndexName::min_for_table(tablet_id)..)
+            .take_while(|(index, _)| *index.table() == tablet_id)
+        {
+            if let (Some(old_document), Some(new_document)) = (old_document, new_document)
+                && let Some(reads) = subscribers.values().next()
+                && reads.is_unchanged_by_write(old_document, new_document)
+            {
+                continue;
+            }
+            for (subscriber_id, reads) in subscribers {
+                if to_notify.contains(subscriber_id) {
+                    continue;
+                }
+                num_checks += 1;
+                if old_document
+                    .into_iter()
+                    .chain(new_document)
+                    .any(|document| reads.overlaps_document(document))
+                {
+                    to_notify.insert(*subscriber_id);
+                }
+            }
+        }
+        if num_checks > 0 {
+            metrics::log_vector_subscription_checks(num_checks);
+        }
+    }
+
     fn get_subscriber(&self, key: SubscriptionKey) -> Option<&Subscriber> {
         let entry = self.subscribers.get(key.id)?;
         if entry.seq > key.seq {
@@ -468,6 +523,7 @@ impl Subscription {
 struct SubscriptionMap {
     indexed: BTreeMap<TabletIndexName, (IndexedFields, IntervalMap<SubscriberId>)>,
     search: TextSearchSubscriptions,
+    vector: BTreeMap<TabletIndexName, BTreeMap<SubscriberId, VectorQueryReads>>,
 }
 
 impl SubscriptionMap {
@@ -475,6 +531,7 @@ impl SubscriptionMap {
         Self {
             indexed: BTreeMap::new(),
             search: TextSearchSubscriptions::new(),
+            vector: BTreeMap::new(),
         }
     }
 
@@ -489,6 +546,12 @@ impl SubscriptionMap {
         for (index, reads) in reads.iter_search() {
             self.search.insert(id, index, reads);
         }
+        for (index, reads) in reads.iter_vector() {
+            self.vector
+                .entry(index.clone())
+                .or_default()
+                .insert(id, reads.clone());
+        }
     }
 
     fn remove(&mut self, id: SubscriberId, reads: &ReadSet) {
@@ -505,6 +568,16 @@ impl SubscriptionMap {
         for (index, reads) in reads.iter_search() {
             self.search.remove(id, index, reads);
         }
+        for (index, _) in reads.iter_vector() {
+            let subscribers = self
+                .vector
+                .get_mut(index)
+                .unwrap_or_else(|| panic!("Missing vector index entry for {}", index));
+            assert!(subscribers.remove(&id).is_some());
+            if subscribers.is_empty() {
+                self.vector.remove(index);
+            }
+        }
     }
 }
 
@@ -522,6 +595,10 @@ mod tests {
 
     use cmd_util::env::env_config;
     use common::{
+        bootstrap_model::index::vector_index::{
+            DeveloperVectorIndexConfig,
+            VectorDistanceMetric,
+        },
         document::{
             CreationTime,
             PackedDocument,
@@ -535,11 +612,15 @@ mod tests {
             PersistenceVersion,
             SubscriberId,
             TabletIndexName,
+            WriteTimestamp,
         },
     };
     use convex_macro::test_runtime;
     use itertools::Itertools;
-    use maplit::btreeset;
+    use maplit::{
+        btreemap,
+        btreeset,
+    };
     use proptest::{
         collection::VecStrategy,
         prelude::*,
@@ -573,6 +654,12 @@ mod tests {
         TabletId,
         TabletIdAndTableNumber,
     };
+    use vector::{
+        CompiledVectorSearch,
+        QdrantSchema,
+        VectorQueryReads,
+        VectorSearchQueryResult,
+    };
 
     use crate::{
         subscription::SubscriptionManager,
@@ -979,6 +1066,99 @@ mod tests {
         to_notify
     }
 
+    fn vector_document(id: ResolvedDocumentId, vector: [f64; 2]) -> PackedDocument {
+        let embedding: ConvexValue = vector
+            .into_iter()
+            .map(ConvexValue::Float64)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let mut map: BTreeMap<FieldName, ConvexValue> = BTreeMap::new();
+        map.insert("embedding".parse().unwrap(), embedding);
+        let time =
+            CreationTime::try_from(Timestamp::MIN.add(Duration::from_secs(1)).unwrap()).unwrap();
+        pack(&ResolvedDocument::new(id, time, ConvexObject::try_from(map).unwrap()).unwrap())
+    }
+
+    #[test_runtime]
+    async fn vector_subscriptions_are_notified_of_writes_entering_top_k(
+        _rt: TestRuntime,
+    ) -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let table_name = id_generator.generate_table_name();
+        let table_id = id_generator.user_table_id(&table_name);
+        let index_name: TabletIndexName =
+            GenericIndexName::new(table_id.tablet_id, IndexDescriptor::new("by_embedding")?)?;
+        let schema = QdrantSchema::new(&DeveloperVectorIndexConfig {
+            dimensions: 2.try_into()?,
+            vector_field: "embedding".parse()?,
+            filter_fields: BTreeSet::new(),
+            metric: VectorDistanceMetric::Cosine,
+            quantization: None,
+            hnsw: Default::default(),
+        });
+        let query = CompiledVectorSearch {
+            vector: vec![1., 0.].try_into()?,
+            limit: 1,
+            filter_conditions: BTreeMap::new(),
+        };
+        // The query's one result has a cosine similarity of 0.8.
+        let results = [VectorSearchQueryResult {
+            score: 0.8,
+            id: id_generator.generate_internal(),
+            ts: WriteTimestamp::Committed(Timestamp::must(1)),
+        }];
+        let reads = ReadSet::new(
+            BTreeMap::new(),
+            BTreeMap::new(),
+            btreemap! { index_name => VectorQueryReads::new(schema, &query, &results) },
+        );
+
+        let mut subscription_manager = SubscriptionManager::new_for_testing();
+        let (_subscription, id) = subscription_manager
+            .subscribe_for_testing(Token::new_for_testing(reads, Timestamp::must(1)))?;
+
+        let notify = |document: PackedDocument| {
+            let mut to_notify = BTreeSet::new();
+            subscription_manager.overlapping_for_testing(
+                &document,
+                &mut to_notify,
+                PersistenceVersion::V5,
+            );
+            to_notify
+        };
+        // A document closer to the query than the current result enters the
+        // top-k and invalidates the subscription.
+        let closer = vector_document(id_generator.user_generate(&table_name), [1., 0.]);
+        assert_eq!(notify(closer), btreeset! { id });
+        // One further from the query than the cutoff doesn't.
+        let further = vector_document(id_generator.user_generate(&table_name), [0., 1.]);
+        assert!(notify(further).is_empty());
+        // Neither does a write to another table.
+        let other_table = id_generator.generate_table_name();
+        let other = vector_document(id_generator.user_generate(&other_table), [1., 0.]);
+        assert!(notify(other).is_empty());
+
+        let write = |old_document: &PackedDocument, new_document: &PackedDocument| {
+            let mut to_notify = BTreeSet::new();
+            subscription_manager.vector_overlapping(
+                Some(old_document),
+                Some(new_document),
+                &mut to_notify,
+            );
+            to_notify
+        };
+        // Updating a document in the top-k without changing its vector can't
+        // change the search's results, so it's skipped.
+        let id_in_results = id_generator.user_generate(&table_name);
+        let before = vector_document(id_in_results, [1., 0.]);
+        assert!(write(&before, &before.clone()).is_empty());
+        // Moving it out of the top-k does invalidate the subscription.
+        let moved = vector_document(id_in_results, [0., 1.]);
+        assert_eq!(write(&before, &moved), btreeset! { id });
+        Ok(())
+    }
+
     fn disconnected_rx<T>() -> mpsc::Receiver<T> {
         mpsc::channel(1).1
     }
@@ -0,0 +1,106 @@
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use common::{
+    runtime::Runtime,
+    shutdown::ShutdownSignal,
+    testing::assert_contains,
+};
+use events::testing::TestUsageEventLogger;
+use keybroker::Identity;
+use runtime::testing::TestRuntime;
+use search::searcher::SearcherStub;
+use value::{
+    assert_obj,
+    ResolvedDocumentId,
+    TableName,
+};
+
+use crate::{
+    test_helpers::DbFixtures,
+    Database,
+    TestFacingModel,
+};
+
+async fn insert<RT: Runtime>(
+    db: &Database<RT>,
+    table_name: &TableName,
+) -> anyhow::Result<ResolvedDocumentId> {
+    let mut tx = db.begin(Identity::system()).await?;
+    let id = TestFacingModel::new(&mut tx)
+        .insert(table_name, assert_obj!("field" => "value"))
+        .await?;
+    db.commit(tx).await?;
+    Ok(id)
+}
+
+async fn wait_for_document<RT: Runtime>(
+    rt: &RT,
+    follower: &Database<RT>,
+    id: ResolvedDocumentId,
+) -> anyhow::Result<()> {
+    for _ in 0..100 {
+        let mut tx = follower.begin(Identity::system()).await?;
+        if tx.get(id).await?.is_some() {
+            return Ok(());
+        }
+        rt.wait(Duration::from_millis(100)).await;
+    }
+    anyhow::bail!("follower never observed {id}");
+}
+
+#[convex_macro::test_runtime]
+async fn test_follower_tails_leader_commits(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db: leader, tp, .. } = DbFixtures::new(&rt).await?;
+    let table_name: TableName = "table".parse()?;
+    let first_id = insert(&leader, &table_name).await?;
+    leader.bump_max_repeatable_ts().await?;
+
+    let follower = Database::load_follower(
+        tp.reader(),
+        rt.clone(),
+        Arc::new(SearcherStub {}),
+        ShutdownSignal::panic(),
+        Default::default(),
+        Arc::new(TestUsageEventLogger::new()),
+    )
+    .await?;
+    assert!(follower.is_read_replica());
+    assert!(!leader.is_read_replica());
+    // Documents committed before the follower loaded are visible immediately.
+    let mut tx = follower.begin(Identity::system()).await?;
+    assert!(tx.get(first_id).await?.is_some());
+
+    // New commits become visible once the leader makes them repeatable.
+    let second_id = insert(&leader, &table_name).await?;
+    leader.bump_max_repeatable_ts().await?;
+    wait_for_document(&rt, &follower, second_id).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_follower_rejects_writes(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db: leader, tp, .. } = DbFixtures::new(&rt).await?;
+    let table_name: TableName = "table".parse()?;
+    insert(&leader, &table_name).await?;
+    leader.bump_max_repeatable_ts().await?;
+
+    let follower = Database::load_follower(
+        tp.reader(),
+        rt.clone(),
+        Arc::new(SearcherStub {}),
+        ShutdownSignal::panic(),
+        Default::default(),
+        Arc::new(TestUsageEventLogger::new()),
+    )
+    .await?;
+    let err = insert(&follower, &table_name).await.unwrap_err();
+    assert_contains(&err, "read replica");
+
+    // Read-only transactions still commit.
+    let tx = follower.begin(Identity::system()).await?;
+    follower.commit(tx).await?;
+    Ok(())
+}
@@ -10,6 +10,7 @@ use std::{
 };
 
 use ::usage_tracking::FunctionUsageTracker;
+use anyhow::Context;
 use cmd_util::env::env_config;
 use common::{
     assert_obj,
@@ -22,7 +23,10 @@ use common::{
             IndexConfig,
             IndexMetadata,
         },
-        schema::SchemaState,
+        schema::{
+            SchemaMetadata,
+            SchemaState,
+        },
     },
     db_schema,
     document::{
@@ -30,6 +34,7 @@ use common::{
         PackedDocument,
         ResolvedDocument,
     },
+    knobs::REFERENCE_ACTIONS_MAX_DOCUMENTS,
     maybe_val,
     object_validator,
     persistence::{
@@ -55,6 +60,8 @@ use common::{
         DatabaseSchema,
         DocumentSchema,
         IndexSchema,
+        OnDeleteAction,
+        ReferenceSchema,
         TableDefinition,
         MAX_INDEXES_PER_TABLE,
     },
@@ -95,6 +102,7 @@ use value::{
     id_v6::DeveloperDocumentId,
     val,
     FieldPath,
+    IdentifierFieldName,
     ResolvedDocumentId,
     TableMapping,
     TableNamespace,
@@ -136,6 +144,7 @@ use crate::{
 };
 
 mod committer_race_tests;
+mod follower_tests;
 mod randomized_search_tests;
 mod streaming_export_tests;
 mod usage_tracking;
@@ -265,6 +274,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
         IndexSchema {
             index_descriptor: index_name1.descriptor().clone(),
             fields: vec![str::parse("a")?, str::parse("b")?].try_into()?,
+            unique: false,
         },
     );
     indexes.insert(
@@ -272,6 +282,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
         IndexSchema {
             index_descriptor: index_name2.descriptor().clone(),
             fields: vec![str::parse("c")?, str::parse("d")?].try_into()?,
+            unique: false,
         },
     );
 
@@ -283,6 +294,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
             indexes,
             search_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
+            references: Default::default(),
             document_type: None,
         },
     );
@@ -321,6 +333,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
         IndexSchema {
             index_descriptor: index_name2.descriptor().clone(),
             fields: vec![str::parse("c")?].try_into()?,
+            unique: false,
         },
     );
     indexes.insert(
@@ -328,6 +341,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
         IndexSchema {
             index_descriptor: index_name3.descriptor().clone(),
             fields: vec![str::parse("e")?, str::parse("f")?].try_into()?,
+            unique: false,
         },
     );
 
@@ -339,6 +353,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
             indexes,
             search_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
+            references: Default::default(),
             document_type: None,
         },
     );
@@ -395,7 +410,7 @@ fn get_pending_index_fields(
         .pending_index_metadata(namespace, index_name)?
         .expect("index should exist");
     must_let!(let IndexConfig::Database { developer_config, .. } = &index_c_d.config);
-    must_let!(let DeveloperDatabaseIndexConfig { fields } = developer_config);
+    must_let!(let DeveloperDatabaseIndexConfig { fields, .. } = developer_config);
     Ok(fields.clone())
 }
 
@@ -2171,6 +2186,411 @@ async fn add_and_enable_index(
     Ok(())
 }
 
+async fn add_and_enable_unique_email_index(
+    rt: TestRuntime,
+    database: &Database<TestRuntime>,
+    tp: Arc<dyn Persistence>,
+    table_name: &TableName,
+) -> anyhow::Result<()> {
+    let namespace = TableNamespace::test_user();
+    let index_name = IndexName::new(table_name.clone(), IndexDescriptor::new("by_email")?)?;
+    let mut tx = database.begin_system().await?;
+    let begin_ts = tx.begin_timestamp();
+    IndexModel::new(&mut tx)
+        .add_application_index(
+            namespace,
+            IndexMetadata::new_backfilling_database_index(
+                *begin_ts,
+                index_name.clone(),
+                DeveloperDatabaseIndexConfig {
+                    fields: vec!["email".parse()?].try_into()?,
+                    unique: true,
+                },
+            ),
+        )
+        .await?;
+    database.commit(tx).await?;
+    IndexWorker::new_terminating(rt, tp, Arc::new(NoopRetentionValidator), database.clone())
+        .await?;
+    let mut tx = database.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .enable_index_for_testing(namespace, &index_name)
+        .await?;
+    database.commit(tx).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_unique_index(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let table_name: TableName = "users".parse()?;
+    let mut tx = db.begin_system().await?;
+    let ada = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "ada@example.com"),
+        )
+        .await?;
+    db.commit(tx).await?;
+
+    add_and_enable_unique_email_index(rt, &db, tp, &table_name).await?;
+
+    let mut tx = db.begin_system().await?;
+    let err = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "ada@example.com"),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.is_conflict());
+    assert_eq!(err.short_msg(), "UniqueConstraintViolation");
+    assert!(format!("{err}").contains(&ada.to_string()), "{err}");
+
+    // Documents missing the indexed field are exempt.
+    for _ in 0..2 {
+        UserFacingModel::new_root_for_test(&mut tx)
+            .insert(table_name.clone(), assert_obj!())
+            .await?;
+    }
+    // Rewriting a document with its own key is fine.
+    UserFacingModel::new_root_for_test(&mut tx)
+        .replace(
+            ada,
+            assert_obj!("email" => "ada@example.com", "name" => "Ada"),
+        )
+        .await?;
+    let grace = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "grace@example.com"),
+        )
+        .await?;
+    let err = UserFacingModel::new_root_for_test(&mut tx)
+        .patch(grace, assert_obj!("email" => "ada@example.com").into())
+        .await
+        .unwrap_err();
+    assert!(err.is_conflict());
+    assert_eq!(err.short_msg(), "UniqueConstraintViolation");
+    db.commit(tx).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_unique_index_concurrent_writes(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let table_name: TableName = "users".parse()?;
+    let mut tx = db.begin_system().await?;
+    let ada = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "ada@example.com"),
+        )
+        .await?;
+    db.commit(tx).await?;
+    add_and_enable_unique_email_index(rt, &db, tp, &table_name).await?;
+
+    // Neither transaction sees the other's insert, so both pass the uniqueness
+    // check, but the second to commit fails with an OCC error because it read
+    // the key's range.
+    let mut tx1 = db.begin_system().await?;
+    UserFacingModel::new_root_for_test(&mut tx1)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "alan@example.com"),
+        )
+        .await?;
+    let mut tx2 = db.begin_system().await?;
+    UserFacingModel::new_root_for_test(&mut tx2)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "alan@example.com"),
+        )
+        .await?;
+    db.commit(tx1).await?;
+    must_let!(let Err(e) = db.commit(tx2).await);
+    assert!(e.is_occ());
+
+    // The same goes for changing an existing document's key to one that's
+    // being inserted concurrently.
+    let mut tx1 = db.begin_system().await?;
+    UserFacingModel::new_root_for_test(&mut tx1)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "grace@example.com"),
+        )
+        .await?;
+    let mut tx2 = db.begin_system().await?;
+    UserFacingModel::new_root_for_test(&mut tx2)
+        .patch(ada, assert_obj!("email" => "grace@example.com").into())
+        .await?;
+    db.commit(tx1).await?;
+    must_let!(let Err(e) = db.commit(tx2).await);
+    assert!(e.is_occ());
+
+    // Once the first commit is visible, retrying reports the violation.
+    let mut tx = db.begin_system().await?;
+    let err = UserFacingModel::new_root_for_test(&mut tx)
+        .patch(ada, assert_obj!("email" => "grace@example.com").into())
+        .await
+        .unwrap_err();
+    assert!(err.is_conflict());
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_unique_index_backfill_fails_schema_on_duplicates(
+    rt: TestRuntime,
+) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let namespace = TableNamespace::test_user();
+    let table_name: TableName = "users".parse()?;
+    let mut tx = db.begin_system().await?;
+    let ada = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "ada@example.com"),
+        )
+        .await?;
+    let ada_again = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            table_name.clone(),
+            assert_obj!("email" => "ada@example.com"),
+        )
+        .await?;
+    let (schema_id, _) = SchemaModel::new(&mut tx, namespace)
+        .submit_pending(db_schema!())
+        .await?;
+    let begin_ts = tx.begin_timestamp();
+    IndexModel::new(&mut tx)
+        .add_application_index(
+            namespace,
+            IndexMetadata::new_backfilling_database_index(
+                *begin_ts,
+                IndexName::new(table_name.clone(), IndexDescriptor::new("by_email")?)?,
+                DeveloperDatabaseIndexConfig {
+                    fields: vec!["email".parse()?].try_into()?,
+                    unique: true,
+                },
+            ),
+        )
+        .await?;
+    db.commit(tx).await?;
+
+    IndexWorker::new_terminating(rt, tp, Arc::new(NoopRetentionValidator), db.clone()).await?;
+
+    let mut tx = db.begin_system().await?;
+    let schema = tx.get(schema_id).await?.context("Missing schema")?;
+    let SchemaMetadata { state, .. } = schema.into_value().0.try_into()?;
+    must_let!(let SchemaState::Failed { error, .. } = state);
+    assert!(error.contains(&ada.to_string()), "{error}");
+    assert!(error.contains(&ada_again.to_string()), "{error}");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_reference_on_delete(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let namespace = TableNamespace::test_user();
+    let id_value = |id: DeveloperDocumentId| -> anyhow::Result<ConvexValue> {
+        Ok(ConvexValue::String(id.encode().try_into()?))
+    };
+    let mut tx = db.begin_system().await?;
+    let mut model = UserFacingModel::new_root_for_test(&mut tx);
+    let ada = model.insert("users".parse()?, assert_obj!()).await?;
+    let grace = model.insert("users".parse()?, assert_obj!()).await?;
+    let post = model
+        .insert("posts".parse()?, assert_obj!("author" => id_value(ada)?))
+        .await?;
+    let comment = model
+        .insert("comments".parse()?, assert_obj!("author" => id_value(ada)?))
+        .await?;
+    model
+        .insert("teams".parse()?, assert_obj!("owner" => id_value(grace)?))
+        .await?;
+    db.commit(tx).await?;
+
+    let mut db_schema = db_schema!(
+        "users" => DocumentSchema::Any,
+        "posts" => DocumentSchema::Any,
+        "comments" => DocumentSchema::Any,
+        "teams" => DocumentSchema::Any,
+    );
+    for (table, field, on_delete) in [
+        ("posts", "author", OnDeleteAction::Cascade),
+        ("comments", "author", OnDeleteAction::SetNull),
+        ("teams", "owner", OnDeleteAction::Restrict),
+    ] {
+        let table_name: TableName = table.parse()?;
+        let field: IdentifierFieldName = field.parse()?;
+        db_schema
+            .tables
+            .get_mut(&table_name)
+            .context("missing table")?
+            .references
+            .insert(
+                field.clone(),
+                ReferenceSchema {
+                    field,
+                    referenced_table: "users".parse()?,
+                    on_delete,
+                },
+            );
+    }
+    let mut tx = db.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .add_reference_indexes(namespace, &db_schema)
+        .await?;
+    let mut schema_model = SchemaModel::new(&mut tx, namespace);
+    let (schema_id, _) = schema_model.submit_pending(db_schema).await?;
+    schema_model.mark_validated(schema_id).await?;
+    schema_model.mark_active(schema_id).await?;
+    db.commit(tx).await?;
+    IndexWorker::new_terminating(rt, tp, Arc::new(NoopRetentionValidator), db.clone()).await?;
+
+    // Deleting ada cascades to her post and clears the comment's author.
+    let mut tx = db.begin_system().await?;
+    let mut model = UserFacingModel::new_root_for_test(&mut tx);
+    model.delete(ada).await?;
+    assert!(model.get(post, None).await?.is_none());
+    let comment = model.get(comment, None).await?.context("missing comment")?;
+    assert_eq!(comment.value().get("author"), Some(&ConvexValue::Null));
+    db.commit(tx).await?;
+
+    // Grace still owns a team, so deleting her fails.
+    let mut tx = db.begin_system().await?;
+    let err = UserFacingModel::new_root_for_test(&mut tx)
+        .delete(grace)
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "ReferenceRestrictViolation");
+    assert!(UserFacingModel::new_root_for_test(&mut tx)
+        .get(grace, None)
+        .await?
+        .is_some());
+    Ok(())
+}
+
+fn posts_reference_users(on_delete: OnDeleteAction) -> anyhow::Result<DatabaseSchema> {
+    let mut db_schema = db_schema!(
+        "users" => DocumentSchema::Any,
+        "posts" => DocumentSchema::Any,
+    );
+    let field: IdentifierFieldName = "author".parse()?;
+    db_schema
+        .tables
+        .get_mut(&"posts".parse()?)
+        .context("missing table")?
+        .references
+        .insert(
+            field.clone(),
+            ReferenceSchema {
+                field,
+                referenced_table: "users".parse()?,
+                on_delete,
+            },
+        );
+    Ok(db_schema)
+}
+
+#[convex_macro::test_runtime]
+async fn test_reference_actions_wait_for_index(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let namespace = TableNamespace::test_user();
+    let id_value = |id: DeveloperDocumentId| -> anyhow::Result<ConvexValue> {
+        Ok(ConvexValue::String(id.encode().try_into()?))
+    };
+    let mut tx = db.begin_system().await?;
+    let mut model = UserFacingModel::new_root_for_test(&mut tx);
+    let ada = model.insert("users".parse()?, assert_obj!()).await?;
+    let grace = model.insert("users".parse()?, assert_obj!()).await?;
+    let ada_post = model
+        .insert("posts".parse()?, assert_obj!("author" => id_value(ada)?))
+        .await?;
+    let grace_post = model
+        .insert("posts".parse()?, assert_obj!("author" => id_value(grace)?))
+        .await?;
+    db.commit(tx).await?;
+
+    let db_schema = posts_reference_users(OnDeleteAction::Cascade)?;
+    let mut tx = db.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .add_reference_indexes(namespace, &db_schema)
+        .await?;
+    let mut schema_model = SchemaModel::new(&mut tx, namespace);
+    let (schema_id, _) = schema_model.submit_pending(db_schema).await?;
+    schema_model.mark_validated(schema_id).await?;
+    // The reference index is still backfilling, so the schema can't be
+    // activated yet.
+    let err = schema_model.apply(Some(schema_id)).await.unwrap_err();
+    assert_eq!(err.short_msg(), "ReferenceIndexNotReady");
+    // Activating it anyway doesn't enforce the reference until the index is
+    // enabled.
+    schema_model.mark_active(schema_id).await?;
+    db.commit(tx).await?;
+    let mut tx = db.begin_system().await?;
+    let mut model = UserFacingModel::new_root_for_test(&mut tx);
+    model.delete(ada).await?;
+    assert!(model.get(ada_post, None).await?.is_some());
+    db.commit(tx).await?;
+
+    IndexWorker::new_terminating(rt, tp, Arc::new(NoopRetentionValidator), db.clone()).await?;
+    let mut tx = db.begin_system().await?;
+    SchemaModel::new(&mut tx, namespace)
+        .apply(Some(schema_id))
+        .await?;
+    let mut model = UserFacingModel::new_root_for_test(&mut tx);
+    model.delete(grace).await?;
+    assert!(model.get(grace_post, None).await?.is_none());
+    db.commit(tx).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_reference_actions_limit(rt: TestRuntime) -> anyhow::Result<()> {
+    let DbFixtures { db, tp, .. } = DbFixtures::new(&rt).await?;
+    let namespace = TableNamespace::test_user();
+    let db_schema = posts_reference_users(OnDeleteAction::Cascade)?;
+    let mut tx = db.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .add_reference_indexes(namespace, &db_schema)
+        .await?;
+    let mut schema_model = SchemaModel::new(&mut tx, namespace);
+    let (schema_id, _) = schema_model.submit_pending(db_schema).await?;
+    schema_model.mark_validated(schema_id).await?;
+    schema_model.mark_active(schema_id).await?;
+    db.commit(tx).await?;
+    IndexWorker::new_terminating(rt, tp, Arc::new(NoopRetentionValidator), db.clone()).await?;
+
+    let mut tx = db.begin_system().await?;
+    let ada = UserFacingModel::new_root_for_test(&mut tx)
+        .insert("users".parse()?, assert_obj!())
+        .await?;
+    db.commit(tx).await?;
+    let author = ConvexValue::String(ada.encode().try_into()?);
+    let mut remaining = *REFERENCE_ACTIONS_MAX_DOCUMENTS + 1;
+    while remaining > 0 {
+        let mut tx = db.begin_system().await?;
+        for _ in 0..remaining.min(1000) {
+            UserFacingModel::new_root_for_test(&mut tx)
+                .insert("posts".parse()?, assert_obj!("author" => author.clone()))
+                .await?;
+        }
+        db.commit(tx).await?;
+        remaining = remaining.saturating_sub(1000);
+    }
+
+    // Cascading to every post would exceed the limit, so the delete fails
+    // before writing anything.
+    let mut tx = db.begin_system().await?;
+    let err = UserFacingModel::new_root_for_test(&mut tx)
+        .delete(ada)
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "TooManyReferenceActions");
+    Ok(())
+}
+
 #[convex_macro::test_runtime]
 async fn test_query_filter_readset(rt: TestRuntime) -> anyhow::Result<()> {
     let DbFixtures {
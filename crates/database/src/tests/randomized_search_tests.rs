@@ -16,21 +16,28 @@ use async_trait::async_trait;
 use cmd_util::env::env_config;
 use common::{
     bootstrap_model::index::{
-        text_index::FragmentedTextSegment,
+        text_index::{
+            FragmentedTextSegment,
+            SearchFieldBoost,
+        },
         vector_index::FragmentedVectorSegment,
         IndexMetadata,
     },
+    components::ComponentId,
+    document::CREATION_TIME_FIELD_PATH,
     floating_point::assert_approx_equal,
     knobs::DATABASE_WORKERS_MAX_CHECKPOINT_AGE,
     pause::PauseController,
     persistence::Persistence,
     query::{
         CursorPosition,
+        Order,
         Query,
         QueryOperator,
         QuerySource,
         Search,
         SearchFilterExpression,
+        SearchOrder,
         SearchVersion,
     },
     types::{
@@ -57,7 +64,10 @@ use futures::{
     FutureExt,
 };
 use keybroker::Identity;
-use maplit::btreeset;
+use maplit::{
+    btreemap,
+    btreeset,
+};
 use must_let::must_let;
 use pb::searchlight::FragmentedVectorSegmentPaths;
 use proptest::prelude::*;
@@ -84,6 +94,7 @@ use storage::Storage;
 use usage_tracking::FunctionUsageTracker;
 use value::{
     assert_obj,
+    DeveloperDocumentId,
     TableNamespace,
 };
 use vector::{
@@ -100,8 +111,11 @@ use crate::{
         writer::SearchIndexMetadataWriter,
     },
     query::{
+        text_search,
         PaginationOptions,
         TableFilter,
+        TextSearch,
+        TextSearchResult,
     },
     search_index_bootstrap::FINISHED_BOOTSTRAP_UPDATES,
     test_helpers::{
@@ -173,7 +187,9 @@ impl Scenario {
         let index = IndexMetadata::new_backfilling_text_index(
             "test.by_text".parse()?,
             "searchField".parse()?,
+            btreemap! {"titleField".parse()? => SearchFieldBoost::new(4.)?},
             btreeset! {"filterField".parse()?},
+            Default::default(),
         );
         let index_id = IndexModel::new(&mut tx)
             .add_application_index(namespace, index)
@@ -300,6 +316,7 @@ impl Scenario {
             index_name: "test.by_text".parse()?,
             table: self.table_name.clone(),
             filters,
+            order: None,
         };
         let query = Query {
             source: QuerySource::Search(search),
@@ -942,6 +959,233 @@ async fn unrelated_sentences_are_queryable_after_flush(rt: TestRuntime) -> anyho
     Ok(())
 }
 
+#[convex_macro::test_runtime]
+async fn test_phrase_and_near_queries(rt: TestRuntime) -> anyhow::Result<()> {
+    let mut scenario = Scenario::new(rt).await?;
+    scenario
+        ._patch("a", "the best pizza in new york", "test")
+        .await?;
+    scenario
+        ._patch("b", "york has a new pizza oven", "test")
+        .await?;
+
+    // Check the memory index, and then the disk index after flushing.
+    for flush in [false, true] {
+        if flush {
+            scenario.backfill().await?;
+        }
+        let results = scenario
+            ._query_with_scores("new york", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 2);
+        let results = scenario
+            ._query_with_scores("\"new york\"", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 1);
+        let results = scenario
+            ._query_with_scores("\"york new\"", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 0);
+        let results = scenario
+            ._query_with_scores("pizza near(0) oven", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 1);
+        let results = scenario
+            ._query_with_scores("pizza near(1) new", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 2);
+        let results = scenario
+            ._query_with_scores("best near(1) york", None, None, SearchVersion::V2)
+            .await?;
+        assert_eq!(results.len(), 0);
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_text_search_range_filters_and_order(rt: TestRuntime) -> anyhow::Result<()> {
+    let mut scenario = Scenario::new(rt).await?;
+    let mut ids = vec![];
+    for (key, filter_field) in [("a", "w"), ("b", "x"), ("c", "y"), ("d", "z")] {
+        let (id, _) = scenario._patch(key, "pizza", filter_field).await?;
+        ids.push(DeveloperDocumentId::from(id));
+    }
+
+    for flush in [false, true] {
+        if flush {
+            scenario.backfill().await?;
+        }
+        let query = |filters: Vec<SearchFilterExpression>, order| TextSearch {
+            component_id: ComponentId::test_user(),
+            index_name: "test.by_text".parse().unwrap(),
+            filters: [SearchFilterExpression::Search(
+                "searchField".parse().unwrap(),
+                "pizza".to_string(),
+            )]
+            .into_iter()
+            .chain(filters)
+            .collect(),
+            order,
+            limit: None,
+            snippet_tokens: None,
+        };
+        let result_ids =
+            |results: Vec<TextSearchResult>| results.into_iter().map(|r| r.id).collect::<Vec<_>>();
+
+        let mut tx = scenario.database.begin(Identity::system()).await?;
+        let filters = vec![
+            SearchFilterExpression::Gte("filterField".parse()?, Some("x".to_string().try_into()?)),
+            SearchFilterExpression::Lt("filterField".parse()?, Some("z".to_string().try_into()?)),
+        ];
+        let order = SearchOrder {
+            field_path: "filterField".parse()?,
+            order: Order::Desc,
+        };
+        let results = text_search(
+            &mut tx,
+            query(filters, Some(order)),
+            TableFilter::ExcludePrivateSystemTables,
+        )
+        .await?;
+        assert_eq!(result_ids(results), vec![ids[2], ids[1]]);
+
+        let order = SearchOrder {
+            field_path: CREATION_TIME_FIELD_PATH.clone(),
+            order: Order::Asc,
+        };
+        let results = text_search(
+            &mut tx,
+            query(vec![], Some(order)),
+            TableFilter::ExcludePrivateSystemTables,
+        )
+        .await?;
+        assert_eq!(result_ids(results), ids);
+
+        let order = SearchOrder {
+            field_path: "searchField".parse()?,
+            order: Order::Asc,
+        };
+        let err = text_search(
+            &mut tx,
+            query(vec![], Some(order)),
+            TableFilter::ExcludePrivateSystemTables,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.is_bad_request());
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_text_search_highlights(rt: TestRuntime) -> anyhow::Result<()> {
+    let mut scenario = Scenario::new(rt).await?;
+    scenario
+        ._patch("a", "the best pizza in New York", "test")
+        .await?;
+
+    for flush in [false, true] {
+        if flush {
+            scenario.backfill().await?;
+        }
+        let mut tx = scenario.database.begin(Identity::system()).await?;
+        let query = TextSearch {
+            component_id: ComponentId::test_user(),
+            index_name: "test.by_text".parse()?,
+            filters: vec![SearchFilterExpression::Search(
+                "searchField".parse()?,
+                "new york".to_string(),
+            )],
+            order: None,
+            limit: None,
+            snippet_tokens: Some(3),
+        };
+        let results = text_search(&mut tx, query, TableFilter::ExcludePrivateSystemTables).await?;
+        assert_eq!(results.len(), 1);
+        let highlight = results[0].highlight.clone().unwrap();
+        assert_eq!(highlight.offsets, vec![(18, 21), (22, 26)]);
+        assert_eq!(highlight.snippet, "in New York");
+        assert_eq!(highlight.snippet_offset, 15);
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_extra_search_field_boost(rt: TestRuntime) -> anyhow::Result<()> {
+    let mut scenario = Scenario::new(rt).await?;
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let body_match = TestFacingModel::new(&mut tx)
+        .insert(
+            &scenario.table_name,
+            assert_obj!("searchField" => "the best pizza in town", "titleField" => "restaurants"),
+        )
+        .await?;
+    let title_match = TestFacingModel::new(&mut tx)
+        .insert(
+            &scenario.table_name,
+            assert_obj!("searchField" => "the best food in town", "titleField" => "pizza"),
+        )
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    for flush in [false, true] {
+        if flush {
+            scenario.backfill().await?;
+        }
+        let results = scenario
+            ._query_with_scores("pizza", None, None, SearchVersion::V2)
+            .await?;
+        let ids = results.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        // The title field's boost ranks its match above the body field's.
+        assert_eq!(ids, vec![title_match, body_match]);
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_phrase_queries_match_extra_search_fields(rt: TestRuntime) -> anyhow::Result<()> {
+    let mut scenario = Scenario::new(rt).await?;
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let body_match = TestFacingModel::new(&mut tx)
+        .insert(
+            &scenario.table_name,
+            assert_obj!("searchField" => "the best pizza in new york", "titleField" => "menu"),
+        )
+        .await?;
+    let title_match = TestFacingModel::new(&mut tx)
+        .insert(
+            &scenario.table_name,
+            assert_obj!("searchField" => "york has a new oven", "titleField" => "new york pizza"),
+        )
+        .await?;
+    TestFacingModel::new(&mut tx)
+        .insert(
+            &scenario.table_name,
+            assert_obj!("searchField" => "new ovens", "titleField" => "york"),
+        )
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    for flush in [false, true] {
+        if flush {
+            scenario.backfill().await?;
+        }
+        let results = scenario
+            ._query_with_scores("\"new york\"", None, None, SearchVersion::V2)
+            .await?;
+        // The title field's boost ranks its match above the body field's, and
+        // a phrase split across fields doesn't match.
+        let ids = results.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![title_match, body_match]);
+        let results = scenario
+            ._query_with_scores("york near(0) pizza", None, None, SearchVersion::V2)
+            .await?;
+        let ids = results.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![title_match]);
+    }
+    Ok(())
+}
+
 #[test]
 fn searches_with_duplicate_terms_have_same_memory_disk_score() {
     let action = TestAction::Update(TestUpdate {
@@ -1109,7 +1353,7 @@ impl VectorSearcher for BrokenSearcher {
         &self,
         _: Arc<dyn Storage>,
         _: Vec<FragmentedVectorSegmentPaths>,
-        _: usize,
+        _: QdrantSchema,
     ) -> anyhow::Result<FragmentedVectorSegment> {
         anyhow::bail!("不");
     }
@@ -330,6 +330,7 @@ impl TextFixtures {
             table: index_name.table().clone(),
             index_name,
             filters,
+            order: None,
         };
 
         let query = Query {
@@ -391,7 +392,9 @@ pub fn backfilling_text_index() -> anyhow::Result<IndexMetadata<TableName>> {
     let metadata = IndexMetadata::new_backfilling_text_index(
         index_name,
         search_field,
+        Default::default(),
         btreeset![filter_field],
+        Default::default(),
     );
     Ok(metadata)
 }
@@ -444,6 +444,9 @@ fn new_backfilling_vector_index() -> anyhow::Result<IndexMetadata<TableName>> {
         vector_field,
         (2u32).try_into()?,
         btreeset![filter_field],
+        Default::default(),
+        None,
+        Default::default(),
     );
     Ok(metadata)
 }
@@ -615,7 +618,7 @@ impl<RT: Runtime> VectorSearcher for DeleteOnCompactSearchlight<RT> {
         &self,
         search_storage: Arc<dyn Storage>,
         segments: Vec<pb::searchlight::FragmentedVectorSegmentPaths>,
-        dimension: usize,
+        schema: QdrantSchema,
     ) -> anyhow::Result<FragmentedVectorSegment> {
         let mut tx: Transaction<RT> = self.db.begin_system().await?;
         UserFacingModel::new_root_for_test(&mut tx)
@@ -631,7 +634,7 @@ impl<RT: Runtime> VectorSearcher for DeleteOnCompactSearchlight<RT> {
         .await?;
 
         self.searcher
-            .execute_vector_compaction(search_storage, segments, dimension)
+            .execute_vector_compaction(search_storage, segments, schema)
             .await
     }
 }
@@ -6,6 +6,7 @@ use std::{
     sync::Arc,
 };
 
+use anyhow::Context;
 use cmd_util::env::env_config;
 use common::{
     bootstrap_model::index::{
@@ -15,6 +16,7 @@ use common::{
             VectorIndexSnapshot,
             VectorIndexSnapshotData,
             VectorIndexState,
+            VectorSnapshotVersion,
         },
         IndexConfig,
         IndexMetadata,
@@ -32,6 +34,7 @@ use common::{
         IndexName,
     },
 };
+use errors::ErrorMetadataAnyhowExt;
 use itertools::Itertools;
 use keybroker::Identity;
 use maplit::{
@@ -66,10 +69,19 @@ use vector::{
     cosine_similarity,
     PublicVectorSearchQueryResult,
     VectorSearch,
+    VectorSearchBound,
     VectorSearchExpression,
+    VectorSearchRange,
 };
 
 use crate::{
+    query::{
+        self,
+        HybridFusion,
+        HybridSearch,
+        HybridSearchResult,
+        TableFilter,
+    },
     test_helpers::{
         vector_utils::{
             random_vector,
@@ -84,6 +96,7 @@ use crate::{
         IndexData,
         VectorFixtures,
     },
+    text_index_worker::flusher::backfill_text_indexes,
     vector_index_worker::{
         compactor::compact_vector_indexes_in_test,
         flusher::{
@@ -93,6 +106,7 @@ use crate::{
     },
     Database,
     IndexModel,
+    SystemMetadataModel,
     TableModel,
     UserFacingModel,
 };
@@ -162,6 +176,9 @@ impl<RT: Runtime> Scenario<RT> {
             INDEXED_FIELD.parse()?,
             DIMENSIONS.try_into()?,
             FILTER_FIELDS.iter().map(|f| f.parse()).try_collect()?,
+            Default::default(),
+            None,
+            Default::default(),
         );
         IndexModel::new(&mut tx)
             .add_application_index(namespace, index)
@@ -512,6 +529,27 @@ async fn test_vector_search(rt: TestRuntime) -> anyhow::Result<()> {
             .await?;
         assert_eq!(results.len(), 0);
 
+        // Check that a range only matches the numeric field.
+        let in_range = VectorSearchExpression::Range(
+            "A".parse()?,
+            VectorSearchRange {
+                lower: Some(VectorSearchBound::Inclusive(ConvexValue::Int64(1000))),
+                upper: Some(VectorSearchBound::Exclusive(ConvexValue::Float64(1017.5))),
+            },
+        );
+        let results = scenario.search(vec![0.; 4], btreeset![in_range]).await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id.internal_id(), id1.internal_id());
+
+        // Check that excluding 1017 only gets the second vector.
+        let exclude_first =
+            VectorSearchExpression::Neq("A".parse()?, Some(ConvexValue::Int64(1017)));
+        let results = scenario
+            .search(vec![0.; 4], btreeset![exclude_first])
+            .await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id.internal_id(), id2.internal_id());
+
         // Backfill and repeat once to check the disk index.
         scenario.backfill().await?;
     }
@@ -566,6 +604,199 @@ async fn test_vector_search(rt: TestRuntime) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[convex_macro::test_runtime]
+async fn test_range_filters_on_old_snapshot_version(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    scenario.seed_table_with_vector_data(2).await?;
+    scenario.backfill().await?;
+
+    // Mark the snapshot as written before numeric filter values were stored.
+    let mut tx = scenario.database.begin_system().await?;
+    let (id, mut metadata) = IndexModel::new(&mut tx)
+        .get_all_indexes()
+        .await?
+        .into_iter()
+        .find(|index| index.name.descriptor().as_str() == INDEX_DESCRIPTOR)
+        .context("Missing vector index")?
+        .into_id_and_value();
+    let IndexConfig::Vector {
+        on_disk_state: VectorIndexState::SnapshottedAt(ref mut snapshot),
+        ..
+    } = metadata.config
+    else {
+        anyhow::bail!("Index isn't snapshotted");
+    };
+    snapshot.version = VectorSnapshotVersion::V0;
+    SystemMetadataModel::new_global(&mut tx)
+        .replace(id, metadata.try_into()?)
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    // Range filters are rejected instead of silently missing the segments'
+    // documents, but other searches still work.
+    let in_range = VectorSearchExpression::Range(
+        "A".parse()?,
+        VectorSearchRange {
+            lower: Some(VectorSearchBound::Inclusive(ConvexValue::Int64(1000))),
+            upper: None,
+        },
+    );
+    let err = scenario
+        .search(vec![0.; 4], btreeset![in_range.clone()])
+        .await
+        .unwrap_err();
+    assert!(err.is_bad_request());
+    assert_eq!(err.short_msg(), "VectorIndexMissingNumericFilterValues");
+    assert_eq!(scenario.search(vec![0.; 4], btreeset![]).await?.len(), 2);
+
+    // The flusher rebuilds the index in the current format.
+    scenario.backfill().await?;
+    let mut vec_indexes = scenario.get_vector_index_configs().await?;
+    must_let!(let (_, VectorIndexState::SnapshottedAt(VectorIndexSnapshot { version, .. })) =
+        vec_indexes.remove(0));
+    assert_eq!(version, VectorSnapshotVersion::V1NumericFilterValues);
+    let results = scenario.search(vec![0.; 4], btreeset![in_range]).await?;
+    assert_eq!(results.len(), 2);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_hybrid_search_with_multiple_filters(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    let text_index_name: IndexName = "test.by_text".parse()?;
+    let mut tx = scenario.database.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .add_application_index(
+            TABLE_NAMESPACE,
+            IndexMetadata::new_backfilling_text_index(
+                text_index_name.clone(),
+                "text".parse()?,
+                Default::default(),
+                btreeset!["A".parse()?, "B".parse()?],
+                Default::default(),
+            ),
+        )
+        .await?;
+    // The documents closest to the query vector only match one of the filters.
+    for _ in 0..8 {
+        UserFacingModel::new_root_for_test(&mut tx)
+            .insert(
+                TABLE_NAME.parse()?,
+                assert_obj!(
+                    INDEXED_FIELD => vector_to_value(vec![1., 0., 0., 0.]),
+                    "text" => "hello",
+                    "A" => "a",
+                    "B" => "other",
+                ),
+            )
+            .await?;
+    }
+    let match_both = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(
+            TABLE_NAME.parse()?,
+            assert_obj!(
+                INDEXED_FIELD => vector_to_value(vec![0., 1., 0., 0.]),
+                "text" => "hello",
+                "A" => "a",
+                "B" => "b",
+            ),
+        )
+        .await?;
+    scenario.database.commit(tx).await?;
+    backfill_text_indexes(
+        rt.clone(),
+        scenario.database.clone(),
+        scenario.reader.clone(),
+        scenario.search_storage.clone(),
+        Arc::new(InProcessSearcher::new(rt.clone()).await?),
+    )
+    .await?;
+    let mut tx = scenario.database.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .enable_index_for_testing(TABLE_NAMESPACE, &text_index_name)
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    // Only weigh the vector ranking, so the document only scores if the vector
+    // search found it past the closer documents that match one filter.
+    let mut tx = scenario.database.begin_system().await?;
+    let results = query::hybrid_search(
+        &mut tx,
+        HybridSearch {
+            component_id: ComponentId::Root,
+            text_index_name,
+            search_field: "text".parse()?,
+            text_query: "hello".to_string(),
+            vector_index_name: INDEX_NAME.parse()?,
+            vector: vec![1., 0., 0., 0.],
+            limit: Some(2),
+            filters: btreemap! {
+                "A".parse()? => Some(ConvexValue::try_from("a")?),
+                "B".parse()? => Some(ConvexValue::try_from("b")?),
+            },
+            fusion: HybridFusion::Weighted {
+                text_weight: 0.,
+                vector_weight: 1.,
+            },
+        },
+        TableFilter::IncludePrivateSystemTables,
+    )
+    .await?;
+    assert_eq!(
+        results,
+        vec![HybridSearchResult {
+            id: match_both,
+            score: 1.,
+        }]
+    );
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_transaction_vector_search_reads_own_writes(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    let committed = scenario.seed_table_with_vector_data(1).await?;
+
+    let search = |limit| -> anyhow::Result<VectorSearch> {
+        Ok(VectorSearch {
+            index_name: INDEX_NAME.parse()?,
+            component_id: ComponentId::Root,
+            vector: vec![1., 0., 0., 0.],
+            limit: Some(limit),
+            expressions: btreeset![],
+        })
+    };
+
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let obj = assert_obj!(INDEXED_FIELD => vector_to_value(vec![1., 0., 0., 0.]));
+    let inserted = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(TABLE_NAME.parse()?, obj)
+        .await?;
+
+    // The uncommitted insert is the closest match to the query.
+    let results =
+        query::vector_search(&mut tx, search(1)?, TableFilter::IncludePrivateSystemTables).await?;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, inserted);
+    assert!(tx.reads.read_set().iter_vector().next().is_some());
+
+    // And a document deleted in the transaction is gone from the results.
+    UserFacingModel::new_root_for_test(&mut tx)
+        .delete(committed[0])
+        .await?;
+    let results = query::vector_search(
+        &mut tx,
+        search(10)?,
+        TableFilter::IncludePrivateSystemTables,
+    )
+    .await?;
+    assert_eq!(
+        results.into_iter().map(|r| r.id).collect_vec(),
+        vec![inserted]
+    );
+    Ok(())
+}
+
 #[convex_macro::test_runtime]
 async fn test_vector_search_compaction(rt: TestRuntime) -> anyhow::Result<()> {
     let mut scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
@@ -804,6 +1035,7 @@ async fn test_index_backfill_is_incremental(rt: TestRuntime) -> anyhow::Result<(
                 VectorIndexSnapshot {
                     data,
                     ts,
+                    ..
                 }) = on_disk_state);
             // Verify snapshot timestamp matches backfill timestamp
             assert_eq!(backfill_ts.unwrap(), ts);
@@ -188,6 +188,7 @@ mod tests {
             text_index::{
                 TextIndexSnapshot,
                 TextIndexState,
+                TextSnapshotVersion,
             },
             IndexConfig,
             IndexMetadata,
@@ -219,6 +220,7 @@ mod tests {
         },
         Database,
         IndexModel,
+        SystemMetadataModel,
         TestFacingModel,
     };
 
@@ -382,6 +384,57 @@ mod tests {
         Ok(())
     }
 
+    #[convex_macro::test_runtime]
+    async fn test_rebuild_search_index_with_old_snapshot_version(
+        rt: TestRuntime,
+    ) -> anyhow::Result<()> {
+        let fixtures = TextFixtures::new(rt.clone()).await?;
+        let IndexData {
+            index_name,
+            resolved_index_name,
+            namespace,
+            ..
+        } = fixtures
+            .insert_backfilling_text_index_with_document()
+            .await?;
+        let mut worker = fixtures.new_search_flusher();
+        worker.step().await?;
+        enable_pending_index(&fixtures.db, namespace, &index_name).await?;
+
+        // Mark the snapshot as written before filter field values were stored.
+        let (id, mut metadata) = fixtures
+            .get_index_metadata(index_name.clone())
+            .await?
+            .into_id_and_value();
+        let IndexConfig::Text {
+            on_disk_state: TextIndexState::SnapshottedAt(ref mut snapshot),
+            ..
+        } = metadata.config
+        else {
+            anyhow::bail!("Index isn't snapshotted");
+        };
+        snapshot.version = TextSnapshotVersion::V2UseStringIds;
+        let old_data = snapshot.data.clone();
+        let mut tx = fixtures.db.begin_system().await?;
+        SystemMetadataModel::new_global(&mut tx)
+            .replace(id, metadata.try_into()?)
+            .await?;
+        fixtures.db.commit(tx).await?;
+
+        // The worker rebuilds the index in the current format.
+        let (metrics, _) = worker.step().await?;
+        assert!(metrics.contains_key(&resolved_index_name));
+        let metadata = fixtures.get_index_metadata(index_name).await?.into_value();
+        must_let!(let IndexConfig::Text {
+            on_disk_state: TextIndexState::SnapshottedAt(TextIndexSnapshot { version, data, .. }),
+            ..
+        } = metadata.config);
+        assert_eq!(version, TextSnapshotVersion::V3FilterValues);
+        assert_ne!(data, old_data);
+
+        Ok(())
+    }
+
     #[convex_macro::test_runtime]
     async fn test_advance_old_snapshot(rt: TestRuntime) -> anyhow::Result<()> {
         common::testing::init_test_logging();
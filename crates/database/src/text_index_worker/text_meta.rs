@@ -121,8 +121,13 @@ impl SearchIndex for TextSearchIndex {
     type PreviousSegments = PreviousTextSegments;
     type Schema = TantivySearchIndexSchema;
     type Segment = FragmentedTextSegment;
+    type SnapshotVersion = TextSnapshotVersion;
     type Statistics = TextStatistics;
 
+    fn current_snapshot_version() -> Self::SnapshotVersion {
+        TextSnapshotVersion::V3FilterValues
+    }
+
     fn get_config(config: IndexConfig) -> Option<SearchIndexConfig<Self>> {
         let IndexConfig::Text {
             on_disk_state,
@@ -164,7 +169,7 @@ impl SearchIndex for TextSearchIndex {
     fn is_version_current(snapshot: &SearchSnapshot<Self>) -> bool {
         // TODO(sam): This doesn't match the current persistence version based check,
         // but it's closer to what vector search does.
-        snapshot.data.is_version_current()
+        snapshot.data.is_version_current() && snapshot.version == Self::current_snapshot_version()
     }
 
     fn new_schema(config: &Self::DeveloperConfig) -> Self::Schema {
@@ -476,6 +481,7 @@ impl From<TextIndexSnapshot> for SearchSnapshot<TextSearchIndex> {
         Self {
             ts: snapshot.ts,
             data: snapshot.data.into(),
+            version: snapshot.version,
         }
     }
 }
@@ -485,7 +491,7 @@ impl From<SearchSnapshot<TextSearchIndex>> for TextIndexSnapshot {
         Self {
             ts: value.ts,
             data: value.data.into(),
-            version: TextSnapshotVersion::V2UseStringIds,
+            version: value.version,
         }
     }
 }
@@ -41,6 +41,7 @@ use common::{
     },
     interval::Interval,
     knobs::{
+        REFERENCE_ACTIONS_MAX_DOCUMENTS,
         TEXT_INDEX_SIZE_HARD_LIMIT,
         VECTOR_INDEX_SIZE_HARD_LIMIT,
     },
@@ -52,12 +53,16 @@ use common::{
         SearchVersion,
     },
     runtime::Runtime,
-    schemas::DatabaseSchema,
+    schemas::{
+        DatabaseSchema,
+        OnDeleteAction,
+    },
     sync::split_rw_lock::Reader,
     types::{
         GenericIndexName,
         IndexId,
         IndexName,
+        MaybeValue,
         PersistenceVersion,
         RepeatableTimestamp,
         StableIndexName,
@@ -69,6 +74,8 @@ use common::{
     value::{
         id_v6::DeveloperDocumentId,
         ConvexObject,
+        ConvexValue,
+        FieldName,
         ResolvedDocumentId,
         Size,
         TableMapping,
@@ -96,6 +103,11 @@ use value::{
     TableNumber,
     TabletId,
 };
+use vector::{
+    InternalVectorSearch,
+    VectorSearch,
+    VectorSearchQueryResult,
+};
 
 use crate::{
     bootstrap_model::{
@@ -567,6 +579,7 @@ impl<RT: Runtime> Transaction<RT> {
         SchemaModel::new(self, namespace)
             .enforce(&new_document)
             .await?;
+        self.check_unique_indexes(&new_document).await?;
 
         self.apply_validated_write(id, Some((old_document, old_ts)), Some(new_document.clone()))?;
         Ok(new_document)
@@ -605,6 +618,7 @@ impl<RT: Runtime> Transaction<RT> {
         SchemaModel::new(self, namespace)
             .enforce(&new_document)
             .await?;
+        self.check_unique_indexes(&new_document).await?;
 
         self.apply_validated_write(
             new_document.id(),
@@ -618,6 +632,19 @@ impl<RT: Runtime> Transaction<RT> {
     pub async fn delete_inner(
         &mut self,
         id: ResolvedDocumentId,
+    ) -> anyhow::Result<ResolvedDocument> {
+        let mut num_reference_actions = 0;
+        self.delete_with_reference_actions(id, &mut num_reference_actions)
+            .await
+    }
+
+    /// Deletes `id` and applies the `onDelete` actions of documents that
+    /// reference it, counting them towards `REFERENCE_ACTIONS_MAX_DOCUMENTS`
+    /// across the whole cascade.
+    async fn delete_with_reference_actions(
+        &mut self,
+        id: ResolvedDocumentId,
+        num_reference_actions: &mut usize,
     ) -> anyhow::Result<ResolvedDocument> {
         task::consume_budget().await;
 
@@ -630,7 +657,37 @@ impl<RT: Runtime> Transaction<RT> {
                     format!("Delete on nonexistent document ID {id}"),
                 ))?;
 
+        let namespace = self.table_mapping().tablet_namespace(id.tablet_id)?;
+        let reference_actions = SchemaModel::new(self, namespace)
+            .reference_actions_for_deletion(
+                &document,
+                REFERENCE_ACTIONS_MAX_DOCUMENTS.saturating_sub(*num_reference_actions),
+            )
+            .await?;
+        *num_reference_actions += reference_actions.len();
         self.apply_validated_write(document.id(), Some((document.clone(), ts)), None)?;
+        for (referencing_id, field, on_delete) in reference_actions {
+            // An earlier cascade may have already removed this document.
+            if self.get(referencing_id).await?.is_none() {
+                continue;
+            }
+            match on_delete {
+                OnDeleteAction::Cascade => {
+                    Box::pin(
+                        self.delete_with_reference_actions(referencing_id, num_reference_actions),
+                    )
+                    .await?;
+                },
+                OnDeleteAction::SetNull => {
+                    let patch: BTreeMap<FieldName, MaybeValue> =
+                        btreemap! { field.into() => MaybeValue(Some(ConvexValue::Null)) };
+                    self.patch_inner(referencing_id, patch.into()).await?;
+                },
+                OnDeleteAction::Restrict => {
+                    anyhow::bail!("Restricted references should fail before the delete")
+                },
+            }
+        }
         Ok(document)
     }
 
@@ -1034,10 +1091,21 @@ impl<RT: Runtime> Transaction<RT> {
             .table_mapping()
             .tablet_namespace(document_id.tablet_id)?;
         SchemaModel::new(self, namespace).enforce(&document).await?;
+        self.check_unique_indexes(&document).await?;
         self.apply_validated_write(document_id, None, Some(document))?;
         Ok(document_id)
     }
 
+    async fn check_unique_indexes(&mut self, document: &ResolvedDocument) -> anyhow::Result<()> {
+        self.take_table_mapping_dep();
+        let tablet_to_name = self.metadata.table_mapping().tablet_to_name();
+        self.index
+            .check_unique_indexes(&mut self.reads, document, |index_name| {
+                index_name.clone().map_table(&tablet_to_name)
+            })
+            .await
+    }
+
     pub async fn search(
         &mut self,
         stable_index_name: &StableIndexName,
@@ -1053,6 +1121,26 @@ impl<RT: Runtime> Transaction<RT> {
             .await
     }
 
+    pub async fn vector_search(
+        &mut self,
+        stable_index_name: &StableIndexName,
+        query: VectorSearch,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let Some(tablet_index_name) = stable_index_name.tablet_index_name() else {
+            return Ok(vec![]);
+        };
+        let query = InternalVectorSearch {
+            index_name: tablet_index_name.clone(),
+            limit: query.limit,
+            vector: query.vector,
+            expressions: query.expressions.into_iter().collect(),
+            original_table_name: query.index_name.table().clone(),
+        };
+        self.index
+            .vector_search(&mut self.reads, query, tablet_index_name.clone())
+            .await
+    }
+
     // TODO(lee) Make this private.
     // We ideally want the transaction to call this internally so caller doesn't
     // have to call this. However, this is currently hard since the query layer
@@ -44,6 +44,7 @@ use common::{
         WriteTimestamp,
     },
 };
+use errors::ErrorMetadata;
 use imbl::OrdMap;
 use indexing::{
     backend_in_memory_indexes::{
@@ -69,9 +70,16 @@ use search::{
 use storage::Storage;
 use tokio::task;
 use value::{
+    values_to_bytes,
     DeveloperDocumentId,
     FieldPath,
 };
+use vector::{
+    InternalVectorSearch,
+    VectorIndexManager,
+    VectorQueryReads,
+    VectorSearchQueryResult,
+};
 
 use crate::{
     preloaded::PreloadedIndexRange,
@@ -104,6 +112,10 @@ pub struct TransactionIndex {
     // on top of the transaction base snapshot.
     text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
     text_index_updates: OrdMap<IndexId, Vec<DocumentUpdate>>,
+
+    // Vector indexes work the same way as text search indexes.
+    vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
+    vector_index_updates: OrdMap<IndexId, Vec<DocumentUpdate>>,
 }
 
 impl PendingWrites for TransactionIndex {}
@@ -113,6 +125,7 @@ impl TransactionIndex {
         index_registry: IndexRegistry,
         database_index_snapshot: DatabaseIndexSnapshot,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     ) -> Self {
         Self {
             index_registry,
@@ -121,6 +134,8 @@ impl TransactionIndex {
             database_index_updates: OrdMap::new(),
             text_index_snapshot,
             text_index_updates: OrdMap::new(),
+            vector_index_snapshot,
+            vector_index_updates: OrdMap::new(),
         }
     }
 
@@ -304,6 +319,32 @@ impl TransactionIndex {
         Ok(results.revisions_with_keys)
     }
 
+    #[fastrace::trace]
+    pub async fn vector_search(
+        &mut self,
+        reads: &mut TransactionReadSet,
+        query: InternalVectorSearch,
+        index_name: TabletIndexName,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        // Like text search, we skip recording a read of the index metadata and
+        // don't allow searching after modifying the index registry.
+        anyhow::ensure!(
+            !self.index_registry_updated,
+            "Vector search and index registry update not allowed in the same transaction"
+        );
+        let index = self
+            .index_registry
+            .require_enabled(&index_name, &query.printable_index_name()?)?;
+        let empty = vec![];
+        let pending_updates = self.vector_index_updates.get(&index.id).unwrap_or(&empty);
+        let (results, vector_reads) = self
+            .vector_index_snapshot
+            .vector_search(&index, query, pending_updates)
+            .await?;
+        reads.record_vector(index_name, vector_reads);
+        Ok(results)
+    }
+
     /// Fetch a batch of index ranges. This method does not update the read set,
     /// since we might be fetching more documents than the caller actually needs
     /// due to filtering.
@@ -330,7 +371,7 @@ impl TransactionIndex {
                     match self.require_enabled(reads, index_name, printable_index_name) {
                         Ok(index) => match index.metadata().config.clone() {
                             IndexConfig::Database {
-                                developer_config: DeveloperDatabaseIndexConfig { fields },
+                                developer_config: DeveloperDatabaseIndexConfig { fields, .. },
                                 ..
                             } => fields,
                             _ => Err(index_not_a_database_index_error(printable_index_name))?,
@@ -485,6 +526,67 @@ impl TransactionIndex {
         ))
     }
 
+    /// Check that writing `document` wouldn't give it the same key as another
+    /// document in any of its table's unique indexes. The probed ranges are
+    /// recorded in the read set, so a concurrent transaction inserting a
+    /// conflicting document causes an OCC conflict at commit time.
+    pub async fn check_unique_indexes(
+        &mut self,
+        reads: &mut TransactionReadSet,
+        document: &ResolvedDocument,
+        printable_index_name: impl Fn(&TabletIndexName) -> anyhow::Result<IndexName>,
+    ) -> anyhow::Result<()> {
+        let tablet_id = document.id().tablet_id;
+        let unique_indexes: Vec<_> = self
+            .index_registry
+            .unique_indexes_by_table(tablet_id)
+            .cloned()
+            .collect();
+        let persistence_version = self.index_registry.persistence_version();
+        for index in unique_indexes {
+            let IndexConfig::Database {
+                developer_config: DeveloperDatabaseIndexConfig { ref fields, .. },
+                ..
+            } = index.metadata().config
+            else {
+                continue;
+            };
+            let index_key = document.index_key(&fields[..], persistence_version);
+            if index_key.indexed_values().iter().any(|v| v.is_none()) {
+                continue;
+            }
+            let index_name = index.name();
+            let interval = Interval::prefix(values_to_bytes(index_key.indexed_values()).into());
+            let (documents, _) = self
+                .range_no_deps(&btreemap! { 0 => RangeRequest {
+                    index_name: index_name.clone(),
+                    printable_index_name: printable_index_name(&index_name)?,
+                    interval: interval.clone(),
+                    order: Order::Asc,
+                    max_size: 2,
+                }})
+                .await
+                .remove(&0)
+                .context("batch_key missing")??;
+            reads.record_indexed_directly(index_name.clone(), fields.clone(), interval)?;
+            for (_, existing, _) in documents {
+                let existing_id = existing.unpack().developer_id();
+                if existing_id != document.developer_id() {
+                    anyhow::bail!(ErrorMetadata::conflict(
+                        "UniqueConstraintViolation",
+                        format!(
+                            "Document {} violates the uniqueness constraint of index {}: document \
+                             {existing_id} already has the same values for {fields}",
+                            document.developer_id(),
+                            printable_index_name(&index_name)?,
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     // TODO: Add precise error types to facilitate detecting which indexing errors
     // are the developer's fault or not.
     pub fn begin_update(
@@ -558,11 +660,19 @@ impl TransactionIndex {
                         new_document: new_document.clone(),
                     });
             }
+            // And to all affected vector indexes.
+            for index in self.index_registry.vector_indexes_by_table(id.tablet_id) {
+                self.vector_index_updates
+                    .entry(index.id)
+                    .or_default()
+                    .push(DocumentUpdate {
+                        id,
+                        old_document: old_document.clone(),
+                        new_document: new_document.clone(),
+                    });
+            }
         }
 
-        // Note that we do not update the vector index and we always read at the
-        // base snapshot.
-
         updates
     }
 
@@ -810,6 +920,88 @@ impl TransactionTextSnapshot for TextIndexManagerSnapshot {
     }
 }
 
+#[async_trait]
+pub trait TransactionVectorSnapshot: Send + Sync + 'static {
+    // Search at the given snapshot after applying the given writes, so that
+    // mutations read their own writes.
+    async fn vector_search(
+        &self,
+        index: &Index,
+        query: InternalVectorSearch,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<(Vec<VectorSearchQueryResult>, VectorQueryReads)>;
+}
+
+#[derive(Clone)]
+pub struct VectorIndexManagerSnapshot {
+    index_registry: IndexRegistry,
+    vector_indexes: VectorIndexManager,
+
+    searcher: Arc<dyn Searcher>,
+    search_storage: Arc<OnceLock<Arc<dyn Storage>>>,
+}
+
+impl VectorIndexManagerSnapshot {
+    pub fn new(
+        index_registry: IndexRegistry,
+        vector_indexes: VectorIndexManager,
+        searcher: Arc<dyn Searcher>,
+        search_storage: Arc<OnceLock<Arc<dyn Storage>>>,
+    ) -> Self {
+        Self {
+            index_registry,
+            vector_indexes,
+            searcher,
+            search_storage,
+        }
+    }
+
+    // Applies the writes to the base snapshot and returns the new snapshot.
+    fn snapshot_with_updates(
+        &self,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<VectorIndexManager> {
+        let mut vector_indexes = self.vector_indexes.clone();
+        for DocumentUpdate {
+            id: _,
+            old_document,
+            new_document,
+        } in pending_updates
+        {
+            vector_indexes.update(
+                &self.index_registry,
+                old_document.as_ref(),
+                new_document.as_ref(),
+                WriteTimestamp::Pending,
+            )?;
+        }
+        Ok(vector_indexes)
+    }
+
+    fn search_storage(&self) -> Arc<dyn Storage> {
+        self.search_storage
+            .get()
+            .expect("search_storage not initialized")
+            .clone()
+    }
+}
+
+#[async_trait]
+impl TransactionVectorSnapshot for VectorIndexManagerSnapshot {
+    async fn vector_search(
+        &self,
+        index: &Index,
+        query: InternalVectorSearch,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<(Vec<VectorSearchQueryResult>, VectorQueryReads)> {
+        let vector_indexes =
+            runtime::block_in_place(|| self.snapshot_with_updates(pending_updates))?;
+        vector_indexes
+            .vector_search_with_reads(index, query, self.searcher.clone(), self.search_storage())
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -880,8 +1072,12 @@ mod tests {
         Storage,
     };
     use value::assert_obj;
+    use vector::VectorIndexManager;
 
-    use super::TextIndexManagerSnapshot;
+    use super::{
+        TextIndexManagerSnapshot,
+        VectorIndexManagerSnapshot,
+    };
     use crate::{
         query::IndexRangeResponse,
         reads::TransactionReadSet,
@@ -976,7 +1172,8 @@ mod tests {
 
         let mut reads = TransactionReadSet::new();
         let searcher = Arc::new(InProcessSearcher::new(rt.clone()).await?);
-        let search_storage = Arc::new(LocalDirStorage::new(rt)?);
+        let search_storage: Arc<OnceLock<Arc<dyn Storage>>> =
+            Arc::new(OnceLock::from(Arc::new(LocalDirStorage::new(rt)?) as _));
         let mut index = TransactionIndex::new(
             index_registry.clone(),
             DatabaseIndexSnapshot::new(
@@ -989,7 +1186,13 @@ mod tests {
                 index_registry.clone(),
                 search,
                 searcher.clone(),
-                Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
+                search_storage.clone(),
+            )),
+            Arc::new(VectorIndexManagerSnapshot::new(
+                index_registry.clone(),
+                VectorIndexManager::bootstrap_index_metadata(&index_registry)?,
+                searcher.clone(),
+                search_storage,
             )),
         );
 
@@ -1083,7 +1286,8 @@ mod tests {
 
         let mut reads = TransactionReadSet::new();
         let searcher = Arc::new(InProcessSearcher::new(rt.clone()).await?);
-        let search_storage = Arc::new(LocalDirStorage::new(rt)?);
+        let search_storage: Arc<OnceLock<Arc<dyn Storage>>> =
+            Arc::new(OnceLock::from(Arc::new(LocalDirStorage::new(rt)?) as _));
         let mut index = TransactionIndex::new(
             index_registry.clone(),
             DatabaseIndexSnapshot::new(
@@ -1096,7 +1300,13 @@ mod tests {
                 index_registry.clone(),
                 search,
                 searcher.clone(),
-                Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
+                search_storage.clone(),
+            )),
+            Arc::new(VectorIndexManagerSnapshot::new(
+                index_registry.clone(),
+                VectorIndexManager::bootstrap_index_metadata(&index_registry)?,
+                searcher.clone(),
+                search_storage,
             )),
         );
 
@@ -1299,7 +1509,10 @@ mod tests {
 
         let mut reads = TransactionReadSet::new();
         let searcher = Arc::new(InProcessSearcher::new(rt.clone()).await?);
-        let search_storage = Arc::new(LocalDirStorage::new(rt.clone())?);
+        let search_storage: Arc<OnceLock<Arc<dyn Storage>>> = Arc::new(OnceLock::from(Arc::new(
+            LocalDirStorage::new(rt.clone())?,
+        )
+            as _));
         let mut index = TransactionIndex::new(
             index_registry.clone(),
             DatabaseIndexSnapshot::new(
@@ -1312,7 +1525,13 @@ mod tests {
                 index_registry.clone(),
                 search,
                 searcher.clone(),
-                Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
+                search_storage.clone(),
+            )),
+            Arc::new(VectorIndexManagerSnapshot::new(
+                index_registry.clone(),
+                VectorIndexManager::bootstrap_index_metadata(&index_registry)?,
+                searcher.clone(),
+                search_storage,
             )),
         );
         let david = ResolvedDocument::new(
@@ -14,6 +14,7 @@ use common::{
             VectorIndexSnapshot,
             VectorIndexSnapshotData,
             VectorIndexState,
+            VectorSnapshotVersion,
         },
         IndexConfig,
         TabletIndexMetadata,
@@ -111,7 +112,7 @@ impl SegmentType<VectorSearchIndex> for FragmentedVectorSegment {
         &self,
         config: &<VectorSearchIndex as SearchIndex>::DeveloperConfig,
     ) -> anyhow::Result<u64> {
-        self.total_size_bytes(config.dimensions)
+        self.total_size_bytes(config)
     }
 }
 
@@ -139,8 +140,13 @@ impl SearchIndex for VectorSearchIndex {
     type PreviousSegments = PreviousVectorSegments;
     type Schema = QdrantSchema;
     type Segment = FragmentedVectorSegment;
+    type SnapshotVersion = VectorSnapshotVersion;
     type Statistics = VectorStatistics;
 
+    fn current_snapshot_version() -> Self::SnapshotVersion {
+        VectorSnapshotVersion::V1NumericFilterValues
+    }
+
     fn get_config(config: IndexConfig) -> Option<SearchIndexConfig<Self>> {
         let IndexConfig::Vector {
             on_disk_state,
@@ -163,7 +169,7 @@ impl SearchIndex for VectorSearchIndex {
     }
 
     fn is_version_current(snapshot: &SearchSnapshot<Self>) -> bool {
-        snapshot.data.is_version_current()
+        snapshot.data.is_version_current() && snapshot.version == Self::current_snapshot_version()
     }
 
     fn new_schema(config: &Self::DeveloperConfig) -> Self::Schema {
@@ -274,7 +280,7 @@ impl SearchIndex for VectorSearchIndex {
             .map(|segment| segment.to_paths_proto())
             .collect::<anyhow::Result<Vec<_>>>()?;
         searcher
-            .execute_vector_compaction(search_storage, protos, config.dimensions.into())
+            .execute_vector_compaction(search_storage, protos, QdrantSchema::new(config))
             .await
     }
 
@@ -345,6 +351,7 @@ impl From<VectorIndexSnapshot> for SearchSnapshot<VectorSearchIndex> {
         Self {
             ts: snapshot.ts,
             data: SnapshotData::from(snapshot.data),
+            version: snapshot.version,
         }
     }
 }
@@ -357,6 +364,7 @@ impl TryFrom<SearchSnapshot<VectorSearchIndex>> for VectorIndexSnapshot {
         Ok(VectorIndexSnapshot {
             data: value.data.try_into()?,
             ts: value.ts,
+            version: value.version,
         })
     }
 }
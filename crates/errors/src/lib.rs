@@ -1,7 +1,10 @@
 #![feature(type_alias_impl_trait)]
 #![feature(let_chains)]
 #![feature(impl_trait_in_assoc_type)]
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    time::Duration,
+};
 
 use ::metrics::StaticMetricLabel;
 use http::StatusCode;
@@ -53,7 +56,10 @@ pub enum ErrorCode {
     Forbidden,
     NotFound,
     ClientDisconnect,
-    RateLimited,
+    RateLimited {
+        /// How long the caller should wait before retrying, if known.
+        retry_after: Option<Duration>,
+    },
 
     Overloaded,
     RejectedBeforeExecution,
@@ -228,7 +234,24 @@ impl ErrorMetadata {
         msg: impl Into<Cow<'static, str>>,
     ) -> Self {
         Self {
-            code: ErrorCode::RateLimited,
+            code: ErrorCode::RateLimited { retry_after: None },
+            short_msg: short_msg.into(),
+            msg: msg.into(),
+            source: None,
+        }
+    }
+
+    /// Like [`ErrorMetadata::rate_limited`], but also tells the caller how
+    /// long to wait before the request can succeed.
+    pub fn rate_limited_with_retry_after(
+        short_msg: impl Into<Cow<'static, str>>,
+        msg: impl Into<Cow<'static, str>>,
+        retry_after: Duration,
+    ) -> Self {
+        Self {
+            code: ErrorCode::RateLimited {
+                retry_after: Some(retry_after),
+            },
             short_msg: short_msg.into(),
             msg: msg.into(),
             source: None,
@@ -428,10 +451,25 @@ impl ErrorMetadata {
         self.code == ErrorCode::NotFound
     }
 
+    pub fn is_conflict(&self) -> bool {
+        self.code == ErrorCode::Conflict
+    }
+
     pub fn is_overloaded(&self) -> bool {
         self.code == ErrorCode::Overloaded
     }
 
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.code, ErrorCode::RateLimited { .. })
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.code {
+            ErrorCode::RateLimited { retry_after } => retry_after,
+            _ => None,
+        }
+    }
+
     pub fn is_operational_internal_server_error(&self) -> bool {
         self.code == ErrorCode::OperationalInternalServerError
     }
@@ -466,7 +504,7 @@ impl ErrorMetadata {
             ErrorCode::OperationalInternalServerError
             | ErrorCode::ClientDisconnect
             | ErrorCode::NotFound
-            | ErrorCode::RateLimited
+            | ErrorCode::RateLimited { .. }
             | ErrorCode::OCC { .. }
             | ErrorCode::OutOfRetention
             | ErrorCode::Overloaded
@@ -507,7 +545,7 @@ impl ErrorMetadata {
 
             // Sampling for OCC/Overloaded/RateLimited, since we only really care about the
             // details if they happen at high volume.
-            ErrorCode::RateLimited => Some((sentry::Level::Info, Some(0.001))),
+            ErrorCode::RateLimited { .. } => Some((sentry::Level::Info, Some(0.001))),
             ErrorCode::OCC {
                 is_system: false, ..
             } => Some((sentry::Level::Warning, Some(0.001))),
@@ -532,7 +570,7 @@ impl ErrorMetadata {
             | ErrorCode::Forbidden
             | ErrorCode::ClientDisconnect
             | ErrorCode::MisdirectedRequest
-            | ErrorCode::RateLimited => None,
+            | ErrorCode::RateLimited { .. } => None,
             ErrorCode::NotFound => Some("not_found"),
             ErrorCode::OCC { .. } => Some("occ"),
             ErrorCode::OutOfRetention => Some("out_of_retention"),
@@ -552,7 +590,7 @@ impl ErrorMetadata {
             ErrorCode::BadRequest => Some(&crate::metrics::BAD_REQUEST_ERROR_TOTAL),
             ErrorCode::Conflict => None,
             ErrorCode::ClientDisconnect => Some(&crate::metrics::CLIENT_DISCONNECT_ERROR_TOTAL),
-            ErrorCode::RateLimited => Some(&crate::metrics::RATE_LIMITED_ERROR_TOTAL),
+            ErrorCode::RateLimited { .. } => Some(&crate::metrics::RATE_LIMITED_ERROR_TOTAL),
             ErrorCode::Unauthenticated | ErrorCode::AuthUpdateFailed => {
                 Some(&crate::metrics::SYNC_AUTH_ERROR_TOTAL)
             },
@@ -577,7 +615,7 @@ impl ErrorMetadata {
             ErrorCode::OCC { .. }
             | ErrorCode::OutOfRetention
             | ErrorCode::Overloaded
-            | ErrorCode::RateLimited
+            | ErrorCode::RateLimited { .. }
             | ErrorCode::RejectedBeforeExecution
             | ErrorCode::MisdirectedRequest => Some(CloseCode::Again),
             ErrorCode::OperationalInternalServerError => Some(CloseCode::Error),
@@ -612,7 +650,7 @@ impl ErrorCode {
             ErrorCode::Unauthenticated | ErrorCode::AuthUpdateFailed => StatusCode::UNAUTHORIZED,
             ErrorCode::Forbidden => StatusCode::FORBIDDEN,
             ErrorCode::NotFound => StatusCode::NOT_FOUND,
-            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
             ErrorCode::OperationalInternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorCode::OCC { .. }
             | ErrorCode::OutOfRetention
@@ -633,9 +671,9 @@ impl ErrorCode {
             ErrorCode::Forbidden => tonic::Code::FailedPrecondition,
             ErrorCode::NotFound => tonic::Code::NotFound,
             ErrorCode::ClientDisconnect => tonic::Code::Aborted,
-            ErrorCode::Overloaded | ErrorCode::RejectedBeforeExecution | ErrorCode::RateLimited => {
-                tonic::Code::ResourceExhausted
-            },
+            ErrorCode::Overloaded
+            | ErrorCode::RejectedBeforeExecution
+            | ErrorCode::RateLimited { .. } => tonic::Code::ResourceExhausted,
             ErrorCode::OCC { .. } => tonic::Code::ResourceExhausted,
             ErrorCode::PaginationLimit => tonic::Code::InvalidArgument,
             ErrorCode::OutOfRetention => tonic::Code::OutOfRange,
@@ -646,10 +684,11 @@ impl ErrorCode {
 
     pub fn from_http_status_code(code: StatusCode) -> Option<Self> {
         match code {
+            StatusCode::CONFLICT => Some(ErrorCode::Conflict),
             StatusCode::UNAUTHORIZED => Some(ErrorCode::Unauthenticated),
             StatusCode::FORBIDDEN => Some(ErrorCode::Forbidden),
             StatusCode::NOT_FOUND => Some(ErrorCode::NotFound),
-            StatusCode::TOO_MANY_REQUESTS => Some(ErrorCode::RateLimited),
+            StatusCode::TOO_MANY_REQUESTS => Some(ErrorCode::RateLimited { retry_after: None }),
             StatusCode::MISDIRECTED_REQUEST => Some(ErrorCode::MisdirectedRequest),
             // Tries to categorize in one of the above more specific 4xx codes first,
             // otherwise categorizes as a general 4xx via BadRequest
@@ -669,7 +708,10 @@ pub trait ErrorMetadataAnyhowExt {
     fn is_out_of_retention(&self) -> bool;
     fn is_bad_request(&self) -> bool;
     fn is_not_found(&self) -> bool;
+    fn is_conflict(&self) -> bool;
     fn is_overloaded(&self) -> bool;
+    fn is_rate_limited(&self) -> bool;
+    fn retry_after(&self) -> Option<Duration>;
     fn is_operational_internal_server_error(&self) -> bool;
     fn is_rejected_before_execution(&self) -> bool;
     fn is_forbidden(&self) -> bool;
@@ -767,6 +809,14 @@ impl ErrorMetadataAnyhowExt for anyhow::Error {
         false
     }
 
+    /// Returns true if error is tagged as Conflict
+    fn is_conflict(&self) -> bool {
+        if let Some(e) = self.downcast_ref::<ErrorMetadata>() {
+            return e.is_conflict();
+        }
+        false
+    }
+
     /// Returns true if error is tagged as Overloaded
     fn is_overloaded(&self) -> bool {
         if let Some(e) = self.downcast_ref::<ErrorMetadata>() {
@@ -775,6 +825,23 @@ impl ErrorMetadataAnyhowExt for anyhow::Error {
         false
     }
 
+    /// Returns true if error is tagged as RateLimited
+    fn is_rate_limited(&self) -> bool {
+        if let Some(e) = self.downcast_ref::<ErrorMetadata>() {
+            return e.is_rate_limited();
+        }
+        false
+    }
+
+    /// Returns how long to wait before retrying if the error is tagged as
+    /// RateLimited and carries a retry-after hint
+    fn retry_after(&self) -> Option<Duration> {
+        if let Some(e) = self.downcast_ref::<ErrorMetadata>() {
+            return e.retry_after();
+        }
+        None
+    }
+
     /// Returns true if error is tagged as Overloaded
     fn is_operational_internal_server_error(&self) -> bool {
         if let Some(e) = self.downcast_ref::<ErrorMetadata>() {
@@ -950,6 +1017,8 @@ const CLIENT_DISCONNECTED: &str = "ClientDisconnected";
 
 #[cfg(any(test, feature = "testing"))]
 mod proptest {
+    use std::time::Duration;
+
     use proptest::prelude::*;
 
     use super::{
@@ -988,7 +1057,20 @@ mod proptest {
                 ErrorCode::Unauthenticated => ErrorMetadata::unauthenticated("un", "auth"),
                 ErrorCode::AuthUpdateFailed => ErrorMetadata::auth_update_failed("un", "auth"),
                 ErrorCode::Forbidden => ErrorMetadata::forbidden("for", "bidden"),
-                ErrorCode::RateLimited => ErrorMetadata::rate_limited("too", "many requests"),
+                ErrorCode::RateLimited { retry_after: None } => {
+                    ErrorMetadata::rate_limited("too", "many requests")
+                },
+                // The proto encoding only fits `i64` seconds.
+                ErrorCode::RateLimited {
+                    retry_after: Some(retry_after),
+                } => ErrorMetadata::rate_limited_with_retry_after(
+                    "too",
+                    "many requests",
+                    Duration::new(
+                        retry_after.as_secs() % i64::MAX as u64,
+                        retry_after.subsec_nanos(),
+                    ),
+                ),
                 ErrorCode::Overloaded => ErrorMetadata::overloaded("overloaded", "error"),
                 ErrorCode::RejectedBeforeExecution => {
                     ErrorMetadata::rejected_before_execution("rejected_before_execution", "error")
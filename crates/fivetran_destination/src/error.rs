@@ -347,6 +347,7 @@ mod tests {
             ]
             .try_into()
             .unwrap(),
+            unique: false,
         };
 
         assert_eq!(
@@ -364,13 +365,15 @@ mod tests {
                     index_descriptor: IndexDescriptor::new("by_name").unwrap(),
                     fields: vec![
                         "name".parse().unwrap()
-                    ].try_into().unwrap()
+                    ].try_into().unwrap(),
+                    unique: false,
                 },
                 IndexDescriptor::new("by_email").unwrap() => IndexSchema {
                     index_descriptor: IndexDescriptor::new("by_email").unwrap(),
                     fields: vec![
                         "email".parse().unwrap()
-                    ].try_into().unwrap()
+                    ].try_into().unwrap(),
+                    unique: false,
                 }
             },
             document_type: Some(DocumentSchema::Union(vec![object_validator!(
@@ -379,6 +382,7 @@ mod tests {
             )])),
             search_indexes: Default::default(),
             vector_indexes: Default::default(),
+            references: Default::default(),
         };
 
         assert_eq!(
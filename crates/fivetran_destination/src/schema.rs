@@ -209,6 +209,7 @@ impl FivetranTableSchema {
             indexes,
             search_indexes: Default::default(),
             vector_indexes: Default::default(),
+            references: Default::default(),
         })
     }
 
@@ -249,6 +250,7 @@ impl FivetranTableSchema {
         Ok(IndexSchema {
             index_descriptor: FIVETRAN_PRIMARY_KEY_INDEX_DESCRIPTOR.clone(),
             fields,
+            unique: false,
         })
     }
 
@@ -260,6 +262,7 @@ impl FivetranTableSchema {
             } else {
                 FIVETRAN_SYNC_INDEX_WITHOUT_SOFT_DELETE_FIELDS.clone()
             },
+            unique: false,
         }
     }
 
@@ -663,6 +666,7 @@ impl FivetranTableSchema {
             indexes,
             search_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
+            references: Default::default(),
             document_type: Some(document_schema),
         })
     }
@@ -937,6 +941,7 @@ fn recognize_fivetran_type(validator: &Validator) -> anyhow::Result<FivetranData
         Validator::String => Ok(FivetranDataType::String),
         Validator::Bytes => Ok(FivetranDataType::Binary),
         Validator::Object(_) | Validator::Array(_) => Ok(FivetranDataType::Json),
+        Validator::Constrained(validator, _) => recognize_fivetran_type(validator),
 
         // Allow nullable types
         Validator::Union(validators) => match &validators[..] {
@@ -1075,6 +1080,7 @@ mod tests {
             table_name: "table_name".parse().unwrap(),
             search_indexes: Default::default(),
             vector_indexes: Default::default(),
+            references: Default::default(),
             document_type: Some(DocumentSchema::Union(vec![ObjectValidator(
                 fields
                     .into_iter()
@@ -1099,6 +1105,7 @@ mod tests {
                     IndexSchema {
                         index_descriptor,
                         fields: IndexedFields::try_from(index_fields).unwrap(),
+                        unique: false,
                     },
                 )
             })
@@ -1884,7 +1891,8 @@ mod tests {
                             "fivetran.deleted".parse()?,
                             "fivetran.synced".parse()?,
                             "_creationTime".parse()?,
-                        ].try_into()?
+                        ].try_into()?,
+                        unique: false,
                     },
                     FIVETRAN_PRIMARY_KEY_INDEX_DESCRIPTOR.clone() => IndexSchema {
                         index_descriptor: FIVETRAN_PRIMARY_KEY_INDEX_DESCRIPTOR.clone(),
@@ -1893,7 +1901,8 @@ mod tests {
                             "fivetran.id".parse()?,
                             "fivetran.columns.key".parse()?,
                             "slug".parse()?,
-                        ].try_into()?
+                        ].try_into()?,
+                        unique: false,
                     }
                 },
                 document_type: Some(DocumentSchema::Union(vec![object_validator!(
@@ -1921,6 +1930,7 @@ mod tests {
                 )])),
                 search_indexes: Default::default(),
                 vector_indexes: Default::default(),
+                references: Default::default(),
             },
         );
         Ok(())
@@ -52,6 +52,7 @@ use database::{
     TransactionIndex,
     TransactionReadSet,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
     COMPONENTS_TABLE,
     SCHEMAS_TABLE,
 };
@@ -104,6 +105,7 @@ fn make_transaction<RT: Runtime>(
     table_count_snapshot: Arc<dyn TableCountSnapshot>,
     database_index_snapshot: DatabaseIndexSnapshot,
     text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+    vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     retention_validator: Arc<dyn RetentionValidator>,
     virtual_system_mapping: VirtualSystemMapping,
     usage_tracker: FunctionUsageTracker,
@@ -113,8 +115,12 @@ fn make_transaction<RT: Runtime>(
     // has been idle. Make sure creation time is always recent. Existing writes to
     // the transaction will advance next_creation_time in `merge_writes` below.
     let creation_time = CreationTime::try_from(cmp::max(*ts, rt.generate_timestamp()?))?;
-    let transaction_index =
-        TransactionIndex::new(index_registry, database_index_snapshot, text_index_snapshot);
+    let transaction_index = TransactionIndex::new(
+        index_registry,
+        database_index_snapshot,
+        text_index_snapshot,
+        vector_index_snapshot,
+    );
     let mut tx = Transaction::new(
         identity,
         id_generator,
@@ -611,6 +617,7 @@ impl<RT: Runtime> InMemoryIndexCache<RT> {
         bootstrap_metadata: BootstrapMetadata,
         table_count_snapshot: Arc<dyn TableCountSnapshot>,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
         usage_tracker: FunctionUsageTracker,
         retention_validator: Arc<dyn RetentionValidator>,
     ) -> anyhow::Result<Transaction<RT>> {
@@ -662,6 +669,7 @@ impl<RT: Runtime> InMemoryIndexCache<RT> {
             table_count_snapshot,
             database_index_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
             retention_validator,
             virtual_system_mapping().clone(),
             usage_tracker,
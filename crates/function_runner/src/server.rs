@@ -49,6 +49,7 @@ use database::{
     TableCountSnapshot,
     Transaction,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
 };
 use file_storage::TransactionalFileStorage;
 use futures::FutureExt;
@@ -124,6 +125,7 @@ pub struct RunRequestArgs {
     pub bootstrap_metadata: BootstrapMetadata,
     pub table_count_snapshot: Arc<dyn TableCountSnapshot>,
     pub text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+    pub vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     pub action_callbacks: Arc<dyn ActionCallbacks>,
     pub fetch_client: Arc<dyn FetchClient>,
     pub log_line_sender: Option<mpsc::UnboundedSender<LogLine>>,
@@ -271,6 +273,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
         bootstrap_metadata: BootstrapMetadata,
         table_count_snapshot: Arc<dyn TableCountSnapshot>,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
         retention_validator: Arc<dyn RetentionValidator>,
     ) -> anyhow::Result<Transaction<RT>> {
         let usage_tracker = FunctionUsageTracker::new();
@@ -286,6 +289,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
                 bootstrap_metadata,
                 table_count_snapshot,
                 text_index_snapshot,
+                vector_index_snapshot,
                 usage_tracker.clone(),
                 retention_validator,
             )
@@ -328,6 +332,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
             bootstrap_metadata,
             table_count_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
             action_callbacks,
             fetch_client,
             log_line_sender,
@@ -374,6 +379,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
                 bootstrap_metadata,
                 table_count_snapshot,
                 text_index_snapshot,
+                vector_index_snapshot,
                 usage_tracker.clone(),
                 retention_validator,
             )
@@ -195,7 +195,7 @@ impl IndexRegistry {
                 for index in self.indexes_by_table(document.id().tablet_id) {
                     // Only yield fields from database indexes.
                     if let IndexConfig::Database {
-                        developer_config: DeveloperDatabaseIndexConfig { fields },
+                        developer_config: DeveloperDatabaseIndexConfig { fields, .. },
                         on_disk_state: _,
                     } = &index.metadata.config
                     {
@@ -250,7 +250,7 @@ impl IndexRegistry {
             .flat_map(|index| {
                 let key = match &index.metadata.config {
                     IndexConfig::Database {
-                        developer_config: DeveloperDatabaseIndexConfig { fields },
+                        developer_config: DeveloperDatabaseIndexConfig { fields, .. },
                         ..
                     } => Some(DocumentIndexKeyValue::Standard(
                         document.index_key_bytes(&fields[..], self.persistence_version()),
@@ -260,6 +260,7 @@ impl IndexRegistry {
                             DeveloperTextIndexConfig {
                                 search_field,
                                 filter_fields,
+                                ..
                             },
                         ..
                     } => {
@@ -550,6 +551,23 @@ impl IndexRegistry {
             .filter(|index| index.metadata.is_vector_index())
     }
 
+    /// Returns the enabled database indexes on the given table that have a
+    /// uniqueness constraint. Pending unique indexes aren't checked on
+    /// writes; the index worker scans them for duplicate keys when their
+    /// backfill finishes and fails the schema that added them.
+    pub fn unique_indexes_by_table(
+        &self,
+        tablet_id: TabletId,
+    ) -> impl Iterator<Item = &'_ Index> + '_ {
+        self.indexes_by_table(tablet_id).filter(|index| {
+            index.metadata.is_unique_database_index()
+                && self
+                    .enabled_indexes
+                    .get(&index.name())
+                    .is_some_and(|enabled| enabled.id == index.id)
+        })
+    }
+
     /// Returns both enabled and pending indexes for the given table.
     ///
     /// Multiple Indexes with a given name will be returned if an index is
@@ -899,7 +917,9 @@ mod tests {
                 by_content.clone(),
                 DeveloperTextIndexConfig {
                     search_field: FieldPath::from_str("content")?,
+                    extra_search_fields: Default::default(),
                     filter_fields: vec![FieldPath::from_str("author")?].into_iter().collect(),
+                    analyzer: Default::default(),
                 },
                 TextIndexState::SnapshottedAt(TextIndexSnapshot {
                     data: TextIndexSnapshotData::MultiSegment(vec![]),
@@ -77,6 +77,10 @@ use common::{
     utils::ensure_utc,
 };
 use database::{
+    query::{
+        HybridSearchResult,
+        TextSearchResult,
+    },
     shutdown_error,
     Transaction,
 };
@@ -304,6 +308,20 @@ pub trait ActionCallbacks: Send + Sync {
         query: JsonValue,
     ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)>;
 
+    // Hybrid Search
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)>;
+
+    // Text Search
+    async fn text_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<TextSearchResult>, FunctionUsageStats)>;
+
     // Components
     async fn lookup_function_handle(
         &self,
@@ -12,6 +12,12 @@ use common::{
         UnixTimestamp,
     },
 };
+use database::query::{
+    HybridSearchJson,
+    HybridSearchRequest,
+    TextSearchJson,
+    TextSearchRequest,
+};
 use errors::{
     ErrorMetadata,
     ErrorMetadataAnyhowExt,
@@ -67,6 +73,8 @@ impl<RT: Runtime> TaskExecutor<RT> {
                 "1.0/actions/schedule" => self.async_syscall_schedule(args).await?.into(),
                 "1.0/actions/cancel_job" => self.async_syscall_cancel_job(args).await?.into(),
                 "1.0/actions/vectorSearch" => self.async_syscall_vectorSearch(args).await?.into(),
+                "1.0/actions/hybridSearch" => self.async_syscall_hybridSearch(args).await?.into(),
+                "1.0/actions/textSearch" => self.async_syscall_textSearch(args).await?.into(),
                 "1.0/getUserIdentity" => self.async_syscall_getUserIdentity(args).await?.into(),
                 "1.0/storageDelete" => self.async_syscall_storageDelete(args).await?.into(),
                 "1.0/storageGetMetadata" => {
@@ -344,6 +352,44 @@ impl<RT: Runtime> TaskExecutor<RT> {
         Ok(json!({ "results": results }))
     }
 
+    #[convex_macro::instrument_future]
+    async fn async_syscall_hybridSearch(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let HybridSearchRequest { query } = serde_json::from_value(args)?;
+        let component_id = self.component_id();
+        let mut hybrid_search_query: HybridSearchJson = serde_json::from_value(query)?;
+        hybrid_search_query.insert_component_id(component_id);
+
+        let (results, usage_stats) = self
+            .action_callbacks
+            .hybrid_search(
+                self.identity.clone(),
+                serde_json::to_value(hybrid_search_query)?,
+            )
+            .await?;
+        self.usage_tracker.add(usage_stats);
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn async_syscall_textSearch(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let TextSearchRequest { query } = serde_json::from_value(args)?;
+        let component_id = self.component_id();
+        let mut text_search_query: TextSearchJson = serde_json::from_value(query)?;
+        text_search_query.insert_component_id(component_id);
+
+        let (results, usage_stats) = self
+            .action_callbacks
+            .text_search(
+                self.identity.clone(),
+                serde_json::to_value(text_search_query)?,
+            )
+            .await?;
+        self.usage_tracker.add(usage_stats);
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
     #[convex_macro::instrument_future]
     async fn async_syscall_getUserIdentity(&self, _args: JsonValue) -> anyhow::Result<JsonValue> {
         self.user_identity()
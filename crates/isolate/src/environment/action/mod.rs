@@ -272,7 +272,7 @@ impl<RT: Runtime> ActionEnvironment<RT> {
             action_callbacks,
             fetch_client,
             _module_loader: module_loader.clone(),
-            key_broker,
+            key_broker: key_broker.clone(),
             task_order: Default::default(),
             task_retval_sender,
             usage_tracker: transaction.usage_tracker.clone(),
@@ -303,6 +303,7 @@ impl<RT: Runtime> ActionEnvironment<RT> {
                 default_system_env_vars,
                 resources,
                 convex_origin_override,
+                key_broker,
             ),
             syscall_trace,
             heap_stats,
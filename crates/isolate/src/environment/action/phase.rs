@@ -26,6 +26,7 @@ use database::{
     Transaction,
 };
 use errors::ErrorMetadata;
+use keybroker::KeyBroker;
 use model::{
     canonical_urls::CanonicalUrlsModel,
     components::ComponentsModel,
@@ -95,6 +96,7 @@ enum ActionPreloaded<RT: Runtime> {
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         resources: Arc<Mutex<BTreeMap<Reference, Resource>>>,
         convex_origin_override: Arc<Mutex<Option<ConvexOrigin>>>,
+        key_broker: KeyBroker,
     },
     Preloading,
     Ready {
@@ -116,6 +118,7 @@ impl<RT: Runtime> ActionPhase<RT> {
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         resources: Arc<Mutex<BTreeMap<Reference, Resource>>>,
         convex_origin_override: Arc<Mutex<Option<ConvexOrigin>>>,
+        key_broker: KeyBroker,
     ) -> Self {
         Self {
             component,
@@ -127,6 +130,7 @@ impl<RT: Runtime> ActionPhase<RT> {
                 default_system_env_vars,
                 resources,
                 convex_origin_override,
+                key_broker,
             },
         }
     }
@@ -146,6 +150,7 @@ impl<RT: Runtime> ActionPhase<RT> {
             default_system_env_vars,
             resources,
             convex_origin_override,
+            key_broker,
         } = preloaded
         else {
             anyhow::bail!("ActionPhase initialized twice");
@@ -218,7 +223,7 @@ impl<RT: Runtime> ActionPhase<RT> {
             let user_env_vars = with_release_permit(
                 timeout,
                 permit_slot,
-                EnvironmentVariablesModel::new(&mut tx).get_all(),
+                EnvironmentVariablesModel::new(&mut tx, &key_broker).get_all(),
             )
             .await?;
             env_vars.extend(user_env_vars);
@@ -43,8 +43,14 @@ use common::{
 use database::{
     query::{
         query_batch_next,
+        HybridSearch,
+        HybridSearchJson,
+        HybridSearchRequest,
         PaginationOptions,
         TableFilter,
+        TextSearch,
+        TextSearchJson,
+        TextSearchRequest,
     },
     soft_data_limit,
     table_summary::table_summary_bootstrapping_error,
@@ -97,6 +103,11 @@ use value::{
     ConvexObject,
     TableName,
 };
+use vector::{
+    VectorSearch,
+    VectorSearchJson,
+    VectorSearchRequest,
+};
 
 use super::DatabaseUdfEnvironment;
 use crate::{
@@ -690,6 +701,9 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
                     "1.0/replace" => Box::pin(Self::replace(provider, args)).await,
                     "1.0/remove" => Box::pin(Self::remove(provider, args)).await,
                     "1.0/queryPage" => Box::pin(Self::query_page(provider, args)).await,
+                    "1.0/vectorSearch" => Box::pin(Self::vector_search(provider, args)).await,
+                    "1.0/hybridSearch" => Box::pin(Self::hybrid_search(provider, args)).await,
+                    "1.0/textSearch" => Box::pin(Self::text_search(provider, args)).await,
                     // Auth
                     "1.0/getUserIdentity" => {
                         Box::pin(Self::get_user_identity(provider, args)).await
@@ -1239,6 +1253,57 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
         DatabaseSyscallsShared::query_page(provider, args).await
     }
 
+    #[convex_macro::instrument_future]
+    async fn vector_search(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let component = provider.component()?;
+        let query = with_argument_error("vectorSearch", || {
+            let VectorSearchRequest { query } = serde_json::from_value(args)?;
+            let mut query: VectorSearchJson =
+                serde_json::from_value(query).context(ArgName("query"))?;
+            query.insert_component_id(component);
+            VectorSearch::try_from(serde_json::to_value(query)?).context(ArgName("query"))
+        })?;
+        let table_filter = provider.table_filter();
+        let tx = provider.tx()?;
+        let results = database::query::vector_search(tx, query, table_filter).await?;
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn hybrid_search(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let component = provider.component()?;
+        let query = with_argument_error("hybridSearch", || {
+            let HybridSearchRequest { query } = serde_json::from_value(args)?;
+            let mut query: HybridSearchJson =
+                serde_json::from_value(query).context(ArgName("query"))?;
+            query.insert_component_id(component);
+            HybridSearch::try_from(serde_json::to_value(query)?).context(ArgName("query"))
+        })?;
+        let table_filter = provider.table_filter();
+        let tx = provider.tx()?;
+        let results = database::query::hybrid_search(tx, query, table_filter).await?;
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn text_search(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let component = provider.component()?;
+        let query = with_argument_error("textSearch", || {
+            let TextSearchRequest { query } = serde_json::from_value(args)?;
+            let mut query: TextSearchJson =
+                serde_json::from_value(query).context(ArgName("query"))?;
+            query.insert_component_id(component);
+            TextSearch::try_from(serde_json::to_value(query)?).context(ArgName("query"))
+        })?;
+        let table_filter = provider.table_filter();
+        let tx = provider.tx()?;
+        let results = database::query::text_search(tx, query, table_filter).await?;
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
     #[fastrace::trace]
     #[convex_macro::instrument_future]
     async fn remove(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
@@ -346,6 +346,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
                 module_loader.clone(),
                 default_system_env_vars,
                 component,
+                key_broker.clone(),
             ),
             file_storage,
 
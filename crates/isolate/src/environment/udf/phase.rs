@@ -22,6 +22,7 @@ use database::{
     Transaction,
 };
 use errors::ErrorMetadata;
+use keybroker::KeyBroker;
 use model::{
     environment_variables::{
         types::{
@@ -82,6 +83,7 @@ pub struct UdfPhase<RT: Runtime> {
     module_loader: Arc<dyn ModuleCache<RT>>,
     preloaded: UdfPreloaded,
     component: ComponentId,
+    key_broker: KeyBroker,
 }
 
 enum UdfPreloaded {
@@ -108,6 +110,7 @@ impl<RT: Runtime> UdfPhase<RT> {
         module_loader: Arc<dyn ModuleCache<RT>>,
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         component: ComponentId,
+        key_broker: KeyBroker,
     ) -> Self {
         Self {
             phase: Phase::Importing,
@@ -118,6 +121,7 @@ impl<RT: Runtime> UdfPhase<RT> {
                 default_system_env_vars,
             },
             component,
+            key_broker,
         }
     }
 
@@ -168,7 +172,13 @@ impl<RT: Runtime> UdfPhase<RT> {
                 with_release_permit(
                     timeout,
                     permit_slot,
-                    EnvironmentVariablesModel::new(self.tx_mut()?).preload(),
+                    EnvironmentVariablesModel::new(
+                        self.tx
+                            .as_mut()
+                            .context("Transaction missing due to concurrent component call")?,
+                        &self.key_broker,
+                    )
+                    .preload(),
                 )
                 .await?,
             )
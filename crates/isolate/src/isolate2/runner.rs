@@ -1050,7 +1050,9 @@ pub async fn run_isolate_v2_udf<RT: Runtime>(
             .map(|c| c.import_phase_unix_timestamp)
             .context("Missing import phase unix timestamp")?,
     };
-    let env_vars = EnvironmentVariablesModel::new(&mut tx).preload().await?;
+    let env_vars = EnvironmentVariablesModel::new(&mut tx, &key_broker)
+        .preload()
+        .await?;
 
     // TODO: This unconditionally takes a table mapping dep.
     let shared = UdfShared::new(tx.table_mapping().clone());
@@ -54,6 +54,12 @@ use common::{
     version::Version,
 };
 use database::{
+    query::{
+        HybridSearch,
+        HybridSearchResult,
+        TextSearch,
+        TextSearchResult,
+    },
     test_helpers::{
         DbFixtures,
         DbFixturesArgs,
@@ -1424,6 +1430,24 @@ impl<RT: Runtime, P: Persistence> ActionCallbacks for UdfTest<RT, P> {
         self.database.vector_search(identity, query).await
     }
 
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let query = HybridSearch::try_from(query)?;
+        self.database.hybrid_search(identity, query).await
+    }
+
+    async fn text_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<TextSearchResult>, FunctionUsageStats)> {
+        let query = TextSearch::try_from(query)?;
+        self.database.text_search(identity, query).await
+    }
+
     async fn lookup_function_handle(
         &self,
         identity: Identity,
@@ -21,7 +21,7 @@ async fn test_get_environment_variable(rt: TestRuntime) -> anyhow::Result<()> {
         let mut tx = t.database.begin(Identity::system()).await?;
         let environment_variable =
             EnvironmentVariable::new("TEST_NAME".parse()?, "TEST_VALUE".parse()?);
-        EnvironmentVariablesModel::new(&mut tx)
+        EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
             .create(environment_variable, &HashSet::new())
             .await?;
         t.database.commit(tx).await?;
@@ -123,7 +123,7 @@ async fn test_environment_variable_reads_recorded(
         let mut tx = t.database.begin_system().await?;
         let unrelated_variable =
             EnvironmentVariable::new("UNRELATED_NAME".parse()?, "TEST_VALUE".parse()?);
-        EnvironmentVariablesModel::new(&mut tx)
+        EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
             .create(unrelated_variable, &HashSet::new())
             .await?;
         let new_ts = t.database.commit(tx).await?;
@@ -136,7 +136,7 @@ async fn test_environment_variable_reads_recorded(
         let mut tx = t.database.begin_system().await?;
         let mut related_variable =
             EnvironmentVariable::new(env_var_name.parse()?, "TEST_VALUE".parse()?);
-        let env_var_id = EnvironmentVariablesModel::new(&mut tx)
+        let env_var_id = EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
             .create(related_variable.clone(), &HashSet::new())
             .await?;
         let new_ts = t.database.commit(tx).await?;
@@ -156,7 +156,7 @@ async fn test_environment_variable_reads_recorded(
         assert_eq!(outcome.result.unwrap().json_value(), json!("TEST_VALUE"));
         let mut tx = t.database.begin_system().await?;
         related_variable.value = "TEST_VALUE_2".parse()?;
-        EnvironmentVariablesModel::new(&mut tx)
+        EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
             .edit([(env_var_id, related_variable)].into_iter().collect())
             .await?;
         let new_ts = t.database.commit(tx).await?;
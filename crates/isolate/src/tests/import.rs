@@ -43,7 +43,7 @@ async fn test_dynamic_import_load_failure(rt: TestRuntime) -> anyhow::Result<()>
     let mut tx = t.database.begin(Identity::system()).await?;
     let environment_variable =
         EnvironmentVariable::new("FAIL_MODULE_LOAD".parse()?, "fail".parse()?);
-    EnvironmentVariablesModel::new(&mut tx)
+    EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
         .create(environment_variable, &HashSet::new())
         .await?;
     t.database.commit(tx).await?;
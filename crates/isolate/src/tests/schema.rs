@@ -250,6 +250,7 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                 indexes: btreemap!(),
                 search_indexes: btreemap!(),
                 vector_indexes: btreemap!(),
+                references: Default::default(),
                 document_type: Some(DocumentSchema::Union(vec![
                   object_validator!(
                     "ref" => FieldValidator::required_field_type(Validator::Id("twoIndexTable".parse()?)),
@@ -278,14 +279,17 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                     by_email.clone() => IndexSchema {
                         index_descriptor: by_email,
                         fields: vec!["email".parse()?].try_into()?,
+                        unique: false,
                     },
                     by_creation_deleted.clone() => IndexSchema {
                         index_descriptor: by_creation_deleted,
                         fields: vec!["creation".parse()?, "deleted".parse()?].try_into()?,
+                        unique: false,
                     },
                 ),
                 search_indexes: btreemap!(),
                 vector_indexes: btreemap!(),
+                references: Default::default(),
                 document_type: None,
             },
             name3.clone() => TableDefinition {
@@ -295,10 +299,13 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                 search_index.clone() => SearchIndexSchema::new(
                   search_index,
                   "title".parse()?,
-                  btreeset!{"is_deleted".parse()?, "workspace_id".parse()?}
+                  btreemap!(),
+                  btreeset!{"is_deleted".parse()?, "workspace_id".parse()?},
+                  Default::default(),
                 )?
                },
                vector_indexes: btreemap!(),
+               references: Default::default(),
                document_type: None,
           }
         ),
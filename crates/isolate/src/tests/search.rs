@@ -39,7 +39,9 @@ async fn add_text_index(t: &UdfTest<TestRuntime, TestPersistence>) -> anyhow::Re
     t.add_index(IndexMetadata::new_backfilling_text_index(
         "messages.by_body".parse()?,
         "body".parse()?,
+        Default::default(),
         btreeset! { "filterField".parse()?},
+        Default::default(),
     ))
     .await
 }
@@ -18,7 +18,7 @@ async fn test_system_udf(rt: TestRuntime) -> anyhow::Result<()> {
     let t = UdfTest::default(rt).await?;
     let mut tx = t.database.begin(Identity::system()).await?;
     let environment_variable = EnvironmentVariable::new("A".parse()?, "B".parse()?);
-    EnvironmentVariablesModel::new(&mut tx)
+    EnvironmentVariablesModel::new(&mut tx, &t.key_broker)
         .create(environment_variable, &HashSet::new())
         .await?;
     t.database.commit(tx).await?;
@@ -115,6 +115,7 @@ const ADMIN_KEY_VERSION: u8 = 1;
 const CURSOR_VERSION: u8 = 7;
 const STORE_FILE_AUTHZ_VERSION: u8 = 1;
 const QUERY_JOURNAL_VERSION: u8 = 7;
+const ENV_VAR_VERSION: u8 = 1;
 
 // Max delay from transaction start time -> key being issued that is tolerable.
 const MAX_TS_DELAY: Duration = Duration::from_secs(15);
@@ -128,6 +129,11 @@ pub struct KeyBroker {
     cursor_encryptor: DeterministicEncryptor,
     journal_encryptor: RandomEncryptor,
     store_file_encryptor: RandomEncryptor,
+    env_var_encryptor: RandomEncryptor,
+    /// Keys derived from instance secrets this instance used to have, so that
+    /// environment variables encrypted before a secret rotation can still be
+    /// decrypted and re-encrypted with the current key.
+    previous_env_var_encryptors: Vec<RandomEncryptor>,
 }
 
 // This enum encodes a successful authentication decision, and its nontrivial
@@ -803,9 +809,30 @@ impl KeyBroker {
                 &instance_secret,
                 Purpose::STORE_FILE_AUTHORIZATION,
             )?,
+            env_var_encryptor: RandomEncryptor::derive_from_secret(
+                &instance_secret,
+                Purpose::ENVIRONMENT_VARIABLE,
+            )?,
+            previous_env_var_encryptors: vec![],
         })
     }
 
+    /// Also accept data encrypted with keys derived from these former instance
+    /// secrets, newest first.
+    pub fn with_previous_instance_secrets(
+        mut self,
+        previous_secrets: impl IntoIterator<Item = InstanceSecret>,
+    ) -> anyhow::Result<Self> {
+        for secret in previous_secrets {
+            self.previous_env_var_encryptors
+                .push(RandomEncryptor::derive_from_secret(
+                    &secret,
+                    Purpose::ENVIRONMENT_VARIABLE,
+                )?);
+        }
+        Ok(self)
+    }
+
     pub fn dev() -> Self {
         Self::new(
             crate::DEV_INSTANCE_NAME,
@@ -1099,6 +1126,35 @@ impl KeyBroker {
         }
     }
 
+    /// Encrypts an environment variable value for storage at rest.
+    pub fn encrypt_env_var_value(&self, value: &str) -> String {
+        self.env_var_encryptor
+            .encrypt_proto(ENV_VAR_VERSION, &value.to_owned())
+    }
+
+    /// Decrypts an environment variable value encrypted with the current or a
+    /// previous instance secret.
+    pub fn decrypt_env_var_value(&self, encrypted: &str) -> anyhow::Result<String> {
+        let mut result = self
+            .env_var_encryptor
+            .decrypt_proto::<String>(ENV_VAR_VERSION, encrypted);
+        for encryptor in &self.previous_env_var_encryptors {
+            if result.is_ok() {
+                break;
+            }
+            result = encryptor.decrypt_proto(ENV_VAR_VERSION, encrypted);
+        }
+        result.context("Couldn't decrypt environment variable value")
+    }
+
+    /// Whether `encrypted` was encrypted with a previous instance secret and
+    /// should be re-encrypted with the current one.
+    pub fn env_var_value_needs_reencryption(&self, encrypted: &str) -> bool {
+        self.env_var_encryptor
+            .decrypt_proto::<String>(ENV_VAR_VERSION, encrypted)
+            .is_err()
+    }
+
     pub fn issue_action_token(&self, component_id: ComponentId) -> ActionCallbackToken {
         let now = SystemTime::now();
         let since_epoch = now
@@ -1198,6 +1254,7 @@ mod tests {
     use crate::{
         AdminIdentity,
         Identity,
+        InstanceSecret,
     };
 
     #[test]
@@ -1360,6 +1417,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_env_var_key_rotation() -> anyhow::Result<()> {
+        let old_secret = InstanceSecret::random();
+        let old_kb = KeyBroker::new("carnitas", old_secret)?;
+        let encrypted = old_kb.encrypt_env_var_value("hunter2");
+        assert_eq!(old_kb.decrypt_env_var_value(&encrypted)?, "hunter2");
+        assert!(!old_kb.env_var_value_needs_reencryption(&encrypted));
+
+        // A broker with a new secret can't read the value until it knows the
+        // old secret.
+        let new_kb = KeyBroker::new("carnitas", InstanceSecret::random())?;
+        assert!(new_kb.decrypt_env_var_value(&encrypted).is_err());
+        let new_kb = new_kb.with_previous_instance_secrets([old_secret])?;
+        assert_eq!(new_kb.decrypt_env_var_value(&encrypted)?, "hunter2");
+        assert!(new_kb.env_var_value_needs_reencryption(&encrypted));
+
+        let reencrypted = new_kb.encrypt_env_var_value("hunter2");
+        assert!(!new_kb.env_var_value_needs_reencryption(&reencrypted));
+        assert!(old_kb.decrypt_env_var_value(&reencrypted).is_err());
+        Ok(())
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig { cases: 64 * env_config("CONVEX_PROPTEST_MULTIPLIER", 1), failure_persistence: None, .. ProptestConfig::default() })]
 
@@ -19,6 +19,7 @@ use common::{
         UdfType,
     },
 };
+use http::header::CONTENT_TYPE;
 use serde::Deserialize;
 use sync_types::UdfPath;
 
@@ -100,6 +101,16 @@ pub(crate) async fn cache_hit_percentage_top_k(
     Ok(Json(timeseries))
 }
 
+/// Per-function metrics in the Prometheus text exposition format, for scraping
+/// with an admin key.
+pub(crate) async fn prometheus_function_metrics(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    let metrics = st.application.prometheus_function_metrics(identity).await?;
+    Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4")], metrics))
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CacheHitPercentageQueryArgs {
@@ -0,0 +1,94 @@
+use anyhow::Context;
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::http::{
+    extract::Json,
+    HttpResponseError,
+};
+use errors::ErrorMetadata;
+use model::config::types::{
+    CronDiff,
+    ModuleDiff,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::id_v6::DeveloperDocumentId;
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeVersionJson {
+    id: String,
+    creation_time: f64,
+    source_package_id: String,
+    server_version: String,
+    modules: Vec<String>,
+    /// Whether this is the code the deployment is currently running.
+    active: bool,
+}
+
+pub async fn list_code_versions(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let (versions, active_source_package) = st.application.list_code_versions(identity).await?;
+    let versions: Vec<_> = versions
+        .into_iter()
+        .map(|version| CodeVersionJson {
+            id: version.developer_id().encode(),
+            creation_time: f64::from(version.creation_time()),
+            source_package_id: DeveloperDocumentId::from(version.source_package_id).encode(),
+            server_version: version.udf_config.server_version.to_string(),
+            modules: version
+                .modules
+                .iter()
+                .map(|module| module.path.as_str().to_string())
+                .collect(),
+            active: Some(version.source_package_id) == active_source_package,
+        })
+        .collect();
+    Ok(Json(versions))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackCodeVersionArgs {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackCodeVersionResponse {
+    modules: ModuleDiff,
+    crons: CronDiff,
+}
+
+pub async fn rollback_code_version(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(RollbackCodeVersionArgs { id }): Json<RollbackCodeVersionArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let id = DeveloperDocumentId::decode(&id).context(ErrorMetadata::bad_request(
+        "InvalidCodeVersion",
+        format!("invalid code version id {id}"),
+    ))?;
+    let diff = st.application.rollback_code_version(identity, id).await?;
+    Ok(Json(RollbackCodeVersionResponse {
+        modules: diff.module_diff,
+        crons: diff.cron_diff,
+    }))
+}
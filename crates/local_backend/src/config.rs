@@ -1,4 +1,7 @@
-use std::fmt;
+use std::{
+    fmt,
+    path::PathBuf,
+};
 
 use clap::Parser;
 use clusters::DbDriverTag;
@@ -17,6 +20,8 @@ use model::database_globals::types::StorageTagInitializer;
 use serde_json::Value as JsonValue;
 use url::Url;
 
+use crate::tracing_export::OtlpProtocol;
+
 #[derive(Parser, Clone)]
 #[clap(version = &**SERVER_VERSION_STR, author = "Convex, Inc. <no-reply@convex.dev>", group(clap::ArgGroup::new("storage").multiple(false)))]
 pub struct LocalConfig {
@@ -80,6 +85,12 @@ pub struct LocalConfig {
     #[clap(long, requires = "instance_name")]
     pub instance_secret: Option<String>,
 
+    /// Instance secrets this backend used previously, comma-separated. Values
+    /// encrypted with keys derived from them (like environment variables) are
+    /// still readable and get re-encrypted with the current secret on startup.
+    #[clap(long, value_delimiter = ',', requires = "instance_secret")]
+    pub previous_instance_secrets: Vec<String>,
+
     /// Identifier (like a user ID) to attach to any sentry
     /// events generated by this backend. Sentry is disabled
     /// by default.
@@ -122,6 +133,43 @@ pub struct LocalConfig {
     /// reach the client for debugging purposes.
     #[clap(long, default_value = "false")]
     pub redact_logs_to_client: bool,
+
+    /// If set, run as a read replica of the backend at this URL. The replica
+    /// connects to the same database with a read-only connection, serves
+    /// queries and subscriptions from the leader's committed data, and
+    /// forwards mutations and actions to the leader.
+    #[clap(long)]
+    pub leader_url: Option<Url>,
+
+    /// If set, completed function executions and their log lines are also
+    /// written to rotating files in this directory, so they survive restarts
+    /// and can be searched through the `/api/query_function_logs` endpoint.
+    #[clap(long)]
+    pub function_log_dir: Option<PathBuf>,
+
+    /// If set, export tracing spans to this OpenTelemetry collector endpoint.
+    /// For gRPC this is the collector address (e.g. `http://localhost:4317`);
+    /// for HTTP it is the base URL that `/v1/traces` is appended to.
+    #[clap(long)]
+    pub otlp_endpoint: Option<Url>,
+
+    /// Protocol used to talk to the OTLP collector.
+    #[clap(long, value_enum, default_value_t = OtlpProtocol::Grpc)]
+    pub otlp_protocol: OtlpProtocol,
+
+    /// Service name reported on exported spans.
+    #[clap(long, default_value = "convex-backend")]
+    pub otlp_service_name: String,
+
+    /// If set, run "use node" actions on the node executor server at this URL,
+    /// like a sidecar container, instead of in local node processes.
+    #[clap(long, requires = "node_executor_secret")]
+    pub node_executor_url: Option<Url>,
+
+    /// Shared secret for the remote node executor. The executor must be
+    /// started with the same value in `NODE_EXECUTOR_SECRET`.
+    #[clap(long, requires = "node_executor_url")]
+    pub node_executor_secret: Option<String>,
 }
 
 impl fmt::Debug for LocalConfig {
@@ -130,6 +178,9 @@ impl fmt::Debug for LocalConfig {
             .field("convex_origin", &self.convex_origin)
             .field("convex_site", &self.convex_site)
             .field("instance_name", &self.instance_name)
+            .field("leader_url", &self.leader_url)
+            .field("otlp_endpoint", &self.otlp_endpoint)
+            .field("node_executor_url", &self.node_executor_url)
             .finish()
     }
 }
@@ -188,7 +239,12 @@ impl LocalConfig {
 
     pub fn key_broker(&self) -> anyhow::Result<KeyBroker> {
         let name = self.name().clone();
-        KeyBroker::new(&name, self.secret()?)
+        let previous_secrets: Vec<_> = self
+            .previous_instance_secrets
+            .iter()
+            .map(|secret| InstanceSecret::try_from(secret.as_str()))
+            .try_collect()?;
+        KeyBroker::new(&name, self.secret()?)?.with_previous_instance_secrets(previous_secrets)
     }
 
     pub fn secret(&self) -> anyhow::Result<InstanceSecret> {
@@ -51,6 +51,10 @@ use model::{
     },
     external_packages::types::ExternalDepsPackageId,
     modules::module_versions::SerializedAnalyzedModule,
+    rate_limits::types::{
+        RateLimitPolicy,
+        SerializedRateLimitPolicy,
+    },
     source_packages::types::SourcePackage,
 };
 use serde::{
@@ -95,6 +99,11 @@ impl TryFrom<StartPushResponse> for SerializedStartPushResponse {
                 .into_iter()
                 .map(SerializedAuthInfo::try_from)
                 .collect::<anyhow::Result<_>>()?,
+            rate_limits: value
+                .rate_limits
+                .into_iter()
+                .map(SerializedRateLimitPolicy::from)
+                .collect(),
             analysis: value
                 .analysis
                 .into_iter()
@@ -139,6 +148,11 @@ impl TryFrom<SerializedStartPushResponse> for StartPushResponse {
                 .into_iter()
                 .map(AuthInfo::try_from)
                 .collect::<anyhow::Result<_>>()?,
+            rate_limits: value
+                .rate_limits
+                .into_iter()
+                .map(RateLimitPolicy::try_from)
+                .collect::<anyhow::Result<_>>()?,
             analysis: value
                 .analysis
                 .into_iter()
@@ -161,6 +175,8 @@ pub struct SerializedStartPushResponse {
 
     // Analysis results.
     app_auth: Vec<SerializedAuthInfo>,
+    #[serde(default)]
+    rate_limits: Vec<SerializedRateLimitPolicy>,
     analysis: BTreeMap<String, SerializedEvaluatedComponentDefinition>,
 
     // Typechecking results.
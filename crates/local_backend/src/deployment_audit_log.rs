@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::{
+    document::CreationTime,
+    http::{
+        extract::{
+            Json,
+            Query,
+        },
+        HttpResponseError,
+    },
+    knobs::AUDIT_LOG_QUERY_MAX_PAGE_SIZE,
+    types::MemberId,
+};
+use errors::ErrorMetadata;
+use model::deployment_audit_log::types::{
+    DeploymentAuditLogEntry,
+    DeploymentAuditLogFilter,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    admin::must_be_admin,
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogArgs {
+    /// Comma-separated actions, like `create_environment_variable,push_config`.
+    actions: Option<String>,
+    member_id: Option<u64>,
+    /// Milliseconds since the Unix epoch, inclusive.
+    from: Option<f64>,
+    /// Milliseconds since the Unix epoch, exclusive.
+    to: Option<f64>,
+    /// The cursor returned with the previous page.
+    cursor: Option<f64>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntryJson {
+    id: String,
+    creation_time: f64,
+    action: String,
+    member_id: Option<u64>,
+    metadata: JsonValue,
+}
+
+impl From<DeploymentAuditLogEntry> for AuditLogEntryJson {
+    fn from(entry: DeploymentAuditLogEntry) -> Self {
+        Self {
+            id: entry.id.encode(),
+            creation_time: f64::from(entry.creation_time),
+            action: entry.action,
+            member_id: entry.member_id.map(u64::from),
+            metadata: entry.metadata.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogResponse {
+    events: Vec<AuditLogEntryJson>,
+    /// Pass as `cursor` to fetch the next page. `null` once there are no more
+    /// events.
+    cursor: Option<f64>,
+}
+
+fn parse_time(field: &str, ms: Option<f64>) -> anyhow::Result<Option<CreationTime>> {
+    ms.map(|ms| {
+        CreationTime::try_from(ms).map_err(|e| {
+            anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidAuditLogQuery",
+                format!("Invalid {field}: {e}"),
+            ))
+        })
+    })
+    .transpose()
+}
+
+pub async fn list_deployment_audit_log(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Query(ListAuditLogArgs {
+        actions,
+        member_id,
+        from,
+        to,
+        cursor,
+        limit,
+    }): Query<ListAuditLogArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let filter = DeploymentAuditLogFilter {
+        actions: actions.map(|actions| {
+            actions
+                .split(',')
+                .map(|action| action.trim().to_string())
+                .filter(|action| !action.is_empty())
+                .collect::<BTreeSet<_>>()
+        }),
+        member_id: member_id.map(MemberId),
+        from: parse_time("from", from)?,
+        to: parse_time("to", to)?,
+    };
+    let page_size = limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, *AUDIT_LOG_QUERY_MAX_PAGE_SIZE);
+    let (entries, cursor) = st
+        .application
+        .list_deployment_audit_log(identity, filter, parse_time("cursor", cursor)?, page_size)
+        .await?;
+    Ok(Json(ListAuditLogResponse {
+        events: entries.into_iter().map(AuditLogEntryJson::from).collect(),
+        cursor: cursor.map(f64::from),
+    }))
+}
@@ -26,6 +26,9 @@ use crate::{
 pub struct UpdateEnvVarRequest {
     name: String,
     value: Option<String>, // None → delete existing
+    /// Secret variables are readable by functions but not through admin APIs.
+    #[serde(default)]
+    secret: bool,
 }
 
 impl UpdateEnvVarRequest {
@@ -34,11 +37,18 @@ impl UpdateEnvVarRequest {
             UpdateEnvVarRequest {
                 name,
                 value: Some(value),
+                secret,
             } => {
                 let env_var = validate_env_var(&name, &value)?;
-                Ok(vec![EnvVarChange::Set(env_var)])
+                if secret {
+                    Ok(vec![EnvVarChange::SetSecret(env_var)])
+                } else {
+                    Ok(vec![EnvVarChange::Set(env_var)])
+                }
             },
-            UpdateEnvVarRequest { name, value: None } => {
+            UpdateEnvVarRequest {
+                name, value: None, ..
+            } => {
                 let name = name.parse()?;
                 Ok(vec![EnvVarChange::Unset(name)])
             },
@@ -124,7 +134,9 @@ mod tests {
         backend: &TestLocalBackend,
     ) -> anyhow::Result<BTreeMap<EnvVarName, EnvVarValue>> {
         let mut tx = backend.st.application.begin(Identity::system()).await?;
-        let envs = EnvironmentVariablesModel::new(&mut tx).get_all().await?;
+        let envs = EnvironmentVariablesModel::new(&mut tx, backend.st.application.key_broker())
+            .get_all()
+            .await?;
         Ok(envs)
     }
 
@@ -206,4 +218,37 @@ mod tests {
         );
         Ok(())
     }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_secret_env_vars(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        update_environment_variables(
+            &backend,
+            json!([
+                {"name": "name1", "value": "value1"},
+                {"name": "name2", "value": "value2", "secret": true},
+            ]),
+        )
+        .await?;
+        // Functions can still read secret values.
+        assert_eq!(
+            list_environment_variables(&backend).await?,
+            btreemap! {
+                "name1".parse()? => "value1".parse()?,
+                "name2".parse()? => "value2".parse()?,
+            }
+        );
+        let mut tx = backend.st.application.begin(Identity::system()).await?;
+        let redacted = EnvironmentVariablesModel::new(&mut tx, backend.st.application.key_broker())
+            .list_redacted()
+            .await?;
+        assert_eq!(
+            redacted,
+            btreemap! {
+                "name1".parse()? => Some("value1".parse()?),
+                "name2".parse()? => None,
+            }
+        );
+        Ok(())
+    }
 }
@@ -17,7 +17,9 @@ use ::authentication::{
 use application::{
     self,
     api::ApplicationApi,
+    function_log_store::FunctionLogStore,
     log_visibility::RedactLogsToClient,
+    usage_accounting::UsageAccounting,
     Application,
     QueryCache,
 };
@@ -32,7 +34,10 @@ use common::{
         UDF_CACHE_MAX_SIZE,
     },
     log_streaming::NoopLogSender,
-    persistence::Persistence,
+    persistence::{
+        Persistence,
+        PersistenceReader,
+    },
     runtime::Runtime,
     shutdown::ShutdownSignal,
     types::{
@@ -52,14 +57,18 @@ use function_runner::{
     server::InstanceStorage,
     FunctionRunner,
 };
+use log_sinks::LocalLogSinkSender;
 use model::{
     initialize_application_system_tables,
     virtual_system_mapping,
 };
 use node_executor::{
     local::LocalNodeExecutor,
+    remote::RemoteNodeExecutor,
     Actions,
+    NodeExecutor,
 };
+use read_replica::ReadReplicaApi;
 use runtime::prod::ProdRuntime;
 use search::{
     searcher::InProcessSearcher,
@@ -67,6 +76,7 @@ use search::{
     SegmentTermMetadataFetcher,
 };
 use serde::Serialize;
+use url::Url;
 
 pub mod admin;
 mod app_metrics;
@@ -74,21 +84,26 @@ mod args_structs;
 pub mod authentication;
 pub mod beacon;
 pub mod canonical_urls;
+pub mod code_versions;
 pub mod config;
 pub mod custom_headers;
 pub mod dashboard;
 pub mod deploy_config;
 pub mod deploy_config2;
+pub mod deployment_audit_log;
 pub mod environment_variables;
 pub mod http_actions;
+pub mod log_sinks;
 pub mod logs;
 pub mod node_action_callbacks;
 pub mod parse;
 pub mod proxy;
 pub mod public_api;
+pub mod read_replica;
 pub mod router;
 pub mod scheduling;
 pub mod schema;
+pub mod schema_migrations;
 pub mod snapshot_export;
 pub mod snapshot_import;
 pub mod storage;
@@ -96,6 +111,8 @@ pub mod streaming_import;
 pub mod subs;
 #[cfg(test)]
 mod test_helpers;
+pub mod tracing_export;
+pub mod usage;
 
 pub const MAX_CONCURRENT_REQUESTS: usize = 128;
 
@@ -108,6 +125,9 @@ pub struct LocalAppState {
     // Name of the instance. (e.g. crazy-giraffe-123)
     pub instance_name: String,
     pub application: Application<ProdRuntime>,
+    // API used by the public routes. On a read replica this forwards mutations
+    // and actions to the leader.
+    pub api: Arc<dyn ApplicationApi>,
     pub zombify_rx: async_broadcast::Receiver<()>,
 }
 
@@ -144,13 +164,14 @@ pub async fn make_app(
     // TODO(CX-6572) Separate `SegmentMetadataFetcher` from `SearcherImpl`
     let segment_metadata_fetcher: Arc<dyn SegmentTermMetadataFetcher> =
         Arc::new(in_process_searcher);
+    let usage_accounting = UsageAccounting::new(runtime.clone());
     let database = Database::load(
         persistence.clone(),
         runtime.clone(),
         searcher.clone(),
         preempt_tx,
         virtual_system_mapping().clone(),
-        Arc::new(NoOpUsageEventLogger),
+        Arc::new(usage_accounting.clone()),
     )
     .await?;
     initialize_application_system_tables(&database).await?;
@@ -161,6 +182,7 @@ pub async fn make_app(
         config.name(),
     )
     .await?;
+    Application::reencrypt_environment_variables(&database, &key_broker).await?;
 
     let file_storage = FileStorage {
         transactional_file_storage: TransactionalFileStorage::new(
@@ -171,8 +193,7 @@ pub async fn make_app(
         database: database.clone(),
     };
 
-    let node_process_timeout = *ACTION_USER_TIMEOUT + Duration::from_secs(5);
-    let node_executor = Arc::new(LocalNodeExecutor::new(node_process_timeout).await?);
+    let node_executor = make_node_executor(runtime.clone(), &config).await?;
     let actions = Actions::new(
         node_executor,
         config.convex_origin_url()?,
@@ -207,6 +228,10 @@ pub async fn make_app(
         .await?,
     );
 
+    let function_log_store = match config.function_log_dir.clone() {
+        Some(dir) => Some(FunctionLogStore::new(runtime.clone(), dir).await?),
+        None => None,
+    };
     let application = Application::new(
         runtime.clone(),
         database.clone(),
@@ -222,13 +247,18 @@ pub async fn make_app(
         segment_metadata_fetcher,
         persistence,
         actions,
-        Arc::new(NoopLogSender),
+        Arc::new(LocalLogSinkSender::start(
+            runtime.clone(),
+            database.clone(),
+        )?),
         Arc::new(RedactLogsToClient::new(config.redact_logs_to_client)),
         Arc::new(ApplicationAuth::new(
             key_broker.clone(),
             Arc::new(NullAccessTokenAuth),
         )),
         QueryCache::new(*UDF_CACHE_MAX_SIZE),
+        Some(usage_accounting),
+        function_log_store,
     )
     .await?;
 
@@ -249,6 +279,7 @@ pub async fn make_app(
         origin,
         site_origin: config.convex_site_url()?,
         instance_name,
+        api: Arc::new(application.clone()),
         application,
         zombify_rx,
     };
@@ -256,6 +287,130 @@ pub async fn make_app(
     Ok(app_state)
 }
 
+/// Runs node actions on the remote executor in `--node-executor-url` if set,
+/// or in local node processes otherwise.
+async fn make_node_executor(
+    runtime: ProdRuntime,
+    config: &LocalConfig,
+) -> anyhow::Result<Arc<dyn NodeExecutor>> {
+    let node_process_timeout = *ACTION_USER_TIMEOUT + Duration::from_secs(5);
+    let (Some(url), Some(secret)) = (&config.node_executor_url, &config.node_executor_secret)
+    else {
+        return Ok(Arc::new(
+            LocalNodeExecutor::new(node_process_timeout).await?,
+        ));
+    };
+    let executor =
+        RemoteNodeExecutor::new(runtime, url.clone(), secret.clone(), node_process_timeout)?;
+    // The executor may start after the backend, so this isn't fatal.
+    if let Err(e) = executor.check_health().await {
+        tracing::warn!("{e:#}");
+    }
+    Ok(Arc::new(executor))
+}
+
+/// Like [`make_app`], but for a read replica following the leader at
+/// `leader_url` through a read-only connection to its database.
+pub async fn make_read_replica_app(
+    runtime: ProdRuntime,
+    config: LocalConfig,
+    leader_url: Url,
+    reader: Arc<dyn PersistenceReader>,
+    zombify_rx: async_broadcast::Receiver<()>,
+    preempt_tx: ShutdownSignal,
+) -> anyhow::Result<LocalAppState> {
+    let key_broker = config.key_broker()?;
+    let searcher: Arc<dyn Searcher> = Arc::new(InProcessSearcher::new(runtime.clone()).await?);
+    let database = Database::load_follower(
+        reader.clone(),
+        runtime.clone(),
+        searcher,
+        preempt_tx,
+        virtual_system_mapping().clone(),
+        Arc::new(NoOpUsageEventLogger),
+    )
+    .await?;
+    // The leader has already initialized the storage tag, so this only reads
+    // it.
+    let application_storage = Application::initialize_storage(
+        runtime.clone(),
+        &database,
+        config.storage_tag_initializer(),
+        config.name(),
+    )
+    .await?;
+
+    let file_storage = FileStorage {
+        transactional_file_storage: TransactionalFileStorage::new(
+            runtime.clone(),
+            application_storage.files_storage.clone(),
+            config.convex_origin_url()?,
+        ),
+        database: database.clone(),
+    };
+
+    let node_executor = make_node_executor(runtime.clone(), &config).await?;
+    let actions = Actions::new(
+        node_executor,
+        config.convex_origin_url()?,
+        *ACTION_USER_TIMEOUT,
+        runtime.clone(),
+    );
+    let fetch_client = Arc::new(ProxiedFetchClient::new(
+        config.convex_http_proxy.clone(),
+        config.name(),
+    ));
+    let function_runner: Arc<dyn FunctionRunner<ProdRuntime>> = Arc::new(
+        InProcessFunctionRunner::new(
+            config.name().clone(),
+            config.secret()?,
+            config.convex_origin_url()?,
+            runtime.clone(),
+            reader,
+            InstanceStorage {
+                files_storage: application_storage.files_storage.clone(),
+                modules_storage: application_storage.modules_storage.clone(),
+            },
+            database.clone(),
+            fetch_client,
+        )
+        .await?,
+    );
+
+    let application = Application::new_read_replica(
+        runtime.clone(),
+        database.clone(),
+        file_storage,
+        application_storage,
+        database.usage_counter(),
+        key_broker.clone(),
+        config.name(),
+        function_runner,
+        config.convex_origin_url()?,
+        config.convex_site_url()?,
+        actions,
+        Arc::new(NoopLogSender),
+        Arc::new(RedactLogsToClient::new(config.redact_logs_to_client)),
+        Arc::new(ApplicationAuth::new(
+            key_broker.clone(),
+            Arc::new(NullAccessTokenAuth),
+        )),
+        QueryCache::new(*UDF_CACHE_MAX_SIZE),
+    )
+    .await?;
+
+    tracing::info!("Serving as a read replica of {leader_url}");
+    let api = Arc::new(ReadReplicaApi::new(application.clone(), leader_url)?);
+    Ok(LocalAppState {
+        origin: config.convex_origin_url()?,
+        site_origin: config.convex_site_url()?,
+        instance_name: config.name(),
+        application,
+        api,
+        zombify_rx,
+    })
+}
+
 #[derive(Clone)]
 pub struct HttpActionRouteMapper;
 
@@ -0,0 +1,256 @@
+//! Streams structured log events, like deployment audit log events, to the log
+//! sinks configured in `_log_sinks`. Self-hosted backends deliver to local file
+//! and webhook sinks.
+
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::{
+    errors::report_error,
+    http::{
+        extract::Json,
+        HttpResponseError,
+    },
+    log_streaming::{
+        LogEvent,
+        LogEventFormatVersion,
+        LogSender,
+    },
+    runtime::Runtime,
+};
+use database::Database;
+use errors::ErrorMetadata;
+use http::StatusCode;
+use keybroker::Identity;
+use model::log_sinks::{
+    types::{
+        SerializedSinkConfig,
+        SinkConfig,
+        SinkState,
+        SinkType,
+    },
+    LogSinksModel,
+};
+use runtime::prod::ProdRuntime;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use tokio::{
+    fs::OpenOptions,
+    io::AsyncWriteExt,
+    sync::mpsc,
+};
+
+use crate::{
+    admin::must_be_admin_with_write_access,
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A [`LogSender`] that hands events to a background worker, which delivers
+/// them to every pending or active sink. A pending sink becomes active after
+/// its first successful delivery, or failed if that delivery fails.
+pub struct LocalLogSinkSender {
+    sender: mpsc::UnboundedSender<Vec<LogEvent>>,
+}
+
+impl LocalLogSinkSender {
+    pub fn start(runtime: ProdRuntime, database: Database<ProdRuntime>) -> anyhow::Result<Self> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+        runtime.spawn_background("log_sink_worker", Self::go(database, client, receiver));
+        Ok(Self { sender })
+    }
+
+    async fn go(
+        database: Database<ProdRuntime>,
+        client: reqwest::Client,
+        mut receiver: mpsc::UnboundedReceiver<Vec<LogEvent>>,
+    ) {
+        while let Some(mut logs) = receiver.recv().await {
+            while let Ok(more) = receiver.try_recv() {
+                logs.extend(more);
+            }
+            if let Err(mut e) = deliver(&database, &client, logs).await {
+                report_error(&mut e).await;
+            }
+        }
+    }
+}
+
+impl LogSender for LocalLogSinkSender {
+    fn send_logs(&self, logs: Vec<LogEvent>) {
+        if logs.is_empty() {
+            return;
+        }
+        // The receiver only goes away when the backend is shutting down.
+        let _ = self.sender.send(logs);
+    }
+
+    fn shutdown(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+async fn deliver(
+    database: &Database<ProdRuntime>,
+    client: &reqwest::Client,
+    logs: Vec<LogEvent>,
+) -> anyhow::Result<()> {
+    let sinks = {
+        let mut tx = database.begin(Identity::system()).await?;
+        LogSinksModel::new(&mut tx).get_all().await?
+    };
+    let lines: Vec<JsonValue> = logs
+        .iter()
+        .map(|event| {
+            anyhow::Ok(JsonValue::Object(
+                event.to_json_map(LogEventFormatVersion::default())?,
+            ))
+        })
+        .try_collect()?;
+
+    let mut activated = vec![];
+    let mut failed = vec![];
+    for sink in sinks {
+        if !matches!(sink.status, SinkState::Pending | SinkState::Active) {
+            continue;
+        }
+        let result = match &sink.config {
+            SinkConfig::Local(path) => write_to_file(path, &lines).await,
+            SinkConfig::Webhook(config) => post_to_webhook(client, &config.url, &lines).await,
+            config => Err(anyhow::anyhow!(
+                "{:?} sinks aren't supported by self-hosted backends",
+                config.sink_type()
+            )),
+        };
+        match result {
+            Ok(()) if sink.status == SinkState::Pending => activated.push(sink.id()),
+            Ok(()) => (),
+            Err(e) if sink.status == SinkState::Pending => failed.push((sink.id(), e)),
+            Err(e) => tracing::warn!("Failed to send logs to {}: {e:#}", sink.config),
+        }
+    }
+    if activated.is_empty() && failed.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = database.begin(Identity::system()).await?;
+    // Only update sinks that are still pending, so we don't resurrect a sink
+    // that was removed in the meantime.
+    let still_pending: Vec<_> = LogSinksModel::new(&mut tx)
+        .get_all()
+        .await?
+        .into_iter()
+        .filter(|sink| sink.status == SinkState::Pending)
+        .map(|sink| sink.id())
+        .collect();
+    for id in activated {
+        if still_pending.contains(&id) {
+            LogSinksModel::new(&mut tx)
+                .patch_status(id, SinkState::Active)
+                .await?;
+        }
+    }
+    for (id, e) in failed {
+        if still_pending.contains(&id) {
+            LogSinksModel::new(&mut tx)
+                .patch_status(
+                    id,
+                    SinkState::Failed {
+                        reason: format!("{e:#}"),
+                    },
+                )
+                .await?;
+        }
+    }
+    database
+        .commit_with_write_source(tx, "log_sink_status")
+        .await?;
+    Ok(())
+}
+
+async fn write_to_file(path: &str, lines: &[JsonValue]) -> anyhow::Result<()> {
+    let mut buf = vec![];
+    for line in lines {
+        serde_json::to_writer(&mut buf, line)?;
+        buf.push(b'\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(&buf).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+async fn post_to_webhook(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    lines: &[JsonValue],
+) -> anyhow::Result<()> {
+    client
+        .post(url.clone())
+        .json(lines)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+pub async fn add_log_sink(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(config): Json<SerializedSinkConfig>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let config = SinkConfig::try_from(config).map_err(|e| {
+        anyhow::anyhow!(ErrorMetadata::bad_request(
+            "InvalidLogSinkConfig",
+            format!("Invalid log sink config: {e}"),
+        ))
+    })?;
+    if !matches!(config, SinkConfig::Local(_) | SinkConfig::Webhook(_)) {
+        return Err(anyhow::anyhow!(ErrorMetadata::bad_request(
+            "UnsupportedLogSink",
+            format!(
+                "{:?} sinks aren't supported by self-hosted backends",
+                config.sink_type()
+            ),
+        ))
+        .into());
+    }
+    let mut tx = st.application.begin(identity).await?;
+    LogSinksModel::new(&mut tx).add_or_update(config).await?;
+    st.application.commit(tx, "add_log_sink").await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveLogSinkArgs {
+    sink_type: SinkType,
+}
+
+pub async fn remove_log_sink(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(RemoveLogSinkArgs { sink_type }): Json<RemoveLogSinkArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let mut tx = st.application.begin(identity).await?;
+    let mut model = LogSinksModel::new(&mut tx);
+    if let Some(sink) = model.get_by_provider(sink_type).await? {
+        model.mark_for_removal(sink.id()).await?;
+    }
+    st.application.commit(tx, "remove_log_sink").await?;
+    Ok(StatusCode::OK)
+}
@@ -1,10 +1,18 @@
 use std::time::Duration;
 
 use anyhow::Context;
-use application::function_log::{
-    FunctionExecution,
-    FunctionExecutionPart,
-    UdfParams,
+use application::{
+    function_log::{
+        FunctionExecution,
+        FunctionExecutionPart,
+        UdfParams,
+    },
+    function_log_store::{
+        ExecutionStatus,
+        FunctionLogFilter,
+        StoredFunctionExecution,
+        MAX_FUNCTION_LOG_QUERY_LIMIT,
+    },
 };
 use axum::{
     extract::State,
@@ -230,6 +238,62 @@ pub async fn stream_function_logs(
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryFunctionLogsArgs {
+    /// Seconds since the Unix epoch, inclusive.
+    start: Option<f64>,
+    /// Seconds since the Unix epoch, exclusive.
+    end: Option<f64>,
+    component_path: Option<String>,
+    identifier: Option<String>,
+    request_id: Option<String>,
+    status: Option<ExecutionStatus>,
+    text: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryFunctionLogsResponse {
+    entries: Vec<StoredFunctionExecution>,
+}
+
+/// Search function executions in the durable function log, newest first.
+pub async fn query_function_logs(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Query(QueryFunctionLogsArgs {
+        start,
+        end,
+        component_path,
+        identifier,
+        request_id,
+        status,
+        text,
+        limit,
+    }): Query<QueryFunctionLogsArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    let filter = FunctionLogFilter {
+        start,
+        end,
+        component_path,
+        identifier,
+        request_id,
+        status,
+        text,
+    };
+    let entries = st
+        .application
+        .query_function_log(
+            identity,
+            filter,
+            limit.unwrap_or(MAX_FUNCTION_LOG_QUERY_LIMIT),
+        )
+        .await?;
+    Ok(Json(QueryFunctionLogsResponse { entries }))
+}
+
 fn execution_to_json(
     execution: FunctionExecution,
     supports_structured_log_lines: bool,
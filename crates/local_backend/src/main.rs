@@ -11,7 +11,10 @@ use common::{
     shutdown::ShutdownSignal,
     version::SERVER_VERSION_STR,
 };
-use db_connection::connect_persistence;
+use db_connection::{
+    connect_persistence,
+    connect_persistence_reader,
+};
 use futures::{
     future::{
         self,
@@ -22,8 +25,13 @@ use futures::{
 use local_backend::{
     config::LocalConfig,
     make_app,
+    make_read_replica_app,
     proxy::dev_site_proxy,
     router::router,
+    tracing_export::{
+        flush_spans,
+        install_otlp_reporter,
+    },
     HttpActionRouteMapper,
     MAX_CONCURRENT_REQUESTS,
 };
@@ -98,29 +106,59 @@ async fn run_server(runtime: ProdRuntime, config: LocalConfig) -> anyhow::Result
 }
 
 async fn run_server_inner(runtime: ProdRuntime, config: LocalConfig) -> anyhow::Result<()> {
+    if let Some(ref otlp_endpoint) = config.otlp_endpoint {
+        install_otlp_reporter(
+            otlp_endpoint,
+            config.otlp_protocol,
+            config.otlp_service_name.clone(),
+        )?;
+    }
     // Used to receive fatal errors from the database or /preempt endpoint.
     let (preempt_tx, preempt_rx) = oneshot::channel();
     let preempt_signal = ShutdownSignal::new(preempt_tx);
     // Use to signal to the http service to stop.
     let (shutdown_tx, shutdown_rx) = async_broadcast::broadcast(1);
-    let persistence = connect_persistence(
-        config.db,
-        &config.db_spec,
-        !config.do_not_require_ssl,
-        false, /* allow_read_only */
-        &config.name(),
-        runtime.clone(),
-        preempt_signal.clone(),
-    )
-    .await?;
-    let st = make_app(
-        runtime.clone(),
-        config.clone(),
-        persistence,
-        shutdown_rx.clone(),
-        preempt_signal.clone(),
-    )
-    .await?;
+    let st = if let Some(leader_url) = config.leader_url.clone() {
+        // Read replicas don't take the persistence lease, so they can run
+        // alongside the leader.
+        let reader = connect_persistence_reader(
+            config.db,
+            &config.db_spec,
+            !config.do_not_require_ssl,
+            true, /* db_should_be_leader */
+            &config.name(),
+            runtime.clone(),
+        )
+        .await?;
+        make_read_replica_app(
+            runtime.clone(),
+            config.clone(),
+            leader_url,
+            reader,
+            shutdown_rx.clone(),
+            preempt_signal.clone(),
+        )
+        .await?
+    } else {
+        let persistence = connect_persistence(
+            config.db,
+            &config.db_spec,
+            !config.do_not_require_ssl,
+            false, /* allow_read_only */
+            &config.name(),
+            runtime.clone(),
+            preempt_signal.clone(),
+        )
+        .await?;
+        make_app(
+            runtime.clone(),
+            config.clone(),
+            persistence,
+            shutdown_rx.clone(),
+            preempt_signal.clone(),
+        )
+        .await?
+    };
     let router = router(st.clone());
     let mut shutdown_rx_ = shutdown_rx.clone();
     let http_service = ConvexHttpService::new(
@@ -173,6 +211,10 @@ async fn run_server_inner(runtime: ProdRuntime, config: LocalConfig) -> anyhow::
         tracing::info!("Shutting down application...");
         st.shutdown().await?;
 
+        if config.otlp_endpoint.is_some() {
+            flush_spans().await?;
+        }
+
         Ok::<_, anyhow::Error>(())
     }
     .fuse();
@@ -1,18 +1,29 @@
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+};
+
 use application::{
     api::ExecuteQueryTimestamp,
+    rate_limiter::RateLimitCaller,
     redaction::{
         RedactedJsError,
         RedactedLogLines,
     },
 };
 use axum::{
-    extract::State,
+    extract::{
+        ConnectInfo,
+        FromRequestParts,
+        State,
+    },
     response::IntoResponse,
 };
 use common::{
     components::{
         CanonicalizedComponentFunctionPath,
         ComponentPath,
+        ExportPath,
     },
     http::{
         extract::{
@@ -25,11 +36,17 @@ use common::{
         ExtractResolvedHostname,
         HttpResponseError,
     },
-    types::FunctionCaller,
+    knobs::RATE_LIMIT_TRUST_FORWARDED_FOR,
+    types::{
+        FunctionCaller,
+        UdfType,
+    },
     version::ClientVersion,
 };
 use errors::ErrorMetadata;
+use http::HeaderName;
 use isolate::UdfArgsJson;
+use model::session_requests::types::SessionRequestIdentifier;
 use serde::{
     Deserialize,
     Serialize,
@@ -51,6 +68,84 @@ use crate::{
     RouterState,
 };
 
+/// What rate limit policies can count a request by: the client's IP address
+/// and the request headers.
+pub struct ExtractRateLimitCaller(pub RateLimitCaller);
+
+impl<S: Sync> FromRequestParts<S> for ExtractRateLimitCaller {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _st: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let forwarded_ip = if *RATE_LIMIT_TRUST_FORWARDED_FOR {
+            parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|h| h.split(',').next())
+                .and_then(|ip| ip.trim().parse().ok())
+        } else {
+            None
+        };
+        let ip = forwarded_ip.or_else(|| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip())
+        });
+        Ok(Self(RateLimitCaller {
+            ip,
+            headers: parts.headers.clone(),
+        }))
+    }
+}
+
+/// Carries a WebSocket mutation's [`SessionRequestIdentifier`] when a read
+/// replica forwards it, so the leader still runs it at most once. Any caller
+/// can set it, so the leader only returns a stored result to the same identity
+/// calling the same function.
+#[allow(clippy::declare_interior_mutable_const)]
+pub const CONVEX_MUTATION_IDENTIFIER_HEADER: HeaderName =
+    HeaderName::from_static("convex-mutation-identifier");
+
+pub fn mutation_identifier_header_value(identifier: &SessionRequestIdentifier) -> String {
+    format!("{}/{}", *identifier.session_id, identifier.request_id)
+}
+
+pub struct ExtractMutationIdentifier(pub Option<SessionRequestIdentifier>);
+
+impl<S: Sync> FromRequestParts<S> for ExtractMutationIdentifier {
+    type Rejection = HttpResponseError;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _st: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(CONVEX_MUTATION_IDENTIFIER_HEADER) else {
+            return Ok(Self(None));
+        };
+        let identifier = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.split_once('/'))
+            .and_then(|(session_id, request_id)| {
+                Some(SessionRequestIdentifier {
+                    session_id: session_id.parse().ok()?,
+                    request_id: request_id.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(ErrorMetadata::bad_request(
+                    "InvalidMutationIdentifier",
+                    "Invalid Convex-Mutation-Identifier header",
+                ))
+            })?;
+        Ok(Self(Some(identifier)))
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UdfPostRequest {
@@ -187,6 +282,8 @@ pub async fn public_function_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
+    ExtractMutationIdentifier(mutation_identifier): ExtractMutationIdentifier,
     Json(req): Json<UdfPostRequestWithComponent>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     // NOTE: We could coalesce authenticating and executing the query into one
@@ -204,6 +301,18 @@ pub async fn public_function_post(
         component,
         udf_path,
     };
+    if component_function_path.component.is_root() {
+        st.api
+            .check_rate_limits(
+                &host,
+                request_id.clone(),
+                &identity,
+                &ExportPath::from(component_function_path.udf_path.clone()),
+                None,
+                &rate_limit_caller,
+            )
+            .await?;
+    }
     let udf_result = st
         .api
         .execute_any_function(
@@ -213,6 +322,8 @@ pub async fn public_function_post(
             component_function_path,
             req.args.into_arg_vec(),
             FunctionCaller::HttpApi(client_version.clone()),
+            &rate_limit_caller,
+            mutation_identifier,
         )
         .await?;
     let value_format = req.format.as_ref().map(|f| f.parse()).transpose()?;
@@ -247,6 +358,8 @@ pub async fn public_function_post_with_path(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
+    ExtractMutationIdentifier(mutation_identifier): ExtractMutationIdentifier,
     Json(req): Json<UdfPostRequestArgsOnly>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     // NOTE: We could coalesce authenticating and executing the query into one
@@ -277,6 +390,16 @@ pub async fn public_function_post_with_path(
     let function_name = path_parts.pop().ok_or_else(bad_request_error)?;
     let udf_path_str = format!("{}:{}", path_parts.join("/"), function_name);
     let udf_path = parse_udf_path(&udf_path_str)?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &ExportPath::from(udf_path.clone()),
+            None,
+            &rate_limit_caller,
+        )
+        .await?;
     let udf_result = st
         .api
         .execute_any_function(
@@ -290,6 +413,8 @@ pub async fn public_function_post_with_path(
             },
             req.args.into_arg_vec(),
             FunctionCaller::HttpApi(client_version.clone()),
+            &rate_limit_caller,
+            mutation_identifier,
         )
         .await?;
     // Default to ConvexCleanJSON if no format is provided.
@@ -333,6 +458,7 @@ pub async fn public_query_get(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let export_path = parse_export_path(&req.path)?;
     let args = req.args.into_arg_vec();
@@ -345,6 +471,16 @@ pub async fn public_query_get(
         .api
         .authenticate(&host, request_id.clone(), auth_token)
         .await?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &export_path,
+            Some(UdfType::Query),
+            &rate_limit_caller,
+        )
+        .await?;
     let query_result = st
         .api
         .execute_public_query(
@@ -377,6 +513,7 @@ pub async fn public_query_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
     Json(req): Json<UdfPostRequest>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let udf_path = parse_export_path(&req.path)?;
@@ -389,6 +526,16 @@ pub async fn public_query_post(
         .api
         .authenticate(&host, request_id.clone(), auth_token)
         .await?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &udf_path,
+            Some(UdfType::Query),
+            &rate_limit_caller,
+        )
+        .await?;
     let query_return = st
         .api
         .execute_public_query(
@@ -431,6 +578,7 @@ pub async fn public_query_at_ts_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
     Json(req): Json<UdfPostWithTsRequest>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let export_path = parse_export_path(&req.path)?;
@@ -443,6 +591,16 @@ pub async fn public_query_at_ts_post(
         .api
         .authenticate(&host, request_id.clone(), auth_token)
         .await?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &export_path,
+            Some(UdfType::Query),
+            &rate_limit_caller,
+        )
+        .await?;
     let ts = Timestamp::try_from(req.ts)?;
     let query_return = st
         .api
@@ -486,6 +644,7 @@ pub async fn public_query_batch_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
     Json(req_batch): Json<QueryBatchArgs>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let mut results = vec![];
@@ -498,6 +657,16 @@ pub async fn public_query_batch_post(
     for req in req_batch.queries {
         let value_format = req.format.as_ref().map(|f| f.parse()).transpose()?;
         let export_path = parse_export_path(&req.path)?;
+        st.api
+            .check_rate_limits(
+                &host,
+                request_id.clone(),
+                &identity,
+                &export_path,
+                Some(UdfType::Query),
+                &rate_limit_caller,
+            )
+            .await?;
         let udf_return = st
             .api
             .execute_public_query(
@@ -535,6 +704,8 @@ pub async fn public_mutation_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
+    ExtractMutationIdentifier(mutation_identifier): ExtractMutationIdentifier,
     Json(req): Json<UdfPostRequest>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let export_path = parse_export_path(&req.path)?;
@@ -546,6 +717,16 @@ pub async fn public_mutation_post(
         .api
         .authenticate(&host, request_id.clone(), auth_token)
         .await?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &export_path,
+            Some(UdfType::Mutation),
+            &rate_limit_caller,
+        )
+        .await?;
     let udf_result = st
         .api
         .execute_public_mutation(
@@ -555,7 +736,8 @@ pub async fn public_mutation_post(
             export_path,
             req.args.into_arg_vec(),
             FunctionCaller::HttpApi(client_version.clone()),
-            None,
+            &rate_limit_caller,
+            mutation_identifier,
             None,
         )
         .await?;
@@ -582,6 +764,7 @@ pub async fn public_action_post(
     ExtractRequestId(request_id): ExtractRequestId,
     ExtractAuthenticationToken(auth_token): ExtractAuthenticationToken,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
     Json(req): Json<UdfPostRequest>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
     let export_path = parse_export_path(&req.path)?;
@@ -594,6 +777,16 @@ pub async fn public_action_post(
         .api
         .authenticate(&host, request_id.clone(), auth_token)
         .await?;
+    st.api
+        .check_rate_limits(
+            &host,
+            request_id.clone(),
+            &identity,
+            &export_path,
+            Some(UdfType::Action),
+            &rate_limit_caller,
+        )
+        .await?;
     let action_result = st
         .api
         .execute_public_action(
@@ -603,6 +796,7 @@ pub async fn public_action_post(
             export_path,
             req.args.into_arg_vec(),
             FunctionCaller::HttpApi(client_version.clone()),
+            &rate_limit_caller,
         )
         .await?;
     let value_format = req.format.as_ref().map(|f| f.parse()).transpose()?;
@@ -635,6 +829,7 @@ mod tests {
         Value as JsonValue,
     };
 
+    use super::CONVEX_MUTATION_IDENTIFIER_HEADER;
     use crate::test_helpers::setup_backend_for_test;
 
     async fn http_format_tester(
@@ -787,4 +982,53 @@ mod tests {
         )
         .await
     }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_http_mutation_identifier_deduplicates(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        backend.st.application.load_udf_tests_modules().await?;
+        let mutation = |path: &'static str,
+                        identifier: Option<&'static str>|
+         -> anyhow::Result<Request<Body>> {
+            let body = Body::from(serde_json::to_vec(&json!({
+                "path": path,
+                "args": {},
+            }))?);
+            let mut req = Request::builder()
+                .uri("/api/mutation")
+                .method("POST")
+                .header("Content-Type", "application/json")
+                .header("Host", "localhost");
+            if let Some(identifier) = identifier {
+                req = req.header(CONVEX_MUTATION_IDENTIFIER_HEADER, identifier);
+            }
+            Ok(req.body(body)?)
+        };
+        let insert = |identifier| mutation("basic:insertObject", identifier);
+        let identifier = "00000000-0000-0000-0000-000000000001/7";
+        let first: JsonValue = backend.expect_success(insert(Some(identifier))?).await?;
+        let retried: JsonValue = backend.expect_success(insert(Some(identifier))?).await?;
+        assert_eq!(first, retried);
+        let other: JsonValue = backend.expect_success(insert(None)?).await?;
+        assert_ne!(first["value"]["_id"], other["value"]["_id"]);
+
+        // Reusing the identifier for another function doesn't return the
+        // stored result.
+        backend
+            .expect_error(
+                mutation("basic:simpleMutation", Some(identifier))?,
+                StatusCode::BAD_REQUEST,
+                "MutationIdentifierMismatch",
+            )
+            .await?;
+
+        backend
+            .expect_error(
+                insert(Some("not-an-identifier"))?,
+                StatusCode::BAD_REQUEST,
+                "InvalidMutationIdentifier",
+            )
+            .await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,660 @@
+//! Support for running `local_backend` as a read replica.
+//!
+//! A read replica opens the leader's persistence with a read-only connection
+//! and follows its document log (see `Database::load_follower`). Queries,
+//! subscriptions and file reads are served locally, while mutations and
+//! actions are forwarded to the leader's HTTP API.
+//!
+//! Rate limits are counted where a call executes. Forwarded calls carry the
+//! caller's IP address in `X-Forwarded-For`, so the leader needs
+//! `RATE_LIMIT_TRUST_FORWARDED_FOR` for IP keyed policies.
+use std::{
+    ops::Bound,
+    time::Duration,
+};
+
+use anyhow::Context;
+use application::{
+    api::{
+        ApplicationApi,
+        ExecuteQueryTimestamp,
+        SubscriptionClient,
+    },
+    rate_limiter::RateLimitCaller,
+    redaction::{
+        RedactedJsError,
+        RedactedLogLines,
+    },
+    Application,
+    FunctionError,
+    FunctionReturn,
+    RedactedActionError,
+    RedactedActionReturn,
+    RedactedMutationError,
+    RedactedMutationReturn,
+    RedactedQueryReturn,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::{
+    components::{
+        CanonicalizedComponentFunctionPath,
+        ComponentId,
+        ExportPath,
+    },
+    errors::JsError,
+    http::{
+        HttpError,
+        ResolvedHostname,
+        CONVEX_REQUEST_ID_HEADER,
+    },
+    types::{
+        ConvexOrigin,
+        FunctionCaller,
+        RepeatableTimestamp,
+        UdfType,
+    },
+    RequestId,
+};
+use errors::ErrorMetadata;
+use file_storage::{
+    FileRangeStream,
+    FileStream,
+};
+use futures::stream::BoxStream;
+use headers::{
+    ContentLength,
+    ContentType,
+};
+use http::StatusCode;
+use keybroker::Identity;
+use model::{
+    file_storage::FileStorageId,
+    session_requests::types::SessionRequestIdentifier,
+};
+use runtime::prod::ProdRuntime;
+use serde::Deserialize;
+use serde_json::{
+    json,
+    Value as JsonValue,
+};
+use sync_types::{
+    AuthenticationToken,
+    SerializedQueryJournal,
+    Timestamp,
+};
+use udf::{
+    HttpActionRequest,
+    HttpActionResponseStreamer,
+};
+use url::Url;
+use value::{
+    sha256::Sha256Digest,
+    ConvexValue,
+    DeveloperDocumentId,
+    JsonPackedValue,
+};
+
+use crate::public_api::{
+    mutation_identifier_header_value,
+    SerializedTs,
+    UdfResponse,
+    CONVEX_MUTATION_IDENTIFIER_HEADER,
+};
+
+/// Timeout for requests forwarded to the leader. This is slightly longer than
+/// the backend's own request timeout so the leader reports its own timeouts.
+const LEADER_REQUEST_TIMEOUT: Duration = Duration::from_secs(130);
+
+/// Implements [`ApplicationApi`] for a read replica: reads go to the local
+/// [`Application`] and writes are forwarded to the leader.
+pub struct ReadReplicaApi {
+    application: Application<ProdRuntime>,
+    leader_url: Url,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct LeaderTs {
+    ts: SerializedTs,
+}
+
+enum ForwardedResult {
+    Success {
+        value: JsonPackedValue,
+        log_lines: RedactedLogLines,
+    },
+    Error {
+        error: RedactedJsError,
+        log_lines: RedactedLogLines,
+    },
+}
+
+impl ReadReplicaApi {
+    pub fn new(application: Application<ProdRuntime>, leader_url: Url) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(LEADER_REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            application,
+            leader_url,
+            client,
+        })
+    }
+
+    fn writes_not_supported(operation: &str) -> anyhow::Error {
+        anyhow::anyhow!(ErrorMetadata::bad_request(
+            "ReadReplicaWriteError",
+            format!(
+                "{operation} is not supported on a read replica. Send this request to the leader \
+                 backend instead."
+            ),
+        ))
+    }
+
+    fn authorization_header(identity: Identity) -> anyhow::Result<Option<String>> {
+        let header = match AuthenticationToken::from(identity) {
+            AuthenticationToken::Admin(key, None) => Some(format!("Convex {key}")),
+            AuthenticationToken::Admin(key, Some(acting_user)) => {
+                let acting_user = serde_json::to_vec(&JsonValue::try_from(acting_user)?)?;
+                Some(format!("Convex {key}:{}", base64::encode(acting_user)))
+            },
+            AuthenticationToken::User(token) => Some(format!("Bearer {token}")),
+            AuthenticationToken::None => None,
+        };
+        Ok(header)
+    }
+
+    /// Run a function on the leader through its HTTP API, reconstructing the
+    /// redacted result the leader returned.
+    async fn forward_to_leader(
+        &self,
+        request_id: RequestId,
+        identity: Identity,
+        rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<&SessionRequestIdentifier>,
+        route: &str,
+        body: JsonValue,
+    ) -> anyhow::Result<ForwardedResult> {
+        let url = self.leader_url.join(route)?;
+        let mut request = self
+            .client
+            .post(url)
+            .header(CONVEX_REQUEST_ID_HEADER, request_id.as_str())
+            .json(&body);
+        if let Some(authorization) = Self::authorization_header(identity)? {
+            request = request.header(http::header::AUTHORIZATION, authorization);
+        }
+        if let Some(identifier) = mutation_identifier {
+            request = request.header(
+                CONVEX_MUTATION_IDENTIFIER_HEADER,
+                mutation_identifier_header_value(identifier),
+            );
+        }
+        // Pass along what the leader's rate limits count the call by.
+        if let Some(ip) = rate_limit_caller.ip {
+            request = request.header("x-forwarded-for", ip.to_string());
+        }
+        for name in self.application.rate_limit_header_names().await? {
+            // The leader already gets the caller's credentials from the
+            // `Authorization` header above.
+            if name == http::header::AUTHORIZATION.as_str() {
+                continue;
+            }
+            if let Some(value) = rate_limit_caller.headers.get(name.as_str()) {
+                request = request.header(name.as_str(), value.clone());
+            }
+        }
+        let response = request
+            .send()
+            .await
+            .context("Failed to forward request to the leader")?;
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(http::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs);
+            let body = response.bytes().await.unwrap_or_default();
+            return Err(Self::leader_error(status, retry_after, &body));
+        }
+        let result = match response.json::<UdfResponse>().await? {
+            UdfResponse::Success { value, log_lines } => ForwardedResult::Success {
+                value: JsonPackedValue::pack(ConvexValue::try_from(value)?),
+                log_lines,
+            },
+            UdfResponse::Error {
+                error_message,
+                error_data,
+                log_lines,
+            } => ForwardedResult::Error {
+                error: Self::parse_leader_error(request_id, error_message, error_data)?,
+                log_lines,
+            },
+        };
+        Ok(result)
+    }
+
+    /// An error that responds to the client with the leader's status and error
+    /// body, tagged so the replica classifies it like the leader did.
+    fn leader_error(
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        body: &[u8],
+    ) -> anyhow::Error {
+        let (code, message) = match HttpError::error_message_from_bytes(body) {
+            Ok((code, message)) => (code, message),
+            Err(_) => (
+                status.canonical_reason().unwrap_or_default().into(),
+                String::from_utf8_lossy(body).into_owned().into(),
+            ),
+        };
+        let http_error = HttpError::new(status, code.clone(), message.clone());
+        let metadata = match retry_after {
+            Some(retry_after) if status == StatusCode::TOO_MANY_REQUESTS => Some(
+                ErrorMetadata::rate_limited_with_retry_after(code, message, retry_after),
+            ),
+            _ => ErrorMetadata::from_http_status_code(status, code, message),
+        };
+        let error = anyhow::Error::new(http_error);
+        match metadata {
+            Some(metadata) => error.context(metadata),
+            None => error,
+        }
+    }
+
+    /// The leader formats errors as `[Request ID: ..] Server Error` followed by
+    /// the underlying error on the next line unless logging is blocked. Strip
+    /// that prefix so the error isn't redacted twice.
+    fn parse_leader_error(
+        request_id: RequestId,
+        error_message: String,
+        error_data: Option<JsonValue>,
+    ) -> anyhow::Result<RedactedJsError> {
+        let (message, block_logging) = if let Some((prefix, message)) =
+            error_message.split_once('\n')
+            && prefix.starts_with("[Request ID:")
+        {
+            (message.to_string(), false)
+        } else {
+            let block_logging = error_message.starts_with("[Request ID:");
+            (error_message, block_logging)
+        };
+        let error = match error_data {
+            Some(data) => JsError::convex_error(message, ConvexValue::try_from(data)?),
+            None => JsError::from_message(message),
+        };
+        Ok(RedactedJsError::from_js_error(
+            error,
+            block_logging,
+            request_id,
+        ))
+    }
+
+    /// The leader's latest timestamp, which is at least the commit timestamp
+    /// of any mutation it has already returned.
+    async fn leader_ts(&self, request_id: RequestId) -> anyhow::Result<Timestamp> {
+        let url = self.leader_url.join("/api/query_ts")?;
+        let response = self
+            .client
+            .post(url)
+            .header(CONVEX_REQUEST_ID_HEADER, request_id.as_str())
+            .send()
+            .await
+            .context("Failed to fetch the leader's timestamp")?
+            .error_for_status()?;
+        let LeaderTs { ts } = response.json().await?;
+        ts.try_into()
+    }
+
+    async fn forward_mutation(
+        &self,
+        request_id: RequestId,
+        identity: Identity,
+        rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
+        route: &str,
+        body: JsonValue,
+    ) -> anyhow::Result<Result<RedactedMutationReturn, RedactedMutationError>> {
+        let result = match self
+            .forward_to_leader(
+                request_id.clone(),
+                identity,
+                rate_limit_caller,
+                mutation_identifier.as_ref(),
+                route,
+                body,
+            )
+            .await?
+        {
+            ForwardedResult::Success { value, log_lines } => Ok(RedactedMutationReturn {
+                value,
+                log_lines,
+                ts: self.leader_ts(request_id).await?,
+            }),
+            ForwardedResult::Error { error, log_lines } => {
+                Err(RedactedMutationError { error, log_lines })
+            },
+        };
+        Ok(result)
+    }
+
+    async fn forward_action(
+        &self,
+        request_id: RequestId,
+        identity: Identity,
+        rate_limit_caller: &RateLimitCaller,
+        route: &str,
+        body: JsonValue,
+    ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
+        let result = match self
+            .forward_to_leader(request_id, identity, rate_limit_caller, None, route, body)
+            .await?
+        {
+            ForwardedResult::Success { value, log_lines } => {
+                Ok(RedactedActionReturn { value, log_lines })
+            },
+            ForwardedResult::Error { error, log_lines } => {
+                Err(RedactedActionError { error, log_lines })
+            },
+        };
+        Ok(result)
+    }
+
+    fn public_body(path: ExportPath, args: Vec<JsonValue>) -> JsonValue {
+        json!({
+            "path": String::from(path),
+            "args": args,
+            "format": "convex_encoded_json",
+        })
+    }
+
+    fn component_body(path: CanonicalizedComponentFunctionPath, args: Vec<JsonValue>) -> JsonValue {
+        json!({
+            "componentPath": path.component.serialize(),
+            "path": path.udf_path.to_string(),
+            "args": args,
+            "format": "convex_encoded_json",
+        })
+    }
+}
+
+#[async_trait]
+impl ApplicationApi for ReadReplicaApi {
+    async fn authenticate(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        auth_token: AuthenticationToken,
+    ) -> anyhow::Result<Identity> {
+        ApplicationApi::authenticate(&self.application, host, request_id, auth_token).await
+    }
+
+    async fn check_rate_limits(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: &Identity,
+        path: &ExportPath,
+        udf_type: Option<UdfType>,
+        caller: &RateLimitCaller,
+    ) -> anyhow::Result<()> {
+        // Everything but queries runs on the leader, which counts the call
+        // itself.
+        if udf_type != Some(UdfType::Query) {
+            return Ok(());
+        }
+        ApplicationApi::check_rate_limits(
+            &self.application,
+            host,
+            request_id,
+            identity,
+            path,
+            udf_type,
+            caller,
+        )
+        .await
+    }
+
+    async fn execute_public_query(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: ExportPath,
+        args: Vec<JsonValue>,
+        caller: FunctionCaller,
+        ts: ExecuteQueryTimestamp,
+        journal: Option<SerializedQueryJournal>,
+    ) -> anyhow::Result<RedactedQueryReturn> {
+        self.application
+            .execute_public_query(host, request_id, identity, path, args, caller, ts, journal)
+            .await
+    }
+
+    async fn execute_admin_query(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: CanonicalizedComponentFunctionPath,
+        args: Vec<JsonValue>,
+        caller: FunctionCaller,
+        ts: ExecuteQueryTimestamp,
+        journal: Option<SerializedQueryJournal>,
+    ) -> anyhow::Result<RedactedQueryReturn> {
+        self.application
+            .execute_admin_query(host, request_id, identity, path, args, caller, ts, journal)
+            .await
+    }
+
+    async fn execute_public_mutation(
+        &self,
+        _host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: ExportPath,
+        args: Vec<JsonValue>,
+        _caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
+        _mutation_queue_length: Option<usize>,
+    ) -> anyhow::Result<Result<RedactedMutationReturn, RedactedMutationError>> {
+        self.forward_mutation(
+            request_id,
+            identity,
+            rate_limit_caller,
+            mutation_identifier,
+            "/api/mutation",
+            Self::public_body(path, args),
+        )
+        .await
+    }
+
+    async fn execute_admin_mutation(
+        &self,
+        _host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: CanonicalizedComponentFunctionPath,
+        args: Vec<JsonValue>,
+        _caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
+        _mutation_queue_length: Option<usize>,
+    ) -> anyhow::Result<Result<RedactedMutationReturn, RedactedMutationError>> {
+        self.forward_mutation(
+            request_id,
+            identity,
+            rate_limit_caller,
+            mutation_identifier,
+            "/api/function",
+            Self::component_body(path, args),
+        )
+        .await
+    }
+
+    async fn execute_public_action(
+        &self,
+        _host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: ExportPath,
+        args: Vec<JsonValue>,
+        _caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+    ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
+        self.forward_action(
+            request_id,
+            identity,
+            rate_limit_caller,
+            "/api/action",
+            Self::public_body(path, args),
+        )
+        .await
+    }
+
+    async fn execute_admin_action(
+        &self,
+        _host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: CanonicalizedComponentFunctionPath,
+        args: Vec<JsonValue>,
+        _caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+    ) -> anyhow::Result<Result<RedactedActionReturn, RedactedActionError>> {
+        self.forward_action(
+            request_id,
+            identity,
+            rate_limit_caller,
+            "/api/function",
+            Self::component_body(path, args),
+        )
+        .await
+    }
+
+    async fn execute_http_action(
+        &self,
+        _host: &ResolvedHostname,
+        _request_id: RequestId,
+        _http_request_metadata: HttpActionRequest,
+        _identity: Identity,
+        _caller: FunctionCaller,
+        _response_streamer: HttpActionResponseStreamer,
+    ) -> anyhow::Result<()> {
+        Err(Self::writes_not_supported("Running HTTP actions"))
+    }
+
+    async fn execute_any_function(
+        &self,
+        _host: &ResolvedHostname,
+        request_id: RequestId,
+        identity: Identity,
+        path: CanonicalizedComponentFunctionPath,
+        args: Vec<JsonValue>,
+        _caller: FunctionCaller,
+        rate_limit_caller: &RateLimitCaller,
+        mutation_identifier: Option<SessionRequestIdentifier>,
+    ) -> anyhow::Result<Result<FunctionReturn, FunctionError>> {
+        // We don't know the function's type without analyzing it, so let the
+        // leader run it.
+        let result = match self
+            .forward_to_leader(
+                request_id,
+                identity,
+                rate_limit_caller,
+                mutation_identifier.as_ref(),
+                "/api/function",
+                Self::component_body(path, args),
+            )
+            .await?
+        {
+            ForwardedResult::Success { value, log_lines } => {
+                Ok(FunctionReturn { value, log_lines })
+            },
+            ForwardedResult::Error { error, log_lines } => Err(FunctionError { error, log_lines }),
+        };
+        Ok(result)
+    }
+
+    async fn latest_timestamp(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+    ) -> anyhow::Result<RepeatableTimestamp> {
+        ApplicationApi::latest_timestamp(&self.application, host, request_id).await
+    }
+
+    async fn check_store_file_authorization(
+        &self,
+        _host: &ResolvedHostname,
+        _request_id: RequestId,
+        _token: &str,
+        _validity: Duration,
+    ) -> anyhow::Result<ComponentId> {
+        Err(Self::writes_not_supported("Uploading files"))
+    }
+
+    async fn store_file(
+        &self,
+        _host: &ResolvedHostname,
+        _request_id: RequestId,
+        _origin: ConvexOrigin,
+        _component: ComponentId,
+        _content_length: Option<ContentLength>,
+        _content_type: Option<ContentType>,
+        _expected_sha256: Option<Sha256Digest>,
+        _body: BoxStream<'_, anyhow::Result<Bytes>>,
+    ) -> anyhow::Result<DeveloperDocumentId> {
+        Err(Self::writes_not_supported("Uploading files"))
+    }
+
+    async fn get_file_range(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        origin: ConvexOrigin,
+        component: ComponentId,
+        file_storage_id: FileStorageId,
+        range: (Bound<u64>, Bound<u64>),
+    ) -> anyhow::Result<FileRangeStream> {
+        ApplicationApi::get_file_range(
+            &self.application,
+            host,
+            request_id,
+            origin,
+            component,
+            file_storage_id,
+            range,
+        )
+        .await
+    }
+
+    async fn get_file(
+        &self,
+        host: &ResolvedHostname,
+        request_id: RequestId,
+        origin: ConvexOrigin,
+        component: ComponentId,
+        file_storage_id: FileStorageId,
+    ) -> anyhow::Result<FileStream> {
+        ApplicationApi::get_file(
+            &self.application,
+            host,
+            request_id,
+            origin,
+            component,
+            file_storage_id,
+        )
+        .await
+    }
+
+    async fn subscription_client(
+        &self,
+        host: &ResolvedHostname,
+    ) -> anyhow::Result<Box<dyn SubscriptionClient>> {
+        ApplicationApi::subscription_client(&self.application, host).await
+    }
+}
@@ -1,6 +1,5 @@
 use std::{
     convert::Infallible,
-    sync::Arc,
     time::Duration,
 };
 
@@ -50,11 +49,16 @@ use crate::{
         cache_hit_percentage_top_k,
         failure_percentage_top_k,
         latency_percentiles,
+        prometheus_function_metrics,
         scheduled_job_lag,
         table_rate,
         udf_rate,
     },
     canonical_urls::update_canonical_url,
+    code_versions::{
+        list_code_versions,
+        rollback_code_version,
+    },
     dashboard::{
         check_admin_key,
         delete_component,
@@ -70,9 +74,15 @@ use crate::{
         push_config,
     },
     deploy_config2,
+    deployment_audit_log::list_deployment_audit_log,
     environment_variables::update_environment_variables,
     http_actions::http_action_handler,
+    log_sinks::{
+        add_log_sink,
+        remove_log_sink,
+    },
     logs::{
+        query_function_logs,
         stream_function_logs,
         stream_udf_execution,
     },
@@ -109,6 +119,13 @@ use crate::{
         prepare_schema,
         schema_state,
     },
+    schema_migrations::{
+        cancel_schema_migration,
+        list_schema_migrations,
+        pause_schema_migration,
+        resume_schema_migration,
+        start_schema_migration,
+    },
     snapshot_export::{
         cancel_export,
         get_zip_export,
@@ -139,6 +156,11 @@ use crate::{
         replace_tables,
     },
     subs::sync,
+    usage::{
+        get_usage,
+        list_usage_quotas,
+        set_usage_quota,
+    },
     LocalAppState,
     RouterState,
 };
@@ -168,6 +190,24 @@ pub fn router(st: LocalAppState) -> Router {
         .route("/update_environment_variables", post(update_environment_variables))
         // Canonical URL routes
         .route("/update_canonical_url", post(update_canonical_url))
+        // Usage accounting routes
+        .route("/usage", get(get_usage))
+        .route("/usage/quotas", get(list_usage_quotas))
+        .route("/usage/quotas", post(set_usage_quota))
+        // Schema migration routes
+        .route("/schema_migrations", get(list_schema_migrations))
+        .route("/schema_migrations/start", post(start_schema_migration))
+        .route("/schema_migrations/pause", post(pause_schema_migration))
+        .route("/schema_migrations/resume", post(resume_schema_migration))
+        .route("/schema_migrations/cancel", post(cancel_schema_migration))
+        // Code version routes
+        .route("/code_versions", get(list_code_versions))
+        .route("/code_versions/rollback", post(rollback_code_version))
+        // Deployment audit log routes
+        .route("/deployment_audit_log", get(list_deployment_audit_log))
+        // Log sink routes
+        .route("/log_sinks/add", post(add_log_sink))
+        .route("/log_sinks/remove", post(remove_log_sink))
         // Local-only route to check if the admin key is valid
         .route("/check_admin_key", get(check_admin_key))
         .layer(ServiceBuilder::new());
@@ -199,6 +239,7 @@ pub fn router(st: LocalAppState) -> Router {
         .route("/schema_state/{schema_id}", get(schema_state))
         .route("/stream_udf_execution", get(stream_udf_execution))
         .route("/stream_function_logs", get(stream_function_logs))
+        .route("/query_function_logs", get(query_function_logs))
         .merge(import_routes())
         .layer(cli_cors());
 
@@ -234,7 +275,7 @@ pub fn router(st: LocalAppState) -> Router {
         // added inside `serve_http`
         .nest("/http/", http_action_routes())
         .with_state(RouterState {
-            api: Arc::new(st.application.clone()),
+            api: st.api.clone(),
             runtime: st.application.runtime().clone(),
         });
 
@@ -334,6 +375,7 @@ where
         .route("/table_rate", get(table_rate))
         .route("/latency_percentiles", get(latency_percentiles))
         .route("/scheduled_job_lag", get(scheduled_job_lag))
+        .route("/prometheus", get(prometheus_function_metrics))
 }
 
 // Routes with the same handlers for the local backend + closed source backend
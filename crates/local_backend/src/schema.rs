@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use application::deploy_config::ModuleJson;
 use axum::{
@@ -120,7 +122,7 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
         let name = meta.name.descriptor().to_string();
         Ok(match meta.config {
             IndexConfig::Database {
-                developer_config: DeveloperDatabaseIndexConfig { fields },
+                developer_config: DeveloperDatabaseIndexConfig { fields, .. },
                 on_disk_state,
             } => {
                 let backfill_state = match on_disk_state {
@@ -149,7 +151,9 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                 developer_config:
                     DeveloperTextIndexConfig {
                         search_field,
+                        extra_search_fields,
                         filter_fields,
+                        analyzer,
                     },
             } => {
                 let backfill_state = match on_disk_state {
@@ -167,7 +171,12 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                     name,
                     fields: json!({
                         "searchField":  String::from(search_field),
-                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>()
+                        "extraSearchFields": extra_search_fields
+                            .into_iter()
+                            .map(|(field_path, boost)| (String::from(field_path), boost.get()))
+                            .collect::<BTreeMap<_, _>>(),
+                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>(),
+                        "analyzer": analyzer.as_str(),
                     }),
                     backfill: BackfillResponse {
                         state: backfill_state,
@@ -180,6 +189,9 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                         dimensions,
                         vector_field,
                         filter_fields,
+                        metric,
+                        quantization,
+                        hnsw,
                     },
                 on_disk_state,
             } => {
@@ -195,7 +207,14 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                     fields: json!({
                         "dimensions": u32::from(dimensions),
                         "vectorField": String::from(vector_field),
-                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>()
+                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>(),
+                        "metric": metric.as_str(),
+                        "quantization": quantization.map(|q| q.as_str()),
+                        "hnsw": {
+                            "m": hnsw.m(),
+                            "efConstruct": hnsw.ef_construct(),
+                            "ef": hnsw.ef(),
+                        },
                     }),
                     backfill: BackfillResponse {
                         state: backfill_state,
@@ -0,0 +1,166 @@
+use anyhow::Context;
+use application::valid_identifier::ValidIdentifier;
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::{
+    document::ParsedDocument,
+    http::{
+        extract::Json,
+        HttpResponseError,
+    },
+};
+use errors::ErrorMetadata;
+use http::StatusCode;
+use model::schema_migrations::types::{
+    SchemaMigration,
+    SchemaMigrationState,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::{
+    id_v6::DeveloperDocumentId,
+    TableName,
+};
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    parse::parse_udf_path,
+    LocalAppState,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaMigrationJson {
+    id: String,
+    table_name: String,
+    udf_path: String,
+    batch_size: u64,
+    dry_run: bool,
+    state: String,
+    error: Option<String>,
+    documents_processed: u64,
+    documents_changed: u64,
+}
+
+impl From<ParsedDocument<SchemaMigration>> for SchemaMigrationJson {
+    fn from(migration: ParsedDocument<SchemaMigration>) -> Self {
+        let id = migration.developer_id().encode();
+        let migration = migration.into_value();
+        let error = match &migration.state {
+            SchemaMigrationState::Failed { error } => Some(error.clone()),
+            _ => None,
+        };
+        Self {
+            id,
+            table_name: migration.table_name.to_string(),
+            udf_path: String::from(migration.udf_path),
+            batch_size: migration.batch_size,
+            dry_run: migration.dry_run,
+            state: migration.state.to_string(),
+            error,
+            documents_processed: migration.documents_processed,
+            documents_changed: migration.documents_changed,
+        }
+    }
+}
+
+pub async fn list_schema_migrations(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let migrations = st.application.list_schema_migrations(identity).await?;
+    let migrations: Vec<SchemaMigrationJson> = migrations.into_iter().map(Into::into).collect();
+    Ok(Json(migrations))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartSchemaMigrationArgs {
+    table_name: String,
+    /// Path to a mutation in the root component, called with each document.
+    udf_path: String,
+    batch_size: Option<u64>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartSchemaMigrationResponse {
+    id: String,
+}
+
+pub async fn start_schema_migration(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(StartSchemaMigrationArgs {
+        table_name,
+        udf_path,
+        batch_size,
+        dry_run,
+    }): Json<StartSchemaMigrationArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let table_name = table_name.parse::<ValidIdentifier<TableName>>()?.0;
+    let udf_path = parse_udf_path(&udf_path)?;
+    let id = st
+        .application
+        .start_schema_migration(identity, table_name, udf_path, batch_size, dry_run)
+        .await?;
+    Ok(Json(StartSchemaMigrationResponse { id: id.encode() }))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaMigrationIdArgs {
+    id: String,
+}
+
+fn parse_schema_migration_id(id: &str) -> anyhow::Result<DeveloperDocumentId> {
+    DeveloperDocumentId::decode(id).context(ErrorMetadata::bad_request(
+        "InvalidSchemaMigration",
+        format!("invalid schema migration id {id}"),
+    ))
+}
+
+pub async fn pause_schema_migration(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(SchemaMigrationIdArgs { id }): Json<SchemaMigrationIdArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let id = parse_schema_migration_id(&id)?;
+    st.application.pause_schema_migration(identity, id).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn resume_schema_migration(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(SchemaMigrationIdArgs { id }): Json<SchemaMigrationIdArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let id = parse_schema_migration_id(&id)?;
+    st.application.resume_schema_migration(identity, id).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn cancel_schema_migration(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(SchemaMigrationIdArgs { id }): Json<SchemaMigrationIdArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let id = parse_schema_migration_id(&id)?;
+    st.application.cancel_schema_migration(identity, id).await?;
+    Ok(StatusCode::OK)
+}
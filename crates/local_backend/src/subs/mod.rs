@@ -1,6 +1,9 @@
-use std::time::{
-    Duration,
-    Instant,
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use ::errors::{
@@ -8,6 +11,7 @@ use ::errors::{
     ErrorMetadataAnyhowExt,
 };
 use anyhow::Context as _;
+use application::rate_limiter::RateLimitCaller;
 use axum::{
     body::Bytes,
     extract::{
@@ -76,7 +80,10 @@ use metrics::{
     websocket_upgrade_timer,
 };
 
-use crate::RouterState;
+use crate::{
+    public_api::ExtractRateLimitCaller,
+    RouterState,
+};
 
 /// How often heartbeat pings are sent.
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
@@ -338,18 +345,25 @@ async fn run_sync_socket(
     log_websocket_closed();
 }
 
-fn new_sync_worker_config(client_version: ClientVersion) -> anyhow::Result<SyncWorkerConfig> {
-    Ok(SyncWorkerConfig { client_version })
+fn new_sync_worker_config(
+    client_version: ClientVersion,
+    rate_limit_caller: RateLimitCaller,
+) -> anyhow::Result<SyncWorkerConfig> {
+    Ok(SyncWorkerConfig {
+        client_version,
+        rate_limit_caller: Arc::new(rate_limit_caller),
+    })
 }
 
 pub async fn sync_handler(
     st: RouterState,
     host: ResolvedHostname,
     client_version: ClientVersion,
+    rate_limit_caller: RateLimitCaller,
     ws: WebSocketUpgrade,
     on_connect: Box<dyn FnOnce(SessionId) + Send>,
 ) -> Result<impl IntoResponse, HttpResponseError> {
-    let config = new_sync_worker_config(client_version)?;
+    let config = new_sync_worker_config(client_version, rate_limit_caller)?;
     // Make a copy of the Sentry scope, which contains the request metadata.
     let sentry_scope = sentry::configure_scope(move |s| s.clone());
 
@@ -368,9 +382,18 @@ pub async fn sync(
     State(st): State<RouterState>,
     ExtractResolvedHostname(host): ExtractResolvedHostname,
     ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractRateLimitCaller(rate_limit_caller): ExtractRateLimitCaller,
     ws: WebSocketUpgrade,
 ) -> Result<impl IntoResponse, HttpResponseError> {
-    sync_handler(st, host, client_version, ws, Box::new(|_session_id| ())).await
+    sync_handler(
+        st,
+        host,
+        client_version,
+        rate_limit_caller,
+        ws,
+        Box::new(|_session_id| ()),
+    )
+    .await
 }
 
 #[cfg(test)]
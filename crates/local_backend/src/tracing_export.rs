@@ -0,0 +1,148 @@
+use std::{
+    borrow::Cow,
+    time::Duration,
+};
+
+use fastrace::collector::{
+    Config,
+    Reporter,
+    SpanRecord,
+};
+use fastrace_opentelemetry::OpenTelemetryReporter;
+use metrics::SERVER_VERSION_STR;
+use opentelemetry::InstrumentationScope;
+use opentelemetry_otlp::{
+    Protocol,
+    SpanExporter,
+    WithExportConfig,
+};
+use opentelemetry_sdk::Resource;
+use tokio::runtime::Handle;
+use url::Url;
+
+const OTLP_EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    Grpc,
+    Http,
+}
+
+/// The OTLP exporters are async and need a tokio reactor, but fastrace
+/// reports spans from its own background thread. Enter the server's runtime
+/// for the duration of each report.
+struct RuntimeReporter {
+    inner: OpenTelemetryReporter,
+    handle: Handle,
+}
+
+impl Reporter for RuntimeReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        let _guard = self.handle.enter();
+        self.inner.report(spans);
+    }
+}
+
+/// Installs a global fastrace reporter that exports sampled spans to an
+/// OpenTelemetry collector. Must be called from within the tokio runtime.
+pub fn install_otlp_reporter(
+    endpoint: &Url,
+    protocol: OtlpProtocol,
+    service_name: String,
+) -> anyhow::Result<()> {
+    let exporter = match protocol {
+        OtlpProtocol::Grpc => SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint.as_str())
+            .with_timeout(OTLP_EXPORT_TIMEOUT)
+            .build()?,
+        OtlpProtocol::Http => SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .with_endpoint(format!(
+                "{}/v1/traces",
+                endpoint.as_str().trim_end_matches('/')
+            ))
+            .with_timeout(OTLP_EXPORT_TIMEOUT)
+            .build()?,
+    };
+    let resource = Resource::builder().with_service_name(service_name).build();
+    let scope = InstrumentationScope::builder("convex-backend")
+        .with_version(SERVER_VERSION_STR.to_string())
+        .build();
+    let reporter = RuntimeReporter {
+        inner: OpenTelemetryReporter::new(exporter, Cow::Owned(resource), scope),
+        handle: Handle::current(),
+    };
+    fastrace::set_reporter(reporter, Config::default());
+    tracing::info!("Exporting traces over {protocol:?} to {endpoint}");
+    Ok(())
+}
+
+/// Reports any buffered spans. Blocks until the export finishes, so call it
+/// off of the async executor.
+pub async fn flush_spans() -> anyhow::Result<()> {
+    tokio::task::spawn_blocking(fastrace::flush).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyhow::Context;
+    use axum::{
+        routing::post,
+        Router,
+    };
+    use bytes::Bytes;
+    use fastrace::{
+        collector::SpanContext,
+        Span,
+    };
+    use http::StatusCode;
+    use runtime::prod::ProdRuntime;
+    use tokio::sync::mpsc;
+
+    use super::{
+        flush_spans,
+        install_otlp_reporter,
+        OtlpProtocol,
+    };
+
+    #[convex_macro::prod_rt_test]
+    async fn test_export_to_collector(_rt: ProdRuntime) -> anyhow::Result<()> {
+        // A stand-in for an OpenTelemetry collector's OTLP/HTTP endpoint.
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let collector = Router::new().route(
+            "/v1/traces",
+            post(move |body: Bytes| {
+                let _ = tx.send(body);
+                async { StatusCode::OK }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move { axum::serve(listener, collector).await });
+
+        install_otlp_reporter(
+            &format!("http://{addr}").parse()?,
+            OtlpProtocol::Http,
+            "convex-test".to_string(),
+        )?;
+        {
+            let root = Span::root("test_root", SpanContext::random());
+            let _child = Span::enter_with_parent("test_child", &root);
+        }
+        flush_spans().await?;
+
+        let body = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await?
+            .context("Collector shut down")?;
+        // Span and service names are encoded as plain protobuf strings.
+        for name in [&b"test_root"[..], b"test_child", b"convex-test"] {
+            assert!(body.windows(name.len()).any(|w| w == name));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use application::usage_accounting::{
+    UsageGroupBy,
+    UsageSummaryRow,
+};
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::{
+    components::ComponentPath,
+    http::{
+        extract::{
+            Json,
+            Query,
+        },
+        HttpResponseError,
+    },
+};
+use http::StatusCode;
+use model::usage::types::{
+    UsageMetrics,
+    UsageQuota,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQueryArgs {
+    /// Seconds since the Unix epoch, inclusive.
+    start: u64,
+    /// Seconds since the Unix epoch, exclusive.
+    end: u64,
+    group_by: UsageGroupBy,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetricsJson {
+    function_calls: u64,
+    function_failures: u64,
+    function_duration_millis: u64,
+    action_compute_mb_millis: u64,
+    database_ingress_bytes: u64,
+    database_egress_bytes: u64,
+    database_egress_rows: u64,
+    vector_ingress_bytes: u64,
+    vector_egress_bytes: u64,
+    storage_calls: u64,
+    storage_ingress_bytes: u64,
+    storage_egress_bytes: u64,
+    document_count: Option<u64>,
+    database_storage_bytes: Option<u64>,
+    vector_storage_bytes: Option<u64>,
+}
+
+impl From<UsageMetrics> for UsageMetricsJson {
+    fn from(metrics: UsageMetrics) -> Self {
+        Self {
+            function_calls: metrics.function_calls,
+            function_failures: metrics.function_failures,
+            function_duration_millis: metrics.function_duration_millis,
+            action_compute_mb_millis: metrics.action_compute_mb_millis,
+            database_ingress_bytes: metrics.database_ingress_bytes,
+            database_egress_bytes: metrics.database_egress_bytes,
+            database_egress_rows: metrics.database_egress_rows,
+            vector_ingress_bytes: metrics.vector_ingress_bytes,
+            vector_egress_bytes: metrics.vector_egress_bytes,
+            storage_calls: metrics.storage_calls,
+            storage_ingress_bytes: metrics.storage_ingress_bytes,
+            storage_egress_bytes: metrics.storage_egress_bytes,
+            document_count: metrics.document_count,
+            database_storage_bytes: metrics.database_storage_bytes,
+            vector_storage_bytes: metrics.vector_storage_bytes,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummaryRowJson {
+    component_path: Option<String>,
+    udf_id: Option<String>,
+    table_name: Option<String>,
+    #[serde(flatten)]
+    metrics: UsageMetricsJson,
+}
+
+impl From<UsageSummaryRow> for UsageSummaryRowJson {
+    fn from(row: UsageSummaryRow) -> Self {
+        Self {
+            component_path: row.component_path,
+            udf_id: row.udf_id,
+            table_name: row.table_name,
+            metrics: row.metrics.into(),
+        }
+    }
+}
+
+/// Usage over `[start, end)` grouped by component, function or table. Usage
+/// is bucketed by hour, so `start` is rounded down to the start of its hour.
+pub async fn get_usage(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Query(UsageQueryArgs {
+        start,
+        end,
+        group_by,
+    }): Query<UsageQueryArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let rows = st
+        .application
+        .usage_summary(identity, start, end, group_by)
+        .await?;
+    let rows: Vec<UsageSummaryRowJson> = rows.into_iter().map(Into::into).collect();
+    Ok(Json(rows))
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQuotaJson {
+    /// Omitted for the root component.
+    component_path: Option<String>,
+    max_function_calls_per_day: Option<u64>,
+    max_database_bandwidth_bytes_per_day: Option<u64>,
+}
+
+impl From<UsageQuota> for UsageQuotaJson {
+    fn from(quota: UsageQuota) -> Self {
+        Self {
+            component_path: quota.component_path,
+            max_function_calls_per_day: quota.max_function_calls_per_day,
+            max_database_bandwidth_bytes_per_day: quota.max_database_bandwidth_bytes_per_day,
+        }
+    }
+}
+
+pub async fn list_usage_quotas(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let quotas = st.application.list_usage_quotas(identity).await?;
+    let quotas: Vec<UsageQuotaJson> = quotas.into_iter().map(Into::into).collect();
+    Ok(Json(quotas))
+}
+
+/// Set the daily quotas for a component. Omitting both limits removes the
+/// component's quotas.
+pub async fn set_usage_quota(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(UsageQuotaJson {
+        component_path,
+        max_function_calls_per_day,
+        max_database_bandwidth_bytes_per_day,
+    }): Json<UsageQuotaJson>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    // Normalize the path so that quotas are keyed the same way as usage.
+    let component_path = ComponentPath::deserialize(component_path.as_deref())?.serialize();
+    let quota = UsageQuota {
+        component_path,
+        max_function_calls_per_day,
+        max_database_bandwidth_bytes_per_day,
+    };
+    st.application.set_usage_quota(identity, quota).await?;
+    Ok(StatusCode::OK)
+}
@@ -29,7 +29,7 @@ pub type DatabaseVersion = i64;
 // migrations unless explicitly dropping support.
 // Add a user name next to the version when you make a change to highlight merge
 // conflicts.
-pub const DATABASE_VERSION: DatabaseVersion = 119; // nipunn
+pub const DATABASE_VERSION: DatabaseVersion = 123; // nipunn
 
 pub struct MigrationExecutor<RT: Runtime> {
     pub db: Database<RT>,
@@ -62,6 +62,15 @@ impl<RT: Runtime> MigrationExecutor<RT> {
                     .await?;
                 MigrationCompletionCriterion::MigrationComplete(to_version)
             },
+            // Empty migration for 120 - represents creation of the usage rollups and
+            // quotas tables
+            120 => MigrationCompletionCriterion::MigrationComplete(to_version),
+            // Empty migration for 121 - represents creation of the schema migrations table
+            121 => MigrationCompletionCriterion::MigrationComplete(to_version),
+            // Empty migration for 122 - represents creation of the code versions table
+            122 => MigrationCompletionCriterion::MigrationComplete(to_version),
+            // Empty migration for 123 - represents creation of the rate limits table
+            123 => MigrationCompletionCriterion::MigrationComplete(to_version),
             // NOTE: Make sure to increase DATABASE_VERSION when adding new migrations.
             _ => anyhow::bail!("Version did not define a migration! {}", to_version),
         };
@@ -0,0 +1,259 @@
+use std::{
+    collections::BTreeMap,
+    sync::LazyLock,
+};
+
+use anyhow::Context;
+use common::{
+    bootstrap_model::components::ComponentState,
+    components::ComponentId,
+    document::{
+        ParseDocument,
+        ParsedDocument,
+    },
+    knobs::CODE_VERSION_RETENTION_COUNT,
+    query::{
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+};
+use database::{
+    unauthorized_error,
+    BootstrapComponentsModel,
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use errors::ErrorMetadata;
+use value::{
+    id_v6::DeveloperDocumentId,
+    TableName,
+    TableNamespace,
+};
+
+use crate::{
+    code_versions::types::{
+        CodeRollbackDiff,
+        CodeVersion,
+    },
+    components::handles::FunctionHandlesModel,
+    cron_jobs::CronModel,
+    modules::ModuleModel,
+    source_packages::{
+        types::SourcePackageId,
+        SourcePackageModel,
+    },
+    udf_config::UdfConfigModel,
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static CODE_VERSIONS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_code_versions"
+        .parse()
+        .expect("Invalid built-in code versions table")
+});
+
+pub struct CodeVersionsTable;
+impl SystemTable for CodeVersionsTable {
+    type Metadata = CodeVersion;
+
+    fn table_name() -> &'static TableName {
+        &CODE_VERSIONS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![]
+    }
+}
+
+/// History of the root component's pushed code, retaining the last
+/// `CODE_VERSION_RETENTION_COUNT` pushes so a deployment can be rolled back to
+/// one of them without re-pushing.
+pub struct CodeVersionModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+}
+
+impl<'a, RT: Runtime> CodeVersionModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
+        Self { tx }
+    }
+
+    /// Retained code versions, newest first.
+    pub async fn list(&mut self) -> anyhow::Result<Vec<ParsedDocument<CodeVersion>>> {
+        let query = Query::full_table_scan(CODE_VERSIONS_TABLE.clone(), Order::Desc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut versions = Vec::new();
+        while let Some(document) = query_stream.next(self.tx, None).await? {
+            versions.push(document.parse()?);
+        }
+        Ok(versions)
+    }
+
+    pub async fn get(
+        &mut self,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<Option<ParsedDocument<CodeVersion>>> {
+        let query = Query::get(CODE_VERSIONS_TABLE.clone(), id);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|doc| doc.parse())
+            .transpose()
+    }
+
+    /// The source package the root component is currently running.
+    pub async fn active_source_package(&mut self) -> anyhow::Result<Option<SourcePackageId>> {
+        Ok(SourcePackageModel::new(self.tx, ComponentId::Root.into())
+            .get_latest()
+            .await?
+            .map(|source_package| DeveloperDocumentId::from(source_package.id()).into()))
+    }
+
+    /// Snapshot the root component's modules and UDF config right after they
+    /// were pushed from `source_package_id`, dropping the oldest versions
+    /// beyond the retention limit.
+    pub async fn record(&mut self, source_package_id: SourcePackageId) -> anyhow::Result<()> {
+        let Some(udf_config) = UdfConfigModel::new(self.tx, ComponentId::Root.into())
+            .get()
+            .await?
+        else {
+            return Ok(());
+        };
+        let modules = ModuleModel::new(self.tx)
+            .get_application_metadata(ComponentId::Root)
+            .await?
+            .into_iter()
+            .map(|module| module.into_value())
+            .collect();
+        let version = CodeVersion {
+            source_package_id,
+            udf_config: udf_config.into_value(),
+            modules,
+        };
+        SystemMetadataModel::new_global(self.tx)
+            .insert(&CODE_VERSIONS_TABLE, version.try_into()?)
+            .await?;
+
+        let expired = self
+            .list()
+            .await?
+            .into_iter()
+            .skip(*CODE_VERSION_RETENTION_COUNT);
+        for version in expired {
+            SystemMetadataModel::new_global(self.tx)
+                .delete(version.id())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Re-activate a previously pushed code version: its modules (and with
+    /// them function specs and HTTP routes), crons, and UDF config. The schema
+    /// and indexes are left as they are, so the restored code must work with
+    /// the current schema.
+    pub async fn rollback(&mut self, id: DeveloperDocumentId) -> anyhow::Result<CodeRollbackDiff> {
+        if !(self.tx.identity().is_admin() || self.tx.identity().is_system()) {
+            anyhow::bail!(unauthorized_error("rollback_code"));
+        }
+        let version = self
+            .get(id)
+            .await?
+            .with_context(|| {
+                ErrorMetadata::not_found(
+                    "CodeVersionNotFound",
+                    format!("Code version {id} not found"),
+                )
+            })?
+            .into_value();
+        let has_child_components = BootstrapComponentsModel::new(self.tx)
+            .load_all_components()
+            .await?
+            .iter()
+            .any(|component| {
+                component.parent_and_name().is_some() && component.state == ComponentState::Active
+            });
+        anyhow::ensure!(
+            !has_child_components,
+            ErrorMetadata::bad_request(
+                "CodeRollbackUnsupported",
+                "Rolling back code is not supported for deployments with components",
+            )
+        );
+
+        let analyze_results: BTreeMap<_, _> = version
+            .modules
+            .iter()
+            .filter_map(|module| Some((module.path.clone(), module.analyze_result.clone()?)))
+            .collect();
+        let module_diff = ModuleModel::new(self.tx)
+            .restore(ComponentId::Root, version.modules)
+            .await?;
+        let cron_diff = CronModel::new(self.tx, ComponentId::Root)
+            .apply(&analyze_results)
+            .await?;
+        FunctionHandlesModel::new(self.tx)
+            .apply_config_diff(ComponentId::Root, Some(&analyze_results))
+            .await?;
+        let udf_server_version_diff = UdfConfigModel::new(self.tx, ComponentId::Root.into())
+            .set(version.udf_config)
+            .await?;
+        Ok(CodeRollbackDiff {
+            code_version_id: id,
+            udf_server_version_diff,
+            module_diff,
+            cron_diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        components::ComponentId,
+        knobs::CODE_VERSION_RETENTION_COUNT,
+    };
+    use database::test_helpers::DbFixtures;
+    use keybroker::Identity;
+    use runtime::testing::TestRuntime;
+    use semver::Version;
+
+    use crate::{
+        code_versions::CodeVersionModel,
+        source_packages::types::SourcePackageId,
+        test_helpers::DbFixturesWithModel,
+        udf_config::{
+            types::UdfConfig,
+            UdfConfigModel,
+        },
+    };
+
+    #[convex_macro::test_runtime]
+    async fn test_record_prunes_old_versions(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let mut tx = db.begin(Identity::system()).await?;
+        let mut udf_config_model = UdfConfigModel::new(&mut tx, ComponentId::Root.into());
+        udf_config_model
+            .set(UdfConfig::new_for_test(&rt, Version::new(1, 0, 0)))
+            .await?;
+        // Any id will do since `record` doesn't load the source package.
+        let source_package_id: SourcePackageId =
+            udf_config_model.get().await?.unwrap().developer_id().into();
+        let mut ids = vec![];
+        for _ in 0..*CODE_VERSION_RETENTION_COUNT + 2 {
+            CodeVersionModel::new(&mut tx)
+                .record(source_package_id)
+                .await?;
+            ids.push(CodeVersionModel::new(&mut tx).list().await?[0].id());
+        }
+        let versions = CodeVersionModel::new(&mut tx).list().await?;
+        assert_eq!(versions.len(), *CODE_VERSION_RETENTION_COUNT);
+        assert_eq!(versions[0].id(), *ids.last().unwrap());
+        assert!(!versions.iter().any(|version| version.id() == ids[0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::{
+    codegen_convex_serialization,
+    obj,
+    remove_object,
+    remove_string,
+    remove_vec,
+    ConvexObject,
+    ConvexValue,
+    DeveloperDocumentId,
+};
+
+use crate::{
+    config::types::{
+        CronDiff,
+        ModuleDiff,
+        UdfServerVersionDiff,
+    },
+    modules::types::ModuleMetadata,
+    source_packages::types::SourcePackageId,
+    udf_config::types::UdfConfig,
+};
+
+/// A snapshot of the root component's code as of a push: the source package
+/// it was loaded from, the analyzed metadata of every module in it (function
+/// specs, crons, HTTP routes), and the UDF config it was pushed with. Rolling
+/// back to a code version restores exactly these, without touching the
+/// schema or indexes.
+#[derive(Debug, Clone)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq))]
+pub struct CodeVersion {
+    pub source_package_id: SourcePackageId,
+    pub udf_config: UdfConfig,
+    pub modules: Vec<ModuleMetadata>,
+}
+
+impl TryFrom<CodeVersion> for ConvexObject {
+    type Error = anyhow::Error;
+
+    fn try_from(version: CodeVersion) -> anyhow::Result<Self> {
+        let modules: Vec<ConvexValue> = version
+            .modules
+            .into_iter()
+            .map(|module| anyhow::Ok(ConvexValue::Object(module.try_into()?)))
+            .try_collect()?;
+        obj!(
+            "sourcePackageId" => DeveloperDocumentId::from(version.source_package_id).encode(),
+            "udfConfig" => ConvexObject::try_from(version.udf_config)?,
+            "modules" => modules,
+        )
+    }
+}
+
+impl TryFrom<ConvexObject> for CodeVersion {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ConvexObject) -> anyhow::Result<Self> {
+        let mut fields = BTreeMap::from(value);
+        let source_package_id =
+            DeveloperDocumentId::decode(&remove_string(&mut fields, "sourcePackageId")?)?.into();
+        let udf_config = remove_object(&mut fields, "udfConfig")?;
+        let modules = remove_vec(&mut fields, "modules")?
+            .into_iter()
+            .map(|module| ModuleMetadata::try_from(ConvexObject::try_from(module)?))
+            .try_collect()?;
+        Ok(Self {
+            source_package_id,
+            udf_config,
+            modules,
+        })
+    }
+}
+
+/// What changed when rolling back to a code version, recorded in the
+/// deployment audit log.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(proptest_derive::Arbitrary, PartialEq)
+)]
+pub struct CodeRollbackDiff {
+    pub code_version_id: DeveloperDocumentId,
+    pub udf_server_version_diff: Option<UdfServerVersionDiff>,
+    pub module_diff: ModuleDiff,
+    pub cron_diff: CronDiff,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedCodeRollbackDiff {
+    pub code_version_id: String,
+    pub server_version: Option<UdfServerVersionDiff>,
+    pub modules: ModuleDiff,
+    pub crons: CronDiff,
+}
+
+impl TryFrom<CodeRollbackDiff> for SerializedCodeRollbackDiff {
+    type Error = anyhow::Error;
+
+    fn try_from(value: CodeRollbackDiff) -> anyhow::Result<Self> {
+        Ok(Self {
+            code_version_id: value.code_version_id.encode(),
+            server_version: value.udf_server_version_diff,
+            modules: value.module_diff,
+            crons: value.cron_diff,
+        })
+    }
+}
+
+impl TryFrom<SerializedCodeRollbackDiff> for CodeRollbackDiff {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedCodeRollbackDiff) -> anyhow::Result<Self> {
+        Ok(Self {
+            code_version_id: DeveloperDocumentId::decode(&value.code_version_id)?,
+            udf_server_version_diff: value.server_version,
+            module_diff: value.modules,
+            cron_diff: value.crons,
+        })
+    }
+}
+
+codegen_convex_serialization!(CodeRollbackDiff, SerializedCodeRollbackDiff);
@@ -57,6 +57,7 @@ use super::{
     types::EvaluatedComponentDefinition,
 };
 use crate::{
+    code_versions::CodeVersionModel,
     component_system_tables,
     config::types::{
         CronDiff,
@@ -558,6 +559,11 @@ impl<'a, RT: Runtime> ComponentConfigModel<'a, RT> {
         IndexModel::new(self.tx)
             .apply(component_id.into(), &next_schema)
             .await?;
+        if component_id.is_root() {
+            CodeVersionModel::new(self.tx)
+                .record(source_package_id)
+                .await?;
+        }
         Ok((
             id,
             ComponentDiff {
@@ -625,6 +631,11 @@ impl<'a, RT: Runtime> ComponentConfigModel<'a, RT> {
         IndexModel::new(self.tx)
             .apply(component_id.into(), &next_schema)
             .await?;
+        if component_id.is_root() {
+            CodeVersionModel::new(self.tx)
+                .record(source_package_id)
+                .await?;
+        }
 
         let diff_type = if existing.state == ComponentState::Unmounted {
             ComponentDiffType::Remount
@@ -94,6 +94,7 @@ macro_rules! db_schema_with_indexes {
                             common::schemas::IndexSchema {
                                 index_descriptor: index_name.descriptor().clone(),
                                 fields: field_paths.try_into()?,
+                                unique: false,
                             },
                         );
                     )*
@@ -102,6 +103,7 @@ macro_rules! db_schema_with_indexes {
                         indexes,
                         search_indexes: Default::default(),
                         vector_indexes: Default::default(),
+                        references: Default::default(),
                         document_type: None,
                     };
                     tables.insert(table_name, table_def);
@@ -171,6 +173,7 @@ pub async fn apply_config(
     let config_metadata = ConfigMetadata {
         functions: "convex/".to_string(),
         auth_info: vec![],
+        rate_limits: vec![],
     };
 
     let mut tx = db.begin_system().await?;
@@ -81,7 +81,9 @@ macro_rules! db_schema_with_search_indexes {
                             SearchIndexSchema::new(
                                 index_name.descriptor().clone(),
                                 field_path.try_into()?,
+                                BTreeMap::new(),
                                 BTreeSet::new(),
+                                Default::default(),
                             )?,
                         );
                     )*
@@ -90,6 +92,7 @@ macro_rules! db_schema_with_search_indexes {
                         indexes: BTreeMap::new(),
                         search_indexes,
                         vector_indexes: Default::default(),
+                        references: Default::default(),
                         document_type: None,
                     };
                     tables.insert(table_name, table_def);
@@ -33,6 +33,7 @@ use value::ResolvedDocumentId;
 use self::module_loader::ModuleLoader;
 use crate::{
     auth::AuthInfoModel,
+    code_versions::CodeVersionModel,
     config::types::{
         ConfigDiff,
         ConfigMetadata,
@@ -45,6 +46,7 @@ use crate::{
         types::ModuleMetadata,
         ModuleModel,
     },
+    rate_limits::RateLimitModel,
     source_packages::{
         types::SourcePackage,
         SourcePackageModel,
@@ -107,9 +109,17 @@ impl<'a, RT: Runtime> ConfigModel<'a, RT> {
 
         // Update auth info.
         let auth_diff = AuthInfoModel::new(self.tx).put(config.auth_info).await?;
+        RateLimitModel::new(self.tx).put(config.rate_limits).await?;
         let udf_server_version_diff = UdfConfigModel::new(self.tx, self.component.into())
             .set(new_config)
             .await?;
+        if self.component.is_root()
+            && let Some(source_package_id) = source_package_id
+        {
+            CodeVersionModel::new(self.tx)
+                .record(source_package_id)
+                .await?;
+        }
         let config_diff = ConfigDiff {
             module_diff,
             auth_diff,
@@ -217,6 +217,7 @@ async fn test_config_delete_auth_info(rt: TestRuntime) -> anyhow::Result<()> {
             ConfigMetadata {
                 functions: "convex/".to_string(),
                 auth_info: vec![],
+                rate_limits: vec![],
             },
             vec![],
             UdfConfig::new_for_test(&rt, "1000.0.0".parse()?),
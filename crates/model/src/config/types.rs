@@ -51,6 +51,10 @@ use crate::{
         ModuleSource,
         SourceMap,
     },
+    rate_limits::types::{
+        RateLimitPolicy,
+        SerializedRateLimitPolicy,
+    },
 };
 
 /// User-specified module definition. See [`ModuleMetadata`] and associated
@@ -113,6 +117,15 @@ pub struct ConfigMetadata {
         )
     )]
     pub auth_info: Vec<AuthInfo>,
+    /// Rate limits on public functions.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(
+            strategy = "proptest::collection::vec(proptest::prelude::any::<RateLimitPolicy>(), \
+                        0..4)"
+        )
+    )]
+    pub rate_limits: Vec<RateLimitPolicy>,
 }
 
 impl ConfigMetadata {
@@ -121,6 +134,7 @@ impl ConfigMetadata {
         Self {
             functions: "convex/".to_string(),
             auth_info: vec![],
+            rate_limits: vec![],
         }
     }
 
@@ -129,14 +143,21 @@ impl ConfigMetadata {
         Self {
             functions: "convex/".to_string(),
             auth_info: vec![AuthInfo::test_example()],
+            rate_limits: vec![],
         }
     }
 
-    pub fn from_file(file: ConfigFile, auth_info: Vec<AuthInfo>) -> Self {
-        Self {
+    pub fn from_file(file: ConfigFile, auth_info: Vec<AuthInfo>) -> anyhow::Result<Self> {
+        Ok(Self {
             functions: file.functions,
             auth_info,
-        }
+            rate_limits: file
+                .rate_limits
+                .unwrap_or_default()
+                .into_iter()
+                .map(RateLimitPolicy::try_from)
+                .collect::<anyhow::Result<_>>()?,
+        })
     }
 }
 
@@ -146,6 +167,8 @@ pub struct ConfigFile {
     pub functions: String,
     // Deprecated, moved to AuthConfig.providers
     pub auth_info: Option<Vec<SerializedAuthInfo>>,
+    #[serde(default)]
+    pub rate_limits: Option<Vec<SerializedRateLimitPolicy>>,
 }
 
 impl TryFrom<ConfigMetadata> for ConvexObject {
@@ -166,6 +189,15 @@ impl TryFrom<ConfigMetadata> for ConvexObject {
                 .try_into()?;
             config.insert("authInfo".parse()?, auth_info);
         }
+        if !m.rate_limits.is_empty() {
+            let rate_limits = m
+                .rate_limits
+                .into_iter()
+                .map(ConvexValue::try_from)
+                .collect::<anyhow::Result<Vec<ConvexValue>>>()?
+                .try_into()?;
+            config.insert("rateLimits".parse()?, rate_limits);
+        }
         config.try_into()
     }
 }
@@ -200,9 +232,17 @@ impl TryFrom<ConvexObject> for ConfigMetadata {
                 .collect::<anyhow::Result<Vec<AuthInfo>>>()?,
             _ => vec![],
         };
+        let rate_limits = match fields.remove("rateLimits") {
+            Some(v) => ConvexArray::try_from(v)?
+                .into_iter()
+                .map(RateLimitPolicy::try_from)
+                .collect::<anyhow::Result<Vec<RateLimitPolicy>>>()?,
+            _ => vec![],
+        };
         Ok(Self {
             functions,
             auth_info,
+            rate_limits,
         })
     }
 }
@@ -2,16 +2,24 @@ use std::sync::LazyLock;
 
 use common::{
     document::{
+        CreationTime,
         ParseDocument,
         ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
     },
+    knobs::AUDIT_LOG_QUERY_MAX_SCANNED,
     obj,
     query::{
+        IndexRange,
+        IndexRangeExpression,
         Order,
         Query,
     },
     runtime::Runtime,
-    types::MemberId,
+    types::{
+        IndexName,
+        MemberId,
+    },
 };
 use database::{
     unauthorized_error,
@@ -22,6 +30,7 @@ use database::{
 use futures_async_stream::try_stream;
 use value::{
     ConvexObject,
+    ConvexValue,
     FieldPath,
     ResolvedDocumentId,
     TableName,
@@ -30,7 +39,11 @@ use value::{
 
 pub mod types;
 
-use types::DeploymentAuditLogEvent;
+use types::{
+    DeploymentAuditLogEntry,
+    DeploymentAuditLogEvent,
+    DeploymentAuditLogFilter,
+};
 
 use crate::{
     SystemIndex,
@@ -117,6 +130,61 @@ impl<'a, RT: Runtime> DeploymentAuditLogModel<'a, RT> {
         Ok(ids[0])
     }
 
+    /// A page of audit log events matching `filter`, newest first, along with
+    /// a cursor to fetch the next page if there may be more. `cursor` is the
+    /// cursor returned with the previous page.
+    pub async fn list_filtered(
+        &mut self,
+        filter: &DeploymentAuditLogFilter,
+        cursor: Option<CreationTime>,
+        page_size: usize,
+    ) -> anyhow::Result<(Vec<DeploymentAuditLogEntry>, Option<CreationTime>)> {
+        if !(self.tx.identity().is_system() || self.tx.identity().is_admin()) {
+            anyhow::bail!(unauthorized_error("list_deployment_audit_log"));
+        }
+        let mut range = vec![];
+        if let Some(from) = filter.from {
+            range.push(IndexRangeExpression::Gte(
+                CREATION_TIME_FIELD_PATH.clone(),
+                ConvexValue::from(f64::from(from)).into(),
+            ));
+        }
+        let upper_bound = match (filter.to, cursor) {
+            (Some(to), Some(cursor)) => Some(to.min(cursor)),
+            (to, cursor) => to.or(cursor),
+        };
+        if let Some(upper_bound) = upper_bound {
+            range.push(IndexRangeExpression::Lt(
+                CREATION_TIME_FIELD_PATH.clone(),
+                ConvexValue::from(f64::from(upper_bound)).into(),
+            ));
+        }
+        let query = Query::index_range(IndexRange {
+            index_name: IndexName::by_creation_time(DEPLOYMENT_AUDIT_LOG_TABLE.clone()),
+            range,
+            order: Order::Desc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut entries = vec![];
+        let mut scanned = 0;
+        let mut last_scanned = None;
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            let entry = DeploymentAuditLogEntry::try_from(doc)?;
+            scanned += 1;
+            last_scanned = Some(entry.creation_time);
+            if filter.matches(&entry) {
+                entries.push(entry);
+                if entries.len() >= page_size {
+                    return Ok((entries, last_scanned));
+                }
+            }
+            if scanned >= *AUDIT_LOG_QUERY_MAX_SCANNED {
+                return Ok((entries, last_scanned));
+            }
+        }
+        Ok((entries, None))
+    }
+
     #[try_stream(boxed, ok = ParsedDocument<DeploymentAuditLogEvent>, error = anyhow::Error)]
     pub async fn list(&mut self) {
         let value_query = Query::full_table_scan(DEPLOYMENT_AUDIT_LOG_TABLE.clone(), Order::Asc);
@@ -127,3 +195,88 @@ impl<'a, RT: Runtime> DeploymentAuditLogModel<'a, RT> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use common::types::MemberId;
+    use database::test_helpers::DbFixtures;
+    use runtime::testing::TestRuntime;
+    use serde_json::{
+        json,
+        Value as JsonValue,
+    };
+
+    use crate::{
+        deployment_audit_log::{
+            types::{
+                DeploymentAuditLogEvent,
+                DeploymentAuditLogFilter,
+            },
+            DeploymentAuditLogModel,
+        },
+        test_helpers::DbFixturesWithModel,
+    };
+
+    #[convex_macro::test_runtime]
+    async fn test_list_filtered(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let mut tx = db.begin_system().await?;
+        let mut model = DeploymentAuditLogModel::new(&mut tx);
+        for i in 0..5 {
+            model
+                .insert_with_member_override(
+                    vec![
+                        DeploymentAuditLogEvent::CreateEnvironmentVariable {
+                            name: format!("VAR_{i}").parse()?,
+                        },
+                        DeploymentAuditLogEvent::DeleteEnvironmentVariable {
+                            name: format!("VAR_{i}").parse()?,
+                        },
+                    ],
+                    Some(MemberId(i % 2)),
+                )
+                .await?;
+        }
+
+        // Everything, newest first.
+        let (all, cursor) = model
+            .list_filtered(&DeploymentAuditLogFilter::default(), None, 100)
+            .await?;
+        assert_eq!(all.len(), 10);
+        assert!(cursor.is_none());
+        assert!(all
+            .windows(2)
+            .all(|w| w[0].creation_time > w[1].creation_time));
+
+        // Filter by action and member, two at a time.
+        let filter = DeploymentAuditLogFilter {
+            actions: Some(BTreeSet::from(["create_environment_variable".to_string()])),
+            member_id: Some(MemberId(0)),
+            ..Default::default()
+        };
+        let (first_page, cursor) = model.list_filtered(&filter, None, 2).await?;
+        assert_eq!(first_page.len(), 2);
+        let (second_page, cursor) = model.list_filtered(&filter, cursor, 2).await?;
+        assert_eq!(second_page.len(), 1);
+        assert!(cursor.is_none());
+        let names: Vec<_> = first_page
+            .iter()
+            .chain(second_page.iter())
+            .map(|entry| JsonValue::from(entry.metadata.clone())["variable_name"].clone())
+            .collect();
+        assert_eq!(names, vec![json!("VAR_4"), json!("VAR_2"), json!("VAR_0")]);
+
+        // Filter by time range.
+        let filter = DeploymentAuditLogFilter {
+            from: Some(all[3].creation_time),
+            to: Some(all[0].creation_time),
+            ..Default::default()
+        };
+        let (in_range, _) = model.list_filtered(&filter, None, 100).await?;
+        let ids: Vec<_> = in_range.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![all[1].id, all[2].id, all[3].id]);
+        Ok(())
+    }
+}
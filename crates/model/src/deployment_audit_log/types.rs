@@ -1,5 +1,8 @@
 use std::{
-    collections::BTreeMap,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     str::FromStr,
     sync::LazyLock,
 };
@@ -12,6 +15,10 @@ use common::{
         SerializedNamedDeveloperIndexConfig,
     },
     components::ComponentPath,
+    document::{
+        CreationTime,
+        ResolvedDocument,
+    },
     http::RequestDestination,
     log_streaming::{
         LogEvent,
@@ -22,6 +29,7 @@ use common::{
         GenericIndexName,
         IndexDiff,
         IndexName,
+        MemberId,
     },
 };
 use database::LegacyIndexDiff;
@@ -44,12 +52,14 @@ use value::{
     val,
     ConvexObject,
     ConvexValue,
+    DeveloperDocumentId,
     TableName,
 };
 
 use crate::{
     auth::types::AuthDiff,
     backend_state::types::BackendState,
+    code_versions::types::CodeRollbackDiff,
     components::config::{
         ComponentDiff,
         SerializedComponentDiff,
@@ -139,6 +149,9 @@ pub enum DeploymentAuditLogEvent {
     PushConfigWithComponents {
         diffs: PushComponentDiffs,
     },
+    RollbackCode {
+        diff: CodeRollbackDiff,
+    },
     BuildIndexes {
         #[cfg_attr(
             any(test, feature = "testing"),
@@ -218,6 +231,7 @@ impl DeploymentAuditLogEvent {
             DeploymentAuditLogEvent::PushConfigWithComponents { .. } => {
                 "push_config_with_components"
             },
+            DeploymentAuditLogEvent::RollbackCode { .. } => "rollback_code",
             DeploymentAuditLogEvent::BuildIndexes { .. } => "build_indexes",
             DeploymentAuditLogEvent::ChangeDeploymentState { .. } => "change_deployment_state",
             DeploymentAuditLogEvent::SnapshotImport { .. } => "snapshot_import",
@@ -253,6 +267,7 @@ impl DeploymentAuditLogEvent {
                 ConvexObject::try_from(config_diff)
             },
             DeploymentAuditLogEvent::PushConfigWithComponents { diffs } => diffs.try_into(),
+            DeploymentAuditLogEvent::RollbackCode { diff } => diff.try_into(),
             DeploymentAuditLogEvent::BuildIndexes {
                 added_indexes,
                 removed_indexes,
@@ -425,6 +440,9 @@ impl TryFrom<ConvexObject> for DeploymentAuditLogEvent {
             "push_config_with_components" => DeploymentAuditLogEvent::PushConfigWithComponents {
                 diffs: ConvexObject::try_from(fields)?.try_into()?,
             },
+            "rollback_code" => DeploymentAuditLogEvent::RollbackCode {
+                diff: ConvexObject::try_from(fields)?.try_into()?,
+            },
             "build_indexes" => {
                 let added_indexes = remove_vec(&mut fields, "added_indexes")?
                     .into_iter()
@@ -651,6 +669,70 @@ impl TryFrom<PushComponentDiffs> for SerializedPushComponentDiffs {
 
 codegen_convex_serialization!(PushComponentDiffs, SerializedPushComponentDiffs);
 
+/// A row of the deployment audit log as stored, without interpreting the
+/// metadata of each action, so older events whose shape has changed can
+/// still be listed.
+#[derive(Debug, Clone)]
+pub struct DeploymentAuditLogEntry {
+    pub id: DeveloperDocumentId,
+    pub creation_time: CreationTime,
+    pub action: String,
+    pub member_id: Option<MemberId>,
+    pub metadata: ConvexObject,
+}
+
+impl TryFrom<ResolvedDocument> for DeploymentAuditLogEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(doc: ResolvedDocument) -> anyhow::Result<Self> {
+        let id = doc.developer_id();
+        let creation_time = doc.creation_time();
+        let mut fields = BTreeMap::from(doc.into_value().0);
+        let action = remove_string(&mut fields, "action")?;
+        let metadata = remove_object(&mut fields, "metadata")?;
+        let member_id = match fields.remove("member_id") {
+            Some(ConvexValue::Int64(member_id)) => Some(MemberId::from(u64::try_from(member_id)?)),
+            None | Some(ConvexValue::Null) => None,
+            v => anyhow::bail!("Invalid member_id for audit log event: {v:?}"),
+        };
+        Ok(Self {
+            id,
+            creation_time,
+            action,
+            member_id,
+            metadata,
+        })
+    }
+}
+
+/// Which audit log events to return when listing them. Unset fields match
+/// every event.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentAuditLogFilter {
+    pub actions: Option<BTreeSet<String>>,
+    pub member_id: Option<MemberId>,
+    /// Inclusive lower bound on the event's creation time.
+    pub from: Option<CreationTime>,
+    /// Exclusive upper bound on the event's creation time.
+    pub to: Option<CreationTime>,
+}
+
+impl DeploymentAuditLogFilter {
+    pub fn matches(&self, entry: &DeploymentAuditLogEntry) -> bool {
+        if let Some(actions) = &self.actions
+            && !actions.contains(&entry.action)
+        {
+            return false;
+        }
+        if let Some(member_id) = self.member_id
+            && entry.member_id != Some(member_id)
+        {
+            return false;
+        }
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cmd_util::env::env_config;
@@ -33,6 +33,7 @@ use database::{
     Transaction,
 };
 use errors::ErrorMetadata;
+use keybroker::KeyBroker;
 use value::{
     ConvexValue,
     FieldPath,
@@ -79,12 +80,16 @@ impl SystemTable for EnvironmentVariablesTable {
     }
 }
 
+/// Environment variable values are encrypted at rest with a key derived from
+/// the instance secret, so reading or writing them needs the `KeyBroker`.
 pub struct EnvironmentVariablesModel<'a, RT: Runtime> {
     tx: &'a mut Transaction<RT>,
+    key_broker: &'a KeyBroker,
 }
 
 pub struct PreloadedEnvironmentVariables {
     range: PreloadedIndexRange,
+    key_broker: KeyBroker,
 }
 
 impl PreloadedEnvironmentVariables {
@@ -98,15 +103,15 @@ impl PreloadedEnvironmentVariables {
             return Ok(None);
         };
         let doc: ParsedDocument<PersistedEnvironmentVariable> = doc.clone().parse()?;
-        let var = doc.into_value().0;
+        let var = doc.decrypt(&self.key_broker)?;
         anyhow::ensure!(var.name() == name, "Invalid environment variable");
         Ok(Some(var.into_value()))
     }
 }
 
 impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
-    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
-        Self { tx }
+    pub fn new(tx: &'a mut Transaction<RT>, key_broker: &'a KeyBroker) -> Self {
+        Self { tx, key_broker }
     }
 
     pub async fn preload(&mut self) -> anyhow::Result<PreloadedEnvironmentVariables> {
@@ -118,22 +123,32 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
                 &Interval::all(),
             )
             .await?;
-        Ok(PreloadedEnvironmentVariables { range })
+        Ok(PreloadedEnvironmentVariables {
+            range,
+            key_broker: self.key_broker.clone(),
+        })
     }
 
     pub async fn get(
         &mut self,
         name: &EnvVarName,
     ) -> anyhow::Result<Option<ParsedDocument<EnvironmentVariable>>> {
+        self.get_persisted(name)
+            .await?
+            .map(|doc| doc.map(|doc| doc.decrypt(self.key_broker)))
+            .transpose()
+    }
+
+    async fn get_persisted(
+        &mut self,
+        name: &EnvVarName,
+    ) -> anyhow::Result<Option<ParsedDocument<PersistedEnvironmentVariable>>> {
         let query = value_query_from_env_var(name)?;
         let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
         query_stream
             .expect_at_most_one(self.tx)
             .await?
-            .map(|doc| {
-                let doc: ParsedDocument<PersistedEnvironmentVariable> = doc.parse()?;
-                doc.map(|doc| Ok(doc.0))
-            })
+            .map(|doc| doc.parse())
             .transpose()
     }
 
@@ -145,7 +160,7 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
             return Ok(None);
         };
         let persisted: ParsedDocument<PersistedEnvironmentVariable> = doc.parse()?;
-        Ok(Some(persisted.into_value().0))
+        Ok(Some(persisted.decrypt(self.key_broker)?))
     }
 
     #[fastrace::trace]
@@ -155,17 +170,56 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
         let mut environment_variables = BTreeMap::new();
         while let Some(doc) = query_stream.next(self.tx, None).await? {
             let env_var: ParsedDocument<PersistedEnvironmentVariable> = doc.parse()?;
-            let old_value = environment_variables
-                .insert(env_var.0.name().to_owned(), env_var.0.value().to_owned());
+            let env_var = env_var.decrypt(self.key_broker)?;
+            let old_value =
+                environment_variables.insert(env_var.name().to_owned(), env_var.value().to_owned());
             anyhow::ensure!(old_value.is_none(), "Duplicate environment variable");
         }
         Ok(environment_variables)
     }
 
+    /// All environment variables as admins may see them: secret variables'
+    /// values are omitted.
+    pub async fn list_redacted(
+        &mut self,
+    ) -> anyhow::Result<BTreeMap<EnvVarName, Option<EnvVarValue>>> {
+        let query = Query::full_table_scan(ENVIRONMENT_VARIABLES_TABLE.clone(), Order::Asc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut environment_variables = BTreeMap::new();
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            let env_var: ParsedDocument<PersistedEnvironmentVariable> = doc.parse()?;
+            let value = if env_var.secret {
+                None
+            } else {
+                Some(env_var.decrypt(self.key_broker)?.into_value())
+            };
+            environment_variables.insert(env_var.name.clone(), value);
+        }
+        Ok(environment_variables)
+    }
+
     pub async fn create(
         &mut self,
         env_var: EnvironmentVariable,
         forbidden_names: &HashSet<EnvVarName>,
+    ) -> anyhow::Result<ResolvedDocumentId> {
+        self.insert(env_var, false, forbidden_names).await
+    }
+
+    /// Create a variable whose value can't be read back through admin APIs.
+    pub async fn create_secret(
+        &mut self,
+        env_var: EnvironmentVariable,
+        forbidden_names: &HashSet<EnvVarName>,
+    ) -> anyhow::Result<ResolvedDocumentId> {
+        self.insert(env_var, true, forbidden_names).await
+    }
+
+    async fn insert(
+        &mut self,
+        env_var: EnvironmentVariable,
+        secret: bool,
+        forbidden_names: &HashSet<EnvVarName>,
     ) -> anyhow::Result<ResolvedDocumentId> {
         if forbidden_names.contains(env_var.name()) {
             anyhow::bail!(env_var_name_forbidden(env_var.name()));
@@ -173,11 +227,38 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
         SystemMetadataModel::new_global(self.tx)
             .insert(
                 &ENVIRONMENT_VARIABLES_TABLE,
-                PersistedEnvironmentVariable(env_var).try_into()?,
+                PersistedEnvironmentVariable::encrypt(env_var, secret, self.key_broker)
+                    .try_into()?,
             )
             .await
     }
 
+    /// Re-encrypt values stored in plaintext or with a key derived from a
+    /// previous instance secret with the current key, returning how many
+    /// variables were rewritten.
+    pub async fn reencrypt_all(&mut self) -> anyhow::Result<usize> {
+        let query = Query::full_table_scan(ENVIRONMENT_VARIABLES_TABLE.clone(), Order::Asc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut stale = vec![];
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            let env_var: ParsedDocument<PersistedEnvironmentVariable> = doc.parse()?;
+            if env_var.needs_reencryption(self.key_broker) {
+                stale.push(env_var);
+            }
+        }
+        for env_var in &stale {
+            let reencrypted = PersistedEnvironmentVariable::encrypt(
+                env_var.decrypt(self.key_broker)?,
+                env_var.secret,
+                self.key_broker,
+            );
+            SystemMetadataModel::new_global(self.tx)
+                .replace(env_var.id(), reencrypted.try_into()?)
+                .await?;
+        }
+        Ok(stale.len())
+    }
+
     pub async fn delete(
         &mut self,
         name: &EnvVarName,
@@ -189,7 +270,7 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
             .delete(doc.id())
             .await?;
         let env_var: ParsedDocument<PersistedEnvironmentVariable> = document.parse()?;
-        Ok(Some(env_var.into_value().0))
+        Ok(Some(env_var.decrypt(self.key_broker)?))
     }
 
     pub async fn edit(
@@ -228,16 +309,21 @@ impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
                 anyhow::bail!(env_var_name_not_unique(Some(&new_env_var_name)));
             }
 
+            // Editing a variable keeps whether it's secret.
+            let previous_env_var: ParsedDocument<PersistedEnvironmentVariable> =
+                document.parse()?;
             SystemMetadataModel::new_global(self.tx)
                 .replace(
                     id,
-                    PersistedEnvironmentVariable(environment_variable).try_into()?,
+                    PersistedEnvironmentVariable::encrypt(
+                        environment_variable,
+                        previous_env_var.secret,
+                        self.key_broker,
+                    )
+                    .try_into()?,
                 )
                 .await?;
-
-            let previous_env_var: ParsedDocument<PersistedEnvironmentVariable> =
-                document.parse()?;
-            previous_env_vars.insert(id, previous_env_var.into_value().0);
+            previous_env_vars.insert(id, previous_env_var.decrypt(self.key_broker)?);
         }
 
         for (id, previous_env_var) in previous_env_vars {
@@ -290,6 +376,7 @@ mod tests {
         EnvironmentVariable,
     };
     use database::test_helpers::DbFixtures;
+    use keybroker::KeyBroker;
     use maplit::btreemap;
     use runtime::testing::TestRuntime;
 
@@ -301,8 +388,9 @@ mod tests {
     #[convex_macro::test_runtime]
     async fn test_create_get(rt: TestRuntime) -> anyhow::Result<()> {
         let database = DbFixtures::new_with_model(&rt).await?.db;
+        let key_broker = KeyBroker::dev();
         let mut tx = database.begin_system().await?;
-        let mut env_model = EnvironmentVariablesModel::new(&mut tx);
+        let mut env_model = EnvironmentVariablesModel::new(&mut tx, &key_broker);
         let name: EnvVarName = "hello".parse()?;
         let value: EnvVarValue = "world".parse()?;
         let env_var = EnvironmentVariable::new(name.clone(), value.clone());
@@ -314,6 +402,7 @@ mod tests {
     #[convex_macro::test_runtime]
     async fn test_preload(rt: TestRuntime) -> anyhow::Result<()> {
         let database = DbFixtures::new_with_model(&rt).await?.db;
+        let key_broker = KeyBroker::dev();
 
         let env_vars: BTreeMap<EnvVarName, EnvVarValue> = btreemap! {
             "hello".parse()? => "world".parse()?,
@@ -323,7 +412,7 @@ mod tests {
             let mut create_tx = database.begin_system().await?;
             for (name, value) in &env_vars {
                 let env_var = EnvironmentVariable::new(name.clone(), value.clone());
-                EnvironmentVariablesModel::new(&mut create_tx)
+                EnvironmentVariablesModel::new(&mut create_tx, &key_broker)
                     .create(env_var.clone(), &HashSet::new())
                     .await?;
             }
@@ -338,7 +427,7 @@ mod tests {
         for &names in test_cases {
             let preload_token = {
                 let mut preload_tx = database.begin_system().await?;
-                let preloaded = EnvironmentVariablesModel::new(&mut preload_tx)
+                let preloaded = EnvironmentVariablesModel::new(&mut preload_tx, &key_broker)
                     .preload()
                     .await?;
                 for name in names {
@@ -355,7 +444,7 @@ mod tests {
                 for name in names {
                     let name = name.parse()?;
                     assert_eq!(
-                        EnvironmentVariablesModel::new(&mut regular_tx)
+                        EnvironmentVariablesModel::new(&mut regular_tx, &key_broker)
                             .get(&name)
                             .await?
                             .map(|doc| doc.into_value().value),
@@ -373,4 +462,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[convex_macro::test_runtime]
+    async fn test_values_encrypted_at_rest(rt: TestRuntime) -> anyhow::Result<()> {
+        let database = DbFixtures::new_with_model(&rt).await?.db;
+        let key_broker = KeyBroker::dev();
+        let mut tx = database.begin_system().await?;
+        let mut env_model = EnvironmentVariablesModel::new(&mut tx, &key_broker);
+        let plain = EnvironmentVariable::new("PLAIN".parse()?, "visible value".parse()?);
+        let secret = EnvironmentVariable::new("SECRET".parse()?, "hidden value".parse()?);
+        let plain_id = env_model.create(plain.clone(), &HashSet::new()).await?;
+        let secret_id = env_model
+            .create_secret(secret.clone(), &HashSet::new())
+            .await?;
+
+        // Both values are readable by functions...
+        assert_eq!(
+            env_model.get(plain.name()).await?.unwrap().into_value(),
+            plain
+        );
+        assert_eq!(
+            env_model.get(secret.name()).await?.unwrap().into_value(),
+            secret
+        );
+        // ...but only the non-secret one is visible to admins.
+        let redacted = env_model.list_redacted().await?;
+        assert_eq!(redacted[plain.name()], Some(plain.value().clone()));
+        assert_eq!(redacted[secret.name()], None);
+
+        // Neither value is stored in plaintext.
+        for id in [plain_id, secret_id] {
+            let stored = format!("{:?}", tx.get(id).await?.unwrap().value());
+            assert!(!stored.contains("visible value"));
+            assert!(!stored.contains("hidden value"));
+        }
+        Ok(())
+    }
 }
@@ -5,25 +5,90 @@ pub use common::types::{
     EnvVarValue,
     EnvironmentVariable,
 };
+use keybroker::KeyBroker;
 use value::{
     obj,
     ConvexObject,
     ConvexValue,
 };
 
+/// How an environment variable's value is stored at rest.
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
-pub struct PersistedEnvironmentVariable(pub EnvironmentVariable);
+pub enum PersistedEnvVarValue {
+    /// Written before values were encrypted at rest. These are encrypted the
+    /// next time the backend starts.
+    Plaintext(EnvVarValue),
+    /// Encrypted with a key derived from the instance secret.
+    Encrypted(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct PersistedEnvironmentVariable {
+    pub name: EnvVarName,
+    pub value: PersistedEnvVarValue,
+    /// Secret variables are available to functions, but their values can't be
+    /// read back through admin APIs.
+    pub secret: bool,
+}
+
+impl PersistedEnvironmentVariable {
+    pub fn encrypt(env_var: EnvironmentVariable, secret: bool, key_broker: &KeyBroker) -> Self {
+        let EnvironmentVariable { name, value } = env_var;
+        Self {
+            name,
+            value: PersistedEnvVarValue::Encrypted(
+                key_broker.encrypt_env_var_value(value.as_ref()),
+            ),
+            secret,
+        }
+    }
+
+    pub fn decrypt(&self, key_broker: &KeyBroker) -> anyhow::Result<EnvironmentVariable> {
+        let value = match &self.value {
+            PersistedEnvVarValue::Plaintext(value) => value.clone(),
+            PersistedEnvVarValue::Encrypted(encrypted) => {
+                key_broker.decrypt_env_var_value(encrypted)?.parse()?
+            },
+        };
+        Ok(EnvironmentVariable::new(self.name.clone(), value))
+    }
+
+    /// Whether the value is stored in plaintext or with a key derived from a
+    /// previous instance secret.
+    pub fn needs_reencryption(&self, key_broker: &KeyBroker) -> bool {
+        match &self.value {
+            PersistedEnvVarValue::Plaintext(_) => true,
+            PersistedEnvVarValue::Encrypted(encrypted) => {
+                key_broker.env_var_value_needs_reencryption(encrypted)
+            },
+        }
+    }
+}
 
 impl TryFrom<PersistedEnvironmentVariable> for ConvexObject {
     type Error = anyhow::Error;
 
     fn try_from(
-        PersistedEnvironmentVariable(
-            EnvironmentVariable { name, value }
-        ): PersistedEnvironmentVariable,
+        PersistedEnvironmentVariable {
+            name,
+            value,
+            secret,
+        }: PersistedEnvironmentVariable,
     ) -> anyhow::Result<ConvexObject> {
-        obj!("name" => String::from(name), "value" => String::from(value))
+        match value {
+            PersistedEnvVarValue::Plaintext(value) => obj!(
+                "name" => String::from(name),
+                "value" => String::from(value),
+                "secret" => secret,
+            ),
+            PersistedEnvVarValue::Encrypted(encrypted) => obj!(
+                "name" => String::from(name),
+                "encryptedValue" => encrypted,
+                "secret" => secret,
+            ),
+        }
     }
 }
 
@@ -36,14 +101,24 @@ impl TryFrom<ConvexObject> for PersistedEnvironmentVariable {
             Some(ConvexValue::String(s)) => s.into(),
             v => anyhow::bail!("Invalid name field for EnvironmentVariable: {v:?}"),
         };
-        let value: String = match fields.remove("value") {
-            Some(ConvexValue::String(s)) => s.into(),
+        let value = match (fields.remove("value"), fields.remove("encryptedValue")) {
+            (Some(ConvexValue::String(s)), None) => {
+                PersistedEnvVarValue::Plaintext(String::from(s).parse()?)
+            },
+            (None, Some(ConvexValue::String(s))) => PersistedEnvVarValue::Encrypted(s.into()),
             v => anyhow::bail!("Invalid value field for EnvironmentVariable: {v:?}"),
         };
-        Ok(Self(EnvironmentVariable {
+        // Variables written before the secret flag existed aren't secret.
+        let secret = match fields.remove("secret") {
+            Some(ConvexValue::Boolean(b)) => b,
+            None => false,
+            v => anyhow::bail!("Invalid secret field for EnvironmentVariable: {v:?}"),
+        };
+        Ok(Self {
             name: name.parse()?,
-            value: value.parse()?,
-        }))
+            value,
+            secret,
+        })
     }
 }
 
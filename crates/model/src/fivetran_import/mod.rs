@@ -174,7 +174,7 @@ impl<'a, RT: Runtime> FivetranImportModel<'a, RT> {
             })?;
 
         let IndexConfig::Database {
-            developer_config: DeveloperDatabaseIndexConfig { fields },
+            developer_config: DeveloperDatabaseIndexConfig { fields, .. },
             ..
         } = index.config
         else {
@@ -248,7 +248,7 @@ impl<'a, RT: Runtime> FivetranImportModel<'a, RT> {
         }
 
         let IndexConfig::Database {
-            developer_config: DeveloperDatabaseIndexConfig { fields },
+            developer_config: DeveloperDatabaseIndexConfig { fields, .. },
             ..
         } = index.config
         else {
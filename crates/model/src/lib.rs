@@ -52,6 +52,10 @@ use backend_state::{
     BACKEND_STATE_TABLE,
 };
 use canonical_urls::CANONICAL_URLS_TABLE;
+use code_versions::{
+    CodeVersionsTable,
+    CODE_VERSIONS_TABLE,
+};
 use common::{
     bootstrap_model::{
         index::{
@@ -147,6 +151,10 @@ use modules::{
     MODULE_INDEX_BY_DELETED,
     MODULE_INDEX_BY_PATH,
 };
+use rate_limits::{
+    RateLimitsTable,
+    RATE_LIMITS_TABLE,
+};
 use scheduled_jobs::{
     ScheduledJobsTable,
     SCHEDULED_JOBS_INDEX,
@@ -154,6 +162,11 @@ use scheduled_jobs::{
     SCHEDULED_JOBS_INDEX_BY_UDF_PATH,
     SCHEDULED_JOBS_TABLE,
 };
+use schema_migrations::{
+    SchemaMigrationsTable,
+    SCHEMA_MIGRATIONS_INDEX_BY_TABLE_NAME,
+    SCHEMA_MIGRATIONS_TABLE,
+};
 use session_requests::{
     SessionRequestsTable,
     SESSION_REQUESTS_INDEX,
@@ -172,6 +185,14 @@ use udf_config::{
     UdfConfigTable,
     UDF_CONFIG_TABLE,
 };
+use usage::{
+    UsageQuotasTable,
+    UsageRollupsTable,
+    USAGE_QUOTAS_INDEX_BY_COMPONENT_PATH,
+    USAGE_QUOTAS_TABLE,
+    USAGE_ROLLUPS_INDEX_BY_KEY,
+    USAGE_ROLLUPS_TABLE,
+};
 pub use value::METADATA_PREFIX;
 use value::{
     TableName,
@@ -204,6 +225,7 @@ pub mod aws_lambda_versions;
 pub mod backend_info;
 pub mod backend_state;
 pub mod canonical_urls;
+pub mod code_versions;
 pub mod components;
 pub mod config;
 pub mod cron_jobs;
@@ -218,11 +240,14 @@ pub mod log_sinks;
 mod metrics;
 pub mod migrations;
 pub mod modules;
+pub mod rate_limits;
 pub mod scheduled_jobs;
+pub mod schema_migrations;
 pub mod session_requests;
 pub mod snapshot_imports;
 pub mod source_packages;
 pub mod udf_config;
+pub mod usage;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod test_helpers;
@@ -261,9 +286,14 @@ enum DefaultTableNumber {
     FunctionHandlesTable = 33,
     CanonicalUrls = 34,
     CronNextRun = 35,
+    UsageRollups = 36,
+    UsageQuotas = 37,
+    SchemaMigrations = 38,
+    CodeVersions = 39,
+    RateLimits = 40,
     // Keep this number and your user name up to date. The number makes it easy to know
     // what to use next. The username on the same line detects merge conflicts
-    // Next Number - 36 - nipunn
+    // Next Number - 41 - nipunn
 }
 
 impl From<DefaultTableNumber> for TableNumber {
@@ -305,6 +335,11 @@ impl From<DefaultTableNumber> for &'static dyn ErasedSystemTable {
             DefaultTableNumber::FunctionHandlesTable => &FunctionHandlesTable,
             DefaultTableNumber::CanonicalUrls => &CanonicalUrlsTable,
             DefaultTableNumber::CronNextRun => &CronNextRunTable,
+            DefaultTableNumber::UsageRollups => &UsageRollupsTable,
+            DefaultTableNumber::UsageQuotas => &UsageQuotasTable,
+            DefaultTableNumber::SchemaMigrations => &SchemaMigrationsTable,
+            DefaultTableNumber::CodeVersions => &CodeVersionsTable,
+            DefaultTableNumber::RateLimits => &RateLimitsTable,
         }
     }
 }
@@ -534,6 +569,11 @@ pub fn app_system_tables() -> Vec<&'static dyn ErasedSystemTable> {
         &LogSinksTable,
         &AwsLambdaVersionsTable,
         &BackendInfoTable,
+        &UsageRollupsTable,
+        &UsageQuotasTable,
+        &SchemaMigrationsTable,
+        &CodeVersionsTable,
+        &RateLimitsTable,
     ];
     system_tables.extend(component_system_tables());
     system_tables.extend(bootstrap_system_tables());
@@ -620,6 +660,11 @@ pub static FIRST_SEEN_TABLE: LazyLock<BTreeMap<TableName, DatabaseVersion>> = La
         COMPONENT_DEFINITIONS_TABLE.clone() => 100,
         FUNCTION_HANDLES_TABLE.clone() => 102,
         CANONICAL_URLS_TABLE.clone() => 116,
+        USAGE_ROLLUPS_TABLE.clone() => 120,
+        USAGE_QUOTAS_TABLE.clone() => 120,
+        SCHEMA_MIGRATIONS_TABLE.clone() => 121,
+        CODE_VERSIONS_TABLE.clone() => 122,
+        RATE_LIMITS_TABLE.clone() => 123,
     }
 });
 
@@ -645,6 +690,9 @@ pub static FIRST_SEEN_INDEX: LazyLock<BTreeMap<IndexName, DatabaseVersion>> = La
         COMPONENTS_BY_PARENT_INDEX.name() => 100,
         BY_COMPONENT_PATH_INDEX.name() => 102,
         EXPORTS_BY_REQUESTOR.name() => 110,
+        USAGE_ROLLUPS_INDEX_BY_KEY.name() => 120,
+        USAGE_QUOTAS_INDEX_BY_COMPONENT_PATH.name() => 120,
+        SCHEMA_MIGRATIONS_INDEX_BY_TABLE_NAME.name() => 121,
     }
 });
 
@@ -190,6 +190,49 @@ impl<'a, RT: Runtime> ModuleModel<'a, RT> {
         ModuleDiff::new(added_modules, removed_modules)
     }
 
+    /// Replace the component's application modules with previously stored
+    /// module metadata, e.g. when rolling back to an earlier push. The
+    /// metadata is written as-is since each module's source already lives in
+    /// its source package.
+    pub async fn restore(
+        &mut self,
+        component: ComponentId,
+        modules: Vec<ModuleMetadata>,
+    ) -> anyhow::Result<ModuleDiff> {
+        let mut added_modules = BTreeSet::new();
+        let mut remaining_modules: BTreeMap<_, _> = self
+            .get_application_metadata(component)
+            .await?
+            .into_iter()
+            .map(|module| (module.path.clone(), module.id()))
+            .collect();
+        for module in modules {
+            let existing_module_id = remaining_modules.remove(&module.path);
+            if existing_module_id.is_none() {
+                added_modules.insert(module.path.clone());
+            }
+            self.put_module_metadata(
+                existing_module_id,
+                CanonicalizedComponentModulePath {
+                    component,
+                    module_path: module.path,
+                },
+                module.source_package_id,
+                module.analyze_result,
+                module.environment,
+                module.sha256,
+            )
+            .await?;
+        }
+
+        let mut removed_modules = BTreeSet::new();
+        for (path, module_id) in remaining_modules {
+            removed_modules.insert(path.clone());
+            self.delete(component, module_id).await?;
+        }
+        ModuleDiff::new(added_modules, removed_modules)
+    }
+
     /// Returns the registered modules metadata, including system modules.
     #[fastrace::trace]
     pub async fn get_all_metadata(
@@ -0,0 +1,159 @@
+use std::{
+    collections::BTreeSet,
+    sync::LazyLock,
+};
+
+use common::{
+    document::{
+        ParseDocument,
+        ParsedDocument,
+    },
+    query::{
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+};
+use database::{
+    unauthorized_error,
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use value::{
+    TableName,
+    TableNamespace,
+};
+
+use crate::{
+    rate_limits::types::RateLimitPolicy,
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static RATE_LIMITS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_rate_limits"
+        .parse()
+        .expect("Invalid built-in rate limits table")
+});
+
+pub struct RateLimitsTable;
+impl SystemTable for RateLimitsTable {
+    type Metadata = RateLimitPolicy;
+
+    fn table_name() -> &'static TableName {
+        &RATE_LIMITS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![]
+    }
+}
+
+/// Rate limit policies for public functions, replaced wholesale on every push.
+pub struct RateLimitModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+}
+
+impl<'a, RT: Runtime> RateLimitModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
+        Self { tx }
+    }
+
+    /// Replace the current policies with `policies`, leaving unchanged
+    /// policies in place.
+    pub async fn put(&mut self, policies: Vec<RateLimitPolicy>) -> anyhow::Result<()> {
+        if !(self.tx.identity().is_admin() || self.tx.identity().is_system()) {
+            anyhow::bail!(unauthorized_error("put_rate_limits"));
+        }
+        let mut new_set: BTreeSet<_> = policies.into_iter().collect();
+        for existing in self.get_all().await? {
+            if !new_set.remove(&*existing) {
+                SystemMetadataModel::new_global(self.tx)
+                    .delete(existing.id())
+                    .await?;
+            }
+        }
+        for policy in new_set {
+            SystemMetadataModel::new_global(self.tx)
+                .insert(&RATE_LIMITS_TABLE, policy.try_into()?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_all(&mut self) -> anyhow::Result<Vec<ParsedDocument<RateLimitPolicy>>> {
+        let query = Query::full_table_scan(RATE_LIMITS_TABLE.clone(), Order::Asc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut policies = vec![];
+        while let Some(document) = query_stream.next(self.tx, None).await? {
+            policies.push(document.parse()?);
+        }
+        Ok(policies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use database::test_helpers::DbFixtures;
+    use keybroker::Identity;
+    use runtime::testing::TestRuntime;
+
+    use crate::{
+        rate_limits::{
+            types::{
+                RateLimitKey,
+                RateLimitPolicy,
+                RateLimitTarget,
+            },
+            RateLimitModel,
+        },
+        test_helpers::DbFixturesWithModel,
+    };
+
+    #[convex_macro::test_runtime]
+    async fn test_put_replaces_policies(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let send = RateLimitPolicy {
+            target: RateLimitTarget::Function("messages:send".parse()?),
+            key: RateLimitKey::Identity,
+            requests: 10,
+            period: Duration::from_secs(60),
+            burst: None,
+        };
+        let list = RateLimitPolicy {
+            target: RateLimitTarget::Module("messages".parse()?),
+            key: RateLimitKey::Ip,
+            requests: 100,
+            period: Duration::from_secs(1),
+            burst: Some(200),
+        };
+
+        let mut tx = db.begin_system().await?;
+        RateLimitModel::new(&mut tx)
+            .put(vec![send.clone(), list.clone()])
+            .await?;
+        db.commit(tx).await?;
+
+        let mut tx = db.begin_system().await?;
+        let before = RateLimitModel::new(&mut tx).get_all().await?;
+        assert_eq!(before.len(), 2);
+        RateLimitModel::new(&mut tx).put(vec![send.clone()]).await?;
+        db.commit(tx).await?;
+
+        let mut tx = db.begin_system().await?;
+        let after = RateLimitModel::new(&mut tx).get_all().await?;
+        assert_eq!(after.len(), 1);
+        assert_eq!(*after[0], send);
+        // The unchanged policy keeps its document.
+        assert!(before.iter().any(|p| p.id() == after[0].id()));
+
+        let mut tx = db.begin(Identity::Unknown(None)).await?;
+        assert!(RateLimitModel::new(&mut tx).put(vec![]).await.is_err());
+        Ok(())
+    }
+}
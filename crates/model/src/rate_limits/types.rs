@@ -0,0 +1,274 @@
+use std::{
+    fmt,
+    time::Duration,
+};
+
+use errors::ErrorMetadata;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sync_types::{
+    CanonicalizedModulePath,
+    CanonicalizedUdfPath,
+};
+use value::codegen_convex_serialization;
+
+/// The functions a rate limit policy applies to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum RateLimitTarget {
+    Function(CanonicalizedUdfPath),
+    Module(CanonicalizedModulePath),
+}
+
+impl RateLimitTarget {
+    pub fn matches(&self, udf_path: &CanonicalizedUdfPath) -> bool {
+        match self {
+            RateLimitTarget::Function(path) => path == udf_path,
+            RateLimitTarget::Module(module) => module == udf_path.module(),
+        }
+    }
+}
+
+impl fmt::Display for RateLimitTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitTarget::Function(path) => write!(f, "{}", String::from(path.clone())),
+            RateLimitTarget::Module(module) => write!(f, "{}", module.as_str()),
+        }
+    }
+}
+
+/// What a rate limit policy counts requests by. Each distinct key gets its own
+/// bucket.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum RateLimitKey {
+    /// The authenticated user's subject. Unauthenticated callers are counted
+    /// by IP address instead.
+    Identity,
+    /// The caller's IP address.
+    Ip,
+    /// The value of a request header, like an API key. Callers that don't send
+    /// the header share a single bucket.
+    Header(
+        /// Lowercased header name.
+        #[cfg_attr(
+            any(test, feature = "testing"),
+            proptest(strategy = "\"[a-z][a-z0-9-]{0,15}\"")
+        )]
+        String,
+    ),
+}
+
+/// A rate limit on public function calls, declared in the deploy config.
+/// Callers may make up to `burst` calls at once, which refill at a rate of
+/// `requests` per `period`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct RateLimitPolicy {
+    pub target: RateLimitTarget,
+    pub key: RateLimitKey,
+    #[cfg_attr(any(test, feature = "testing"), proptest(strategy = "1..=10_000u32"))]
+    pub requests: u32,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(
+            strategy = "proptest::strategy::Strategy::prop_map(1..=86_400_000u64, \
+                        Duration::from_millis)"
+        )
+    )]
+    pub period: Duration,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "proptest::option::of(1..=10_000u32)")
+    )]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitPolicy {
+    /// The time it takes to earn back a single call.
+    pub fn replenish_interval(&self) -> Duration {
+        self.period / self.requests
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst.unwrap_or(self.requests)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SerializedRateLimitKey {
+    Identity,
+    Ip,
+    Header { name: String },
+}
+
+/// The form a policy takes in the deploy config and in `_rate_limits`.
+/// Exactly one of `function` (e.g. `messages:send`) and `module` (e.g.
+/// `messages`) is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedRateLimitPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    pub key: SerializedRateLimitKey,
+    pub requests: u32,
+    pub period_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+fn invalid_policy(msg: String) -> anyhow::Error {
+    anyhow::anyhow!(ErrorMetadata::bad_request("InvalidRateLimitPolicy", msg))
+}
+
+impl TryFrom<SerializedRateLimitPolicy> for RateLimitPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedRateLimitPolicy) -> anyhow::Result<Self> {
+        let target = match (value.function, value.module) {
+            (Some(function), None) => {
+                RateLimitTarget::Function(function.parse().map_err(|e| {
+                    invalid_policy(format!("Invalid function path {function:?}: {e}"))
+                })?)
+            },
+            (None, Some(module)) => RateLimitTarget::Module(
+                module
+                    .parse()
+                    .map_err(|e| invalid_policy(format!("Invalid module path {module:?}: {e}")))?,
+            ),
+            _ => {
+                return Err(invalid_policy(
+                    "Rate limit policies must set exactly one of `function` and `module`"
+                        .to_string(),
+                ))
+            },
+        };
+        let key = match value.key {
+            SerializedRateLimitKey::Identity => RateLimitKey::Identity,
+            SerializedRateLimitKey::Ip => RateLimitKey::Ip,
+            SerializedRateLimitKey::Header { name } => {
+                if name.is_empty()
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    return Err(invalid_policy(format!("Invalid header name {name:?}")));
+                }
+                RateLimitKey::Header(name.to_ascii_lowercase())
+            },
+        };
+        if value.requests == 0 {
+            return Err(invalid_policy(format!(
+                "Rate limit for {target} must allow at least one request"
+            )));
+        }
+        if value.burst == Some(0) {
+            return Err(invalid_policy(format!(
+                "Rate limit burst for {target} must be at least one"
+            )));
+        }
+        let period = Duration::from_millis(value.period_ms);
+        if (period / value.requests).is_zero() {
+            return Err(invalid_policy(format!(
+                "Rate limit period for {target} is too short for {} requests",
+                value.requests
+            )));
+        }
+        Ok(Self {
+            target,
+            key,
+            requests: value.requests,
+            period,
+            burst: value.burst,
+        })
+    }
+}
+
+impl From<RateLimitPolicy> for SerializedRateLimitPolicy {
+    fn from(value: RateLimitPolicy) -> Self {
+        let (function, module) = match value.target {
+            RateLimitTarget::Function(path) => (Some(String::from(path)), None),
+            RateLimitTarget::Module(module) => (None, Some(String::from(module))),
+        };
+        Self {
+            function,
+            module,
+            key: match value.key {
+                RateLimitKey::Identity => SerializedRateLimitKey::Identity,
+                RateLimitKey::Ip => SerializedRateLimitKey::Ip,
+                RateLimitKey::Header(name) => SerializedRateLimitKey::Header { name },
+            },
+            requests: value.requests,
+            period_ms: value.period.as_millis() as u64,
+            burst: value.burst,
+        }
+    }
+}
+
+codegen_convex_serialization!(RateLimitPolicy, SerializedRateLimitPolicy);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        RateLimitKey,
+        RateLimitPolicy,
+        RateLimitTarget,
+        SerializedRateLimitPolicy,
+    };
+
+    fn parse(value: serde_json::Value) -> anyhow::Result<RateLimitPolicy> {
+        serde_json::from_value::<SerializedRateLimitPolicy>(value)?.try_into()
+    }
+
+    #[test]
+    fn test_parse_policy() -> anyhow::Result<()> {
+        let policy = parse(json!({
+            "module": "messages",
+            "key": { "type": "header", "name": "X-Api-Key" },
+            "requests": 10,
+            "periodMs": 1000,
+        }))?;
+        assert_eq!(
+            policy.target,
+            RateLimitTarget::Module("messages.js".parse()?)
+        );
+        assert_eq!(policy.key, RateLimitKey::Header("x-api-key".to_string()));
+        assert_eq!(policy.burst(), 10);
+        assert!(policy.target.matches(&"messages.js:send".parse()?));
+
+        let policy = parse(json!({
+            "function": "messages:send",
+            "key": { "type": "identity" },
+            "requests": 1,
+            "periodMs": 60000,
+            "burst": 5,
+        }))?;
+        assert!(policy.target.matches(&"messages:send".parse()?));
+        assert!(!policy.target.matches(&"messages:list".parse()?));
+
+        assert!(parse(json!({
+            "function": "messages:send",
+            "module": "messages",
+            "key": { "type": "ip" },
+            "requests": 1,
+            "periodMs": 1000,
+        }))
+        .is_err());
+        assert!(parse(json!({
+            "function": "messages:send",
+            "key": { "type": "ip" },
+            "requests": 0,
+            "periodMs": 1000,
+        }))
+        .is_err());
+        Ok(())
+    }
+}
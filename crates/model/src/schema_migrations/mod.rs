@@ -0,0 +1,302 @@
+use std::{
+    collections::BTreeSet,
+    sync::LazyLock,
+};
+
+use anyhow::Context;
+use common::{
+    document::{
+        ParseDocument,
+        ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
+    query::{
+        IndexRange,
+        IndexRangeExpression,
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+};
+use database::{
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use errors::ErrorMetadata;
+use sync_types::CanonicalizedUdfPath;
+use value::{
+    id_v6::DeveloperDocumentId,
+    ConvexValue,
+    FieldPath,
+    ResolvedDocumentId,
+    TableName,
+    TableNamespace,
+};
+
+use crate::{
+    schema_migrations::types::{
+        SchemaMigration,
+        SchemaMigrationState,
+    },
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static SCHEMA_MIGRATIONS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_schema_migrations"
+        .parse()
+        .expect("Invalid built-in schema migrations table")
+});
+
+static TABLE_NAME_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "tableName".parse().expect("invalid tableName field"));
+
+pub static SCHEMA_MIGRATIONS_INDEX_BY_TABLE_NAME: LazyLock<SystemIndex<SchemaMigrationsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_table_name",
+            [&TABLE_NAME_FIELD, &CREATION_TIME_FIELD_PATH],
+        )
+        .unwrap()
+    });
+
+pub struct SchemaMigrationsTable;
+impl SystemTable for SchemaMigrationsTable {
+    type Metadata = SchemaMigration;
+
+    fn table_name() -> &'static TableName {
+        &SCHEMA_MIGRATIONS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![SCHEMA_MIGRATIONS_INDEX_BY_TABLE_NAME.clone()]
+    }
+}
+
+pub struct SchemaMigrationModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+}
+
+impl<'a, RT: Runtime> SchemaMigrationModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
+        Self { tx }
+    }
+
+    /// All migrations, oldest first.
+    pub async fn list(&mut self) -> anyhow::Result<Vec<ParsedDocument<SchemaMigration>>> {
+        let query = Query::full_table_scan(SCHEMA_MIGRATIONS_TABLE.clone(), Order::Asc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut migrations = Vec::new();
+        while let Some(document) = query_stream.next(self.tx, None).await? {
+            migrations.push(document.parse()?);
+        }
+        Ok(migrations)
+    }
+
+    pub async fn get(
+        &mut self,
+        id: DeveloperDocumentId,
+    ) -> anyhow::Result<Option<ParsedDocument<SchemaMigration>>> {
+        let query = Query::get(SCHEMA_MIGRATIONS_TABLE.clone(), id);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|doc| doc.parse())
+            .transpose()
+    }
+
+    async fn list_for_table(
+        &mut self,
+        table_name: &TableName,
+    ) -> anyhow::Result<Vec<ParsedDocument<SchemaMigration>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: SCHEMA_MIGRATIONS_INDEX_BY_TABLE_NAME.name(),
+            range: vec![IndexRangeExpression::Eq(
+                TABLE_NAME_FIELD.clone(),
+                ConvexValue::try_from(table_name.to_string())?.into(),
+            )],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut migrations = Vec::new();
+        while let Some(document) = query_stream.next(self.tx, None).await? {
+            migrations.push(document.parse()?);
+        }
+        Ok(migrations)
+    }
+
+    /// Start migrating `table_name` with the mutation at `udf_path`. Only one
+    /// migration may be active on a table at a time.
+    pub async fn start(
+        &mut self,
+        table_name: TableName,
+        udf_path: CanonicalizedUdfPath,
+        batch_size: u64,
+        dry_run: bool,
+    ) -> anyhow::Result<ResolvedDocumentId> {
+        anyhow::ensure!(
+            batch_size > 0,
+            ErrorMetadata::bad_request(
+                "InvalidSchemaMigrationBatchSize",
+                "Schema migration batch size must be greater than 0",
+            )
+        );
+        if self
+            .list_for_table(&table_name)
+            .await?
+            .iter()
+            .any(|migration| migration.state.is_active())
+        {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "SchemaMigrationAlreadyActive",
+                format!("Table {table_name} already has an active schema migration"),
+            ));
+        }
+        let migration = SchemaMigration {
+            table_name,
+            udf_path,
+            batch_size,
+            dry_run,
+            state: SchemaMigrationState::Running,
+            cursor: None,
+            documents_processed: 0,
+            documents_changed: 0,
+        };
+        SystemMetadataModel::new_global(self.tx)
+            .insert(&SCHEMA_MIGRATIONS_TABLE, migration.try_into()?)
+            .await
+    }
+
+    /// The oldest migration the worker should make progress on, if any.
+    pub async fn next_running(
+        &mut self,
+    ) -> anyhow::Result<Option<ParsedDocument<SchemaMigration>>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .find(|migration| migration.state == SchemaMigrationState::Running))
+    }
+
+    /// Tables with a running or paused migration.
+    pub async fn active_tables(&mut self) -> anyhow::Result<BTreeSet<TableName>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|migration| migration.state.is_active())
+            .map(|migration| migration.into_value().table_name)
+            .collect())
+    }
+
+    pub async fn replace(
+        &mut self,
+        id: ResolvedDocumentId,
+        migration: SchemaMigration,
+    ) -> anyhow::Result<()> {
+        SystemMetadataModel::new_global(self.tx)
+            .replace(id, migration.try_into()?)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn pause(&mut self, id: DeveloperDocumentId) -> anyhow::Result<()> {
+        self.transition(id, |state| match state {
+            SchemaMigrationState::Running => Some(SchemaMigrationState::Paused),
+            _ => None,
+        })
+        .await
+    }
+
+    pub async fn resume(&mut self, id: DeveloperDocumentId) -> anyhow::Result<()> {
+        self.transition(id, |state| match state {
+            SchemaMigrationState::Paused => Some(SchemaMigrationState::Running),
+            _ => None,
+        })
+        .await
+    }
+
+    pub async fn cancel(&mut self, id: DeveloperDocumentId) -> anyhow::Result<()> {
+        self.transition(id, |state| {
+            state.is_active().then_some(SchemaMigrationState::Canceled)
+        })
+        .await
+    }
+
+    async fn transition(
+        &mut self,
+        id: DeveloperDocumentId,
+        next_state: impl FnOnce(&SchemaMigrationState) -> Option<SchemaMigrationState>,
+    ) -> anyhow::Result<()> {
+        let (id, mut migration) = self
+            .get(id)
+            .await?
+            .with_context(|| {
+                ErrorMetadata::not_found(
+                    "SchemaMigrationNotFound",
+                    format!("Schema migration {id} not found"),
+                )
+            })?
+            .into_id_and_value();
+        let Some(state) = next_state(&migration.state) else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidSchemaMigrationState",
+                format!("Schema migration is {}", migration.state),
+            ));
+        };
+        migration.state = state;
+        self.replace(id, migration).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use database::test_helpers::DbFixtures;
+    use errors::ErrorMetadataAnyhowExt;
+    use keybroker::Identity;
+    use runtime::testing::TestRuntime;
+    use value::TableName;
+
+    use crate::{
+        schema_migrations::{
+            types::SchemaMigrationState,
+            SchemaMigrationModel,
+        },
+        test_helpers::DbFixturesWithModel,
+    };
+
+    #[convex_macro::test_runtime]
+    async fn test_state_transitions(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let mut tx = db.begin(Identity::system()).await?;
+        let table_name: TableName = "users".parse()?;
+        let udf_path = "migrations:addEmail".parse()?;
+        let id = SchemaMigrationModel::new(&mut tx)
+            .start(table_name.clone(), udf_path, 100, false)
+            .await?;
+        let err = SchemaMigrationModel::new(&mut tx)
+            .start(table_name.clone(), "migrations:other".parse()?, 100, false)
+            .await
+            .unwrap_err();
+        assert_eq!(err.short_msg(), "SchemaMigrationAlreadyActive");
+
+        let mut model = SchemaMigrationModel::new(&mut tx);
+        model.pause(id.into()).await?;
+        assert_eq!(
+            model.get(id.into()).await?.unwrap().state,
+            SchemaMigrationState::Paused
+        );
+        assert!(model.pause(id.into()).await.is_err());
+        assert!(model.next_running().await?.is_none());
+        model.resume(id.into()).await?;
+        assert!(model.next_running().await?.is_some());
+        model.cancel(id.into()).await?;
+        assert!(model.active_tables().await?.is_empty());
+        assert!(model.resume(id.into()).await.is_err());
+        Ok(())
+    }
+}
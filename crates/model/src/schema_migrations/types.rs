@@ -0,0 +1,161 @@
+use std::fmt::{
+    self,
+    Display,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sync_types::CanonicalizedUdfPath;
+use value::{
+    codegen_convex_serialization,
+    id_v6::DeveloperDocumentId,
+    TableName,
+};
+
+/// A migration that runs a deployment-declared mutation over every document
+/// of a table in the root component. The worker walks the table in `_id`
+/// order, one batch per transaction, and records the id of the last document
+/// it processed in `cursor` so it can resume after a restart or pause.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SchemaMigration {
+    pub table_name: TableName,
+    /// Mutation called once per document with the document as its only
+    /// argument.
+    pub udf_path: CanonicalizedUdfPath,
+    pub batch_size: u64,
+    /// Run the mutation over every document but discard its writes, only
+    /// recording how many documents it would have changed.
+    pub dry_run: bool,
+    pub state: SchemaMigrationState,
+    /// Id of the last document processed, if any.
+    pub cursor: Option<DeveloperDocumentId>,
+    pub documents_processed: u64,
+    pub documents_changed: u64,
+}
+
+/// The migration state machine. A new migration starts as `Running` and the
+/// valid transitions are:
+///
+/// - Running -> Paused, Paused -> Running: an admin paused or resumed it.
+/// - Running -> Completed: the worker reached the end of the table.
+/// - Running -> Failed: the migration mutation threw on some document.
+/// - Running,Paused -> Canceled: an admin canceled it.
+///
+/// Completed, Failed, and Canceled are terminal states.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum SchemaMigrationState {
+    Running,
+    Paused,
+    Completed,
+    Failed { error: String },
+    Canceled,
+}
+
+impl SchemaMigrationState {
+    /// Active migrations hold back enforcement of pending schemas that cover
+    /// their table.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Running | Self::Paused)
+    }
+}
+
+impl Display for SchemaMigrationState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "running"),
+            Self::Paused => write!(f, "paused"),
+            Self::Completed => write!(f, "completed"),
+            Self::Failed { .. } => write!(f, "failed"),
+            Self::Canceled => write!(f, "canceled"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[serde(rename_all = "camelCase")]
+pub enum SerializedSchemaMigrationState {
+    Running,
+    Paused,
+    Completed,
+    Failed { error: String },
+    Canceled,
+}
+
+impl From<SchemaMigrationState> for SerializedSchemaMigrationState {
+    fn from(value: SchemaMigrationState) -> Self {
+        match value {
+            SchemaMigrationState::Running => Self::Running,
+            SchemaMigrationState::Paused => Self::Paused,
+            SchemaMigrationState::Completed => Self::Completed,
+            SchemaMigrationState::Failed { error } => Self::Failed { error },
+            SchemaMigrationState::Canceled => Self::Canceled,
+        }
+    }
+}
+
+impl From<SerializedSchemaMigrationState> for SchemaMigrationState {
+    fn from(value: SerializedSchemaMigrationState) -> Self {
+        match value {
+            SerializedSchemaMigrationState::Running => Self::Running,
+            SerializedSchemaMigrationState::Paused => Self::Paused,
+            SerializedSchemaMigrationState::Completed => Self::Completed,
+            SerializedSchemaMigrationState::Failed { error } => Self::Failed { error },
+            SerializedSchemaMigrationState::Canceled => Self::Canceled,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedSchemaMigration {
+    table_name: String,
+    udf_path: String,
+    batch_size: i64,
+    dry_run: bool,
+    state: SerializedSchemaMigrationState,
+    cursor: Option<String>,
+    documents_processed: i64,
+    documents_changed: i64,
+}
+
+impl From<SchemaMigration> for SerializedSchemaMigration {
+    fn from(value: SchemaMigration) -> Self {
+        Self {
+            table_name: value.table_name.to_string(),
+            udf_path: String::from(value.udf_path),
+            batch_size: value.batch_size as i64,
+            dry_run: value.dry_run,
+            state: value.state.into(),
+            cursor: value.cursor.map(|id| id.encode()),
+            documents_processed: value.documents_processed as i64,
+            documents_changed: value.documents_changed as i64,
+        }
+    }
+}
+
+impl TryFrom<SerializedSchemaMigration> for SchemaMigration {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedSchemaMigration) -> Result<Self, Self::Error> {
+        Ok(Self {
+            table_name: value.table_name.parse()?,
+            udf_path: value.udf_path.parse()?,
+            batch_size: value.batch_size as u64,
+            dry_run: value.dry_run,
+            state: value.state.into(),
+            cursor: value
+                .cursor
+                .map(|id| DeveloperDocumentId::decode(&id))
+                .transpose()?,
+            documents_processed: value.documents_processed as u64,
+            documents_changed: value.documents_changed as u64,
+        })
+    }
+}
+
+codegen_convex_serialization!(SchemaMigration, SerializedSchemaMigration);
@@ -33,7 +33,6 @@ pub mod types;
 
 use types::{
     SessionRequestIdentifier,
-    SessionRequestOutcome,
     SessionRequestRecord,
 };
 
@@ -91,7 +90,7 @@ impl<'a, RT: Runtime> SessionRequestModel<'a, RT> {
         &mut self,
         request_identifier: &SessionRequestIdentifier,
         identity: Identity,
-    ) -> anyhow::Result<Option<(Timestamp, SessionRequestOutcome)>> {
+    ) -> anyhow::Result<Option<(Timestamp, SessionRequestRecord)>> {
         // We only expect this function to be called by the framework as part
         // of a mutation UDF. We require passing in a system identity to confirm
         // that the caller isn't letting a user call this directly.
@@ -140,9 +139,7 @@ impl<'a, RT: Runtime> SessionRequestModel<'a, RT> {
             (doc.parse()?, ts)
         };
 
-        let outcome = doc.into_value().outcome;
-
-        Ok(Some((ts, outcome)))
+        Ok(Some((ts, doc.into_value())))
     }
 
     pub async fn record_session_request(
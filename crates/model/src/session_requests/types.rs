@@ -48,18 +48,26 @@ pub struct SessionRequestRecord {
     /// Non-permission-granting representation of the identity input to the
     /// mutation.
     pub identity: InertIdentity,
+
+    /// Component and UDF path of the mutation. Missing for records written
+    /// before it was tracked.
+    pub function_path: Option<String>,
 }
 
 impl TryFrom<SessionRequestRecord> for ConvexObject {
     type Error = anyhow::Error;
 
     fn try_from(request: SessionRequestRecord) -> anyhow::Result<Self> {
-        obj!(
+        let mut object = obj!(
             "sessionId" => request.session_id.to_string(),
             "requestId" => (request.request_id as i64),
             "outcome" =>  ConvexValue::Object(request.outcome.try_into()?),
             "identity" => request.identity.to_string(),
-        )
+        )?;
+        if let Some(function_path) = request.function_path {
+            object = object.shallow_merge(obj!("functionPath" => function_path)?)?;
+        }
+        Ok(object)
     }
 }
 
@@ -85,12 +93,18 @@ impl TryFrom<ConvexObject> for SessionRequestRecord {
             Some(ConvexValue::String(s)) => s.to_string().parse()?,
             v => anyhow::bail!("Invalid identity field for SessionRequest: {:?}", v),
         };
+        let function_path = match fields.remove("functionPath") {
+            Some(ConvexValue::String(s)) => Some(s.to_string()),
+            None => None,
+            v => anyhow::bail!("Invalid functionPath field for SessionRequest: {:?}", v),
+        };
 
         Ok(SessionRequestRecord {
             session_id,
             request_id,
             outcome,
             identity,
+            function_path,
         })
     }
 }
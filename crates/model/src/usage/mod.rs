@@ -0,0 +1,413 @@
+use std::sync::LazyLock;
+
+use common::{
+    document::{
+        ParseDocument,
+        ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
+    query::{
+        IndexRange,
+        IndexRangeExpression,
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+};
+use database::{
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use value::{
+    ConvexValue,
+    FieldPath,
+    TableName,
+    TableNamespace,
+};
+
+use crate::{
+    usage::types::{
+        UsageMetrics,
+        UsageQuota,
+        UsageRollup,
+        UsageRollupKey,
+    },
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static USAGE_ROLLUPS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_usage_rollups"
+        .parse()
+        .expect("Invalid built-in usage rollups table")
+});
+
+pub static USAGE_QUOTAS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_usage_quotas"
+        .parse()
+        .expect("Invalid built-in usage quotas table")
+});
+
+static HOUR_START_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "hourStart".parse().expect("invalid hourStart field"));
+static COMPONENT_PATH_FIELD: LazyLock<FieldPath> = LazyLock::new(|| {
+    "componentPath"
+        .parse()
+        .expect("invalid componentPath field")
+});
+static UDF_ID_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "udfId".parse().expect("invalid udfId field"));
+static TABLE_NAME_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "tableName".parse().expect("invalid tableName field"));
+
+pub static USAGE_ROLLUPS_INDEX_BY_KEY: LazyLock<SystemIndex<UsageRollupsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_key",
+            [
+                &HOUR_START_FIELD,
+                &COMPONENT_PATH_FIELD,
+                &UDF_ID_FIELD,
+                &TABLE_NAME_FIELD,
+                &CREATION_TIME_FIELD_PATH,
+            ],
+        )
+        .unwrap()
+    });
+
+pub static USAGE_QUOTAS_INDEX_BY_COMPONENT_PATH: LazyLock<SystemIndex<UsageQuotasTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_component_path",
+            [&COMPONENT_PATH_FIELD, &CREATION_TIME_FIELD_PATH],
+        )
+        .unwrap()
+    });
+
+pub struct UsageRollupsTable;
+impl SystemTable for UsageRollupsTable {
+    type Metadata = UsageRollup;
+
+    fn table_name() -> &'static TableName {
+        &USAGE_ROLLUPS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![USAGE_ROLLUPS_INDEX_BY_KEY.clone()]
+    }
+}
+
+pub struct UsageQuotasTable;
+impl SystemTable for UsageQuotasTable {
+    type Metadata = UsageQuota;
+
+    fn table_name() -> &'static TableName {
+        &USAGE_QUOTAS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![USAGE_QUOTAS_INDEX_BY_COMPONENT_PATH.clone()]
+    }
+}
+
+fn optional_string(s: &Option<String>) -> anyhow::Result<ConvexValue> {
+    Ok(match s {
+        Some(s) => ConvexValue::try_from(s.clone())?,
+        None => ConvexValue::Null,
+    })
+}
+
+pub struct UsageModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+}
+
+impl<'a, RT: Runtime> UsageModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
+        Self { tx }
+    }
+
+    async fn get_rollup(
+        &mut self,
+        key: &UsageRollupKey,
+    ) -> anyhow::Result<Option<ParsedDocument<UsageRollup>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: USAGE_ROLLUPS_INDEX_BY_KEY.name(),
+            range: vec![
+                IndexRangeExpression::Eq(
+                    HOUR_START_FIELD.clone(),
+                    ConvexValue::from(key.hour_start).into(),
+                ),
+                IndexRangeExpression::Eq(
+                    COMPONENT_PATH_FIELD.clone(),
+                    optional_string(&key.component_path)?.into(),
+                ),
+                IndexRangeExpression::Eq(
+                    UDF_ID_FIELD.clone(),
+                    optional_string(&key.udf_id)?.into(),
+                ),
+                IndexRangeExpression::Eq(
+                    TABLE_NAME_FIELD.clone(),
+                    optional_string(&key.table_name)?.into(),
+                ),
+            ],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|doc| doc.parse())
+            .transpose()
+    }
+
+    /// Add `metrics` to the rollup for `key`, creating it if this is the first
+    /// usage recorded for the key.
+    pub async fn add_to_rollup(
+        &mut self,
+        key: UsageRollupKey,
+        metrics: &UsageMetrics,
+    ) -> anyhow::Result<()> {
+        match self.get_rollup(&key).await? {
+            Some(doc) => {
+                let (id, mut rollup) = doc.into_id_and_value();
+                rollup.metrics.merge(metrics);
+                SystemMetadataModel::new_global(self.tx)
+                    .replace(id, rollup.try_into()?)
+                    .await?;
+            },
+            None => {
+                let rollup = UsageRollup {
+                    key,
+                    metrics: metrics.clone(),
+                };
+                SystemMetadataModel::new_global(self.tx)
+                    .insert(&USAGE_ROLLUPS_TABLE, rollup.try_into()?)
+                    .await?;
+            },
+        }
+        Ok(())
+    }
+
+    /// All rollups for hours starting in `[start_hour, end_hour)`, ordered by
+    /// hour.
+    pub async fn rollups_in_range(
+        &mut self,
+        start_hour: i64,
+        end_hour: i64,
+    ) -> anyhow::Result<Vec<ParsedDocument<UsageRollup>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: USAGE_ROLLUPS_INDEX_BY_KEY.name(),
+            range: vec![
+                IndexRangeExpression::Gte(
+                    HOUR_START_FIELD.clone(),
+                    ConvexValue::from(start_hour).into(),
+                ),
+                IndexRangeExpression::Lt(
+                    HOUR_START_FIELD.clone(),
+                    ConvexValue::from(end_hour).into(),
+                ),
+            ],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut rollups = vec![];
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            rollups.push(doc.parse()?);
+        }
+        Ok(rollups)
+    }
+
+    /// Delete up to `limit` rollups for hours before `hour`, returning how many
+    /// were deleted.
+    pub async fn delete_rollups_before(
+        &mut self,
+        hour: i64,
+        limit: usize,
+    ) -> anyhow::Result<usize> {
+        let query = Query::index_range(IndexRange {
+            index_name: USAGE_ROLLUPS_INDEX_BY_KEY.name(),
+            range: vec![IndexRangeExpression::Lt(
+                HOUR_START_FIELD.clone(),
+                ConvexValue::from(hour).into(),
+            )],
+            order: Order::Asc,
+        })
+        .limit(limit);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut ids = vec![];
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            ids.push(doc.id());
+        }
+        for id in &ids {
+            SystemMetadataModel::new_global(self.tx).delete(*id).await?;
+        }
+        Ok(ids.len())
+    }
+
+    async fn get_quota_doc(
+        &mut self,
+        component_path: &Option<String>,
+    ) -> anyhow::Result<Option<ParsedDocument<UsageQuota>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: USAGE_QUOTAS_INDEX_BY_COMPONENT_PATH.name(),
+            range: vec![IndexRangeExpression::Eq(
+                COMPONENT_PATH_FIELD.clone(),
+                optional_string(component_path)?.into(),
+            )],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|doc| doc.parse())
+            .transpose()
+    }
+
+    pub async fn list_quotas(&mut self) -> anyhow::Result<Vec<UsageQuota>> {
+        let query = Query::full_table_scan(USAGE_QUOTAS_TABLE.clone(), Order::Asc);
+        let mut query_stream = ResolvedQuery::new(self.tx, TableNamespace::Global, query)?;
+        let mut quotas = vec![];
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            let quota: ParsedDocument<UsageQuota> = doc.parse()?;
+            quotas.push(quota.into_value());
+        }
+        Ok(quotas)
+    }
+
+    /// Set the quota for a component, replacing any existing quota. A quota
+    /// without any limits removes the component's quota.
+    pub async fn set_quota(&mut self, quota: UsageQuota) -> anyhow::Result<()> {
+        let existing = self.get_quota_doc(&quota.component_path).await?;
+        let has_limits = quota.max_function_calls_per_day.is_some()
+            || quota.max_database_bandwidth_bytes_per_day.is_some();
+        match (existing, has_limits) {
+            (Some(doc), true) => {
+                SystemMetadataModel::new_global(self.tx)
+                    .replace(doc.id(), quota.try_into()?)
+                    .await?;
+            },
+            (Some(doc), false) => {
+                SystemMetadataModel::new_global(self.tx)
+                    .delete(doc.id())
+                    .await?;
+            },
+            (None, true) => {
+                SystemMetadataModel::new_global(self.tx)
+                    .insert(&USAGE_QUOTAS_TABLE, quota.try_into()?)
+                    .await?;
+            },
+            (None, false) => {},
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use database::test_helpers::DbFixtures;
+    use keybroker::Identity;
+    use runtime::testing::TestRuntime;
+
+    use super::{
+        types::{
+            UsageMetrics,
+            UsageQuota,
+            UsageRollupKey,
+        },
+        UsageModel,
+    };
+    use crate::test_helpers::DbFixturesWithModel;
+
+    fn key(hour_start: i64, udf_id: &str) -> UsageRollupKey {
+        UsageRollupKey {
+            hour_start,
+            component_path: None,
+            udf_id: Some(udf_id.to_string()),
+            table_name: None,
+        }
+    }
+
+    #[convex_macro::test_runtime]
+    async fn test_rollups_merge(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let calls = UsageMetrics {
+            function_calls: 2,
+            database_egress_bytes: 100,
+            ..Default::default()
+        };
+        let mut tx = db.begin(Identity::system()).await?;
+        UsageModel::new(&mut tx)
+            .add_to_rollup(key(3600, "a.js:f"), &calls)
+            .await?;
+        UsageModel::new(&mut tx)
+            .add_to_rollup(key(3600, "a.js:f"), &calls)
+            .await?;
+        UsageModel::new(&mut tx)
+            .add_to_rollup(key(7200, "a.js:f"), &calls)
+            .await?;
+        db.commit(tx).await?;
+
+        let mut tx = db.begin(Identity::system()).await?;
+        let rollups = UsageModel::new(&mut tx).rollups_in_range(0, 7200).await?;
+        assert_eq!(rollups.len(), 1);
+        assert_eq!(rollups[0].metrics.function_calls, 4);
+        assert_eq!(rollups[0].metrics.database_egress_bytes, 200);
+        assert_eq!(
+            UsageModel::new(&mut tx)
+                .rollups_in_range(0, 10800)
+                .await?
+                .len(),
+            2
+        );
+
+        assert_eq!(
+            UsageModel::new(&mut tx)
+                .delete_rollups_before(7200, 10)
+                .await?,
+            1
+        );
+        assert_eq!(
+            UsageModel::new(&mut tx)
+                .rollups_in_range(0, 10800)
+                .await?
+                .len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[convex_macro::test_runtime]
+    async fn test_set_quota(rt: TestRuntime) -> anyhow::Result<()> {
+        let db = DbFixtures::new_with_model(&rt).await?.db;
+        let mut tx = db.begin(Identity::system()).await?;
+        let quota = UsageQuota {
+            component_path: Some("widgets".to_string()),
+            max_function_calls_per_day: Some(10),
+            max_database_bandwidth_bytes_per_day: None,
+        };
+        UsageModel::new(&mut tx).set_quota(quota.clone()).await?;
+        assert_eq!(UsageModel::new(&mut tx).list_quotas().await?, vec![quota]);
+
+        let updated = UsageQuota {
+            component_path: Some("widgets".to_string()),
+            max_function_calls_per_day: None,
+            max_database_bandwidth_bytes_per_day: Some(1 << 20),
+        };
+        UsageModel::new(&mut tx).set_quota(updated.clone()).await?;
+        assert_eq!(UsageModel::new(&mut tx).list_quotas().await?, vec![updated]);
+
+        UsageModel::new(&mut tx)
+            .set_quota(UsageQuota {
+                component_path: Some("widgets".to_string()),
+                max_function_calls_per_day: None,
+                max_database_bandwidth_bytes_per_day: None,
+            })
+            .await?;
+        assert!(UsageModel::new(&mut tx).list_quotas().await?.is_empty());
+        Ok(())
+    }
+}
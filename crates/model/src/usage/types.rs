@@ -0,0 +1,285 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::codegen_convex_serialization;
+
+pub const SECONDS_PER_HOUR: u64 = 60 * 60;
+pub const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+
+/// Identifies the hourly bucket a [`UsageRollup`] aggregates. Usage is keyed by
+/// component, and additionally by function and/or table when the underlying
+/// usage event has them. For example, database bandwidth is attributed to both
+/// the function that read or wrote the data and the table it touched, while
+/// table storage is only attributed to a table.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct UsageRollupKey {
+    /// Start of the hour, in seconds since the Unix epoch.
+    pub hour_start: i64,
+    /// `None` for the root component.
+    pub component_path: Option<String>,
+    pub udf_id: Option<String>,
+    pub table_name: Option<String>,
+}
+
+impl UsageRollupKey {
+    /// Round `unix_secs` down to the start of its hour.
+    pub fn hour_containing(unix_secs: u64) -> i64 {
+        (unix_secs - unix_secs % SECONDS_PER_HOUR) as i64
+    }
+}
+
+/// Usage accumulated over an hour. Counters are summed as events arrive, while
+/// the storage gauges record the latest value observed during the hour.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct UsageMetrics {
+    pub function_calls: u64,
+    pub function_failures: u64,
+    pub function_duration_millis: u64,
+    /// Memory (in MB) multiplied by execution time (in milliseconds), summed
+    /// over action executions.
+    pub action_compute_mb_millis: u64,
+    pub database_ingress_bytes: u64,
+    pub database_egress_bytes: u64,
+    pub database_egress_rows: u64,
+    pub vector_ingress_bytes: u64,
+    pub vector_egress_bytes: u64,
+    pub storage_calls: u64,
+    pub storage_ingress_bytes: u64,
+    pub storage_egress_bytes: u64,
+
+    pub document_count: Option<u64>,
+    pub database_storage_bytes: Option<u64>,
+    pub vector_storage_bytes: Option<u64>,
+}
+
+impl UsageMetrics {
+    pub fn merge(&mut self, other: &UsageMetrics) {
+        self.function_calls += other.function_calls;
+        self.function_failures += other.function_failures;
+        self.function_duration_millis += other.function_duration_millis;
+        self.action_compute_mb_millis += other.action_compute_mb_millis;
+        self.database_ingress_bytes += other.database_ingress_bytes;
+        self.database_egress_bytes += other.database_egress_bytes;
+        self.database_egress_rows += other.database_egress_rows;
+        self.vector_ingress_bytes += other.vector_ingress_bytes;
+        self.vector_egress_bytes += other.vector_egress_bytes;
+        self.storage_calls += other.storage_calls;
+        self.storage_ingress_bytes += other.storage_ingress_bytes;
+        self.storage_egress_bytes += other.storage_egress_bytes;
+        if other.document_count.is_some() {
+            self.document_count = other.document_count;
+        }
+        if other.database_storage_bytes.is_some() {
+            self.database_storage_bytes = other.database_storage_bytes;
+        }
+        if other.vector_storage_bytes.is_some() {
+            self.vector_storage_bytes = other.vector_storage_bytes;
+        }
+    }
+
+    /// Total database bandwidth, which is what database bandwidth quotas are
+    /// measured against.
+    pub fn database_bandwidth_bytes(&self) -> u64 {
+        self.database_ingress_bytes + self.database_egress_bytes
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl proptest::arbitrary::Arbitrary for UsageMetrics {
+    type Parameters = ();
+
+    type Strategy = impl proptest::strategy::Strategy<Value = UsageMetrics>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        // Usage is stored as `Int64`, so stay within its range.
+        (
+            prop::collection::vec(0..=i64::MAX as u64, 12),
+            prop::collection::vec(prop::option::of(0..=i64::MAX as u64), 3),
+        )
+            .prop_map(|(counters, gauges)| UsageMetrics {
+                function_calls: counters[0],
+                function_failures: counters[1],
+                function_duration_millis: counters[2],
+                action_compute_mb_millis: counters[3],
+                database_ingress_bytes: counters[4],
+                database_egress_bytes: counters[5],
+                database_egress_rows: counters[6],
+                vector_ingress_bytes: counters[7],
+                vector_egress_bytes: counters[8],
+                storage_calls: counters[9],
+                storage_ingress_bytes: counters[10],
+                storage_egress_bytes: counters[11],
+                document_count: gauges[0],
+                database_storage_bytes: gauges[1],
+                vector_storage_bytes: gauges[2],
+            })
+    }
+}
+
+/// A row of the `_usage_rollups` table.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct UsageRollup {
+    pub key: UsageRollupKey,
+    pub metrics: UsageMetrics,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedUsageRollup {
+    hour_start: i64,
+    component_path: Option<String>,
+    udf_id: Option<String>,
+    table_name: Option<String>,
+
+    function_calls: i64,
+    function_failures: i64,
+    function_duration_millis: i64,
+    action_compute_mb_millis: i64,
+    database_ingress_bytes: i64,
+    database_egress_bytes: i64,
+    database_egress_rows: i64,
+    vector_ingress_bytes: i64,
+    vector_egress_bytes: i64,
+    storage_calls: i64,
+    storage_ingress_bytes: i64,
+    storage_egress_bytes: i64,
+
+    document_count: Option<i64>,
+    database_storage_bytes: Option<i64>,
+    vector_storage_bytes: Option<i64>,
+}
+
+fn to_i64(n: u64) -> anyhow::Result<i64> {
+    Ok(i64::try_from(n)?)
+}
+
+fn to_u64(n: i64) -> anyhow::Result<u64> {
+    Ok(u64::try_from(n)?)
+}
+
+impl TryFrom<UsageRollup> for SerializedUsageRollup {
+    type Error = anyhow::Error;
+
+    fn try_from(rollup: UsageRollup) -> anyhow::Result<Self> {
+        let UsageRollup { key, metrics } = rollup;
+        Ok(Self {
+            hour_start: key.hour_start,
+            component_path: key.component_path,
+            udf_id: key.udf_id,
+            table_name: key.table_name,
+            function_calls: to_i64(metrics.function_calls)?,
+            function_failures: to_i64(metrics.function_failures)?,
+            function_duration_millis: to_i64(metrics.function_duration_millis)?,
+            action_compute_mb_millis: to_i64(metrics.action_compute_mb_millis)?,
+            database_ingress_bytes: to_i64(metrics.database_ingress_bytes)?,
+            database_egress_bytes: to_i64(metrics.database_egress_bytes)?,
+            database_egress_rows: to_i64(metrics.database_egress_rows)?,
+            vector_ingress_bytes: to_i64(metrics.vector_ingress_bytes)?,
+            vector_egress_bytes: to_i64(metrics.vector_egress_bytes)?,
+            storage_calls: to_i64(metrics.storage_calls)?,
+            storage_ingress_bytes: to_i64(metrics.storage_ingress_bytes)?,
+            storage_egress_bytes: to_i64(metrics.storage_egress_bytes)?,
+            document_count: metrics.document_count.map(to_i64).transpose()?,
+            database_storage_bytes: metrics.database_storage_bytes.map(to_i64).transpose()?,
+            vector_storage_bytes: metrics.vector_storage_bytes.map(to_i64).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<SerializedUsageRollup> for UsageRollup {
+    type Error = anyhow::Error;
+
+    fn try_from(rollup: SerializedUsageRollup) -> anyhow::Result<Self> {
+        Ok(Self {
+            key: UsageRollupKey {
+                hour_start: rollup.hour_start,
+                component_path: rollup.component_path,
+                udf_id: rollup.udf_id,
+                table_name: rollup.table_name,
+            },
+            metrics: UsageMetrics {
+                function_calls: to_u64(rollup.function_calls)?,
+                function_failures: to_u64(rollup.function_failures)?,
+                function_duration_millis: to_u64(rollup.function_duration_millis)?,
+                action_compute_mb_millis: to_u64(rollup.action_compute_mb_millis)?,
+                database_ingress_bytes: to_u64(rollup.database_ingress_bytes)?,
+                database_egress_bytes: to_u64(rollup.database_egress_bytes)?,
+                database_egress_rows: to_u64(rollup.database_egress_rows)?,
+                vector_ingress_bytes: to_u64(rollup.vector_ingress_bytes)?,
+                vector_egress_bytes: to_u64(rollup.vector_egress_bytes)?,
+                storage_calls: to_u64(rollup.storage_calls)?,
+                storage_ingress_bytes: to_u64(rollup.storage_ingress_bytes)?,
+                storage_egress_bytes: to_u64(rollup.storage_egress_bytes)?,
+                document_count: rollup.document_count.map(to_u64).transpose()?,
+                database_storage_bytes: rollup.database_storage_bytes.map(to_u64).transpose()?,
+                vector_storage_bytes: rollup.vector_storage_bytes.map(to_u64).transpose()?,
+            },
+        })
+    }
+}
+
+codegen_convex_serialization!(UsageRollup, SerializedUsageRollup);
+
+/// Daily limits for a component, stored in the `_usage_quotas` table. Calls to
+/// the component's functions are rejected once either limit is exceeded for
+/// the current UTC day.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct UsageQuota {
+    /// `None` for the root component.
+    pub component_path: Option<String>,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "proptest::option::of(0..=i64::MAX as u64)")
+    )]
+    pub max_function_calls_per_day: Option<u64>,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "proptest::option::of(0..=i64::MAX as u64)")
+    )]
+    pub max_database_bandwidth_bytes_per_day: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedUsageQuota {
+    component_path: Option<String>,
+    max_function_calls_per_day: Option<i64>,
+    max_database_bandwidth_bytes_per_day: Option<i64>,
+}
+
+impl TryFrom<UsageQuota> for SerializedUsageQuota {
+    type Error = anyhow::Error;
+
+    fn try_from(quota: UsageQuota) -> anyhow::Result<Self> {
+        Ok(Self {
+            component_path: quota.component_path,
+            max_function_calls_per_day: quota.max_function_calls_per_day.map(to_i64).transpose()?,
+            max_database_bandwidth_bytes_per_day: quota
+                .max_database_bandwidth_bytes_per_day
+                .map(to_i64)
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<SerializedUsageQuota> for UsageQuota {
+    type Error = anyhow::Error;
+
+    fn try_from(quota: SerializedUsageQuota) -> anyhow::Result<Self> {
+        Ok(Self {
+            component_path: quota.component_path,
+            max_function_calls_per_day: quota.max_function_calls_per_day.map(to_u64).transpose()?,
+            max_database_bandwidth_bytes_per_day: quota
+                .max_database_bandwidth_bytes_per_day
+                .map(to_u64)
+                .transpose()?,
+        })
+    }
+}
+
+codegen_convex_serialization!(UsageQuota, SerializedUsageQuota);
@@ -38,9 +38,12 @@ use errors::{
     ErrorMetadataAnyhowExt,
 };
 use futures::{
+    select_biased,
+    FutureExt,
     Stream,
     StreamExt,
 };
+use futures_async_stream::try_stream;
 use http::Uri;
 use isolate::{
     deserialize_udf_custom_error,
@@ -52,8 +55,6 @@ use model::{
     environment_variables::types::{
         EnvVarName,
         EnvVarValue,
-        EnvironmentVariable,
-        PersistedEnvironmentVariable,
     },
     modules::{
         function_validators::{
@@ -96,7 +97,6 @@ use udf::{
 use value::{
     base64,
     heap_size::WithHeapSize,
-    ConvexObject,
     ConvexValue,
 };
 
@@ -144,6 +144,8 @@ pub trait NodeExecutor: Sync + Send {
 pub struct InvokeResponse {
     pub response: JsonValue,
     pub aws_request_id: Option<String>,
+    /// Memory the executor measured the invocation using, if it can.
+    pub memory_used_mb: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -242,6 +244,7 @@ impl<RT: Runtime> Actions<RT> {
         let InvokeResponse {
             response,
             aws_request_id,
+            memory_used_mb,
         } = self.executor.invoke(request, log_line_sender).await?;
         let execute_result = ExecuteResponse::try_from(response.clone()).map_err(|e| {
             anyhow::anyhow!(
@@ -305,8 +308,12 @@ impl<RT: Runtime> Actions<RT> {
         Ok(NodeActionOutcome {
             result,
             syscall_trace,
-            // This shouldn't ever be None, but we'll use the default 512MB as a fallback.
-            memory_used_in_mb: execute_result.memory_allocated_mb.unwrap_or(512),
+            // Prefer what the executor measured, then what the executor allocated.
+            // The allocation shouldn't ever be None, but we'll use the default
+            // 512MB as a fallback.
+            memory_used_in_mb: memory_used_mb
+                .or(execute_result.memory_allocated_mb)
+                .unwrap_or(512),
         })
     }
 
@@ -321,6 +328,7 @@ impl<RT: Runtime> Actions<RT> {
         let InvokeResponse {
             response,
             aws_request_id,
+            ..
         } = self.executor.invoke(request, log_line_sender).await?;
         let response: BuildDepsResponse =
             serde_json::from_value(response.clone()).map_err(|e| {
@@ -399,6 +407,7 @@ impl<RT: Runtime> Actions<RT> {
         let InvokeResponse {
             response,
             aws_request_id,
+            ..
         } = self.invoke_analyze(request).await?;
         let response: AnalyzeResponse = serde_json::from_value(response.clone()).map_err(|e| {
             anyhow::anyhow!(
@@ -564,12 +573,12 @@ impl TryFrom<ExecutorRequest> for JsonValue {
                     .environment_variables
                     .into_iter()
                     .map(|(name, value)| {
-                        ConvexObject::try_from(PersistedEnvironmentVariable(
-                            EnvironmentVariable::new(name, value),
-                        ))
-                        .map(JsonValue::from)
+                        json!({
+                            "name": String::from(name),
+                            "value": String::from(value),
+                        })
                     })
-                    .collect::<anyhow::Result<_>>()?;
+                    .collect();
                 let (path, args, npm_version) = r.path_and_args.consume();
                 // TODO(lee)
                 anyhow::ensure!(path.component.is_root());
@@ -600,12 +609,12 @@ impl TryFrom<ExecutorRequest> for JsonValue {
                     .environment_variables
                     .into_iter()
                     .map(|(name, value)| {
-                        ConvexObject::try_from(PersistedEnvironmentVariable(
-                            EnvironmentVariable::new(name, value),
-                        ))
-                        .map(JsonValue::from)
+                        json!({
+                            "name": String::from(name),
+                            "value": String::from(value),
+                        })
                     })
-                    .collect::<anyhow::Result<_>>()?;
+                    .collect();
                 json!({
                     "type": "analyze",
                     "sourcePackage": JsonValue::from(r.source_package),
@@ -946,6 +955,46 @@ pub enum NodeExecutorStreamPart {
     InvokeComplete(Result<(), InvokeResponse>),
 }
 
+/// Stream the body of an `/invoke` response from a node executor server,
+/// completing with a timeout response if it takes longer than `timeout`.
+#[try_stream(ok = NodeExecutorStreamPart, error = anyhow::Error)]
+pub(crate) async fn invoke_response_stream(timeout: Duration, mut response: reqwest::Response) {
+    let mut timeout_future = Box::pin(tokio::time::sleep(timeout));
+    let timeout_future = &mut timeout_future;
+    loop {
+        let process_chunk = async {
+            select_biased! {
+                chunk = response.chunk().fuse() => {
+                    let chunk = chunk?;
+                    match chunk {
+                        Some(chunk) => {
+                            let chunk_vec = chunk.to_vec();
+                            anyhow::Ok(NodeExecutorStreamPart::Chunk(chunk_vec))
+                        }
+                        None => {
+                            anyhow::Ok(NodeExecutorStreamPart::InvokeComplete(Ok(())))
+                        }
+                    }
+                },
+                _ = timeout_future.fuse() => {
+                    anyhow::Ok(NodeExecutorStreamPart::InvokeComplete(Err(InvokeResponse {
+                        response: EXECUTE_TIMEOUT_RESPONSE_JSON.clone(),
+                        aws_request_id: None,
+                        memory_used_mb: None,
+                    })))
+                },
+            }
+        };
+        let part = process_chunk.await?;
+        if let NodeExecutorStreamPart::InvokeComplete(_) = part {
+            yield part;
+            break;
+        } else {
+            yield part;
+        }
+    }
+}
+
 pub async fn handle_node_executor_stream(
     log_line_sender: mpsc::UnboundedSender<LogLine>,
     mut stream: impl Stream<Item = anyhow::Result<NodeExecutorStreamPart>> + Unpin,
@@ -12,7 +12,9 @@ mod executor;
 pub mod local;
 mod metrics;
 pub mod noop;
+pub mod remote;
 pub mod source_package;
+mod worker;
 
 pub use crate::executor::{
     error_response_json,
@@ -7,85 +7,113 @@ use std::{
 use anyhow::Context;
 use async_once_cell::OnceCell;
 use async_trait::async_trait;
-use common::log_lines::LogLine;
+use common::{
+    knobs::NODE_EXECUTOR_POOL_SIZE,
+    log_lines::LogLine,
+};
 use errors::ErrorMetadata;
 use futures::{
+    pin_mut,
     select_biased,
     FutureExt,
 };
-use futures_async_stream::try_stream;
 use isolate::bundled_js::node_executor_file;
+use parking_lot::Mutex;
 use reqwest::Client;
 use serde_json::Value as JsonValue;
 use tempfile::TempDir;
 use tokio::{
-    process::{
-        Child,
-        Command as TokioCommand,
+    process::Command as TokioCommand,
+    sync::{
+        mpsc,
+        Semaphore,
     },
-    sync::mpsc,
 };
 
+pub use crate::worker::NodeWorkerLimits;
 use crate::{
     executor::{
+        invoke_response_stream,
         ExecutorRequest,
         InvokeResponse,
         NodeExecutor,
         EXECUTE_TIMEOUT_RESPONSE_JSON,
     },
     handle_node_executor_stream,
-    NodeExecutorStreamPart,
+    worker::NodeWorker,
 };
 
 /// Always use node version specified in .nvmrc for lambda execution, even if
 /// we're using older version for CLI.
 const NODE_VERSION: &str = include_str!("../../../.nvmrc");
-const HEALTH_CHECK_INTERVAL: Duration = Duration::from_millis(100);
-const MAX_HEALTH_CHECK_ATTEMPTS: u32 = 50;
 
+/// Runs node actions on a pool of local node server processes. Each worker
+/// runs one invocation at a time under the memory and CPU limits in
+/// `NodeWorkerLimits`, and is replaced after a limit breach, a timeout, or
+/// `max_invocations` invocations.
 pub struct LocalNodeExecutor {
-    inner: OnceCell<InnerLocalNodeExecutor>,
+    source: OnceCell<NodeSource>,
+    client: Client,
+    idle_workers: Mutex<Vec<NodeWorker>>,
+    worker_permits: Semaphore,
     config: LocalNodeExecutorConfig,
 }
 
 struct LocalNodeExecutorConfig {
     node_process_timeout: Duration,
+    limits: NodeWorkerLimits,
 }
 
-struct InnerLocalNodeExecutor {
-    _source_dir: TempDir,
-    port: u16,
-    client: reqwest::Client,
-    _server_handle: Child,
+/// The bundled node executor source and the node binary that runs it, shared
+/// by every worker.
+pub(crate) struct NodeSource {
+    dir: TempDir,
+    pub(crate) node_path: String,
 }
 
-impl InnerLocalNodeExecutor {
-    async fn new() -> anyhow::Result<Self> {
-        tracing::info!("Initializing inner local node executor");
-        // Create a single temp directory for both source files and Node.js temp files
-        let source_dir = TempDir::new()?;
-        let (source, source_map) =
-            node_executor_file("local.cjs").expect("local.cjs not generated!");
+impl NodeSource {
+    pub(crate) async fn new() -> anyhow::Result<Self> {
+        tracing::info!("Initializing local node executor source");
+        let dir = TempDir::new()?;
+        let (code, source_map) = node_executor_file("local.cjs").expect("local.cjs not generated!");
         let source_map = source_map.context("Missing local.cjs.map")?;
-        let source_path = source_dir.path().join("local.cjs");
-        let source_map_path = source_dir.path().join("local.cjs.map");
-        fs::write(&source_path, source.as_bytes())?;
-        fs::write(source_map_path, source_map.as_bytes())?;
+        let source = Self {
+            dir,
+            node_path: Self::find_node(),
+        };
+        fs::write(source.source_path(), code.as_bytes())?;
+        fs::write(
+            source.dir.path().join("local.cjs.map"),
+            source_map.as_bytes(),
+        )?;
         tracing::info!(
             "Using local node executor. Source: {}",
-            source_path.to_str().expect("Path is not UTF-8 string?"),
+            source
+                .source_path()
+                .to_str()
+                .expect("Path is not UTF-8 string?"),
         );
+        Self::check_node_version(&source.node_path).await?;
+        Ok(source)
+    }
 
-        let client = Client::new();
-        let port = portpicker::pick_unused_port().context("No ports free")?;
-        let server_handle =
-            Self::try_start_node_executor_server(&client, port, &source_path, &source_dir).await?;
-        Ok(Self {
-            _source_dir: source_dir,
-            port,
-            client,
-            _server_handle: server_handle,
-        })
+    pub(crate) fn source_path(&self) -> PathBuf {
+        self.dir.path().join("local.cjs")
+    }
+
+    fn find_node() -> String {
+        let node_version = NODE_VERSION.trim();
+
+        // Look for node in a few places.
+        let possible_path = home::home_dir()
+            .unwrap()
+            .join(".nvm")
+            .join(format!("versions/node/v{node_version}/bin/node"));
+        if possible_path.exists() {
+            possible_path.to_string_lossy().to_string()
+        } else {
+            "node".to_string()
+        }
     }
 
     async fn check_node_version(node_path: &str) -> anyhow::Result<()> {
@@ -106,131 +134,97 @@ impl InnerLocalNodeExecutor {
         }
         Ok(())
     }
+}
 
-    async fn check_server_health(client: &Client, port: u16) -> anyhow::Result<bool> {
-        match client
-            .get(format!("http://127.0.0.1:{}/health", port))
-            .timeout(Duration::from_secs(1))
-            .send()
-            .await
-        {
-            Ok(response) if response.status().is_success() => Ok(true),
-            _ => Ok(false),
-        }
-    }
-
-    async fn try_start_node_executor_server(
-        client: &Client,
-        port: u16,
-        source_path: &PathBuf,
-        temp_dir: &TempDir,
-    ) -> anyhow::Result<Child> {
-        let node_version = NODE_VERSION.trim();
-
-        // Look for node in a few places.
-        let possible_path = home::home_dir()
-            .unwrap()
-            .join(".nvm")
-            .join(format!("versions/node/v{node_version}/bin/node"));
-        let node_path = if possible_path.exists() {
-            possible_path.to_string_lossy().to_string()
-        } else {
-            "node".to_string()
-        };
-        Self::check_node_version(&node_path).await?;
-
-        let mut cmd = TokioCommand::new(node_path);
-        cmd.arg(source_path)
-            .arg("--port")
-            .arg(port.to_string())
-            .arg("--tempdir")
-            .arg(temp_dir.path())
-            .kill_on_drop(true);
-
-        tracing::info!("Starting node executor server on port {}", port);
-        let child = cmd.spawn()?;
-
-        for _ in 0..MAX_HEALTH_CHECK_ATTEMPTS {
-            if Self::check_server_health(client, port).await? {
-                return Ok(child);
-            }
-            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
-        }
-        anyhow::bail!("Node executor server failed to start and become healthy")
-    }
+/// The result of running one invocation on a worker.
+struct WorkerInvocation {
+    response: InvokeResponse,
+    /// Whether the worker must be replaced rather than returned to the pool.
+    recycle: bool,
 }
 
 impl LocalNodeExecutor {
     pub async fn new(node_process_timeout: Duration) -> anyhow::Result<Self> {
+        Self::new_with_limits(node_process_timeout, NodeWorkerLimits::from_knobs()).await
+    }
+
+    pub async fn new_with_limits(
+        node_process_timeout: Duration,
+        limits: NodeWorkerLimits,
+    ) -> anyhow::Result<Self> {
         let executor = Self {
-            inner: OnceCell::new(),
+            source: OnceCell::new(),
+            client: Client::new(),
+            idle_workers: Mutex::new(vec![]),
+            worker_permits: Semaphore::new(*NODE_EXECUTOR_POOL_SIZE),
             config: LocalNodeExecutorConfig {
                 node_process_timeout,
+                limits,
             },
         };
 
         Ok(executor)
     }
 
-    #[try_stream(ok = NodeExecutorStreamPart, error = anyhow::Error)]
-    async fn response_stream(config: &LocalNodeExecutorConfig, mut response: reqwest::Response) {
-        let mut timeout_future = Box::pin(tokio::time::sleep(config.node_process_timeout));
-        let timeout_future = &mut timeout_future;
-        loop {
-            let process_chunk = async {
-                select_biased! {
-                    chunk = response.chunk().fuse() => {
-                        let chunk = chunk?;
-                        match chunk {
-                            Some(chunk) => {
-                                let chunk_vec = chunk.to_vec();
-                                anyhow::Ok(NodeExecutorStreamPart::Chunk(chunk_vec))
-                            }
-                            None => {
-                                anyhow::Ok(NodeExecutorStreamPart::InvokeComplete(Ok(())))
-                            }
-                        }
-                    },
-                    _ = timeout_future.fuse() => {
-                        anyhow::Ok(NodeExecutorStreamPart::InvokeComplete(Err(InvokeResponse {
-                            response: EXECUTE_TIMEOUT_RESPONSE_JSON.clone(),
-                            aws_request_id: None,
-                        })))
-                    },
-                }
-            };
-            let part = process_chunk.await?;
-            if let NodeExecutorStreamPart::InvokeComplete(_) = part {
-                yield part;
-                break;
-            } else {
-                yield part;
+    async fn invoke_worker(
+        &self,
+        worker: &mut NodeWorker,
+        request: ExecutorRequest,
+        log_line_sender: mpsc::UnboundedSender<LogLine>,
+    ) -> anyhow::Result<WorkerInvocation> {
+        let limits = &self.config.limits;
+        let start = worker.begin_invocation();
+        let outcome = {
+            let request_future = self
+                .send_request(worker.port, request, log_line_sender)
+                .fuse();
+            let watch_future = worker.watch(&start, limits).fuse();
+            pin_mut!(request_future, watch_future);
+            select_biased! {
+                result = request_future => Ok(result),
+                breach = watch_future => Err(breach),
             }
-        }
-    }
-}
-
-#[async_trait]
-impl NodeExecutor for LocalNodeExecutor {
-    fn enable(&self) -> anyhow::Result<()> {
-        Ok(())
+        };
+        let memory_used_mb = worker.peak_memory_mb();
+        let (response, recycle) = match outcome {
+            Ok(Ok(Ok(payload))) => (payload, false),
+            // The worker may still be running the timed out invocation.
+            Ok(Ok(Err(timeout))) => (timeout.response, true),
+            Ok(Err(e)) => match worker.exit_breach() {
+                Some(breach) => {
+                    tracing::warn!("Node executor worker killed for exceeding {breach:?} limit");
+                    (breach.response_json(limits), true)
+                },
+                None => return Err(e),
+            },
+            Err(breach) => {
+                tracing::warn!("Node executor worker exceeded {breach:?} limit, recycling");
+                (breach.response_json(limits), true)
+            },
+        };
+        Ok(WorkerInvocation {
+            response: InvokeResponse {
+                response,
+                aws_request_id: None,
+                memory_used_mb,
+            },
+            recycle,
+        })
     }
 
-    async fn invoke(
+    /// Send a request to the worker listening on `port`, returning an `Err`
+    /// response if it times out.
+    async fn send_request(
         &self,
+        port: u16,
         request: ExecutorRequest,
         log_line_sender: mpsc::UnboundedSender<LogLine>,
-    ) -> anyhow::Result<InvokeResponse> {
-        let inner = self
-            .inner
-            .get_or_try_init(InnerLocalNodeExecutor::new())
-            .await
-            .context("Failed to initialize inner local node executor")?;
+    ) -> anyhow::Result<Result<JsonValue, InvokeResponse>> {
         let request_json = JsonValue::try_from(request)?;
 
-        let response_result = inner
+        let response_result = self
             .client
-            .post(format!("http://127.0.0.1:{}/invoke", inner.port))
+            .post(format!("http://127.0.0.1:{}/invoke", port))
             .json(&request_json)
             .timeout(self.config.node_process_timeout)
             .send()
@@ -239,10 +233,11 @@ impl NodeExecutor for LocalNodeExecutor {
             Ok(response) => response,
             Err(e) => {
                 if e.is_timeout() {
-                    return Ok(InvokeResponse {
+                    return Ok(Err(InvokeResponse {
                         response: EXECUTE_TIMEOUT_RESPONSE_JSON.clone(),
                         aws_request_id: None,
-                    });
+                        memory_used_mb: None,
+                    }));
                 } else {
                     return Err(anyhow::anyhow!(e).context("Node server request failed"));
                 }
@@ -253,23 +248,63 @@ impl NodeExecutor for LocalNodeExecutor {
             let error = response.text().await?;
             anyhow::bail!("Node executor server returned error: {}", error);
         }
-        let stream = Self::response_stream(&self.config, response);
+        let stream = invoke_response_stream(self.config.node_process_timeout, response);
         let stream = Box::pin(stream);
-        let result = handle_node_executor_stream(log_line_sender, stream).await?;
-        match result {
-            Ok(payload) => Ok(InvokeResponse {
-                response: payload,
-                aws_request_id: None,
-            }),
-            Err(e) => Ok(e),
+        handle_node_executor_stream(log_line_sender, stream).await
+    }
+}
+
+#[async_trait]
+impl NodeExecutor for LocalNodeExecutor {
+    fn enable(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn invoke(
+        &self,
+        request: ExecutorRequest,
+        log_line_sender: mpsc::UnboundedSender<LogLine>,
+    ) -> anyhow::Result<InvokeResponse> {
+        let source = self
+            .source
+            .get_or_try_init(NodeSource::new())
+            .await
+            .context("Failed to initialize local node executor")?;
+        let _permit = self.worker_permits.acquire().await?;
+        let idle_worker = self.idle_workers.lock().pop();
+        let mut worker = match idle_worker {
+            Some(worker) => worker,
+            None => NodeWorker::start(
+                &self.client,
+                &source.node_path,
+                &source.source_path(),
+                &self.config.limits,
+            )
+            .await
+            .context("Failed to start local node executor worker")?,
+        };
+        let result = self
+            .invoke_worker(&mut worker, request, log_line_sender)
+            .await;
+        worker.invocations += 1;
+        let reusable = matches!(result, Ok(WorkerInvocation { recycle: false, .. }))
+            && worker.invocations < self.config.limits.max_invocations
+            && worker.is_alive();
+        if reusable {
+            self.idle_workers.lock().push(worker);
+        } else {
+            worker.stop().await;
         }
+        result.map(|invocation| invocation.response)
     }
 
-    fn shutdown(&self) {}
+    fn shutdown(&self) {
+        self.idle_workers.lock().clear();
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::{
         collections::BTreeMap,
         future::Future,
@@ -341,7 +376,10 @@ mod tests {
         ConvexObject,
     };
 
-    use super::LocalNodeExecutor;
+    use super::{
+        LocalNodeExecutor,
+        NodeWorkerLimits,
+    };
     use crate::{
         executor::{
             NodeActionOutcome,
@@ -353,15 +391,15 @@ mod tests {
         SourcePackage,
     };
 
-    const TEST_BACKEND_ADDRESS: &str = "http://127.0.0.1:8080";
+    pub(crate) const TEST_BACKEND_ADDRESS: &str = "http://127.0.0.1:8080";
     // Use lower timeouts for tests.
-    const TEST_USER_TIMEOUT: Duration = Duration::from_secs(2);
-    const TEST_NODE_PROCESS_TIMEOUT: Duration = Duration::from_secs(5);
+    pub(crate) const TEST_USER_TIMEOUT: Duration = Duration::from_secs(2);
+    pub(crate) const TEST_NODE_PROCESS_TIMEOUT: Duration = Duration::from_secs(5);
 
-    static VERSION: LazyLock<Option<Version>> =
+    pub(crate) static VERSION: LazyLock<Option<Version>> =
         LazyLock::new(|| Some("0.18.0".parse().expect("Failed to parse version")));
 
-    async fn upload_modules(
+    pub(crate) async fn upload_modules(
         storage: Arc<dyn Storage>,
         modules: Vec<ModuleConfig>,
     ) -> anyhow::Result<SourcePackage> {
@@ -380,11 +418,11 @@ mod tests {
         })
     }
 
-    fn create_args(args_object: ConvexObject) -> anyhow::Result<ConvexArray> {
+    pub(crate) fn create_args(args_object: ConvexObject) -> anyhow::Result<ConvexArray> {
         array![ConvexValue::Object(args_object)]
     }
 
-    fn execute_request(
+    pub(crate) fn execute_request(
         path_and_args: ValidatedPathAndArgs,
         source_package: SourcePackage,
     ) -> ExecuteRequest {
@@ -402,7 +440,7 @@ mod tests {
     }
 
     #[rustfmt::skip]
-    async fn execute<RT: Runtime>(
+    pub(crate) async fn execute<RT: Runtime>(
         actions: &Actions<RT>,
         execute_request: ExecuteRequest,
         source_maps_callback: impl Future<Output = anyhow::Result<
@@ -436,7 +474,7 @@ mod tests {
         )
     }
 
-    async fn empty_source_maps_callback(
+    pub(crate) async fn empty_source_maps_callback(
     ) -> anyhow::Result<BTreeMap<CanonicalizedModulePath, SourceMap>> {
         Ok(BTreeMap::new())
     }
@@ -462,6 +500,14 @@ mod tests {
         .await?;
 
         assert_eq!(response.result?, ConvexValue::from(8.));
+        if cfg!(target_os = "linux") {
+            // Measured from the worker process rather than the 512MB default.
+            assert!(
+                response.memory_used_in_mb > 0 && response.memory_used_in_mb < 512,
+                "{}",
+                response.memory_used_in_mb
+            );
+        }
 
         Ok(())
     }
@@ -831,6 +877,58 @@ mod tests {
         Ok(())
     }
 
+    #[convex_macro::prod_rt_test]
+    async fn test_cpu_time_limit(rt: ProdRuntime) -> anyhow::Result<()> {
+        if !cfg!(target_os = "linux") {
+            return Ok(());
+        }
+        let storage = Arc::new(LocalDirStorage::new(rt.clone())?);
+        let limits = NodeWorkerLimits {
+            max_invocations: 10,
+            memory_limit_mb: 512,
+            cpu_time_limit: Duration::from_secs(1),
+            cgroup_parent: None,
+            cgroup_cpu_percent: 100,
+        };
+        let actions = Actions::new(
+            Arc::new(LocalNodeExecutor::new_with_limits(TEST_NODE_PROCESS_TIMEOUT, limits).await?),
+            TEST_BACKEND_ADDRESS.into(),
+            TEST_USER_TIMEOUT,
+            rt,
+        );
+        let source_package = upload_modules(storage.clone(), TEST_SOURCE.clone()).await?;
+        let path_and_args = ValidatedPathAndArgs::new_for_tests(
+            "node_actions.js:workHardForAnHour".parse()?,
+            array![],
+            VERSION.clone(),
+        );
+        let (response, _log_lines) = execute(
+            &actions,
+            execute_request(path_and_args, source_package.clone()),
+            empty_source_maps_callback(),
+        )
+        .await?;
+        // The worker is killed for using too much CPU before the process timeout.
+        let message = response.result.unwrap_err().message;
+        assert!(message.contains("CPU time limit"), "{message}");
+
+        // The next invocation gets a fresh worker.
+        let numbers: ConvexArray = array![1f64.into(), 7f64.into()]?;
+        let path_and_args = ValidatedPathAndArgs::new_for_tests(
+            "node_actions.js:addNumbers".parse()?,
+            create_args(assert_obj!("numbers" => ConvexValue::Array(numbers)))?,
+            VERSION.clone(),
+        );
+        let (response, _log_lines) = execute(
+            &actions,
+            execute_request(path_and_args, source_package),
+            empty_source_maps_callback(),
+        )
+        .await?;
+        assert_eq!(response.result?, ConvexValue::from(8.));
+        Ok(())
+    }
+
     #[convex_macro::prod_rt_test]
     async fn test_deadlock(rt: ProdRuntime) -> anyhow::Result<()> {
         let storage = Arc::new(LocalDirStorage::new(rt.clone())?);
@@ -0,0 +1,376 @@
+//! Runs node actions on a node executor server in a separate process or
+//! container, like a sidecar, over HTTP.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use common::{
+    backoff::Backoff,
+    knobs::REMOTE_NODE_EXECUTOR_MAX_RETRIES,
+    log_lines::LogLine,
+    runtime::Runtime,
+};
+use reqwest::{
+    Client,
+    StatusCode,
+    Url,
+};
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+
+use crate::{
+    executor::{
+        invoke_response_stream,
+        ExecutorRequest,
+        InvokeResponse,
+        NodeExecutor,
+        EXECUTE_TIMEOUT_RESPONSE_JSON,
+    },
+    handle_node_executor_stream,
+};
+
+const REMOTE_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const REMOTE_MAX_BACKOFF: Duration = Duration::from_secs(2);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sends requests to the `/invoke` endpoint of a node executor server at
+/// `url`, authenticated with a shared secret. The server is the same one
+/// `LocalNodeExecutor` runs, started with `NODE_EXECUTOR_SECRET` set.
+///
+/// Requests are retried when they fail to connect. Analyze and build
+/// requests are idempotent, so they're also retried on other transport
+/// errors and gateway errors, but action executions are never retried once
+/// they may have reached the server.
+pub struct RemoteNodeExecutor<RT: Runtime> {
+    runtime: RT,
+    client: Client,
+    url: Url,
+    secret: String,
+    node_process_timeout: Duration,
+}
+
+impl<RT: Runtime> RemoteNodeExecutor<RT> {
+    pub fn new(
+        runtime: RT,
+        url: Url,
+        secret: String,
+        node_process_timeout: Duration,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            matches!(url.scheme(), "http" | "https"),
+            "Remote node executor URL must be http or https, got {url}"
+        );
+        Ok(Self {
+            runtime,
+            client: Client::new(),
+            url,
+            secret,
+            node_process_timeout,
+        })
+    }
+
+    /// Check that the remote executor is up and serving requests.
+    pub async fn check_health(&self) -> anyhow::Result<()> {
+        let response = self
+            .client
+            .get(self.endpoint("health")?)
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .send()
+            .await
+            .with_context(|| format!("Remote node executor at {} is unreachable", self.url))?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "Remote node executor at {} is unhealthy: {}",
+            self.url,
+            response.status()
+        );
+        Ok(())
+    }
+
+    fn endpoint(&self, path: &str) -> anyhow::Result<Url> {
+        // Join relative to the URL's path, so executors behind a path prefix
+        // work too.
+        let mut base = self.url.clone();
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        Ok(base.join(path)?)
+    }
+
+    async fn send_invoke(
+        &self,
+        request_json: &JsonValue,
+        idempotent: bool,
+    ) -> reqwest::Result<reqwest::Response> {
+        let url = self
+            .endpoint("invoke")
+            .expect("invoke is a valid relative URL");
+        let mut backoff = Backoff::new(REMOTE_INITIAL_BACKOFF, REMOTE_MAX_BACKOFF);
+        loop {
+            let result = self
+                .client
+                .post(url.clone())
+                .bearer_auth(&self.secret)
+                .json(request_json)
+                .timeout(self.node_process_timeout)
+                .send()
+                .await;
+            let retryable = match &result {
+                Ok(response) => {
+                    idempotent
+                        && matches!(
+                            response.status(),
+                            StatusCode::BAD_GATEWAY
+                                | StatusCode::SERVICE_UNAVAILABLE
+                                | StatusCode::GATEWAY_TIMEOUT
+                        )
+                },
+                // The request never reached the executor.
+                Err(e) if e.is_connect() => true,
+                Err(e) => idempotent && !e.is_timeout(),
+            };
+            if !retryable || backoff.failures() >= *REMOTE_NODE_EXECUTOR_MAX_RETRIES {
+                return result;
+            }
+            let delay = backoff.fail(&mut self.runtime.rng());
+            tracing::warn!(
+                "Retrying remote node executor request in {delay:?} after {}",
+                match &result {
+                    Ok(response) => response.status().to_string(),
+                    Err(e) => e.to_string(),
+                }
+            );
+            self.runtime.wait(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<RT: Runtime> NodeExecutor for RemoteNodeExecutor<RT> {
+    fn enable(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn invoke(
+        &self,
+        request: ExecutorRequest,
+        log_line_sender: mpsc::UnboundedSender<LogLine>,
+    ) -> anyhow::Result<InvokeResponse> {
+        let idempotent = !matches!(request, ExecutorRequest::Execute { .. });
+        let request_json = JsonValue::try_from(request)?;
+        let response = match self.send_invoke(&request_json, idempotent).await {
+            Ok(response) => response,
+            Err(e) if e.is_timeout() => {
+                return Ok(InvokeResponse {
+                    response: EXECUTE_TIMEOUT_RESPONSE_JSON.clone(),
+                    aws_request_id: None,
+                    memory_used_mb: None,
+                });
+            },
+            Err(e) => {
+                return Err(anyhow::anyhow!(e).context("Remote node executor request failed"));
+            },
+        };
+        if response.status() == StatusCode::UNAUTHORIZED {
+            anyhow::bail!(
+                "Remote node executor at {} rejected the shared secret",
+                self.url
+            );
+        }
+        if !response.status().is_success() {
+            let error = response.text().await?;
+            anyhow::bail!("Remote node executor returned error: {}", error);
+        }
+        let stream = invoke_response_stream(self.node_process_timeout, response);
+        let stream = Box::pin(stream);
+        let result = handle_node_executor_stream(log_line_sender, stream).await?;
+        match result {
+            Ok(payload) => Ok(InvokeResponse {
+                response: payload,
+                aws_request_id: None,
+                memory_used_mb: None,
+            }),
+            Err(e) => Ok(e),
+        }
+    }
+
+    fn shutdown(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::Duration,
+    };
+
+    use anyhow::Context;
+    use common::{
+        assert_obj,
+        value::ConvexValue,
+    };
+    use isolate::test_helpers::TEST_SOURCE;
+    use reqwest::Url;
+    use runtime::prod::ProdRuntime;
+    use storage::LocalDirStorage;
+    use tempfile::TempDir;
+    use tokio::process::{
+        Child,
+        Command as TokioCommand,
+    };
+    use udf::validation::ValidatedPathAndArgs;
+    use value::{
+        array,
+        ConvexArray,
+    };
+
+    use super::RemoteNodeExecutor;
+    use crate::{
+        local::{
+            tests::{
+                create_args,
+                empty_source_maps_callback,
+                execute,
+                execute_request,
+                upload_modules,
+                TEST_BACKEND_ADDRESS,
+                TEST_NODE_PROCESS_TIMEOUT,
+                TEST_USER_TIMEOUT,
+                VERSION,
+            },
+            NodeSource,
+        },
+        Actions,
+    };
+
+    const TEST_SECRET: &str = "test-node-executor-secret";
+
+    /// A node executor server in its own process, standing in for a sidecar
+    /// container.
+    struct StandIn {
+        _source: NodeSource,
+        _temp_dir: TempDir,
+        _process: Child,
+        url: Url,
+    }
+
+    async fn start_stand_in(rt: &ProdRuntime) -> anyhow::Result<StandIn> {
+        let source = NodeSource::new().await?;
+        let temp_dir = TempDir::new()?;
+        let port = portpicker::pick_unused_port().context("No ports free")?;
+        let process = TokioCommand::new(&source.node_path)
+            .arg(source.source_path())
+            .arg("--port")
+            .arg(port.to_string())
+            .arg("--tempdir")
+            .arg(temp_dir.path())
+            .env("NODE_EXECUTOR_SECRET", TEST_SECRET)
+            .kill_on_drop(true)
+            .spawn()?;
+        let url: Url = format!("http://127.0.0.1:{port}").parse()?;
+        let executor = RemoteNodeExecutor::new(
+            rt.clone(),
+            url.clone(),
+            TEST_SECRET.to_string(),
+            TEST_NODE_PROCESS_TIMEOUT,
+        )?;
+        for _ in 0..50 {
+            if executor.check_health().await.is_ok() {
+                return Ok(StandIn {
+                    _source: source,
+                    _temp_dir: temp_dir,
+                    _process: process,
+                    url,
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        anyhow::bail!("Stand-in node executor failed to become healthy")
+    }
+
+    fn add_numbers() -> anyhow::Result<ValidatedPathAndArgs> {
+        let numbers: ConvexArray = array![1f64.into(), 7f64.into()]?;
+        Ok(ValidatedPathAndArgs::new_for_tests(
+            "node_actions.js:addNumbers".parse()?,
+            create_args(assert_obj!("numbers" => ConvexValue::Array(numbers)))?,
+            VERSION.clone(),
+        ))
+    }
+
+    fn create_actions(
+        rt: &ProdRuntime,
+        url: Url,
+        secret: &str,
+    ) -> anyhow::Result<Actions<ProdRuntime>> {
+        Ok(Actions::new(
+            Arc::new(RemoteNodeExecutor::new(
+                rt.clone(),
+                url,
+                secret.to_string(),
+                TEST_NODE_PROCESS_TIMEOUT,
+            )?),
+            TEST_BACKEND_ADDRESS.into(),
+            TEST_USER_TIMEOUT,
+            rt.clone(),
+        ))
+    }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_remote_execute(rt: ProdRuntime) -> anyhow::Result<()> {
+        let storage = Arc::new(LocalDirStorage::new(rt.clone())?);
+        let source_package = upload_modules(storage, TEST_SOURCE.clone()).await?;
+        let stand_in = start_stand_in(&rt).await?;
+
+        let actions = create_actions(&rt, stand_in.url.clone(), TEST_SECRET)?;
+        let (response, _log_lines) = execute(
+            &actions,
+            execute_request(add_numbers()?, source_package.clone()),
+            empty_source_maps_callback(),
+        )
+        .await?;
+        assert_eq!(response.result?, ConvexValue::from(8.));
+
+        let actions = create_actions(&rt, stand_in.url.clone(), "wrong-secret")?;
+        let err = execute(
+            &actions,
+            execute_request(add_numbers()?, source_package),
+            empty_source_maps_callback(),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("rejected the shared secret"),
+            "{err:#}"
+        );
+        Ok(())
+    }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_remote_unreachable(rt: ProdRuntime) -> anyhow::Result<()> {
+        let storage = Arc::new(LocalDirStorage::new(rt.clone())?);
+        let source_package = upload_modules(storage, TEST_SOURCE.clone()).await?;
+        let port = portpicker::pick_unused_port().context("No ports free")?;
+        let url: Url = format!("http://127.0.0.1:{port}").parse()?;
+
+        let executor = RemoteNodeExecutor::new(
+            rt.clone(),
+            url.clone(),
+            TEST_SECRET.to_string(),
+            TEST_NODE_PROCESS_TIMEOUT,
+        )?;
+        assert!(executor.check_health().await.is_err());
+
+        // Connection failures are retried, then surfaced as errors.
+        let actions = create_actions(&rt, url, TEST_SECRET)?;
+        let err = execute(
+            &actions,
+            execute_request(add_numbers()?, source_package),
+            empty_source_maps_callback(),
+        )
+        .await
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("request failed"), "{err:#}");
+        Ok(())
+    }
+}
@@ -0,0 +1,357 @@
+//! A single node server process run by the local node executor, along with the
+//! per-invocation resource limits it runs under.
+
+use std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::Duration,
+};
+
+use anyhow::Context;
+use common::knobs::{
+    NODE_EXECUTOR_CGROUP_CPU_PERCENT,
+    NODE_EXECUTOR_CGROUP_PARENT,
+    NODE_EXECUTOR_CPU_TIME_LIMIT,
+    NODE_EXECUTOR_MAX_INVOCATIONS_PER_WORKER,
+    NODE_EXECUTOR_MEMORY_LIMIT_MB,
+};
+use reqwest::Client;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+use tokio::process::{
+    Child,
+    Command as TokioCommand,
+};
+
+use crate::error_response_json;
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_HEALTH_CHECK_ATTEMPTS: u32 = 50;
+/// How often a busy worker's memory and CPU usage are checked against its
+/// limits.
+const USAGE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+const CGROUP_CPU_PERIOD_MICROS: u64 = 100_000;
+
+/// Resource limits for local node workers. Memory and CPU limits apply to
+/// each invocation, and workers are replaced after `max_invocations`.
+#[derive(Clone, Debug)]
+pub struct NodeWorkerLimits {
+    pub max_invocations: usize,
+    pub memory_limit_mb: u64,
+    pub cpu_time_limit: Duration,
+    /// A delegated cgroup v2 directory to create per-worker cgroups in.
+    pub cgroup_parent: Option<PathBuf>,
+    pub cgroup_cpu_percent: u64,
+}
+
+impl NodeWorkerLimits {
+    pub fn from_knobs() -> Self {
+        Self {
+            max_invocations: *NODE_EXECUTOR_MAX_INVOCATIONS_PER_WORKER,
+            memory_limit_mb: *NODE_EXECUTOR_MEMORY_LIMIT_MB,
+            cpu_time_limit: *NODE_EXECUTOR_CPU_TIME_LIMIT,
+            cgroup_parent: NODE_EXECUTOR_CGROUP_PARENT.clone().map(PathBuf::from),
+            cgroup_cpu_percent: *NODE_EXECUTOR_CGROUP_CPU_PERCENT,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitBreach {
+    Memory,
+    CpuTime,
+}
+
+impl LimitBreach {
+    pub fn response_json(&self, limits: &NodeWorkerLimits) -> JsonValue {
+        let message = match self {
+            LimitBreach::Memory => format!(
+                "Your function ran out of memory: it used more than the {}MB limit. Check your \
+                 function for memory leaks or large allocations.",
+                limits.memory_limit_mb
+            ),
+            LimitBreach::CpuTime => format!(
+                "Your function used more than the {}s CPU time limit. Check your function for \
+                 infinite loops or other CPU-intensive operations.",
+                limits.cpu_time_limit.as_secs_f64()
+            ),
+        };
+        error_response_json(&message)
+    }
+}
+
+/// Usage at the start of an invocation, to measure the invocation's own usage
+/// against.
+pub struct InvocationStart {
+    cpu_time: Option<Duration>,
+}
+
+pub struct NodeWorker {
+    pub port: u16,
+    pub invocations: usize,
+    process: Child,
+    pid: Option<u32>,
+    // Dropped after `process`, so the worker is killed before its cgroup is
+    // removed.
+    cgroup: Option<WorkerCgroup>,
+    _temp_dir: TempDir,
+}
+
+impl NodeWorker {
+    pub async fn start(
+        client: &Client,
+        node_path: &str,
+        source_path: &Path,
+        limits: &NodeWorkerLimits,
+    ) -> anyhow::Result<Self> {
+        let temp_dir = TempDir::new()?;
+        let port = portpicker::pick_unused_port().context("No ports free")?;
+
+        let mut cmd = TokioCommand::new(node_path);
+        cmd.arg(source_path)
+            .arg("--port")
+            .arg(port.to_string())
+            .arg("--tempdir")
+            .arg(temp_dir.path())
+            .kill_on_drop(true);
+        apply_rlimits(&mut cmd, limits);
+
+        tracing::info!("Starting node executor worker on port {}", port);
+        let process = cmd.spawn()?;
+        let pid = process.id();
+        let cgroup = match (&limits.cgroup_parent, pid) {
+            (Some(parent), Some(pid)) => {
+                match WorkerCgroup::create(parent, &format!("node-worker-{port}"), limits)
+                    .and_then(|cgroup| cgroup.add(pid).map(|()| cgroup))
+                {
+                    Ok(cgroup) => Some(cgroup),
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to create cgroup for node worker, relying on rlimits: {e:#}"
+                        );
+                        None
+                    },
+                }
+            },
+            _ => None,
+        };
+        let worker = Self {
+            port,
+            invocations: 0,
+            process,
+            pid,
+            cgroup,
+            _temp_dir: temp_dir,
+        };
+
+        for _ in 0..MAX_HEALTH_CHECK_ATTEMPTS {
+            if Self::check_health(client, port).await {
+                return Ok(worker);
+            }
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        }
+        anyhow::bail!("Node executor server failed to start and become healthy")
+    }
+
+    async fn check_health(client: &Client, port: u16) -> bool {
+        match client
+            .get(format!("http://127.0.0.1:{}/health", port))
+            .timeout(Duration::from_secs(1))
+            .send()
+            .await
+        {
+            Ok(response) => response.status().is_success(),
+            Err(_) => false,
+        }
+    }
+
+    /// Reset the worker's peak memory and snapshot its CPU time, so usage can
+    /// be measured for the next invocation alone.
+    pub fn begin_invocation(&self) -> InvocationStart {
+        if let Some(pid) = self.pid {
+            // Writing 5 to clear_refs resets the process's peak RSS (VmHWM).
+            let _ = fs::write(format!("/proc/{pid}/clear_refs"), "5");
+        }
+        InvocationStart {
+            cpu_time: self.cpu_time(),
+        }
+    }
+
+    /// Resolves once the current invocation exceeds one of `limits`. Never
+    /// resolves on platforms without `/proc`.
+    pub async fn watch(&self, start: &InvocationStart, limits: &NodeWorkerLimits) -> LimitBreach {
+        loop {
+            tokio::time::sleep(USAGE_CHECK_INTERVAL).await;
+            if let Some(resident_mb) = self.memory_mb("VmRSS:")
+                && resident_mb > limits.memory_limit_mb
+            {
+                return LimitBreach::Memory;
+            }
+            if let (Some(now), Some(start)) = (self.cpu_time(), start.cpu_time)
+                && now.saturating_sub(start) > limits.cpu_time_limit
+            {
+                return LimitBreach::CpuTime;
+            }
+        }
+    }
+
+    /// The most memory the worker has held since the current invocation began.
+    pub fn peak_memory_mb(&self) -> Option<u64> {
+        self.memory_mb("VmHWM:")
+    }
+
+    pub fn is_alive(&mut self) -> bool {
+        matches!(self.process.try_wait(), Ok(None))
+    }
+
+    /// If the worker has been killed by the kernel for exceeding a limit,
+    /// which one.
+    pub fn exit_breach(&mut self) -> Option<LimitBreach> {
+        let status = self.process.try_wait().ok()??;
+        if self
+            .cgroup
+            .as_ref()
+            .is_some_and(|cgroup| cgroup.oom_killed())
+        {
+            return Some(LimitBreach::Memory);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if status.signal() == Some(libc::SIGXCPU) {
+                return Some(LimitBreach::CpuTime);
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = status;
+        None
+    }
+
+    pub async fn stop(mut self) {
+        if let Err(e) = self.process.kill().await {
+            tracing::warn!("Failed to kill node executor worker: {e}");
+        }
+    }
+
+    fn memory_mb(&self, field: &str) -> Option<u64> {
+        let status = fs::read_to_string(format!("/proc/{}/status", self.pid?)).ok()?;
+        let kb: u64 = status
+            .lines()
+            .find_map(|line| line.strip_prefix(field))?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kb.div_ceil(1024))
+    }
+
+    fn cpu_time(&self) -> Option<Duration> {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", self.pid?)).ok()?;
+        // The command name may contain spaces, so skip past it before splitting.
+        // utime and stime are the 14th and 15th fields.
+        let (_, fields) = stat.rsplit_once(')')?;
+        let mut fields = fields.split_whitespace().skip(11);
+        let utime: u64 = fields.next()?.parse().ok()?;
+        let stime: u64 = fields.next()?.parse().ok()?;
+        let ticks_per_second = clock_ticks_per_second()?;
+        Some(Duration::from_secs_f64(
+            (utime + stime) as f64 / ticks_per_second as f64,
+        ))
+    }
+}
+
+#[cfg(unix)]
+fn clock_ticks_per_second() -> Option<u64> {
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (ticks > 0).then_some(ticks as u64)
+}
+
+#[cfg(not(unix))]
+fn clock_ticks_per_second() -> Option<u64> {
+    None
+}
+
+/// Bound the worker's lifetime CPU time and disable core dumps. Per-invocation
+/// limits are enforced by `NodeWorker::watch`; the CPU rlimit is a backstop for
+/// when usage can't be read.
+#[cfg(unix)]
+fn apply_rlimits(cmd: &mut TokioCommand, limits: &NodeWorkerLimits) {
+    let cpu_seconds = limits
+        .cpu_time_limit
+        .as_secs()
+        .max(1)
+        .saturating_mul(limits.max_invocations.max(1) as u64);
+    let set = |resource, value: u64| {
+        let limit = libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    };
+    // SAFETY: `setrlimit` is async-signal-safe and the closure doesn't allocate.
+    unsafe {
+        cmd.pre_exec(move || {
+            set(libc::RLIMIT_CPU, cpu_seconds)?;
+            set(libc::RLIMIT_CORE, 0)?;
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_rlimits(_cmd: &mut TokioCommand, _limits: &NodeWorkerLimits) {}
+
+/// A cgroup v2 holding a single worker, removed when dropped.
+struct WorkerCgroup {
+    path: PathBuf,
+}
+
+impl WorkerCgroup {
+    fn create(parent: &Path, name: &str, limits: &NodeWorkerLimits) -> anyhow::Result<Self> {
+        let path = parent.join(name);
+        fs::create_dir(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let cgroup = Self { path };
+        cgroup.write("memory.max", &(limits.memory_limit_mb << 20).to_string())?;
+        // Not every kernel has swap accounting; memory.max still applies.
+        let _ = cgroup.write("memory.swap.max", "0");
+        let quota = limits.cgroup_cpu_percent * CGROUP_CPU_PERIOD_MICROS / 100;
+        cgroup.write("cpu.max", &format!("{quota} {CGROUP_CPU_PERIOD_MICROS}"))?;
+        Ok(cgroup)
+    }
+
+    fn add(&self, pid: u32) -> anyhow::Result<()> {
+        self.write("cgroup.procs", &pid.to_string())
+    }
+
+    fn oom_killed(&self) -> bool {
+        fs::read_to_string(self.path.join("memory.events"))
+            .ok()
+            .and_then(|events| {
+                events
+                    .lines()
+                    .find_map(|line| line.strip_prefix("oom_kill "))
+                    .and_then(|count| count.trim().parse::<u64>().ok())
+            })
+            .is_some_and(|count| count > 0)
+    }
+
+    fn write(&self, file: &str, value: &str) -> anyhow::Result<()> {
+        let path = self.path.join(file);
+        fs::write(&path, value).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+impl Drop for WorkerCgroup {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir(&self.path) {
+            tracing::warn!("Failed to remove cgroup {}: {e}", self.path.display());
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    time::Duration,
+};
 
 use anyhow::Context;
 use errors::{
@@ -24,7 +27,7 @@ impl From<ErrorCode> for ErrorCodeProto {
             ErrorCode::Forbidden => ErrorCodeProto::Forbidden,
             ErrorCode::NotFound => ErrorCodeProto::TransientNotFound,
             ErrorCode::ClientDisconnect => ErrorCodeProto::ClientDisconnect,
-            ErrorCode::RateLimited => ErrorCodeProto::RateLimited,
+            ErrorCode::RateLimited { .. } => ErrorCodeProto::RateLimited,
             ErrorCode::Overloaded => ErrorCodeProto::Overloaded,
             ErrorCode::RejectedBeforeExecution => ErrorCodeProto::RejectedBeforeExecution,
             ErrorCode::OCC { .. } => ErrorCodeProto::Occ,
@@ -39,7 +42,7 @@ impl From<ErrorCode> for ErrorCodeProto {
 }
 
 impl ErrorCodeProto {
-    fn into_rust_type(self, occ_info: OccInfoProto) -> ErrorCode {
+    fn into_rust_type(self, occ_info: OccInfoProto, retry_after: Option<Duration>) -> ErrorCode {
         match self {
             ErrorCodeProto::BadRequest => ErrorCode::BadRequest,
             ErrorCodeProto::Conflict => ErrorCode::Conflict,
@@ -48,7 +51,7 @@ impl ErrorCodeProto {
             ErrorCodeProto::Forbidden => ErrorCode::Forbidden,
             ErrorCodeProto::TransientNotFound => ErrorCode::NotFound,
             ErrorCodeProto::ClientDisconnect => ErrorCode::ClientDisconnect,
-            ErrorCodeProto::RateLimited => ErrorCode::RateLimited,
+            ErrorCodeProto::RateLimited => ErrorCode::RateLimited { retry_after },
             ErrorCodeProto::Overloaded => ErrorCode::Overloaded,
             ErrorCodeProto::RejectedBeforeExecution => ErrorCode::RejectedBeforeExecution,
             ErrorCodeProto::Occ => ErrorCode::OCC {
@@ -69,6 +72,9 @@ impl ErrorCodeProto {
 
 impl From<ErrorMetadata> for ErrorMetadataProto {
     fn from(metadata: ErrorMetadata) -> Self {
+        let retry_after = metadata
+            .retry_after()
+            .and_then(|retry_after| retry_after.try_into().ok());
         ErrorMetadataProto {
             code: ErrorCodeProto::from(metadata.code.clone()).into(),
             short_msg: Some(metadata.short_msg.to_string()),
@@ -88,6 +94,7 @@ impl From<ErrorMetadata> for ErrorMetadataProto {
                 _ => None,
             },
             source: metadata.source,
+            retry_after,
         }
     }
 }
@@ -96,8 +103,12 @@ impl TryFrom<ErrorMetadataProto> for ErrorMetadata {
     type Error = anyhow::Error;
 
     fn try_from(metadata: ErrorMetadataProto) -> anyhow::Result<Self> {
+        let retry_after = metadata
+            .retry_after
+            .map(|retry_after| retry_after.try_into())
+            .transpose()?;
         let code = ErrorCodeProto::try_from(metadata.code)?
-            .into_rust_type(metadata.occ_info.unwrap_or_default());
+            .into_rust_type(metadata.occ_info.unwrap_or_default(), retry_after);
         let short_msg = metadata.short_msg.context("Missing `short_msg` field")?;
         let msg = metadata.msg.context("Missing `msg` field")?;
         Ok(Self {
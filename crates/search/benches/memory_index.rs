@@ -94,7 +94,9 @@ impl Dataset {
         let Ok(index_name) = index_name.map_table(&|_| Ok::<_, !>(table_id.tablet_id));
         let config = DeveloperTextIndexConfig {
             search_field: "body".parse()?,
+            extra_search_fields: BTreeMap::new(),
             filter_fields: BTreeSet::new(),
+            analyzer: Default::default(),
         };
 
         let schema = TantivySearchIndexSchema::new(&config);
@@ -138,6 +140,7 @@ impl Dataset {
                     "body".parse()?,
                     q.query,
                 )],
+                order: None,
             };
             let (compiled_query, _) = schema.compile(&internal_search, SearchVersion::V1)?;
             compiled.insert(q.name, compiled_query);
@@ -146,15 +149,10 @@ impl Dataset {
         let mut indexes = BTreeMap::new();
         for (name, documents) in &loaded {
             let mut index = MemoryTextIndex::new(WriteTimestamp::Committed(Timestamp::MIN));
-            for (internal_id, creation_time, document, _) in documents {
-                let terms = schema.index_into_terms(document).unwrap();
+            for (internal_id, _, document, _) in documents {
+                let indexed = schema.index_into_memory_document(document).unwrap();
                 index
-                    .update(
-                        *internal_id,
-                        WriteTimestamp::Pending,
-                        None,
-                        Some((terms, *creation_time)),
-                    )
+                    .update(*internal_id, WriteTimestamp::Pending, None, Some(indexed))
                     .unwrap();
             }
             indexes.insert(name.clone(), index);
@@ -189,23 +187,23 @@ fn load(bencher: divan::Bencher, dataset_name: &str) {
 
     let mut to_load = Vec::new();
     let mut total_size = 0;
-    for (internal_id, creation_time, document, size) in documents {
+    for (internal_id, _, document, size) in documents {
         total_size += size;
         if total_size > MAX_LOAD_SIZE {
             break;
         }
-        let terms = dataset.schema.index_into_terms(document).unwrap();
-        to_load.push((*internal_id, *creation_time, terms));
+        let indexed = dataset.schema.index_into_memory_document(document).unwrap();
+        to_load.push((*internal_id, indexed));
     }
     bencher.counter(BytesCount::new(total_size)).bench(|| {
         let mut index = MemoryTextIndex::new(WriteTimestamp::Committed(Timestamp::MIN));
-        for (internal_id, creation_time, terms) in &to_load {
+        for (internal_id, indexed) in &to_load {
             index
                 .update(
                     *internal_id,
                     WriteTimestamp::Pending,
                     None,
-                    Some((terms.clone(), *creation_time)),
+                    Some(indexed.clone()),
                 )
                 .unwrap();
         }
@@ -14,8 +14,9 @@ use crate::searcher::{
     TokenMatch,
 };
 
-// Aggregate the top `max_results` posting list matches, sorted by BM25 score,
-// creation time, and internal ID in descending order. This is implemented
+// Aggregate the top `max_results` posting list matches, sorted by sort key (if
+// the query has a sort), BM25 score, creation time, and internal ID in
+// descending order. This is implemented
 // using a min-heap so we can efficiently pop the worst match when adding a new
 // candidate.
 pub struct PostingListMatchAggregator {
@@ -0,0 +1,294 @@
+use common::bootstrap_model::index::text_index::TextIndexAnalyzer;
+use tantivy::tokenizer::{
+    AsciiFoldingFilter,
+    BoxTokenStream,
+    Language,
+    LowerCaser,
+    RemoveLongFilter,
+    Stemmer,
+    StopWordFilter,
+    TextAnalyzer,
+    Token,
+    TokenStream,
+    Tokenizer,
+    TokenizerManager,
+};
+
+use crate::constants::{
+    convex_en,
+    CONVEX_EN_TOKENIZER,
+    MAX_TEXT_TERM_LENGTH,
+};
+
+/// Every analyzer, so segments can be opened without knowing which one
+/// their index uses.
+const TEXT_INDEX_ANALYZERS: [TextIndexAnalyzer; 8] = [
+    TextIndexAnalyzer::Standard,
+    TextIndexAnalyzer::English,
+    TextIndexAnalyzer::German,
+    TextIndexAnalyzer::French,
+    TextIndexAnalyzer::Spanish,
+    TextIndexAnalyzer::AsciiFolding,
+    TextIndexAnalyzer::Ngram,
+    TextIndexAnalyzer::CjkBigram,
+];
+
+/// The length of the character n-grams produced by `TextIndexAnalyzer::Ngram`.
+const NGRAM_LENGTH: usize = 3;
+
+/// Name of the analyzer's tokenizer in tantivy's `TokenizerManager`. The
+/// standard analyzer keeps the name existing segments were written with.
+pub fn tokenizer_name(analyzer: TextIndexAnalyzer) -> &'static str {
+    match analyzer {
+        TextIndexAnalyzer::Standard => CONVEX_EN_TOKENIZER,
+        TextIndexAnalyzer::English => "convex_english",
+        TextIndexAnalyzer::German => "convex_german",
+        TextIndexAnalyzer::French => "convex_french",
+        TextIndexAnalyzer::Spanish => "convex_spanish",
+        TextIndexAnalyzer::AsciiFolding => "convex_ascii_folding",
+        TextIndexAnalyzer::Ngram => "convex_ngram",
+        TextIndexAnalyzer::CjkBigram => "convex_cjk_bigram",
+    }
+}
+
+pub fn text_analyzer(analyzer: TextIndexAnalyzer) -> TextAnalyzer {
+    match analyzer {
+        TextIndexAnalyzer::Standard => convex_en(),
+        TextIndexAnalyzer::English => stemmed(Language::English),
+        TextIndexAnalyzer::German => stemmed(Language::German).filter(AsciiFoldingFilter),
+        TextIndexAnalyzer::French => stemmed(Language::French).filter(AsciiFoldingFilter),
+        TextIndexAnalyzer::Spanish => stemmed(Language::Spanish).filter(AsciiFoldingFilter),
+        TextIndexAnalyzer::AsciiFolding => convex_en().filter(AsciiFoldingFilter),
+        TextIndexAnalyzer::Ngram => TextAnalyzer::from(NgramWordTokenizer).filter(LowerCaser),
+        TextIndexAnalyzer::CjkBigram => TextAnalyzer::from(CjkBigramTokenizer)
+            .filter(RemoveLongFilter::limit(MAX_TEXT_TERM_LENGTH))
+            .filter(LowerCaser),
+    }
+}
+
+/// Register every analyzer with an index's tokenizers.
+pub fn register_text_analyzers(tokenizers: &TokenizerManager) {
+    for analyzer in TEXT_INDEX_ANALYZERS {
+        tokenizers.register(tokenizer_name(analyzer), text_analyzer(analyzer));
+    }
+}
+
+/// Stop words are removed before stemming since the stop word lists contain
+/// unstemmed words.
+fn stemmed(language: Language) -> TextAnalyzer {
+    convex_en()
+        .filter(StopWordFilter::new(language).expect("Missing stop words for language"))
+        .filter(Stemmer::new(language))
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        // Hangul Jamo
+        '\u{1100}'..='\u{11FF}'
+        // Hiragana, Katakana, and Hangul Compatibility Jamo
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3130}'..='\u{318F}'
+        | '\u{31F0}'..='\u{31FF}'
+        // CJK Unified Ideographs and Extension A
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        // Hangul Syllables
+        | '\u{AC00}'..='\u{D7AF}'
+        // CJK Compatibility Ideographs
+        | '\u{F900}'..='\u{FAFF}'
+        // Halfwidth Katakana
+        | '\u{FF66}'..='\u{FF9F}'
+        // CJK Unified Ideographs Extensions B and later
+        | '\u{20000}'..='\u{3134F}'
+    )
+}
+
+/// Splits text into words of alphanumeric characters, like
+/// `SimpleTokenizer`, except that runs of CJK characters become overlapping
+/// bigrams. A run of a single CJK character is kept as is.
+#[derive(Clone)]
+pub struct CjkBigramTokenizer;
+
+impl Tokenizer for CjkBigramTokenizer {
+    fn token_stream<'a>(&self, text: &'a str) -> BoxTokenStream<'a> {
+        let mut tokens = TokenBuffer::default();
+        let mut chars = text.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if is_cjk(c) {
+                let mut run = vec![(start, c)];
+                while let Some(&(offset, next)) = chars.peek()
+                    && is_cjk(next)
+                {
+                    run.push((offset, next));
+                    chars.next();
+                }
+                let end = |(offset, c): (usize, char)| offset + c.len_utf8();
+                if run.len() == 1 {
+                    tokens.push(text, start, end(run[0]));
+                }
+                for pair in run.windows(2) {
+                    tokens.push(text, pair[0].0, end(pair[1]));
+                }
+            } else if c.is_alphanumeric() {
+                let mut end = start + c.len_utf8();
+                while let Some(&(offset, next)) = chars.peek()
+                    && next.is_alphanumeric()
+                    && !is_cjk(next)
+                {
+                    end = offset + next.len_utf8();
+                    chars.next();
+                }
+                tokens.push(text, start, end);
+            }
+        }
+        tokens.into_stream()
+    }
+}
+
+/// Splits text into words of alphanumeric characters and emits each word's
+/// overlapping character n-grams. Words shorter than an n-gram are kept as
+/// is.
+#[derive(Clone)]
+pub struct NgramWordTokenizer;
+
+impl Tokenizer for NgramWordTokenizer {
+    fn token_stream<'a>(&self, text: &'a str) -> BoxTokenStream<'a> {
+        let mut tokens = TokenBuffer::default();
+        let mut chars = text.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if !c.is_alphanumeric() {
+                continue;
+            }
+            let mut word = vec![(start, c)];
+            while let Some(&(offset, next)) = chars.peek()
+                && next.is_alphanumeric()
+            {
+                word.push((offset, next));
+                chars.next();
+            }
+            let end = |(offset, c): (usize, char)| offset + c.len_utf8();
+            if word.len() < NGRAM_LENGTH {
+                tokens.push(text, start, end(word[word.len() - 1]));
+            }
+            for ngram in word.windows(NGRAM_LENGTH) {
+                tokens.push(text, ngram[0].0, end(ngram[NGRAM_LENGTH - 1]));
+            }
+        }
+        tokens.into_stream()
+    }
+}
+
+/// Tokens computed up front, for tokenizers that need to look ahead.
+#[derive(Default)]
+struct TokenBuffer {
+    tokens: Vec<Token>,
+}
+
+impl TokenBuffer {
+    fn push(&mut self, text: &str, offset_from: usize, offset_to: usize) {
+        self.tokens.push(Token {
+            offset_from,
+            offset_to,
+            position: self.tokens.len(),
+            text: text[offset_from..offset_to].to_string(),
+            position_length: 1,
+        });
+    }
+
+    fn into_stream<'a>(self) -> BoxTokenStream<'a> {
+        BoxTokenStream::from(BufferedTokenStream {
+            tokens: self.tokens,
+            next: 0,
+        })
+    }
+}
+
+struct BufferedTokenStream {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl TokenStream for BufferedTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.next < self.tokens.len() {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.next - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.next - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::bootstrap_model::index::text_index::TextIndexAnalyzer;
+
+    use super::text_analyzer;
+
+    fn tokens(analyzer: TextIndexAnalyzer, text: &str) -> Vec<String> {
+        let analyzer = text_analyzer(analyzer);
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = vec![];
+        while let Some(token) = stream.next() {
+            tokens.push(token.text.clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_standard() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::Standard, "The Running dogs"),
+            vec!["the", "running", "dogs"]
+        );
+    }
+
+    #[test]
+    fn test_english() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::English, "The Running dogs"),
+            vec!["run", "dog"]
+        );
+    }
+
+    #[test]
+    fn test_french_folds_accents() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::French, "Les élèves"),
+            vec!["elev"]
+        );
+    }
+
+    #[test]
+    fn test_ascii_folding() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::AsciiFolding, "Crème Brûlée"),
+            vec!["creme", "brulee"]
+        );
+    }
+
+    #[test]
+    fn test_ngram() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::Ngram, "Rust is"),
+            vec!["rus", "ust", "is"]
+        );
+    }
+
+    #[test]
+    fn test_cjk_bigram() {
+        assert_eq!(
+            tokens(TextIndexAnalyzer::CjkBigram, "東京都 in Tokyo 日"),
+            vec!["東京", "京都", "in", "tokyo", "日"]
+        );
+    }
+}
@@ -41,6 +41,12 @@ pub const MAX_EDIT_DISTANCE: u32 = 2;
 /// maximum number of posting lists we'll want to consider in a single query.
 pub const MAX_UNIQUE_QUERY_TERMS: usize = 64;
 
+/// How many words a highlighted result's snippet spans by default.
+pub const DEFAULT_SNIPPET_TOKENS: usize = 20;
+
+/// The most words a highlighted result's snippet can span.
+pub const MAX_SNIPPET_TOKENS: usize = 256;
+
 pub fn convex_en() -> TextAnalyzer {
     TextAnalyzer::from(SimpleTokenizer)
         .filter(RemoveLongFilter::limit(MAX_TEXT_TERM_LENGTH))
@@ -1,11 +1,21 @@
 use std::{
-    collections::BTreeSet,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     fmt,
 };
 
 use anyhow::Context;
 use tantivy::{
-    fastfield::AliveBitSet,
+    fastfield::{
+        AliveBitSet,
+        BytesFastFieldReader,
+    },
+    postings::{
+        Postings,
+        SegmentPostings,
+    },
     query::{
         intersect_scorers,
         BitSetDocSet,
@@ -25,14 +35,26 @@ use tantivy::{
     DocSet,
     Score,
     SegmentReader,
+    TantivyError,
     Term,
     TERMINATED,
 };
 use tantivy_common::ReadOnlyBitSet;
 
+use crate::{
+    filter_values::{
+        filter_values_reader,
+        FilterRange,
+        RangeFilter,
+    },
+    phrase::PositionConstraint,
+};
+
 /// A query for documents that:
 /// 1. Contain at least one of the OR terms.
 /// 2. Match all of the AND terms.
+/// 3. Satisfy all of the position constraints.
+/// 4. Have filter field values in all of the range filters.
 ///
 /// Unlike tantivy's BooleanQuery, this query will be scored only by the or
 /// terms.
@@ -40,6 +62,8 @@ use tantivy_common::ReadOnlyBitSet;
 pub struct ConvexSearchQuery {
     or_query: BooleanQuery,
     and_queries: Vec<TermQuery>,
+    position_constraints: Vec<PositionConstraint<Term>>,
+    range_filters: Vec<RangeFilter>,
     alive_documents: AliveDocuments,
 }
 
@@ -48,6 +72,8 @@ impl ConvexSearchQuery {
     pub fn new(
         or_terms: Vec<OrTerm>,
         and_terms: Vec<Term>,
+        position_constraints: Vec<PositionConstraint<Term>>,
+        range_filters: Vec<RangeFilter>,
         alive_documents: AliveDocuments,
     ) -> Box<dyn Query> {
         let or_queries = or_terms
@@ -66,6 +92,8 @@ impl ConvexSearchQuery {
         Box::new(Self {
             or_query,
             and_queries,
+            position_constraints,
+            range_filters,
             alive_documents,
         })
     }
@@ -92,6 +120,8 @@ impl Query for ConvexSearchQuery {
         Ok(Box::new(ConvexSearchWeight {
             or_weight,
             and_weights,
+            position_constraints: self.position_constraints.clone(),
+            range_filters: self.range_filters.clone(),
             alive_documents: self.alive_documents.clone(),
         }))
     }
@@ -101,12 +131,19 @@ impl Query for ConvexSearchQuery {
         for filter_query in &self.and_queries {
             filter_query.query_terms(visitor);
         }
+        for constraint in &self.position_constraints {
+            for term in constraint.terms() {
+                visitor(term, true);
+            }
+        }
     }
 }
 
 struct ConvexSearchWeight {
     or_weight: Box<dyn Weight>,
     and_weights: Vec<Box<dyn Weight>>,
+    position_constraints: Vec<PositionConstraint<Term>>,
+    range_filters: Vec<RangeFilter>,
     alive_documents: AliveDocuments,
 }
 
@@ -116,11 +153,28 @@ impl Weight for ConvexSearchWeight {
         for filter_weight in &self.and_weights {
             and_scorers.push(filter_weight.scorer(reader, boost)?);
         }
-        let scorer = intersect_scorers_and_use_one_for_scores(
+        let mut scorer = intersect_scorers_and_use_one_for_scores(
             self.or_weight.scorer(reader, boost)?,
             intersect_scorers(and_scorers),
         );
-        Ok(scorer)
+        if !self.position_constraints.is_empty() {
+            let Some(position_scorer) =
+                PositionConstraintScorer::new(scorer, reader, &self.position_constraints)?
+            else {
+                return Ok(Box::new(EmptyScorer));
+            };
+            scorer = Box::new(position_scorer);
+        }
+        if self.range_filters.is_empty() {
+            return Ok(scorer);
+        }
+        let mut ranges = Vec::with_capacity(self.range_filters.len());
+        for filter in &self.range_filters {
+            let values = filter_values_reader(reader, filter.field)
+                .map_err(|e| TantivyError::InvalidArgument(e.to_string()))?;
+            ranges.push((values, filter.range.clone()));
+        }
+        Ok(Box::new(RangeFilterScorer::new(scorer, ranges)))
     }
 
     fn explain(&self, reader: &SegmentReader, doc: DocId) -> tantivy::Result<Explanation> {
@@ -220,6 +274,165 @@ impl Scorer for AliveDocumentsScorer {
     }
 }
 
+/// Filters another scorer's documents down to those that satisfy all of a
+/// query's position constraints, by seeking their terms' posting lists to each
+/// document and checking the terms' positions in it.
+pub struct PositionConstraintScorer {
+    scorer: Box<dyn Scorer>,
+    postings: Vec<SegmentPostings>,
+    /// Constraints with each term replaced by its index into `postings`.
+    constraints: Vec<PositionConstraint<usize>>,
+}
+
+impl PositionConstraintScorer {
+    /// Returns `None` if the segment is missing terms that every match needs.
+    fn new(
+        scorer: Box<dyn Scorer>,
+        reader: &SegmentReader,
+        constraints: &[PositionConstraint<Term>],
+    ) -> tantivy::Result<Option<Self>> {
+        let mut term_indexes = BTreeMap::new();
+        let mut postings = vec![];
+        for term in constraints.iter().flat_map(|c| c.terms()) {
+            if term_indexes.contains_key(term) {
+                continue;
+            }
+            let inverted_index = reader.inverted_index(term.field())?;
+            if let Some(term_postings) =
+                inverted_index.read_postings(term, IndexRecordOption::WithFreqsAndPositions)?
+            {
+                term_indexes.insert(term.clone(), postings.len());
+                postings.push(term_postings);
+            }
+        }
+        // A constraint with a term missing from the segment can't match, but an
+        // `AnyField` constraint can still match in its other fields.
+        let Some(constraints) = constraints
+            .iter()
+            .map(|c| c.map(|term| term_indexes.get(term).copied()))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        let mut scorer = Self {
+            scorer,
+            postings,
+            constraints,
+        };
+        scorer.go_to_match();
+        Ok(Some(scorer))
+    }
+
+    /// Advance to the first document at or after the current one that contains
+    /// all the terms in the positions the constraints require.
+    fn go_to_match(&mut self) -> DocId {
+        let mut doc = self.scorer.doc();
+        while doc != TERMINATED && !self.matches(doc) {
+            doc = self.scorer.advance();
+        }
+        doc
+    }
+
+    fn matches(&mut self, doc: DocId) -> bool {
+        let postings = &mut self.postings;
+        self.constraints.iter().all(|constraint| {
+            constraint.matches(|&i| {
+                let term_postings = &mut postings[i];
+                if term_postings.doc() < doc {
+                    term_postings.seek(doc);
+                }
+                let mut positions = vec![];
+                if term_postings.doc() == doc {
+                    term_postings.positions(&mut positions);
+                }
+                positions
+            })
+        })
+    }
+}
+
+impl DocSet for PositionConstraintScorer {
+    fn advance(&mut self) -> DocId {
+        self.scorer.advance();
+        self.go_to_match()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.scorer.seek(target);
+        self.go_to_match()
+    }
+
+    fn doc(&self) -> DocId {
+        self.scorer.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorer.size_hint()
+    }
+}
+
+impl Scorer for PositionConstraintScorer {
+    fn score(&mut self) -> Score {
+        self.scorer.score()
+    }
+}
+
+/// Filters another scorer's documents down to those whose filter field values
+/// are in range, reading the values from their fast fields.
+pub struct RangeFilterScorer {
+    scorer: Box<dyn Scorer>,
+    ranges: Vec<(BytesFastFieldReader, FilterRange)>,
+}
+
+impl RangeFilterScorer {
+    fn new(scorer: Box<dyn Scorer>, ranges: Vec<(BytesFastFieldReader, FilterRange)>) -> Self {
+        let mut scorer = Self { scorer, ranges };
+        scorer.go_to_match();
+        scorer
+    }
+
+    /// Advance to the first document at or after the current one whose values
+    /// are in range.
+    fn go_to_match(&mut self) -> DocId {
+        let mut doc = self.scorer.doc();
+        while doc != TERMINATED
+            && !self
+                .ranges
+                .iter()
+                .all(|(values, range)| range.contains(values.get_bytes(doc)))
+        {
+            doc = self.scorer.advance();
+        }
+        doc
+    }
+}
+
+impl DocSet for RangeFilterScorer {
+    fn advance(&mut self) -> DocId {
+        self.scorer.advance();
+        self.go_to_match()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.scorer.seek(target);
+        self.go_to_match()
+    }
+
+    fn doc(&self) -> DocId {
+        self.scorer.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorer.size_hint()
+    }
+}
+
+impl Scorer for RangeFilterScorer {
+    fn score(&mut self) -> Score {
+        self.scorer.score()
+    }
+}
+
 /// Intersect two scorers using only one to compute the score.
 ///
 /// This is similar to `tantivy::intersect_scorers` but it only uses one of the
@@ -59,11 +59,10 @@ use vector::qdrant_segments::{
 use walkdir::WalkDir;
 
 use crate::{
-    constants::CONVEX_EN_TOKENIZER,
-    convex_en,
     metrics::{
         self,
     },
+    register_text_analyzers,
     NewTextSegment,
     SearchFileType,
     TantivySearchIndexSchema,
@@ -81,9 +80,7 @@ pub async fn index_reader_for_directory<P: AsRef<Path>>(
     let directory = directory.as_ref().to_path_buf();
     let index =
         tokio_spawn_blocking("disk_index_open", move || Index::open_in_dir(directory)).await??;
-    index
-        .tokenizers()
-        .register(CONVEX_EN_TOKENIZER, convex_en());
+    register_text_analyzers(index.tokenizers());
     let reader = index.reader()?;
     timer.finish();
     Ok(reader)
@@ -99,9 +96,7 @@ pub async fn index_writer_for_directory<P: AsRef<Path>>(
         Index::create_in_dir(&directory, schema)
     })
     .await??;
-    index
-        .tokenizers()
-        .register(CONVEX_EN_TOKENIZER, convex_en());
+    register_text_analyzers(index.tokenizers());
     Ok(index.writer(*SEARCH_INDEXING_MEMORY_ARENA_BYTES)?)
 }
 
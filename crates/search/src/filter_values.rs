@@ -0,0 +1,259 @@
+//! Range filters and ordering on a text index's filter fields.
+//!
+//! Filter fields are indexed as (possibly hashed) terms, which only support
+//! equality. So each filter field's unhashed sort key is also stored in a fast
+//! field on disk and alongside each document in the memory index.
+
+use std::ops::Bound;
+
+use anyhow::Context;
+use common::{
+    document::CreationTime,
+    query::{
+        FilterSortKey,
+        Order,
+    },
+};
+use errors::ErrorMetadata;
+use tantivy::{
+    fastfield::BytesFastFieldReader,
+    schema::Field,
+    SegmentReader,
+};
+use value::ConvexValue;
+
+/// A range of values, compared by their sort keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct FilterRange {
+    pub start: Bound<FilterSortKey>,
+    pub end: Bound<FilterSortKey>,
+}
+
+impl FilterRange {
+    pub fn contains(&self, sort_key: &[u8]) -> bool {
+        let after_start = match self.start {
+            Bound::Included(ref start) => sort_key >= &start[..],
+            Bound::Excluded(ref start) => sort_key > &start[..],
+            Bound::Unbounded => true,
+        };
+        let before_end = match self.end {
+            Bound::Included(ref end) => sort_key <= &end[..],
+            Bound::Excluded(ref end) => sort_key < &end[..],
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
+    }
+}
+
+/// A range filter on the fast field storing a filter field's values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeFilter {
+    pub field: Field,
+    pub range: FilterRange,
+}
+
+/// What to order matches by instead of their BM25 score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    CreationTime,
+    /// The fast field storing a filter field's values.
+    FilterField(Field),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchSort {
+    pub field: SortField,
+    pub order: Order,
+}
+
+impl SearchSort {
+    /// Map the sort key of a match's value onto a key where better matches
+    /// are larger, like BM25 scores. Sort keys are prefix-free, so inverting
+    /// their bytes reverses their order.
+    pub fn match_key(&self, sort_key: &[u8]) -> Vec<u8> {
+        match self.order {
+            Order::Desc => sort_key.to_vec(),
+            Order::Asc => sort_key.iter().map(|b| !b).collect(),
+        }
+    }
+}
+
+/// Open the fast field storing a filter field's values in a segment.
+pub fn filter_values_reader(
+    segment: &SegmentReader,
+    field: Field,
+) -> anyhow::Result<BytesFastFieldReader> {
+    check_filter_values(segment, [field])?;
+    let field_name = segment.schema().get_field_name(field);
+    Ok(segment.fast_fields().bytes(field_name)?)
+}
+
+/// Check that a segment stores the values of the given filter fields. Segments
+/// built before filter field values were stored don't, so they can't serve
+/// range filters or sorting until their index is rebuilt.
+pub fn check_filter_values(
+    segment: &SegmentReader,
+    fields: impl IntoIterator<Item = Field>,
+) -> anyhow::Result<()> {
+    let schema = segment.schema();
+    for field in fields {
+        if !schema.fields().any(|(f, _)| f == field) {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "TextIndexMissingFilterValues",
+                "This search index is being rebuilt to support range filters and sorting on its \
+                 filter fields. Try again once it has finished rebuilding.",
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn creation_time_sort_key(creation_time: CreationTime) -> FilterSortKey {
+    let value = ConvexValue::Float64(f64::from(creation_time));
+    FilterSortKey::from_search_value(Some(&value))
+}
+
+impl From<RangeFilter> for pb::searchlight::RangeFilter {
+    fn from(filter: RangeFilter) -> Self {
+        let start = match filter.range.start {
+            Bound::Included(key) => Some(pb::searchlight::range_filter::Start::StartIncluded(
+                key.into(),
+            )),
+            Bound::Excluded(key) => Some(pb::searchlight::range_filter::Start::StartExcluded(
+                key.into(),
+            )),
+            Bound::Unbounded => None,
+        };
+        let end = match filter.range.end {
+            Bound::Included(key) => {
+                Some(pb::searchlight::range_filter::End::EndIncluded(key.into()))
+            },
+            Bound::Excluded(key) => {
+                Some(pb::searchlight::range_filter::End::EndExcluded(key.into()))
+            },
+            Bound::Unbounded => None,
+        };
+        pb::searchlight::RangeFilter {
+            field: Some(filter.field.field_id()),
+            start,
+            end,
+        }
+    }
+}
+
+impl TryFrom<pb::searchlight::RangeFilter> for RangeFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: pb::searchlight::RangeFilter) -> Result<Self, Self::Error> {
+        let start = match filter.start {
+            Some(pb::searchlight::range_filter::Start::StartIncluded(key)) => {
+                Bound::Included(key.into())
+            },
+            Some(pb::searchlight::range_filter::Start::StartExcluded(key)) => {
+                Bound::Excluded(key.into())
+            },
+            None => Bound::Unbounded,
+        };
+        let end = match filter.end {
+            Some(pb::searchlight::range_filter::End::EndIncluded(key)) => {
+                Bound::Included(key.into())
+            },
+            Some(pb::searchlight::range_filter::End::EndExcluded(key)) => {
+                Bound::Excluded(key.into())
+            },
+            None => Bound::Unbounded,
+        };
+        Ok(RangeFilter {
+            field: Field::from_field_id(filter.field.context("Missing field")?),
+            range: FilterRange { start, end },
+        })
+    }
+}
+
+impl From<SearchSort> for pb::searchlight::SearchSort {
+    fn from(sort: SearchSort) -> Self {
+        let field = match sort.field {
+            SortField::CreationTime => pb::searchlight::search_sort::Field::CreationTime(()),
+            SortField::FilterField(field) => {
+                pb::searchlight::search_sort::Field::FilterField(field.field_id())
+            },
+        };
+        pb::searchlight::SearchSort {
+            field: Some(field),
+            descending: Some(sort.order == Order::Desc),
+        }
+    }
+}
+
+impl TryFrom<pb::searchlight::SearchSort> for SearchSort {
+    type Error = anyhow::Error;
+
+    fn try_from(sort: pb::searchlight::SearchSort) -> Result<Self, Self::Error> {
+        let field = match sort.field.context("Missing field")? {
+            pb::searchlight::search_sort::Field::CreationTime(()) => SortField::CreationTime,
+            pb::searchlight::search_sort::Field::FilterField(field) => {
+                SortField::FilterField(Field::from_field_id(field))
+            },
+        };
+        let order = if sort.descending.context("Missing descending")? {
+            Order::Desc
+        } else {
+            Order::Asc
+        };
+        Ok(SearchSort { field, order })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use common::query::{
+        FilterSortKey,
+        Order,
+    };
+    use proptest::prelude::*;
+    use value::ConvexValue;
+
+    use super::{
+        FilterRange,
+        SearchSort,
+        SortField,
+    };
+
+    fn sort_key(value: Option<ConvexValue>) -> FilterSortKey {
+        FilterSortKey::from_search_value(value.as_ref())
+    }
+
+    #[test]
+    fn test_filter_range_contains() {
+        let range = FilterRange {
+            start: Bound::Excluded(sort_key(Some(ConvexValue::Float64(1.)))),
+            end: Bound::Included(sort_key(Some(ConvexValue::Float64(3.)))),
+        };
+        assert!(!range.contains(&sort_key(Some(ConvexValue::Float64(1.)))));
+        assert!(range.contains(&sort_key(Some(ConvexValue::Float64(2.5)))));
+        assert!(range.contains(&sort_key(Some(ConvexValue::Float64(3.)))));
+        assert!(!range.contains(&sort_key(Some(ConvexValue::Float64(3.5)))));
+        assert!(!range.contains(&sort_key(None)));
+    }
+
+    proptest! {
+        #![proptest_config(
+            ProptestConfig { failure_persistence: None, ..ProptestConfig::default() }
+        )]
+
+        #[test]
+        fn proptest_match_key_order(
+            a in any::<Option<ConvexValue>>(),
+            b in any::<Option<ConvexValue>>(),
+        ) {
+            let (a, b) = (sort_key(a), sort_key(b));
+            let desc = SearchSort { field: SortField::CreationTime, order: Order::Desc };
+            let asc = SearchSort { field: SortField::CreationTime, order: Order::Asc };
+            prop_assert_eq!(desc.match_key(&a).cmp(&desc.match_key(&b)), a.cmp(&b));
+            prop_assert_eq!(asc.match_key(&a).cmp(&asc.match_key(&b)), b.cmp(&a));
+        }
+    }
+}
@@ -43,6 +43,7 @@ use vector::{
     },
     PreviousVectorSegmentsHack,
     QdrantExternalId,
+    QdrantSchema,
 };
 
 use crate::{
@@ -163,7 +164,7 @@ impl<RT: Runtime> FragmentedSegmentCompactor<RT> {
     pub async fn compact<'a, T: TryInto<FragmentedSegmentStorageKeys> + Clone + Send + 'a>(
         &'a self,
         segments: Vec<T>,
-        dimension: usize,
+        schema: QdrantSchema,
         search_storage: Arc<dyn Storage>,
     ) -> anyhow::Result<FragmentedVectorSegment>
     where
@@ -210,7 +211,8 @@ impl<RT: Runtime> FragmentedSegmentCompactor<RT> {
                         .iter()
                         .map(|(paths, segment)| (Some(paths.clone()), segment))
                         .collect_vec(),
-                    dimension,
+                    schema.dimension(),
+                    schema.segment_options(),
                     &scratch_dir,
                     &target_path,
                 )?;
@@ -0,0 +1,187 @@
+use std::cmp;
+
+use levenshtein_automata::{
+    Distance,
+    DFA,
+};
+use tantivy::tokenizer::TextAnalyzer;
+
+use crate::{
+    levenshtein_dfa::build_fuzzy_dfa,
+    query::QueryTerm,
+};
+
+/// Where a text query matched a document's search field. All offsets are in
+/// UTF-16 code units so they can index directly into JavaScript strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHighlight {
+    /// `[start, end)` ranges of the field's words that matched a query term.
+    pub offsets: Vec<(usize, usize)>,
+    /// The part of the field around the best match.
+    pub snippet: String,
+    /// Where `snippet` starts within the field.
+    pub snippet_offset: usize,
+}
+
+/// Highlight the words in `text` matching `query_terms`.
+///
+/// `text` is tokenized with the index's analyzer, so its tokens (and their
+/// positions) are exactly the ones that were indexed, and a token matches a
+/// query term under the same edit distance and prefix rules the search used.
+/// The snippet is the run of `snippet_tokens` consecutive tokens containing the
+/// most matches, preferring the earliest such run.
+pub fn highlight(
+    analyzer: &TextAnalyzer,
+    query_terms: &[QueryTerm],
+    text: &str,
+    snippet_tokens: usize,
+) -> anyhow::Result<SearchHighlight> {
+    let mut dfas: Vec<DFA> = Vec::with_capacity(query_terms.len());
+    for query_term in query_terms {
+        let Some(term) = query_term.term().as_str() else {
+            continue;
+        };
+        let max_distance = u8::try_from(query_term.max_distance())?;
+        dfas.push(build_fuzzy_dfa(term, max_distance, query_term.prefix()));
+    }
+
+    // (byte offset from, byte offset to, matched) for each token.
+    let mut tokens = vec![];
+    let mut token_stream = analyzer.token_stream(text);
+    while let Some(token) = token_stream.next() {
+        let matched = dfas
+            .iter()
+            .any(|dfa| matches!(dfa.eval(&token.text), Distance::Exact(_)));
+        tokens.push((token.offset_from, token.offset_to, matched));
+    }
+    if tokens.is_empty() {
+        return Ok(SearchHighlight {
+            offsets: vec![],
+            snippet: String::new(),
+            snippet_offset: 0,
+        });
+    }
+
+    let window = cmp::min(cmp::max(snippet_tokens, 1), tokens.len());
+    let mut matches_in_window = tokens[..window].iter().filter(|t| t.2).count();
+    let (mut best_start, mut best_matches) = (0, matches_in_window);
+    for start in 1..=(tokens.len() - window) {
+        matches_in_window -= usize::from(tokens[start - 1].2);
+        matches_in_window += usize::from(tokens[start + window - 1].2);
+        if matches_in_window > best_matches {
+            best_start = start;
+            best_matches = matches_in_window;
+        }
+    }
+    let snippet_from = tokens[best_start].0;
+    let snippet_to = tokens[best_start + window - 1].1;
+
+    let mut utf16 = Utf16Offsets::new(text);
+    let offsets = tokens
+        .iter()
+        .filter(|(.., matched)| *matched)
+        .map(|&(from, to, _)| (utf16.get(from), utf16.get(to)))
+        .collect();
+    Ok(SearchHighlight {
+        offsets,
+        snippet: text[snippet_from..snippet_to].to_string(),
+        snippet_offset: utf16.get(snippet_from),
+    })
+}
+
+/// Converts byte offsets into UTF-16 offsets, scanning forward from the last
+/// offset converted since tokens are visited in order.
+struct Utf16Offsets<'a> {
+    text: &'a str,
+    byte_offset: usize,
+    utf16_offset: usize,
+}
+
+impl<'a> Utf16Offsets<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            byte_offset: 0,
+            utf16_offset: 0,
+        }
+    }
+
+    fn get(&mut self, byte_offset: usize) -> usize {
+        if byte_offset < self.byte_offset {
+            self.byte_offset = 0;
+            self.utf16_offset = 0;
+        }
+        self.utf16_offset += self.text[self.byte_offset..byte_offset]
+            .encode_utf16()
+            .count();
+        self.byte_offset = byte_offset;
+        self.utf16_offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::{
+        schema::Field,
+        Term,
+    };
+
+    use super::highlight;
+    use crate::{
+        convex_en,
+        query::QueryTerm,
+    };
+
+    fn query_terms(words: &[&str]) -> Vec<QueryTerm> {
+        words
+            .iter()
+            .map(|word| QueryTerm::new(Term::from_field_text(Field::from_field_id(0), word), false))
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_offsets() -> anyhow::Result<()> {
+        let result = highlight(
+            &convex_en(),
+            &query_terms(&["pizza"]),
+            "Pizza in New York. More pizza!",
+            20,
+        )?;
+        assert_eq!(result.offsets, vec![(0, 5), (24, 29)]);
+        assert_eq!(result.snippet, "Pizza in New York. More pizza");
+        assert_eq!(result.snippet_offset, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_highlight_snippet_window() -> anyhow::Result<()> {
+        let result = highlight(
+            &convex_en(),
+            &query_terms(&["new", "york"]),
+            "one two three four new york five six",
+            3,
+        )?;
+        assert_eq!(result.snippet, "four new york");
+        assert_eq!(result.snippet_offset, 14);
+        Ok(())
+    }
+
+    #[test]
+    fn test_highlight_utf16_offsets() -> anyhow::Result<()> {
+        // "🍕" is four bytes in UTF-8 but two code units in UTF-16.
+        let result = highlight(&convex_en(), &query_terms(&["pie"]), "🍕 pie", 20)?;
+        assert_eq!(result.offsets, vec![(3, 6)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_highlight_prefix() -> anyhow::Result<()> {
+        let terms = vec![QueryTerm::new(
+            Term::from_field_text(Field::from_field_id(0), "piz"),
+            true,
+        )];
+        let result = highlight(&convex_en(), &terms, "a pizza place", 20)?;
+        assert_eq!(result.offsets, vec![(2, 7)]);
+        Ok(())
+    }
+}
@@ -52,8 +52,6 @@ use value::InternalId;
 
 use crate::{
     archive::cache::ArchiveCacheManager,
-    constants::CONVEX_EN_TOKENIZER,
-    convex_en,
     disk_index::{
         download_single_file_zip,
         upload_single_file,
@@ -64,6 +62,7 @@ use crate::{
         log_text_document_indexed,
         SearchType,
     },
+    register_text_analyzers,
     searcher::{
         FieldDeletions,
         FragmentedTextStorageKeys,
@@ -347,9 +346,7 @@ pub async fn build_new_segment(
     let index = IndexBuilder::new()
         .schema(tantivy_schema.schema.clone())
         .create_in_dir(&index_path)?;
-    index
-        .tokenizers()
-        .register(CONVEX_EN_TOKENIZER, convex_en());
+    register_text_analyzers(index.tokenizers());
     let mut segment_writer = SingleSegmentIndexWriter::new(index, SEGMENT_MAX_SIZE_BYTES)?;
     let mut new_id_tracker = SearchMemoryIdTracker::default();
     futures::pin_mut!(revision_stream);
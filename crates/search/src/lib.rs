@@ -8,16 +8,20 @@
 #![feature(trait_alias)]
 
 mod aggregation;
+mod analyzer;
 mod archive;
 mod constants;
 mod convex_query;
 pub mod disk_index;
+mod filter_values;
 pub mod fragmented_segment;
+mod highlight;
 mod incremental_index;
 mod intersection;
 mod levenshtein_dfa;
 mod memory_index;
 pub mod metrics;
+mod phrase;
 pub mod query;
 pub mod scoring;
 pub mod searcher;
@@ -30,20 +34,34 @@ use std::{
         BTreeMap,
         BTreeSet,
     },
+    ops::Bound,
     sync::Arc,
 };
 
 use aggregation::PostingListMatchAggregator;
+use analyzer::tokenizer_name;
+pub use analyzer::{
+    register_text_analyzers,
+    text_analyzer,
+};
 use anyhow::Context;
 use common::{
     bootstrap_model::index::{
-        text_index::DeveloperTextIndexConfig,
+        text_index::{
+            DeveloperTextIndexConfig,
+            SearchFieldBoost,
+            TextIndexAnalyzer,
+        },
         IndexConfig,
     },
-    document::ResolvedDocument,
+    document::{
+        ResolvedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
     index::IndexKeyBytes,
     query::{
         search_value_to_bytes,
+        FilterSortKey,
         InternalSearch,
         InternalSearchFilterExpression,
         SearchVersion,
@@ -57,20 +75,35 @@ use common::{
         Timestamp,
     },
 };
-use constants::CONVEX_EN_TOKENIZER;
 pub use constants::{
     convex_en,
+    DEFAULT_SNIPPET_TOKENS,
     EXACT_SEARCH_MAX_WORD_LENGTH,
     MAX_CANDIDATE_REVISIONS,
     MAX_FILTER_CONDITIONS,
     MAX_QUERY_TERMS,
+    MAX_SNIPPET_TOKENS,
     SINGLE_TYPO_SEARCH_MAX_WORD_LENGTH,
 };
 use convex_query::OrTerm;
 use errors::ErrorMetadata;
+pub use filter_values::FilterRange;
+use filter_values::{
+    RangeFilter,
+    SearchSort,
+    SortField,
+};
+pub use highlight::SearchHighlight;
 use indexing::index_registry::Index;
 use itertools::Itertools;
 use metrics::log_search_token_limit_exceeded;
+use phrase::{
+    parse_search_text,
+    position_constraints,
+    PartTerms,
+    PositionConstraint,
+    SearchTextPart,
+};
 pub use query::{
     CandidateRevision,
     FilterConditionRead,
@@ -104,6 +137,7 @@ use tantivy::{
 pub use tantivy_query::SearchQueryResult;
 use value::{
     values_to_bytes,
+    ConvexObject,
     ConvexValue,
     FieldPath,
 };
@@ -125,6 +159,7 @@ pub use self::{
     },
     memory_index::{
         build_term_weights,
+        IndexedDocument,
         MemoryTextIndex,
     },
     searcher::{
@@ -229,6 +264,7 @@ impl TryFrom<&Token> for FieldPosition {
 #[derive(Clone)]
 pub struct TantivySearchIndexSchema {
     analyzer: TextAnalyzer,
+    index_analyzer: TextIndexAnalyzer,
 
     internal_id_field: Field,
     ts_field: Field,
@@ -237,7 +273,15 @@ pub struct TantivySearchIndexSchema {
     search_field_path: FieldPath,
     pub search_field: Field,
 
+    /// Search fields besides `search_field` and their boosts. Query terms are
+    /// matched against every search field, and a match's BM25 score is
+    /// multiplied by its field's boost.
+    extra_search_fields: BTreeMap<FieldPath, (Field, SearchFieldBoost)>,
+
     pub filter_fields: BTreeMap<FieldPath, Field>,
+    /// Fast fields storing the sort key of each filter field's value, for range
+    /// filters and sorting.
+    filter_value_fields: BTreeMap<FieldPath, Field>,
 
     pub(crate) schema: Schema,
 }
@@ -246,19 +290,31 @@ impl From<&TantivySearchIndexSchema> for pb::searchlight::SearchIndexConfig {
     fn from(schema: &TantivySearchIndexSchema) -> Self {
         pb::searchlight::SearchIndexConfig {
             search_field_path: Some(schema.search_field_path.clone().into()),
+            extra_search_fields: schema
+                .extra_search_fields
+                .iter()
+                .map(
+                    |(field_path, (_, boost))| pb::searchlight::SearchFieldBoost {
+                        field_path: Some(field_path.clone().into()),
+                        boost: boost.get(),
+                    },
+                )
+                .collect(),
             filter_fields: schema
                 .filter_fields
                 .keys()
                 .cloned()
                 .map(|p| p.into())
                 .collect::<Vec<_>>(),
+            analyzer: pb::searchlight::TextIndexAnalyzer::from(schema.index_analyzer) as i32,
         }
     }
 }
 
 impl TantivySearchIndexSchema {
     pub fn new(index_config: &DeveloperTextIndexConfig) -> Self {
-        let analyzer = convex_en();
+        let index_analyzer = index_config.analyzer;
+        let analyzer = text_analyzer(index_analyzer);
 
         let mut schema_builder = Schema::builder();
 
@@ -268,13 +324,13 @@ impl TantivySearchIndexSchema {
 
         let search_field_path = index_config.search_field.clone();
         let index_opts = TextFieldIndexing::default()
-            .set_tokenizer(CONVEX_EN_TOKENIZER)
+            .set_tokenizer(tokenizer_name(index_analyzer))
             .set_fieldnorms(true)
             .set_index_option(IndexRecordOption::WithFreqsAndPositions);
         let field_opts = TextOptions::default().set_indexing_options(index_opts);
 
         let field_name = format!("user/search/{}", String::from(search_field_path.clone()));
-        let search_field = schema_builder.add_text_field(&field_name, field_opts);
+        let search_field = schema_builder.add_text_field(&field_name, field_opts.clone());
 
         // NB: It's important that we iterate over `index_config.filter_fields` in
         // sorted order since tantivy assigns field ids in declaration order.
@@ -286,17 +342,37 @@ impl TantivySearchIndexSchema {
             let filter_field = schema_builder.add_bytes_field(&field_name, field_opts);
             filter_fields.insert(field_path.clone(), filter_field);
         }
+
+        // Extra search fields are declared after the filter fields so adding them
+        // doesn't change the ids of any other field.
+        let mut extra_search_fields = BTreeMap::new();
+        for (field_path, boost) in &index_config.extra_search_fields {
+            let field_name = format!("user/search/{}", String::from(field_path.clone()));
+            let field = schema_builder.add_text_field(&field_name, field_opts.clone());
+            extra_search_fields.insert(field_path.clone(), (field, *boost));
+        }
+
+        // Likewise, the filter fields' values are declared last.
+        let mut filter_value_fields = BTreeMap::new();
+        for field_path in &index_config.filter_fields {
+            let field_name = format!("user/filter_value/{}", String::from(field_path.clone()));
+            let field = schema_builder.add_bytes_field(&field_name, FAST);
+            filter_value_fields.insert(field_path.clone(), field);
+        }
         let schema = schema_builder.build();
         Self {
             analyzer,
+            index_analyzer,
             internal_id_field,
             ts_field,
             creation_time_field,
 
             search_field_path,
             search_field,
+            extra_search_fields,
 
             filter_fields,
+            filter_value_fields,
             schema,
         }
     }
@@ -321,10 +397,32 @@ impl TantivySearchIndexSchema {
     pub fn to_index_config(&self) -> DeveloperTextIndexConfig {
         DeveloperTextIndexConfig {
             search_field: self.search_field_path.clone(),
+            extra_search_fields: self
+                .extra_search_fields
+                .iter()
+                .map(|(field_path, (_, boost))| (field_path.clone(), *boost))
+                .collect(),
             filter_fields: self.filter_fields.keys().cloned().collect(),
+            analyzer: self.index_analyzer,
         }
     }
 
+    /// The primary search field followed by the extra search fields.
+    fn search_fields(&self) -> impl Iterator<Item = (&FieldPath, Field)> {
+        std::iter::once((&self.search_field_path, self.search_field)).chain(
+            self.extra_search_fields
+                .iter()
+                .map(|(field_path, (field, _))| (field_path, *field)),
+        )
+    }
+
+    fn search_field_boost(&self, field: Field) -> f32 {
+        self.extra_search_fields
+            .values()
+            .find(|(extra_field, _)| *extra_field == field)
+            .map_or(1., |(_, boost)| boost.get() as f32)
+    }
+
     fn filter_field_bytes(document: &ResolvedDocument, field_path: &FieldPath) -> Vec<u8> {
         let value = document.value().get_path(field_path);
         search_value_to_bytes(value)
@@ -335,13 +433,12 @@ impl TantivySearchIndexSchema {
     /// when a super rough estimate is sufficient (e.g. capping the maximum
     /// size of a new segment).
     pub fn estimate_size(&self, document: &ResolvedDocument) -> u64 {
-        let document_size = if let Some(ConvexValue::String(ref s)) =
-            document.value().get_path(&self.search_field_path)
-        {
-            s.len()
-        } else {
-            0
-        };
+        let mut document_size = 0;
+        for (field_path, _) in self.search_fields() {
+            if let Some(ConvexValue::String(ref s)) = document.value().get_path(field_path) {
+                document_size += s.len();
+            }
+        }
         let mut filter_field_sizes = 0;
         for field_path in self.filter_fields.keys() {
             let value = TantivySearchIndexSchema::filter_field_bytes(document, field_path);
@@ -350,6 +447,27 @@ impl TantivySearchIndexSchema {
         (document_size + filter_field_sizes) as u64
     }
 
+    /// Index a document's terms and the values of its filter fields for the
+    /// memory index.
+    pub fn index_into_memory_document(
+        &self,
+        document: &ResolvedDocument,
+    ) -> anyhow::Result<IndexedDocument> {
+        let filter_values = self
+            .filter_value_fields
+            .iter()
+            .map(|(field_path, field)| {
+                let value = document.value().get_path(field_path);
+                (*field, FilterSortKey::from_search_value(value))
+            })
+            .collect();
+        Ok(IndexedDocument {
+            terms: self.index_into_terms(document)?,
+            filter_values,
+            creation_time: document.creation_time(),
+        })
+    }
+
     pub fn index_into_terms(
         &self,
         document: &ResolvedDocument,
@@ -357,15 +475,17 @@ impl TantivySearchIndexSchema {
         let _timer = metrics::index_into_terms_timer();
 
         let mut doc_terms = vec![];
-        if let Some(ConvexValue::String(ref s)) = document.value().get_path(&self.search_field_path)
-        {
+        for (field_path, field) in self.search_fields() {
+            let Some(ConvexValue::String(ref s)) = document.value().get_path(field_path) else {
+                continue;
+            };
             let mut token_stream = self.analyzer.token_stream(&s[..]);
 
             while let Some(token) = token_stream.next() {
                 metrics::log_text_term(&token.text);
 
                 doc_terms.push(DocumentTerm::Search {
-                    term: Term::from_field_text(self.search_field, &token.text),
+                    term: Term::from_field_text(field, &token.text),
                     pos: FieldPosition::try_from(token)?,
                 });
             }
@@ -396,22 +516,30 @@ impl TantivySearchIndexSchema {
         let creation_time = document.creation_time();
         tantivy_document.add_f64(self.creation_time_field, creation_time.into());
 
-        if let Some(ConvexValue::String(ref s)) = document.value().get_path(&self.search_field_path)
-        {
-            tantivy_document.add_text(self.search_field, s);
+        for (field_path, field) in self.search_fields() {
+            if let Some(ConvexValue::String(ref s)) = document.value().get_path(field_path) {
+                tantivy_document.add_text(field, s);
+            }
         }
         for (field_path, tantivy_field) in &self.filter_fields {
             let value = TantivySearchIndexSchema::filter_field_bytes(document, field_path);
             tantivy_document.add_bytes(*tantivy_field, value);
         }
+        for (field_path, tantivy_field) in &self.filter_value_fields {
+            let value = document.value().get_path(field_path);
+            let sort_key = FilterSortKey::from_search_value(value);
+            tantivy_document.add_bytes(*tantivy_field, Vec::from(sort_key));
+        }
 
         tantivy_document
     }
 
     pub fn document_lengths(&self, document: &TantivyDocument) -> DocumentLengths {
         let mut search_field = 0;
-        if let Some(tantivy::schema::Value::Str(ref s)) = document.get_first(self.search_field) {
-            search_field += s.len();
+        for (_, field) in self.search_fields() {
+            if let Some(tantivy::schema::Value::Str(ref s)) = document.get_first(field) {
+                search_field += s.len();
+            }
         }
         let mut filter_fields = BTreeMap::new();
         for (field_path, tantivy_field) in &self.filter_fields {
@@ -437,17 +565,27 @@ impl TantivySearchIndexSchema {
     ) -> anyhow::Result<RevisionWithKeys> {
         log_num_segments_searched_total(segments.len());
 
-        // Step 1: Map the old `CompiledQuery` struct onto `TokenQuery`s.
+        // Step 1: Map the old `CompiledQuery` struct onto `TokenQuery`s. The compiled
+        // text query is against the primary search field, so match each of its terms
+        // against every search field.
         let mut token_queries = vec![];
-        let num_text_query_terms = compiled_query.text_query.len() as u32;
+        let position_constraints = compiled_query.position_constraints;
+        let range_filters = compiled_query.range_filters;
+        let sort = compiled_query.sort;
         for query_term in compiled_query.text_query {
-            let query = TokenQuery {
-                max_distance: query_term.max_distance(),
-                prefix: query_term.prefix(),
-                term: query_term.into_term(),
-            };
-            token_queries.push(query);
+            for (_, field) in self.search_fields() {
+                let Some(text) = query_term.term().as_str() else {
+                    anyhow::bail!("Term was not valid UTF8");
+                };
+                let query = TokenQuery {
+                    max_distance: query_term.max_distance(),
+                    prefix: query_term.prefix(),
+                    term: Term::from_field_text(field, text),
+                };
+                token_queries.push(query);
+            }
         }
+        let num_text_query_terms = token_queries.len() as u32;
         let mut exist_filter_conditions = false;
         for CompiledFilterCondition::Must(term) in compiled_query.filter_conditions {
             exist_filter_conditions = true;
@@ -569,6 +707,7 @@ impl TantivySearchIndexSchema {
                 if prefix {
                     boost *= 0.5;
                 }
+                boost *= self.search_field_boost(term.field());
                 let or_term = OrTerm {
                     term,
                     doc_frequency,
@@ -590,8 +729,14 @@ impl TantivySearchIndexSchema {
         // to know which `InternalId`s to exclude when querying the disk
         // indexes.
         let (prepared_memory_query, query) = block_in_place(|| {
-            let prepared_memory_query =
-                memory_index.prepare_posting_list_query(&and_terms, &or_terms, &bm25_stats)?;
+            let prepared_memory_query = memory_index.prepare_posting_list_query(
+                &and_terms,
+                &or_terms,
+                &position_constraints,
+                &range_filters,
+                sort,
+                &bm25_stats,
+            )?;
             let mut deleted_internal_ids = BTreeSet::new();
             if let Some(ref prepared_query) = prepared_memory_query {
                 deleted_internal_ids =
@@ -603,6 +748,9 @@ impl TantivySearchIndexSchema {
                 num_documents: bm25_stats.num_documents,
                 or_terms,
                 and_terms,
+                position_constraints,
+                range_filters,
+                sort,
                 max_results: MAX_CANDIDATE_REVISIONS,
             };
             anyhow::Ok((prepared_memory_query, query))
@@ -651,7 +799,16 @@ impl TantivySearchIndexSchema {
                     ts: m.ts,
                     creation_time: m.creation_time,
                 };
-                let index_fields = vec![
+                // Index keys increase in result order, so with a sort, lead with the
+                // inverted sort key, which is larger for better matches.
+                let mut index_fields = vec![];
+                if let Some(ref sort_key) = m.sort_key {
+                    let inverted = sort_key.iter().map(|b| !b).collect::<Vec<_>>();
+                    index_fields.push(Some(ConvexValue::Bytes(
+                        inverted.try_into().expect("Sort key too large"),
+                    )));
+                }
+                index_fields.extend([
                     Some(ConvexValue::Float64(-f64::from(m.bm25_score))),
                     Some(ConvexValue::Float64(-f64::from(m.creation_time))),
                     Some(ConvexValue::Bytes(
@@ -659,7 +816,7 @@ impl TantivySearchIndexSchema {
                             .try_into()
                             .expect("Could not convert internal ID to value"),
                     )),
-                ];
+                ]);
                 let bytes = values_to_bytes(&index_fields);
                 let index_key_bytes = IndexKeyBytes(bytes);
                 result.push((candidate, index_key_bytes));
@@ -686,6 +843,77 @@ impl TantivySearchIndexSchema {
         Ok(res)
     }
 
+    /// Tokenize a text query's search text into its query terms and the
+    /// position constraints from its quoted phrases and `near(n)` operators.
+    fn compile_search_text(
+        &self,
+        search_text: &str,
+        version: SearchVersion,
+    ) -> anyhow::Result<(Vec<QueryTerm>, Vec<PositionConstraint<Term>>)> {
+        // Tokenize quoted phrases and the words around `near(n)` operators
+        // separately so their terms' positions can be matched up.
+        let parts = parse_search_text(search_text);
+        let mut tokens = vec![];
+        let mut part_terms = Vec::with_capacity(parts.len());
+        for part in &parts {
+            let mut terms = PartTerms::default();
+            let (SearchTextPart::Words(text) | SearchTextPart::Phrase(text)) = part else {
+                part_terms.push(terms);
+                continue;
+            };
+            let mut token_stream = self.analyzer.token_stream(text);
+            // TODO(CX-5693): Consider how/if we should surface this to developers.
+            while let Some(token) = token_stream.next() {
+                if tokens.len() == MAX_QUERY_TERMS {
+                    terms.truncated = true;
+                    break;
+                }
+                let term = Term::from_field_text(self.search_field, &token.text);
+                terms.terms.push((term, u32::try_from(token.position)?));
+                tokens.push(token.text.clone());
+            }
+            part_terms.push(terms);
+        }
+        if part_terms.iter().any(|terms| terms.truncated) {
+            log_search_token_limit_exceeded();
+        }
+        let mut position_constraints = position_constraints(&parts, &part_terms);
+        // The constraints are against the primary search field, so let each match
+        // in any search field instead.
+        if !self.extra_search_fields.is_empty() {
+            position_constraints = position_constraints
+                .into_iter()
+                .map(|constraint| {
+                    let constraints = self
+                        .search_fields()
+                        .map(|(_, field)| {
+                            constraint
+                                .map(|term| Some(Term::from_field_text(field, term.as_str()?)))
+                                .context("Term was not valid UTF8")
+                        })
+                        .collect::<anyhow::Result<_>>()?;
+                    anyhow::Ok(PositionConstraint::AnyField(constraints))
+                })
+                .collect::<anyhow::Result<_>>()?;
+        }
+
+        let text_query = match version {
+            SearchVersion::V1 => tokens
+                .iter()
+                .map(|text| {
+                    let term = Term::from_field_text(self.search_field, text);
+                    anyhow::ensure!(term.as_str().is_some(), "Term was not valid UTF8");
+                    Ok(QueryTerm::new(term, false))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            // Only the V2 search codepath can generate QueryTerm::Fuzzy
+            SearchVersion::V2 => {
+                Self::compile_tokens_with_typo_tolerance(self.search_field, &tokens)?
+            },
+        };
+        Ok((text_query, position_constraints))
+    }
+
     pub fn compile(
         &self,
         query: &InternalSearch,
@@ -695,6 +923,7 @@ impl TantivySearchIndexSchema {
 
         let mut search_text: Option<&str> = None;
         let mut filter_conditions = Vec::new();
+        let mut range_filters = Vec::new();
         let mut filter_reads = Vec::new();
         for filter in query.filters.iter() {
             match filter {
@@ -739,8 +968,67 @@ impl TantivySearchIndexSchema {
                     filter_conditions.push(CompiledFilterCondition::Must(term));
                     filter_reads.push(FilterConditionRead::Must(field_path.clone(), value.clone()));
                 },
+                InternalSearchFilterExpression::Gt(field_path, key)
+                | InternalSearchFilterExpression::Gte(field_path, key)
+                | InternalSearchFilterExpression::Lt(field_path, key)
+                | InternalSearchFilterExpression::Lte(field_path, key) => {
+                    let Some(field) = self.filter_value_fields.get(field_path) else {
+                        anyhow::bail!(ErrorMetadata::bad_request(
+                            "IncorrectFilterFieldError",
+                            format!(
+                                "Search query against {} contains a range filter on \
+                                 {field_path:?} but that field isn't indexed for filtering in \
+                                 `filterFields`.",
+                                query.printable_index_name()?,
+                            )
+                        ))
+                    };
+                    let (start, end) = match filter {
+                        InternalSearchFilterExpression::Gt(..) => {
+                            (Bound::Excluded(key.clone()), Bound::Unbounded)
+                        },
+                        InternalSearchFilterExpression::Gte(..) => {
+                            (Bound::Included(key.clone()), Bound::Unbounded)
+                        },
+                        InternalSearchFilterExpression::Lt(..) => {
+                            (Bound::Unbounded, Bound::Excluded(key.clone()))
+                        },
+                        _ => (Bound::Unbounded, Bound::Included(key.clone())),
+                    };
+                    let range = FilterRange { start, end };
+                    range_filters.push(RangeFilter {
+                        field: *field,
+                        range: range.clone(),
+                    });
+                    filter_reads.push(FilterConditionRead::Range(field_path.clone(), range));
+                },
             }
         }
+        let sort = query
+            .order
+            .as_ref()
+            .map(|order| {
+                let field = if order.field_path == *CREATION_TIME_FIELD_PATH {
+                    SortField::CreationTime
+                } else if let Some(field) = self.filter_value_fields.get(&order.field_path) {
+                    SortField::FilterField(*field)
+                } else {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "InvalidSearchOrderField",
+                        format!(
+                            "Search query against {} is ordered by {:?}, but search results can \
+                             only be ordered by `_creationTime` or a field in `filterFields`.",
+                            query.printable_index_name()?,
+                            order.field_path,
+                        )
+                    ))
+                };
+                anyhow::Ok(SearchSort {
+                    field,
+                    order: order.order,
+                })
+            })
+            .transpose()?;
 
         let Some(search_text) = search_text else {
             anyhow::bail!(ErrorMetadata::bad_request(
@@ -754,65 +1042,64 @@ impl TantivySearchIndexSchema {
             ))
         };
 
-        let mut token_stream = self.analyzer.token_stream(search_text);
-        let mut tokens = vec![];
-        // TODO(CX-5693): Consider how/if we should surface this to developers.
-        while tokens.len() < MAX_QUERY_TERMS
-            && let Some(token) = token_stream.next()
-        {
-            tokens.push(token.text.clone());
-        }
-        if tokens.len() == MAX_QUERY_TERMS && token_stream.next().is_some() {
-            log_search_token_limit_exceeded();
+        let (text_query, position_constraints) = self.compile_search_text(search_text, version)?;
+
+        // A write to any search field can change the results, so read each query
+        // term from all of them.
+        let mut text_reads = vec![];
+        for t in &text_query {
+            let term = TextQueryTerm::try_from(t.clone())?;
+            for (field_path, _) in self.search_fields() {
+                text_reads.push(TextQueryTermRead::new(
+                    field_path.clone(),
+                    self.index_analyzer,
+                    term.clone(),
+                ));
+            }
         }
 
-        let text_query = match version {
-            SearchVersion::V1 => tokens
-                .iter()
-                .map(|text| {
-                    let term = Term::from_field_text(self.search_field, text);
-                    anyhow::ensure!(term.as_str().is_some(), "Term was not valid UTF8");
-                    Ok(QueryTerm::new(term, false))
-                })
-                .collect::<anyhow::Result<Vec<_>>>()?,
-            // Only the V2 search codepath can generate QueryTerm::Fuzzy
-            SearchVersion::V2 => {
-                Self::compile_tokens_with_typo_tolerance(self.search_field, &tokens)?
-            },
-        };
-
-        let text_reads = text_query
-            .clone()
-            .into_iter()
-            .map(|t| {
-                anyhow::Ok(TextQueryTermRead::new(
-                    self.search_field_path.clone(),
-                    TextQueryTerm::try_from(t)?,
-                ))
-            })
-            .collect::<anyhow::Result<_>>()?;
-
-        if filter_conditions.len() > MAX_FILTER_CONDITIONS {
+        let num_filter_conditions = filter_conditions.len() + range_filters.len();
+        if num_filter_conditions > MAX_FILTER_CONDITIONS {
             anyhow::bail!(ErrorMetadata::bad_request(
                 "TooManyFilterConditionsInSearchQueryError",
                 format!(
                     "Search query against {} has too many filter conditions. Max: {} Actual: {}",
                     query.printable_index_name()?,
                     MAX_FILTER_CONDITIONS,
-                    filter_conditions.len()
+                    num_filter_conditions,
                 )
             ))
         }
         let query = CompiledQuery {
             text_query,
             filter_conditions,
+            position_constraints,
+            range_filters,
+            sort,
         };
-        let reads = QueryReads::new(text_reads, filter_reads.into());
+        let reads = QueryReads::new(text_reads.into(), filter_reads.into());
         metrics::log_compiled_query(&query);
 
         timer.finish();
         Ok((query, reads))
     }
+
+    /// Highlight where `search_text` matches the search field of a document's
+    /// `value`, with a snippet spanning `snippet_tokens` words. Returns `None`
+    /// if the document doesn't have a string in its search field.
+    pub fn highlight(
+        &self,
+        search_text: &str,
+        value: &ConvexObject,
+        snippet_tokens: usize,
+    ) -> anyhow::Result<Option<SearchHighlight>> {
+        let Some(ConvexValue::String(ref s)) = value.get_path(&self.search_field_path) else {
+            return Ok(None);
+        };
+        let (query_terms, _) = self.compile_search_text(search_text, SearchVersion::V2)?;
+        let highlight = highlight::highlight(&self.analyzer, &query_terms, &s[..], snippet_tokens)?;
+        Ok(Some(highlight))
+    }
 }
 
 pub struct DocumentLengths {
@@ -834,11 +1121,23 @@ pub enum SearchFileType {
 
 #[cfg(test)]
 mod test {
-    use std::collections::BTreeSet;
+    use std::collections::{
+        BTreeMap,
+        BTreeSet,
+    };
 
-    use common::bootstrap_model::index::text_index::DeveloperTextIndexConfig;
+    use common::bootstrap_model::index::text_index::{
+        DeveloperTextIndexConfig,
+        SearchFieldBoost,
+    };
+    use tantivy::Term;
 
     use crate::{
+        phrase::{
+            Phrase,
+            PositionConstraint,
+        },
+        SearchVersion,
         TantivySearchIndexSchema,
         SEARCH_FIELD_ID,
     };
@@ -850,7 +1149,9 @@ mod test {
     fn test_field_ids_dont_change() -> anyhow::Result<()> {
         let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: "mySearchField".parse()?,
+            extra_search_fields: BTreeMap::new(),
             filter_fields: BTreeSet::new(),
+            analyzer: Default::default(),
         });
         assert_eq!(schema.internal_id_field.field_id(), 0);
         assert_eq!(schema.ts_field.field_id(), 1);
@@ -858,4 +1159,56 @@ mod test {
         assert_eq!(schema.search_field.field_id(), SEARCH_FIELD_ID);
         Ok(())
     }
+
+    #[test]
+    fn test_extra_search_fields_declared_last() -> anyhow::Result<()> {
+        let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
+            search_field: "body".parse()?,
+            extra_search_fields: BTreeMap::from([("title".parse()?, SearchFieldBoost::new(2.)?)]),
+            filter_fields: BTreeSet::from(["channel".parse()?]),
+            analyzer: Default::default(),
+        });
+        assert_eq!(schema.search_field.field_id(), SEARCH_FIELD_ID);
+        assert_eq!(
+            schema.filter_fields[&"channel".parse()?].field_id(),
+            SEARCH_FIELD_ID + 1
+        );
+        let (title_field, _) = schema.extra_search_fields[&"title".parse()?];
+        assert_eq!(title_field.field_id(), SEARCH_FIELD_ID + 2);
+        assert_eq!(schema.search_field_boost(title_field), 2.);
+        assert_eq!(
+            schema.filter_value_fields[&"channel".parse()?].field_id(),
+            SEARCH_FIELD_ID + 3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_phrases_match_in_any_search_field() -> anyhow::Result<()> {
+        let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
+            search_field: "body".parse()?,
+            extra_search_fields: BTreeMap::from([("title".parse()?, SearchFieldBoost::new(2.)?)]),
+            filter_fields: BTreeSet::new(),
+            analyzer: Default::default(),
+        });
+        let (title_field, _) = schema.extra_search_fields[&"title".parse()?];
+        let (_, position_constraints) =
+            schema.compile_search_text("\"new york\"", SearchVersion::V2)?;
+        let phrase = |field| {
+            PositionConstraint::Phrase(Phrase {
+                terms: vec![
+                    (Term::from_field_text(field, "new"), 0),
+                    (Term::from_field_text(field, "york"), 1),
+                ],
+            })
+        };
+        assert_eq!(
+            position_constraints,
+            vec![PositionConstraint::AnyField(vec![
+                phrase(schema.search_field),
+                phrase(title_field),
+            ])]
+        );
+        Ok(())
+    }
 }
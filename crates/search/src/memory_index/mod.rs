@@ -20,6 +20,7 @@ use std::{
 use anyhow::Context;
 use common::{
     document::CreationTime,
+    query::FilterSortKey,
     types::{
         Timestamp,
         WriteTimestamp,
@@ -54,11 +55,19 @@ use crate::{
         MAX_UNIQUE_QUERY_TERMS,
     },
     convex_query::OrTerm,
+    filter_values::{
+        creation_time_sort_key,
+        FilterRange,
+        RangeFilter,
+        SearchSort,
+        SortField,
+    },
     memory_index::{
         bitset64::Bitset64,
         term_table::TermTable,
     },
     metrics,
+    phrase::PositionConstraint,
     query::{
         shortlist_and_id_mapping,
         CandidateRevisionPositions,
@@ -72,6 +81,7 @@ use crate::{
     scoring::{
         bm25_weight_boost_for_edit_distance,
         Bm25StatisticsDiff,
+        FieldBm25StatisticsDiff,
     },
     searcher::{
         Bm25Stats,
@@ -88,10 +98,45 @@ use crate::{
 pub struct Document {
     ts: WriteTimestamp,
     term_list: TermList,
-    num_search_tokens: u32,
+    // The number of tokens in each of the document's search fields.
+    num_search_tokens: Box<[(Field, u32)]>,
+    // The sort key of each filter field's value, keyed by the field storing it.
+    filter_values: Box<[(Field, FilterSortKey)]>,
     creation_time: CreationTime,
 }
 
+impl Document {
+    fn num_search_tokens(&self, field: Field) -> u32 {
+        self.num_search_tokens
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map_or(0, |(_, n)| *n)
+    }
+
+    fn filter_value(&self, field: Field) -> anyhow::Result<&FilterSortKey> {
+        self.filter_values
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, value)| value)
+            .with_context(|| format!("Missing value for filter field {field:?}"))
+    }
+
+    fn filter_values_size(&self) -> usize {
+        self.filter_values
+            .iter()
+            .map(|(_, value)| mem::size_of::<(Field, FilterSortKey)>() + value.len())
+            .sum()
+    }
+}
+
+/// A document's terms along with the values the memory index stores for it.
+#[derive(Clone, Debug)]
+pub struct IndexedDocument {
+    pub terms: Vec<DocumentTerm>,
+    pub filter_values: Vec<(Field, FilterSortKey)>,
+    pub creation_time: CreationTime,
+}
+
 #[derive(Clone, Debug)]
 pub struct Tombstone {
     id: InternalId,
@@ -117,6 +162,8 @@ pub struct MemoryTextIndex {
     documents: OrdMap<InternalId, Document>,
     // sum(d.terms.heap_allocations() for d in documents)
     documents_terms_size: TermListBytes,
+    // sum(d.filter_values_size() for d in documents)
+    documents_filter_values_size: usize,
 
     tombstones: Vector<(WriteTimestamp, Tombstone)>,
     // sum(t.terms.heap_allocations() for _, t in tombstones)
@@ -137,6 +184,7 @@ impl MemoryTextIndex {
 
             documents: OrdMap::new(),
             documents_terms_size: TermListBytes::ZERO,
+            documents_filter_values_size: 0,
 
             tombstones: Vector::new(),
             tombstones_terms_size: TermListBytes::ZERO,
@@ -161,6 +209,7 @@ impl MemoryTextIndex {
 
         size += self.documents.len() * mem::size_of::<(InternalId, Document)>();
         size += self.documents_terms_size.bytes();
+        size += self.documents_filter_values_size;
 
         size += self.tombstones.len() * mem::size_of::<(WriteTimestamp, Tombstone)>();
         size += self.tombstones_terms_size.bytes();
@@ -192,6 +241,7 @@ impl MemoryTextIndex {
                 self.term_table.decref(term_id, term_freq);
             }
             self.documents_terms_size -= document.term_list.heap_allocations();
+            self.documents_filter_values_size -= document.filter_values_size();
         }
 
         while let Some((ts, _)) = self.tombstones.front()
@@ -225,8 +275,8 @@ impl MemoryTextIndex {
         &mut self,
         id: InternalId,
         ts: WriteTimestamp,
-        old_value: Option<(Vec<DocumentTerm>, CreationTime)>,
-        new_value: Option<(Vec<DocumentTerm>, CreationTime)>,
+        old_value: Option<IndexedDocument>,
+        new_value: Option<IndexedDocument>,
     ) -> anyhow::Result<()> {
         let timer = metrics::index_update_timer();
 
@@ -253,10 +303,13 @@ impl MemoryTextIndex {
             }
             let stats = self.statistics.get_mut(&ts).unwrap();
 
-            if let Some((old_terms, _)) = &old_value {
+            if let Some(IndexedDocument {
+                terms: old_terms, ..
+            }) = &old_value
+            {
                 let term_set = old_terms
                     .iter()
-                    .filter(|doc_term| doc_term.field_id() == SEARCH_FIELD_ID)
+                    .filter(|doc_term| matches!(doc_term, DocumentTerm::Search { .. }))
                     .map(|doc_term| doc_term.term())
                     .collect::<BTreeSet<_>>();
                 for term in term_set {
@@ -287,10 +340,13 @@ impl MemoryTextIndex {
                     .checked_sub(1)
                     .ok_or_else(|| anyhow::anyhow!("Underflow on total docs diff"))?;
             }
-            if let Some((new_terms, _)) = &new_value {
+            if let Some(IndexedDocument {
+                terms: new_terms, ..
+            }) = &new_value
+            {
                 let term_set = new_terms
                     .iter()
-                    .filter(|doc_term| doc_term.field_id() == SEARCH_FIELD_ID)
+                    .filter(|doc_term| matches!(doc_term, DocumentTerm::Search { .. }))
                     .map(|doc_term| doc_term.term())
                     .collect::<BTreeSet<_>>();
                 for term in term_set {
@@ -323,7 +379,7 @@ impl MemoryTextIndex {
             }
         }
 
-        if let Some((terms, _)) = old_value {
+        if let Some(IndexedDocument { terms, .. }) = old_value {
             if let Some((prev_ts, _)) = self.tombstones.last() {
                 anyhow::ensure!(*prev_ts <= ts);
             }
@@ -347,15 +403,23 @@ impl MemoryTextIndex {
                     self.term_table.decref(term_id, term_freq);
                 }
                 self.documents_terms_size -= prev_document.term_list.heap_allocations();
+                self.documents_filter_values_size -= prev_document.filter_values_size();
             }
         }
 
-        if let Some((terms, creation_time)) = new_value {
-            let num_search_tokens = terms
-                .iter()
-                .filter(|doc_term| doc_term.field_id() == SEARCH_FIELD_ID)
-                .count()
-                .try_into()?;
+        if let Some(IndexedDocument {
+            terms,
+            filter_values,
+            creation_time,
+        }) = new_value
+        {
+            let mut num_search_tokens_by_field = BTreeMap::<Field, u32>::new();
+            for doc_term in &terms {
+                if let DocumentTerm::Search { term, .. } = doc_term {
+                    *num_search_tokens_by_field.entry(term.field()).or_default() += 1;
+                }
+            }
+            let num_search_tokens = num_search_tokens_by_field.into_iter().collect();
             let term_ids = terms
                 .iter()
                 .map(|doc_term| (self.term_table.incref(doc_term.term()), doc_term.position()))
@@ -366,8 +430,10 @@ impl MemoryTextIndex {
                 term_list,
                 creation_time,
                 num_search_tokens,
+                filter_values: filter_values.into(),
             };
             self.documents_terms_size += document.term_list.heap_allocations();
+            self.documents_filter_values_size += document.filter_values_size();
             assert!(self.documents.insert(id, document).is_none());
         }
 
@@ -504,6 +570,9 @@ impl MemoryTextIndex {
         &self,
         and_terms: &[Term],
         or_terms: &[OrTerm],
+        position_constraints: &[PositionConstraint<Term>],
+        range_filters: &[RangeFilter],
+        sort: Option<SearchSort>,
         stats: &Bm25Stats,
     ) -> anyhow::Result<Option<PreparedMemoryPostingListQuery>> {
         let _timer = metrics::index_prepare_posting_list_query_timer();
@@ -516,6 +585,19 @@ impl MemoryTextIndex {
             all_term_ids.insert(term_id);
             intersection_term_ids.insert(term_id);
         }
+        // If a position constraint is missing terms that every match needs, no
+        // document can match.
+        let mut prepared_constraints = Vec::with_capacity(position_constraints.len());
+        for constraint in position_constraints {
+            let Some(prepared) = constraint.map(|term| self.term_table.get(term)) else {
+                return Ok(None);
+            };
+            for term_id in prepared.required_terms() {
+                all_term_ids.insert(*term_id);
+                intersection_term_ids.insert(*term_id);
+            }
+            prepared_constraints.push(prepared);
+        }
         let mut weights_by_union_id = BTreeMap::new();
         for or_term in or_terms {
             let Some(term_id) = self.term_table.get(&or_term.term) else {
@@ -533,7 +615,7 @@ impl MemoryTextIndex {
                 average_fieldnorm,
             )
             .boost_by(or_term.bm25_boost);
-            weights_by_union_id.insert(term_id, weight);
+            weights_by_union_id.insert(term_id, (weight, or_term.term.field()));
         }
         if weights_by_union_id.is_empty() {
             return Ok(None);
@@ -543,13 +625,15 @@ impl MemoryTextIndex {
         let mut intersection_terms = Bitset64::new();
         let mut union_terms = Bitset64::new();
         let mut union_weights = Vec::with_capacity(weights_by_union_id.len());
+        let mut union_fields = Vec::with_capacity(weights_by_union_id.len());
         for (i, term_id) in all_term_ids.iter().enumerate() {
             if intersection_term_ids.contains(term_id) {
                 intersection_terms.insert(i);
             }
-            if let Some(bm25_weight) = weights_by_union_id.remove(term_id) {
+            if let Some((bm25_weight, field)) = weights_by_union_id.remove(term_id) {
                 union_terms.insert(i);
                 union_weights.push(bm25_weight);
+                union_fields.push(field);
             }
         }
         let prepared = PreparedMemoryPostingListQuery {
@@ -557,6 +641,13 @@ impl MemoryTextIndex {
             intersection_terms,
             union_terms,
             union_weights,
+            union_fields,
+            position_constraints: prepared_constraints,
+            range_filters: range_filters
+                .iter()
+                .map(|filter| (filter.field, filter.range.clone()))
+                .collect(),
+            sort,
         };
         Ok(Some(prepared))
     }
@@ -604,15 +695,43 @@ impl MemoryTextIndex {
             };
             let maybe_score = document
                 .term_list
-                .matches2_with_score(query, document.num_search_tokens);
+                .matches2_with_score(query, |field| document.num_search_tokens(field));
             let Some(bm25_score) = maybe_score else {
                 continue;
             };
+            let matches_positions = query
+                .position_constraints
+                .iter()
+                .all(|c| c.matches(|term_id| document.term_list.positions(*term_id)));
+            if !matches_positions {
+                continue;
+            }
+            let mut matches_ranges = true;
+            for (field, range) in &query.range_filters {
+                if !range.contains(document.filter_value(*field)?) {
+                    matches_ranges = false;
+                    break;
+                }
+            }
+            if !matches_ranges {
+                continue;
+            }
+            let sort_key = match query.sort {
+                Some(sort) => {
+                    let sort_key = match sort.field {
+                        SortField::CreationTime => creation_time_sort_key(document.creation_time),
+                        SortField::FilterField(field) => document.filter_value(field)?.clone(),
+                    };
+                    Some(sort.match_key(&sort_key))
+                },
+                None => None,
+            };
             let m = PostingListMatch {
                 internal_id,
                 ts: document.ts,
                 creation_time: document.creation_time,
                 bm25_score,
+                sort_key,
             };
             // NB: Since we're scanning over all of `self.documents` and they're not in BM25
             // score order, we can't early return if we've filled up `results` and
@@ -649,9 +768,14 @@ impl MemoryTextIndex {
                 // the disk index returns a combined shortlist of results that includes terms
                 // that the memory index does not have.
                 if let Some(term_id) = term_shortlist_ids.get(id) {
+                    let Ok(term) = term_shortlist.get_term(*id) else {
+                        continue;
+                    };
                     term_ids.insert(*term_id);
-                    *union_id_boosts.entry(*term_id).or_insert(0.) +=
-                        bm25_weight_boost_for_edit_distance(*dist);
+                    union_id_boosts
+                        .entry(*term_id)
+                        .or_insert((0., term.field()))
+                        .0 += bm25_weight_boost_for_edit_distance(*dist);
                 }
             }
         }
@@ -716,10 +840,12 @@ impl MemoryTextIndex {
             self.min_ts,
         );
         let from_ts = WriteTimestamp::Committed(snapshot_ts);
-        let (total_num_documents, total_num_search_tokens) =
+        let (total_num_documents, mut num_tokens_by_field) =
             self.total_num_documents_and_tokens(from_ts);
+        let search_field = Field::from_field_id(SEARCH_FIELD_ID);
 
         let mut term_statistics = BTreeMap::new();
+        let mut extra_field_statistics = BTreeMap::<u32, FieldBm25StatisticsDiff>::new();
         for term in terms {
             let Some(term_str) = term.as_str() else {
                 anyhow::bail!(
@@ -727,15 +853,24 @@ impl MemoryTextIndex {
                     term.typ()
                 );
             };
-            term_statistics.insert(
-                term_str.to_string(),
-                self.num_documents_with_term(from_ts, term),
-            );
+            let num_documents = self.num_documents_with_term(from_ts, term);
+            if term.field() == search_field {
+                term_statistics.insert(term_str.to_string(), num_documents);
+            } else {
+                let field = term.field();
+                let stats = extra_field_statistics.entry(field.field_id()).or_default();
+                stats.num_search_tokens_diff =
+                    num_tokens_by_field.get(&field).copied().unwrap_or(0);
+                stats
+                    .term_statistics
+                    .insert(term_str.to_string(), num_documents);
+            }
         }
         let diff = Bm25StatisticsDiff {
             term_statistics,
             num_documents_diff: total_num_documents,
-            num_search_tokens_diff: total_num_search_tokens,
+            num_search_tokens_diff: num_tokens_by_field.remove(&search_field).unwrap_or(0),
+            extra_field_statistics,
         };
         metrics::log_bm25_statistics_diff(timer, &diff);
         Ok(diff)
@@ -766,11 +901,12 @@ impl MemoryTextIndex {
             if document.ts <= WriteTimestamp::Committed(snapshot_ts) {
                 continue;
             };
-            let maybe_score = document.term_list.matches_with_score_and_positions(
-                query,
-                term_weights,
-                document.num_search_tokens,
-            );
+            let maybe_score =
+                document
+                    .term_list
+                    .matches_with_score_and_positions(query, term_weights, |field| {
+                        document.num_search_tokens(field)
+                    });
             let Some((score, positions)) = maybe_score else {
                 continue;
             };
@@ -818,23 +954,25 @@ impl MemoryTextIndex {
         num_documents as i64
     }
 
-    fn total_num_documents_and_tokens(&self, from_ts: WriteTimestamp) -> (i64, i64) {
+    /// The number of documents and number of tokens in each field added since
+    /// `from_ts`.
+    fn total_num_documents_and_tokens(
+        &self,
+        from_ts: WriteTimestamp,
+    ) -> (i64, BTreeMap<Field, i64>) {
         let _timer = metrics::total_num_documents_and_tokens_timer();
         let mut num_documents = 0i64;
-        let mut num_tokens = 0i64;
+        let mut num_tokens_by_field = BTreeMap::new();
         for (_, stats) in self
             .statistics
             .range((Bound::Excluded(from_ts), Bound::Unbounded))
         {
             num_documents += stats.total_docs_diff as i64;
-            // Only use the total_term_diff from SEARCH_FIELD_ID because this is called on
-            // the single segment search path.
-            num_tokens += *stats
-                .total_term_diff_by_field
-                .get(&Field::from_field_id(SEARCH_FIELD_ID))
-                .unwrap_or(&0) as i64;
-        }
-        (num_documents, num_tokens)
+            for (field, total_term_diff) in &stats.total_term_diff_by_field {
+                *num_tokens_by_field.entry(*field).or_insert(0) += *total_term_diff as i64;
+            }
+        }
+        (num_documents, num_tokens_by_field)
     }
 
     pub fn consistency_check(&self) -> anyhow::Result<()> {
@@ -844,14 +982,17 @@ impl MemoryTextIndex {
         let mut expected_refcounts = BTreeMap::new();
 
         let mut expected_document_terms = TermListBytes::ZERO;
+        let mut expected_filter_values = 0;
         for (_, document) in &self.documents {
             anyhow::ensure!(self.min_ts <= document.ts && document.ts <= self.max_ts);
             for (term_id, term_freq) in document.term_list.iter_term_freqs() {
                 *expected_refcounts.entry(term_id).or_insert(0) += term_freq;
             }
             expected_document_terms += document.term_list.heap_allocations();
+            expected_filter_values += document.filter_values_size();
         }
         anyhow::ensure!(expected_document_terms == self.documents_terms_size);
+        anyhow::ensure!(expected_filter_values == self.documents_filter_values_size);
 
         let mut prev_ts = None;
         let mut expected_tombstone_terms = TermListBytes::ZERO;
@@ -894,8 +1035,6 @@ pub fn build_term_weights(
     }
 
     let total_num_docs = combined_bm25_statistics.num_documents_diff.try_into()?;
-    let average_fieldnorm =
-        combined_bm25_statistics.num_search_tokens_diff as Score / total_num_docs as Score;
 
     // Construct a TermId -> ShortlistId mapping so we can search up each sorted
     // term in query to get a term in term_shortlist
@@ -916,6 +1055,9 @@ pub fn build_term_weights(
                 .get_term(*shortlist_id)?;
 
             let term_stats = combined_bm25_statistics.doc_freq(term)?;
+            let average_fieldnorm = combined_bm25_statistics.total_num_tokens(term.field())?
+                as Score
+                / total_num_docs as Score;
             anyhow::Ok(Bm25Weight::for_one_term(
                 term_stats,
                 total_num_docs,
@@ -937,6 +1079,17 @@ pub struct PreparedMemoryPostingListQuery {
 
     // BM25 weights corresponding to each element in `union_terms`.
     pub union_weights: Vec<Bm25Weight>,
+    // The field of each element in `union_terms`, whose length normalizes its
+    // score.
+    pub union_fields: Vec<Field>,
+
+    /// Phrase and proximity conditions, whose terms are all intersection terms.
+    pub position_constraints: Vec<PositionConstraint<TermId>>,
+
+    /// Ranges that the values of filter fields must be in. Tombstones are
+    /// matched without them, since a superset of deleted documents is fine.
+    pub range_filters: Vec<(Field, FilterRange)>,
+    pub sort: Option<SearchSort>,
 }
 
 impl PreparedMemoryPostingListQuery {
@@ -965,7 +1118,10 @@ mod tests {
     };
     use value::InternalId;
 
-    use super::MemoryTextIndex;
+    use super::{
+        IndexedDocument,
+        MemoryTextIndex,
+    };
     use crate::{
         memory_index::WriteTimestamp,
         DocumentTerm,
@@ -985,13 +1141,14 @@ mod tests {
             InternalId::MIN,
             WriteTimestamp::Committed(ts1),
             None,
-            Some((
-                vec![DocumentTerm::Search {
+            Some(IndexedDocument {
+                terms: vec![DocumentTerm::Search {
                     term: term.clone(),
                     pos: FieldPosition::default(),
                 }],
-                CreationTime::ONE,
-            )),
+                filter_values: vec![],
+                creation_time: CreationTime::ONE,
+            }),
         )?;
 
         // At t=1 we can see the document and have a size.
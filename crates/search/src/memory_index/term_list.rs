@@ -27,6 +27,7 @@ use sucds::{
 use tantivy::{
     fieldnorm::FieldNormReader,
     query::Bm25Weight,
+    schema::Field,
     Score,
 };
 use xorf::{
@@ -247,6 +248,23 @@ impl TermList {
         )
     }
 
+    /// All of the positions of `term_id` in the document, in increasing order.
+    pub fn positions(&self, term_id: TermId) -> Vec<u32> {
+        let Some(ref inner) = self.inner else {
+            return vec![];
+        };
+        let sorted_terms = [term_id];
+        let Some((_, pos)) = inner.term_matches(&sorted_terms).next() else {
+            return vec![];
+        };
+        let term_freq = inner.cumulative_freqs.delta(pos).unwrap();
+        let positions_end = inner.cumulative_freqs.select(pos).unwrap();
+        let positions_start = positions_end - term_freq;
+        (positions_start..positions_end)
+            .map(|i| inner.positions.access(i).unwrap() as u32)
+            .collect()
+    }
+
     pub fn matches(&self, query: &TermListBitsetQuery) -> bool {
         let Some(ref inner) = self.inner else {
             return false;
@@ -294,7 +312,7 @@ impl TermList {
     pub fn matches2_with_score(
         &self,
         query: &PreparedMemoryPostingListQuery,
-        num_search_tokens: u32,
+        num_search_tokens: impl Fn(Field) -> u32,
     ) -> Option<Score> {
         let inner = self.inner.as_ref()?;
         if !inner.term_filter_matches2(query) {
@@ -302,7 +320,6 @@ impl TermList {
         }
 
         let mut score = 0.;
-        let fieldnorm_id = FieldNormReader::fieldnorm_to_id(num_search_tokens);
 
         // Build up a bitset of which terms match.
         let mut matching_terms = Bitset64::new();
@@ -315,6 +332,9 @@ impl TermList {
                     .expect("term position missing from cumulative_freqs");
                 let union_rank = query.union_terms.rank(i);
                 let bm25_weight = &query.union_weights[union_rank];
+                let fieldnorm_id = FieldNormReader::fieldnorm_to_id(num_search_tokens(
+                    query.union_fields[union_rank],
+                ));
                 score += bm25_weight.score(fieldnorm_id, term_freq as u32);
             }
         }
@@ -340,7 +360,7 @@ impl TermList {
         &self,
         query: &TermListBitsetQuery,
         term_weights: &[Bm25Weight],
-        fieldnorm: u32,
+        num_search_tokens: impl Fn(Field) -> u32,
     ) -> Option<(Score, BTreeMap<TermId, Vec<u32>>)> {
         let inner = self.inner.as_ref()?;
 
@@ -352,7 +372,6 @@ impl TermList {
             return None;
         }
 
-        let fieldnorm_id = FieldNormReader::fieldnorm_to_id(fieldnorm);
         let mut matches = BitVec::<usize, Lsb0>::repeat(false, sorted_terms.len());
         let mut score = 0.;
         let mut union_idx = 0;
@@ -378,6 +397,8 @@ impl TermList {
             // Compute which index into `term_weights` we're at by counting the number of
             // bits in `is_union` set before our current position.
             let union_rank = union_ids.as_bitslice()[..i].count_ones();
+            let fieldnorm_id =
+                FieldNormReader::fieldnorm_to_id(num_search_tokens(query.union_fields[union_rank]));
             let candidate_score = term_weights[union_rank].score(fieldnorm_id, term_freq as u32);
 
             // Apply the scoring.
@@ -531,6 +552,7 @@ mod tests {
     use tantivy::{
         fieldnorm::FieldNormReader,
         query::Bm25Weight,
+        schema::Field,
     };
 
     use crate::{
@@ -575,6 +597,14 @@ mod tests {
 
         assert_eq!(term_freqs, computed_freqs);
         assert_eq!(term_positions, computed_positions);
+        for (term, positions) in &term_positions {
+            let positions_for_term = term_list.positions(*term);
+            assert!(positions_for_term.is_sorted());
+            assert_eq!(
+                positions_for_term.into_iter().collect::<BTreeSet<_>>(),
+                *positions
+            );
+        }
     }
 
     #[derive(Arbitrary, Debug)]
@@ -607,7 +637,7 @@ mod tests {
             for term_id in q.union_terms.iter() {
                 let term_id = TermId::from(*term_id);
                 term_ids.insert(term_id);
-                boosts.insert(term_id, 1.);
+                boosts.insert(term_id, (1., Field::from_field_id(0)));
             }
             let term_list_query = TermListBitsetQuery::new(term_ids, intersection_term_ids, boosts);
 
@@ -677,15 +707,14 @@ mod tests {
                     Bm25Weight::for_one_term(term.term_doc_freq, total_doc_freq, avg_fieldnorm);
                 term_weights.push(weight.clone());
                 term_weights_by_id.insert(term_id, weight);
-                boosts.insert(term_id, 1.);
+                boosts.insert(term_id, (1., Field::from_field_id(0)));
             }
             let term_list_query = TermListBitsetQuery::new(term_ids, intersection_term_ids, boosts);
 
-            let computed = term_list.matches_with_score_and_positions(
-                &term_list_query,
-                &term_weights,
-                fieldnorm,
-            );
+            let computed =
+                term_list.matches_with_score_and_positions(&term_list_query, &term_weights, |_| {
+                    fieldnorm
+                });
 
             let mut expected = None;
             if q.intersection_terms
@@ -718,6 +747,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_matches_with_score_uses_each_fields_length() {
+        let (body, title) = (Field::from_field_id(0), Field::from_field_id(1));
+        let term_list = TermList::new(vec![(1, FieldPosition(0)), (2, FieldPosition(1))]).unwrap();
+        let boosts = BTreeMap::from([(1, (1., body)), (2, (2., title))]);
+        let term_list_query =
+            TermListBitsetQuery::new(BTreeSet::from([1, 2]), BTreeSet::new(), boosts);
+        let weight = Bm25Weight::for_one_term(1, 10, 4.);
+        let num_search_tokens = |field| if field == body { 20 } else { 2 };
+
+        let (score, _) = term_list
+            .matches_with_score_and_positions(
+                &term_list_query,
+                &[weight.clone(), weight.clone()],
+                num_search_tokens,
+            )
+            .unwrap();
+        let expected = weight.score(FieldNormReader::fieldnorm_to_id(20), 1)
+            + 2. * weight.score(FieldNormReader::fieldnorm_to_id(2), 1);
+        assert_eq!(score, expected);
+    }
+
     proptest! {
         // It's useful during development to run many more tests in release builds:
         // PROPTEST_CASES=102400 RUSTFLAGS="-C target-cpu=native -C debug-assertions=yes" cargo test --release --lib term_list
@@ -0,0 +1,585 @@
+use std::{
+    cmp,
+    collections::BTreeSet,
+};
+
+use anyhow::Context;
+use tantivy::Term;
+
+/// A part of a text query's search text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchTextPart<'a> {
+    /// Unquoted words, which are matched individually.
+    Words(&'a str),
+    /// A quoted phrase, whose words must appear adjacently and in order.
+    Phrase(&'a str),
+    /// `near(n)`: the words or phrases on either side must appear within `n`
+    /// positions of each other.
+    Near(u32),
+}
+
+/// Split search text into unquoted words, quoted phrases, and `near(n)`
+/// operators, e.g. `"new york" near(3) pizza`. An unterminated quote extends to
+/// the end of the text. Text without quotes or operators is a single
+/// `SearchTextPart::Words`.
+pub fn parse_search_text(text: &str) -> Vec<SearchTextPart<'_>> {
+    let bytes = text.as_bytes();
+    let mut parts = vec![];
+    let mut words_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'"' {
+            push_words(&mut parts, &text[words_start..i]);
+            let phrase_start = i + 1;
+            let phrase_end = text[phrase_start..]
+                .find('"')
+                .map_or(text.len(), |j| phrase_start + j);
+            parts.push(SearchTextPart::Phrase(&text[phrase_start..phrase_end]));
+            i = cmp::min(phrase_end + 1, text.len());
+            words_start = i;
+            continue;
+        }
+        let at_word_start = i == 0 || bytes[i - 1].is_ascii_whitespace() || bytes[i - 1] == b'"';
+        if bytes[i] == b'n'
+            && at_word_start
+            && let Some((distance, len)) = parse_near(&text[i..])
+        {
+            push_words(&mut parts, &text[words_start..i]);
+            parts.push(SearchTextPart::Near(distance));
+            i += len;
+            words_start = i;
+            continue;
+        }
+        i += 1;
+    }
+    push_words(&mut parts, &text[words_start..]);
+    parts
+}
+
+fn push_words<'a>(parts: &mut Vec<SearchTextPart<'a>>, words: &'a str) {
+    if !words.trim().is_empty() {
+        parts.push(SearchTextPart::Words(words));
+    }
+}
+
+/// Parse a `near(n)` operator at the start of `text`, returning `n` and the
+/// operator's length.
+fn parse_near(text: &str) -> Option<(u32, usize)> {
+    let rest = text.strip_prefix("near(")?;
+    let digits = rest.find(')')?;
+    let distance = rest[..digits].parse().ok()?;
+    let len = "near(".len() + digits + 1;
+    match text[len..].chars().next() {
+        None | Some('"') => Some((distance, len)),
+        Some(c) if c.is_whitespace() => Some((distance, len)),
+        Some(_) => None,
+    }
+}
+
+/// The analyzed terms of a `SearchTextPart`, with their token positions.
+#[derive(Debug, Clone)]
+pub struct PartTerms<T> {
+    pub terms: Vec<(T, u32)>,
+    /// Whether some of the part's terms were dropped by the query term limit.
+    pub truncated: bool,
+}
+
+impl<T> Default for PartTerms<T> {
+    fn default() -> Self {
+        Self {
+            terms: vec![],
+            truncated: false,
+        }
+    }
+}
+
+impl<T: Clone> PartTerms<T> {
+    fn phrase(&self) -> Option<Phrase<T>> {
+        let (_, start) = self.terms.first()?;
+        if self.truncated {
+            return None;
+        }
+        let terms = self
+            .terms
+            .iter()
+            .map(|(term, position)| (term.clone(), position - start))
+            .collect();
+        Some(Phrase { terms })
+    }
+}
+
+/// Build the constraints for the quoted phrases and `near(n)` operators in
+/// `parts`, where `part_terms[i]` holds the terms of `parts[i]`. An unquoted
+/// operand of `near(n)` is the single word next to it, and operators missing
+/// an operand are ignored.
+pub fn position_constraints<T: Clone>(
+    parts: &[SearchTextPart<'_>],
+    part_terms: &[PartTerms<T>],
+) -> Vec<PositionConstraint<T>> {
+    let operand = |i: Option<usize>, last: bool| -> Option<Phrase<T>> {
+        let i = i?;
+        let terms = part_terms.get(i)?;
+        match parts.get(i)? {
+            SearchTextPart::Phrase(_) => terms.phrase(),
+            SearchTextPart::Words(_) if last && terms.truncated => None,
+            SearchTextPart::Words(_) => {
+                let (term, _) = if last {
+                    terms.terms.last()?
+                } else {
+                    terms.terms.first()?
+                };
+                Some(Phrase {
+                    terms: vec![(term.clone(), 0)],
+                })
+            },
+            SearchTextPart::Near(_) => None,
+        }
+    };
+    let mut constraints = vec![];
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            SearchTextPart::Words(_) => (),
+            SearchTextPart::Phrase(_) => {
+                if let Some(phrase) = part_terms[i].phrase() {
+                    constraints.push(PositionConstraint::Phrase(phrase));
+                }
+            },
+            SearchTextPart::Near(distance) => {
+                let (Some(left), Some(right)) =
+                    (operand(i.checked_sub(1), true), operand(Some(i + 1), false))
+                else {
+                    continue;
+                };
+                constraints.push(PositionConstraint::Near {
+                    left,
+                    right,
+                    distance: *distance,
+                });
+            },
+        }
+    }
+    constraints
+}
+
+/// Terms that must appear at fixed offsets from each other. The offsets come
+/// from the positions of the query's tokens, so a stop word removed from the
+/// middle of a phrase still leaves a gap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phrase<T> {
+    /// Each term along with its offset from the start of the phrase, in order.
+    pub terms: Vec<(T, u32)>,
+}
+
+impl<T> Phrase<T> {
+    fn len(&self) -> u32 {
+        self.terms.last().map_or(0, |(_, offset)| offset + 1)
+    }
+
+    fn map<U>(&self, f: &mut impl FnMut(&T) -> Option<U>) -> Option<Phrase<U>> {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(term, offset)| Some((f(term)?, *offset)))
+            .collect::<Option<_>>()?;
+        Some(Phrase { terms })
+    }
+
+    /// The sorted positions at which the phrase starts in a document.
+    fn start_positions(&self, positions: &mut impl FnMut(&T) -> Vec<u32>) -> Vec<u32> {
+        let mut starts: Option<Vec<u32>> = None;
+        for (term, offset) in &self.terms {
+            let term_starts = positions(term)
+                .into_iter()
+                .filter_map(|position| position.checked_sub(*offset));
+            let next = match starts {
+                None => term_starts.collect::<BTreeSet<_>>().into_iter().collect(),
+                Some(starts) => {
+                    let term_starts = term_starts.collect::<BTreeSet<_>>();
+                    starts
+                        .into_iter()
+                        .filter(|start| term_starts.contains(start))
+                        .collect()
+                },
+            };
+            if next.is_empty() {
+                return vec![];
+            }
+            starts = Some(next);
+        }
+        starts.unwrap_or_default()
+    }
+}
+
+/// A condition on where a text query's terms appear in a search field.
+///
+/// Documents are only checked against these after they've matched the rest
+/// of the query, so every term here is also one of the query's terms, and
+/// the query's reads of those terms cover any document that could start or
+/// stop matching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionConstraint<T> {
+    /// The phrase appears in the document.
+    Phrase(Phrase<T>),
+    /// Both phrases appear, in either order, with at most `distance` other
+    /// positions between them.
+    Near {
+        left: Phrase<T>,
+        right: Phrase<T>,
+        distance: u32,
+    },
+    /// At least one of the constraints holds. An index with several search
+    /// fields has one per field, each with its terms in that field, so a
+    /// phrase only needs to appear within one of them.
+    AnyField(Vec<PositionConstraint<T>>),
+}
+
+impl<T> PositionConstraint<T> {
+    pub fn terms(&self) -> Vec<&T> {
+        match self {
+            PositionConstraint::Phrase(phrase) => {
+                phrase.terms.iter().map(|(term, _)| term).collect()
+            },
+            PositionConstraint::Near { left, right, .. } => left
+                .terms
+                .iter()
+                .chain(right.terms.iter())
+                .map(|(term, _)| term)
+                .collect(),
+            PositionConstraint::AnyField(constraints) => {
+                constraints.iter().flat_map(|c| c.terms()).collect()
+            },
+        }
+    }
+
+    /// The terms every matching document contains. None of an `AnyField`
+    /// constraint's terms are required, since each is only in one field.
+    pub fn required_terms(&self) -> Vec<&T> {
+        match self {
+            PositionConstraint::Phrase(_) | PositionConstraint::Near { .. } => self.terms(),
+            PositionConstraint::AnyField(_) => vec![],
+        }
+    }
+
+    /// Replace each term with `f(term)`, returning `None` if `f` does. An
+    /// `AnyField` constraint instead drops the alternatives `f` rejects, and
+    /// is only `None` if it rejects all of them.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> Option<U>) -> Option<PositionConstraint<U>> {
+        self.map_dyn(&mut f)
+    }
+
+    fn map_dyn<U>(&self, mut f: &mut dyn FnMut(&T) -> Option<U>) -> Option<PositionConstraint<U>> {
+        let constraint = match self {
+            PositionConstraint::Phrase(phrase) => PositionConstraint::Phrase(phrase.map(&mut f)?),
+            PositionConstraint::Near {
+                left,
+                right,
+                distance,
+            } => PositionConstraint::Near {
+                left: left.map(&mut f)?,
+                right: right.map(&mut f)?,
+                distance: *distance,
+            },
+            PositionConstraint::AnyField(constraints) => {
+                let constraints: Vec<_> = constraints
+                    .iter()
+                    .filter_map(|c| c.map_dyn(&mut *f))
+                    .collect();
+                if constraints.is_empty() {
+                    return None;
+                }
+                PositionConstraint::AnyField(constraints)
+            },
+        };
+        Some(constraint)
+    }
+
+    /// Check the constraint against a document, where `positions` returns the
+    /// positions of a term in the document.
+    pub fn matches(&self, mut positions: impl FnMut(&T) -> Vec<u32>) -> bool {
+        self.matches_dyn(&mut positions)
+    }
+
+    fn matches_dyn(&self, mut positions: &mut dyn FnMut(&T) -> Vec<u32>) -> bool {
+        match self {
+            PositionConstraint::Phrase(phrase) => {
+                !phrase.start_positions(&mut positions).is_empty()
+            },
+            PositionConstraint::Near {
+                left,
+                right,
+                distance,
+            } => {
+                let left_starts = left.start_positions(&mut positions);
+                if left_starts.is_empty() {
+                    return false;
+                }
+                let right_starts = right.start_positions(&mut positions);
+                let (left_len, right_len) = (left.len(), right.len());
+                left_starts.into_iter().any(|l| {
+                    // Only the right phrases starting in this window can be close enough.
+                    let lowest = l.saturating_sub(right_len + distance);
+                    let highest = l.saturating_add(left_len + distance);
+                    let first = right_starts.partition_point(|r| *r < lowest);
+                    right_starts[first..]
+                        .iter()
+                        .take_while(|r| **r <= highest)
+                        .any(|&r| {
+                            // The phrases can't overlap, so a term near itself needs two
+                            // occurrences.
+                            let gap = if r >= l + left_len {
+                                r - (l + left_len)
+                            } else if l >= r + right_len {
+                                l - (r + right_len)
+                            } else {
+                                return false;
+                            };
+                            gap <= *distance
+                        })
+                })
+            },
+            PositionConstraint::AnyField(constraints) => {
+                constraints.iter().any(|c| c.matches_dyn(&mut *positions))
+            },
+        }
+    }
+}
+
+impl From<Phrase<Term>> for pb::searchlight::Phrase {
+    fn from(phrase: Phrase<Term>) -> Self {
+        pb::searchlight::Phrase {
+            terms: phrase
+                .terms
+                .into_iter()
+                .map(|(term, offset)| pb::searchlight::PhraseTerm {
+                    term: Some(term.as_slice().to_vec()),
+                    offset: Some(offset),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<pb::searchlight::Phrase> for Phrase<Term> {
+    type Error = anyhow::Error;
+
+    fn try_from(phrase: pb::searchlight::Phrase) -> Result<Self, Self::Error> {
+        let terms = phrase
+            .terms
+            .into_iter()
+            .map(|t| {
+                anyhow::Ok((
+                    Term::wrap(t.term.context("Missing term")?),
+                    t.offset.context("Missing offset")?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Phrase { terms })
+    }
+}
+
+impl From<PositionConstraint<Term>> for pb::searchlight::PositionConstraint {
+    fn from(constraint: PositionConstraint<Term>) -> Self {
+        let constraint = match constraint {
+            PositionConstraint::Phrase(phrase) => {
+                pb::searchlight::position_constraint::Constraint::Phrase(phrase.into())
+            },
+            PositionConstraint::Near {
+                left,
+                right,
+                distance,
+            } => pb::searchlight::position_constraint::Constraint::Near(
+                pb::searchlight::NearConstraint {
+                    left: Some(left.into()),
+                    right: Some(right.into()),
+                    distance: Some(distance),
+                },
+            ),
+            PositionConstraint::AnyField(constraints) => {
+                pb::searchlight::position_constraint::Constraint::AnyField(
+                    pb::searchlight::AnyFieldConstraint {
+                        constraints: constraints.into_iter().map(Into::into).collect(),
+                    },
+                )
+            },
+        };
+        pb::searchlight::PositionConstraint {
+            constraint: Some(constraint),
+        }
+    }
+}
+
+impl TryFrom<pb::searchlight::PositionConstraint> for PositionConstraint<Term> {
+    type Error = anyhow::Error;
+
+    fn try_from(constraint: pb::searchlight::PositionConstraint) -> Result<Self, Self::Error> {
+        let constraint = match constraint.constraint.context("Missing constraint")? {
+            pb::searchlight::position_constraint::Constraint::Phrase(phrase) => {
+                PositionConstraint::Phrase(phrase.try_into()?)
+            },
+            pb::searchlight::position_constraint::Constraint::Near(near) => {
+                PositionConstraint::Near {
+                    left: near.left.context("Missing left phrase")?.try_into()?,
+                    right: near.right.context("Missing right phrase")?.try_into()?,
+                    distance: near.distance.context("Missing distance")?,
+                }
+            },
+            pb::searchlight::position_constraint::Constraint::AnyField(any_field) => {
+                PositionConstraint::AnyField(
+                    any_field
+                        .constraints
+                        .into_iter()
+                        .map(PositionConstraint::try_from)
+                        .collect::<anyhow::Result<_>>()?,
+                )
+            },
+        };
+        Ok(constraint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        parse_search_text,
+        position_constraints,
+        PartTerms,
+        Phrase,
+        PositionConstraint,
+        SearchTextPart,
+    };
+
+    fn phrase(terms: &[(&'static str, u32)]) -> Phrase<&'static str> {
+        Phrase {
+            terms: terms.to_vec(),
+        }
+    }
+
+    fn matches(constraint: &PositionConstraint<&'static str>, text: &str) -> bool {
+        let mut positions: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+        for (i, word) in text.split_whitespace().enumerate() {
+            positions.entry(word).or_default().push(i as u32);
+        }
+        constraint.matches(|term| positions.get(term).cloned().unwrap_or_default())
+    }
+
+    #[test]
+    fn test_parse_search_text() {
+        assert_eq!(
+            parse_search_text("hello world"),
+            vec![SearchTextPart::Words("hello world")]
+        );
+        assert_eq!(
+            parse_search_text("best \"new york\" pizza near(2) oven"),
+            vec![
+                SearchTextPart::Words("best "),
+                SearchTextPart::Phrase("new york"),
+                SearchTextPart::Words(" pizza "),
+                SearchTextPart::Near(2),
+                SearchTextPart::Words(" oven"),
+            ]
+        );
+        // Operators have to be their own word.
+        assert_eq!(
+            parse_search_text("nearly near(x) anear(1)"),
+            vec![SearchTextPart::Words("nearly near(x) anear(1)")]
+        );
+        assert_eq!(
+            parse_search_text("\"unterminated phrase"),
+            vec![SearchTextPart::Phrase("unterminated phrase")]
+        );
+    }
+
+    #[test]
+    fn test_position_constraints() {
+        let parts = parse_search_text("best \"new york\" pizza near(2) oven near(1)");
+        let terms = |terms: &[(&'static str, u32)]| PartTerms {
+            terms: terms.to_vec(),
+            truncated: false,
+        };
+        let part_terms = vec![
+            terms(&[("best", 0)]),
+            terms(&[("new", 0), ("york", 1)]),
+            terms(&[("pizza", 0)]),
+            PartTerms::default(),
+            terms(&[("oven", 0)]),
+            PartTerms::default(),
+        ];
+        assert_eq!(
+            position_constraints(&parts, &part_terms),
+            vec![
+                PositionConstraint::Phrase(phrase(&[("new", 0), ("york", 1)])),
+                PositionConstraint::Near {
+                    left: phrase(&[("pizza", 0)]),
+                    right: phrase(&[("oven", 0)]),
+                    distance: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_phrase() {
+        let constraint = PositionConstraint::Phrase(phrase(&[("new", 0), ("york", 1)]));
+        assert!(matches(&constraint, "pizza in new york city"));
+        assert!(!matches(&constraint, "york is new"));
+        assert!(!matches(&constraint, "new jersey and york"));
+
+        // A gap left by a stop word.
+        let constraint = PositionConstraint::Phrase(phrase(&[("state", 0), ("art", 2)]));
+        assert!(matches(&constraint, "state of art"));
+        assert!(!matches(&constraint, "state art"));
+    }
+
+    #[test]
+    fn test_near() {
+        let constraint = PositionConstraint::Near {
+            left: phrase(&[("pizza", 0)]),
+            right: phrase(&[("oven", 0)]),
+            distance: 1,
+        };
+        assert!(matches(&constraint, "pizza oven"));
+        assert!(matches(&constraint, "oven for pizza"));
+        assert!(!matches(&constraint, "pizza in the oven"));
+
+        let constraint = PositionConstraint::Near {
+            left: phrase(&[("new", 0), ("york", 1)]),
+            right: phrase(&[("pizza", 0)]),
+            distance: 0,
+        };
+        assert!(matches(&constraint, "pizza new york"));
+        assert!(!matches(&constraint, "new pizza york"));
+
+        // A term near itself needs two occurrences.
+        let constraint = PositionConstraint::Near {
+            left: phrase(&[("echo", 0)]),
+            right: phrase(&[("echo", 0)]),
+            distance: 2,
+        };
+        assert!(!matches(&constraint, "echo"));
+        assert!(matches(&constraint, "echo echo"));
+    }
+
+    #[test]
+    fn test_any_field() {
+        let constraint = PositionConstraint::AnyField(vec![
+            PositionConstraint::Phrase(phrase(&[("new", 0), ("york", 1)])),
+            PositionConstraint::Phrase(phrase(&[("title:new", 0), ("title:york", 1)])),
+        ]);
+        assert!(matches(&constraint, "pizza in new york"));
+        assert!(matches(&constraint, "title:new title:york pizza"));
+        assert!(!matches(&constraint, "new title:york"));
+        assert!(constraint.required_terms().is_empty());
+
+        // Rejecting a term drops only the alternatives that contain it.
+        let mapped = constraint.map(|term| (!term.starts_with("title:")).then_some(*term));
+        assert_eq!(
+            mapped,
+            Some(PositionConstraint::AnyField(vec![
+                PositionConstraint::Phrase(phrase(&[("new", 0), ("york", 1)]))
+            ]))
+        );
+        assert_eq!(constraint.map(|_| None::<&str>), None);
+    }
+}
@@ -4,18 +4,25 @@ use std::{
         BTreeSet,
         HashSet,
     },
-    ops::Deref,
+    ops::{
+        Bound,
+        Deref,
+    },
 };
 
 use anyhow::Context;
 use bitvec::vec::BitVec;
 use common::{
+    bootstrap_model::index::text_index::TextIndexAnalyzer,
     document::{
         CreationTime,
         PackedDocument,
     },
     index::IndexKeyBytes,
-    query::FilterValue,
+    query::{
+        FilterSortKey,
+        FilterValue,
+    },
     types::{
         SubscriberId,
         TabletIndexName,
@@ -52,13 +59,19 @@ use value::{
 };
 
 use crate::{
-    convex_en,
+    filter_values::{
+        FilterRange,
+        RangeFilter,
+        SearchSort,
+    },
     memory_index::{
         art::ART,
         TermId,
     },
     metrics,
+    phrase::PositionConstraint,
     scoring::term_from_str,
+    text_analyzer,
     EditDistance,
 };
 
@@ -67,6 +80,11 @@ use crate::{
 pub struct CompiledQuery {
     pub text_query: Vec<QueryTerm>,
     pub filter_conditions: Vec<CompiledFilterCondition>,
+    /// Phrases and proximity conditions on the terms in `text_query`.
+    pub position_constraints: Vec<PositionConstraint<Term>>,
+    pub range_filters: Vec<RangeFilter>,
+    /// Order results by a field's value rather than by relevance.
+    pub sort: Option<SearchSort>,
 }
 
 impl CompiledQuery {
@@ -90,6 +108,17 @@ impl CompiledQuery {
                 // TODO(CX-5481): get rid of this `Term::wrap` call. Need to propagate the Field for these.
                 .map(|bytes| CompiledFilterCondition::Must(Term::wrap(bytes)))
                 .collect_vec(),
+            position_constraints: value
+                .position_constraints
+                .into_iter()
+                .map(PositionConstraint::try_from)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            range_filters: value
+                .range_filters
+                .into_iter()
+                .map(RangeFilter::try_from)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            sort: value.sort.map(SearchSort::try_from).transpose()?,
         })
     }
 }
@@ -107,6 +136,17 @@ impl From<CompiledQuery> for pb::searchlight::TextQuery {
                 .into_iter()
                 .map(|CompiledFilterCondition::Must(term)| term.as_slice().to_vec())
                 .collect_vec(),
+            position_constraints: value
+                .position_constraints
+                .into_iter()
+                .map(pb::searchlight::PositionConstraint::from)
+                .collect_vec(),
+            range_filters: value
+                .range_filters
+                .into_iter()
+                .map(pb::searchlight::RangeFilter::from)
+                .collect_vec(),
+            sort: value.sort.map(pb::searchlight::SearchSort::from),
         }
     }
 }
@@ -432,6 +472,8 @@ pub struct TermListBitsetQuery {
     pub union_terms: BitVec,
     /// Score multiplier for a match of this union term
     pub union_id_boosts: Vec<Score>,
+    /// The field of this union term, whose length normalizes its score
+    pub union_fields: Vec<Field>,
 }
 
 impl TermListBitsetQuery {
@@ -440,24 +482,27 @@ impl TermListBitsetQuery {
         intersection_terms: BitVec::EMPTY,
         union_terms: BitVec::EMPTY,
         union_id_boosts: vec![],
+        union_fields: vec![],
     };
 
     pub fn new(
         term_ids: BTreeSet<TermId>,
         intersection_term_ids: BTreeSet<TermId>,
-        boosts_by_union_id: BTreeMap<TermId, Score>,
+        boosts_and_fields_by_union_id: BTreeMap<TermId, (Score, Field)>,
     ) -> Self {
         let sorted_terms = term_ids.into_iter().collect_vec();
 
         let mut intersection_terms = BitVec::repeat(false, sorted_terms.len());
         let mut union_terms = BitVec::repeat(false, sorted_terms.len());
         let mut union_id_boosts = Vec::with_capacity(sorted_terms.len());
+        let mut union_fields = Vec::with_capacity(sorted_terms.len());
 
         for (i, term) in sorted_terms.iter().enumerate() {
             intersection_terms.set(i, intersection_term_ids.contains(term));
-            if let Some(boost) = boosts_by_union_id.get(term) {
+            if let Some((boost, field)) = boosts_and_fields_by_union_id.get(term) {
                 union_terms.set(i, true);
                 union_id_boosts.push(*boost);
+                union_fields.push(*field);
             }
         }
 
@@ -466,6 +511,7 @@ impl TermListBitsetQuery {
             intersection_terms,
             union_terms,
             union_id_boosts,
+            union_fields,
         }
     }
 
@@ -595,12 +641,19 @@ impl QueryResults {
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct TextQueryTermRead {
     pub field_path: FieldPath,
+    /// The index's analyzer, which documents must be tokenized with to check
+    /// whether they match `term`.
+    pub analyzer: TextIndexAnalyzer,
     pub term: TextQueryTerm,
 }
 
 impl TextQueryTermRead {
-    pub fn new(field_path: FieldPath, term: TextQueryTerm) -> Self {
-        Self { field_path, term }
+    pub fn new(field_path: FieldPath, analyzer: TextIndexAnalyzer, term: TextQueryTerm) -> Self {
+        Self {
+            field_path,
+            analyzer,
+            term,
+        }
     }
 }
 
@@ -715,12 +768,35 @@ impl HeapSize for TextQueryTermRead {
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub enum FilterConditionRead {
     Must(FieldPath, FilterValue),
+    Range(FieldPath, FilterRange),
+}
+
+impl FilterConditionRead {
+    fn matches(&self, document: &PackedDocument) -> bool {
+        match self {
+            FilterConditionRead::Must(field_path, filter_value) => {
+                let document_value = document.value().get_path(field_path);
+                FilterValue::from_search_value(document_value.as_ref()) == *filter_value
+            },
+            FilterConditionRead::Range(field_path, range) => {
+                let document_value = document.value().get_path(field_path);
+                range.contains(&FilterSortKey::from_search_value(document_value.as_ref()))
+            },
+        }
+    }
 }
 
 impl HeapSize for FilterConditionRead {
     fn heap_size(&self) -> usize {
         match self {
             FilterConditionRead::Must(p, v) => p.heap_size() + v.heap_size(),
+            FilterConditionRead::Range(p, range) => {
+                let bound_size = |bound: &Bound<FilterSortKey>| match bound {
+                    Bound::Included(key) | Bound::Excluded(key) => key.heap_size(),
+                    Bound::Unbounded => 0,
+                };
+                p.heap_size() + bound_size(&range.start) + bound_size(&range.end)
+            },
         }
     }
 }
@@ -785,7 +861,9 @@ impl HeapSize for QueryReads {
 
 #[derive(Debug, Clone)]
 struct SearchTermTries<T: Clone + Ord> {
-    terms: BTreeMap<FieldPath, Tries<T>>,
+    /// Documents are tokenized with each analyzer before looking up their
+    /// tokens in the analyzer's tries.
+    terms: BTreeMap<(FieldPath, TextIndexAnalyzer), Tries<T>>,
 }
 
 impl<T: Clone + Ord> SearchTermTries<T> {
@@ -796,19 +874,15 @@ impl<T: Clone + Ord> SearchTermTries<T> {
     }
 
     #[fastrace::trace]
-    fn overlaps_document<'a>(
-        &'a self,
-        document: &'a PackedDocument,
-        analyzer: &'a TextAnalyzer,
-    ) -> bool {
+    fn overlaps_document<'a>(&'a self, document: &'a PackedDocument) -> bool {
         let mut result = BTreeSet::new();
 
-        for (path, tries) in self.terms.iter() {
+        for ((path, analyzer), tries) in self.terms.iter() {
             let Some(ConvexValue::String(document_text)) = document.value().get_path(path) else {
                 continue;
             };
 
-            let mut tokens = ValueTokens::new(analyzer, &document_text);
+            let mut tokens = ValueTokens::new(&text_analyzer(*analyzer), &document_text);
             tries.matching_values(&mut tokens, &mut result);
             if !result.is_empty() {
                 return true;
@@ -824,7 +898,7 @@ impl<T: Clone + Ord> SearchTermTries<T> {
             let (token, max_distance, prefix) = text_query.term.fuzzy_params();
             let art = self
                 .terms
-                .entry(path.clone())
+                .entry((path.clone(), text_query.analyzer))
                 .or_insert_with(Tries::new)
                 .tries
                 .entry((prefix, max_distance))
@@ -845,8 +919,8 @@ impl<T: Clone + Ord> SearchTermTries<T> {
             let value = value.clone();
             let tries = self
                 .terms
-                .get_mut(path)
-                .unwrap_or_else(|| panic!("Missing tries for {}", path));
+                .get_mut(&(path.clone(), text_query.analyzer))
+                .unwrap_or_else(|| panic!("Missing tries for {} ({})", path, text_query.analyzer));
             let trie = tries
                 .tries
                 .get_mut(&(prefix, max_distance))
@@ -924,12 +998,9 @@ impl QueryReads {
         let _timer = metrics::query_reads_overlaps_timer();
 
         for filter_condition in &self.filter_conditions {
-            let FilterConditionRead::Must(field_path, filter_value) = filter_condition;
-            let document_value = document.value().get_path(field_path);
-            let document_value = FilterValue::from_search_value(document_value.as_ref());
             // If the document doesn't match the filter condition, we can skip checking
             // fuzzy terms
-            if document_value != *filter_value {
+            if !filter_condition.matches(document) {
                 metrics::log_query_reads_outcome(false);
                 return false;
             }
@@ -942,8 +1013,7 @@ impl QueryReads {
         }
         // If all the filter conditions match and there are text queries, we then check
         // for fuzzy matches.
-        let analyzer = convex_en();
-        let is_fuzzy_match = self.fuzzy_terms.overlaps_document(document, &analyzer);
+        let is_fuzzy_match = self.fuzzy_terms.overlaps_document(document);
         metrics::log_query_reads_outcome(is_fuzzy_match);
         is_fuzzy_match
     }
@@ -1010,11 +1080,7 @@ impl TextSearchSubscriptions {
 
             for (subscriber_id, filter_conditions) in filter_conditions_map {
                 for filter_condition in filter_conditions {
-                    let FilterConditionRead::Must(field_path, filter_value) = filter_condition;
-                    let document_value = document.value().get_path(field_path);
-                    let document_value = FilterValue::from_search_value(document_value.as_ref());
-
-                    if document_value == *filter_value {
+                    if filter_condition.matches(document) {
                         metrics::log_query_reads_outcome(true);
                         to_notify.insert(*subscriber_id);
                     }
@@ -1031,17 +1097,16 @@ impl TextSearchSubscriptions {
     /// reads/subscriptions is significantly larger than the number of
     /// tokens in the document.
     fn add_fuzzy_matches(&self, document: &PackedDocument, matches: &mut BTreeSet<SubscriberId>) {
-        let analyzer = convex_en();
         for (_, fuzzy_terms) in self
             .fuzzy_searches
             .iter()
             .filter(|(index, _)| *index.table() == document.id().tablet_id)
         {
-            for (field, tries) in fuzzy_terms.terms.iter() {
+            for ((field, analyzer), tries) in fuzzy_terms.terms.iter() {
                 let Some(ConvexValue::String(value)) = document.value().get_path(field) else {
                     continue;
                 };
-                let mut tokens = ValueTokens::new(&analyzer, &value);
+                let mut tokens = ValueTokens::new(&text_analyzer(*analyzer), &value);
                 tries.matching_values(&mut tokens, matches);
             }
         }
@@ -1140,7 +1205,6 @@ mod tests {
 
     #[test]
     fn test_search_term_tries_overlaps() -> anyhow::Result<()> {
-        let analyzer = convex_en();
         let mut tries = SearchTermTries::new();
 
         // Create a document with a text field
@@ -1159,24 +1223,26 @@ mod tests {
         // Add a search term that matches the document using extend
         let text_query = TextQueryTermRead::new(
             FieldPath::from_str("title")?,
+            TextIndexAnalyzer::Standard,
             TextQueryTerm::Exact("hello".to_string()),
         );
         let text_queries = WithHeapSize::from(vec![text_query]);
         tries.extend((), &text_queries);
 
         // Test that the document matches
-        assert!(tries.overlaps_document(&doc, &analyzer));
+        assert!(tries.overlaps_document(&doc));
 
         // Add a non-matching term
         let text_query = TextQueryTermRead::new(
             FieldPath::from_str("title")?,
+            TextIndexAnalyzer::Standard,
             TextQueryTerm::Exact("goodbye".to_string()),
         );
         let text_queries = WithHeapSize::from(vec![text_query]);
         tries.extend((), &text_queries);
 
         // Document should still match because it matches at least one term
-        assert!(tries.overlaps_document(&doc, &analyzer));
+        assert!(tries.overlaps_document(&doc));
 
         // Create a document that doesn't match any terms
         let mut map = BTreeMap::new();
@@ -1192,17 +1258,17 @@ mod tests {
         )?);
 
         // Document should not match
-        assert!(!tries.overlaps_document(&doc, &analyzer));
+        assert!(!tries.overlaps_document(&doc));
         Ok(())
     }
 
     #[test]
     fn test_search_term_tries_overlaps_returns_false_if_the_field_does_not_exist(
     ) -> anyhow::Result<()> {
-        let analyzer = convex_en();
         let mut tries = SearchTermTries::new();
         let text_query = TextQueryTermRead::new(
             FieldPath::from_str("title")?,
+            TextIndexAnalyzer::Standard,
             TextQueryTerm::Exact("hello".to_string()),
         );
         let text_queries = WithHeapSize::from(vec![text_query]);
@@ -1214,7 +1280,7 @@ mod tests {
             ConvexObject::try_from(btreemap! {})?,
         )?);
 
-        assert!(!tries.overlaps_document(&doc, &analyzer));
+        assert!(!tries.overlaps_document(&doc));
         Ok(())
     }
 
@@ -1238,6 +1304,7 @@ mod tests {
         let query_reads = QueryReads::new(
             WithHeapSize::from(vec![TextQueryTermRead::new(
                 FieldPath::from_str("text")?,
+                TextIndexAnalyzer::Standard,
                 TextQueryTerm::Exact("hello".to_string()),
             )]),
             WithHeapSize::default(),
@@ -1283,6 +1350,7 @@ mod tests {
         let query_reads = QueryReads::new(
             WithHeapSize::from(vec![TextQueryTermRead::new(
                 FieldPath::from_str("text")?,
+                TextIndexAnalyzer::Standard,
                 TextQueryTerm::Exact("hello".to_string()),
             )]),
             WithHeapSize::default(),
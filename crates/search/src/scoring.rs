@@ -19,14 +19,25 @@ pub fn term_from_str(term_value: &str) -> Term {
 
 /// Per-term statistics used to compute BM25 scores.
 ///
-/// Note that this only includes terms for the search field of the query.
-/// Filter fields are not included.
+/// Note that this only includes terms for the search fields of the query.
+/// Filter fields are not included. `term_statistics` and
+/// `num_search_tokens_diff` are for the primary search field, and
+/// `extra_field_statistics` has the same statistics for each of the index's
+/// extra search fields, keyed by field id.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct Bm25StatisticsDiff {
     pub term_statistics: BTreeMap<String, i64>,
     pub num_documents_diff: i64,
     pub num_search_tokens_diff: i64,
+    pub extra_field_statistics: BTreeMap<u32, FieldBm25StatisticsDiff>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct FieldBm25StatisticsDiff {
+    pub term_statistics: BTreeMap<String, i64>,
+    pub num_search_tokens_diff: i64,
 }
 
 impl Bm25StatisticsDiff {
@@ -37,13 +48,35 @@ impl Bm25StatisticsDiff {
         for (term, freq) in other.term_statistics {
             *self.term_statistics.entry(term).or_insert(0) += freq;
         }
+        for (field_id, other_stats) in other.extra_field_statistics {
+            let stats = self.extra_field_statistics.entry(field_id).or_default();
+            stats.num_search_tokens_diff += other_stats.num_search_tokens_diff;
+            for (term, freq) in other_stats.term_statistics {
+                *stats.term_statistics.entry(term).or_insert(0) += freq;
+            }
+        }
         self
     }
+
+    /// The term statistics and number of tokens for `field`.
+    fn field_statistics(&self, field: Field) -> tantivy::Result<(&BTreeMap<String, i64>, i64)> {
+        if field.field_id() == SEARCH_FIELD_ID {
+            return Ok((&self.term_statistics, self.num_search_tokens_diff));
+        }
+        let stats = self
+            .extra_field_statistics
+            .get(&field.field_id())
+            .ok_or_else(|| {
+                TantivyError::InternalError(format!("Missing statistics for field {field:?}"))
+            })?;
+        Ok((&stats.term_statistics, stats.num_search_tokens_diff))
+    }
 }
 
 impl Bm25StatisticsProvider for Bm25StatisticsDiff {
-    fn total_num_tokens(&self, _field: Field) -> tantivy::Result<u64> {
-        u64::try_from(self.num_search_tokens_diff)
+    fn total_num_tokens(&self, field: Field) -> tantivy::Result<u64> {
+        let (_, num_search_tokens_diff) = self.field_statistics(field)?;
+        u64::try_from(num_search_tokens_diff)
             .map_err(|err| TantivyError::InternalError(err.to_string()))
     }
 
@@ -67,7 +100,8 @@ impl Bm25StatisticsProvider for Bm25StatisticsDiff {
                 term.typ()
             ))),
             Some(term_str) => {
-                let num_documents_with_term_diff_opt = self.term_statistics.get(term_str);
+                let (term_statistics, _) = self.field_statistics(term.field())?;
+                let num_documents_with_term_diff_opt = term_statistics.get(term_str);
 
                 match num_documents_with_term_diff_opt {
                     None => Err(TantivyError::InternalError(
@@ -89,6 +123,19 @@ impl From<pb::searchlight::Bm25StatisticsDiff> for Bm25StatisticsDiff {
             term_statistics: proto.term_statistics.into_iter().collect(),
             num_documents_diff: proto.num_documents_diff,
             num_search_tokens_diff: proto.num_search_tokens_diff,
+            extra_field_statistics: proto
+                .extra_field_statistics
+                .into_iter()
+                .map(|stats| {
+                    (
+                        stats.field_id,
+                        FieldBm25StatisticsDiff {
+                            term_statistics: stats.term_statistics.into_iter().collect(),
+                            num_search_tokens_diff: stats.num_search_tokens_diff,
+                        },
+                    )
+                })
+                .collect(),
         }
     }
 }
@@ -99,6 +146,17 @@ impl From<Bm25StatisticsDiff> for pb::searchlight::Bm25StatisticsDiff {
             term_statistics: stats.term_statistics.into_iter().collect(),
             num_documents_diff: stats.num_documents_diff,
             num_search_tokens_diff: stats.num_search_tokens_diff,
+            extra_field_statistics: stats
+                .extra_field_statistics
+                .into_iter()
+                .map(
+                    |(field_id, stats)| pb::searchlight::FieldBm25StatisticsDiff {
+                        field_id,
+                        term_statistics: stats.term_statistics.into_iter().collect(),
+                        num_search_tokens_diff: stats.num_search_tokens_diff,
+                    },
+                )
+                .collect(),
         }
     }
 }
@@ -104,7 +104,7 @@ impl VectorSearcher for SearcherStub {
         &self,
         _search_storage: Arc<dyn Storage>,
         _segments: Vec<FragmentedVectorSegmentPaths>,
-        _dimension: usize,
+        _schema: QdrantSchema,
     ) -> anyhow::Result<FragmentedVectorSegment> {
         anyhow::bail!("Not implemented!");
     }
@@ -227,10 +227,10 @@ impl<RT: Runtime> VectorSearcher for InProcessSearcher<RT> {
         &self,
         search_storage: Arc<dyn Storage>,
         segments: Vec<FragmentedVectorSegmentPaths>,
-        dimension: usize,
+        schema: QdrantSchema,
     ) -> anyhow::Result<FragmentedVectorSegment> {
         self.searcher
-            .execute_vector_compaction(search_storage, segments, dimension)
+            .execute_vector_compaction(search_storage, segments, schema)
             .await
     }
 }
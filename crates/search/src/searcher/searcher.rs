@@ -56,12 +56,15 @@ use tantivy::{
     query::{
         Bm25StatisticsProvider,
         EnableScoring,
+        Scorer,
     },
     schema::Field,
     termdict::TermOrdinal,
+    DocSet,
     InvertedIndexReader,
     SegmentReader,
     TantivyError,
+    TERMINATED,
 };
 use text_search::tracker::StaticDeletionTracker;
 use value::InternalId;
@@ -88,7 +91,10 @@ use super::{
     },
 };
 use crate::{
-    aggregation::TokenMatchAggregator,
+    aggregation::{
+        PostingListMatchAggregator,
+        TokenMatchAggregator,
+    },
     archive::cache::ArchiveCacheManager,
     constants::{
         MAX_EDIT_DISTANCE,
@@ -100,6 +106,14 @@ use crate::{
         OrTerm,
     },
     disk_index::index_reader_for_directory,
+    filter_values::{
+        check_filter_values,
+        creation_time_sort_key,
+        filter_values_reader,
+        RangeFilter,
+        SearchSort,
+        SortField,
+    },
     fragmented_segment::{
         FragmentedSegmentCompactor,
         FragmentedSegmentFetcher,
@@ -111,6 +125,7 @@ use crate::{
         build_fuzzy_dfa,
         LevenshteinDfaWrapper,
     },
+    phrase::PositionConstraint,
     searcher::{
         metrics::{
             text_compaction_searcher_latency_seconds,
@@ -622,11 +637,11 @@ impl<RT: Runtime> VectorSearcher for SearcherImpl<RT> {
         &self,
         search_storage: Arc<dyn Storage>,
         segments: Vec<FragmentedVectorSegmentPaths>,
-        dimension: usize,
+        schema: QdrantSchema,
     ) -> anyhow::Result<common::bootstrap_model::index::vector_index::FragmentedVectorSegment> {
         let segment = self
             .fragmented_segment_compactor
-            .compact(segments, dimension, search_storage.clone())
+            .compact(segments, schema, search_storage.clone())
             .await?;
 
         self.prefetch_segment(search_storage, segment.clone())
@@ -914,35 +929,84 @@ impl<RT: Runtime> SearcherImpl<RT> {
                     segment_alive_bitset: deletion_tracker.alive_bitset().clone(),
                 };
 
-                let search_query =
-                    ConvexSearchQuery::new(query.or_terms, query.and_terms, alive_documents);
+                // Check for the filter fields' values up front, since errors from within
+                // the query's scorer lose their metadata.
+                let segment = searcher.segment_reader(*segment_ord);
+                let sort_field = match query.sort {
+                    Some(SearchSort {
+                        field: SortField::FilterField(field),
+                        ..
+                    }) => Some(field),
+                    _ => None,
+                };
+                check_filter_values(
+                    segment,
+                    query
+                        .range_filters
+                        .iter()
+                        .map(|filter| filter.field)
+                        .chain(sort_field),
+                )?;
+
+                let search_query = ConvexSearchQuery::new(
+                    query.or_terms,
+                    query.and_terms,
+                    query.position_constraints,
+                    query.range_filters,
+                    alive_documents,
+                );
                 let enable_scoring =
                     EnableScoring::enabled_from_statistics_provider(&stats_provider, searcher);
                 let search_weight = search_query.weight(enable_scoring)?;
 
-                let collector = TopDocs::with_limit(query.max_results);
-                let segment = searcher.segment_reader(*segment_ord);
-                let segment_results = collector.collect_segment(&*search_weight, 0, segment)?;
-
                 let fast_fields = segment.fast_fields();
                 let internal_ids = fast_fields.bytes(INTERNAL_ID_FIELD_NAME)?;
                 let timestamps = fast_fields.u64(TS_FIELD_NAME)?;
                 let creation_times = fast_fields.f64(CREATION_TIME_FIELD_NAME)?;
-
-                let mut results = Vec::with_capacity(segment_results.len());
-                for (bm25_score, doc_address) in segment_results {
-                    let internal_id = internal_ids.get_bytes(doc_address.doc_id).try_into()?;
-
-                    let ts = Timestamp::try_from(timestamps.get_val(doc_address.doc_id))?;
-                    let creation_time =
-                        CreationTime::try_from(creation_times.get_val(doc_address.doc_id))?;
-                    let posting_list_match = PostingListMatch {
+                let posting_list_match = |doc_id, bm25_score| {
+                    let internal_id = internal_ids.get_bytes(doc_id).try_into()?;
+                    let ts = Timestamp::try_from(timestamps.get_val(doc_id))?;
+                    let creation_time = CreationTime::try_from(creation_times.get_val(doc_id))?;
+                    anyhow::Ok(PostingListMatch {
                         internal_id,
                         ts: WriteTimestamp::Committed(ts),
                         creation_time,
                         bm25_score,
+                        sort_key: None,
+                    })
+                };
+
+                // If the query has a sort, the top results depend on the sort field's value
+                // rather than the score, so visit every match.
+                if let Some(sort) = query.sort {
+                    let sort_values = match sort.field {
+                        SortField::CreationTime => None,
+                        SortField::FilterField(field) => {
+                            Some(filter_values_reader(segment, field)?)
+                        },
                     };
-                    results.push(posting_list_match);
+                    let mut aggregator = PostingListMatchAggregator::new(query.max_results);
+                    let mut scorer = search_weight.scorer(segment, 1.0)?;
+                    let mut doc_id = scorer.doc();
+                    while doc_id != TERMINATED {
+                        let mut m = posting_list_match(doc_id, scorer.score())?;
+                        let sort_key = match sort_values {
+                            Some(ref values) => sort.match_key(values.get_bytes(doc_id)),
+                            None => sort.match_key(&creation_time_sort_key(m.creation_time)),
+                        };
+                        m.sort_key = Some(sort_key);
+                        aggregator.insert(m);
+                        doc_id = scorer.advance();
+                    }
+                    return Ok(aggregator.into_results().collect());
+                }
+
+                let collector = TopDocs::with_limit(query.max_results);
+                let segment_results = collector.collect_segment(&*search_weight, 0, segment)?;
+
+                let mut results = Vec::with_capacity(segment_results.len());
+                for (bm25_score, doc_address) in segment_results {
+                    results.push(posting_list_match(doc_address.doc_id, bm25_score)?);
                 }
 
                 anyhow::ensure!(results.len() <= query.max_results);
@@ -1255,6 +1319,12 @@ pub struct PostingListQuery {
 
     pub or_terms: Vec<OrTerm>,
     pub and_terms: Vec<Term>,
+    pub position_constraints: Vec<PositionConstraint<Term>>,
+    pub range_filters: Vec<RangeFilter>,
+
+    /// If set, return the top `max_results` matches by the sort field rather
+    /// than by BM25 score.
+    pub sort: Option<SearchSort>,
 
     pub max_results: usize,
 }
@@ -1270,6 +1340,9 @@ impl TryFrom<PostingListQueryProto> for PostingListQuery {
             or_terms,
             and_terms,
             max_results,
+            position_constraints,
+            range_filters,
+            sort,
         }: PostingListQueryProto,
     ) -> Result<Self, Self::Error> {
         let num_terms_by_field = num_terms_by_field
@@ -1287,12 +1360,23 @@ impl TryFrom<PostingListQueryProto> for PostingListQuery {
             .collect::<anyhow::Result<_>>()?;
         let or_terms = or_terms.into_iter().map(|t| t.try_into()).try_collect()?;
         let and_terms = and_terms.into_iter().map(Term::wrap).collect();
+        let position_constraints = position_constraints
+            .into_iter()
+            .map(PositionConstraint::try_from)
+            .try_collect()?;
+        let range_filters = range_filters
+            .into_iter()
+            .map(RangeFilter::try_from)
+            .try_collect()?;
         Ok(PostingListQuery {
             deleted_internal_ids,
             num_terms_by_field,
             num_documents: num_documents.context("Missing num_documents")?,
             or_terms,
             and_terms,
+            position_constraints,
+            range_filters,
+            sort: sort.map(SearchSort::try_from).transpose()?,
             max_results: max_results.context("Missing max_results")? as usize,
         })
     }
@@ -1308,6 +1392,9 @@ impl TryFrom<PostingListQuery> for PostingListQueryProto {
             num_documents,
             or_terms,
             and_terms,
+            position_constraints,
+            range_filters,
+            sort,
             max_results,
         }: PostingListQuery,
     ) -> Result<Self, Self::Error> {
@@ -1327,6 +1414,14 @@ impl TryFrom<PostingListQuery> for PostingListQueryProto {
             .into_iter()
             .map(|t| t.as_slice().to_vec())
             .collect();
+        let position_constraints = position_constraints
+            .into_iter()
+            .map(pb::searchlight::PositionConstraint::from)
+            .collect();
+        let range_filters = range_filters
+            .into_iter()
+            .map(pb::searchlight::RangeFilter::from)
+            .collect();
         Ok(PostingListQueryProto {
             deleted_internal_ids,
             num_terms_by_field,
@@ -1334,6 +1429,9 @@ impl TryFrom<PostingListQuery> for PostingListQueryProto {
             or_terms,
             and_terms,
             max_results: Some(max_results as u32),
+            position_constraints,
+            range_filters,
+            sort: sort.map(pb::searchlight::SearchSort::from),
         })
     }
 }
@@ -1344,12 +1442,16 @@ pub struct PostingListMatch {
     pub ts: WriteTimestamp,
     pub creation_time: CreationTime,
     pub bm25_score: f32,
+    /// `SearchSort::match_key` of the match's sort field, if the query has a
+    /// sort.
+    pub sort_key: Option<Vec<u8>>,
 }
 
 impl Ord for PostingListMatch {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.bm25_score
-            .total_cmp(&other.bm25_score)
+        self.sort_key
+            .cmp(&other.sort_key)
+            .then(self.bm25_score.total_cmp(&other.bm25_score))
             .then(self.creation_time.cmp(&other.creation_time))
             .then(self.internal_id.cmp(&other.internal_id))
             .then(self.ts.cmp(&other.ts))
@@ -1392,6 +1494,7 @@ impl TryFrom<pb::searchlight::PostingListMatch> for PostingListMatch {
                 .context("Missing creation_time")?
                 .try_into()?,
             bm25_score: value.bm25_score.context("Missing bm25_score")?,
+            sort_key: value.sort_key,
         })
     }
 }
@@ -1412,6 +1515,7 @@ impl TryFrom<PostingListMatch> for pb::searchlight::PostingListMatch {
             },
             creation_time: Some(value.creation_time.into()),
             bm25_score: Some(value.bm25_score),
+            sort_key: value.sort_key,
         })
     }
 }
@@ -1429,6 +1533,7 @@ mod tests {
             BufRead,
             BufReader,
         },
+        ops::Bound,
         path::{
             Path,
             PathBuf,
@@ -1450,9 +1555,11 @@ mod tests {
             DocumentRevision,
             RevisionPair,
         },
+        query::Order,
         testing::TestIdGenerator,
         types::Timestamp,
     };
+    use errors::ErrorMetadataAnyhowExt;
     use futures::StreamExt;
     use runtime::testing::TestRuntime;
     use storage::{
@@ -1481,6 +1588,11 @@ mod tests {
     use crate::{
         convex_query::OrTerm,
         disk_index::index_reader_for_directory,
+        filter_values::{
+            RangeFilter,
+            SearchSort,
+            SortField,
+        },
         incremental_index::{
             build_new_segment,
             merge_segments,
@@ -1499,6 +1611,7 @@ mod tests {
             InProcessSearcher,
             SearcherImpl,
         },
+        FilterRange,
         TantivySearchIndexSchema,
         TextSegmentPaths,
         EXACT_SEARCH_MAX_WORD_LENGTH,
@@ -1526,7 +1639,9 @@ mod tests {
         let field_path: FieldPath = "mySearchField".parse()?;
         let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: field_path.clone(),
+            extra_search_fields: BTreeMap::new(),
             filter_fields: BTreeSet::new(),
+            analyzer: Default::default(),
         });
 
         #[derive(serde::Deserialize)]
@@ -1675,6 +1790,9 @@ mod tests {
             deleted_internal_ids: BTreeSet::new(),
             or_terms,
             and_terms: vec![],
+            position_constraints: vec![],
+            range_filters: vec![],
+            sort: None,
             num_terms_by_field: stats.num_terms_by_field,
             num_documents: stats.num_documents,
             max_results,
@@ -1702,7 +1820,9 @@ mod tests {
         let field_path: FieldPath = "mySearchField".parse().unwrap();
         TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: field_path.clone(),
+            extra_search_fields: BTreeMap::new(),
             filter_fields: BTreeSet::new(),
+            analyzer: Default::default(),
         })
     }
 
@@ -1924,6 +2044,9 @@ mod tests {
             deleted_internal_ids: BTreeSet::new(),
             or_terms,
             and_terms: vec![],
+            position_constraints: vec![],
+            range_filters: vec![],
+            sort: None,
             num_terms_by_field: stats.num_terms_by_field,
             num_documents: stats.num_documents,
             max_results,
@@ -1988,6 +2111,87 @@ mod tests {
         Ok(())
     }
 
+    #[convex_macro::test_runtime]
+    async fn test_segment_without_filter_values(rt: TestRuntime) -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let revisions = vec![(
+            id_generator.generate_internal(),
+            None,
+            Some("emma works at convex"),
+        )];
+        let test_dir = TempDir::new()?;
+        let test_index = build_test_index(rt, revisions.into(), test_dir.path()).await?;
+        let segment_paths = test_index.segment_paths.unwrap();
+        let index_reader = index_reader_for_directory(&segment_paths.index_path).await?;
+        let text_segment = Arc::new(TextSegment::Segment {
+            searcher: index_reader.searcher(),
+            deletion_tracker: StaticDeletionTracker::load(
+                load_alive_bitset(&segment_paths.alive_bit_set_path)?,
+                &segment_paths.deleted_terms_path,
+            )?,
+            id_tracker: StaticIdTracker::load_from_path(segment_paths.id_tracker_path)?,
+            segment_ord: 0,
+        });
+
+        // The segment was written with a schema without filter fields, so it's
+        // missing the values of the filter field in the current schema.
+        let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
+            search_field: "mySearchField".parse()?,
+            extra_search_fields: BTreeMap::new(),
+            filter_fields: BTreeSet::from(["channel".parse()?]),
+            analyzer: Default::default(),
+        });
+        let filter_value_field = schema.filter_value_fields[&"channel".parse()?];
+        let term = Term::from_field_text(schema.search_field, "emma");
+        let stats = SearcherImpl::<TestRuntime>::query_bm25_stats_impl(
+            text_segment.clone(),
+            vec![term.clone()],
+        )?;
+        let query = |range_filters, sort| PostingListQuery {
+            deleted_internal_ids: BTreeSet::new(),
+            or_terms: vec![OrTerm {
+                term: term.clone(),
+                doc_frequency: stats.doc_frequencies[&term],
+                bm25_boost: 1.,
+            }],
+            and_terms: vec![],
+            position_constraints: vec![],
+            range_filters,
+            sort,
+            num_terms_by_field: stats.num_terms_by_field.clone(),
+            num_documents: stats.num_documents,
+            max_results: 10,
+        };
+
+        let results = SearcherImpl::<TestRuntime>::query_posting_lists_impl(
+            text_segment.clone(),
+            query(vec![], None),
+        )?;
+        assert_eq!(results.len(), 1);
+
+        let range_filter = RangeFilter {
+            field: filter_value_field,
+            range: FilterRange {
+                start: Bound::Unbounded,
+                end: Bound::Unbounded,
+            },
+        };
+        let sort = SearchSort {
+            field: SortField::FilterField(filter_value_field),
+            order: Order::Asc,
+        };
+        for filtered_query in [query(vec![range_filter], None), query(vec![], Some(sort))] {
+            let err = SearcherImpl::<TestRuntime>::query_posting_lists_impl(
+                text_segment.clone(),
+                filtered_query,
+            )
+            .unwrap_err();
+            assert!(err.is_bad_request());
+            assert_eq!(err.short_msg(), "TextIndexMissingFilterValues");
+        }
+        Ok(())
+    }
+
     #[convex_macro::test_runtime]
     async fn test_merge_tantivy_segments(rt: TestRuntime) -> anyhow::Result<()> {
         let query = "emma";
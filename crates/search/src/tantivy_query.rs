@@ -33,6 +33,7 @@ impl SearchQueryResult {
                 term_statistics: BTreeMap::new(),
                 num_documents_diff: 0,
                 num_search_tokens_diff: 0,
+                extra_field_statistics: BTreeMap::new(),
             },
             combined_shortlisted_terms: TermShortlist::new(BTreeMap::new()),
         }
@@ -44,6 +44,10 @@ use indexing::index_registry::{
 use storage::Storage;
 
 use crate::{
+    filter_values::{
+        SearchSort,
+        SortField,
+    },
     memory_index::MemoryTextIndex,
     metrics,
     query::{
@@ -172,8 +176,9 @@ impl TextIndexManager {
                     // If the search index was written to disk with a different format from
                     // how the current backend constructs search queries, assume the new
                     // search index is backfilling.
-                    snapshot_info.disk_index_version
-                        == TextSnapshotVersion::new(self.persistence_version),
+                    snapshot_info
+                        .disk_index_version
+                        .is_queryable_as(TextSnapshotVersion::new(self.persistence_version)),
                     index_backfilling_error(printable_index_name)
                 );
                 Ok(snapshot_info)
@@ -263,9 +268,23 @@ impl TextIndexManager {
         let SnapshotInfo {
             disk_index,
             disk_index_ts,
+            disk_index_version,
             memory_index,
-            ..
         } = self.get_snapshot_info(index, printable_index_name)?;
+        // Range filters and sorting on a filter field read its values, which
+        // older snapshots don't have until they're rebuilt.
+        let reads_filter_values = !compiled_query.range_filters.is_empty()
+            || matches!(
+                compiled_query.sort,
+                Some(SearchSort {
+                    field: SortField::FilterField(_),
+                    ..
+                })
+            );
+        anyhow::ensure!(
+            !reads_filter_values || disk_index_version.has_filter_values(),
+            index_backfilling_error(printable_index_name)
+        );
         tantivy_schema
             .search(
                 compiled_query,
@@ -503,15 +522,15 @@ impl TextIndexManager {
             };
             let old_value = deletion
                 .as_ref()
-                .map(|d| anyhow::Ok((tantivy_schema.index_into_terms(d)?, d.creation_time())))
+                .map(|d| tantivy_schema.index_into_memory_document(d))
                 .transpose()?;
-            let new_terms = insertion
+            let new_value = insertion
                 .as_ref()
-                .map(|d| anyhow::Ok((tantivy_schema.index_into_terms(d)?, d.creation_time())))
+                .map(|d| tantivy_schema.index_into_memory_document(d))
                 .transpose()?;
             index
                 .memory_index_mut()
-                .update(id.internal_id(), ts, old_value, new_terms)?;
+                .update(id.internal_id(), ts, old_value, new_value)?;
         }
 
         timer.finish();
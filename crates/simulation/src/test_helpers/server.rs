@@ -5,6 +5,7 @@ use std::{
 
 use application::{
     api::ApplicationApi,
+    rate_limiter::RateLimitCaller,
     RedactedMutationError,
     RedactedMutationReturn,
 };
@@ -211,6 +212,7 @@ impl ServerThread {
                                 udf_path.canonicalize().into(),
                                 vec![args.into()],
                                 FunctionCaller::Test,
+                                &RateLimitCaller::default(),
                                 None,
                                 None,
                             ).await?;
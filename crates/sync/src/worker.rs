@@ -18,6 +18,7 @@ use application::{
         SubscriptionClient,
         SubscriptionTrait,
     },
+    rate_limiter::RateLimitCaller,
     redaction::{
         RedactedJsError,
         RedactedLogLines,
@@ -72,6 +73,7 @@ use maplit::btreemap;
 use model::session_requests::types::SessionRequestIdentifier;
 use sync_types::{
     ClientMessage,
+    ErrorPayload,
     IdentityVersion,
     QueryId,
     QuerySetModification,
@@ -111,16 +113,28 @@ const SYNC_WORKER_PROCESS_TIMEOUT: Duration = Duration::from_secs(60);
 #[derive(Clone, Debug)]
 pub struct SyncWorkerConfig {
     pub client_version: ClientVersion,
+    /// The WebSocket's IP address and upgrade request headers, which rate
+    /// limits apply to for every call on the connection.
+    pub rate_limit_caller: Arc<RateLimitCaller>,
 }
 
 impl Default for SyncWorkerConfig {
     fn default() -> Self {
         Self {
             client_version: ClientVersion::unknown(),
+            rate_limit_caller: Arc::new(RateLimitCaller::default()),
         }
     }
 }
 
+/// Rate limited calls get an error response instead of closing the WebSocket.
+fn rate_limited_error_payload(e: anyhow::Error) -> anyhow::Result<ErrorPayload<JsonPackedValue>> {
+    if !e.is_rate_limited() {
+        return Err(e);
+    }
+    Ok(ErrorPayload::Message(e.user_facing_message()))
+}
+
 /// Creates a channel which allows the sender to track the buffer size and
 /// opt-in to slow down if the buffer becomes too large.
 pub fn measurable_unbounded_channel() -> (SingleFlightSender, SingleFlightReceiver) {
@@ -463,6 +477,8 @@ impl<RT: Runtime> SyncWorker<RT> {
                 new_version,
                 modifications,
             } => {
+                // Subscribing to queries isn't rate limited, unlike mutations and
+                // actions (see `application::rate_limiter`).
                 self.state
                     .modify_query_set(base_version, new_version, modifications)?;
                 self.schedule_update();
@@ -500,6 +516,7 @@ impl<RT: Runtime> SyncWorker<RT> {
                 let api = self.api.clone();
                 let host = self.host.clone();
                 let caller = FunctionCaller::SyncWorker(client_version);
+                let rate_limit_caller = self.config.rate_limit_caller.clone();
 
                 let mutation_queue_size =
                     self.mutation_sender.max_capacity() - self.mutation_sender.capacity();
@@ -510,13 +527,33 @@ impl<RT: Runtime> SyncWorker<RT> {
                         timer.finish();
                         let result = match component_path {
                             None => {
+                                let path = ExportPath::from(udf_path.canonicalize());
+                                if let Err(e) = api
+                                    .check_rate_limits(
+                                        &host,
+                                        server_request_id.clone(),
+                                        &identity,
+                                        &path,
+                                        Some(UdfType::Mutation),
+                                        &rate_limit_caller,
+                                    )
+                                    .await
+                                {
+                                    return Ok(ServerMessage::MutationResponse {
+                                        request_id,
+                                        result: Err(rate_limited_error_payload(e)?),
+                                        ts: None,
+                                        log_lines: RedactedLogLines::empty().into(),
+                                    });
+                                }
                                 api.execute_public_mutation(
                                     &host,
                                     server_request_id,
                                     identity,
-                                    ExportPath::from(udf_path.canonicalize()),
+                                    path,
                                     args,
                                     caller,
+                                    &rate_limit_caller,
                                     mutation_identifier,
                                     Some(mutation_queue_size),
                                 )
@@ -533,6 +570,7 @@ impl<RT: Runtime> SyncWorker<RT> {
                                     path,
                                     args,
                                     caller,
+                                    &rate_limit_caller,
                                     mutation_identifier,
                                     Some(mutation_queue_size),
                                 )
@@ -600,17 +638,37 @@ impl<RT: Runtime> SyncWorker<RT> {
                        "udf_path".into() => udf_path.clone().into(),
                     },
                 );
+                let rate_limit_caller = self.config.rate_limit_caller.clone();
                 let future = async move {
                     let caller = FunctionCaller::SyncWorker(client_version);
                     let result = match component_path {
                         None => {
+                            let path = ExportPath::from(udf_path.canonicalize());
+                            if let Err(e) = api
+                                .check_rate_limits(
+                                    &host,
+                                    server_request_id.clone(),
+                                    &identity,
+                                    &path,
+                                    Some(UdfType::Action),
+                                    &rate_limit_caller,
+                                )
+                                .await
+                            {
+                                return Ok(ServerMessage::ActionResponse {
+                                    request_id,
+                                    result: Err(rate_limited_error_payload(e)?),
+                                    log_lines: RedactedLogLines::empty().into(),
+                                });
+                            }
                             api.execute_public_action(
                                 &host,
                                 server_request_id,
                                 identity,
-                                ExportPath::from(udf_path.canonicalize()),
+                                path,
                                 args,
                                 caller,
+                                &rate_limit_caller,
                             )
                             .in_span(root)
                             .await?
@@ -624,6 +682,7 @@ impl<RT: Runtime> SyncWorker<RT> {
                                 path,
                                 args,
                                 caller,
+                                &rate_limit_caller,
                             )
                             .in_span(root)
                             .await?
@@ -27,7 +27,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 
     let ts = Timestamp::must(1);
 
-    let mut index = MemoryVectorIndex::new(WriteTimestamp::Committed(ts));
+    let mut index = MemoryVectorIndex::new(WriteTimestamp::Committed(ts), Default::default());
     let mut next_id = 1u128;
 
     for _ in 0..n {
@@ -41,6 +41,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 .try_into()
                 .unwrap(),
             filter_fields: BTreeMap::new(),
+            numeric_filter_fields: BTreeMap::new(),
         };
         index
             .update(id, WriteTimestamp::Committed(ts), None, Some(document))
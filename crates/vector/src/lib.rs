@@ -26,6 +26,7 @@ pub mod metrics;
 mod qdrant_index;
 pub mod qdrant_segments;
 mod query;
+mod query_reads;
 mod searcher;
 mod vector_index_manager;
 
@@ -50,11 +51,14 @@ pub use self::{
         InternalVectorSearch,
         PublicVectorSearchQueryResult,
         VectorSearch,
+        VectorSearchBound,
         VectorSearchExpression,
         VectorSearchJson,
         VectorSearchQueryResult,
+        VectorSearchRange,
         VectorSearchRequest,
     },
+    query_reads::VectorQueryReads,
     searcher::VectorSearcher,
     vector_index_manager::{
         IndexState,
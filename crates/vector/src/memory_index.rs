@@ -3,28 +3,29 @@ use std::{
     mem,
 };
 
-use common::types::{
-    Timestamp,
-    WriteTimestamp,
+use common::{
+    bootstrap_model::index::vector_index::VectorDistanceMetric,
+    types::{
+        Timestamp,
+        WriteTimestamp,
+    },
 };
 use imbl::{
     OrdMap,
     OrdSet,
     Vector,
 };
-use qdrant_segment::spaces::{
-    metric::Metric,
-    simple::CosineMetric,
-};
 use value::InternalId;
 
 use crate::{
     qdrant_index::{
+        preprocess_vector,
+        similarity,
         NormalizedQdrantDocument,
         QdrantDocument,
     },
     query::{
-        CompiledVectorFilter,
+        matches_filter_conditions,
         CompiledVectorSearch,
         VectorSearchQueryResult,
     },
@@ -32,6 +33,7 @@ use crate::{
 
 #[derive(Clone)]
 pub struct MemoryVectorIndex {
+    metric: VectorDistanceMetric,
     min_ts: WriteTimestamp,
     max_ts: WriteTimestamp,
 
@@ -45,8 +47,9 @@ pub struct MemoryVectorIndex {
 }
 
 impl MemoryVectorIndex {
-    pub fn new(base_ts: WriteTimestamp) -> Self {
+    pub fn new(base_ts: WriteTimestamp, metric: VectorDistanceMetric) -> Self {
         Self {
+            metric,
             min_ts: base_ts,
             max_ts: base_ts,
 
@@ -110,7 +113,7 @@ impl MemoryVectorIndex {
             }
         }
         if let Some(old_value) = old_value {
-            let normalized = NormalizedQdrantDocument::from(old_value);
+            let normalized = NormalizedQdrantDocument::new(old_value, self.metric);
             self.tombstones_size += normalized.size();
             self.tombstones.push_back((ts, normalized));
         }
@@ -119,7 +122,7 @@ impl MemoryVectorIndex {
             self.documents_size -= old_value.document.size();
         }
         if let Some(new_value) = new_value {
-            let normalized = NormalizedQdrantDocument::from(new_value);
+            let normalized = NormalizedQdrantDocument::new(new_value, self.metric);
             self.documents_size += normalized.size();
             let revision = Revision {
                 ts,
@@ -203,14 +206,14 @@ impl MemoryVectorIndex {
             self.min_ts,
         );
         let query_vector = Vec::from(query.vector.clone());
-        let query_vector = CosineMetric::preprocess(query_vector);
+        let query_vector = preprocess_vector(self.metric, query_vector);
         let mut candidates = vec![];
 
         for (&id, revision) in &self.documents {
             if revision.document.matches(query) {
-                let distance = CosineMetric::similarity(&query_vector, &revision.document.vector);
+                let score = similarity(self.metric, &query_vector, &revision.document.vector);
                 candidates.push(VectorSearchQueryResult {
-                    score: distance,
+                    score,
                     id,
                     ts: revision.ts,
                 });
@@ -232,21 +235,190 @@ pub struct Revision {
 
 impl NormalizedQdrantDocument {
     fn matches(&self, query: &CompiledVectorSearch) -> bool {
-        if query.filter_conditions.is_empty() {
-            return true;
-        }
-        for (field_path, filter_condition) in &query.filter_conditions {
-            let Some(value) = self.filter_fields.get(field_path) else {
-                return false;
+        matches_filter_conditions(
+            &self.filter_fields,
+            &self.numeric_filter_fields,
+            &query.filter_conditions,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{
+        BTreeMap,
+        BTreeSet,
+    };
+
+    use common::{
+        bootstrap_model::index::vector_index::VectorDistanceMetric,
+        query::search_value_to_bytes,
+        types::{
+            Timestamp,
+            WriteTimestamp,
+        },
+    };
+    use maplit::btreemap;
+    use value::{
+        ConvexValue,
+        FieldPath,
+        InternalId,
+    };
+
+    use crate::{
+        memory_index::MemoryVectorIndex,
+        query::{
+            CompiledVectorFilter,
+            CompiledVectorRange,
+        },
+        CompiledVectorSearch,
+        QdrantDocument,
+    };
+
+    fn query_scores(
+        metric: VectorDistanceMetric,
+        query: Vec<f32>,
+    ) -> anyhow::Result<Vec<(u128, f32)>> {
+        let ts = Timestamp::must(1);
+        let mut index = MemoryVectorIndex::new(WriteTimestamp::Committed(ts), metric);
+        // A short vector pointing the same way as the query and a long one at a
+        // slight angle to it.
+        let vectors = [(1u128, vec![1., 0.]), (2u128, vec![10., 1.])];
+        for (id, vector) in vectors {
+            let internal_id = InternalId(id.to_le_bytes());
+            let document = QdrantDocument {
+                internal_id,
+                vector: vector.try_into()?,
+                filter_fields: BTreeMap::new(),
+                numeric_filter_fields: BTreeMap::new(),
             };
-            let condition_result = match filter_condition {
-                CompiledVectorFilter::Eq(ref term) => term == value,
-                CompiledVectorFilter::In(ref terms) => terms.iter().any(|t| t == value),
+            index.update(
+                internal_id,
+                WriteTimestamp::Committed(ts),
+                None,
+                Some(document),
+            )?;
+        }
+        let query = CompiledVectorSearch {
+            vector: query.try_into()?,
+            limit: 10,
+            filter_conditions: BTreeMap::new(),
+        };
+        Ok(index
+            .query(ts, &query)?
+            .into_iter()
+            .map(|result| (u128::from_le_bytes(result.id.0), result.score))
+            .collect())
+    }
+
+    fn assert_scores(actual: Vec<(u128, f32)>, expected: Vec<(u128, f32)>) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for ((id, score), (expected_id, expected_score)) in actual.into_iter().zip(expected) {
+            assert_eq!(id, expected_id);
+            assert!(
+                (score - expected_score).abs() < 1e-4,
+                "{score} != {expected_score}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_scores_by_metric() -> anyhow::Result<()> {
+        let query = vec![2., 0.];
+        assert_scores(
+            query_scores(VectorDistanceMetric::Cosine, query.clone())?,
+            vec![(1, 1.), (2, 10. / 101f32.sqrt())],
+        );
+        assert_scores(
+            query_scores(VectorDistanceMetric::Dot, query.clone())?,
+            vec![(2, 20.), (1, 2.)],
+        );
+        assert_scores(
+            query_scores(VectorDistanceMetric::Euclidean, query)?,
+            vec![(1, -1.), (2, -65f32.sqrt())],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_and_negation_filters() -> anyhow::Result<()> {
+        let ts = Timestamp::must(1);
+        let mut index =
+            MemoryVectorIndex::new(WriteTimestamp::Committed(ts), VectorDistanceMetric::Cosine);
+        let year: FieldPath = "year".parse()?;
+        let artist: FieldPath = "artist".parse()?;
+        for (id, year_value, artist_value) in
+            [(1u128, 1940., "a"), (2, 1960., "b"), (3, 1980., "c")]
+        {
+            let internal_id = InternalId(id.to_le_bytes());
+            let artist_value = ConvexValue::try_from(artist_value.to_string())?;
+            let document = QdrantDocument {
+                internal_id,
+                vector: vec![1., 0.].try_into()?,
+                filter_fields: btreemap! {
+                    year.clone() => search_value_to_bytes(Some(&ConvexValue::from(year_value))),
+                    artist.clone() => search_value_to_bytes(Some(&artist_value)),
+                },
+                numeric_filter_fields: btreemap! { year.clone() => year_value },
             };
-            if condition_result {
-                return true;
-            }
+            index.update(
+                internal_id,
+                WriteTimestamp::Committed(ts),
+                None,
+                Some(document),
+            )?;
         }
-        false
+        let query_ids = |filter_conditions| -> anyhow::Result<BTreeSet<u128>> {
+            let query = CompiledVectorSearch {
+                vector: vec![1., 0.].try_into()?,
+                limit: 10,
+                filter_conditions,
+            };
+            Ok(index
+                .query(ts, &query)?
+                .into_iter()
+                .map(|result| u128::from_le_bytes(result.id.0))
+                .collect())
+        };
+        let artist_bytes = |a: &str| {
+            anyhow::Ok(search_value_to_bytes(Some(&ConvexValue::try_from(
+                a.to_string(),
+            )?)))
+        };
+        let since_1950 = CompiledVectorFilter::Range(CompiledVectorRange {
+            gte: Some(1950.),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            query_ids(btreemap! { year.clone() => since_1950.clone() })?,
+            BTreeSet::from([2, 3])
+        );
+        assert_eq!(
+            query_ids(btreemap! {
+                year.clone() => since_1950.clone(),
+                artist.clone() => CompiledVectorFilter::NotIn(vec![artist_bytes("c")?]),
+            })?,
+            BTreeSet::from([2])
+        );
+        // Narrowing conditions apply on top of the equality conditions.
+        assert_eq!(
+            query_ids(btreemap! {
+                year.clone() => since_1950,
+                artist.clone() => CompiledVectorFilter::Eq(artist_bytes("a")?),
+            })?,
+            BTreeSet::new()
+        );
+        assert_eq!(
+            query_ids(btreemap! {
+                year.clone() => CompiledVectorFilter::Range(CompiledVectorRange {
+                    lt: Some(1970.),
+                    ..Default::default()
+                }),
+                artist.clone() => CompiledVectorFilter::In(vec![artist_bytes("a")?, artist_bytes("b")?]),
+            })?,
+            BTreeSet::from([1, 2])
+        );
+        Ok(())
     }
 }
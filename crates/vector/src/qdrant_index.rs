@@ -23,9 +23,21 @@ use std::{
 
 use atomic_refcell::AtomicRefCell;
 use common::{
-    bootstrap_model::index::vector_index::DeveloperVectorIndexConfig,
-    document::ResolvedDocument,
-    knobs::VECTOR_INDEX_THREADS,
+    bootstrap_model::index::vector_index::{
+        DeveloperVectorIndexConfig,
+        VectorDimensions,
+        VectorDistanceMetric,
+        VectorHnswConfig,
+        VectorQuantization,
+    },
+    document::{
+        PackedDocument,
+        ResolvedDocument,
+    },
+    knobs::{
+        VECTOR_INDEX_THREADS,
+        VECTOR_QUANTIZATION_OVERSAMPLING,
+    },
     persistence::DocumentStream,
     query::search_value_to_bytes,
     types::{
@@ -53,7 +65,11 @@ use qdrant_segment::{
     segment::Segment,
     spaces::{
         metric::Metric,
-        simple::CosineMetric,
+        simple::{
+            CosineMetric,
+            DotProductMetric,
+            EuclidMetric,
+        },
     },
     types::{
         AnyVariants,
@@ -69,6 +85,8 @@ use qdrant_segment::{
         PayloadSelector,
         PayloadSelectorInclude,
         PointIdType,
+        QuantizationSearchParams,
+        Range,
         SearchParams,
         ValueVariants,
         WithPayload,
@@ -99,11 +117,14 @@ use crate::{
         create_mutable_segment,
         segment_config,
         snapshot_segment,
+        SegmentIndexOptions,
         VectorDiskSegmentValues,
         DEFAULT_VECTOR_NAME,
     },
     query::{
+        filter_value_number,
         CompiledVectorFilter,
+        CompiledVectorRange,
         CompiledVectorSearch,
         InternalVectorSearch,
         VectorSearchExpression,
@@ -117,12 +138,20 @@ use crate::{
 };
 
 const TIMESTAMP_FIELD: &str = "_ts";
+/// Filter fields with numeric values are also stored as numbers under this
+/// field, so range filters can use qdrant's numeric payload indexes. Segments
+/// built before this existed don't have it, so indexes with older snapshots
+/// are rebuilt and reject range filters until then.
+const NUMERIC_FIELD: &str = "_numeric";
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QdrantSchema {
     dimension: usize,
     vector_field: FieldPath,
     filter_fields: BTreeSet<FieldPath>,
+    metric: VectorDistanceMetric,
+    quantization: Option<VectorQuantization>,
+    hnsw: VectorHnswConfig,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -137,12 +166,63 @@ impl QdrantSchema {
             dimension: u32::from(index_config.dimensions) as usize,
             vector_field: index_config.vector_field.clone(),
             filter_fields: index_config.filter_fields.clone(),
+            metric: index_config.metric,
+            quantization: index_config.quantization,
+            hnsw: index_config.hnsw,
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn metric(&self) -> VectorDistanceMetric {
+        self.metric
+    }
+
+    pub fn segment_options(&self) -> SegmentIndexOptions {
+        SegmentIndexOptions {
+            metric: self.metric,
+            quantization: self.quantization,
+            hnsw: self.hnsw,
         }
     }
 
     pub fn index(&self, document: &ResolvedDocument) -> Option<QdrantDocument> {
         let object = document.value();
-        let Some(ConvexValue::Array(ref array)) = object.get_path(&self.vector_field) else {
+        self.qdrant_document(
+            document.internal_id(),
+            object.get_path(&self.vector_field)?,
+            |field_path| {
+                let value = object.get_path(field_path);
+                (search_value_to_bytes(value), filter_value_number(value))
+            },
+        )
+    }
+
+    /// Same behavior as `index` but doesn't fully unpack the document.
+    pub fn index_packed(&self, document: &PackedDocument) -> Option<QdrantDocument> {
+        let value = document.value();
+        self.qdrant_document(
+            document.id().internal_id(),
+            &value.get_path(&self.vector_field)?,
+            |field_path| {
+                let value = value.get_path(field_path);
+                (
+                    search_value_to_bytes(value.as_ref()),
+                    filter_value_number(value.as_ref()),
+                )
+            },
+        )
+    }
+
+    fn qdrant_document(
+        &self,
+        internal_id: InternalId,
+        vector_value: &ConvexValue,
+        filter_value: impl Fn(&FieldPath) -> (Vec<u8>, Option<f64>),
+    ) -> Option<QdrantDocument> {
+        let ConvexValue::Array(ref array) = vector_value else {
             return None;
         };
         let mut vector = Vec::with_capacity(self.dimension);
@@ -161,20 +241,36 @@ impl QdrantSchema {
             vector.push(*f as f32);
         }
         let vector = IndexedVector::try_from(vector).ok()?;
+        let mut filter_fields = BTreeMap::new();
+        let mut numeric_filter_fields = BTreeMap::new();
+        for field_path in &self.filter_fields {
+            let (value_bytes, number) = filter_value(field_path);
+            if let Some(number) = number {
+                numeric_filter_fields.insert(field_path.clone(), number);
+            }
+            filter_fields.insert(field_path.clone(), value_bytes);
+        }
         let document = QdrantDocument {
-            internal_id: document.internal_id(),
+            internal_id,
             vector,
-            filter_fields: self
-                .filter_fields
-                .iter()
-                .map(|f| (f.clone(), search_value_to_bytes(object.get_path(f))))
-                .collect(),
+            filter_fields,
+            numeric_filter_fields,
         };
         Some(document)
     }
 
+    /// Includes the quantized copy of the vector, if the index has one.
     pub fn estimate_vector_size(&self) -> usize {
-        self.dimension * VECTOR_ELEMENT_SIZE
+        let quantized = match (
+            self.quantization,
+            VectorDimensions::try_from(self.dimension as u32),
+        ) {
+            (Some(quantization), Ok(dimensions)) => {
+                quantization.bytes_per_vector(dimensions) as usize
+            },
+            _ => 0,
+        };
+        self.dimension * VECTOR_ELEMENT_SIZE + quantized
     }
 
     pub fn compile(&self, query: InternalVectorSearch) -> anyhow::Result<CompiledVectorSearch> {
@@ -194,44 +290,47 @@ impl QdrantSchema {
             )
         );
         let mut filter_conditions = BTreeMap::new();
-        // Each equality expression contributes to this, so an `In` with N elements
+        // Each (in)equality expression contributes to this, so an `In` with N elements
         // increments this by N
         let mut filter_length = 0;
 
         for expresion in query.expressions {
-            match expresion {
-                VectorSearchExpression::Eq(field_path, value) => {
-                    if !self.filter_fields.contains(&field_path) {
-                        anyhow::bail!(incorrect_vector_filter_field_error(
-                            &index_name,
-                            &field_path
-                        ))
-                    }
-                    let value_bytes = search_value_to_bytes(value.as_ref());
-                    if filter_conditions.contains_key(&field_path) {
-                        anyhow::bail!("Found multiple filters for the same field?")
-                    }
-                    filter_conditions.insert(field_path, CompiledVectorFilter::Eq(value_bytes));
-                    filter_length += 1;
-                },
+            let field_path = expresion.field_path();
+            if !self.filter_fields.contains(field_path) {
+                anyhow::bail!(incorrect_vector_filter_field_error(&index_name, field_path))
+            }
+            if filter_conditions.contains_key(field_path) {
+                anyhow::bail!("Found multiple filters for the same field?")
+            }
+            let values_bytes = |values: BTreeSet<Option<ConvexValue>>| -> Vec<_> {
+                values
+                    .into_iter()
+                    .map(|v| search_value_to_bytes(v.as_ref()))
+                    .collect()
+            };
+            let (field_path, condition) = match expresion {
+                VectorSearchExpression::Eq(field_path, value) => (
+                    field_path,
+                    CompiledVectorFilter::Eq(search_value_to_bytes(value.as_ref())),
+                ),
                 VectorSearchExpression::In(field_path, values) => {
-                    if !self.filter_fields.contains(&field_path) {
-                        anyhow::bail!(incorrect_vector_filter_field_error(
-                            &index_name,
-                            &field_path
-                        ))
-                    }
-                    let values_bytes: Vec<_> = values
-                        .into_iter()
-                        .map(|v| search_value_to_bytes(v.as_ref()))
-                        .collect();
-                    if filter_conditions.contains_key(&field_path) {
-                        anyhow::bail!("Found multiple filters for the same field?")
-                    }
-                    filter_length += values_bytes.len();
-                    filter_conditions.insert(field_path, CompiledVectorFilter::In(values_bytes));
+                    (field_path, CompiledVectorFilter::In(values_bytes(values)))
                 },
-            }
+                VectorSearchExpression::Neq(field_path, value) => (
+                    field_path,
+                    CompiledVectorFilter::NotIn(vec![search_value_to_bytes(value.as_ref())]),
+                ),
+                VectorSearchExpression::NotIn(field_path, values) => (
+                    field_path,
+                    CompiledVectorFilter::NotIn(values_bytes(values)),
+                ),
+                VectorSearchExpression::Range(field_path, range) => (
+                    field_path,
+                    CompiledVectorFilter::Range(CompiledVectorRange::try_from(range)?),
+                ),
+            };
+            filter_length += condition.filter_length();
+            filter_conditions.insert(field_path, condition);
         }
         anyhow::ensure!(
             filter_length <= MAX_FILTER_LENGTH,
@@ -265,27 +364,61 @@ impl QdrantSchema {
         slow_vector_query_threshold_millis: u64,
         require_exact: bool,
     ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
-        let qdrant_conditions = query
-            .filter_conditions
-            .iter()
-            .map(|(field_path, condition)| {
-                let field_condition = FieldCondition::new_match(
-                    encode_user_field_path(field_path)?,
-                    qdrant_filter_condition(condition),
-                );
-                Ok(Some(Condition::Field(field_condition)))
-            })
-            .collect::<anyhow::Result<Option<Vec<_>>>>()?;
+        // `Eq` and `In` conditions select documents, so a document only has to
+        // match one of them. The other conditions narrow the selection down, so a
+        // document has to match all of them.
+        let mut should = vec![];
+        let mut must = vec![];
+        let mut must_not = vec![];
+        for (field_path, condition) in &query.filter_conditions {
+            match condition {
+                CompiledVectorFilter::Eq(value) => {
+                    should.push(Condition::Field(FieldCondition::new_match(
+                        encode_user_field_path(field_path)?,
+                        qdrant_match_value(value),
+                    )))
+                },
+                CompiledVectorFilter::In(values) => {
+                    should.push(Condition::Field(FieldCondition::new_match(
+                        encode_user_field_path(field_path)?,
+                        qdrant_match_any(values),
+                    )))
+                },
+                CompiledVectorFilter::NotIn(values) => {
+                    must_not.push(Condition::Field(FieldCondition::new_match(
+                        encode_user_field_path(field_path)?,
+                        qdrant_match_any(values),
+                    )))
+                },
+                CompiledVectorFilter::Range(range) => {
+                    must.push(Condition::Field(FieldCondition::new_range(
+                        encode_numeric_field_path(field_path)?,
+                        Range {
+                            gt: range.gt,
+                            gte: range.gte,
+                            lt: range.lt,
+                            lte: range.lte,
+                        },
+                    )))
+                },
+            }
+        }
+        let non_empty = |conditions: Vec<Condition>| (!conditions.is_empty()).then_some(conditions);
         let qdrant_filter = Filter {
-            should: qdrant_conditions,
+            should: non_empty(should),
             min_should: None,
-            must: None,
-            must_not: None,
+            must: non_empty(must),
+            must_not: non_empty(must_not),
         };
+        // Segments without quantized vectors ignore the quantization params.
         let search_params = SearchParams {
-            hnsw_ef: None,
+            hnsw_ef: self.hnsw.ef().map(|ef| ef as usize),
             exact: require_exact,
-            quantization: None,
+            quantization: self.quantization.map(|_| QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(true),
+                oversampling: Some(*VECTOR_QUANTIZATION_OVERSAMPLING),
+            }),
             indexed_only: false,
         };
         let payload_selector = PayloadSelectorInclude {
@@ -333,7 +466,7 @@ impl QdrantSchema {
             let ts = u64::from_le_bytes(ts_bytes[..].try_into()?);
 
             let result = VectorSearchQueryResult {
-                score: qdrant_result.score,
+                score: score_from_qdrant(self.metric, qdrant_result.score),
                 id: internal_id,
                 ts: WriteTimestamp::Committed(ts.try_into()?),
             };
@@ -357,7 +490,12 @@ impl QdrantSchema {
         // upfront, always set up the more complex directory.
         let memory_dir: PathBuf = tmpdir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(self.dimension, true, *VECTOR_INDEX_THREADS);
+        let mutable_config = segment_config(
+            self.dimension,
+            self.segment_options(),
+            true,
+            *VECTOR_INDEX_THREADS,
+        );
         let mut memory_segment = create_mutable_segment(
             &memory_dir,
             id_tracker.clone(),
@@ -401,14 +539,21 @@ impl QdrantSchema {
             // consistency, but it's faster and simpler.
             previous_segments.maybe_delete_qdrant(*point_id)?;
         }
-        // We encode all of our index values as strings.
+        // We encode all of our index values as strings, and numeric values as
+        // numbers too.
         let field_schema = Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword));
+        let numeric_field_schema = Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Float));
         for field in self.filter_fields.iter() {
             memory_segment.create_field_index(
                 op_num,
                 &encode_user_field_path(field)?,
                 field_schema,
             )?;
+            memory_segment.create_field_index(
+                op_num,
+                &encode_numeric_field_path(field)?,
+                numeric_field_schema,
+            )?;
         }
         memory_timer.finish();
 
@@ -463,7 +608,12 @@ impl QdrantSchema {
                 fs::create_dir_all(&indexing_path)?;
                 let disk_path = index_path.join("disk");
                 fs::create_dir_all(&disk_path)?;
-                let disk_config = segment_config(self.dimension, false, *VECTOR_INDEX_THREADS);
+                let disk_config = segment_config(
+                    self.dimension,
+                    self.segment_options(),
+                    false,
+                    *VECTOR_INDEX_THREADS,
+                );
                 build_disk_segment(&memory_segment, &indexing_path, &disk_path, disk_config)
             },
         }?;
@@ -480,6 +630,8 @@ pub struct QdrantDocument {
     pub internal_id: InternalId,
     pub vector: IndexedVector,
     pub filter_fields: BTreeMap<FieldPath, Vec<u8>>,
+    /// The filter fields whose values are numbers, for range filters.
+    pub numeric_filter_fields: BTreeMap<FieldPath, f64>,
 }
 
 impl QdrantDocument {
@@ -490,24 +642,20 @@ impl QdrantDocument {
     pub fn encode_payload(&self, ts: Timestamp) -> anyhow::Result<JsonValue> {
         let mut map = serde_json::Map::new();
         for (field_path, field_value) in &self.filter_fields {
-            let mut current = &mut map;
-            // The path should consist of nested json objects.
-            for i in 0..field_path.fields().len() - 1 {
-                let field: String = field_path.fields()[i].clone().into();
-                let JsonValue::Object(inner) = current
-                    .entry(field)
-                    .or_insert_with(|| JsonValue::Object(serde_json::Map::new()))
-                else {
-                    // This means one filter field path is a prefix of another. We should
-                    // prevent the developer from defining such index. Throw a system error here.
-                    anyhow::bail!("Conflicting field path: {:?} {:?}", field_path, map);
-                };
-                current = inner;
-            }
-            current.insert(
-                field_path.last().clone().into(),
+            insert_payload_value(
+                &mut map,
+                field_path,
                 JsonValue::String(base64::encode_urlsafe(&field_value[..])),
-            );
+            )?;
+        }
+        if !self.numeric_filter_fields.is_empty() {
+            let mut numeric_map = serde_json::Map::new();
+            for (field_path, number) in &self.numeric_filter_fields {
+                let number = serde_json::Number::from_f64(*number)
+                    .ok_or_else(|| anyhow::anyhow!("Non-finite numeric filter value"))?;
+                insert_payload_value(&mut numeric_map, field_path, JsonValue::Number(number))?;
+            }
+            map.insert(NUMERIC_FIELD.to_string(), numeric_map.into());
         }
         map.insert(
             TIMESTAMP_FIELD.to_string(),
@@ -522,6 +670,30 @@ impl QdrantDocument {
     }
 }
 
+/// Insert `value` at `field_path` in a payload, creating the nested json
+/// objects along the path.
+fn insert_payload_value(
+    map: &mut serde_json::Map<String, JsonValue>,
+    field_path: &FieldPath,
+    value: JsonValue,
+) -> anyhow::Result<()> {
+    let mut current = map;
+    for i in 0..field_path.fields().len() - 1 {
+        let field: String = field_path.fields()[i].clone().into();
+        let JsonValue::Object(inner) = current
+            .entry(field)
+            .or_insert_with(|| JsonValue::Object(serde_json::Map::new()))
+        else {
+            // This means one filter field path is a prefix of another. We should
+            // prevent the developer from defining such index. Throw a system error here.
+            anyhow::bail!("Conflicting field path: {:?}", field_path);
+        };
+        current = inner;
+    }
+    current.insert(field_path.last().clone().into(), value);
+    Ok(())
+}
+
 #[cfg(any(test, feature = "testing"))]
 pub fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f32 {
     let v1 = CosineMetric::preprocess(v1.to_vec());
@@ -529,27 +701,57 @@ pub fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f32 {
     CosineMetric::similarity(&v1, &v2)
 }
 
+/// Preprocess a vector the way qdrant does before storing or querying it for
+/// `metric`. Only cosine normalizes vectors.
+pub(crate) fn preprocess_vector(metric: VectorDistanceMetric, vector: Vec<f32>) -> Vec<f32> {
+    match metric {
+        VectorDistanceMetric::Cosine => CosineMetric::preprocess(vector),
+        VectorDistanceMetric::Dot => DotProductMetric::preprocess(vector),
+        VectorDistanceMetric::Euclidean => EuclidMetric::preprocess(vector),
+    }
+}
+
+/// Score two vectors that have been through `preprocess_vector`, matching the
+/// scores of disk segment searches.
+pub(crate) fn similarity(metric: VectorDistanceMetric, v1: &[f32], v2: &[f32]) -> f32 {
+    let score = match metric {
+        VectorDistanceMetric::Cosine => CosineMetric::similarity(v1, v2),
+        VectorDistanceMetric::Dot => DotProductMetric::similarity(v1, v2),
+        VectorDistanceMetric::Euclidean => EuclidMetric::similarity(v1, v2),
+    };
+    score_from_qdrant(metric, score)
+}
+
+/// Qdrant orients every score so that higher is closer, but scores Euclidean
+/// distance as the negated squared distance. Convert that to the negated
+/// distance so scores are comparable to the distance itself.
+fn score_from_qdrant(metric: VectorDistanceMetric, score: f32) -> f32 {
+    match metric {
+        VectorDistanceMetric::Cosine | VectorDistanceMetric::Dot => score,
+        VectorDistanceMetric::Euclidean => -score.abs().sqrt(),
+    }
+}
+
 // NB: For cosine similarity, we need to normalize vectors before indexing them.
 #[derive(Clone, Debug)]
 pub struct NormalizedQdrantDocument {
     pub internal_id: InternalId,
     pub vector: Vec<f32>,
     pub filter_fields: BTreeMap<FieldPath, Vec<u8>>,
+    pub numeric_filter_fields: BTreeMap<FieldPath, f64>,
 }
 
-impl From<QdrantDocument> for NormalizedQdrantDocument {
-    fn from(value: QdrantDocument) -> Self {
-        let vector = Vec::from(value.vector);
-        let vector = CosineMetric::preprocess(vector);
+impl NormalizedQdrantDocument {
+    pub fn new(value: QdrantDocument, metric: VectorDistanceMetric) -> Self {
+        let vector = preprocess_vector(metric, Vec::from(value.vector));
         Self {
             internal_id: value.internal_id,
             vector,
             filter_fields: value.filter_fields,
+            numeric_filter_fields: value.numeric_filter_fields,
         }
     }
-}
 
-impl NormalizedQdrantDocument {
     pub fn size(&self) -> usize {
         let mut size = 0;
         size += self.vector.len() * mem::size_of::<f32>();
@@ -558,6 +760,7 @@ impl NormalizedQdrantDocument {
             size += field_path.fields().iter().map(|f| f.len()).sum::<usize>();
             size += maybe_value.len();
         }
+        size += self.numeric_filter_fields.len() * mem::size_of::<(FieldPath, f64)>();
         size
     }
 }
@@ -567,26 +770,28 @@ fn encode_user_field_path(field_path: &FieldPath) -> anyhow::Result<JsonPath> {
     json_path_from_str(key.as_str())
 }
 
-fn qdrant_filter_condition(condition: &CompiledVectorFilter) -> Match {
-    match condition {
-        CompiledVectorFilter::Eq(value) => {
-            let value_b64 = base64::encode_urlsafe(&value[..]);
-            let match_value = MatchValue {
-                value: ValueVariants::Keyword(value_b64),
-            };
-            Match::Value(match_value)
-        },
-        CompiledVectorFilter::In(values) => {
-            let values_b64 = values
-                .iter()
-                .map(|v| base64::encode_urlsafe(&v[..]))
-                .collect();
-            let match_value = MatchAny {
-                any: AnyVariants::Keywords(values_b64),
-            };
-            Match::Any(match_value)
-        },
-    }
+fn encode_numeric_field_path(field_path: &FieldPath) -> anyhow::Result<JsonPath> {
+    let key = format!("{NUMERIC_FIELD}.{}", String::from(field_path.clone()));
+    json_path_from_str(key.as_str())
+}
+
+fn qdrant_match_value(value: &[u8]) -> Match {
+    let value_b64 = base64::encode_urlsafe(value);
+    let match_value = MatchValue {
+        value: ValueVariants::Keyword(value_b64),
+    };
+    Match::Value(match_value)
+}
+
+fn qdrant_match_any(values: &[Vec<u8>]) -> Match {
+    let values_b64 = values
+        .iter()
+        .map(|v| base64::encode_urlsafe(&v[..]))
+        .collect();
+    let match_value = MatchAny {
+        any: AnyVariants::Keywords(values_b64),
+    };
+    Match::Any(match_value)
 }
 
 impl From<QdrantSchema> for proto::VectorIndexConfig {
@@ -595,6 +800,11 @@ impl From<QdrantSchema> for proto::VectorIndexConfig {
             dimension: value.dimension as u32,
             vector_field_path: Some(value.vector_field.into()),
             filter_fields: value.filter_fields.into_iter().map(|f| f.into()).collect(),
+            metric: proto::VectorDistanceMetric::from(value.metric) as i32,
+            quantization: value
+                .quantization
+                .map(|q| proto::VectorQuantization::from(q) as i32),
+            hnsw: Some(value.hnsw.into()),
         }
     }
 }
@@ -603,6 +813,17 @@ impl TryFrom<proto::VectorIndexConfig> for QdrantSchema {
     type Error = anyhow::Error;
 
     fn try_from(value: proto::VectorIndexConfig) -> Result<Self, Self::Error> {
+        let metric = value.metric().into();
+        let quantization = value
+            .quantization
+            .map(proto::VectorQuantization::try_from)
+            .transpose()?
+            .map(VectorQuantization::from);
+        let hnsw = value
+            .hnsw
+            .map(VectorHnswConfig::try_from)
+            .transpose()?
+            .unwrap_or_default();
         let vector_field = value
             .vector_field_path
             .ok_or_else(|| anyhow::anyhow!("Missing vector field path in VectorIndexConfigProto"))?
@@ -614,6 +835,9 @@ impl TryFrom<proto::VectorIndexConfig> for QdrantSchema {
             .collect::<Result<_, _>>()?;
         Ok(QdrantSchema {
             dimension: value.dimension as usize,
+            metric,
+            quantization,
+            hnsw,
             vector_field,
             filter_fields,
         })
@@ -697,6 +921,7 @@ mod tests {
                 .try_into()
                 .unwrap(),
             filter_fields: btreemap!(),
+            numeric_filter_fields: btreemap!(),
         };
         let payload = document.encode_payload(Timestamp::MIN)?;
         assert_eq!(payload, json!({ "_ts": "AAAAAAAAAAA"}));
@@ -713,6 +938,7 @@ mod tests {
                 "def.ghi".parse()? => vec![98],
                 "def.xyz".parse()? => vec![99],
             ),
+            numeric_filter_fields: btreemap!(),
         };
         let payload = document.encode_payload(Timestamp::MIN)?;
         assert_eq!(
@@ -730,9 +956,36 @@ mod tests {
             filter_fields: btreemap!(
                 "zzz".parse()? => vec![97],
             ),
+            numeric_filter_fields: btreemap!(),
         };
         let payload = document.encode_payload(Timestamp::MIN)?;
         assert_eq!(payload, json!({ "zzz": "YQ", "_ts": "AAAAAAAAAAA"}));
+
+        let document = QdrantDocument {
+            internal_id: InternalId(1u128.to_le_bytes()),
+            vector: (0..d)
+                .map(|_| rng.random())
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            filter_fields: btreemap!(
+                "abc".parse()? => vec![97],
+                "def.ghi".parse()? => vec![98],
+            ),
+            numeric_filter_fields: btreemap!(
+                "def.ghi".parse()? => 1.5,
+            ),
+        };
+        let payload = document.encode_payload(Timestamp::MIN)?;
+        assert_eq!(
+            payload,
+            json!({
+                "abc": "YQ",
+                "def": { "ghi": "Yg" },
+                "_numeric": { "def": { "ghi": 1.5 } },
+                "_ts": "AAAAAAAAAAA",
+            })
+        );
         Ok(())
     }
 }
@@ -17,6 +17,11 @@ use std::{
 
 use atomic_refcell::AtomicRefCell;
 use common::{
+    bootstrap_model::index::vector_index::{
+        VectorDistanceMetric,
+        VectorHnswConfig,
+        VectorQuantization,
+    },
     deleted_bitset::DeletedBitset,
     id_tracker::StaticIdTracker,
     runtime::tokio_spawn_blocking,
@@ -66,18 +71,26 @@ use qdrant_segment::{
         PAYLOAD_INDEX_PATH,
     },
     types::{
+        BinaryQuantization,
+        BinaryQuantizationConfig,
         Distance,
         HnswConfig,
         Indexes,
         PayloadStorageType,
+        QuantizationConfig,
+        ScalarQuantization,
+        ScalarQuantizationConfig,
+        ScalarType,
         SegmentConfig,
         SegmentType,
         VectorDataConfig,
         VectorStorageType,
         DEFAULT_FULL_SCAN_THRESHOLD,
-        DEFAULT_HNSW_EF_CONSTRUCT,
     },
-    vector_storage::VectorStorage,
+    vector_storage::{
+        quantized::quantized_vectors::QuantizedVectors,
+        VectorStorage,
+    },
 };
 use rocksdb::DB;
 
@@ -90,8 +103,44 @@ const UUID_TABLE_FILENAME: &str = "uuids.table";
 const DELETED_BITSET_FILENAME: &str = "deleted.bitset";
 pub(crate) const DEFAULT_VECTOR_NAME: &str = "default_vector";
 
+/// The per-index options that determine how an index's segments are built.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SegmentIndexOptions {
+    pub metric: VectorDistanceMetric,
+    pub quantization: Option<VectorQuantization>,
+    pub hnsw: VectorHnswConfig,
+}
+
+fn qdrant_distance(metric: VectorDistanceMetric) -> Distance {
+    match metric {
+        VectorDistanceMetric::Cosine => Distance::Cosine,
+        VectorDistanceMetric::Dot => Distance::Dot,
+        VectorDistanceMetric::Euclidean => Distance::Euclid,
+    }
+}
+
+fn qdrant_quantization(quantization: VectorQuantization) -> QuantizationConfig {
+    match quantization {
+        VectorQuantization::Scalar => QuantizationConfig::Scalar(ScalarQuantization {
+            scalar: ScalarQuantizationConfig {
+                r#type: ScalarType::Int8,
+                // Clip the most extreme 1% of values so they don't stretch the
+                // range the rest are quantized into.
+                quantile: Some(0.99),
+                always_ram: Some(false),
+            },
+        }),
+        VectorQuantization::Binary => QuantizationConfig::Binary(BinaryQuantization {
+            binary: BinaryQuantizationConfig {
+                always_ram: Some(false),
+            },
+        }),
+    }
+}
+
 pub(crate) fn segment_config(
     dimension: usize,
+    options: SegmentIndexOptions,
     mutable: bool,
     max_indexing_threads: usize,
 ) -> SegmentConfig {
@@ -101,11 +150,11 @@ pub(crate) fn segment_config(
         let hnsw_config = HnswConfig {
             // Number of edges per node in the index graph. Larger the value -
             // more accurate the search, more space required.
-            m: 16,
+            m: options.hnsw.m() as usize,
             // Number of neighbours to consider during the index building.
             // Larger  the value - more accurate the search, more
             // time required to build index.
-            ef_construct: DEFAULT_HNSW_EF_CONSTRUCT,
+            ef_construct: options.hnsw.ef_construct() as usize,
             // Minimal size (in KiloBytes) of vectors for additional
             // payload-based indexing. If payload chunk is smaller
             // than `full_scan_threshold_kb` additional indexing
@@ -126,12 +175,19 @@ pub(crate) fn segment_config(
     } else {
         VectorStorageType::Mmap
     };
+    // Quantized vectors are only built alongside HNSW graphs, and plain
+    // segments are small enough to search exactly.
+    let quantization_config = if mutable {
+        None
+    } else {
+        options.quantization.map(qdrant_quantization)
+    };
     let vector_data_config = VectorDataConfig {
         size: dimension,
-        distance: Distance::Cosine,
+        distance: qdrant_distance(options.metric),
         storage_type: vector_storage_type,
         index,
-        quantization_config: None,
+        quantization_config,
     };
     SegmentConfig {
         vector_data: HashMap::from([(DEFAULT_VECTOR_NAME.to_string(), vector_data_config)]),
@@ -166,7 +222,7 @@ pub fn create_mutable_segment(
     let vector_storage = open_appendable_memmap_vector_storage(
         &vector_storage_path,
         dimension,
-        Distance::Cosine,
+        segment_config.distance(),
         &stopped,
     )?;
     let point_count = id_tracker.borrow().total_point_count();
@@ -258,10 +314,19 @@ pub fn build_disk_segment(
 pub fn merge_disk_segments_hnsw(
     segments: Vec<(Option<UntarredVectorDiskSegmentPaths>, &Segment)>,
     dimension: usize,
+    options: SegmentIndexOptions,
     tmp_path: &Path,
     disk_path: &Path,
 ) -> anyhow::Result<VectorDiskSegmentValues> {
-    let segment_config = segment_config(dimension, false, 4);
+    // Segments are only merged within an index, so they share its metric.
+    for (paths, segment) in &segments {
+        anyhow::ensure!(
+            segment.segment_config.metric()? == options.metric,
+            "Segment {paths:?} has a different distance metric than {}",
+            options.metric
+        );
+    }
+    let segment_config = segment_config(dimension, options, false, 4);
     merge_disk_segments(segments, tmp_path, disk_path, segment_config)
 }
 
@@ -487,6 +552,14 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
     let vector_count = vector_storage.borrow().total_vector_count();
     anyhow::ensure!(vector_count == point_count);
 
+    let quantized_vectors = match vector_config.quantization_config {
+        Some(_) if QuantizedVectors::config_exists(&vector_storage_path) => Some(
+            QuantizedVectors::load(&vector_storage.borrow(), &vector_storage_path)?,
+        ),
+        _ => None,
+    };
+    let quantized_vectors = Arc::new(AtomicRefCell::new(quantized_vectors));
+
     let vector_index = match vector_config.index {
         qdrant_segment::types::Indexes::Plain {} => VectorIndexEnum::Plain(PlainIndex::new(
             id_tracker.clone(),
@@ -502,7 +575,7 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
                 &vector_index_path,
                 id_tracker.clone(),
                 vector_storage.clone(),
-                Arc::new(AtomicRefCell::new(None)),
+                quantized_vectors.clone(),
                 payload_index.clone(),
                 hnsw_config.clone(),
             )?)
@@ -513,7 +586,7 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
     let vector_data = VectorData {
         vector_storage,
         vector_index,
-        quantized_vectors: Arc::new(AtomicRefCell::new(None)),
+        quantized_vectors,
     };
     let segment = Segment {
         version: segment_state.version,
@@ -538,12 +611,27 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
 
 pub trait SegmentConfigExt {
     fn dimensions(&self) -> usize;
+    fn distance(&self) -> Distance;
+    fn metric(&self) -> anyhow::Result<VectorDistanceMetric>;
 }
 
 impl SegmentConfigExt for SegmentConfig {
     fn dimensions(&self) -> usize {
         self.vector_data[DEFAULT_VECTOR_NAME].size
     }
+
+    fn distance(&self) -> Distance {
+        self.vector_data[DEFAULT_VECTOR_NAME].distance
+    }
+
+    fn metric(&self) -> anyhow::Result<VectorDistanceMetric> {
+        match self.distance() {
+            Distance::Cosine => Ok(VectorDistanceMetric::Cosine),
+            Distance::Dot => Ok(VectorDistanceMetric::Dot),
+            Distance::Euclid => Ok(VectorDistanceMetric::Euclidean),
+            distance => anyhow::bail!("Unsupported segment distance {distance:?}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -561,6 +649,7 @@ mod tests {
     use anyhow::Context;
     use atomic_refcell::AtomicRefCell;
     use common::{
+        bootstrap_model::index::vector_index::VectorQuantization,
         deleted_bitset::DeletedBitset,
         id_tracker::StaticIdTracker,
     };
@@ -590,6 +679,8 @@ mod tests {
             PayloadSelector,
             PayloadSelectorInclude,
             PointIdType,
+            QuantizationSearchParams,
+            SearchParams,
             SegmentConfig,
             ValueVariants,
             WithPayload,
@@ -618,6 +709,7 @@ mod tests {
             segment_config,
             snapshot_segment,
             unsafe_load_disk_segment,
+            SegmentIndexOptions,
             VectorDiskSegmentPaths,
             VectorDiskSegmentValues,
             DEFAULT_VECTOR_NAME,
@@ -657,7 +749,7 @@ mod tests {
     ) -> anyhow::Result<(Segment, Arc<AtomicRefCell<VectorMemoryIdTracker>>)> {
         let memory_path = test_dir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(dimensions, true, 4);
+        let mutable_config = segment_config(dimensions, SegmentIndexOptions::default(), true, 4);
         let mut memory_segment =
             create_mutable_segment(&memory_path, id_tracker.clone(), dimensions, mutable_config)?;
 
@@ -677,7 +769,7 @@ mod tests {
     ) -> anyhow::Result<(Segment, Arc<AtomicRefCell<VectorMemoryIdTracker>>)> {
         let memory_path = test_dir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(dimensions, true, 4);
+        let mutable_config = segment_config(dimensions, SegmentIndexOptions::default(), true, 4);
         let mut memory_segment =
             create_mutable_segment(&memory_path, id_tracker.clone(), dimensions, mutable_config)?;
 
@@ -705,7 +797,7 @@ mod tests {
         let disk_path = test_dir.path().join("disk");
         fs::create_dir_all(&disk_path)?;
 
-        let disk_config = segment_config(dimensions, false, 4);
+        let disk_config = segment_config(dimensions, SegmentIndexOptions::default(), false, 4);
         Ok(build_disk_segment(&memory_segment, &indexing_path, &disk_path, disk_config)?.paths)
     }
 
@@ -773,7 +865,7 @@ mod tests {
         let disk_path = test_dir.path().join("disk");
         fs::create_dir_all(&disk_path)?;
 
-        let disk_config = segment_config(DIMENSIONS, false, 4);
+        let disk_config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         Ok(build_disk_segment(memory_segment, &indexing_path, &disk_path, disk_config)?.paths)
     }
 
@@ -849,6 +941,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn quantized_disk_segment_finds_vectors() -> anyhow::Result<()> {
+        for quantization in [VectorQuantization::Scalar, VectorQuantization::Binary] {
+            let test_dir = tempfile::tempdir()?;
+            let vectors: Vec<_> = stream_vectors(10).collect();
+            let (memory_segment, _) =
+                create_test_memory_segment(DIMENSIONS, &test_dir, vectors.clone().into_iter())?;
+
+            let indexing_path = test_dir.path().join("indexing");
+            fs::create_dir_all(&indexing_path)?;
+            let disk_path = test_dir.path().join("disk");
+            fs::create_dir_all(&disk_path)?;
+            let options = SegmentIndexOptions {
+                quantization: Some(quantization),
+                ..Default::default()
+            };
+            let disk_config = segment_config(DIMENSIONS, options, false, 4);
+            let paths =
+                build_disk_segment(&memory_segment, &indexing_path, &disk_path, disk_config)?.paths;
+            let disk_segment = unsafe_load_disk_segment(&paths).await?;
+            assert!(disk_segment.vector_data[DEFAULT_VECTOR_NAME]
+                .quantized_vectors
+                .borrow()
+                .is_some());
+
+            let search_params = SearchParams {
+                hnsw_ef: None,
+                exact: false,
+                quantization: Some(QuantizationSearchParams {
+                    ignore: false,
+                    rescore: Some(true),
+                    oversampling: Some(2.0),
+                }),
+                indexed_only: false,
+            };
+            for (point_id, vector) in vectors {
+                let results = disk_segment.search(
+                    DEFAULT_VECTOR_NAME,
+                    &QueryVector::Nearest(Vector::Dense(vector)),
+                    &WithPayload {
+                        enable: false,
+                        payload_selector: None,
+                    },
+                    &WithVector::Bool(false),
+                    None,
+                    1,
+                    Some(&search_params),
+                    &AtomicBool::new(false),
+                )?;
+                let result = results.first().context("Missing vector")?;
+                assert_eq!(result.id, point_id);
+            }
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn disk_segment_with_empty_filter() -> anyhow::Result<()> {
         let num_vectors: usize = 10;
@@ -1349,7 +1497,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vector.into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         let merged_dir = tempfile::tempdir()?;
         let result =
             merge_disk_segments_tmpdir(vec![&initial_segment, &new_segment], &merged_dir, config)
@@ -1375,7 +1523,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vectors.into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues { paths, .. } =
             merge_disk_segments_tmpdir(vec![&initial_segment, &new_segment], &merged_dir, config)?;
@@ -1414,7 +1562,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vector.clone().into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
@@ -1453,7 +1601,7 @@ mod tests {
             .map(|(segment, ..)| segment)
             .collect();
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
@@ -1496,7 +1644,7 @@ mod tests {
             create_test_disk_segment(DIMENSIONS, &other_dir, other_vectors.clone().into_iter())?;
         let other_segment = unsafe_load_disk_segment(&other_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, SegmentIndexOptions::default(), false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
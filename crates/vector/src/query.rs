@@ -67,6 +67,33 @@ pub struct VectorSearch {
 pub enum VectorSearchExpression {
     Eq(FieldPath, Option<ConvexValue>),
     In(FieldPath, BTreeSet<Option<ConvexValue>>),
+    Neq(FieldPath, Option<ConvexValue>),
+    NotIn(FieldPath, BTreeSet<Option<ConvexValue>>),
+    Range(FieldPath, VectorSearchRange),
+}
+
+/// Bounds on a numeric filter field. Bounds are `Int64`s or finite `Float64`s,
+/// and documents where the field isn't a number never match.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VectorSearchRange {
+    pub lower: Option<VectorSearchBound>,
+    pub upper: Option<VectorSearchBound>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VectorSearchBound {
+    Inclusive(ConvexValue),
+    Exclusive(ConvexValue),
+}
+
+/// The number range filters compare a filter field's value against, if it's a
+/// number. `Int64`s are widened to `f64`, so very large ones lose precision.
+pub(crate) fn filter_value_number(value: Option<&ConvexValue>) -> Option<f64> {
+    match value? {
+        ConvexValue::Float64(f) if f.is_finite() => Some(*f),
+        ConvexValue::Int64(i) => Some(*i as f64),
+        _ => None,
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -83,23 +110,93 @@ impl Arbitrary for VectorSearch {
             any::<Option<u32>>(),
             any::<Vec<f32>>(),
             // There's an invariant that there's at most one `VectorSearchExpression` for a given
-            // field. To ensure this, generate a map from FieldPath to filtered values
-            // and construct the `VectorSearchExpression` from that.
+            // field. To ensure this, generate maps from FieldPath to filtered values
+            // and construct the `VectorSearchExpression` from those, skipping fields
+            // that already have a condition.
             proptest::collection::btree_map(
                 any::<FieldPath>(),
                 proptest::collection::btree_set(any::<Option<ConvexValue>>(), 1..5),
                 1..5,
             ),
+            proptest::collection::btree_map(
+                any::<FieldPath>(),
+                proptest::collection::btree_set(any::<Option<ConvexValue>>(), 1..5),
+                0..3,
+            ),
+            proptest::collection::btree_map(any::<FieldPath>(), any::<VectorSearchRange>(), 0..3),
         )
-            .prop_map(|(index_name, component_id, limit, vector, field_map)| {
-                VectorSearch {
+            .prop_map(
+                |(
                     index_name,
                     component_id,
                     limit,
                     vector,
-                    expressions: VectorSearchExpression::from_field_map(field_map),
-                }
+                    field_map,
+                    negated_field_map,
+                    ranges,
+                )| {
+                    let negated_field_map: BTreeMap<_, _> = negated_field_map
+                        .into_iter()
+                        .filter(|(field_path, _)| !field_map.contains_key(field_path))
+                        .collect();
+                    let ranges: Vec<_> = ranges
+                        .into_iter()
+                        .filter(|(field_path, _)| {
+                            !field_map.contains_key(field_path)
+                                && !negated_field_map.contains_key(field_path)
+                        })
+                        .collect();
+                    let mut expressions = VectorSearchExpression::from_field_map(field_map);
+                    expressions.extend(VectorSearchExpression::from_negated_field_map(
+                        negated_field_map,
+                    ));
+                    expressions.extend(ranges.into_iter().map(|(field_path, range)| {
+                        VectorSearchExpression::Range(field_path, range)
+                    }));
+                    VectorSearch {
+                        index_name,
+                        component_id,
+                        limit,
+                        vector,
+                        expressions,
+                    }
+                },
+            )
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Arbitrary for VectorSearchRange {
+    type Parameters = ();
+
+    type Strategy = impl Strategy<Value = VectorSearchRange>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        fn bound() -> impl Strategy<Value = VectorSearchBound> {
+            (
+                any::<bool>(),
+                prop_oneof![
+                    any::<i64>().prop_map(ConvexValue::from),
+                    (-1e9..1e9f64).prop_map(ConvexValue::from),
+                ],
+            )
+                .prop_map(|(inclusive, value)| {
+                    if inclusive {
+                        VectorSearchBound::Inclusive(value)
+                    } else {
+                        VectorSearchBound::Exclusive(value)
+                    }
+                })
+        }
+
+        // Ranges without any bounds aren't expressible as filters.
+        (proptest::option::of(bound()), proptest::option::of(bound()))
+            .prop_filter("Ranges need at least one bound", |(lower, upper)| {
+                lower.is_some() || upper.is_some()
             })
+            .prop_map(|(lower, upper)| VectorSearchRange { lower, upper })
     }
 }
 
@@ -122,17 +219,133 @@ impl Arbitrary for VectorSearchExpression {
             )
                 .prop_map(|(field_path, elements)| {
                     VectorSearchExpression::In(field_path, elements)
-                })
+                }),
+            any::<(FieldPath, Option<ConvexValue>)>()
+                .prop_map(|(field_path, value)| VectorSearchExpression::Neq(field_path, value)),
+            (
+                any::<FieldPath>(),
+                prop::collection::btree_set(any::<Option<ConvexValue>>(), 2..5),
+            )
+                .prop_map(|(field_path, elements)| {
+                    VectorSearchExpression::NotIn(field_path, elements)
+                }),
+            any::<(FieldPath, VectorSearchRange)>()
+                .prop_map(|(field_path, range)| VectorSearchExpression::Range(field_path, range)),
         ]
     }
 }
 
 impl VectorSearchExpression {
-    /// Vector filters use a subset of the `Expression` syntax -- `q.or` and
-    /// `q.eq`.
+    pub fn field_path(&self) -> &FieldPath {
+        match self {
+            VectorSearchExpression::Eq(field_path, _)
+            | VectorSearchExpression::In(field_path, _)
+            | VectorSearchExpression::Neq(field_path, _)
+            | VectorSearchExpression::NotIn(field_path, _)
+            | VectorSearchExpression::Range(field_path, _) => field_path,
+        }
+    }
+
+    /// Vector filters use a subset of the `Expression` syntax. A combination
+    /// of `q.or` and `q.eq` matches documents equal to any of the values, and
+    /// `q.neq`, `q.gt`, `q.gte`, `q.lt`, and `q.lte` narrow the results down
+    /// further. These are combined with a top level `q.and`, e.g.
+    /// `q.and(q.or(q.eq("genre", "jazz"), q.eq("genre", "blues")),
+    /// q.gte("year", 1950), q.neq("artist", "x"))`.
     ///
-    /// We massage these into a list of Vec<VectorSearchExpression> (or error if
-    /// this is impossible). As an intermediate step, we create a map from
+    /// Each field can only have one kind of condition: `q.neq`s on the same
+    /// field become a `VectorSearchExpression::NotIn`, and bounds on the same
+    /// field become a single `VectorSearchExpression::Range`.
+    fn from_expression(expression: Expression) -> anyhow::Result<BTreeSet<Self>> {
+        let clauses = match expression {
+            Expression::And(clauses) => clauses,
+            expression => vec![expression],
+        };
+        let mut field_map = None;
+        let mut negated_field_map: BTreeMap<FieldPath, BTreeSet<Option<ConvexValue>>> =
+            BTreeMap::new();
+        let mut ranges: BTreeMap<FieldPath, VectorSearchRange> = BTreeMap::new();
+        for clause in clauses {
+            match clause {
+                clause @ (Expression::Eq(..) | Expression::Or(_)) => {
+                    anyhow::ensure!(
+                        field_map.is_none(),
+                        ErrorMetadata::bad_request(
+                            "InvalidVectorSearchFilter",
+                            "Filters can contain at most one combination of `q.eq` and `q.or`."
+                        )
+                    );
+                    field_map = Some(Self::assemble_filter_map(clause)?);
+                },
+                Expression::Neq(left, right) => {
+                    let (field_path, value) = Self::field_and_value(*left, *right, "q.neq")?;
+                    negated_field_map
+                        .entry(field_path)
+                        .or_default()
+                        .insert(value);
+                },
+                Expression::Gt(left, right) => {
+                    Self::add_bound(&mut ranges, *left, *right, "q.gt", true, false)?
+                },
+                Expression::Gte(left, right) => {
+                    Self::add_bound(&mut ranges, *left, *right, "q.gte", true, true)?
+                },
+                Expression::Lt(left, right) => {
+                    Self::add_bound(&mut ranges, *left, *right, "q.lt", false, false)?
+                },
+                Expression::Lte(left, right) => {
+                    Self::add_bound(&mut ranges, *left, *right, "q.lte", false, true)?
+                },
+                Expression::Literal(_)
+                | Expression::Add(..)
+                | Expression::Sub(..)
+                | Expression::Mul(..)
+                | Expression::Div(..)
+                | Expression::Mod(..)
+                | Expression::Neg(_)
+                | Expression::And(_)
+                | Expression::Not(_)
+                | Expression::Field(_) => {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "InvalidVectorSearchFilter",
+                        "Filters should be a combination of `q.eq` and `q.or`, optionally \
+                         combined with `q.neq`, `q.gt`, `q.gte`, `q.lt`, and `q.lte` using \
+                         `q.and`."
+                    ))
+                },
+            }
+        }
+        let field_map = field_map.unwrap_or_default();
+
+        let mut fields = BTreeSet::new();
+        for field_path in field_map
+            .keys()
+            .chain(negated_field_map.keys())
+            .chain(ranges.keys())
+        {
+            anyhow::ensure!(
+                fields.insert(field_path),
+                ErrorMetadata::bad_request(
+                    "InvalidVectorSearchFilter",
+                    format!(
+                        "Filters can only use one kind of condition on each field, but \
+                         {field_path:?} has several."
+                    )
+                )
+            );
+        }
+
+        let mut expressions = Self::from_field_map(field_map);
+        expressions.extend(Self::from_negated_field_map(negated_field_map));
+        expressions.extend(
+            ranges
+                .into_iter()
+                .map(|(field_path, range)| VectorSearchExpression::Range(field_path, range)),
+        );
+        Ok(expressions)
+    }
+
+    /// As an intermediate step for `q.or` and `q.eq`, we create a map from
     /// FieldPath to a Vec of Values so we can create
     /// `VectorSearchExpression::In` or `VectorSearchExpression::Eq`
     /// accordingly.
@@ -141,20 +354,12 @@ impl VectorSearchExpression {
     ) -> anyhow::Result<BTreeMap<FieldPath, BTreeSet<Option<ConvexValue>>>> {
         match expression {
             Expression::Eq(left, right) => {
-                if let (Expression::Field(field_path), Expression::Literal(value)) = (*left, *right)
-                {
-                    let mut field_map = BTreeMap::new();
-                    let mut values = BTreeSet::new();
-                    values.insert(value.0);
-                    field_map.insert(field_path, values);
-                    Ok(field_map)
-                } else {
-                    anyhow::bail!(ErrorMetadata::bad_request(
-                        "InvalidVectorSearchFilter",
-                        "`q.eq` must take a field path as its first argument and a value as its \
-                         second"
-                    ))
-                }
+                let (field_path, value) = Self::field_and_value(*left, *right, "q.eq")?;
+                let mut field_map = BTreeMap::new();
+                let mut values = BTreeSet::new();
+                values.insert(value);
+                field_map.insert(field_path, values);
+                Ok(field_map)
             },
             Expression::Or(expressions) => {
                 let mut full_field_map = BTreeMap::new();
@@ -184,15 +389,66 @@ impl VectorSearchExpression {
             | Expression::Field(_) => {
                 anyhow::bail!(ErrorMetadata::bad_request(
                     "InvalidVectorSearchFilter",
-                    "Filters should be a combination of `q.eq` and `q.or`."
+                    "`q.or` should only contain a combination of `q.eq` and `q.or`."
                 ))
             },
         }
     }
 
-    fn from_expression(expression: Expression) -> anyhow::Result<BTreeSet<Self>> {
-        let field_map = Self::assemble_filter_map(expression)?;
-        Ok(Self::from_field_map(field_map))
+    fn field_and_value(
+        left: Expression,
+        right: Expression,
+        operator: &str,
+    ) -> anyhow::Result<(FieldPath, Option<ConvexValue>)> {
+        let (Expression::Field(field_path), Expression::Literal(value)) = (left, right) else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidVectorSearchFilter",
+                format!(
+                    "`{operator}` must take a field path as its first argument and a value as its \
+                     second"
+                )
+            ))
+        };
+        Ok((field_path, value.0))
+    }
+
+    fn add_bound(
+        ranges: &mut BTreeMap<FieldPath, VectorSearchRange>,
+        left: Expression,
+        right: Expression,
+        operator: &str,
+        is_lower: bool,
+        inclusive: bool,
+    ) -> anyhow::Result<()> {
+        let (field_path, value) = Self::field_and_value(left, right, operator)?;
+        let Some(value) = value.filter(|v| filter_value_number(Some(v)).is_some()) else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidVectorSearchFilter",
+                format!("`{operator}` must compare {field_path:?} against a number")
+            ))
+        };
+        let range = ranges.entry(field_path.clone()).or_default();
+        let bound = if is_lower {
+            &mut range.lower
+        } else {
+            &mut range.upper
+        };
+        anyhow::ensure!(
+            bound.is_none(),
+            ErrorMetadata::bad_request(
+                "InvalidVectorSearchFilter",
+                format!(
+                    "Filters can have at most one {} bound on {field_path:?}",
+                    if is_lower { "lower" } else { "upper" }
+                )
+            )
+        );
+        *bound = Some(if inclusive {
+            VectorSearchBound::Inclusive(value)
+        } else {
+            VectorSearchBound::Exclusive(value)
+        });
+        Ok(())
     }
 
     fn from_field_map(
@@ -216,25 +472,75 @@ impl VectorSearchExpression {
         filters
     }
 
+    fn from_negated_field_map(
+        negated_field_map: BTreeMap<FieldPath, BTreeSet<Option<ConvexValue>>>,
+    ) -> BTreeSet<Self> {
+        let mut filters = BTreeSet::new();
+        for (key, mut values) in negated_field_map {
+            if values.len() == 1 {
+                let value = values
+                    .pop_first()
+                    .expect("Set does not have a single element");
+                filters.insert(VectorSearchExpression::Neq(key, value));
+            } else {
+                filters.insert(VectorSearchExpression::NotIn(key, values));
+            }
+        }
+        filters
+    }
+
     fn to_expression(filter_expressions: BTreeSet<Self>) -> Expression {
+        let field = |field_path: FieldPath| Box::new(Expression::Field(field_path));
+        let literal = |value: Option<ConvexValue>| Box::new(Expression::Literal(MaybeValue(value)));
         let mut expressions = vec![];
+        let mut narrowing_expressions = vec![];
         for filter in filter_expressions {
             match filter {
-                VectorSearchExpression::Eq(field_path, value) => expressions.push(Expression::Eq(
-                    Box::new(Expression::Field(field_path)),
-                    Box::new(Expression::Literal(MaybeValue(value))),
-                )),
+                VectorSearchExpression::Eq(field_path, value) => {
+                    expressions.push(Expression::Eq(field(field_path), literal(value)))
+                },
                 VectorSearchExpression::In(field_path, values) => {
                     for value in values {
-                        expressions.push(Expression::Eq(
-                            Box::new(Expression::Field(field_path.clone())),
-                            Box::new(Expression::Literal(MaybeValue(value))),
-                        ))
+                        expressions.push(Expression::Eq(field(field_path.clone()), literal(value)))
+                    }
+                },
+                VectorSearchExpression::Neq(field_path, value) => {
+                    narrowing_expressions.push(Expression::Neq(field(field_path), literal(value)))
+                },
+                VectorSearchExpression::NotIn(field_path, values) => {
+                    for value in values {
+                        narrowing_expressions
+                            .push(Expression::Neq(field(field_path.clone()), literal(value)))
                     }
                 },
+                VectorSearchExpression::Range(field_path, range) => {
+                    let lower = range.lower.map(|bound| match bound {
+                        VectorSearchBound::Inclusive(value) => {
+                            Expression::Gte(field(field_path.clone()), literal(Some(value)))
+                        },
+                        VectorSearchBound::Exclusive(value) => {
+                            Expression::Gt(field(field_path.clone()), literal(Some(value)))
+                        },
+                    });
+                    let upper = range.upper.map(|bound| match bound {
+                        VectorSearchBound::Inclusive(value) => {
+                            Expression::Lte(field(field_path.clone()), literal(Some(value)))
+                        },
+                        VectorSearchBound::Exclusive(value) => {
+                            Expression::Lt(field(field_path.clone()), literal(Some(value)))
+                        },
+                    });
+                    narrowing_expressions.extend(lower.into_iter().chain(upper));
+                },
             }
         }
-        Expression::Or(expressions)
+        if narrowing_expressions.is_empty() {
+            return Expression::Or(expressions);
+        }
+        if !expressions.is_empty() {
+            narrowing_expressions.insert(0, Expression::Or(expressions));
+        }
+        Expression::And(narrowing_expressions)
     }
 }
 
@@ -270,6 +576,54 @@ enum VectorSearchExpressionJson {
         path: String,
         values: Vec<JsonValue>,
     },
+    Neq {
+        path: String,
+        value: JsonValue,
+    },
+    NotIn {
+        path: String,
+        values: Vec<JsonValue>,
+    },
+    Range {
+        path: String,
+        lower: Option<VectorSearchBoundJson>,
+        upper: Option<VectorSearchBoundJson>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VectorSearchBoundJson {
+    value: JsonValue,
+    inclusive: bool,
+}
+
+impl From<VectorSearchBound> for VectorSearchBoundJson {
+    fn from(value: VectorSearchBound) -> Self {
+        let (value, inclusive) = match value {
+            VectorSearchBound::Inclusive(value) => (value, true),
+            VectorSearchBound::Exclusive(value) => (value, false),
+        };
+        Self {
+            value: MaybeValue(Some(value)).to_internal_json(),
+            inclusive,
+        }
+    }
+}
+
+impl TryFrom<VectorSearchBoundJson> for VectorSearchBound {
+    type Error = anyhow::Error;
+
+    fn try_from(value: VectorSearchBoundJson) -> Result<Self, Self::Error> {
+        let Some(bound) = MaybeValue::try_from(value.value)?.0 else {
+            anyhow::bail!("Range bounds can't be undefined");
+        };
+        Ok(if value.inclusive {
+            VectorSearchBound::Inclusive(bound)
+        } else {
+            VectorSearchBound::Exclusive(bound)
+        })
+    }
 }
 
 impl TryFrom<JsonValue> for VectorSearch {
@@ -335,6 +689,22 @@ impl TryFrom<VectorSearchExpression> for VectorSearchExpressionJson {
                     .map(|v| MaybeValue(v).to_internal_json())
                     .collect(),
             },
+            VectorSearchExpression::Neq(path, value) => VectorSearchExpressionJson::Neq {
+                path: path.into(),
+                value: MaybeValue(value).to_internal_json(),
+            },
+            VectorSearchExpression::NotIn(path, values) => VectorSearchExpressionJson::NotIn {
+                path: path.into(),
+                values: values
+                    .into_iter()
+                    .map(|v| MaybeValue(v).to_internal_json())
+                    .collect(),
+            },
+            VectorSearchExpression::Range(path, range) => VectorSearchExpressionJson::Range {
+                path: path.into(),
+                lower: range.lower.map(VectorSearchBoundJson::from),
+                upper: range.upper.map(VectorSearchBoundJson::from),
+            },
         };
         Ok(result)
     }
@@ -355,6 +725,25 @@ impl TryFrom<VectorSearchExpressionJson> for VectorSearchExpression {
                     .map(|v| anyhow::Ok(MaybeValue::try_from(v)?.0))
                     .try_collect()?,
             ),
+            VectorSearchExpressionJson::Neq { path, value } => {
+                VectorSearchExpression::Neq(path.parse()?, MaybeValue::try_from(value)?.0)
+            },
+            VectorSearchExpressionJson::NotIn { path, values } => VectorSearchExpression::NotIn(
+                path.parse()?,
+                values
+                    .into_iter()
+                    .map(|v| anyhow::Ok(MaybeValue::try_from(v)?.0))
+                    .try_collect()?,
+            ),
+            VectorSearchExpressionJson::Range { path, lower, upper } => {
+                VectorSearchExpression::Range(
+                    path.parse()?,
+                    VectorSearchRange {
+                        lower: lower.map(VectorSearchBound::try_from).transpose()?,
+                        upper: upper.map(VectorSearchBound::try_from).transpose()?,
+                    },
+                )
+            },
         };
         Ok(result)
     }
@@ -424,15 +813,114 @@ impl Debug for CompiledVectorSearch {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum CompiledVectorFilter {
     Eq(Vec<u8>),
     In(Vec<Vec<u8>>),
+    NotIn(Vec<Vec<u8>>),
+    Range(CompiledVectorRange),
+}
+
+impl CompiledVectorFilter {
+    /// How much this condition counts towards `MAX_FILTER_LENGTH`. Each
+    /// (in)equality counts once, so an `In` with N elements counts N times.
+    pub fn filter_length(&self) -> usize {
+        match self {
+            CompiledVectorFilter::Eq(_) | CompiledVectorFilter::Range(_) => 1,
+            CompiledVectorFilter::In(terms) | CompiledVectorFilter::NotIn(terms) => terms.len(),
+        }
+    }
+
+    /// `Eq` and `In` conditions select documents, and a document only needs to
+    /// match one of them. `NotIn` and `Range` conditions narrow the selected
+    /// documents down, and a document must match all of them.
+    fn is_narrowing(&self) -> bool {
+        matches!(
+            self,
+            CompiledVectorFilter::NotIn(_) | CompiledVectorFilter::Range(_)
+        )
+    }
+}
+
+/// Bounds on a numeric filter field, in the form qdrant's range conditions
+/// take.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CompiledVectorRange {
+    pub gt: Option<f64>,
+    pub gte: Option<f64>,
+    pub lt: Option<f64>,
+    pub lte: Option<f64>,
+}
+
+impl CompiledVectorRange {
+    pub fn contains(&self, value: f64) -> bool {
+        self.gt.map_or(true, |bound| value > bound)
+            && self.gte.map_or(true, |bound| value >= bound)
+            && self.lt.map_or(true, |bound| value < bound)
+            && self.lte.map_or(true, |bound| value <= bound)
+    }
+}
+
+impl TryFrom<VectorSearchRange> for CompiledVectorRange {
+    type Error = anyhow::Error;
+
+    fn try_from(range: VectorSearchRange) -> anyhow::Result<Self> {
+        let number = |value: &ConvexValue| {
+            filter_value_number(Some(value))
+                .ok_or_else(|| anyhow::anyhow!("Range bound {value:?} isn't a number"))
+        };
+        let mut compiled = Self::default();
+        match range.lower {
+            Some(VectorSearchBound::Inclusive(ref value)) => compiled.gte = Some(number(value)?),
+            Some(VectorSearchBound::Exclusive(ref value)) => compiled.gt = Some(number(value)?),
+            None => (),
+        }
+        match range.upper {
+            Some(VectorSearchBound::Inclusive(ref value)) => compiled.lte = Some(number(value)?),
+            Some(VectorSearchBound::Exclusive(ref value)) => compiled.lt = Some(number(value)?),
+            None => (),
+        }
+        Ok(compiled)
+    }
+}
+
+/// A document matches if it matches every `NotIn` and `Range` condition and,
+/// if there are any `Eq` or `In` conditions, at least one of those. So a query
+/// with no filter conditions matches every document.
+pub(crate) fn matches_filter_conditions(
+    filter_fields: &BTreeMap<FieldPath, Vec<u8>>,
+    numeric_filter_fields: &BTreeMap<FieldPath, f64>,
+    filter_conditions: &BTreeMap<FieldPath, CompiledVectorFilter>,
+) -> bool {
+    let mut has_selecting_condition = false;
+    let mut selected = false;
+    for (field_path, filter_condition) in filter_conditions {
+        has_selecting_condition |= !filter_condition.is_narrowing();
+        let value = filter_fields.get(field_path);
+        let condition_result = match filter_condition {
+            CompiledVectorFilter::Eq(term) => value == Some(term),
+            CompiledVectorFilter::In(terms) => value.is_some_and(|v| terms.contains(v)),
+            CompiledVectorFilter::NotIn(terms) => value.is_some_and(|v| !terms.contains(v)),
+            CompiledVectorFilter::Range(range) => numeric_filter_fields
+                .get(field_path)
+                .is_some_and(|number| range.contains(*number)),
+        };
+        if filter_condition.is_narrowing() {
+            if !condition_result {
+                return false;
+            }
+        } else {
+            selected |= condition_result;
+        }
+    }
+    !has_selecting_condition || selected
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct VectorSearchQueryResult {
+    /// Higher is closer for every metric: cosine similarity, dot product, or
+    /// negated Euclidean distance.
     pub score: f32,
     pub id: InternalId,
     pub ts: WriteTimestamp,
@@ -525,6 +1013,19 @@ impl From<CompiledVectorFilter> for proto::compiled_vector_query_filter_conditio
                     eq_conditions: values,
                 })
             },
+            CompiledVectorFilter::NotIn(values) => {
+                Self::NotInCondition(proto::CompiledVectorQueryFilterInCondition {
+                    eq_conditions: values,
+                })
+            },
+            CompiledVectorFilter::Range(range) => {
+                Self::RangeCondition(proto::CompiledVectorQueryFilterRangeCondition {
+                    gt: range.gt,
+                    gte: range.gte,
+                    lt: range.lt,
+                    lte: range.lte,
+                })
+            },
         }
     }
 }
@@ -542,6 +1043,17 @@ impl TryFrom<proto::compiled_vector_query_filter_condition::Filter> for Compiled
             proto::compiled_vector_query_filter_condition::Filter::InCondition(value) => {
                 Ok(Self::In(value.eq_conditions))
             },
+            proto::compiled_vector_query_filter_condition::Filter::NotInCondition(value) => {
+                Ok(Self::NotIn(value.eq_conditions))
+            },
+            proto::compiled_vector_query_filter_condition::Filter::RangeCondition(value) => {
+                Ok(Self::Range(CompiledVectorRange {
+                    gt: value.gt,
+                    gte: value.gte,
+                    lt: value.lt,
+                    lte: value.lte,
+                }))
+            },
         }
     }
 }
@@ -0,0 +1,383 @@
+use std::{
+    collections::BTreeMap,
+    mem,
+};
+
+use common::document::PackedDocument;
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+use value::{
+    heap_size::HeapSize,
+    FieldPath,
+};
+
+use crate::{
+    qdrant_index::{
+        preprocess_vector,
+        similarity,
+        QdrantSchema,
+    },
+    query::{
+        matches_filter_conditions,
+        CompiledVectorFilter,
+        CompiledVectorSearch,
+        VectorSearchQueryResult,
+    },
+};
+
+/// The vector searches a transaction ran against a single index.
+///
+/// Every document in the index can affect a vector search's results, so
+/// instead of recording the documents that were read we keep enough of each
+/// search to decide whether a written document could enter its top results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorQueryReads {
+    searches: Vec<VectorSearchRead>,
+}
+
+#[derive(Debug, Clone)]
+struct VectorSearchRead {
+    schema: QdrantSchema,
+    // The query vector, preprocessed for the index's metric.
+    vector: Vec<f32>,
+    filter_conditions: BTreeMap<FieldPath, CompiledVectorFilter>,
+    // The lowest score in the results if the search returned as many results
+    // as it asked for. Documents that score below it can't enter the results.
+    // `None` if every matching document is in the results.
+    min_score: Option<f32>,
+}
+
+impl VectorQueryReads {
+    pub fn empty() -> Self {
+        Self { searches: vec![] }
+    }
+
+    pub fn new(
+        schema: QdrantSchema,
+        query: &CompiledVectorSearch,
+        results: &[VectorSearchQueryResult],
+    ) -> Self {
+        let min_score = if results.len() < query.limit as usize {
+            None
+        } else {
+            results
+                .iter()
+                .map(|r| r.score)
+                .min_by(|a, b| a.total_cmp(b))
+        };
+        let vector = preprocess_vector(schema.metric(), Vec::from(query.vector.clone()));
+        let search = VectorSearchRead {
+            schema,
+            vector,
+            filter_conditions: query.filter_conditions.clone(),
+            min_score,
+        };
+        Self {
+            searches: vec![search],
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.searches.extend(other.searches);
+    }
+
+    /// A write overlaps a vector search if either the old or the new revision
+    /// of the document could be among its results. The old revision covers
+    /// documents that were in the results and were changed or deleted, and the
+    /// new revision covers documents that moved into the results.
+    pub fn overlaps_document(&self, document: &PackedDocument) -> bool {
+        self.searches
+            .iter()
+            .any(|search| search.overlaps_document(document))
+    }
+
+    /// Whether a write from `old` to `new` leaves the document's vector and
+    /// filter values unchanged. Such a write can't change the ids or scores
+    /// returned by any search on the index, so it doesn't need to be checked
+    /// with [`Self::overlaps_document`].
+    pub fn is_unchanged_by_write(&self, old: &PackedDocument, new: &PackedDocument) -> bool {
+        // All searches are against the same index, so they share a schema.
+        let Some(search) = self.searches.first() else {
+            return true;
+        };
+        match (
+            search.schema.index_packed(old),
+            search.schema.index_packed(new),
+        ) {
+            (None, None) => true,
+            (Some(old), Some(new)) => {
+                old.vector[..] == new.vector[..]
+                    && old.filter_fields == new.filter_fields
+                    && old.numeric_filter_fields == new.numeric_filter_fields
+            },
+            _ => false,
+        }
+    }
+}
+
+impl VectorSearchRead {
+    fn overlaps_document(&self, document: &PackedDocument) -> bool {
+        let Some(indexed) = self.schema.index_packed(document) else {
+            return false;
+        };
+        if !matches_filter_conditions(
+            &indexed.filter_fields,
+            &indexed.numeric_filter_fields,
+            &self.filter_conditions,
+        ) {
+            return false;
+        }
+        let Some(min_score) = self.min_score else {
+            return true;
+        };
+        let metric = self.schema.metric();
+        let vector = preprocess_vector(metric, Vec::from(indexed.vector));
+        similarity(metric, &self.vector, &vector) >= min_score
+    }
+}
+
+impl PartialEq for VectorSearchRead {
+    fn eq(&self, other: &Self) -> bool {
+        self.schema == other.schema
+            && self.filter_conditions == other.filter_conditions
+            && self.vector.len() == other.vector.len()
+            && self
+                .vector
+                .iter()
+                .zip(&other.vector)
+                .all(|(a, b)| a.total_cmp(b).is_eq())
+            && self.min_score.map(f32::to_bits) == other.min_score.map(f32::to_bits)
+    }
+}
+
+impl Eq for VectorSearchRead {}
+
+impl HeapSize for VectorQueryReads {
+    fn heap_size(&self) -> usize {
+        self.searches
+            .iter()
+            .map(|search| {
+                let filters: usize = search
+                    .filter_conditions
+                    .iter()
+                    .map(|(field_path, filter)| {
+                        field_path.heap_size()
+                            + match filter {
+                                CompiledVectorFilter::Eq(term) => term.heap_size(),
+                                CompiledVectorFilter::In(terms)
+                                | CompiledVectorFilter::NotIn(terms) => {
+                                    terms.iter().map(|t| t.heap_size()).sum()
+                                },
+                                CompiledVectorFilter::Range(_) => 0,
+                            }
+                    })
+                    .sum();
+                mem::size_of::<VectorSearchRead>()
+                    + search.vector.len() * mem::size_of::<f32>()
+                    + filters
+            })
+            .sum()
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Arbitrary for VectorQueryReads {
+    type Parameters = ();
+
+    type Strategy = impl Strategy<Value = VectorQueryReads>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use common::bootstrap_model::index::vector_index::{
+            DeveloperVectorIndexConfig,
+            MIN_VECTOR_DIMENSIONS,
+        };
+
+        // Keep the vectors short so generating read sets stays cheap.
+        let search = (
+            any::<DeveloperVectorIndexConfig>(),
+            MIN_VECTOR_DIMENSIONS..=8,
+        )
+            .prop_flat_map(|(mut config, dimensions)| {
+                config.dimensions = dimensions.try_into().unwrap();
+                (
+                    Just(QdrantSchema::new(&config)),
+                    prop::collection::vec(-1f32..1f32, dimensions as usize),
+                    prop::collection::btree_map(
+                        any::<FieldPath>(),
+                        any::<Vec<u8>>().prop_map(CompiledVectorFilter::Eq),
+                        0..3,
+                    ),
+                    prop::option::of(-1f32..1f32),
+                )
+            })
+            .prop_map(
+                |(schema, vector, filter_conditions, min_score)| VectorSearchRead {
+                    schema,
+                    vector,
+                    filter_conditions,
+                    min_score,
+                },
+            );
+        prop::collection::vec(search, 1..3).prop_map(|searches| Self { searches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        bootstrap_model::index::vector_index::{
+            DeveloperVectorIndexConfig,
+            VectorDistanceMetric,
+        },
+        document::{
+            CreationTime,
+            PackedDocument,
+            ResolvedDocument,
+        },
+        query::search_value_to_bytes,
+        testing::TestIdGenerator,
+        types::{
+            Timestamp,
+            WriteTimestamp,
+        },
+    };
+    use maplit::{
+        btreemap,
+        btreeset,
+    };
+    use value::{
+        assert_obj,
+        ConvexValue,
+        InternalId,
+        ResolvedDocumentId,
+    };
+
+    use crate::{
+        qdrant_index::QdrantSchema,
+        query::{
+            CompiledVectorFilter,
+            CompiledVectorSearch,
+            VectorSearchQueryResult,
+        },
+        VectorQueryReads,
+    };
+
+    fn schema() -> anyhow::Result<QdrantSchema> {
+        Ok(QdrantSchema::new(&DeveloperVectorIndexConfig {
+            dimensions: 2.try_into()?,
+            vector_field: "embedding".parse()?,
+            filter_fields: btreeset!["tag".parse()?],
+            metric: VectorDistanceMetric::Cosine,
+            quantization: None,
+            hnsw: Default::default(),
+        }))
+    }
+
+    fn result(score: f32) -> VectorSearchQueryResult {
+        VectorSearchQueryResult {
+            score,
+            id: InternalId(1u128.to_le_bytes()),
+            ts: WriteTimestamp::Committed(Timestamp::must(1)),
+        }
+    }
+
+    fn document(
+        id: ResolvedDocumentId,
+        vector: [f64; 2],
+        tag: &str,
+    ) -> anyhow::Result<PackedDocument> {
+        let embedding: ConvexValue = vector
+            .into_iter()
+            .map(ConvexValue::Float64)
+            .collect::<Vec<_>>()
+            .try_into()?;
+        let document = ResolvedDocument::new(
+            id,
+            CreationTime::ONE,
+            assert_obj!("embedding" => embedding, "tag" => tag),
+        )?;
+        Ok(PackedDocument::pack(&document))
+    }
+
+    fn tag_filter(tag: &str) -> CompiledVectorFilter {
+        CompiledVectorFilter::Eq(search_value_to_bytes(Some(
+            &ConvexValue::try_from(tag).unwrap(),
+        )))
+    }
+
+    #[test]
+    fn test_unfilled_search_overlaps_any_matching_document() -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let id = id_generator.user_generate(&"test".parse()?);
+        let query = CompiledVectorSearch {
+            vector: vec![1., 0.].try_into()?,
+            limit: 2,
+            filter_conditions: btreemap! {},
+        };
+        // Only one result for a limit of two, so every document in the index
+        // was returned and any new one would be too.
+        let reads = VectorQueryReads::new(schema()?, &query, &[result(0.9)]);
+        assert!(reads.overlaps_document(&document(id, [1., 0.], "a")?));
+        assert!(reads.overlaps_document(&document(id, [-1., 0.], "a")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_search_overlaps_documents_above_min_score() -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let id = id_generator.user_generate(&"test".parse()?);
+        let query = CompiledVectorSearch {
+            vector: vec![1., 0.].try_into()?,
+            limit: 2,
+            filter_conditions: btreemap! {},
+        };
+        let reads = VectorQueryReads::new(schema()?, &query, &[result(0.9), result(0.5)]);
+        // Cosine similarity of 1 and 0.6 are at least the lowest score.
+        assert!(reads.overlaps_document(&document(id, [1., 0.], "a")?));
+        assert!(reads.overlaps_document(&document(id, [3., 4.], "a")?));
+        // Cosine similarity of 0 and -1 are below it.
+        assert!(!reads.overlaps_document(&document(id, [0., 1.], "a")?));
+        assert!(!reads.overlaps_document(&document(id, [-1., 0.], "a")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_mismatch_does_not_overlap() -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let id = id_generator.user_generate(&"test".parse()?);
+        let query = CompiledVectorSearch {
+            vector: vec![1., 0.].try_into()?,
+            limit: 2,
+            filter_conditions: btreemap! { "tag".parse()? => tag_filter("a") },
+        };
+        let reads = VectorQueryReads::new(schema()?, &query, &[]);
+        assert!(reads.overlaps_document(&document(id, [1., 0.], "a")?));
+        assert!(!reads.overlaps_document(&document(id, [1., 0.], "b")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_old_and_new_revisions() -> anyhow::Result<()> {
+        let mut id_generator = TestIdGenerator::new();
+        let id = id_generator.user_generate(&"test".parse()?);
+        let query = CompiledVectorSearch {
+            vector: vec![1., 0.].try_into()?,
+            limit: 1,
+            filter_conditions: btreemap! {},
+        };
+        let reads = VectorQueryReads::new(schema()?, &query, &[result(0.8)]);
+        // A document in the results moving away from the query only overlaps
+        // through its old revision.
+        let old = document(id, [1., 0.], "a")?;
+        let new = document(id, [0., 1.], "a")?;
+        assert!(reads.overlaps_document(&old));
+        assert!(!reads.overlaps_document(&new));
+        // And a document moving into the results only overlaps through its new
+        // revision.
+        let old = document(id, [-1., 0.], "a")?;
+        let new = document(id, [1., 0.], "a")?;
+        assert!(!reads.overlaps_document(&old));
+        assert!(reads.overlaps_document(&new));
+        Ok(())
+    }
+}
@@ -27,6 +27,6 @@ pub trait VectorSearcher: Send + Sync + 'static {
         &self,
         search_storage: Arc<dyn Storage>,
         segments: Vec<pb::searchlight::FragmentedVectorSegmentPaths>,
-        dimension: usize,
+        schema: QdrantSchema,
     ) -> anyhow::Result<FragmentedVectorSegment>;
 }
@@ -57,11 +57,13 @@ use crate::{
     qdrant_index::QdrantSchema,
     query::{
         InternalVectorSearch,
+        VectorSearchExpression,
         VectorSearchQueryResult,
     },
     searcher::VectorSearcher,
     CompiledVectorSearch,
     DocInVectorIndex,
+    VectorQueryReads,
 };
 
 #[derive(Clone)]
@@ -268,7 +270,8 @@ impl VectorIndexManager {
             (None, Some(insertion)) => {
                 let metadata = IndexMetadata::try_from(insertion.value().clone().0)?;
                 if let IndexConfig::Vector {
-                    ref on_disk_state, ..
+                    ref on_disk_state,
+                    ref developer_config,
                 } = metadata.config
                 {
                     let VectorIndexState::Backfilling(state) = on_disk_state else {
@@ -280,7 +283,7 @@ impl VectorIndexManager {
                     self.indexes.insert(
                         insertion.id().internal_id(),
                         index,
-                        MemoryVectorIndex::new(ts),
+                        MemoryVectorIndex::new(ts, developer_config.metric),
                     );
 
                     metrics::log_index_created()
@@ -402,6 +405,22 @@ impl VectorIndexManager {
         searcher: Arc<dyn VectorSearcher>,
         search_storage: Arc<dyn Storage>,
     ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let (results, _) = self
+            .vector_search_with_reads(index, query, searcher, search_storage)
+            .await?;
+        Ok(results)
+    }
+
+    /// Like `vector_search`, but also returns the reads a transaction should
+    /// record so that it's invalidated by writes that could change the
+    /// results.
+    pub async fn vector_search_with_reads(
+        &self,
+        index: &Index,
+        query: InternalVectorSearch,
+        searcher: Arc<dyn VectorSearcher>,
+        search_storage: Arc<dyn Storage>,
+    ) -> anyhow::Result<(Vec<VectorSearchQueryResult>, VectorQueryReads)> {
         let timer = metrics::search_timer(&SEARCHLIGHT_CLUSTER_NAME);
         let result: anyhow::Result<_> = try {
             let IndexConfig::Vector {
@@ -424,6 +443,24 @@ impl VectorIndexManager {
             let VectorIndexState::SnapshottedAt(ref snapshot) = vector_index else {
                 anyhow::bail!(index_backfilling_error(&query.printable_index_name()?));
             };
+            // Segments written before numeric filter values were stored would
+            // silently drop every document from a range filter, unlike the
+            // memory index, so reject those queries until they're rebuilt.
+            if !snapshot.version.has_numeric_filter_values()
+                && query
+                    .expressions
+                    .iter()
+                    .any(|expression| matches!(expression, VectorSearchExpression::Range(..)))
+            {
+                anyhow::bail!(ErrorMetadata::bad_request(
+                    "VectorIndexMissingNumericFilterValues",
+                    format!(
+                        "Vector index {} is being rebuilt to support range filters. Try again \
+                         once it has finished rebuilding.",
+                        query.printable_index_name()?
+                    )
+                ));
+            }
             let (disk_revisions, vector_index_type) = match snapshot.data {
                 VectorIndexSnapshotData::Unknown(_) => {
                     anyhow::bail!(index_backfilling_error(&query.printable_index_name()?))
@@ -445,9 +482,9 @@ impl VectorIndexManager {
             (disk_revisions, vector_index_type)
         };
         match result {
-            Ok((disk_revisions, vector_index_type)) => {
+            Ok(((disk_revisions, reads), vector_index_type)) => {
                 metrics::finish_search(timer, &disk_revisions, vector_index_type);
-                Ok(disk_revisions)
+                Ok((disk_revisions, reads))
             },
             Err(e) => {
                 if e.is_bad_request() {
@@ -467,7 +504,7 @@ impl VectorIndexManager {
         qdrant_schema: QdrantSchema,
         memory_index: &MemoryVectorIndex,
         ts: Timestamp,
-    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+    ) -> anyhow::Result<(Vec<VectorSearchQueryResult>, VectorQueryReads)> {
         self.compile_search_and_truncate(
             query,
             qdrant_schema,
@@ -515,8 +552,9 @@ impl VectorIndexManager {
             usize,
         )
             -> BoxFuture<'a, anyhow::Result<Vec<VectorSearchQueryResult>>>,
-    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+    ) -> anyhow::Result<(Vec<VectorSearchQueryResult>, VectorQueryReads)> {
         let compiled_query = qdrant_schema.compile(query)?;
+        let reads_schema = qdrant_schema.clone();
         let updated_matches = memory_index.updated_matches(ts, &compiled_query)?;
         let overfetch_delta = updated_matches.len();
         metrics::log_searchlight_overfetch_delta(overfetch_delta);
@@ -538,7 +576,8 @@ impl VectorIndexManager {
             anyhow::Ok(())
         })?;
 
-        Ok(disk_revisions)
+        let reads = VectorQueryReads::new(reads_schema, &compiled_query, &disk_revisions);
+        Ok((disk_revisions, reads))
     }
 
     pub fn total_in_memory_size(&self) -> usize {
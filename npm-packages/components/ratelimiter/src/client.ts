@@ -5,11 +5,21 @@ import { GenericActionCtx, PublicHttpAction } from "convex/server";
 import { httpAction } from "./ratelimiter/_generated/server.js";
 
 // A version of httpAction that typechecks when used in other components.
-type ClientHttpCtx = Omit<GenericActionCtx<any>, "vectorSearch"> & {
+type ClientHttpCtx = Omit<
+  GenericActionCtx<any>,
+  "vectorSearch" | "hybridSearch" | "textSearch"
+> & {
   vectorSearch: unknown;
+  hybridSearch: unknown;
+  textSearch: unknown;
 };
-type ClientExportedHttpCtx = Omit<GenericActionCtx<any>, "vectorSearch"> & {
+type ClientExportedHttpCtx = Omit<
+  GenericActionCtx<any>,
+  "vectorSearch" | "hybridSearch" | "textSearch"
+> & {
   vectorSearch: any;
+  hybridSearch: any;
+  textSearch: any;
 };
 type OmitCallSignature<T> = T extends {
   (...args: any[]): any;
@@ -0,0 +1,142 @@
+import { Id } from "../values/value.js";
+import {
+  DocumentByInfo,
+  FieldTypeFromFieldPath,
+  GenericDataModel,
+  GenericDocument,
+  GenericTableInfo,
+  NamedSearchIndex,
+  NamedTableInfo,
+  NamedVectorIndex,
+  SearchIndexNames,
+  TableNamesInDataModel,
+  VectorIndexNames,
+} from "./data_model.js";
+import { FilterExpression } from "./vector_search.js";
+
+/**
+ * How a hybrid search combines the rankings of its text search and vector
+ * search.
+ *
+ * - `"rrf"`: reciprocal rank fusion. Each document scores `1 / (k + rank)` in
+ *   each search that returned it. `k` defaults to 60.
+ * - `"weighted"`: each search's scores are normalized to between 0 and 1 and
+ *   summed with the given weights.
+ *
+ * @public
+ */
+export type HybridFusion =
+  | { type: "rrf"; k?: number }
+  | { type: "weighted"; textWeight: number; vectorWeight: number };
+
+/**
+ * An object with parameters for performing a hybrid search against a search
+ * index and a vector index on the same table.
+ * @public
+ */
+export interface HybridSearchQuery<
+  TableInfo extends GenericTableInfo,
+  TextIndexName extends SearchIndexNames<TableInfo>,
+  VectorIndexName extends VectorIndexNames<TableInfo>,
+> {
+  /**
+   * The text search to run.
+   */
+  text: {
+    /**
+     * The name of the search index to query.
+     */
+    indexName: TextIndexName;
+    /**
+     * The `searchField` of the search index.
+     */
+    searchField: NamedSearchIndex<TableInfo, TextIndexName>["searchField"];
+    /**
+     * The query text to search for.
+     */
+    query: string;
+  };
+  /**
+   * The vector search to run.
+   */
+  vector: {
+    /**
+     * The name of the vector index to query.
+     */
+    indexName: VectorIndexName;
+    /**
+     * The query vector. This must have the same length as the `dimensions` of
+     * the index.
+     */
+    vector: number[];
+  };
+  /**
+   * The number of results to return. Each search fetches this many results
+   * before they're fused. If specified, must be between 1 and 256 inclusive.
+   *
+   * @default 10
+   */
+  limit?: number;
+  /**
+   * Optional filter expression made up of `q.eq` and `q.and`, applied to both
+   * searches. The fields must be filter fields of both indexes.
+   *
+   * e.g. `filter: q => q.and(q.eq("channel", channelId), q.eq("author", "Tom"))`
+   */
+  filter?: (
+    q: HybridFilterBuilder<
+      DocumentByInfo<TableInfo>,
+      NamedSearchIndex<TableInfo, TextIndexName>["filterFields"] &
+        NamedVectorIndex<TableInfo, VectorIndexName>["filterFields"]
+    >,
+  ) => FilterExpression<boolean>;
+  /**
+   * How to combine the two rankings.
+   *
+   * @default { type: "rrf", k: 60 }
+   */
+  fusion?: HybridFusion;
+}
+
+export type HybridSearch<
+  DataModel extends GenericDataModel,
+  TableName extends TableNamesInDataModel<DataModel>,
+  TextIndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+  VectorIndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+> = (
+  tableName: TableName,
+  query: HybridSearchQuery<
+    NamedTableInfo<DataModel, TableName>,
+    TextIndexName,
+    VectorIndexName
+  >,
+) => Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+/**
+ * An interface for defining filters for hybrid searches.
+ *
+ * Documents must match every filter in both the text and vector search.
+ *
+ * @public
+ */
+export interface HybridFilterBuilder<
+  Document extends GenericDocument,
+  FilterFields extends string,
+> {
+  /**
+   * Is the field at `fieldName` equal to `value`
+   *
+   * @public
+   * */
+  eq<FieldName extends FilterFields>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): FilterExpression<boolean>;
+
+  /**
+   * `exprs[0] && exprs[1] && ... && exprs[n]`
+   *
+   * @public
+   */
+  and(...exprs: Array<FilterExpression<boolean>>): FilterExpression<boolean>;
+}
@@ -0,0 +1,67 @@
+import { performAsyncSyscall } from "./syscall.js";
+import { version } from "../../index.js";
+import { HybridSearch, HybridSearchQuery } from "../hybrid_search.js";
+import { GenericDataModel, GenericTableInfo } from "../data_model.js";
+import { validateArg } from "./validate.js";
+import { filterBuilderImpl, serializeExpression } from "./vector_search_impl.js";
+
+export function setupActionHybridSearch(
+  requestId: string,
+): HybridSearch<GenericDataModel, string, string, string> {
+  return setupHybridSearch(requestId, "1.0/actions/hybridSearch");
+}
+
+/**
+ * Hybrid search within a query or mutation. Like vector search, both searches
+ * are part of the function's read set.
+ */
+export function setupQueryHybridSearch(): HybridSearch<
+  GenericDataModel,
+  string,
+  string,
+  string
+> {
+  return setupHybridSearch("", "1.0/hybridSearch");
+}
+
+function setupHybridSearch(
+  requestId: string,
+  syscall: string,
+): HybridSearch<GenericDataModel, string, string, string> {
+  return async (
+    tableName: string,
+    query: HybridSearchQuery<GenericTableInfo, string, string>,
+  ) => {
+    validateArg(tableName, 1, "hybridSearch", "tableName");
+    validateArg(query, 2, "hybridSearch", "query");
+    if (typeof query.text?.query !== "string") {
+      throw Error("`text.query` must be a string in hybridSearch");
+    }
+    if (
+      !query.vector?.vector ||
+      !Array.isArray(query.vector.vector) ||
+      query.vector.vector.length === 0
+    ) {
+      throw Error("`vector.vector` must be a non-empty Array in hybridSearch");
+    }
+    const filter = query.filter
+      ? serializeExpression(query.filter(filterBuilderImpl as any))
+      : null;
+
+    const { results } = await performAsyncSyscall(syscall, {
+      requestId,
+      version,
+      query: {
+        textIndexName: tableName + "." + query.text.indexName,
+        searchField: query.text.searchField,
+        textQuery: query.text.query,
+        vectorIndexName: tableName + "." + query.vector.indexName,
+        vector: query.vector.vector,
+        limit: query.limit,
+        filter,
+        fusion: query.fusion,
+      },
+    });
+    return results;
+  };
+}
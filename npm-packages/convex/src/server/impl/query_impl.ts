@@ -15,6 +15,7 @@ import {
 import {
   SearchFilterBuilderImpl,
   SerializedSearchFilter,
+  SerializedSearchOrder,
 } from "./search_filter_builder_impl.js";
 import { validateArg, validateArgIsNonNegativeInteger } from "./validate.js";
 import { version } from "../../index.js";
@@ -34,6 +35,7 @@ type Source =
       type: "Search";
       indexName: string;
       filters: ReadonlyArray<SerializedSearchFilter>;
+      order: SerializedSearchOrder | null;
     };
 
 type SerializedQuery = {
@@ -77,11 +79,13 @@ export class QueryInitializerImpl
     validateArg(indexName, 1, "withSearchIndex", "indexName");
     validateArg(searchFilter, 2, "withSearchIndex", "searchFilter");
     const searchFilterBuilder = SearchFilterBuilderImpl.new();
+    const { filters, order } = searchFilter(searchFilterBuilder).export();
     return new QueryImpl({
       source: {
         type: "Search",
         indexName: this.tableName + "." + indexName,
-        filters: searchFilter(searchFilterBuilder).export(),
+        filters,
+        order,
       },
       operators: [],
     });
@@ -208,7 +212,7 @@ export class QueryImpl implements Query<GenericTableInfo> {
     const query = this.takeQuery();
     if (query.source.type === "Search") {
       throw new Error(
-        "Search queries must always be in relevance order. Can not set order manually.",
+        "Search queries are in relevance order unless ordered with `orderBy` in the search filter. Can not set order manually.",
       );
     }
     if (query.source.order !== null) {
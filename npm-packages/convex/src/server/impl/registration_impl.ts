@@ -22,7 +22,18 @@ import {
   RegisteredQuery,
 } from "../registration.js";
 import { setupActionCalls } from "./actions_impl.js";
-import { setupActionVectorSearch } from "./vector_search_impl.js";
+import {
+  setupActionVectorSearch,
+  setupQueryVectorSearch,
+} from "./vector_search_impl.js";
+import {
+  setupActionHybridSearch,
+  setupQueryHybridSearch,
+} from "./hybrid_search_impl.js";
+import {
+  setupActionTextSearch,
+  setupQueryTextSearch,
+} from "./text_search_impl.js";
 import { setupAuth } from "./authentication_impl.js";
 import { setupReader, setupWriter } from "./database_impl.js";
 import { QueryImpl, QueryInitializerImpl } from "./query_impl.js";
@@ -52,6 +63,9 @@ async function invokeMutation<
     auth: setupAuth(requestId),
     storage: setupStorageWriter(requestId),
     scheduler: setupMutationScheduler(),
+    vectorSearch: setupQueryVectorSearch() as any,
+    hybridSearch: setupQueryHybridSearch() as any,
+    textSearch: setupQueryTextSearch() as any,
 
     runQuery: (reference: any, args?: any) => runUdf("query", reference, args),
     runMutation: (reference: any, args?: any) =>
@@ -259,6 +273,9 @@ async function invokeQuery<
     db: setupReader(),
     auth: setupAuth(requestId),
     storage: setupStorageReader(requestId),
+    vectorSearch: setupQueryVectorSearch() as any,
+    hybridSearch: setupQueryHybridSearch() as any,
+    textSearch: setupQueryTextSearch() as any,
     runQuery: (reference: any, args?: any) => runUdf("query", reference, args),
   };
   const result = await invokeFunction(func, queryCtx, args as any);
@@ -351,6 +368,8 @@ async function invokeAction<
     scheduler: setupActionScheduler(requestId),
     storage: setupStorageActionWriter(requestId),
     vectorSearch: setupActionVectorSearch(requestId) as any,
+    hybridSearch: setupActionHybridSearch(requestId) as any,
+    textSearch: setupActionTextSearch(requestId) as any,
   };
   const result = await invokeFunction(func, ctx, args as any);
   return JSON.stringify(convexToJson(result === undefined ? null : result));
@@ -441,6 +460,8 @@ async function invokeHttpAction<
     storage: setupStorageActionWriter(requestId),
     scheduler: setupActionScheduler(requestId),
     vectorSearch: setupActionVectorSearch(requestId) as any,
+    hybridSearch: setupActionHybridSearch(requestId) as any,
+    textSearch: setupActionTextSearch(requestId) as any,
   };
   return await invokeFunction(func, ctx, [request]);
 }
@@ -18,11 +18,16 @@ export type SerializedSearchFilter =
       value: string;
     }
   | {
-      type: "Eq";
+      type: "Eq" | "Gt" | "Gte" | "Lt" | "Lte";
       fieldPath: string;
       value: JSONValue;
     };
 
+export type SerializedSearchOrder = {
+  fieldPath: string;
+  order: "asc" | "desc";
+};
+
 export class SearchFilterBuilderImpl
   extends SearchFilter
   implements
@@ -30,10 +35,15 @@ export class SearchFilterBuilderImpl
     SearchFilterFinalizer<GenericDocument, GenericSearchIndexConfig>
 {
   private filters: ReadonlyArray<SerializedSearchFilter>;
+  private order: SerializedSearchOrder | null;
   private isConsumed: boolean;
-  private constructor(filters: ReadonlyArray<SerializedSearchFilter>) {
+  private constructor(
+    filters: ReadonlyArray<SerializedSearchFilter>,
+    order: SerializedSearchOrder | null = null,
+  ) {
     super();
     this.filters = filters;
+    this.order = order;
     this.isConsumed = false;
   }
 
@@ -74,18 +84,66 @@ export class SearchFilterBuilderImpl
     if (arguments.length !== 2) {
       validateArg(value, 2, "search", "value");
     }
+    return this.compare("Eq", fieldName, value);
+  }
+  gt<FieldName extends string>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
+  ): SearchFilterFinalizer<GenericDocument, GenericSearchIndexConfig> {
+    validateArg(fieldName, 1, "gt", "fieldName");
+    validateArg(value, 2, "gt", "value");
+    return this.compare("Gt", fieldName, value);
+  }
+  gte<FieldName extends string>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
+  ): SearchFilterFinalizer<GenericDocument, GenericSearchIndexConfig> {
+    validateArg(fieldName, 1, "gte", "fieldName");
+    validateArg(value, 2, "gte", "value");
+    return this.compare("Gte", fieldName, value);
+  }
+  lt<FieldName extends string>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
+  ): SearchFilterFinalizer<GenericDocument, GenericSearchIndexConfig> {
+    validateArg(fieldName, 1, "lt", "fieldName");
+    validateArg(value, 2, "lt", "value");
+    return this.compare("Lt", fieldName, value);
+  }
+  lte<FieldName extends string>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
+  ): SearchFilterFinalizer<GenericDocument, GenericSearchIndexConfig> {
+    validateArg(fieldName, 1, "lte", "fieldName");
+    validateArg(value, 2, "lte", "value");
+    return this.compare("Lte", fieldName, value);
+  }
+  private compare(
+    type: "Eq" | "Gt" | "Gte" | "Lt" | "Lte",
+    fieldName: string,
+    value: any,
+  ): SearchFilterBuilderImpl {
     this.consume();
     return new SearchFilterBuilderImpl(
       this.filters.concat({
-        type: "Eq",
+        type,
         fieldPath: fieldName,
         value: convexOrUndefinedToJson(value),
       }),
     );
   }
 
+  orderBy(fieldName: string, order?: "asc" | "desc"): SearchFilter {
+    validateArg(fieldName, 1, "orderBy", "fieldName");
+    this.consume();
+    return new SearchFilterBuilderImpl(this.filters, {
+      fieldPath: fieldName,
+      order: order ?? "asc",
+    });
+  }
+
   export() {
     this.consume();
-    return this.filters;
+    return { filters: this.filters, order: this.order };
   }
 }
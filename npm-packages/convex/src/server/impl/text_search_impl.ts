@@ -0,0 +1,62 @@
+import { performAsyncSyscall } from "./syscall.js";
+import { version } from "../../index.js";
+import { TextSearch, TextSearchOptions } from "../text_search.js";
+import { GenericDataModel } from "../data_model.js";
+import { validateArg } from "./validate.js";
+import { SearchFilterBuilderImpl } from "./search_filter_builder_impl.js";
+
+export function setupActionTextSearch(
+  requestId: string,
+): TextSearch<GenericDataModel, string, string> {
+  return setupTextSearch(requestId, "1.0/actions/textSearch");
+}
+
+/**
+ * Text search within a query or mutation. Like `withSearchIndex`, the search
+ * is part of the function's read set.
+ */
+export function setupQueryTextSearch(): TextSearch<
+  GenericDataModel,
+  string,
+  string
+> {
+  return setupTextSearch("", "1.0/textSearch");
+}
+
+function setupTextSearch(
+  requestId: string,
+  syscall: string,
+): TextSearch<GenericDataModel, string, string> {
+  return async (
+    tableName: string,
+    indexName: string,
+    searchFilter: (q: any) => any,
+    options?: TextSearchOptions,
+  ) => {
+    validateArg(tableName, 1, "textSearch", "tableName");
+    validateArg(indexName, 2, "textSearch", "indexName");
+    validateArg(searchFilter, 3, "textSearch", "searchFilter");
+    const { filters, order } = (
+      searchFilter(SearchFilterBuilderImpl.new()) as SearchFilterBuilderImpl
+    ).export();
+    const highlight =
+      options?.highlight === true
+        ? {}
+        : options?.highlight
+          ? { snippetTokens: options.highlight.snippetTokens }
+          : null;
+
+    const { results } = await performAsyncSyscall(syscall, {
+      requestId,
+      version,
+      query: {
+        indexName: tableName + "." + indexName,
+        filters,
+        order,
+        limit: options?.limit,
+        highlight,
+      },
+    });
+    return results;
+  };
+}
@@ -19,6 +19,26 @@ import { Value, convexOrUndefinedToJson } from "../../values/value.js";
 
 export function setupActionVectorSearch(
   requestId: string,
+): VectorSearch<GenericDataModel, string, string> {
+  return setupVectorSearch(requestId, "1.0/actions/vectorSearch");
+}
+
+/**
+ * Vector search within a query or mutation. The search is part of the
+ * function's read set, so query subscriptions rerun when a write could change
+ * the results.
+ */
+export function setupQueryVectorSearch(): VectorSearch<
+  GenericDataModel,
+  string,
+  string
+> {
+  return setupVectorSearch("", "1.0/vectorSearch");
+}
+
+function setupVectorSearch(
+  requestId: string,
+  syscall: string,
 ): VectorSearch<GenericDataModel, string, string> {
   return async (
     tableName: string,
@@ -40,12 +60,14 @@ export function setupActionVectorSearch(
       requestId,
       tableName + "." + indexName,
       query,
+      syscall,
     ).collect();
   };
 }
 
 export class VectorQueryImpl {
   private requestId: string;
+  private syscall: string;
   private state:
     | { type: "preparing"; query: SerializedVectorQuery }
     | { type: "consumed" };
@@ -54,8 +76,10 @@ export class VectorQueryImpl {
     requestId: string,
     indexName: string,
     query: VectorSearchQuery<GenericTableInfo, string>,
+    syscall: string = "1.0/actions/vectorSearch",
   ) {
     this.requestId = requestId;
+    this.syscall = syscall;
     const filters = query.filter
       ? serializeExpression(query.filter(filterBuilderImpl))
       : null;
@@ -78,7 +102,7 @@ export class VectorQueryImpl {
     const query = this.state.query;
     this.state = { type: "consumed" };
 
-    const { results } = await performAsyncSyscall("1.0/actions/vectorSearch", {
+    const { results } = await performAsyncSyscall(this.syscall, {
       requestId: this.requestId,
       version,
       query,
@@ -132,15 +156,42 @@ export const filterBuilderImpl: VectorFilterBuilder<
     fieldName: FieldName,
     value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
   ): FilterExpression<boolean> {
-    if (typeof fieldName !== "string") {
-      throw new Error("The first argument to `q.eq` must be a field name.");
-    }
-    return new ExpressionImpl({
-      $eq: [
-        serializeExpression(new ExpressionImpl({ $field: fieldName })),
-        serializeExpression(value),
-      ],
-    });
+    return comparison("eq", fieldName, value);
+  },
+
+  neq<FieldName extends GenericVectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<GenericDocument, FieldName>,
+  ): FilterExpression<boolean> {
+    return comparison("neq", fieldName, value);
+  },
+
+  gt<FieldName extends GenericVectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean> {
+    return comparison("gt", fieldName, value);
+  },
+
+  gte<FieldName extends GenericVectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean> {
+    return comparison("gte", fieldName, value);
+  },
+
+  lt<FieldName extends GenericVectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean> {
+    return comparison("lt", fieldName, value);
+  },
+
+  lte<FieldName extends GenericVectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean> {
+    return comparison("lte", fieldName, value);
   },
 
   //  Logic  ///////////////////////////////////////////////////////////////////
@@ -148,4 +199,26 @@ export const filterBuilderImpl: VectorFilterBuilder<
   or(...exprs: Array<ExpressionOrValue<boolean>>): FilterExpression<boolean> {
     return new ExpressionImpl({ $or: exprs.map(serializeExpression) });
   },
+
+  and(...exprs: Array<ExpressionOrValue<boolean>>): FilterExpression<boolean> {
+    return new ExpressionImpl({ $and: exprs.map(serializeExpression) });
+  },
 };
+
+function comparison(
+  operator: "eq" | "neq" | "gt" | "gte" | "lt" | "lte",
+  fieldName: unknown,
+  value: ExpressionOrValue<Value | undefined>,
+): FilterExpression<boolean> {
+  if (typeof fieldName !== "string") {
+    throw new Error(
+      `The first argument to \`q.${operator}\` must be a field name.`,
+    );
+  }
+  return new ExpressionImpl({
+    [`$${operator}`]: [
+      serializeExpression(new ExpressionImpl({ $field: fieldName })),
+      serializeExpression(value),
+    ],
+  });
+}
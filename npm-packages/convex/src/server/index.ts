@@ -193,7 +193,12 @@ export type { Index, SearchIndex, VectorIndex } from "./schema.js";
 
 export type {
   SearchIndexConfig,
+  TextIndexAnalyzer,
   VectorIndexConfig,
+  VectorDistanceMetric,
+  VectorQuantization,
+  VectorHnswConfig,
+  OnDeleteAction,
   TableDefinition,
   SchemaDefinition,
   DefineSchemaOptions,
@@ -210,6 +215,18 @@ export type {
   VectorFilterBuilder,
   FilterExpression,
 } from "./vector_search.js";
+export type {
+  HybridSearch,
+  HybridSearchQuery,
+  HybridFilterBuilder,
+  HybridFusion,
+} from "./hybrid_search.js";
+export type {
+  TextSearch,
+  TextSearchOptions,
+  TextSearchHighlight,
+  TextSearchResult,
+} from "./text_search.js";
 
 /**
  * @public
@@ -70,20 +70,21 @@ export interface QueryInitializer<TableInfo extends GenericTableInfo>
    * Query by running a full text search against a search index.
    *
    * Search queries must always search for some text within the index's
-   * `searchField`. This query can optionally add equality filters for any
-   * `filterFields` specified in the index.
+   * `searchField`. This query can optionally add equality and range filters
+   * for any `filterFields` specified in the index.
    *
    * Documents will be returned in relevance order based on how well they
-   * match the search text.
+   * match the search text, unless the search filter orders them by
+   * `_creationTime` or a filter field with `orderBy`.
    *
    * To learn about full text search, see [Indexes](https://docs.convex.dev/text-search).
    *
    * @param indexName - The name of the search index to query.
    * @param searchFilter - A search filter expression constructed with the
    * supplied {@link SearchFilterBuilder}. This defines the full text search to run
-   * along with equality filtering to run within the search index.
+   * along with equality and range filtering to run within the search index.
    * @returns - A query that searches for matching documents, returning them
-   * in relevancy order.
+   * in relevancy order or the order given by `orderBy`.
    */
   withSearchIndex<IndexName extends SearchIndexNames<TableInfo>>(
     indexName: IndexName,
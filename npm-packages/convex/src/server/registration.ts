@@ -22,13 +22,19 @@ import {
 } from "../values/validator.js";
 import { Id } from "../values/value.js";
 import {
+  DocumentByInfo,
   GenericDataModel,
+  NamedSearchIndex,
   NamedTableInfo,
+  SearchIndexNames,
   TableNamesInDataModel,
   VectorIndexNames,
 } from "./data_model.js";
 import { Scheduler } from "./scheduler.js";
 import { VectorSearchQuery } from "./vector_search.js";
+import { HybridSearchQuery } from "./hybrid_search.js";
+import { TextSearchOptions, TextSearchResult } from "./text_search.js";
+import { SearchFilter, SearchFilterBuilder } from "./search_filter_builder.js";
 import { Expand } from "../type_utils.js";
 import { Validator } from "../values/validators.js";
 
@@ -64,6 +70,92 @@ export interface GenericMutationCtx<DataModel extends GenericDataModel> {
    */
   scheduler: Scheduler;
 
+  /**
+   * Run a vector search on the given table and index.
+   *
+   * The search is part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the vector index on the table to query.
+   * @param query - A {@link VectorSearchQuery} containing the vector to query,
+   * the number of results to return, and any filters.
+   * @returns A promise of IDs and scores for the documents with the nearest
+   * vectors. Higher scores are closer.
+   */
+  vectorSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    query: Expand<
+      VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a hybrid search that combines a text search and a vector search on
+   * the given table.
+   *
+   * The searches are part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param query - A {@link HybridSearchQuery} containing the text search,
+   * the vector search, the number of results to return, shared filters, and
+   * how to fuse the two rankings.
+   * @returns A promise of IDs and fused scores for the best matching
+   * documents. Higher scores are better matches.
+   */
+  hybridSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    TextIndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+    VectorIndexName extends VectorIndexNames<
+      NamedTableInfo<DataModel, TableName>
+    >,
+  >(
+    tableName: TableName,
+    query: Expand<
+      HybridSearchQuery<
+        NamedTableInfo<DataModel, TableName>,
+        TextIndexName,
+        VectorIndexName
+      >
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a text search on the given table, returning scored IDs rather than
+   * documents and optionally where each document matched.
+   *
+   * The search is part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the search index on the table to query.
+   * @param searchFilter - A search filter expression constructed with the
+   * supplied {@link SearchFilterBuilder}, like `withSearchIndex`.
+   * @param options - A {@link TextSearchOptions} with the number of results
+   * to return and whether to highlight them.
+   * @returns A promise of IDs and scores for the best matching documents,
+   * best first, with highlights if requested.
+   */
+  textSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    searchFilter: (
+      q: SearchFilterBuilder<
+        DocumentByInfo<NamedTableInfo<DataModel, TableName>>,
+        NamedSearchIndex<NamedTableInfo<DataModel, TableName>, IndexName>
+      >,
+    ) => SearchFilter,
+    options?: TextSearchOptions,
+  ): Promise<Array<TextSearchResult<TableName>>>;
+
   /**
    * Call a query function within the same transaction.
    *
@@ -139,6 +231,92 @@ export interface GenericQueryCtx<DataModel extends GenericDataModel> {
    */
   storage: StorageReader;
 
+  /**
+   * Run a vector search on the given table and index.
+   *
+   * The search is part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the vector index on the table to query.
+   * @param query - A {@link VectorSearchQuery} containing the vector to query,
+   * the number of results to return, and any filters.
+   * @returns A promise of IDs and scores for the documents with the nearest
+   * vectors. Higher scores are closer.
+   */
+  vectorSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    query: Expand<
+      VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a hybrid search that combines a text search and a vector search on
+   * the given table.
+   *
+   * The searches are part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param query - A {@link HybridSearchQuery} containing the text search,
+   * the vector search, the number of results to return, shared filters, and
+   * how to fuse the two rankings.
+   * @returns A promise of IDs and fused scores for the best matching
+   * documents. Higher scores are better matches.
+   */
+  hybridSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    TextIndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+    VectorIndexName extends VectorIndexNames<
+      NamedTableInfo<DataModel, TableName>
+    >,
+  >(
+    tableName: TableName,
+    query: Expand<
+      HybridSearchQuery<
+        NamedTableInfo<DataModel, TableName>,
+        TextIndexName,
+        VectorIndexName
+      >
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a text search on the given table, returning scored IDs rather than
+   * documents and optionally where each document matched.
+   *
+   * The search is part of this function's reads: a query that calls it
+   * reruns when a write could change its results.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the search index on the table to query.
+   * @param searchFilter - A search filter expression constructed with the
+   * supplied {@link SearchFilterBuilder}, like `withSearchIndex`.
+   * @param options - A {@link TextSearchOptions} with the number of results
+   * to return and whether to highlight them.
+   * @returns A promise of IDs and scores for the best matching documents,
+   * best first, with highlights if requested.
+   */
+  textSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    searchFilter: (
+      q: SearchFilterBuilder<
+        DocumentByInfo<NamedTableInfo<DataModel, TableName>>,
+        NamedSearchIndex<NamedTableInfo<DataModel, TableName>, IndexName>
+      >,
+    ) => SearchFilter,
+    options?: TextSearchOptions,
+  ): Promise<Array<TextSearchResult<TableName>>>;
+
   /**
    * Call a query function within the same transaction.
    *
@@ -253,7 +431,9 @@ export interface GenericActionCtx<DataModel extends GenericDataModel> {
    * @param query - A {@link VectorSearchQuery} containing the vector to query,
    * the number of results to return, and any filters.
    * @returns A promise of IDs and scores for the documents with the nearest
-   * vectors
+   * vectors. Higher scores are closer for every metric: cosine similarity,
+   * dot product, or negated Euclidean distance, depending on the index's
+   * `metric`.
    */
   vectorSearch<
     TableName extends TableNamesInDataModel<DataModel>,
@@ -265,6 +445,62 @@ export interface GenericActionCtx<DataModel extends GenericDataModel> {
       VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
     >,
   ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a hybrid search that combines a text search and a vector search on
+   * the given table.
+   *
+   * @param tableName - The name of the table to query.
+   * @param query - A {@link HybridSearchQuery} containing the text search,
+   * the vector search, the number of results to return, shared filters, and
+   * how to fuse the two rankings.
+   * @returns A promise of IDs and fused scores for the best matching
+   * documents. Higher scores are better matches.
+   */
+  hybridSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    TextIndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+    VectorIndexName extends VectorIndexNames<
+      NamedTableInfo<DataModel, TableName>
+    >,
+  >(
+    tableName: TableName,
+    query: Expand<
+      HybridSearchQuery<
+        NamedTableInfo<DataModel, TableName>,
+        TextIndexName,
+        VectorIndexName
+      >
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a text search on the given table, returning scored IDs rather than
+   * documents and optionally where each document matched.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the search index on the table to query.
+   * @param searchFilter - A search filter expression constructed with the
+   * supplied {@link SearchFilterBuilder}, like `withSearchIndex`.
+   * @param options - A {@link TextSearchOptions} with the number of results
+   * to return and whether to highlight them.
+   * @returns A promise of IDs and scores for the best matching documents,
+   * best first, with highlights if requested.
+   */
+  textSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    searchFilter: (
+      q: SearchFilterBuilder<
+        DocumentByInfo<NamedTableInfo<DataModel, TableName>>,
+        NamedSearchIndex<NamedTableInfo<DataModel, TableName>, IndexName>
+      >,
+    ) => SearchFilter,
+    options?: TextSearchOptions,
+  ): Promise<Array<TextSearchResult<TableName>>>;
 }
 
 /**
@@ -82,6 +82,7 @@ type ExtractDocument<T extends Validator<any, any, any>> =
 export interface SearchIndexConfig<
   SearchField extends string,
   FilterFields extends string,
+  ExtraSearchFields extends string = never,
 > {
   /**
    * The field to index for full text search.
@@ -90,12 +91,56 @@ export interface SearchIndexConfig<
    */
   searchField: SearchField;
 
+  /**
+   * More `string` fields to search alongside `searchField`, mapped to their
+   * boosts. A match in one of these fields scores its boost times as much as a
+   * match in `searchField`, so `{ title: 4 }` ranks title matches higher.
+   * Boosts must be greater than 0 and at most 100.
+   *
+   * Search queries still filter on `searchField` with `q.search`, and phrase
+   * and `near` queries only match `searchField`.
+   */
+  extraSearchFields?: { [Field in ExtraSearchFields]?: number };
+
   /**
    * Additional fields to index for fast filtering when running search queries.
    */
   filterFields?: FilterFields[];
+
+  /**
+   * How to split the search field and search queries into terms. Changing the
+   * analyzer rebuilds the index.
+   *
+   * - `"standard"`: split on non-alphanumeric characters and lowercase.
+   * - `"english"`, `"german"`, `"french"`, `"spanish"`: also remove common
+   *   words and stem words, so "running" matches "runs". The non-English
+   *   analyzers also fold accents.
+   * - `"asciiFolding"`: also fold accents, so "café" matches "cafe".
+   * - `"ngram"`: index the three-character substrings of each word, to match
+   *   within words.
+   * - `"cjkBigram"`: split Chinese, Japanese, and Korean text into pairs of
+   *   characters since it isn't separated by spaces.
+   *
+   * @default "standard"
+   */
+  analyzer?: TextIndexAnalyzer;
 }
 
+/**
+ * How a search index splits text into terms. See {@link SearchIndexConfig}.
+ *
+ * @public
+ */
+export type TextIndexAnalyzer =
+  | "standard"
+  | "english"
+  | "german"
+  | "french"
+  | "spanish"
+  | "asciiFolding"
+  | "ngram"
+  | "cjkBigram";
+
 /**
  * The configuration for a vector index.
  *
@@ -119,6 +164,72 @@ export interface VectorIndexConfig<
    * Additional fields to index for fast filtering when running vector searches.
    */
   filterFields?: FilterFields[];
+  /**
+   * How to compare vectors. Changing the metric rebuilds the index.
+   *
+   * - `"cosine"`: cosine similarity, between -1 and 1.
+   * - `"dot"`: dot product. Use this for embeddings trained for it.
+   * - `"euclidean"`: the negated Euclidean distance, so closer vectors still
+   *   score higher.
+   *
+   * @default "cosine"
+   */
+  metric?: VectorDistanceMetric;
+  /**
+   * Store compressed copies of the vectors to search large indexes with less
+   * memory. Results are rescored against the original vectors.
+   *
+   * - `"scalar"`: one byte per dimension. Close to unquantized accuracy.
+   * - `"binary"`: one bit per dimension. Smallest, but works best for
+   *   high-dimensional embeddings trained for it.
+   *
+   * By default vectors aren't quantized.
+   */
+  quantization?: VectorQuantization;
+  /**
+   * Tune the HNSW graphs built for large indexes. Larger values are more
+   * accurate but use more memory and are slower.
+   */
+  hnsw?: VectorHnswConfig;
+}
+
+/**
+ * How a vector index compares vectors. See {@link VectorIndexConfig}.
+ *
+ * @public
+ */
+export type VectorDistanceMetric = "cosine" | "dot" | "euclidean";
+
+/**
+ * How a vector index compresses vectors. See {@link VectorIndexConfig}.
+ *
+ * @public
+ */
+export type VectorQuantization = "scalar" | "binary";
+
+/**
+ * HNSW parameters for a vector index. See {@link VectorIndexConfig}.
+ *
+ * @public
+ */
+export interface VectorHnswConfig {
+  /**
+   * Edges per node in the graph, between 4 and 64.
+   *
+   * @default 16
+   */
+  m?: number;
+  /**
+   * Neighbors considered while building the graph, between 4 and 1024.
+   *
+   * @default 100
+   */
+  efConstruct?: number;
+  /**
+   * Neighbors considered while searching the graph, between 4 and 1024.
+   * Defaults to `efConstruct`.
+   */
+  ef?: number;
 }
 
 /**
@@ -129,6 +240,9 @@ export type VectorIndex = {
   vectorField: string;
   dimensions: number;
   filterFields: string[];
+  metric?: VectorDistanceMetric;
+  quantization?: VectorQuantization;
+  hnsw?: VectorHnswConfig;
 };
 
 /**
@@ -137,6 +251,26 @@ export type VectorIndex = {
 export type Index = {
   indexDescriptor: string;
   fields: string[];
+  unique?: boolean;
+};
+
+/**
+ * What happens to documents referencing a deleted document.
+ *
+ * - `"restrict"`: the delete fails while referencing documents exist.
+ * - `"cascade"`: referencing documents are deleted too.
+ * - `"setNull"`: the referencing field is set to `null`.
+ *
+ * @public
+ */
+export type OnDeleteAction = "restrict" | "cascade" | "setNull";
+
+/**
+ * @internal
+ */
+export type Reference = {
+  field: string;
+  onDelete: OnDeleteAction;
 };
 
 /**
@@ -145,7 +279,9 @@ export type Index = {
 export type SearchIndex = {
   indexDescriptor: string;
   searchField: string;
+  extraSearchFields?: Record<string, number>;
   filterFields: string[];
+  analyzer?: TextIndexAnalyzer;
 };
 /**
  * The definition of a table within a schema.
@@ -162,6 +298,7 @@ export class TableDefinition<
   private indexes: Index[];
   private searchIndexes: SearchIndex[];
   private vectorIndexes: VectorIndex[];
+  private references: Reference[];
   // The type of documents stored in this table.
   validator: DocumentType;
 
@@ -172,6 +309,7 @@ export class TableDefinition<
     this.indexes = [];
     this.searchIndexes = [];
     this.vectorIndexes = [];
+    this.references = [];
     this.validator = documentType;
   }
 
@@ -183,6 +321,9 @@ export class TableDefinition<
    * @param name - The name of the index.
    * @param fields - The fields to index, in order. Must specify at least one
    * field.
+   * @param options - Pass `{ unique: true }` to reject writes that would
+   * create two documents with the same values for all of `fields`. Documents
+   * missing any of the fields are exempt.
    * @returns A {@link TableDefinition} with this index included.
    */
   index<
@@ -192,6 +333,7 @@ export class TableDefinition<
   >(
     name: IndexName,
     fields: [FirstFieldPath, ...RestFieldPaths],
+    options?: { unique?: boolean },
   ): TableDefinition<
     DocumentType,
     // Update `Indexes` to include the new index and use `Expand` to make the
@@ -206,7 +348,11 @@ export class TableDefinition<
     SearchIndexes,
     VectorIndexes
   > {
-    this.indexes.push({ indexDescriptor: name, fields });
+    this.indexes.push({
+      indexDescriptor: name,
+      fields,
+      ...(options?.unique ? { unique: true } : {}),
+    });
     return this;
   }
 
@@ -223,9 +369,12 @@ export class TableDefinition<
     IndexName extends string,
     SearchField extends ExtractFieldPaths<DocumentType>,
     FilterFields extends ExtractFieldPaths<DocumentType> = never,
+    ExtraSearchFields extends ExtractFieldPaths<DocumentType> = never,
   >(
     name: IndexName,
-    indexConfig: Expand<SearchIndexConfig<SearchField, FilterFields>>,
+    indexConfig: Expand<
+      SearchIndexConfig<SearchField, FilterFields, ExtraSearchFields>
+    >,
   ): TableDefinition<
     DocumentType,
     Indexes,
@@ -246,7 +395,18 @@ export class TableDefinition<
     this.searchIndexes.push({
       indexDescriptor: name,
       searchField: indexConfig.searchField,
+      ...(indexConfig.extraSearchFields !== undefined
+        ? {
+            extraSearchFields: indexConfig.extraSearchFields as Record<
+              string,
+              number
+            >,
+          }
+        : {}),
       filterFields: indexConfig.filterFields || [],
+      ...(indexConfig.analyzer !== undefined
+        ? { analyzer: indexConfig.analyzer }
+        : {}),
     });
     return this;
   }
@@ -288,10 +448,41 @@ export class TableDefinition<
       vectorField: indexConfig.vectorField,
       dimensions: indexConfig.dimensions,
       filterFields: indexConfig.filterFields || [],
+      ...(indexConfig.metric !== undefined
+        ? { metric: indexConfig.metric }
+        : {}),
+      ...(indexConfig.quantization !== undefined
+        ? { quantization: indexConfig.quantization }
+        : {}),
+      ...(indexConfig.hnsw !== undefined ? { hnsw: indexConfig.hnsw } : {}),
     });
     return this;
   }
 
+  /**
+   * Declare what happens to documents in this table when the document that
+   * `field` references is deleted.
+   *
+   * `field` must be a top-level `v.id()` field. To use `"setNull"`, it must
+   * also accept `v.null()`.
+   *
+   * A single delete can cascade to or update at most 4096 referencing
+   * documents. Deleting a document with more fails, so delete or update the
+   * referencing documents in batches first.
+   *
+   * @param field - The name of the `v.id()` field.
+   * @param onDelete - The action to take when the referenced document is
+   * deleted.
+   * @returns This {@link TableDefinition}.
+   */
+  onDelete(
+    field: ExtractFieldPaths<DocumentType>,
+    onDelete: OnDeleteAction,
+  ): this {
+    this.references.push({ field, onDelete });
+    return this;
+  }
+
   /**
    * Work around for https://github.com/microsoft/TypeScript/issues/57035
    */
@@ -321,6 +512,7 @@ export class TableDefinition<
       indexes: this.indexes,
       searchIndexes: this.searchIndexes,
       vectorIndexes: this.vectorIndexes,
+      references: this.references,
       documentType,
     };
   }
@@ -438,13 +630,19 @@ export class SchemaDefinition<
   export(): string {
     return JSON.stringify({
       tables: Object.entries(this.tables).map(([tableName, definition]) => {
-        const { indexes, searchIndexes, vectorIndexes, documentType } =
-          definition.export();
+        const {
+          indexes,
+          searchIndexes,
+          vectorIndexes,
+          references,
+          documentType,
+        } = definition.export();
         return {
           tableName,
           indexes,
           searchIndexes,
           vectorIndexes,
+          ...(references.length > 0 ? { references } : {}),
           documentType,
         };
       }),
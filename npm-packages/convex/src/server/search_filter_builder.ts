@@ -9,7 +9,9 @@ import {
  *
  * A search filter is a chained list of:
  * 1. One search expression constructed with `.search`.
- * 2. Zero or more equality expressions constructed with `.eq`.
+ * 2. Zero or more equality and range expressions constructed with `.eq`,
+ *    `.gt`, `.gte`, `.lt` and `.lte`.
+ * 3. Optionally, an ordering constructed with `.orderBy`.
  *
  * The search expression must search for text in the index's `searchField`. The
  * filter expressions can use any of the `filterFields` defined in the index.
@@ -35,6 +37,11 @@ export interface SearchFilterBuilder<
    * - How many times do they appear?
    * - How long is the text field?
    *
+   * Words in double quotes are matched as a phrase: `"new york"` only matches
+   * documents where "new" is directly followed by "york". `near(n)` between two
+   * words or phrases matches documents where they're at most `n` words apart,
+   * in either order, e.g. `pizza near(3) oven`.
+   *
    * @param fieldName - The name of the field to search in. This must be listed
    * as the index's `searchField`.
    * @param query - The query text to search for.
@@ -46,7 +53,7 @@ export interface SearchFilterBuilder<
 }
 
 /**
- * Builder to define equality expressions as part of a search filter.
+ * Builder to define equality and range expressions as part of a search filter.
  *
  * See {@link SearchFilterBuilder}.
  *
@@ -67,6 +74,69 @@ export interface SearchFilterFinalizer<
     fieldName: FieldName,
     value: FieldTypeFromFieldPath<Document, FieldName>,
   ): SearchFilterFinalizer<Document, SearchIndexConfig>;
+
+  /**
+   * Restrict this query to documents where `doc[fieldName] > value`.
+   *
+   * Values are compared with Convex's ordering of values, like index ranges.
+   *
+   * @param fieldName - The name of the field to compare. This must be listed in
+   * the search index's `filterFields`.
+   * @param value - The value to compare against.
+   */
+  gt<FieldName extends SearchIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): SearchFilterFinalizer<Document, SearchIndexConfig>;
+
+  /**
+   * Restrict this query to documents where `doc[fieldName] >= value`.
+   *
+   * @param fieldName - The name of the field to compare. This must be listed in
+   * the search index's `filterFields`.
+   * @param value - The value to compare against.
+   */
+  gte<FieldName extends SearchIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): SearchFilterFinalizer<Document, SearchIndexConfig>;
+
+  /**
+   * Restrict this query to documents where `doc[fieldName] < value`.
+   *
+   * @param fieldName - The name of the field to compare. This must be listed in
+   * the search index's `filterFields`.
+   * @param value - The value to compare against.
+   */
+  lt<FieldName extends SearchIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): SearchFilterFinalizer<Document, SearchIndexConfig>;
+
+  /**
+   * Restrict this query to documents where `doc[fieldName] <= value`.
+   *
+   * @param fieldName - The name of the field to compare. This must be listed in
+   * the search index's `filterFields`.
+   * @param value - The value to compare against.
+   */
+  lte<FieldName extends SearchIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): SearchFilterFinalizer<Document, SearchIndexConfig>;
+
+  /**
+   * Return the matching documents ordered by `doc[fieldName]` instead of by
+   * relevance. Documents with equal values are returned in relevance order.
+   *
+   * @param fieldName - The name of the field to order by. This must be
+   * `_creationTime` or listed in the search index's `filterFields`.
+   * @param order - The order to return documents in. Defaults to `"asc"`.
+   */
+  orderBy(
+    fieldName: SearchIndexConfig["filterFields"] | "_creationTime",
+    order?: "asc" | "desc",
+  ): SearchFilter;
 }
 
 /**
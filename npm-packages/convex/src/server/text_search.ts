@@ -0,0 +1,80 @@
+import { Id } from "../values/value.js";
+import {
+  DocumentByInfo,
+  GenericDataModel,
+  NamedSearchIndex,
+  NamedTableInfo,
+  SearchIndexNames,
+  TableNamesInDataModel,
+} from "./data_model.js";
+import { SearchFilter, SearchFilterBuilder } from "./search_filter_builder.js";
+
+/**
+ * Options for a text search run with `ctx.textSearch`.
+ * @public
+ */
+export interface TextSearchOptions {
+  /**
+   * The number of results to return. If specified, must be at most 1024.
+   *
+   * @default 10
+   */
+  limit?: number;
+  /**
+   * Whether to return where each result matched the search text. Pass an
+   * object to change how many words the snippet spans.
+   *
+   * @default false
+   */
+  highlight?: boolean | { snippetTokens?: number };
+}
+
+/**
+ * Where a text search matched a document's search field.
+ *
+ * Offsets are indexes into the search field's string, so
+ * `field.slice(start, end)` is the matched word.
+ * @public
+ */
+export interface TextSearchHighlight {
+  /**
+   * The `[start, end)` offsets of every word that matched the search text.
+   */
+  offsets: Array<[number, number]>;
+  /**
+   * The part of the search field around the best match. Spans 20 words
+   * unless `snippetTokens` is specified.
+   */
+  snippet: string;
+  /**
+   * Where `snippet` starts in the search field.
+   */
+  snippetOffset: number;
+}
+
+/**
+ * A text search result. `_highlight` is only set if the search asked for
+ * highlights.
+ * @public
+ */
+export type TextSearchResult<TableName extends string> = {
+  _id: Id<TableName>;
+  _score: number;
+  _highlight?: TextSearchHighlight;
+};
+
+export type TextSearch<
+  DataModel extends GenericDataModel,
+  TableName extends TableNamesInDataModel<DataModel>,
+  IndexName extends SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+> = (
+  tableName: TableName,
+  indexName: IndexName,
+  searchFilter: (
+    q: SearchFilterBuilder<
+      DocumentByInfo<NamedTableInfo<DataModel, TableName>>,
+      NamedSearchIndex<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ) => SearchFilter,
+  options?: TextSearchOptions,
+) => Promise<Array<TextSearchResult<TableName>>>;
@@ -41,6 +41,12 @@ export interface VectorSearchQuery<
    *
    * e.g. `filter: q => q.or(q.eq("genre", "comedy"), q.eq("genre", "drama"))`
    *
+   * These can be narrowed down further with `q.neq`, `q.gt`, `q.gte`, `q.lt`
+   * and `q.lte` combined with `q.and`. Each field can only have one kind of
+   * condition.
+   *
+   * e.g. `filter: q => q.and(q.eq("genre", "comedy"), q.gte("year", 2000), q.neq("director", "Tati"))`
+   *
    * @param q
    * @returns
    */
@@ -112,6 +118,56 @@ export interface VectorFilterBuilder<
     value: FieldTypeFromFieldPath<Document, FieldName>,
   ): FilterExpression<boolean>;
 
+  /**
+   * Is the field at `fieldName` not equal to `value`
+   *
+   * @public
+   * */
+  neq<FieldName extends VectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: FieldTypeFromFieldPath<Document, FieldName>,
+  ): FilterExpression<boolean>;
+
+  /**
+   * Is the field at `fieldName` a number greater than `value`
+   *
+   * @public
+   * */
+  gt<FieldName extends VectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean>;
+
+  /**
+   * Is the field at `fieldName` a number greater than or equal to `value`
+   *
+   * @public
+   * */
+  gte<FieldName extends VectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean>;
+
+  /**
+   * Is the field at `fieldName` a number less than `value`
+   *
+   * @public
+   * */
+  lt<FieldName extends VectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean>;
+
+  /**
+   * Is the field at `fieldName` a number less than or equal to `value`
+   *
+   * @public
+   * */
+  lte<FieldName extends VectorIndexConfig["filterFields"]>(
+    fieldName: FieldName,
+    value: number | bigint,
+  ): FilterExpression<boolean>;
+
   //  Logic  ///////////////////////////////////////////////////////////////////
 
   /**
@@ -120,4 +176,13 @@ export interface VectorFilterBuilder<
    * @public
    */
   or(...exprs: Array<FilterExpression<boolean>>): FilterExpression<boolean>;
+
+  /**
+   * `exprs[0] && exprs[1] && ... && exprs[n]`
+   *
+   * Only allowed at the top level of a filter.
+   *
+   * @public
+   */
+  and(...exprs: Array<FilterExpression<boolean>>): FilterExpression<boolean>;
 }
@@ -41,6 +41,9 @@ export type {
   VRecord,
   VUnion,
   VOptional,
+  NumberConstraints,
+  LengthConstraints,
+  StringConstraints,
 } from "./validators.js";
 import * as Base64 from "./base64.js";
 export { Base64 };
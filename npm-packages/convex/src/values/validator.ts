@@ -1,7 +1,10 @@
 import { Expand } from "../type_utils.js";
 import { GenericId } from "./index.js";
 import {
+  LengthConstraints,
+  NumberConstraints,
   OptionalProperty,
+  StringConstraints,
   VAny,
   VArray,
   VBoolean,
@@ -101,15 +104,16 @@ export const v = {
    *
    * Alias for `v.float64()`
    */
-  number: () => {
-    return new VFloat64({ isOptional: "required" });
+  number: (constraints?: NumberConstraints<number>) => {
+    return new VFloat64({ isOptional: "required", constraints });
   },
 
   /**
    * Validates that the value is of Convex type Float64 (Number in JS).
+   * @param constraints Optional inclusive `min` and `max` bounds.
    */
-  float64: () => {
-    return new VFloat64({ isOptional: "required" });
+  float64: (constraints?: NumberConstraints<number>) => {
+    return new VFloat64({ isOptional: "required", constraints });
   },
 
   /**
@@ -121,9 +125,10 @@ export const v = {
 
   /**
    * Validates that the value is of Convex type Int64 (BigInt in JS).
+   * @param constraints Optional inclusive `min` and `max` bounds.
    */
-  int64: () => {
-    return new VInt64({ isOptional: "required" });
+  int64: (constraints?: NumberConstraints<bigint>) => {
+    return new VInt64({ isOptional: "required", constraints });
   },
 
   /**
@@ -135,16 +140,19 @@ export const v = {
 
   /**
    * Validates that the value is of type String.
+   * @param constraints Optional length bounds, a `pattern` the string must
+   * contain a match for, or a well-known `format` such as `"email"`.
    */
-  string: () => {
-    return new VString({ isOptional: "required" });
+  string: (constraints?: StringConstraints) => {
+    return new VString({ isOptional: "required", constraints });
   },
 
   /**
    * Validates that the value is of Convex type Bytes (constructed in JS via `ArrayBuffer`).
+   * @param constraints Optional bounds on the number of bytes.
    */
-  bytes: () => {
-    return new VBytes({ isOptional: "required" });
+  bytes: (constraints?: LengthConstraints) => {
+    return new VBytes({ isOptional: "required", constraints });
   },
 
   /**
@@ -158,9 +166,17 @@ export const v = {
   /**
    * Validates that the value is an Array of the given element type.
    * @param element The validator for the elements of the array.
+   * @param constraints Optional bounds on the number of elements.
    */
-  array: <T extends Validator<any, "required", any>>(element: T) => {
-    return new VArray<T["type"][], T>({ isOptional: "required", element });
+  array: <T extends Validator<any, "required", any>>(
+    element: T,
+    constraints?: LengthConstraints,
+  ) => {
+    return new VArray<T["type"][], T>({
+      isOptional: "required",
+      element,
+      constraints,
+    });
   },
 
   /**
@@ -1,6 +1,6 @@
 import { GenericId } from "./index.js";
 import { GenericValidator } from "./validator.js";
-import { JSONValue, convexToJson } from "./value.js";
+import { JSONValue, Value, convexToJson } from "./value.js";
 
 type TableNameFromType<T> =
   T extends GenericId<infer TableName> ? TableName : string;
@@ -92,6 +92,51 @@ export class VId<
   }
 }
 
+/**
+ * Inclusive bounds accepted by `v.float64()` and `v.int64()`.
+ */
+export type NumberConstraints<T extends number | bigint> = {
+  min?: T;
+  max?: T;
+};
+
+/**
+ * Inclusive bounds on the length of strings (in UTF-16 code units, like
+ * `String.prototype.length`), bytes, and arrays.
+ */
+export type LengthConstraints = {
+  minLength?: number;
+  maxLength?: number;
+};
+
+/**
+ * Constraints accepted by `v.string()`.
+ *
+ * `pattern` is matched anywhere in the string, so anchor it with `^` and `$`
+ * to match the whole string. Lookaround and backreferences aren't supported.
+ */
+export type StringConstraints = LengthConstraints & {
+  pattern?: RegExp | string;
+  format?: "email" | "url";
+};
+
+function constraintsJson(
+  constraints: Record<string, unknown> | undefined,
+): Record<string, JSONValue> {
+  const json: Record<string, JSONValue> = {};
+  for (const [key, value] of Object.entries(constraints ?? {})) {
+    if (value === undefined) {
+      continue;
+    }
+    if (value instanceof RegExp) {
+      json[key] = value.source;
+    } else {
+      json[key] = convexToJson(value as Value);
+    }
+  }
+  return json;
+}
+
 /**
  * The type of the `v.float64()` validator.
  */
@@ -104,15 +149,34 @@ export class VFloat64<
    */
   readonly kind = "float64" as const;
 
+  /**
+   * Bounds on the validated numbers, if any.
+   */
+  readonly constraints: NumberConstraints<number> | undefined;
+
+  /**
+   * Usually you'd use `v.float64()` instead.
+   */
+  constructor({
+    isOptional,
+    constraints,
+  }: {
+    isOptional: IsOptional;
+    constraints?: NumberConstraints<number>;
+  }) {
+    super({ isOptional });
+    this.constraints = constraints;
+  }
   /** @internal */
   get json(): ValidatorJSON {
     // Server expects the old name `number` string instead of `float64`.
-    return { type: "number" };
+    return { type: "number", ...constraintsJson(this.constraints) };
   }
   /** @internal */
   asOptional() {
     return new VFloat64<Type | undefined, "optional">({
       isOptional: "optional",
+      constraints: this.constraints,
     });
   }
 }
@@ -129,14 +193,35 @@ export class VInt64<
    */
   readonly kind = "int64" as const;
 
+  /**
+   * Bounds on the validated integers, if any.
+   */
+  readonly constraints: NumberConstraints<bigint> | undefined;
+
+  /**
+   * Usually you'd use `v.int64()` instead.
+   */
+  constructor({
+    isOptional,
+    constraints,
+  }: {
+    isOptional: IsOptional;
+    constraints?: NumberConstraints<bigint>;
+  }) {
+    super({ isOptional });
+    this.constraints = constraints;
+  }
   /** @internal */
   get json(): ValidatorJSON {
     // Server expects the old name `bigint`.
-    return { type: "bigint" };
+    return { type: "bigint", ...constraintsJson(this.constraints) };
   }
   /** @internal */
   asOptional() {
-    return new VInt64<Type | undefined, "optional">({ isOptional: "optional" });
+    return new VInt64<Type | undefined, "optional">({
+      isOptional: "optional",
+      constraints: this.constraints,
+    });
   }
 }
 
@@ -176,13 +261,34 @@ export class VBytes<
    */
   readonly kind = "bytes" as const;
 
+  /**
+   * Bounds on the length of the validated bytes, if any.
+   */
+  readonly constraints: LengthConstraints | undefined;
+
+  /**
+   * Usually you'd use `v.bytes()` instead.
+   */
+  constructor({
+    isOptional,
+    constraints,
+  }: {
+    isOptional: IsOptional;
+    constraints?: LengthConstraints;
+  }) {
+    super({ isOptional });
+    this.constraints = constraints;
+  }
   /** @internal */
   get json(): ValidatorJSON {
-    return { type: this.kind };
+    return { type: this.kind, ...constraintsJson(this.constraints) };
   }
   /** @internal */
   asOptional() {
-    return new VBytes<Type | undefined, "optional">({ isOptional: "optional" });
+    return new VBytes<Type | undefined, "optional">({
+      isOptional: "optional",
+      constraints: this.constraints,
+    });
   }
 }
 
@@ -198,14 +304,33 @@ export class VString<
    */
   readonly kind = "string" as const;
 
+  /**
+   * Length, pattern, and format constraints on the validated strings, if any.
+   */
+  readonly constraints: StringConstraints | undefined;
+
+  /**
+   * Usually you'd use `v.string()` instead.
+   */
+  constructor({
+    isOptional,
+    constraints,
+  }: {
+    isOptional: IsOptional;
+    constraints?: StringConstraints;
+  }) {
+    super({ isOptional });
+    this.constraints = constraints;
+  }
   /** @internal */
   get json(): ValidatorJSON {
-    return { type: this.kind };
+    return { type: this.kind, ...constraintsJson(this.constraints) };
   }
   /** @internal */
   asOptional() {
     return new VString<Type | undefined, "optional">({
       isOptional: "optional",
+      constraints: this.constraints,
     });
   }
 }
@@ -378,24 +503,33 @@ export class VArray<
    */
   readonly kind = "array" as const;
 
+  /**
+   * Bounds on the length of the validated arrays, if any.
+   */
+  readonly constraints: LengthConstraints | undefined;
+
   /**
    * Usually you'd use `v.array(element)` instead.
    */
   constructor({
     isOptional,
     element,
+    constraints,
   }: {
     isOptional: IsOptional;
     element: Element;
+    constraints?: LengthConstraints;
   }) {
     super({ isOptional });
     this.element = element;
+    this.constraints = constraints;
   }
   /** @internal */
   get json(): ValidatorJSON {
     return {
       type: this.kind,
       value: this.element.json,
+      ...constraintsJson(this.constraints),
     };
   }
   /** @internal */
@@ -403,6 +537,7 @@ export class VArray<
     return new VArray<Type | undefined, Element, "optional">({
       isOptional: "optional",
       element: this.element,
+      constraints: this.constraints,
     });
   }
 }
@@ -620,17 +755,26 @@ export type JoinFieldPaths<
 
 export type ObjectFieldType = { fieldType: ValidatorJSON; optional: boolean };
 
+type ConstraintsJSON = {
+  min?: JSONValue;
+  max?: JSONValue;
+  minLength?: JSONValue;
+  maxLength?: JSONValue;
+  pattern?: JSONValue;
+  format?: JSONValue;
+};
+
 export type ValidatorJSON =
   | { type: "null" }
-  | { type: "number" }
-  | { type: "bigint" }
+  | ({ type: "number" } & ConstraintsJSON)
+  | ({ type: "bigint" } & ConstraintsJSON)
   | { type: "boolean" }
-  | { type: "string" }
-  | { type: "bytes" }
+  | ({ type: "string" } & ConstraintsJSON)
+  | ({ type: "bytes" } & ConstraintsJSON)
   | { type: "any" }
   | { type: "literal"; value: JSONValue }
   | { type: "id"; tableName: string }
-  | { type: "array"; value: ValidatorJSON }
+  | ({ type: "array"; value: ValidatorJSON } & ConstraintsJSON)
   | {
       type: "record";
       keys: RecordKeyValidatorJSON;
@@ -4,9 +4,23 @@ import { v4 as uuidv4 } from "uuid";
 import { log, setDebugLogging } from "./log";
 import os from "node:os";
 import express, { Request, Response } from "express";
+import { timingSafeEqual } from "node:crypto";
 
 const DEFAULT_PORT = 3002;
 
+// When set, `/invoke` requests must carry this secret as a bearer token. Set it
+// when the executor runs in its own container, reachable by more than the
+// backend.
+const SECRET_ENV_VAR = "NODE_EXECUTOR_SECRET";
+
+function hasValidSecret(req: Request, secret: string): boolean {
+  const expected = Buffer.from(`Bearer ${secret}`);
+  const actual = Buffer.from(req.get("authorization") ?? "");
+  return (
+    actual.length === expected.length && timingSafeEqual(actual, expected)
+  );
+}
+
 async function startServer(port: number, debug: boolean, tempdir: string) {
   setDebugLogging(debug);
   const app = express();
@@ -21,7 +35,15 @@ async function startServer(port: number, debug: boolean, tempdir: string) {
     res.json({ status: "ok" });
   });
 
+  const secret = process.env[SECRET_ENV_VAR] || undefined;
   app.post("/invoke", async (req: Request, res: Response) => {
+    if (secret !== undefined && !hasValidSecret(req, secret)) {
+      res.status(401).json({
+        type: "error",
+        message: "Invalid node executor secret",
+      });
+      return;
+    }
     try {
       const request = req.body;
       request.requestId = uuidv4();
@@ -1,16 +1,18 @@
 import { v } from "convex/values";
 import { queryPrivateSystem } from "../secretSystemTables";
+import { redactEnvironmentVariable } from "../frontend/common";
 
 // This query returns a new result every time
 // the given table's document change in any way.
 export default queryPrivateSystem({
   args: {},
   handler: async ({ db }) => {
-    return await db
+    const docs = await db
       .query("_environment_variables")
       .withIndex("by_name")
       .order("asc")
       .collect();
+    return docs.map(redactEnvironmentVariable);
   },
 });
 
@@ -18,7 +20,14 @@ export const get = queryPrivateSystem({
   args: {
     name: v.string(),
   },
-  handler: async (_, { name }) => {
+  handler: async ({ db }, { name }) => {
+    const doc = await db
+      .query("_environment_variables")
+      .withIndex("by_name", (q) => q.eq("name", name))
+      .unique();
+    if (doc?.secret) {
+      return { name, value: null };
+    }
     const value = process.env[name];
     if (value !== undefined) {
       return { name, value };
@@ -107,7 +107,22 @@ export type Export = Doc<"_exports">;
 
 export type CompletedExport = Export & { state: "completed" };
 
-export type EnvironmentVariable = Doc<"_environment_variables">;
+// Values are stored encrypted, so they're read from the function's
+// environment instead. Secret variables never have their value returned.
+export type EnvironmentVariable = Omit<
+  Doc<"_environment_variables">,
+  "value" | "encryptedValue"
+> & { value: string | null };
+
+export function redactEnvironmentVariable(
+  doc: Doc<"_environment_variables">,
+): EnvironmentVariable {
+  const { encryptedValue: _encryptedValue, value: _value, ...rest } = doc;
+  return {
+    ...rest,
+    value: doc.secret ? null : (process.env[doc.name] ?? null),
+  };
+}
 
 export type ScheduledJob = Doc<"_scheduled_jobs">;
 
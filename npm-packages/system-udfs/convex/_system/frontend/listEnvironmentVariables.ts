@@ -1,12 +1,13 @@
-import { Doc } from "../../_generated/dataModel";
 import { queryPrivateSystem } from "../secretSystemTables";
+import { EnvironmentVariable, redactEnvironmentVariable } from "./common";
 export default queryPrivateSystem({
   args: {},
-  handler: async ({ db }): Promise<Doc<"_environment_variables">[]> => {
-    return await db
+  handler: async ({ db }): Promise<EnvironmentVariable[]> => {
+    const docs = await db
       .query("_environment_variables")
       .withIndex("by_name")
       .order("asc")
       .collect();
+    return docs.map(redactEnvironmentVariable);
   },
 });
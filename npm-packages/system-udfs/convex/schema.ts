@@ -282,7 +282,11 @@ export default defineSchema({
   }),
   _environment_variables: defineTable({
     name: v.string(),
-    value: v.string(),
+    // Legacy plaintext value, rewritten to `encryptedValue` on startup.
+    value: v.optional(v.string()),
+    encryptedValue: v.optional(v.string()),
+    // Secret variables are write-only through admin APIs.
+    secret: v.optional(v.boolean()),
   }).index("by_name", ["name"]),
   _exports: defineTable(
     v.union(
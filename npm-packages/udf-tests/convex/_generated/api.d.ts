@@ -63,6 +63,7 @@ import type * as js_builtins_url from "../js_builtins/url.js";
 import type * as js_builtins_urlSearchParams from "../js_builtins/urlSearchParams.js";
 import type * as load_failure from "../load_failure.js";
 import type * as logging from "../logging.js";
+import type * as migrations from "../migrations.js";
 import type * as name from "../name.js";
 import type * as node_actions from "../node_actions.js";
 import type * as query from "../query.js";
@@ -139,6 +140,7 @@ declare const fullApi: ApiFromModules<{
   "js_builtins/urlSearchParams": typeof js_builtins_urlSearchParams;
   load_failure: typeof load_failure;
   logging: typeof logging;
+  migrations: typeof migrations;
   name: typeof name;
   node_actions: typeof node_actions;
   query: typeof query;
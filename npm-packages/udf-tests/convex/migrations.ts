@@ -0,0 +1,19 @@
+import { Doc } from "./_generated/dataModel";
+import { internalMutation } from "./_generated/server";
+
+export const addCounter = internalMutation(
+  async ({ db }, doc: Doc<"objects">) => {
+    if (doc.counter === undefined) {
+      await db.patch(doc._id, { counter: 0 });
+    }
+  },
+);
+
+export const failOnOdd = internalMutation(
+  async ({ db }, doc: Doc<"objects">) => {
+    if (doc.index % 2 === 1) {
+      throw new Error(`Can't migrate document ${doc.index}`);
+    }
+    await db.patch(doc._id, { counter: 0 });
+  },
+);